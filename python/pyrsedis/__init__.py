@@ -28,37 +28,98 @@ Quick start::
     pipe = r.pipeline()
     pipe.set("a", "1").set("b", "2")
     pipe.execute()                  # [True, True]
+
+    # asyncio
+    r = pyrsedis.AsyncRedis()
+    await r.set("key", "value")
 """
 
 # Exception hierarchy
 from pyrsedis._pyrsedis import (
+    AsyncPipeline,
+    AsyncRedis,
+    BigNumber,
     BusyError,
     ClusterDownError,
     ClusterError,
+    ConnectionSetupError,
+    CrossSlotError,
+    DedicatedConnection,
+    Encryption,
+    ExpiryWatcher,
+    FloatArray,
     GraphError,
+    NodeResults,
     NoScriptError,
     Pipeline,
+    PipelineTooLargeError,
+    PoolTimeoutError,
     ProtocolError,
+    PubSub,
     PyrsedisError,
+    QueryResult,
     ReadOnlyError,
     Redis,
     RedisConnectionError,
     RedisError,
     RedisTimeoutError,
     ResponseError,
+    Row,
+    ScanIterator,
+    Script,
     SentinelError,
+    TransactionError,
+    Verbatim,
+    WatchError,
+    WouldBlockError,
     WrongTypeError,
     __version__,
+    after_fork_in_child,
+    all_clients,
+    before_fork,
+    configure_default,
+    configure_topology_cache,
+    get_default,
+    parse_graph_compact,
 )
 
 __all__ = [
     "__version__",
+    "AsyncPipeline",
+    "AsyncRedis",
+    "DedicatedConnection",
+    "Encryption",
+    "ExpiryWatcher",
+    "FloatArray",
+    "NodeResults",
     "Pipeline",
+    "PubSub",
+    "QueryResult",
     "Redis",
+    "Row",
+    "ScanIterator",
+    "Script",
+    # RESP3 reply types
+    "BigNumber",
+    "Verbatim",
+    # Default client
+    "configure_default",
+    "get_default",
+    # Process lifecycle
+    "before_fork",
+    "after_fork_in_child",
+    "all_clients",
+    # Shared topology cache
+    "configure_topology_cache",
+    # Graph replies
+    "parse_graph_compact",
     # Exceptions
     "PyrsedisError",
     "RedisConnectionError",
+    "ConnectionSetupError",
     "RedisTimeoutError",
+    "PoolTimeoutError",
+    "WouldBlockError",
     "ProtocolError",
     "RedisError",
     "ResponseError",
@@ -69,5 +130,9 @@ __all__ = [
     "ClusterDownError",
     "GraphError",
     "ClusterError",
+    "CrossSlotError",
     "SentinelError",
+    "TransactionError",
+    "WatchError",
+    "PipelineTooLargeError",
 ]
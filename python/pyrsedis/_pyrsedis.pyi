@@ -1,9 +1,24 @@
 """Type stubs for pyrsedis._pyrsedis (native Rust module)."""
 
-from typing import Any, Optional
+from typing import Any, Callable, Iterable, Iterator, Literal, Optional, Sequence, overload
 
 __version__: str
 
+# A binary-safe command argument. ``bytes``-like values are sent unchanged;
+# numbers use their shortest round-trip decimal form.
+_CommandArg = str | bytes | bytearray | memoryview | int | float
+
+# A stream entry as returned by ``XRANGE``/``XREAD``: ``(id, {field: value})``.
+# The fields are ``None`` for pending entries deleted from the stream.
+_StreamEntry = tuple[str | bytes, Optional[dict[Any, Any]]]
+
+# A ``GEOSEARCH`` match with ``withcoord``/``withdist``/``withhash``:
+# ``{"member": ..., "distance": float, "hash": int,
+# "coordinates": (longitude, latitude)}``, holding the fields asked for.
+_GeoMatch = dict[str, Any]
+
+_GeoUnit = Literal["m", "km", "mi", "ft"]
+
 # ── Exception hierarchy ─────────────────────────────────────────────
 
 class PyrsedisError(Exception):
@@ -14,10 +29,24 @@ class RedisConnectionError(PyrsedisError):
     """Cannot connect to Redis or the connection was dropped."""
     ...
 
+class ConnectionSetupError(RedisConnectionError):
+    """The server refused a step of connection setup, such as ``AUTH``
+    (a wrong password) or ``SELECT`` (a database out of range). The
+    message names the step."""
+    ...
+
 class RedisTimeoutError(PyrsedisError):
     """Connect or read timeout exceeded."""
     ...
 
+class PoolTimeoutError(RedisTimeoutError):
+    """No pool connection became free within ``pool_timeout_ms``."""
+    ...
+
+class WouldBlockError(PoolTimeoutError):
+    """No pool connection was free for :meth:`Redis.try_execute_command`."""
+    ...
+
 class ProtocolError(PyrsedisError):
     """Malformed RESP data received from the server."""
     ...
@@ -58,10 +87,136 @@ class ClusterError(PyrsedisError):
     """Cluster topology error (e.g. no node for slot)."""
     ...
 
+class CrossSlotError(ClusterError):
+    """A command's keys hash to more than one cluster slot.
+
+    Raised before the command is sent. Put the keys under one hash tag
+    (``{user:1}:a``, ``{user:1}:b``) to colocate them.
+    """
+    ...
+
 class SentinelError(PyrsedisError):
     """Sentinel topology error (e.g. master not found)."""
     ...
 
+class TransactionError(PyrsedisError):
+    """A ``MULTI``/``EXEC`` transaction was aborted: a queued command was
+    rejected (``EXECABORT``) or a watched key changed."""
+    ...
+
+class WatchError(TransactionError):
+    """A key passed to :meth:`Pipeline.watch` changed before ``EXEC``."""
+    ...
+
+class PipelineTooLargeError(PyrsedisError):
+    """Adding a command would take a pipeline past its ``max_bytes``."""
+    ...
+
+# ── Default client ──────────────────────────────────────────────────
+
+def configure_default(
+    url: Optional[str] = None,
+    pool_size: int = 8,
+    connect_timeout_ms: int = 5000,
+    read_timeout_ms: int = 30000,
+    idle_timeout_ms: int = 300000,
+    decode_responses: bool = True,
+) -> "Redis":
+    """Configure the process-wide default client.
+
+    Replaces any previously configured default. No connection is opened
+    until the first command.
+
+    Args:
+        url: Connection URL; defaults to ``127.0.0.1:6379`` when omitted.
+        pool_size: Connection pool size.
+        connect_timeout_ms: Connect timeout in milliseconds.
+        read_timeout_ms: Read/response timeout in milliseconds.
+        idle_timeout_ms: Idle connection timeout in milliseconds.
+        decode_responses: Decode bulk strings to ``str``.
+
+    Returns:
+        The new default :class:`Redis` client.
+    """
+    ...
+
+def get_default() -> "Redis":
+    """Return the process-wide default client.
+
+    Created lazily for ``127.0.0.1:6379`` if :func:`configure_default`
+    was never called. Every caller receives the same instance.
+    """
+    ...
+
+# ── Process lifecycle ───────────────────────────────────────────────
+
+def before_fork() -> None:
+    """Close idle connections of every client before ``os.fork()``.
+
+    Use from a pre-fork hook (e.g. gunicorn ``pre_fork``) so children do
+    not inherit sockets shared with the parent.
+    """
+    ...
+
+def after_fork_in_child() -> None:
+    """Reinitialize pyrsedis in a freshly forked child process.
+
+    Use from a post-fork hook (e.g. gunicorn ``post_fork``, celery
+    ``worker_process_init``). Inherited connections are abandoned without
+    being closed and a fresh I/O runtime is started on next use.
+    Client-side caches (``cache=True``) are emptied and resume once their
+    invalidation connection is re-established.
+    """
+    ...
+
+def all_clients() -> list[dict[str, Any]]:
+    """Describe every live client in the process.
+
+    Returns:
+        One dict per client: ``addr`` (as in ``repr()``), ``topology``
+        (``"standalone"``, ``"cluster"`` or ``"sentinel"``), ``db`` and
+        ``pool`` (see :meth:`Redis.pool_stats`). Clients are held weakly
+        and appear until they are garbage collected.
+    """
+    ...
+
+# ── Topology cache ──────────────────────────────────────────────────
+
+def configure_topology_cache(
+    enabled: bool = True,
+    capacity: int = 256,
+    ttl_ms: int = 30000,
+) -> None:
+    """Share resolved addresses and cluster slot maps between clients.
+
+    Opt-in and process-wide: once enabled, every client reuses cached DNS
+    results and ``CLUSTER SLOTS`` replies for the same endpoints, so
+    short-lived clients skip redundant lookups. Calling this again (or
+    with ``enabled=False``) clears the cache.
+
+    Args:
+        enabled: Turn the cache on or off.
+        capacity: Maximum entries kept per cache.
+        ttl_ms: How long an entry stays valid, in milliseconds.
+
+    Raises:
+        TypeError: If ``capacity`` is 0 while enabling.
+    """
+    ...
+
+def parse_graph_compact(raw_reply: list[Any] | bytes) -> "QueryResult":
+    """Parse a graph reply captured outside :meth:`Redis.graph_query_result`.
+
+    Takes the nested list :meth:`Redis.graph_query` and pipelines return,
+    or the raw RESP bytes of a ``GRAPH.QUERY ... --compact`` reply, such
+    as one saved from a recording.
+
+    Raises:
+        GraphError: If the reply is not a compact graph result.
+        TypeError: If ``raw_reply`` holds values no reply contains.
+    """
+    ...
+
 # ── Client ──────────────────────────────────────────────────────────
 
 class Redis:
@@ -93,6 +248,42 @@ class Redis:
         idle_timeout_ms: int = 300000,
         max_buffer_size: int = 67108864,
         decode_responses: bool = True,
+        retry_unsafe: bool = False,
+        serializer: Optional[Literal["msgpack"]] = None,
+        cluster: Optional[Sequence[tuple[str, int]]] = None,
+        resp3_types: bool = False,
+        sentinels: Optional[Sequence[tuple[str, int]]] = None,
+        master_name: Optional[str] = None,
+        sentinel_retries: int = 3,
+        sentinel_retry_backoff_ms: int = 100,
+        ssl: bool = False,
+        ssl_ca_certs: Optional[str] = None,
+        ssl_certfile: Optional[str] = None,
+        ssl_keyfile: Optional[str] = None,
+        ssl_server_hostname: Optional[str] = None,
+        ssl_insecure_skip_verify: bool = False,
+        slot_refresh_interval_ms: int = 30000,
+        unix_socket_path: Optional[str] = None,
+        protocol: Literal[2, 3] = 2,
+        protocol_fallback: bool = True,
+        cache: bool = False,
+        cache_max_entries: int = 10000,
+        cache_ttl: Optional[float] = None,
+        encryption: Optional["Encryption"] = None,
+        health_check_interval_ms: int = 0,
+        reserved_connections: int = 0,
+        min_idle: int = 0,
+        max_lifetime_ms: int = 0,
+        pool_timeout_ms: int = 0,
+        allow_cross_slot: bool = False,
+        capture_frames: int = 0,
+        address_remap: Optional[dict[str, str] | Callable[[str], Optional[str]]] = None,
+        read_preference: str = "primary",
+        keyless_routing: "str | dict[str, str]" = "first",
+        allow_destructive: bool = False,
+        sentinel_username: Optional[str] = None,
+        sentinel_password: Optional[str] = None,
+        strict_numbers: bool = False,
     ) -> None:
         """Create a new Redis client.
 
@@ -111,9 +302,159 @@ class Redis:
                 Defaults to 64 MiB.
             decode_responses: If ``False``, return bulk-string responses as
                 ``bytes`` instead of ``str``.
+            retry_unsafe: Commands that fail with a connection error are
+                replayed once on a fresh connection only if they are
+                idempotent (reads, ``SET``, ``DEL``, ``SADD``…). Set to
+                ``True`` to also replay commands such as ``INCR`` or
+                ``LPUSH``, which may then be applied twice.
+            serializer: Value codec used by :meth:`set` and :meth:`get`.
+                With ``"msgpack"``, ``None``/``bool``/``int``/``float``/
+                ``str``/``bytes``/``list``/``dict`` values are encoded and
+                decoded in Rust. ``None`` (default) stores plain strings.
+            cluster: Redis Cluster seed nodes as ``(host, port)`` pairs.
+                The slot map is fetched from the first reachable seed
+                (``CLUSTER SHARDS``, or ``CLUSTER SLOTS`` before Redis 7)
+                and every command is routed to the node owning its key;
+                ``host``, ``port`` and ``db`` are then ignored. Nodes are
+                addressed by their advertised hostname when they have one,
+                so TLS certificates issued for it validate.
+                :meth:`dedicated_connection` is unavailable in this mode.
+            resp3_types: Preserve RESP3 reply types that have no exact
+                Python equivalent: verbatim strings become :class:`Verbatim`
+                (keeping their encoding) and big numbers become
+                :class:`BigNumber` (keeping their wire text). Off by default,
+                which returns plain ``str`` and ``int``.
+            sentinels: Redis Sentinel nodes as ``(host, port)`` pairs. The
+                address of ``master_name`` is looked up from them, and the
+                client re-resolves it and retries when the master fails
+                over; ``host`` and ``port`` are then ignored.
+            master_name: Name of the monitored master. Required with
+                ``sentinels``.
+            sentinel_retries: How many times a command is retried after
+                re-resolving the master on a connection error or
+                ``READONLY`` reply.
+            sentinel_retry_backoff_ms: Pause before each such retry, in
+                milliseconds.
+            ssl: Connect over TLS. Requires pyrsedis built with the ``tls``
+                feature; otherwise connecting raises :class:`ProtocolError`.
+            ssl_ca_certs: PEM file of CA certificates to trust. Defaults to
+                the bundled Mozilla root store.
+            ssl_certfile: PEM client certificate chain for mutual TLS.
+            ssl_keyfile: PEM private key for ``ssl_certfile``; both must be
+                given together.
+            ssl_server_hostname: Name used for SNI and certificate
+                verification instead of ``host``.
+            ssl_insecure_skip_verify: Accept any server certificate. Only
+                for testing: it allows man-in-the-middle attacks.
+            slot_refresh_interval_ms: Cluster only. How often the slot map is
+                re-fetched in the background, in milliseconds; ``0``
+                disables the refresh. Each wait is randomized by ±10% so
+                many clients do not refresh in lockstep, and the refresh is
+                skipped while the client has issued no commands. Repeated
+                ``MOVED`` redirects (e.g. during resharding) also trigger a
+                full refresh, at most once a second.
+            unix_socket_path: Connect over this Unix domain socket instead
+                of TCP; ``host`` and ``port`` are then ignored. Cannot be
+                combined with ``cluster``, ``sentinels`` or ``ssl``.
+            protocol: RESP version to speak, ``2`` or ``3``. With ``3`` each
+                connection sends ``HELLO 3``, so replies keep their native
+                types (maps as ``dict``, doubles as ``float``, sets as
+                ``set``) and Pub/Sub messages arrive as push frames.
+            protocol_fallback: With ``protocol=3``, continue over RESP2 when
+                the server does not support RESP3 (Redis before 6.0)
+                instead of raising :class:`ProtocolError`. See
+                :attr:`protocol_version`.
+            cache: Serve :meth:`get` from an in-process cache. The server
+                tracks the keys read (``CLIENT TRACKING``, Redis 6.0+) and
+                sends invalidations to one pooled connection reserved for
+                them, so entries are dropped as soon as the keys change;
+                writes through this client evict their keys right away. Not
+                available with ``cluster``; needs ``pool_size`` of at least
+                2. See :meth:`cache_info`.
+            cache_max_entries: Keys kept before the least recently used one
+                is evicted.
+            cache_ttl: Also expire entries after this many seconds; by
+                default only invalidations remove them.
+            encryption: An :class:`Encryption` whose callbacks encrypt values
+                written by :meth:`set` (after the ``serializer``) and decrypt
+                values read by :meth:`get`. Values stored without encryption
+                are returned unchanged.
+            health_check_interval_ms: Ping a pooled connection idle for
+                longer than this before reusing it, and reconnect if it
+                does not answer, so a connection dropped by the server or a
+                proxy never surfaces as an error. ``0`` (the default)
+                disables the check.
+            reserved_connections: Pool connections kept free for single
+                commands. Pipelines and transactions use at most
+                ``pool_size - reserved_connections`` connections at once, so
+                point reads keep low latency during bulk jobs. Must be less
+                than ``pool_size``; applies per node in a cluster.
+            min_idle: Connections to open (and initialize with AUTH/SELECT)
+                while the client is created, so the first requests skip
+                connection setup. ``0`` (the default) connects lazily. An
+                unreachable server then fails the constructor. At most
+                ``pool_size``. See :meth:`warmup`.
+            max_lifetime_ms: Close and reopen connections older than this, in
+                milliseconds. ``0`` (the default) sets no limit. A background
+                task also closes connections past ``idle_timeout_ms``
+                without waiting for the next checkout.
+            pool_timeout_ms: How long a command may wait for a free pool
+                connection, in milliseconds. ``0`` (the default) waits
+                indefinitely. Raises :class:`PoolTimeoutError`, naming the
+                pool usage and the longest-running command, when exceeded.
+            allow_cross_slot: Cluster only. Split :meth:`mget`,
+                :meth:`delete`, :meth:`unlink` and :meth:`exists` calls whose
+                keys span several hash slots into one command per slot, run
+                them concurrently and merge the replies in key order. With
+                the default ``False`` such calls fail with ``CROSSSLOT``.
+                The split commands are not atomic as a whole.
+            capture_frames: Keep the last this many raw frames sent to and
+                received from the server, for :meth:`dump_frames`. ``0``
+                (the default) turns capture off. Only the first 4 KiB of
+                each frame is kept. Captured frames include passwords sent
+                with ``AUTH``.
+            address_remap: Cluster only. Rewrites the node addresses the
+                cluster announces, in its slot map and in ``MOVED``/``ASK``
+                redirects, before connecting to them: either a dict such
+                as ``{"10.0.0.1:6379": "localhost:7001"}`` or a callable
+                taking ``"host:port"`` and returning the address to
+                connect to, or ``None`` to keep it. Use it to reach a
+                cluster running in containers or behind NAT. Nodes keep
+                their announced names in :meth:`execute_on_all_nodes`
+                results.
+            read_preference: Cluster and Sentinel only. Which node serves
+                read-only commands: ``"primary"`` (the default),
+                ``"prefer_replica"`` (the master when the slot has no
+                replica up), ``"replica_required"`` (fail instead),
+                ``"lowest_latency"`` (the node with the fastest recent pings,
+                master or replica) or ``"az_affinity:<zone>"`` (a replica in
+                that availability zone, as reported by Valkey 8+, else any
+                replica, else the master). The last two ping every node once
+                a second. With ``sentinels``, ``"prefer_replica"`` and
+                ``"replica_required"`` spread reads over the master's
+                healthy replicas.
+            keyless_routing: Cluster only. Which master serves commands
+                without keys, such as ``PING`` or ``DBSIZE``: ``"first"``
+                (the default, the master of the lowest slots),
+                ``"round_robin"``, ``"random"`` or ``"least_loaded"`` (the
+                master with the fewest connections in use). A dict sets it
+                per category — ``"connection"``, ``"server"``,
+                ``"scripting"`` — with ``"default"`` for the rest.
+            allow_destructive: Let :meth:`flushdb` and :meth:`flushall`
+                run without ``confirm=True``.
+            sentinel_username: Username for the Sentinel nodes.
+            sentinel_password: Password for the Sentinel nodes. When
+                omitted, Sentinels are sent ``username``/``password`` and a
+                rejected login is ignored.
+            strict_numbers: Raise ``TypeError`` instead of sending a
+                non-integer where Redis expects an integer (such as
+                ``execute_command("EXPIRE", key, 1.5)``) or an ``int``
+                score that a double cannot hold exactly.
 
         Raises:
             RedisConnectionError: If the initial connection cannot be established.
+            ClusterError: If no cluster seed node can be reached.
+            SentinelError: If ``master_name`` is missing or cannot be resolved.
         """
         ...
 
@@ -125,15 +466,46 @@ class Redis:
         read_timeout_ms: int = 30000,
         idle_timeout_ms: int = 300000,
         decode_responses: bool = True,
+        retry_unsafe: bool = False,
+        serializer: Optional[Literal["msgpack"]] = None,
+        resp3_types: bool = False,
+        sentinel_retries: int = 3,
+        sentinel_retry_backoff_ms: int = 100,
+        ssl_ca_certs: Optional[str] = None,
+        ssl_certfile: Optional[str] = None,
+        ssl_keyfile: Optional[str] = None,
+        ssl_server_hostname: Optional[str] = None,
+        ssl_insecure_skip_verify: bool = False,
+        slot_refresh_interval_ms: int = 30000,
+        protocol: Literal[2, 3] = 2,
+        protocol_fallback: bool = True,
+        cache: bool = False,
+        cache_max_entries: int = 10000,
+        cache_ttl: Optional[float] = None,
+        encryption: Optional["Encryption"] = None,
+        health_check_interval_ms: int = 0,
+        reserved_connections: int = 0,
+        min_idle: int = 0,
+        max_lifetime_ms: int = 0,
+        pool_timeout_ms: int = 0,
+        allow_cross_slot: bool = False,
+        capture_frames: int = 0,
+        address_remap: Optional[dict[str, str] | Callable[[str], Optional[str]]] = None,
+        read_preference: str = "primary",
+        keyless_routing: "str | dict[str, str]" = "first",
+        allow_destructive: bool = False,
+        strict_numbers: bool = False,
     ) -> "Redis":
         """Create a client from a ``redis://``, ``rediss://``, ``redis+sentinel://``,
-        or ``redis+cluster://`` URL.
-
-        .. note::
+        ``redis+cluster://`` or ``unix://`` URL.
 
-            Sentinel and cluster URLs are parsed but routing is not yet
-            implemented in v0.1.0.  The client connects to the first host
-            as a standalone server.
+        ``redis+cluster://host:port[,host:port…]`` URLs create a cluster
+        client (see the ``cluster`` argument of :class:`Redis`).
+        ``redis+sentinel://master@host:port[,host:port…]`` URLs resolve the
+        master through Sentinel (see the ``sentinels`` argument); prefix
+        ``suser:spass@user:pass@`` to give the Sentinels their own credentials.
+        ``unix://[user:password@]/path/to/redis.sock[?db=N]`` URLs connect
+        over a Unix domain socket.
 
         Args:
             url: Connection URL.  Format:
@@ -144,6 +516,41 @@ class Redis:
             idle_timeout_ms: Idle-connection eviction timeout in milliseconds.
             decode_responses: If ``False``, return bulk-string responses as
                 ``bytes``.
+            retry_unsafe: Also replay non-idempotent commands after an
+                ambiguous connection error.
+            serializer: Value codec for :meth:`set` / :meth:`get`
+                (``"msgpack"`` or ``None``).
+            resp3_types: Return :class:`Verbatim` / :class:`BigNumber` for
+                RESP3 verbatim strings and big numbers.
+            sentinel_retries: Retries after re-resolving a Sentinel master.
+            sentinel_retry_backoff_ms: Pause before each Sentinel retry, in
+                milliseconds.
+            ssl_ca_certs: TLS settings for ``rediss://`` URLs, as on
+                :class:`Redis`.
+            ssl_certfile: See :class:`Redis`.
+            ssl_keyfile: See :class:`Redis`.
+            ssl_server_hostname: See :class:`Redis`.
+            ssl_insecure_skip_verify: See :class:`Redis`.
+            slot_refresh_interval_ms: Background slot map refresh interval
+                for ``redis+cluster://`` URLs, as on :class:`Redis`.
+            protocol: RESP version, as on :class:`Redis`.
+            protocol_fallback: See :class:`Redis`.
+            cache: Client-side caching, as on :class:`Redis`.
+            cache_max_entries: See :class:`Redis`.
+            cache_ttl: See :class:`Redis`.
+            encryption: Value encryption, as on :class:`Redis`.
+            health_check_interval_ms: See :class:`Redis`.
+            reserved_connections: See :class:`Redis`.
+            min_idle: See :class:`Redis`.
+            max_lifetime_ms: See :class:`Redis`.
+            pool_timeout_ms: See :class:`Redis`.
+            allow_cross_slot: See :class:`Redis`.
+            capture_frames: See :class:`Redis`.
+            address_remap: See :class:`Redis`.
+            read_preference: See :class:`Redis`.
+            keyless_routing: See :class:`Redis`.
+            allow_destructive: See :class:`Redis`.
+            strict_numbers: See :class:`Redis`.
 
         Returns:
             A new :class:`Redis` instance.
@@ -151,32 +558,170 @@ class Redis:
         Raises:
             RedisConnectionError: If the URL is unreachable.
             ResponseError: If the URL scheme is unsupported.
+            ClusterError: If no cluster seed node can be reached.
+            SentinelError: If the master cannot be resolved.
         """
         ...
 
-    def execute_command(self, *args: str) -> Any:
+    def execute_command(
+        self,
+        *args: _CommandArg,
+        timeout_ms: int | None = None,
+        expected_response_size: int | None = None,
+        route: "str | tuple[str, str | int] | None" = None,
+        node: str | None = None,
+    ) -> Any:
         """Execute a raw Redis command.
 
         Args:
-            *args: Command name followed by its arguments, all as strings.
+            *args: Command name followed by its arguments. ``bytes``,
+                ``bytearray`` and ``memoryview`` are sent unchanged, so
+                binary keys and values round-trip exactly.
+            timeout_ms: Upper bound on the whole round trip, including
+                waiting for a pooled connection and any retries. When it
+                expires the connection is closed rather than reused.
+                Simple commands such as :meth:`ttl` and :meth:`hget`
+                take the same bound as a keyword-only ``timeout`` in
+                seconds.
+            expected_response_size: Approximate reply size in bytes. The
+                read buffer grows to fit it before the reply is read,
+                instead of doubling repeatedly while a large reply
+                arrives. Without a hint, the average reply size of the
+                command so far is used.
+            route: Cluster only. Override where the command goes:
+                ``"master"`` sends it to the master owning its keys even
+                when reads go to replicas; ``"replica"`` to a replica of
+                that slot; ``("node", "host:port")`` to that node (as in
+                the keys of :meth:`execute_on_all_nodes` results);
+                ``("slot", n)`` to the master of slot ``n``. ``MOVED`` and
+                ``ASK`` redirects are still followed.
+            node: Shorthand for ``route=("node", node)``.
 
         Returns:
             The Redis response converted to the appropriate Python type.
 
+        Raises:
+            RedisTimeoutError: If ``timeout_ms`` expired first. The command
+                may still have run on the server.
+            ClusterError: If the node is not part of the cluster, or the
+                slot has no replica up for ``route="replica"``.
+            TypeError: If ``route`` or ``node`` is given outside cluster
+                mode, both are given, or ``route`` is malformed.
+
         Example:
             >>> r.execute_command("SET", "key", "value")
             True
             >>> r.execute_command("GET", "key")
             b'value'
+            >>> r.execute_command("SET", b"\\x00key", b"\\xff", "EX", 60)
+            True
+            >>> r.execute_command("GET", "key", timeout_ms=500)
+            b'value'
+            >>> r.execute_command("DBSIZE", node="10.0.0.2:6379")
+            1042
+            >>> r.execute_command("GET", "key", route="master")
+            b'value'
+        """
+        ...
+
+    def try_execute_command(self, *args: _CommandArg, timeout_ms: int = 0) -> Any:
+        """Execute a command only if a pooled connection is free for it.
+
+        Unlike :meth:`execute_command`, the command does not queue behind
+        busy connections: unless one frees up within ``timeout_ms`` (by
+        default, none at all) :class:`WouldBlockError` is raised and
+        nothing is sent, so a latency-critical path can degrade, e.g. by
+        serving stale data. :attr:`pool_available` tells beforehand
+        whether a connection is free. Once it has a connection the
+        command runs as usual; a free slot without an idle connection
+        still opens one.
+
+        Args:
+            *args: Command name and arguments, as for :meth:`execute_command`.
+            timeout_ms: How long to wait for a free connection.
+
+        Raises:
+            WouldBlockError: If no connection was free in time.
+
+        Example:
+            >>> try:
+            ...     price = r.try_execute_command("GET", "price")
+            ... except pyrsedis.WouldBlockError:
+            ...     price = stale["price"]
+        """
+        ...
+
+    def execute_on_all_nodes(self, *args: str) -> "NodeResults":
+        """Execute a command on every node and report each node's outcome.
+
+        In cluster mode the command runs on every master concurrently;
+        otherwise on the one server. A failing node does not stop the
+        others: its error is collected instead of raised.
+
+        Args:
+            *args: Command name followed by its arguments, all as strings.
+
+        Returns:
+            A :class:`NodeResults` with the replies and errors per node.
+
+        Example:
+            >>> res = r.execute_on_all_nodes("CONFIG", "SET", "maxmemory-policy", "allkeys-lru")
+            >>> for node, err in res.failures.items():
+            ...     print(node, err)
+        """
+        ...
+
+    def execute_many(self, commands: Sequence[Sequence[str]]) -> list[Any]:
+        """Execute a batch of commands in a single round-trip.
+
+        Equivalent to building a :class:`Pipeline` and calling
+        :meth:`Pipeline.execute`, without the intermediate object.
+
+        Args:
+            commands: Commands, each a sequence of the command name
+                followed by its arguments.
+
+        Returns:
+            A list of responses, one per command.
+
+        Example:
+            >>> r.execute_many([("SET", "a", "1"), ("INCR", "a"), ("GET", "a")])
+            ['OK', 2, '2']
         """
         ...
 
-    def pipeline(self) -> "Pipeline":
+    def pipeline(
+        self,
+        immediate: bool = False,
+        transaction: bool = False,
+        max_bytes: Optional[int] = None,
+        on_overflow: Literal["raise", "flush"] = "raise",
+    ) -> "Pipeline":
         """Create a pipeline for batching multiple commands.
 
+        Args:
+            immediate: If ``True``, each command is sent as soon as it is
+                added. :meth:`Pipeline.execute` still returns the collected
+                results, so the same code works with or without batching.
+            transaction: If ``True``, :meth:`Pipeline.execute` wraps the
+                commands in ``MULTI``/``EXEC`` on a single connection so
+                they run atomically. The ``QUEUED`` replies are dropped and
+                the ``EXEC`` results returned one per command. In cluster
+                mode every key must hash to the same slot.
+            max_bytes: Limit on the encoded size of the buffered commands
+                (see :attr:`Pipeline.size_bytes`). ``None`` means no limit.
+            on_overflow: When a command would exceed ``max_bytes``:
+                ``"raise"`` raises :class:`PipelineTooLargeError`;
+                ``"flush"`` sends the buffered commands first and keeps
+                their replies for :meth:`Pipeline.execute`.
+
         Returns:
             A new :class:`Pipeline` instance bound to this client.
 
+        Raises:
+            TypeError: If both ``immediate`` and ``transaction`` are set,
+                or ``on_overflow="flush"`` is combined with ``transaction``.
+
         Example:
             >>> pipe = r.pipeline()
             >>> pipe.set("a", "1").set("b", "2")
@@ -185,6 +730,96 @@ class Redis:
         """
         ...
 
+    def transaction(
+        self,
+        func: Callable[["Pipeline"], Any],
+        *watch_keys: str,
+        retries: int = 5,
+    ) -> list[Any]:
+        """Run ``func`` as an optimistic-locking transaction.
+
+        Watches ``watch_keys`` on a pinned connection, calls ``func(pipe)``
+        with a transactional :class:`Pipeline` and executes it. ``func``
+        reads current values (through this client) and queues writes on
+        ``pipe``. If a watched key changed before ``EXEC``, the whole
+        attempt is repeated, up to ``retries`` more times.
+
+        Args:
+            func: Called with the pipeline on every attempt.
+            *watch_keys: Keys to ``WATCH``.
+            retries: Extra attempts after a watch conflict.
+
+        Returns:
+            The results of the successful attempt, one per queued command.
+
+        Raises:
+            WatchError: If every attempt hit a watch conflict.
+
+        Example:
+            >>> def incr_balance(pipe):
+            ...     balance = int(r.get("balance") or 0)
+            ...     pipe.set("balance", str(balance + 10))
+            >>> r.transaction(incr_balance, "balance")
+            [True]
+        """
+        ...
+
+    def dedicated_connection(self) -> "DedicatedConnection":
+        """Pin one pooled connection for a sequence of stateful commands.
+
+        Use as a context manager. On exit the connection is ``RESET``,
+        re-authenticated and returned to the pool.
+
+        Returns:
+            A :class:`DedicatedConnection` holding one pool slot.
+
+        Example:
+            >>> with r.dedicated_connection() as conn:
+            ...     conn.execute_command("SELECT", "3")
+            ...     conn.execute_command("SET", "k", "v")
+        """
+        ...
+
+    def pubsub(self) -> "PubSub":
+        """Create a Pub/Sub subscriber on its own pooled connection.
+
+        Returns:
+            A :class:`PubSub` holding one pool slot until closed.
+
+        Example:
+            >>> with r.pubsub() as p:
+            ...     p.subscribe("news")
+            ...     for msg in p.listen():
+            ...         print(msg["channel"], msg["data"])
+        """
+        ...
+
+    def on_expire(self, callback: Callable[[Any], Any], pattern: str = "*") -> "ExpiryWatcher":
+        """Call ``callback(key)`` whenever a key matching ``pattern`` expires.
+
+        Enables ``expired`` keyevent notifications on the server (keeping
+        any ``notify-keyspace-events`` classes already set) and subscribes
+        to them on a background connection, re-subscribing if it drops.
+        The callback runs on a background thread; exceptions it raises are
+        reported through ``sys.unraisablehook``.
+
+        Args:
+            callback: Called with each expired key (``str``, or ``bytes``
+                when ``decode_responses`` is off).
+            pattern: Glob pattern keys must match.
+
+        Returns:
+            An :class:`ExpiryWatcher` holding one pool slot until stopped.
+
+        Raises:
+            ClusterError: In cluster mode, where notifications are per node.
+
+        Example:
+            >>> watcher = r.on_expire(lambda key: cache.pop(key, None), pattern="session:*")
+            >>> watcher.stop()
+        """
+        ...
+
     # ── String commands ─────────────────────────────────────────
 
     def ping(self) -> bool:
@@ -197,8 +832,8 @@ class Redis:
 
     def set(
         self,
-        name: str,
-        value: str,
+        name: str | bytes,
+        value: Any,
         ex: Optional[int] = None,
         px: Optional[int] = None,
         nx: bool = False,
@@ -208,7 +843,9 @@ class Redis:
 
         Args:
             name: Key name.
-            value: Value to set.
+            value: Value to set: ``str``, ``bytes``, ``bytearray``,
+                ``memoryview``, ``int`` or ``float``, or anything the
+                ``serializer`` supports if one is configured.
             ex: Expire time in seconds.
             px: Expire time in milliseconds.
             nx: Only set if the key does not already exist.
@@ -220,14 +857,17 @@ class Redis:
         """
         ...
 
-    def get(self, name: str) -> Optional[str | bytes]:
+    def get(self, name: str | bytes) -> Any:
         """Get the value of a key.
 
         Args:
             name: Key name.
 
         Returns:
-            The value as ``bytes``, or ``None`` if the key does not exist.
+            The value, or ``None`` if the key does not exist. With a
+            ``serializer`` the stored bytes are decoded back to the original
+            Python value. With ``cache=True`` the reply may come from the
+            client-side cache.
         """
         ...
 
@@ -253,31 +893,40 @@ class Redis:
         """
         ...
 
-    def expire(self, name: str, seconds: int) -> int:
+    def expire(self, name: str, seconds: int, nx: bool = False, xx: bool = False, gt: bool = False, lt: bool = False) -> int:
         """Set a timeout on a key.
 
         Args:
             name: Key name.
             seconds: TTL in seconds.
+            nx: Only set it if the key has no timeout.
+            xx: Only set it if the key already has one.
+            gt: Only set it if it is later than the current one; a key
+                without a timeout counts as never expiring.
+            lt: Only set it if it is sooner than the current one, e.g.
+                to tighten a TTL without a read-then-write race.
 
         Returns:
-            ``1`` if the timeout was set, ``0`` if the key does not exist.
+            ``1`` if the timeout was set, ``0`` if the key does not exist
+            or a condition was not met.
         """
         ...
 
-    def expireat(self, name: str, when: int) -> int:
+    def expireat(self, name: str, when: int, nx: bool = False, xx: bool = False, gt: bool = False, lt: bool = False) -> int:
         """Set an absolute Unix-timestamp expiry on a key.
 
         Args:
             name: Key name.
             when: Unix timestamp (seconds since epoch).
+            nx, xx, gt, lt: Conditions, as for :meth:`expire`.
 
         Returns:
-            ``1`` if the timeout was set, ``0`` if the key does not exist.
+            ``1`` if the timeout was set, ``0`` if the key does not exist
+            or a condition was not met.
         """
         ...
 
-    def ttl(self, name: str) -> int:
+    def ttl(self, name: str, *, timeout: float | None = None) -> int:
         """Get the remaining time-to-live of a key in seconds.
 
         Args:
@@ -289,15 +938,180 @@ class Redis:
         """
         ...
 
-    def pexpire(self, name: str, millis: int) -> int:
+    def exists_many(self, keys: Sequence[str], chunk_size: int = 1000) -> dict[str, bool]:
+        """Check which of many keys exist.
+
+        Sends one ``EXISTS`` per key, pipelined in chunks grouped by hash
+        slot, instead of one round-trip per key.
+
+        Args:
+            keys: Keys to check.
+            chunk_size: Commands per pipelined round-trip.
+
+        Returns:
+            A dict mapping each key to ``True`` if it exists.
+        """
+        ...
+
+    def ttl_many(self, keys: Sequence[str], chunk_size: int = 1000) -> dict[str, int]:
+        """Get the TTL of many keys.
+
+        Sends one ``TTL`` per key, pipelined in chunks grouped by hash
+        slot, instead of one round-trip per key.
+
+        Args:
+            keys: Keys to inspect.
+            chunk_size: Commands per pipelined round-trip.
+
+        Returns:
+            A dict mapping each key to its TTL in seconds (``-1`` if no
+            expiry, ``-2`` if the key does not exist).
+        """
+        ...
+
+    def expire_many(self, mapping: dict[str, int], chunk_size: int = 1000) -> dict[str, bool]:
+        """Set the expiry of many keys.
+
+        Sends one ``EXPIRE`` per key, pipelined in chunks grouped by hash
+        slot, instead of one round-trip per key.
+
+        Args:
+            mapping: Dict of key to timeout in seconds.
+            chunk_size: Commands per pipelined round-trip.
+
+        Returns:
+            A dict mapping each key to ``True`` if the timeout was set
+            (``False`` if the key does not exist).
+        """
+        ...
+
+    def persist_many(self, keys: Sequence[str], chunk_size: int = 1000) -> dict[str, bool]:
+        """Remove the expiry of many keys.
+
+        Sends one ``PERSIST`` per key, pipelined in chunks grouped by hash
+        slot, instead of one round-trip per key.
+
+        Args:
+            keys: Keys to make persistent.
+            chunk_size: Commands per pipelined round-trip.
+
+        Returns:
+            A dict mapping each key to ``True`` if its timeout was removed
+            (``False`` if it had none or does not exist).
+        """
+        ...
+
+    def purge_expiring(self, pattern: str, older_than_ttl: int, count: int = 1000, pause_ms: int = 10) -> int:
+        """Unlink the keys matching a pattern that are about to expire.
+
+        Walks the keyspace with ``SCAN MATCH`` (every master in cluster
+        mode), checks each page's keys with pipelined ``PTTL`` and unlinks
+        those with less than ``older_than_ttl`` seconds left, or with no
+        TTL at all. The job sleeps ``pause_ms`` between pages. Keys that
+        are not valid UTF-8 are skipped.
+
+        Args:
+            pattern: Glob pattern of the keys to consider.
+            older_than_ttl: Keys with fewer seconds left than this are unlinked.
+            count: ``SCAN`` size hint, and so the batch size.
+            pause_ms: Sleep between batches in milliseconds.
+
+        Returns:
+            The number of keys unlinked.
+        """
+        ...
+
+    def refresh_ttl_if_below(self, keys: Sequence[str], min_ttl: int, new_ttl: int) -> int:
+        """Extend the TTL of the given keys that are about to expire.
+
+        Keys with less than ``min_ttl`` seconds left get a TTL of
+        ``new_ttl`` seconds; keys without a TTL, or that do not exist, are
+        left alone. The check and the ``EXPIRE`` run in one Lua script,
+        sent with ``EVALSHA`` for all keys at once (one call per hash slot
+        in cluster mode, pipelined).
+
+        Args:
+            keys: Key names.
+            min_ttl: Keys with fewer seconds left than this are refreshed.
+            new_ttl: Their new TTL in seconds.
+
+        Returns:
+            The number of keys refreshed.
+        """
+        ...
+
+    def load_hash_rows(
+        self,
+        prefix: str,
+        rows: Iterable[Any],
+        key_field: Optional[str] = None,
+        chunk_size: int = 1000,
+        progress: Optional[Callable[[int], Any]] = None,
+    ) -> int:
+        """Load rows into one hash per row.
+
+        Each row becomes ``HSET {prefix}{key} column value ...``. Rows are
+        read and validated in chunks, and each chunk is pipelined in one
+        round-trip with the GIL released.
+
+        Every row must have the same columns as the first one. ``None``
+        and NaN values are skipped. An invalid row raises ``TypeError``
+        naming the row and column; the chunks before it are already
+        written.
+
+        Args:
+            prefix: Prepended to each row's key.
+            rows: Iterable of dicts, or of the named tuples produced by
+                ``DataFrame.itertuples()``.
+            key_field: Column holding the key, which is not stored in the
+                hash. Required for dicts; defaults to ``"Index"`` for
+                named tuples.
+            chunk_size: Rows per pipelined round-trip.
+            progress: Called with the number of rows loaded so far after
+                each chunk.
+
+        Returns:
+            The number of rows loaded.
+        """
+        ...
+
+    def load_zset(
+        self,
+        name: str,
+        mapping: dict[Any, float] | Iterable[tuple[Any, float]],
+        chunk_size: int = 1000,
+        progress: Optional[Callable[[int], Any]] = None,
+    ) -> int:
+        """Load members into a sorted set.
+
+        Members are validated and sent as ``ZADD`` commands of up to
+        ``chunk_size`` members each, with the GIL released. NaN scores
+        raise ``TypeError``; the chunks before it are already written.
+
+        Args:
+            name: Sorted set key.
+            mapping: Dict of member to score, or an iterable of
+                ``(member, score)`` pairs.
+            chunk_size: Members per ``ZADD``.
+            progress: Called with the number of members loaded so far
+                after each chunk.
+
+        Returns:
+            The number of members added (not counting score updates).
+        """
+        ...
+
+    def pexpire(self, name: str, millis: int, nx: bool = False, xx: bool = False, gt: bool = False, lt: bool = False) -> int:
         """Set a timeout on a key in milliseconds.
 
         Args:
             name: Key name.
             millis: TTL in milliseconds.
+            nx, xx, gt, lt: Conditions, as for :meth:`expire`.
 
         Returns:
-            ``1`` if the timeout was set, ``0`` if the key does not exist.
+            ``1`` if the timeout was set, ``0`` if the key does not exist
+            or a condition was not met.
         """
         ...
 
@@ -313,7 +1127,32 @@ class Redis:
         """
         ...
 
-    def persist(self, name: str) -> int:
+    def expiretime(self, name: str, *, timeout: float | None = None) -> int:
+        """Get the absolute Unix time at which a key expires, in seconds.
+
+        Args:
+            name: Key name.
+
+        Returns:
+            The timestamp, ``-1`` if the key has no expiry, or ``-2`` if
+            the key does not exist.
+        """
+        ...
+
+    def pexpiretime(self, name: str, *, timeout: float | None = None) -> int:
+        """Get the absolute Unix time at which a key expires, in
+        milliseconds.
+
+        Args:
+            name: Key name.
+
+        Returns:
+            The timestamp, ``-1`` if the key has no expiry, or ``-2`` if
+            the key does not exist.
+        """
+        ...
+
+    def persist(self, name: str, *, timeout: float | None = None) -> int:
         """Remove the expiry from a key.
 
         Args:
@@ -325,7 +1164,7 @@ class Redis:
         """
         ...
 
-    def rename(self, src: str, dst: str) -> Any:
+    def rename(self, src: str, dst: str, *, timeout: float | None = None) -> Any:
         """Rename a key.
 
         Args:
@@ -340,7 +1179,7 @@ class Redis:
         """
         ...
 
-    def incr(self, name: str) -> int:
+    def incr(self, name: str, *, timeout: float | None = None) -> int:
         """Increment the integer value of a key by one.
 
         Args:
@@ -351,7 +1190,7 @@ class Redis:
         """
         ...
 
-    def decr(self, name: str) -> int:
+    def decr(self, name: str, *, timeout: float | None = None) -> int:
         """Decrement the integer value of a key by one.
 
         Args:
@@ -398,29 +1237,51 @@ class Redis:
         """
         ...
 
-    def mget(self, *names: str) -> list[Optional[str | bytes]]:
+    def mget(
+        self, *names: str, expected_response_size: int | None = None
+    ) -> list[Optional[str | bytes]]:
         """Get the values of multiple keys.
 
         Args:
             *names: Key names.
+            expected_response_size: Approximate reply size in bytes, to
+                size the read buffer up front (see :meth:`execute_command`).
 
         Returns:
             A list of values (or ``None`` for missing keys).
         """
         ...
 
-    def mset(self, mapping: dict[str, str]) -> bool:
+    def get_floats(self, keys: Sequence[str]) -> Any:
+        """Get the values of multiple keys as a ``numpy.float64`` array.
+
+        Values are parsed in Rust into a buffer the array wraps without
+        copying. Requires ``numpy``.
+
+        Args:
+            keys: Key names.
+
+        Returns:
+            A read-only ``numpy.ndarray``; missing keys are ``NaN``.
+
+        Raises:
+            TypeError: If a value is not a valid float.
+        """
+        ...
+
+    def mset(self, mapping: dict[str, str] | Iterable[tuple[str, str]]) -> bool:
         """Set multiple keys to multiple values.
 
         Args:
-            mapping: A ``{key: value}`` dictionary.
+            mapping: A ``{key: value}`` dictionary, or an iterable of
+                ``(key, value)`` pairs. Keys are sent in this order.
 
         Returns:
             ``True`` (``MSET`` never fails).
         """
         ...
 
-    def append(self, name: str, value: str) -> int:
+    def append(self, name: str, value: str, *, timeout: float | None = None) -> int:
         """Append a value to a key.
 
         Args:
@@ -432,7 +1293,7 @@ class Redis:
         """
         ...
 
-    def strlen(self, name: str) -> int:
+    def strlen(self, name: str, *, timeout: float | None = None) -> int:
         """Get the length of the string stored at a key.
 
         Args:
@@ -479,24 +1340,54 @@ class Redis:
         """
         ...
 
-    def setnx(self, name: str, value: str) -> int:
-        """Set a key only if it does not already exist.
+    def getex(
+        self,
+        name: str,
+        ex: Optional[int] = None,
+        px: Optional[int] = None,
+        exat: Optional[int] = None,
+        pxat: Optional[int] = None,
+        persist: bool = False,
+    ) -> Optional[str | bytes]:
+        """Get the value of a key and set or clear its expiration.
+
+        At most one expiry option may be given; with none, this is a plain
+        ``GET``.
 
         Args:
             name: Key name.
-            value: Value to set.
+            ex: Expire in this many seconds.
+            px: Expire in this many milliseconds.
+            exat: Expire at this Unix time in seconds.
+            pxat: Expire at this Unix time in milliseconds.
+            persist: Remove the expiration.
 
         Returns:
-            ``1`` if the key was set, ``0`` if it already existed.
+            The value, or ``None`` if the key does not exist.
+
+        Raises:
+            TypeError: If more than one expiry option is given.
         """
         ...
 
-    def setex(self, name: str, seconds: int, value: str) -> Any:
-        """Set a key with an expiration in seconds.
+    def setnx(self, name: str, value: str, *, timeout: float | None = None) -> int:
+        """Set a key only if it does not already exist.
 
         Args:
             name: Key name.
-            seconds: TTL in seconds.
+            value: Value to set.
+
+        Returns:
+            ``1`` if the key was set, ``0`` if it already existed.
+        """
+        ...
+
+    def setex(self, name: str, seconds: int, value: str) -> Any:
+        """Set a key with an expiration in seconds.
+
+        Args:
+            name: Key name.
+            seconds: TTL in seconds.
             value: Value to set.
 
         Returns:
@@ -526,33 +1417,49 @@ class Redis:
         """
         ...
 
-    def type(self, name: str) -> Any:
+    def type(self, name: str, *, timeout: float | None = None) -> str:
         """Return the type of the value stored at a key.
 
         Args:
             name: Key name.
 
         Returns:
-            A string like ``"string"``, ``"list"``, ``"set"``, etc.
+            A ``str`` like ``"string"``, ``"list"``, ``"set"``, etc., or
+            ``"none"`` if the key doesn't exist, even with
+            ``decode_responses=False``.
         """
         ...
 
     # ── Hash commands ───────────────────────────────────────────
 
-    def hset(self, name: str, key: str, value: str) -> int:
-        """Set a hash field to a value.
+    def hset(
+        self,
+        name: str,
+        key: Optional[str] = None,
+        value: Optional[str] = None,
+        mapping: Optional[dict[str, str] | Iterable[tuple[str, str]]] = None,
+        *,
+        timeout: float | None = None,
+    ) -> int:
+        """Set one or more hash fields.
 
         Args:
             name: Hash key name.
-            key: Field name.
+            key: Field name, together with ``value``.
             value: Field value.
+            mapping: A dict of ``{field: value}``, or an iterable of
+                ``(field, value)`` pairs, set in the same command.
 
         Returns:
-            ``1`` if the field is new, ``0`` if it was updated.
+            The number of fields added; updated fields are not counted.
+
+        Raises:
+            TypeError: If only one of ``key`` and ``value`` is given, or
+                no field is given at all.
         """
         ...
 
-    def hget(self, name: str, key: str) -> Optional[str | bytes]:
+    def hget(self, name: str, key: str, *, timeout: float | None = None) -> Optional[str | bytes]:
         """Get the value of a hash field.
 
         Args:
@@ -564,14 +1471,29 @@ class Redis:
         """
         ...
 
-    def hgetall(self, name: str) -> Any:
+    def hgetall(self, name: str, *, timeout: float | None = None) -> dict[Any, Any]:
         """Get all fields and values of a hash.
 
         Args:
             name: Hash key name.
 
         Returns:
-            A list of alternating field names and values.
+            A ``{field: value}`` dict, empty when the hash does not exist.
+        """
+        ...
+
+    def hgetall_floats(self, name: str) -> tuple[list[str], Any]:
+        """Get all fields of a hash with their values as floats.
+
+        Args:
+            name: Hash key name.
+
+        Returns:
+            A ``(fields, values)`` tuple: field names and a read-only
+            ``numpy.float64`` array of the same length.
+
+        Raises:
+            TypeError: If a value is not a valid float.
         """
         ...
 
@@ -587,7 +1509,7 @@ class Redis:
         """
         ...
 
-    def hexists(self, name: str, key: str) -> int:
+    def hexists(self, name: str, key: str, *, timeout: float | None = None) -> int:
         """Check if a hash field exists.
 
         Args:
@@ -599,7 +1521,7 @@ class Redis:
         """
         ...
 
-    def hkeys(self, name: str) -> list[str | bytes]:
+    def hkeys(self, name: str, *, timeout: float | None = None) -> list[str | bytes]:
         """Get all field names in a hash.
 
         Args:
@@ -610,7 +1532,7 @@ class Redis:
         """
         ...
 
-    def hvals(self, name: str) -> list[str | bytes]:
+    def hvals(self, name: str, *, timeout: float | None = None) -> list[str | bytes]:
         """Get all values in a hash.
 
         Args:
@@ -621,7 +1543,7 @@ class Redis:
         """
         ...
 
-    def hlen(self, name: str) -> int:
+    def hlen(self, name: str, *, timeout: float | None = None) -> int:
         """Get the number of fields in a hash.
 
         Args:
@@ -683,6 +1605,18 @@ class Redis:
         """
         ...
 
+    def hrandfield(self, name: str, count: Optional[int] = None, withvalues: bool = False) -> Any:
+        """Return random fields of a hash without removing them.
+
+        Args:
+            name: Hash key name.
+            count: Return this many distinct fields, or, when negative,
+                this many fields that may repeat. Without it a single
+                field (or ``None``) is returned instead of a list.
+            withvalues: Return ``(field, value)`` tuples. Requires ``count``.
+        """
+        ...
+
     # ── List commands ───────────────────────────────────────────
 
     def lpush(self, name: str, *values: str) -> int:
@@ -748,7 +1682,7 @@ class Redis:
         """
         ...
 
-    def llen(self, name: str) -> int:
+    def llen(self, name: str, *, timeout: float | None = None) -> int:
         """Get the length of a list.
 
         Args:
@@ -801,6 +1735,160 @@ class Redis:
         """
         ...
 
+    def lpos(
+        self,
+        name: str,
+        value: str,
+        rank: Optional[int] = None,
+        count: Optional[int] = None,
+        maxlen: Optional[int] = None,
+    ) -> Optional[int] | list[int]:
+        """Find the index of an element in a list.
+
+        Args:
+            name: List key name.
+            value: Element to look for.
+            rank: Return the ``rank``-th match; negative ranks search
+                from the tail. Must not be ``0``.
+            count: Return up to ``count`` matches as a list (``0`` for all).
+            maxlen: Compare at most ``maxlen`` elements.
+
+        Returns:
+            The index, or ``None`` if not found. A list of indexes when
+            ``count`` is given.
+        """
+        ...
+
+    def linsert(self, name: str, where: Literal["BEFORE", "AFTER"], refvalue: str, value: str) -> int:
+        """Insert an element before or after another element in a list.
+
+        Args:
+            name: List key name.
+            where: ``"BEFORE"`` or ``"AFTER"`` (case-insensitive).
+            refvalue: Element to insert next to.
+            value: Element to insert.
+
+        Returns:
+            The new length of the list, ``-1`` if ``refvalue`` was not
+            found, or ``0`` if the key does not exist.
+        """
+        ...
+
+    def ltrim(self, name: str, start: int, end: int, *, timeout: float | None = None) -> bool:
+        """Trim a list to the elements between ``start`` and ``end``.
+
+        Args:
+            name: List key name.
+            start: First index to keep.
+            end: Last index to keep (inclusive; negative counts from the end).
+
+        Returns:
+            ``True``.
+        """
+        ...
+
+    def rpoplpush(self, src: str, dst: str, *, timeout: float | None = None) -> Optional[str | bytes]:
+        """Pop the last element of ``src`` and push it onto ``dst``.
+
+        Returns:
+            The element, or ``None`` if ``src`` is empty.
+        """
+        ...
+
+    def lmove(
+        self,
+        src: str,
+        dst: str,
+        wherefrom: Literal["LEFT", "RIGHT"] = "LEFT",
+        whereto: Literal["LEFT", "RIGHT"] = "RIGHT",
+    ) -> Optional[str | bytes]:
+        """Pop an element from one end of ``src`` and push it onto one end of ``dst``.
+
+        In cluster mode both keys must hash to the same slot.
+
+        Returns:
+            The moved element, or ``None`` if ``src`` is empty.
+        """
+        ...
+
+    def lmpop(
+        self,
+        keys: Sequence[str],
+        direction: Literal["LEFT", "RIGHT"] = "LEFT",
+        count: Optional[int] = None,
+    ) -> Optional[list[Any]]:
+        """Pop elements from the first non-empty list among ``keys``.
+
+        Args:
+            keys: The lists, tried in order.
+            direction: Pop from the ``"LEFT"`` (head) or ``"RIGHT"`` (tail).
+            count: Pop up to this many elements (one by default).
+
+        In cluster mode every key must hash to the same slot.
+
+        Returns:
+            ``[key, [element, ...]]``, or ``None`` if every list is empty.
+        """
+        ...
+
+    # ── Blocking commands ───────────────────────────────────────
+
+    def blpop(self, keys: str | Sequence[str], timeout: float = 0) -> Optional[list[Any]]:
+        """Pop the first element of the first non-empty list, blocking.
+
+        The connection is held and the GIL released for the whole wait,
+        and the read timeout is extended by ``timeout`` so long waits do
+        not fail.
+
+        Args:
+            keys: One list key or several, checked in order.
+            timeout: Seconds to wait. ``0`` waits forever.
+
+        Returns:
+            ``[key, element]``, or ``None`` if the timeout expired.
+        """
+        ...
+
+    def brpop(self, keys: str | Sequence[str], timeout: float = 0) -> Optional[list[Any]]:
+        """Like :meth:`blpop`, popping from the tail."""
+        ...
+
+    def brpoplpush(self, src: str, dst: str, timeout: float = 0) -> Any:
+        """Pop the last element of ``src`` and push it onto ``dst``, blocking.
+
+        Returns:
+            The moved element, or ``None`` if the timeout expired.
+        """
+        ...
+
+    def blmove(
+        self,
+        src: str,
+        dst: str,
+        wherefrom: Literal["LEFT", "RIGHT"] = "LEFT",
+        whereto: Literal["LEFT", "RIGHT"] = "RIGHT",
+        timeout: float = 0,
+    ) -> Any:
+        """Move an element between lists, blocking.
+
+        Returns:
+            The moved element, or ``None`` if the timeout expired.
+        """
+        ...
+
+    def bzpopmin(self, keys: str | Sequence[str], timeout: float = 0) -> Optional[tuple[Any, Any, float]]:
+        """Pop the lowest-scored member of the first non-empty sorted set, blocking.
+
+        Returns:
+            ``(key, member, score)`` with a float score, or ``None`` if the
+            timeout expired.
+        """
+        ...
+
+    def bzpopmax(self, keys: str | Sequence[str], timeout: float = 0) -> Optional[tuple[Any, Any, float]]:
+        """Like :meth:`bzpopmin`, popping the highest-scored member."""
+        ...
+
     # ── Set commands ────────────────────────────────────────────
 
     def sadd(self, name: str, *members: str) -> int:
@@ -815,7 +1903,7 @@ class Redis:
         """
         ...
 
-    def smembers(self, name: str) -> Any:
+    def smembers(self, name: str, *, timeout: float | None = None) -> Any:
         """Get all members of a set.
 
         Args:
@@ -826,7 +1914,7 @@ class Redis:
         """
         ...
 
-    def scard(self, name: str) -> int:
+    def scard(self, name: str, *, timeout: float | None = None) -> int:
         """Get the cardinality (number of members) of a set.
 
         Args:
@@ -849,7 +1937,7 @@ class Redis:
         """
         ...
 
-    def sismember(self, name: str, value: str) -> int:
+    def sismember(self, name: str, value: str, *, timeout: float | None = None) -> int:
         """Check if a value is a member of a set.
 
         Args:
@@ -884,6 +1972,45 @@ class Redis:
         """
         ...
 
+    def sinter_page(
+        self,
+        keys: Sequence[str],
+        cursor: int = 0,
+        count: int = 100,
+        ttl: int = 60,
+    ) -> tuple[int, list[Any]]:
+        """Read the intersection of sets one page at a time.
+
+        The first page snapshots the intersection on the server into a
+        temporary key (skipped when ``SINTERCARD`` shows it is empty);
+        later pages ``SSCAN`` that snapshot. The snapshot expires ``ttl``
+        seconds after the last page read and is deleted after the final
+        page. Paginations over the same keys share one snapshot, and
+        starting again from ``cursor=0`` replaces it.
+
+        Args:
+            keys: Set keys to intersect (one hash slot in cluster mode).
+            cursor: ``0`` for the first page, then the returned cursor.
+            count: Hint for the number of members per page.
+            ttl: Seconds the snapshot outlives the last page read.
+
+        Returns:
+            ``(next_cursor, members)``; ``next_cursor`` is ``0`` after the
+            last page. Pages may be empty before the end.
+
+        Raises:
+            ResponseError: The snapshot expired between pages.
+
+        Example:
+            >>> cursor = 0
+            >>> while True:
+            ...     cursor, members = r.sinter_page(["a", "b"], cursor)
+            ...     handle(members)
+            ...     if cursor == 0:
+            ...         break
+        """
+        ...
+
     def sunion(self, *names: str) -> Any:
         """Return the union of one or more sets.
 
@@ -906,12 +2033,105 @@ class Redis:
         """
         ...
 
+    def sinterstore(self, dest: str, *keys: str) -> int:
+        """Store the intersection of one or more sets in ``dest``.
+
+        Args:
+            dest: Destination key (overwritten).
+            *keys: Set key names.
+
+        Returns:
+            The number of members in the resulting set.
+
+        Raises:
+            ClusterError: In cluster mode, if the keys span several slots.
+        """
+        ...
+
+    def sunionstore(self, dest: str, *keys: str) -> int:
+        """Store the union of one or more sets in ``dest``.
+
+        Args:
+            dest: Destination key (overwritten).
+            *keys: Set key names.
+
+        Returns:
+            The number of members in the resulting set.
+
+        Raises:
+            ClusterError: In cluster mode, if the keys span several slots.
+        """
+        ...
+
+    def sdiffstore(self, dest: str, *keys: str) -> int:
+        """Store the difference of the first set with all successive sets in ``dest``.
+
+        Args:
+            dest: Destination key (overwritten).
+            *keys: Set key names.
+
+        Returns:
+            The number of members in the resulting set.
+
+        Raises:
+            ClusterError: In cluster mode, if the keys span several slots.
+        """
+        ...
+
+    def smove(self, src: str, dst: str, value: str) -> int:
+        """Move a member from one set to another.
+
+        Args:
+            src: Source set key.
+            dst: Destination set key.
+            value: Member to move.
+
+        Returns:
+            ``1`` if the member was moved, ``0`` if it was not in ``src``.
+
+        Raises:
+            ClusterError: In cluster mode, if the keys are in different slots.
+        """
+        ...
+
+    def smismember(self, name: str, *values: str) -> list[int]:
+        """Check which of ``values`` are members of a set.
+
+        Returns:
+            ``1`` or ``0`` per value, in order.
+        """
+        ...
+
+    def srandmember(self, name: str, count: Optional[int] = None) -> Any:
+        """Return random members of a set without removing them.
+
+        Args:
+            name: The set key.
+            count: Return this many distinct members, or, when negative,
+                this many members that may repeat. Without it a single
+                member (or ``None``) is returned instead of a list.
+        """
+        ...
+
+    def sintercard(self, keys: Sequence[str], limit: Optional[int] = None) -> int:
+        """Count the members of the intersection of ``keys`` without returning them.
+
+        Args:
+            keys: The sets to intersect.
+            limit: Stop counting at this many members (``0`` or ``None``
+                for no limit), which lets the server stop early.
+
+        Raises:
+            ClusterError: In cluster mode, if the keys are in different slots.
+        """
+        ...
+
     # ── Sorted set commands ─────────────────────────────────────
 
     def zadd(
         self,
         name: str,
-        mapping: dict[str, float],
+        mapping: dict[str, float] | Iterable[tuple[str, float]],
         nx: bool = False,
         xx: bool = False,
         gt: bool = False,
@@ -922,7 +2142,8 @@ class Redis:
 
         Args:
             name: Sorted-set key name.
-            mapping: A ``{member: score}`` dictionary.
+            mapping: A ``{member: score}`` dictionary, or an iterable of
+                ``(member, score)`` pairs. Members are sent in this order.
             nx: Only add new elements (do not update existing).
             xx: Only update existing elements (do not add new).
             gt: Only update when the new score is greater than the current.
@@ -946,7 +2167,7 @@ class Redis:
         """
         ...
 
-    def zscore(self, name: str, member: str) -> Optional[float]:
+    def zscore(self, name: str, member: str, *, timeout: float | None = None) -> Optional[float]:
         """Get the score of a member in a sorted set.
 
         Args:
@@ -958,7 +2179,7 @@ class Redis:
         """
         ...
 
-    def zrank(self, name: str, member: str) -> Optional[int]:
+    def zrank(self, name: str, member: str, *, timeout: float | None = None) -> Optional[int]:
         """Get the rank (0-based) of a member in a sorted set.
 
         Args:
@@ -970,7 +2191,7 @@ class Redis:
         """
         ...
 
-    def zcard(self, name: str) -> int:
+    def zcard(self, name: str, *, timeout: float | None = None) -> int:
         """Get the number of members in a sorted set.
 
         Args:
@@ -1089,999 +2310,4442 @@ class Redis:
         """
         ...
 
-    # ── Scan ────────────────────────────────────────────────────
-
-    def scan(
+    def zunionstore(
         self,
-        cursor: int = 0,
-        match_pattern: Optional[str] = None,
-        count: Optional[int] = None,
-    ) -> list[Any]:
-        """Incrementally iterate the keyspace.
+        dest: str,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+    ) -> int:
+        """Store the union of sorted sets in ``dest``.
 
         Args:
-            cursor: Cursor position (``0`` to begin a new iteration).
-            match_pattern: Glob-style pattern to filter keys.
-            count: Hint for the number of keys to return per call.
+            dest: Destination key.
+            keys: Source sorted-set keys.
+            weights: One multiplier per key, applied to that source's scores.
+            aggregate: How the scores of a member found in several sources
+                combine: ``"SUM"`` (the server default), ``"MIN"`` or ``"MAX"``.
+
+        In cluster mode ``dest`` and every key must hash to the same slot.
 
         Returns:
-            A two-element list ``[next_cursor, [key, ...]]``.
+            The number of members in the resulting sorted set.
         """
         ...
 
-    # ── Scripting ───────────────────────────────────────────────
-
-    def eval(self, script: str, numkeys: int, *args: str) -> Any:
-        """Evaluate a Lua script server-side.
+    def zinterstore(
+        self,
+        dest: str,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+    ) -> int:
+        """Store the intersection of sorted sets in ``dest`` (see :meth:`zunionstore`)."""
+        ...
 
-        Args:
-            script: The Lua script source.
-            numkeys: Number of arguments that are key names.
-            *args: Key names followed by additional arguments.
+    def zunion(
+        self,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+        withscores: bool = False,
+    ) -> list[Any]:
+        """Return the union of sorted sets (see :meth:`zunionstore`).
 
         Returns:
-            The script's return value.
+            The members, or ``(member, score)`` tuples with float scores
+            when ``withscores`` is set.
         """
         ...
 
-    def evalsha(self, sha: str, numkeys: int, *args: str) -> Any:
-        """Evaluate a cached Lua script by its SHA1 digest.
+    def zinter(
+        self,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+        withscores: bool = False,
+    ) -> list[Any]:
+        """Return the intersection of sorted sets (see :meth:`zunion`)."""
+        ...
+
+    def zdiff(self, keys: Sequence[str], withscores: bool = False) -> list[Any]:
+        """Return the members of the first sorted set that are in none of the
+        others, or ``(member, score)`` tuples with float scores when
+        ``withscores`` is set."""
+        ...
+
+    def zrangestore(
+        self,
+        dest: str,
+        name: str,
+        start: str | int | float,
+        end: str | int | float,
+        byscore: bool = False,
+        bylex: bool = False,
+        desc: bool = False,
+        offset: Optional[int] = None,
+        num: Optional[int] = None,
+    ) -> int:
+        """Store a range of ``name`` in ``dest``.
 
         Args:
-            sha: SHA1 hex digest of a previously loaded script.
-            numkeys: Number of arguments that are key names.
-            *args: Key names followed by additional arguments.
+            dest: The destination key.
+            name: The source sorted set.
+            start: First index, or the lowest score or member with
+                ``byscore``/``bylex`` (``"(1"``, ``"-inf"``, ``"[a"``, ...).
+            end: Last index, score or member.
+            byscore: Range by score.
+            bylex: Range by member, for members sharing a score.
+            desc: Walk the set from the highest score down.
+            offset: Skip this many members (with ``byscore``/``bylex``).
+            num: Store at most this many members (with ``offset``).
+
+        In cluster mode ``dest`` and ``name`` must hash to the same slot.
 
         Returns:
-            The script's return value.
+            The number of members in ``dest``.
         """
         ...
 
-    def script_load(self, script: str) -> str:
-        """Load a Lua script into the server's script cache.
-
-        Args:
-            script: The Lua script source.
+    def zpopmin(self, name: str, count: Optional[int] = None) -> list[tuple[Any, float]]:
+        """Remove and return the lowest-scored members of a sorted set.
 
         Returns:
-            The SHA1 hex digest of the cached script.
+            A list of ``(member, score)`` tuples with float scores; one
+            member unless ``count`` is given.
         """
         ...
 
-    # ── FalkorDB / Graph commands ───────────────────────────────
+    def zpopmax(self, name: str, count: Optional[int] = None) -> list[tuple[Any, float]]:
+        """Like :meth:`zpopmin`, popping the highest-scored members."""
+        ...
 
-    def graph_query(
-        self, graph: str, query: str, timeout: Optional[int] = None
-    ) -> Any:
-        """Execute a Cypher query on a FalkorDB graph.
+    def zmpop(
+        self,
+        keys: Sequence[str],
+        min: bool = False,
+        max: bool = False,
+        count: Optional[int] = None,
+    ) -> Optional[tuple[Any, list[tuple[Any, float]]]]:
+        """Pop members from the first non-empty sorted set among ``keys``.
 
         Args:
-            graph: The graph key name.
-            query: A Cypher query string.
-            timeout: Optional query timeout in milliseconds.
+            keys: The sorted sets, tried in order.
+            min: Pop the lowest-scored members.
+            max: Pop the highest-scored members.
+            count: Pop up to this many members (one by default).
+
+        Exactly one of ``min`` and ``max`` must be set. In cluster mode
+        every key must hash to the same slot.
 
         Returns:
-            The raw graph result as a nested list (compact format).
+            ``(key, [(member, score), ...])`` with float scores, or
+            ``None`` if every set is empty.
         """
         ...
 
-    def graph_ro_query(
-        self, graph: str, query: str, timeout: Optional[int] = None
-    ) -> Any:
-        """Execute a read-only Cypher query on a FalkorDB graph.
-
-        Same as :meth:`graph_query` but uses ``GRAPH.RO_QUERY``,
-        which may be routed to a replica in cluster mode.
+    def zrandmember(self, name: str, count: Optional[int] = None, withscores: bool = False) -> Any:
+        """Return random members of a sorted set.
 
         Args:
-            graph: The graph key name.
-            query: A Cypher query string.
-            timeout: Optional query timeout in milliseconds.
-
-        Returns:
-            The raw graph result as a nested list (compact format).
+            name: The sorted set key.
+            count: Return this many distinct members, or, when negative,
+                this many members that may repeat. Without it a single
+                member is returned.
+            withscores: Return ``(member, score)`` tuples with float
+                scores (requires ``count``).
         """
         ...
 
-    def graph_delete(self, graph: str) -> Any:
-        """Delete a graph and all its data.
+    def zmscore(self, name: str, *members: str) -> list[Optional[float]]:
+        """Get the scores of several members as floats, ``None`` for a
+        missing member."""
+        ...
+
+    # ── Geo ─────────────────────────────────────────────────────
+
+    def geoadd(
+        self,
+        name: str,
+        values: Sequence[tuple[float, float, str]],
+        nx: bool = False,
+        xx: bool = False,
+        ch: bool = False,
+    ) -> int:
+        """Add members with their positions to a geo index.
 
         Args:
-            graph: The graph key name.
+            name: The geo key (a sorted set).
+            values: ``(longitude, latitude, member)`` tuples.
+            nx: Only add new members.
+            xx: Only update members that already exist.
+            ch: Count changed members as well as added ones.
 
         Returns:
-            The server's acknowledgement string.
+            The number of members added (or changed, with ``ch``).
         """
         ...
 
-    def graph_list(self) -> list[Any]:
-        """List all graphs in the current database.
+    def geopos(self, name: str, *members: str) -> list[Optional[tuple[float, float]]]:
+        """Get the positions of members.
 
         Returns:
-            A list of graph key names.
+            A ``(longitude, latitude)`` float tuple per member, ``None``
+            for a missing one.
         """
         ...
 
-    def graph_explain(self, graph: str, query: str) -> Any:
-        """Return the execution plan for a Cypher query without executing it.
-
-        Args:
-            graph: The graph key name.
-            query: A Cypher query string.
+    def geodist(
+        self, name: str, member1: str, member2: str, unit: Optional[_GeoUnit] = None
+    ) -> Optional[float]:
+        """Get the distance between two members in ``unit`` (metres by default).
 
         Returns:
-            The execution plan as a string or list.
+            The distance, or ``None`` if a member is missing.
         """
         ...
 
-    def graph_profile(self, graph: str, query: str) -> Any:
-        """Execute a Cypher query and return the execution plan with timings.
+    def geosearch(
+        self,
+        name: str,
+        member: Optional[str] = None,
+        longitude: Optional[float] = None,
+        latitude: Optional[float] = None,
+        radius: Optional[float] = None,
+        width: Optional[float] = None,
+        height: Optional[float] = None,
+        unit: _GeoUnit = "m",
+        sort: Optional[Literal["ASC", "DESC"]] = None,
+        count: Optional[int] = None,
+        any: bool = False,
+        withcoord: bool = False,
+        withdist: bool = False,
+        withhash: bool = False,
+    ) -> list[str | bytes | _GeoMatch]:
+        """Find the members within an area.
+
+        Give either ``member`` or ``longitude`` and ``latitude`` as the
+        centre, and either ``radius`` or ``width`` and ``height``.
 
         Args:
-            graph: The graph key name.
-            query: A Cypher query string.
+            name: The geo key.
+            member: Search around this member.
+            longitude: Search around this position (with ``latitude``).
+            latitude: See ``longitude``.
+            radius: Search a circle of this radius.
+            width: Search a box of this width (with ``height``).
+            height: See ``width``.
+            unit: Unit of the radius, the box and the returned distances.
+            sort: ``"ASC"`` (nearest first) or ``"DESC"``.
+            count: Return at most this many matches.
+            any: Stop at the first ``count`` matches instead of the nearest.
+            withcoord: Include each match's coordinates.
+            withdist: Include each match's distance from the centre.
+            withhash: Include each match's geohash.
 
         Returns:
-            The profiled execution plan.
+            The members, or, with any of the ``with*`` options, dicts with
+            a ``"member"`` key plus ``"distance"`` (float), ``"hash"``
+            (int) and ``"coordinates"`` (``(longitude, latitude)``) as asked.
         """
         ...
 
-    def graph_slowlog(self, graph: str) -> Any:
-        """Return the slow log for a graph.
+    def geosearchstore(
+        self,
+        dest: str,
+        source: str,
+        member: Optional[str] = None,
+        longitude: Optional[float] = None,
+        latitude: Optional[float] = None,
+        radius: Optional[float] = None,
+        width: Optional[float] = None,
+        height: Optional[float] = None,
+        unit: _GeoUnit = "m",
+        sort: Optional[Literal["ASC", "DESC"]] = None,
+        count: Optional[int] = None,
+        any: bool = False,
+        storedist: bool = False,
+    ) -> int:
+        """Store the members :meth:`geosearch` finds in ``source`` into ``dest``.
 
-        Args:
-            graph: The graph key name.
+        With ``storedist`` the result is a sorted set scored by distance
+        rather than a geo index.
 
         Returns:
-            A list of slow-log entries.
+            The number of members stored.
         """
         ...
 
-    def graph_config(
-        self, action: str, name: str, value: Optional[str] = None
-    ) -> Any:
-        """Get or set a FalkorDB graph configuration parameter.
+    # ── Scan ────────────────────────────────────────────────────
+
+    def scan(
+        self,
+        cursor: int = 0,
+        match_pattern: Optional[str] = None,
+        count: Optional[int] = None,
+    ) -> list[Any]:
+        """Incrementally iterate the keyspace.
 
         Args:
-            action: ``"GET"`` or ``"SET"``.
-            name: Configuration parameter name.
-            value: New value (required for ``SET``).
+            cursor: Cursor position (``0`` to begin a new iteration).
+            match_pattern: Glob-style pattern to filter keys.
+            count: Hint for the number of keys to return per call.
 
         Returns:
-            The configuration value(s).
+            A two-element list ``[next_cursor, [key, ...]]``.
         """
         ...
 
-    # ── Server commands ─────────────────────────────────────────
-
-    def keys(self, pattern: str = "*") -> list[str | bytes]:
-        """Find all keys matching a glob-style pattern.
+    def hscan(
+        self,
+        name: str,
+        cursor: int = 0,
+        match_pattern: Optional[str] = None,
+        count: Optional[int] = None,
+    ) -> list[Any]:
+        """Incrementally iterate the fields of a hash.
 
         Args:
-            pattern: Glob pattern (default ``"*"`` matches all).
+            name: Hash key name.
+            cursor: Cursor position (``0`` to begin a new iteration).
+            match_pattern: Glob-style pattern to filter fields.
+            count: Hint for the number of fields to return per call.
 
         Returns:
-            A list of matching key names.
-
-        Warning:
-            Avoid using this in production on large databases.
+            A two-element list ``[next_cursor, [field, value, ...]]``.
+            :meth:`hscan_iter` drives the cursor instead.
         """
         ...
 
-    def flushdb(self) -> Any:
-        """Delete all keys in the current database.
+    def scan_iter(self, match: Optional[str] = None, count: Optional[int] = None) -> "ScanIterator":
+        """Iterate over keys, driving the SCAN cursor behind the scenes.
 
-        Returns:
-            ``True`` on success.
-        """
-        ...
+        In cluster mode every master is scanned in turn.
 
-    def flushall(self) -> Any:
-        """Delete all keys in all databases.
+        Args:
+            match: Glob-style pattern to filter keys.
+            count: Hint for the number of keys fetched per round-trip.
 
-        Returns:
-            ``True`` on success.
+        Example:
+            >>> for key in r.scan_iter(match="user:*"):
+            ...     print(key)
         """
         ...
 
-    def info(self, section: Optional[str] = None) -> Any:
-        """Return information and statistics about the server.
-
-        Args:
-            section: Optional section name (e.g. ``"memory"``).
-
-        Returns:
-            The server info as a bulk string.
-        """
+    def hscan_iter(self, name: str, match: Optional[str] = None, count: Optional[int] = None) -> "ScanIterator":
+        """Iterate over the ``(field, value)`` pairs of a hash with HSCAN."""
         ...
 
-    def dbsize(self) -> int:
-        """Return the number of keys in the current database.
+    def sscan_iter(self, name: str, match: Optional[str] = None, count: Optional[int] = None) -> "ScanIterator":
+        """Iterate over the members of a set with SSCAN."""
+        ...
 
-        Returns:
-            The number of keys.
-        """
+    def zscan_iter(self, name: str, match: Optional[str] = None, count: Optional[int] = None) -> "ScanIterator":
+        """Iterate over the ``(member, score)`` pairs of a sorted set with ZSCAN."""
         ...
 
-    def select(self, db: int) -> Any:
-        """Switch to a different database.
+    # ── Streams ─────────────────────────────────────────────────
+
+    def xadd(
+        self,
+        name: str,
+        fields: dict[_CommandArg, _CommandArg],
+        id: str = "*",
+        maxlen: Optional[int] = None,
+        minid: Optional[str] = None,
+        approximate: bool = True,
+        nomkstream: bool = False,
+        limit: Optional[int] = None,
+    ) -> Optional[str | bytes]:
+        """Append an entry to a stream.
 
         Args:
-            db: Database index.
+            name: Stream key.
+            fields: The entry's ``{field: value}`` pairs.
+            id: Entry ID, or ``"*"`` to let the server generate one.
+            maxlen: Trim the stream to about this many entries.
+            minid: Trim entries with IDs lower than this one.
+            approximate: Trim with ``~`` (efficient) rather than ``=``.
+            nomkstream: Don't create the stream if it doesn't exist.
+            limit: Cap on entries evicted while trimming; needs
+                ``approximate``.
 
         Returns:
-            ``True`` on success.
+            The new entry's ID, or ``None`` with ``nomkstream`` when the
+            stream doesn't exist.
         """
         ...
 
-    def randomkey(self) -> Optional[str | bytes]:
-        """Return a random key from the current database.
-
-        Returns:
-            A random key name, or ``None`` if the database is empty.
-        """
+    def xlen(self, name: str) -> int:
+        """Get the number of entries in a stream."""
         ...
 
-    def lastsave(self) -> int:
-        """Return the Unix timestamp of the last successful save.
+    def xdel(self, name: str, *ids: str) -> int:
+        """Delete entries from a stream.
 
         Returns:
-            Unix timestamp in seconds.
+            The number of entries deleted.
         """
         ...
 
-    def echo(self, message: str) -> str | bytes:
-        """Echo the given message.
+    def xtrim(
+        self,
+        name: str,
+        maxlen: Optional[int] = None,
+        minid: Optional[str] = None,
+        approximate: bool = True,
+        limit: Optional[int] = None,
+    ) -> int:
+        """Trim a stream by length (``maxlen``) or lowest ID (``minid``).
 
         Args:
-            message: Message string.
+            name: Stream key.
+            maxlen: Keep about this many entries.
+            minid: Evict entries with IDs lower than this one.
+            approximate: Trim with ``~`` rather than ``=``.
+            limit: Cap on entries evicted per call; needs ``approximate``.
 
         Returns:
-            The same message as ``bytes``.
+            The number of entries removed.
         """
         ...
 
-    def publish(self, channel: str, message: str) -> int:
-        """Publish a message to a Pub/Sub channel.
+    def stream_retention(
+        self,
+        name: str,
+        max_age_ms: int,
+        approximate: bool = True,
+        limit: Optional[int] = None,
+    ) -> int:
+        """Drop the entries of an auto-ID stream older than ``max_age_ms``.
+
+        The cutoff comes from the server clock (see :meth:`server_now`), so
+        a skewed local clock doesn't shift it; once the clock offset is
+        cached this is a single ``XTRIM MINID`` round trip.
 
         Args:
-            channel: Channel name.
-            message: Message to publish.
+            name: Stream key.
+            max_age_ms: Keep entries whose ID is at most this old.
+            approximate: Trim with ``~`` rather than ``=``.
+            limit: Cap on entries evicted per call; needs ``approximate``.
 
         Returns:
-            The number of clients that received the message.
+            The number of entries removed.
         """
         ...
 
-    def time(self) -> list[Any]:
-        """Return the server time.
+    def xrange(
+        self, name: str, min: str = "-", max: str = "+", count: Optional[int] = None
+    ) -> list[_StreamEntry]:
+        """Get the entries with IDs between ``min`` and ``max``, oldest first.
 
         Returns:
-            A two-element list ``[unix_seconds, microseconds]``.
+            A list of ``(id, {field: value})`` tuples.
         """
         ...
 
-    @property
-    def pool_idle_count(self) -> int:
-        """Number of idle connections currently in the pool."""
-        ...
-
-    @property
-    def pool_available(self) -> int:
-        """Number of connections available (idle + remaining capacity)."""
+    def xrevrange(
+        self, name: str, max: str = "+", min: str = "-", count: Optional[int] = None
+    ) -> list[_StreamEntry]:
+        """Like :meth:`xrange`, newest first."""
         ...
 
-    def __repr__(self) -> str: ...
-    def __str__(self) -> str: ...
+    def xread(
+        self,
+        streams: dict[str, str],
+        count: Optional[int] = None,
+        block: Optional[int] = None,
+    ) -> Optional[dict[str | bytes, list[_StreamEntry]]]:
+        """Read entries newer than the given IDs from one or more streams.
 
+        Args:
+            streams: ``{stream: last_seen_id}``; ``"$"`` reads only new
+                entries.
+            count: Maximum entries per stream.
+            block: Wait up to this many milliseconds for entries. Keep it
+                below ``read_timeout_ms``, or the read times out first.
 
-class Pipeline:
-    """A pipeline for batching Redis commands.
+        Returns:
+            ``{stream: [(id, {field: value}), ...]}``, or ``None`` if the
+            block timed out.
+        """
+        ...
 
-    Commands are buffered client-side until :meth:`execute` is called,
-    at which point they are sent to Redis in a single round-trip.
+    def xgroup_create(
+        self, name: str, groupname: str, id: str = "$", mkstream: bool = False
+    ) -> bool:
+        """Create a consumer group.
 
-    Example:
-        >>> pipe = r.pipeline()
-        >>> pipe.set("a", "1").set("b", "2")
-        >>> results = pipe.execute()
-        >>> results
-        [True, True]
-    """
+        Args:
+            name: Stream key.
+            groupname: Group name.
+            id: Where the group starts: ``"$"`` for new entries, ``"0"``
+                for the whole stream.
+            mkstream: Create the stream if it doesn't exist.
+        """
+        ...
 
-    def execute_command(self, *args: str) -> "Pipeline":
-        """Buffer a raw Redis command.
+    def xreadgroup(
+        self,
+        groupname: str,
+        consumername: str,
+        streams: dict[str, str],
+        count: Optional[int] = None,
+        block: Optional[int] = None,
+        noack: bool = False,
+    ) -> Optional[dict[str | bytes, list[_StreamEntry]]]:
+        """Read entries as a consumer of a group.
 
         Args:
-            *args: Command name followed by its arguments.
+            groupname: Group name.
+            consumername: Consumer name.
+            streams: ``{stream: id}``; ``">"`` for never-delivered entries,
+                an ID to re-read this consumer's pending ones.
+            count: Maximum entries per stream.
+            block: Wait up to this many milliseconds (see :meth:`xread`).
+            noack: Don't add the entries to the pending entries list.
 
         Returns:
-            ``self`` for chaining.
+            Shaped like :meth:`xread`. Pending entries deleted since
+            appear as ``(id, None)``.
         """
         ...
 
-    def execute(self) -> list[Any]:
-        """Execute all buffered commands in a single round-trip.
+    def xack(self, name: str, groupname: str, *ids: str) -> int:
+        """Acknowledge entries of a consumer group.
 
         Returns:
-            A list of responses, one per buffered command.
+            The number of entries acknowledged.
         """
         ...
 
-    def reset(self) -> None:
-        """Discard all buffered commands."""
-        ...
-
-    def __len__(self) -> int:
-        """Return the number of buffered commands."""
-        ...
-
-    def __repr__(self) -> str: ...
-
-    # ── String ──────────────────────────────────────────────────
-
-    def ping(self) -> "Pipeline":
-        """Buffer a ``PING`` command.
+    def xpending(self, name: str, groupname: str) -> dict[str, Any]:
+        """Summarize the entries of a consumer group that were delivered
+        but not yet acknowledged.
 
         Returns:
-            ``self`` for chaining.
+            ``{"pending": count, "min": id, "max": id, "consumers":
+            {name: count}}``, with ``None`` IDs when nothing is pending.
         """
         ...
 
-    def set(
+    def xclaim(
         self,
         name: str,
-        value: str,
-        ex: Optional[int] = None,
-        px: Optional[int] = None,
-        nx: bool = False,
-        xx: bool = False,
-    ) -> "Pipeline":
-        """Buffer a ``SET`` command.
-
-        Args:
-            name: Key name.
-            value: Value to set.
-            ex: Expire time in seconds.
-            px: Expire time in milliseconds.
-            nx: Only set if the key does not exist.
-            xx: Only set if the key exists.
+        groupname: str,
+        consumername: str,
+        min_idle_time: int,
+        message_ids: Sequence[str],
+        idle: Optional[int] = None,
+        time: Optional[int] = None,
+        retrycount: Optional[int] = None,
+        force: bool = False,
+        justid: bool = False,
+    ) -> list[Any]:
+        """Take ownership of pending entries idle for at least
+        ``min_idle_time`` milliseconds.
 
         Returns:
-            ``self`` for chaining.
+            The claimed ``(id, {field: value})`` entries, or just their
+            IDs with ``justid=True``.
         """
         ...
 
-    def get(self, name: str) -> "Pipeline":
-        """Buffer a ``GET`` command.
+    # ── Scripting ───────────────────────────────────────────────
+
+    def eval(self, script: str, numkeys: int, *args: str) -> Any:
+        """Evaluate a Lua script server-side.
 
         Args:
-            name: Key name.
+            script: The Lua script source.
+            numkeys: Number of arguments that are key names.
+            *args: Key names followed by additional arguments.
 
         Returns:
-            ``self`` for chaining.
+            The script's return value.
         """
         ...
 
-    def delete(self, *names: str) -> "Pipeline":
-        """Buffer a ``DEL`` command.
+    def evalsha(self, sha: str, numkeys: int, *args: str) -> Any:
+        """Evaluate a cached Lua script by its SHA1 digest.
 
         Args:
-            *names: Key names to delete.
+            sha: SHA1 hex digest of a previously loaded script.
+            numkeys: Number of arguments that are key names.
+            *args: Key names followed by additional arguments.
 
         Returns:
-            ``self`` for chaining.
+            The script's return value.
         """
         ...
 
-    def exists(self, *names: str) -> "Pipeline":
-        """Buffer an ``EXISTS`` command.
+    def script_load(self, script: str) -> "str | NodeResults":
+        """Load a Lua script into the server's script cache.
 
         Args:
-            *names: Key names to check.
+            script: The Lua script source.
 
         Returns:
-            ``self`` for chaining.
+            The SHA1 hex digest of the cached script. In cluster mode the
+            script is loaded on every master and a :class:`NodeResults`
+            is returned instead.
         """
         ...
 
-    def expire(self, name: str, seconds: int) -> "Pipeline":
-        """Buffer an ``EXPIRE`` command.
+    def register_script(self, script: str) -> "Script":
+        """Wrap a Lua script in a callable :class:`Script`.
+
+        The SHA1 is computed locally; nothing is sent until the script is
+        called.
 
         Args:
-            name: Key name.
-            seconds: TTL in seconds.
+            script: The Lua script source.
 
         Returns:
-            ``self`` for chaining.
+            A :class:`Script` that runs on this client.
         """
         ...
 
-    def ttl(self, name: str) -> "Pipeline":
-        """Buffer a ``TTL`` command.
+    # ── Functions ──────────────────────────────────────────────────
+
+    def function_load(self, code: str, replace: bool = False) -> "str | NodeResults":
+        """Load a library of functions (Redis 7+).
 
         Args:
-            name: Key name.
+            code: The library source, starting with ``#!lua name=<library>``.
+            replace: Replace an existing library of the same name instead
+                of failing.
 
         Returns:
-            ``self`` for chaining.
+            The library name. In cluster mode the library is loaded on
+            every master and a :class:`NodeResults` is returned instead.
         """
         ...
 
-    def incr(self, name: str) -> "Pipeline":
-        """Buffer an ``INCR`` command.
+    def function_list(self, library: Optional[str] = None, with_code: bool = False) -> list[Any]:
+        """List the loaded libraries and their functions.
 
         Args:
-            name: Key name.
-
-        Returns:
-            ``self`` for chaining.
+            library: Only list libraries whose name matches this pattern.
+            with_code: Include each library's source.
         """
         ...
 
-    def decr(self, name: str) -> "Pipeline":
-        """Buffer a ``DECR`` command.
+    def function_dump(self) -> bytes:
+        """Serialize every loaded library for :meth:`function_restore`."""
+        ...
+
+    def function_restore(self, payload: bytes, policy: Optional[str] = None) -> "bool | NodeResults":
+        """Restore libraries from a :meth:`function_dump` payload.
 
         Args:
-            name: Key name.
+            payload: The serialized libraries.
+            policy: ``"append"`` (the server default) fails on a library
+                that already exists, ``"replace"`` overwrites it and
+                ``"flush"`` deletes every library first.
 
         Returns:
-            ``self`` for chaining.
+            ``True``. In cluster mode the libraries are restored on every
+            master and a :class:`NodeResults` is returned instead.
+
+        Raises:
+            TypeError: Unknown ``policy``.
         """
         ...
 
-    def incrby(self, name: str, amount: int) -> "Pipeline":
-        """Buffer an ``INCRBY`` command.
+    def fcall(self, function: str, numkeys: int, *args: Any) -> Any:
+        """Call a function loaded with :meth:`function_load`.
 
         Args:
-            name: Key name.
-            amount: Increment amount.
-
-        Returns:
-            ``self`` for chaining.
+            function: The function name.
+            numkeys: Number of keys.
+            *args: Keys followed by arguments.
         """
         ...
 
-    def decrby(self, name: str, amount: int) -> "Pipeline":
-        """Buffer a ``DECRBY`` command.
-
-        Args:
-            name: Key name.
-            amount: Decrement amount.
+    def fcall_ro(self, function: str, numkeys: int, *args: Any) -> Any:
+        """Call a read-only function (declared with the ``no-writes`` flag).
 
-        Returns:
-            ``self`` for chaining.
+        Like :meth:`fcall`, but may be served by a replica when the client
+        reads from replicas.
         """
         ...
 
-    def append(self, name: str, value: str) -> "Pipeline":
-        """Buffer an ``APPEND`` command.
+    # ── FalkorDB / Graph commands ───────────────────────────────
+
+    def graph_query(
+        self,
+        graph: str,
+        query: str,
+        timeout: Optional[int] = None,
+        expected_response_size: Optional[int] = None,
+    ) -> Any:
+        """Execute a Cypher query on a FalkorDB graph.
 
         Args:
-            name: Key name.
-            value: Value to append.
+            graph: The graph key name.
+            query: A Cypher query string.
+            timeout: Optional query timeout in milliseconds.
+            expected_response_size: Approximate reply size in bytes, to
+                size the read buffer up front (see :meth:`execute_command`).
 
         Returns:
-            ``self`` for chaining.
+            The raw graph result as a nested list (compact format).
         """
         ...
 
-    def strlen(self, name: str) -> "Pipeline":
-        """Buffer a ``STRLEN`` command.
+    def graph_ro_query(
+        self,
+        graph: str,
+        query: str,
+        timeout: Optional[int] = None,
+        expected_response_size: Optional[int] = None,
+    ) -> Any:
+        """Execute a read-only Cypher query on a FalkorDB graph.
+
+        Same as :meth:`graph_query` but uses ``GRAPH.RO_QUERY``,
+        which may be routed to a replica in cluster mode.
 
         Args:
-            name: Key name.
+            graph: The graph key name.
+            query: A Cypher query string.
+            timeout: Optional query timeout in milliseconds.
+            expected_response_size: Approximate reply size in bytes, to
+                size the read buffer up front (see :meth:`execute_command`).
 
         Returns:
-            ``self`` for chaining.
+            The raw graph result as a nested list (compact format).
         """
         ...
 
-    def setnx(self, name: str, value: str) -> "Pipeline":
-        """Buffer a ``SETNX`` command.
+    def graph_query_result(
+        self,
+        graph: str,
+        query: str,
+        timeout: Optional[int] = None,
+        read_only: bool = False,
+        expected_response_size: Optional[int] = None,
+    ) -> "QueryResult":
+        """Execute a Cypher query and keep the parsed result in Rust.
+
+        Use :meth:`QueryResult.to_arrow` / :meth:`QueryResult.to_pandas`
+        to export it column-wise without per-cell Python objects.
 
         Args:
-            name: Key name.
-            value: Value to set.
+            graph: The graph key name.
+            query: A Cypher query string.
+            timeout: Optional query timeout in milliseconds.
+            read_only: Use ``GRAPH.RO_QUERY`` instead of ``GRAPH.QUERY``.
+            expected_response_size: Approximate reply size in bytes, to
+                size the read buffer up front (see :meth:`execute_command`).
 
         Returns:
-            ``self`` for chaining.
+            A :class:`QueryResult`.
         """
         ...
 
-    def unlink(self, *names: str) -> "Pipeline":
-        """Buffer an ``UNLINK`` command.
+    def graph_delete(self, graph: str, *, timeout: float | None = None) -> Any:
+        """Delete a graph and all its data.
 
         Args:
-            *names: Key names to unlink.
+            graph: The graph key name.
 
         Returns:
-            ``self`` for chaining.
+            The server's acknowledgement string.
         """
         ...
 
-    def rename(self, src: str, dst: str) -> "Pipeline":
-        """Buffer a ``RENAME`` command.
-
-        Args:
-            src: Current key name.
-            dst: New key name.
+    def graph_list(self, *, timeout: float | None = None) -> list[Any]:
+        """List all graphs in the current database.
 
         Returns:
-            ``self`` for chaining.
+            A list of graph key names.
         """
         ...
 
-    def persist(self, name: str) -> "Pipeline":
-        """Buffer a ``PERSIST`` command.
+    def graph_explain(self, graph: str, query: str) -> Any:
+        """Return the execution plan for a Cypher query without executing it.
 
         Args:
-            name: Key name.
+            graph: The graph key name.
+            query: A Cypher query string.
 
         Returns:
-            ``self`` for chaining.
+            The execution plan as a string or list.
         """
         ...
 
-    def type(self, name: str) -> "Pipeline":
-        """Buffer a ``TYPE`` command.
+    def graph_profile(self, graph: str, query: str) -> Any:
+        """Execute a Cypher query and return the execution plan with timings.
 
         Args:
-            name: Key name.
+            graph: The graph key name.
+            query: A Cypher query string.
 
         Returns:
-            ``self`` for chaining.
+            The profiled execution plan.
         """
         ...
 
-    # ── Hash ────────────────────────────────────────────────────
-
-    def hset(self, name: str, key: str, value: str) -> "Pipeline":
-        """Buffer an ``HSET`` command.
+    def graph_slowlog(self, graph: str) -> Any:
+        """Return the slow log for a graph.
 
         Args:
-            name: Hash key name.
-            key: Field name.
-            value: Field value.
+            graph: The graph key name.
 
         Returns:
-            ``self`` for chaining.
+            A list of slow-log entries.
         """
         ...
 
-    def hget(self, name: str, key: str) -> "Pipeline":
-        """Buffer an ``HGET`` command.
+    def graph_config(
+        self, action: str, name: str, value: Optional[str] = None
+    ) -> Any:
+        """Get or set a FalkorDB graph configuration parameter.
 
         Args:
-            name: Hash key name.
-            key: Field name.
+            action: ``"GET"`` or ``"SET"``.
+            name: Configuration parameter name.
+            value: New value (required for ``SET``).
 
         Returns:
-            ``self`` for chaining.
+            The configuration value(s).
         """
         ...
 
-    def hgetall(self, name: str) -> "Pipeline":
-        """Buffer an ``HGETALL`` command.
+    # ── Server commands ─────────────────────────────────────────
+
+    def keys(self, pattern: str = "*") -> list[str | bytes]:
+        """Find all keys matching a glob-style pattern.
 
         Args:
-            name: Hash key name.
+            pattern: Glob pattern (default ``"*"`` matches all).
 
         Returns:
-            ``self`` for chaining.
+            A list of matching key names.
+
+        Warning:
+            Avoid using this in production on large databases.
         """
         ...
 
-    def hdel(self, name: str, *keys: str) -> "Pipeline":
-        """Buffer an ``HDEL`` command.
+    def flushdb(
+        self,
+        mode: Literal["async", "sync"] | None = None,
+        *,
+        confirm: bool = False,
+        timeout: float | None = None,
+    ) -> Any:
+        """Delete all keys in the current database.
 
         Args:
-            name: Hash key name.
-            *keys: Field names to delete.
+            mode: ``"async"`` to free memory in the background, or
+                ``"sync"``; the server's ``lazyfree-lazy-user-flush``
+                setting decides when omitted.
+            confirm: Must be ``True`` unless the client was created with
+                ``allow_destructive=True``.
+            timeout: Upper bound on the round trip, in seconds.
 
         Returns:
-            ``self`` for chaining.
+            ``True`` on success.
+
+        Raises:
+            ValueError: If the flush was not confirmed, or ``mode`` is not
+                ``"async"`` or ``"sync"``.
+
+        Example:
+            >>> r.flushdb("async", confirm=True)
+            True
         """
         ...
 
-    def hexists(self, name: str, key: str) -> "Pipeline":
-        """Buffer an ``HEXISTS`` command.
+    def flushall(self, mode: Literal["async", "sync"] | None = None, *, confirm: bool = False) -> Any:
+        """Delete all keys in all databases.
 
-        Args:
-            name: Hash key name.
-            key: Field name.
+        ``mode`` and ``confirm`` work as for :meth:`flushdb`.
 
         Returns:
-            ``self`` for chaining.
+            ``True`` on success. In cluster mode every master is flushed
+            and a :class:`NodeResults` is returned instead.
+
+        Raises:
+            ValueError: If the flush was not confirmed, or ``mode`` is not
+                ``"async"`` or ``"sync"``.
         """
         ...
 
-    def hlen(self, name: str) -> "Pipeline":
-        """Buffer an ``HLEN`` command.
-
-        Args:
-            name: Hash key name.
+    def config_get(self, pattern: str = "*") -> dict[Any, Any]:
+        """Get the server configuration parameters matching ``pattern``
+        (``CONFIG GET``).
 
         Returns:
-            ``self`` for chaining.
+            A ``{name: value}`` dict. In cluster mode the parameters are
+            read from one node.
         """
         ...
 
-    def hkeys(self, name: str) -> "Pipeline":
-        """Buffer an ``HKEYS`` command.
-
-        Args:
-            name: Hash key name.
+    def config_set(self, name: str, value: str) -> Any:
+        """Set a server configuration parameter (``CONFIG SET``).
 
         Returns:
-            ``self`` for chaining.
+            ``True`` on success. In cluster mode the parameter is set on
+            every master and a :class:`NodeResults` is returned instead.
         """
         ...
 
-    def hvals(self, name: str) -> "Pipeline":
-        """Buffer an ``HVALS`` command.
+    def info(self, section: Optional[str] = None) -> Any:
+        """Return information and statistics about the server.
 
         Args:
-            name: Hash key name.
+            section: Optional section name (e.g. ``"memory"``).
 
         Returns:
-            ``self`` for chaining.
+            The server info as a bulk string.
         """
         ...
 
-    def hmget(self, name: str, *keys: str) -> "Pipeline":
-        """Buffer an ``HMGET`` command.
-
-        Args:
-            name: Hash key name.
-            *keys: Field names.
+    def sentinel_masters(self) -> list[dict[str, str]]:
+        """The masters the sentinels monitor.
 
         Returns:
-            ``self`` for chaining.
+            One dict of strings per master (``name``, ``ip``, ``port``,
+            ``flags``, ``num-slaves``, ...).
+
+        Raises:
+            TypeError: The client is not in Sentinel mode.
         """
         ...
 
-    def hincrby(self, name: str, key: str, amount: int) -> "Pipeline":
-        """Buffer an ``HINCRBY`` command.
+    def sentinel_master(self, name: str) -> dict[str, str]:
+        """The sentinels' state of master ``name``, as a dict of strings."""
+        ...
 
-        Args:
-            name: Hash key name.
-            key: Field name.
-            amount: Increment amount.
+    def sentinel_replicas(self, name: str) -> list[dict[str, str]]:
+        """The replicas of master ``name``, one dict of strings per replica."""
+        ...
 
-        Returns:
-            ``self`` for chaining.
-        """
+    def sentinel_get_master_addr(self, name: str) -> Optional[tuple[str, int]]:
+        """The address of master ``name`` as ``(host, port)``, or ``None``
+        if the sentinels do not know it."""
         ...
 
-    # ── List ────────────────────────────────────────────────────
+    # ── Cluster resharding ─────────────────────────────────────────
 
-    def lpush(self, name: str, *values: str) -> "Pipeline":
-        """Buffer an ``LPUSH`` command.
+    def cluster_setslot(
+        self,
+        slot: int,
+        state: str,
+        node_id: Optional[str] = None,
+        node: Optional[str] = None,
+    ) -> bool:
+        """Set the migration state of a hash slot (``CLUSTER SETSLOT``).
 
         Args:
-            name: List key name.
-            *values: Values to prepend.
+            slot: The hash slot.
+            state: ``"importing"`` or ``"migrating"`` (with the other
+                side's ``node_id``), ``"node"`` (with the new owner's
+                ``node_id``) or ``"stable"``.
+            node_id: Cluster ID of the node the state refers to.
+            node: ``"host:port"`` of the node to change; the slot's
+                current owner by default.
 
-        Returns:
-            ``self`` for chaining.
+        Only available in cluster mode.
         """
         ...
 
-    def rpush(self, name: str, *values: str) -> "Pipeline":
-        """Buffer an ``RPUSH`` command.
-
-        Args:
-            name: List key name.
-            *values: Values to append.
+    def cluster_getkeysinslot(self, slot: int, count: int, node: Optional[str] = None) -> list[Any]:
+        """Up to ``count`` keys of a hash slot (``CLUSTER GETKEYSINSLOT``).
 
-        Returns:
-            ``self`` for chaining.
+        Asks ``node`` (``"host:port"``), or the slot's owner by default.
+        Only available in cluster mode.
         """
         ...
 
-    def lpop(self, name: str, count: Optional[int] = None) -> "Pipeline":
-        """Buffer an ``LPOP`` command.
+    def migrate_slot(
+        self,
+        slot: int,
+        from_node: str,
+        to_node: str,
+        batch: int = 100,
+        timeout_ms: int = 5000,
+        progress: Optional[Callable[[int], Any]] = None,
+    ) -> int:
+        """Move a hash slot and its keys from one master to another.
+
+        Marks the slot ``IMPORTING`` on ``to_node`` and ``MIGRATING`` on
+        ``from_node``, moves its keys with ``MIGRATE`` ``batch`` at a time,
+        then assigns it to ``to_node``. The slot stays usable meanwhile:
+        clients are redirected with ``ASK``. If a batch fails the slot is
+        left migrating, and calling this again resumes.
 
         Args:
-            name: List key name.
-            count: Number of elements to pop.
+            slot: The hash slot.
+            from_node: ``"host:port"`` of the master owning the slot.
+            to_node: ``"host:port"`` of the master receiving it.
+            batch: Keys moved per ``MIGRATE`` (default ``100``).
+            timeout_ms: ``MIGRATE`` timeout in milliseconds (default ``5000``).
+            progress: Called with the number of keys moved so far after
+                each batch.
 
         Returns:
-            ``self`` for chaining.
+            The number of keys moved.
         """
         ...
 
-    def rpop(self, name: str, count: Optional[int] = None) -> "Pipeline":
-        """Buffer an ``RPOP`` command.
+    def cluster_failover(self, node: str, force: bool = False, takeover: bool = False) -> Any:
+        """Promote a replica to master of its slots (``CLUSTER FAILOVER``).
+
+        The command goes to the replica, which coordinates with its
+        master and returns once the failover has started. The client
+        learns the new owner from redirects and slot map refreshes.
 
         Args:
-            name: List key name.
-            count: Number of elements to pop.
+            node: ``"host:port"`` of the replica to promote.
+            force: Do not wait for the master, e.g. when it is down.
+            takeover: Do not wait for the other masters either.
 
-        Returns:
-            ``self`` for chaining.
+        Raises:
+            TypeError: If both ``force`` and ``takeover`` are set, or
+                outside cluster mode.
         """
         ...
 
-    def lrange(self, name: str, start: int, stop: int) -> "Pipeline":
-        """Buffer an ``LRANGE`` command.
+    def drain_node(self, addr: str) -> None:
+        """Stop routing reads and key-less commands to a node before
+        maintenance.
 
-        Args:
-            name: List key name.
-            start: Start index.
-            stop: Stop index.
+        Reads go to other nodes of the same shard, as when the node's
+        circuit breaker is open. Writes to a drained master's slots keep
+        going to it until a failover moves them; once a slot map refresh
+        shows the slots on another node, the drain ends by itself. A
+        drained replica stays drained until :meth:`undrain_node`.
 
-        Returns:
-            ``self`` for chaining.
+        Raises:
+            ClusterError: If ``addr`` is not a node of the cluster.
+            TypeError: Outside cluster mode.
         """
         ...
 
-    def llen(self, name: str) -> "Pipeline":
-        """Buffer an ``LLEN`` command.
+    def undrain_node(self, addr: str) -> bool:
+        """Route to a drained node again. Returns ``False`` if it was not
+        being drained."""
+        ...
 
-        Args:
-            name: List key name.
+    @property
+    def draining_nodes(self) -> list[str]:
+        """The ``"host:port"`` of the nodes being drained."""
+        ...
+
+    def dbsize(self, *, timeout: float | None = None) -> int:
+        """Return the number of keys in the current database.
 
         Returns:
-            ``self`` for chaining.
+            The number of keys.
         """
         ...
 
-    def lindex(self, name: str, index: int) -> "Pipeline":
-        """Buffer an ``LINDEX`` command.
+    def select(self, db: int) -> Any:
+        """Switch to a different database.
 
         Args:
-            name: List key name.
-            index: Zero-based index.
+            db: Database index.
 
         Returns:
-            ``self`` for chaining.
+            ``True`` on success.
         """
         ...
 
-    # ── Set ─────────────────────────────────────────────────────
+    def randomkey(self) -> Optional[str | bytes]:
+        """Return a random key from the current database.
 
-    def sadd(self, name: str, *members: str) -> "Pipeline":
-        """Buffer an ``SADD`` command.
+        Returns:
+            A random key name, or ``None`` if the database is empty.
+        """
+        ...
 
-        Args:
-            name: Set key name.
-            *members: Members to add.
+    def lastsave(self) -> int:
+        """Return the Unix timestamp of the last successful save.
 
         Returns:
-            ``self`` for chaining.
+            Unix timestamp in seconds.
         """
         ...
 
-    def smembers(self, name: str) -> "Pipeline":
-        """Buffer an ``SMEMBERS`` command.
+    def echo(self, message: str, *, timeout: float | None = None) -> str | bytes:
+        """Echo the given message.
 
         Args:
-            name: Set key name.
+            message: Message string.
 
         Returns:
-            ``self`` for chaining.
+            The same message as ``bytes``.
         """
         ...
 
-    def scard(self, name: str) -> "Pipeline":
-        """Buffer an ``SCARD`` command.
+    def publish(self, channel: str, message: str, *, timeout: float | None = None) -> int:
+        """Publish a message to a Pub/Sub channel.
 
         Args:
-            name: Set key name.
+            channel: Channel name.
+            message: Message to publish.
 
         Returns:
-            ``self`` for chaining.
+            The number of clients that received the message.
         """
         ...
 
-    def srem(self, name: str, *members: str) -> "Pipeline":
-        """Buffer an ``SREM`` command.
+    def publish_many(self, messages: Sequence[tuple[str, str]]) -> list[int]:
+        """Publish many messages in a single round-trip.
+
+        In cluster mode the messages are sent with ``SPUBLISH``, grouped by
+        the node owning each channel's slot, and reach shard-channel
+        (``SSUBSCRIBE``) subscribers.
 
         Args:
-            name: Set key name.
-            *members: Members to remove.
+            messages: ``(channel, message)`` pairs.
 
         Returns:
-            ``self`` for chaining.
+            The number of receivers of each message, in order.
+
+        Example:
+            >>> r.publish_many([("orders", "1"), ("alerts", "disk full")])
+            [2, 0]
         """
         ...
 
-    def sismember(self, name: str, value: str) -> "Pipeline":
-        """Buffer an ``SISMEMBER`` command.
-
-        Args:
-            name: Set key name.
-            value: Value to test.
+    def time(self) -> list[Any]:
+        """Return the server time.
 
         Returns:
-            ``self`` for chaining.
+            A two-element list ``[unix_seconds, microseconds]``.
         """
         ...
 
-    # ── Sorted set ──────────────────────────────────────────────
+    def time_offset(self, refresh: bool = False) -> float:
+        """Seconds to add to the local clock to get the server's.
 
-    def zscore(self, name: str, member: str) -> "Pipeline":
-        """Buffer a ``ZSCORE`` command.
+        Measured from a few ``TIME`` round-trips, taking each server
+        reading as halfway through its round-trip and keeping the fastest.
+        The result is cached for a minute.
 
         Args:
-            name: Sorted-set key name.
-            member: Member name.
+            refresh: Measure again even if a cached value is fresh.
+        """
+        ...
 
-        Returns:
-            ``self`` for chaining.
+    def server_now(self) -> float:
+        """The server's current time in Unix seconds.
+
+        The local clock corrected by :meth:`time_offset`; no round-trip
+        while the cached offset is fresh.
         """
         ...
 
-    def zrank(self, name: str, member: str) -> "Pipeline":
-        """Buffer a ``ZRANK`` command.
+    @property
+    def pool_idle_count(self) -> int:
+        """Number of idle connections currently in the pool."""
+        ...
 
-        Args:
-            name: Sorted-set key name.
-            member: Member name.
+    @property
+    def pool_available(self) -> int:
+        """Number of connections available (idle + remaining capacity)."""
+        ...
+
+    def pool_stats(self) -> dict[str, Any]:
+        """Connection pool statistics since the client was created, summed
+        over every node's pool in a cluster.
 
         Returns:
-            ``self`` for chaining.
+            A dict with the current ``idle``, ``in_use`` and ``max_size``
+            connection counts; the ``connections_created``,
+            ``connections_closed``, ``connection_errors`` (failed connects
+            and socket errors) and ``checkouts`` totals; ``wait_ms_p50``,
+            ``wait_ms_p90``, ``wait_ms_p99`` and ``wait_ms_max``: how long
+            checkouts waited for a free connection (percentiles within
+            25%); and ``bytes_sent`` / ``bytes_received``. With Sentinel,
+            the counts restart when a failover swaps the pool.
         """
         ...
 
-    def zcard(self, name: str) -> "Pipeline":
-        """Buffer a ``ZCARD`` command.
-
-        Args:
-            name: Sorted-set key name.
+    def command_stats(self) -> dict[str, dict[str, float]]:
+        """Reply latency and timeouts by command family since the client was
+        created or :meth:`reset_command_stats` was last called.
 
         Returns:
-            ``self`` for chaining.
+            A dict keyed by family (``strings``, ``hashes``, ``lists``,
+            ``sets``, ``sorted_sets``, ``streams``, ``keys``,
+            ``scripting``, ``pubsub``, ``graph``, ``server`` or ``other``),
+            holding only the families that have been used. Each value has
+            ``count`` (replies received), ``timeouts`` (requests whose
+            reply did not arrive in time, retries included) and
+            ``latency_ms_p50``, ``latency_ms_p90``, ``latency_ms_p99`` and
+            ``latency_ms_max`` (percentiles within 25%).
         """
         ...
 
-    def zrem(self, name: str, *members: str) -> "Pipeline":
-        """Buffer a ``ZREM`` command.
+    def reset_command_stats(self) -> None:
+        """Zero the counters behind :meth:`command_stats`."""
+        ...
+
+    def warmup(self, count: Optional[int] = None) -> int:
+        """Open connections until ``count`` are idle in the pool (every
+        master's pool in a cluster), so later commands skip connection
+        setup.
 
         Args:
-            name: Sorted-set key name.
-            *members: Members to remove.
+            count: Idle connections wanted, capped at ``pool_size`` less
+                the connections checked out. Fills the pool by default.
 
         Returns:
-            ``self`` for chaining.
+            The number of connections opened.
         """
         ...
 
-    def zincrby(self, name: str, amount: float, member: str) -> "Pipeline":
-        """Buffer a ``ZINCRBY`` command.
+    @property
+    def protocol_version(self) -> Optional[int]:
+        """RESP protocol version negotiated with the server, or ``None``
+        before the first connection is made.
 
-        Args:
-            name: Sorted-set key name.
-            amount: Score increment.
-            member: Member name.
+        ``2`` when a server without RESP3 support (Redis before 6.0) was
+        asked for ``protocol=3`` and the client fell back to RESP2.
+        """
+        ...
 
-        Returns:
-            ``self`` for chaining.
+    def cache_info(self) -> Optional[dict[str, Any]]:
+        """Client-side cache statistics, or ``None`` without ``cache=True``.
+
+        A dict with ``hits``, ``misses``, ``size`` (cached keys),
+        ``max_entries`` and ``tracking``: whether invalidations are being
+        received. While ``tracking`` is ``False`` (before the first command,
+        or after the invalidation connection was lost) the cache is empty
+        and every :meth:`get` goes to the server.
         """
         ...
 
-    def zrange(
-        self, name: str, start: int, stop: int, withscores: bool = False
-    ) -> "Pipeline":
-        """Buffer a ``ZRANGE`` command.
+    def cache_clear(self) -> None:
+        """Drop every entry from the client-side cache (no-op without
+        ``cache=True``)."""
+        ...
 
-        Args:
-            name: Sorted-set key name.
-            start: Start index.
-            stop: Stop index.
-            withscores: If ``True``, include scores.
+    def disconnect_all(self) -> None:
+        """Close all pooled connections.
 
-        Returns:
-            ``self`` for chaining.
+        Idle connections are closed immediately; connections in use by
+        other threads are closed when their command completes. The client
+        reconnects lazily on the next command.
         """
         ...
 
-    # ── FalkorDB / Graph ────────────────────────────────────────
+    def recent_events(self, limit: Optional[int] = None) -> list[dict[str, Any]]:
+        """Recent connection lifecycle events, oldest first.
 
-    def graph_query(
-        self, graph: str, query: str, timeout: Optional[int] = None
-    ) -> "Pipeline":
-        """Buffer a ``GRAPH.QUERY`` command.
+        Each event is a ``{"type", "address", "detail", "timestamp"}`` dict.
+        ``type`` is ``"connected"``, ``"disconnected"`` (``detail`` holds the
+        error), ``"reconnected"`` or ``"subscribed"`` (``detail`` lists the
+        channels); ``timestamp`` is in seconds since the epoch. The last 256
+        events are kept.
 
         Args:
-            graph: The graph key name.
-            query: A Cypher query string.
-            timeout: Optional query timeout in milliseconds.
+            limit: Return at most this many of the newest events.
+        """
+        ...
 
-        Returns:
-            ``self`` for chaining.
+    def set_event_callback(self, callback: Optional[Callable[[dict[str, Any]], None]] = None) -> None:
+        """Call ``callback(event)`` for each lifecycle event as it happens.
+
+        The callback receives the same dicts as :meth:`recent_events`. It
+        runs on a pyrsedis I/O thread, so it should return quickly;
+        exceptions are reported through ``sys.unraisablehook``. Pass
+        ``None`` to remove it.
+
+        Raises:
+            TypeError: If ``callback`` is not callable.
         """
         ...
 
-    def graph_ro_query(
-        self, graph: str, query: str, timeout: Optional[int] = None
-    ) -> "Pipeline":
-        """Buffer a ``GRAPH.RO_QUERY`` command.
+    def dump_frames(self, limit: Optional[int] = None) -> str:
+        """Hexdump of the raw frames captured with ``capture_frames``.
 
-        Args:
-            graph: The graph key name.
-            query: A Cypher query string.
-            timeout: Optional query timeout in milliseconds.
+        Frames are listed oldest first, each under a header line giving its
+        direction (``>>>`` sent, ``<<<`` received), the server address, its
+        size and a timestamp in seconds since the epoch. A reply that failed
+        to parse shows the bytes that were buffered. Empty when capture is
+        off.
 
-        Returns:
-            ``self`` for chaining.
+        Args:
+            limit: Dump at most this many of the newest frames.
         """
         ...
 
-    def graph_delete(self, graph: str) -> "Pipeline":
-        """Buffer a ``GRAPH.DELETE`` command.
+    def __repr__(self) -> str: ...
+    def __str__(self) -> str: ...
+
+
+class Pipeline:
+    """A pipeline for batching Redis commands.
+
+    Commands are buffered client-side until :meth:`execute` is called,
+    at which point they are sent to Redis in a single round-trip.
+
+    Example:
+        >>> pipe = r.pipeline()
+        >>> pipe.set("a", "1").set("b", "2")
+        >>> results = pipe.execute()
+        >>> results
+        [True, True]
+    """
+
+    def execute_command(self, *args: _CommandArg) -> "Pipeline":
+        """Buffer a raw Redis command.
 
         Args:
-            graph: The graph key name.
+            *args: Command name followed by its arguments. ``bytes`` must
+                be valid UTF-8 in a pipeline.
 
         Returns:
             ``self`` for chaining.
         """
         ...
 
-    def graph_list(self) -> "Pipeline":
-        """Buffer a ``GRAPH.LIST`` command.
+    def execute(self) -> list[Any]:
+        """Execute all buffered commands in a single round-trip.
+
+        Returns:
+            A list of responses, one per buffered command.
+
+        Raises:
+            TransactionError: In transaction mode, if the server aborted the
+                transaction (a queued command was rejected).
+            WatchError: If a key passed to :meth:`watch` changed.
+        """
+        ...
+
+    def watch(self, *keys: str) -> None:
+        """Watch keys for changes until the transaction runs.
+
+        Pins a pooled connection and sends ``WATCH`` on it; :meth:`execute`
+        then runs ``MULTI``/``EXEC`` on that connection and raises
+        :class:`WatchError` if any watched key changed in between.
+
+        Raises:
+            TypeError: If the pipeline was not created with ``transaction=True``.
+            ClusterError: In cluster mode.
+        """
+        ...
+
+    def unwatch(self) -> None:
+        """Forget all watched keys and release the pinned connection."""
+        ...
+
+    @property
+    def watching(self) -> bool:
+        """Whether keys are being watched (see :meth:`watch`)."""
+        ...
+
+    def reset(self) -> None:
+        """Discard all buffered commands and watched keys."""
+        ...
+
+    @property
+    def size_bytes(self) -> int:
+        """Encoded size in bytes of the buffered commands."""
+        ...
+
+    @property
+    def max_bytes(self) -> Optional[int]:
+        """The ``max_bytes`` limit, or ``None`` if unbounded."""
+        ...
+
+    @property
+    def immediate(self) -> bool:
+        """Whether commands are sent as they are added (no buffering)."""
+        ...
+
+    @property
+    def transaction(self) -> bool:
+        """Whether :meth:`execute` wraps the commands in ``MULTI``/``EXEC``."""
+        ...
+
+    def __len__(self) -> int:
+        """Return the number of buffered commands."""
+        ...
+
+    def __repr__(self) -> str: ...
+
+    # ── String ──────────────────────────────────────────────────
+
+    def ping(self) -> "Pipeline":
+        """Buffer a ``PING`` command.
 
         Returns:
             ``self`` for chaining.
         """
         ...
 
-    # ── Server ──────────────────────────────────────────────────
+    def set(
+        self,
+        name: str,
+        value: str,
+        ex: Optional[int] = None,
+        px: Optional[int] = None,
+        nx: bool = False,
+        xx: bool = False,
+    ) -> "Pipeline":
+        """Buffer a ``SET`` command.
 
-    def flushdb(self) -> "Pipeline":
-        """Buffer a ``FLUSHDB`` command.
+        Args:
+            name: Key name.
+            value: Value to set.
+            ex: Expire time in seconds.
+            px: Expire time in milliseconds.
+            nx: Only set if the key does not exist.
+            xx: Only set if the key exists.
 
         Returns:
             ``self`` for chaining.
         """
         ...
 
-    def flushall(self) -> "Pipeline":
-        """Buffer a ``FLUSHALL`` command.
+    def get(self, name: str) -> "Pipeline":
+        """Buffer a ``GET`` command.
+
+        Args:
+            name: Key name.
 
         Returns:
             ``self`` for chaining.
         """
         ...
 
-    def dbsize(self) -> "Pipeline":
-        """Buffer a ``DBSIZE`` command.
+    def delete(self, *names: str) -> "Pipeline":
+        """Buffer a ``DEL`` command.
+
+        Args:
+            *names: Key names to delete.
 
         Returns:
             ``self`` for chaining.
         """
         ...
 
-    def echo(self, message: str) -> "Pipeline":
-        """Buffer an ``ECHO`` command.
+    def exists(self, *names: str) -> "Pipeline":
+        """Buffer an ``EXISTS`` command.
 
         Args:
-            message: Message string.
+            *names: Key names to check.
 
         Returns:
             ``self`` for chaining.
         """
         ...
 
-    def publish(self, channel: str, message: str) -> "Pipeline":
-        """Buffer a ``PUBLISH`` command.
+    def expire(self, name: str, seconds: int, nx: bool = False, xx: bool = False, gt: bool = False, lt: bool = False) -> "Pipeline":
+        """Buffer an ``EXPIRE`` command.
 
         Args:
-            channel: Channel name.
-            message: Message to publish.
+            name: Key name.
+            seconds: TTL in seconds.
 
         Returns:
             ``self`` for chaining.
         """
         ...
 
-    def time(self) -> "Pipeline":
-        """Buffer a ``TIME`` command.
+    def ttl(self, name: str) -> "Pipeline":
+        """Buffer a ``TTL`` command.
+
+        Args:
+            name: Key name.
 
         Returns:
             ``self`` for chaining.
         """
         ...
+
+    def incr(self, name: str) -> "Pipeline":
+        """Buffer an ``INCR`` command.
+
+        Args:
+            name: Key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def decr(self, name: str) -> "Pipeline":
+        """Buffer a ``DECR`` command.
+
+        Args:
+            name: Key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def incrby(self, name: str, amount: int) -> "Pipeline":
+        """Buffer an ``INCRBY`` command.
+
+        Args:
+            name: Key name.
+            amount: Increment amount.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def decrby(self, name: str, amount: int) -> "Pipeline":
+        """Buffer a ``DECRBY`` command.
+
+        Args:
+            name: Key name.
+            amount: Decrement amount.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def append(self, name: str, value: str) -> "Pipeline":
+        """Buffer an ``APPEND`` command.
+
+        Args:
+            name: Key name.
+            value: Value to append.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def strlen(self, name: str) -> "Pipeline":
+        """Buffer a ``STRLEN`` command.
+
+        Args:
+            name: Key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def setnx(self, name: str, value: str) -> "Pipeline":
+        """Buffer a ``SETNX`` command.
+
+        Args:
+            name: Key name.
+            value: Value to set.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def incrbyfloat(self, name: str, amount: float) -> "Pipeline":
+        """Buffer an ``INCRBYFLOAT`` command.
+
+        Args:
+            name: Key name.
+            amount: Increment amount.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def mget(self, *names: str) -> "Pipeline":
+        """Buffer an ``MGET`` command.
+
+        Args:
+            *names: Key names.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def mset(self, mapping: dict[str, str] | Iterable[tuple[str, str]]) -> "Pipeline":
+        """Buffer an ``MSET`` command.
+
+        Args:
+            mapping: A dict of ``{key: value}``, or an iterable of
+                ``(key, value)`` pairs.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def getset(self, name: str, value: str) -> "Pipeline":
+        """Buffer a ``GETSET`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def getdel(self, name: str) -> "Pipeline":
+        """Buffer a ``GETDEL`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def getex(
+        self,
+        name: str,
+        ex: Optional[int] = None,
+        px: Optional[int] = None,
+        exat: Optional[int] = None,
+        pxat: Optional[int] = None,
+        persist: bool = False,
+    ) -> "Pipeline":
+        """Buffer a ``GETEX`` command (see :meth:`Redis.getex`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def setex(self, name: str, seconds: int, value: str) -> "Pipeline":
+        """Buffer a ``SETEX`` command.
+
+        Args:
+            name: Key name.
+            seconds: Expiry in seconds.
+            value: Value to set.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def getrange(self, name: str, start: int, end: int) -> "Pipeline":
+        """Buffer a ``GETRANGE`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def unlink(self, *names: str) -> "Pipeline":
+        """Buffer an ``UNLINK`` command.
+
+        Args:
+            *names: Key names to unlink.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def rename(self, src: str, dst: str) -> "Pipeline":
+        """Buffer a ``RENAME`` command.
+
+        Args:
+            src: Current key name.
+            dst: New key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def expiretime(self, name: str) -> "Pipeline":
+        """Buffer an ``EXPIRETIME`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def pexpiretime(self, name: str) -> "Pipeline":
+        """Buffer a ``PEXPIRETIME`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def persist(self, name: str) -> "Pipeline":
+        """Buffer a ``PERSIST`` command.
+
+        Args:
+            name: Key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def type(self, name: str) -> "Pipeline":
+        """Buffer a ``TYPE`` command.
+
+        Args:
+            name: Key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def pexpire(self, name: str, millis: int, nx: bool = False, xx: bool = False, gt: bool = False, lt: bool = False) -> "Pipeline":
+        """Buffer a ``PEXPIRE`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def expireat(self, name: str, when: int, nx: bool = False, xx: bool = False, gt: bool = False, lt: bool = False) -> "Pipeline":
+        """Buffer an ``EXPIREAT`` command.
+
+        Args:
+            name: Key name.
+            when: Expiry as a UNIX timestamp in seconds.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def pttl(self, name: str) -> "Pipeline":
+        """Buffer a ``PTTL`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def scan(
+        self, cursor: int = 0, match_pattern: Optional[str] = None, count: Optional[int] = None
+    ) -> "Pipeline":
+        """Buffer a ``SCAN`` command.
+
+        Args:
+            cursor: The cursor position (start with ``0``).
+            match_pattern: Optional glob pattern to filter keys.
+            count: Hint for number of keys per iteration.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hscan(
+        self, name: str, cursor: int = 0, match_pattern: Optional[str] = None, count: Optional[int] = None
+    ) -> "Pipeline":
+        """Buffer an ``HSCAN`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def keys(self, pattern: str = "*") -> "Pipeline":
+        """Buffer a ``KEYS`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    # ── Hash ────────────────────────────────────────────────────
+
+    def hset(
+        self,
+        name: str,
+        key: Optional[str] = None,
+        value: Optional[str] = None,
+        mapping: Optional[dict[str, str] | Iterable[tuple[str, str]]] = None,
+    ) -> "Pipeline":
+        """Buffer an ``HSET`` command.
+
+        Args:
+            name: Hash key name.
+            key: Field name, together with ``value``.
+            value: Field value.
+            mapping: More ``{field: value}`` items or ``(field, value)`` pairs.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hget(self, name: str, key: str) -> "Pipeline":
+        """Buffer an ``HGET`` command.
+
+        Args:
+            name: Hash key name.
+            key: Field name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hgetall(self, name: str) -> "Pipeline":
+        """Buffer an ``HGETALL`` command.
+
+        Args:
+            name: Hash key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hdel(self, name: str, *keys: str) -> "Pipeline":
+        """Buffer an ``HDEL`` command.
+
+        Args:
+            name: Hash key name.
+            *keys: Field names to delete.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hexists(self, name: str, key: str) -> "Pipeline":
+        """Buffer an ``HEXISTS`` command.
+
+        Args:
+            name: Hash key name.
+            key: Field name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hlen(self, name: str) -> "Pipeline":
+        """Buffer an ``HLEN`` command.
+
+        Args:
+            name: Hash key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hkeys(self, name: str) -> "Pipeline":
+        """Buffer an ``HKEYS`` command.
+
+        Args:
+            name: Hash key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hvals(self, name: str) -> "Pipeline":
+        """Buffer an ``HVALS`` command.
+
+        Args:
+            name: Hash key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hmget(self, name: str, *keys: str) -> "Pipeline":
+        """Buffer an ``HMGET`` command.
+
+        Args:
+            name: Hash key name.
+            *keys: Field names.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hincrby(self, name: str, key: str, amount: int) -> "Pipeline":
+        """Buffer an ``HINCRBY`` command.
+
+        Args:
+            name: Hash key name.
+            key: Field name.
+            amount: Increment amount.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hincrbyfloat(self, name: str, key: str, amount: float) -> "Pipeline":
+        """Buffer a ``HINCRBYFLOAT`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hsetnx(self, name: str, key: str, value: str) -> "Pipeline":
+        """Buffer a ``HSETNX`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def hrandfield(self, name: str, count: Optional[int] = None, withvalues: bool = False) -> "Pipeline":
+        """Buffer an ``HRANDFIELD`` command; ``withvalues`` replies become
+        ``(field, value)`` tuples.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    # ── List ────────────────────────────────────────────────────
+
+    def lpush(self, name: str, *values: str) -> "Pipeline":
+        """Buffer an ``LPUSH`` command.
+
+        Args:
+            name: List key name.
+            *values: Values to prepend.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def rpush(self, name: str, *values: str) -> "Pipeline":
+        """Buffer an ``RPUSH`` command.
+
+        Args:
+            name: List key name.
+            *values: Values to append.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def lpop(self, name: str, count: Optional[int] = None) -> "Pipeline":
+        """Buffer an ``LPOP`` command.
+
+        Args:
+            name: List key name.
+            count: Number of elements to pop.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def rpop(self, name: str, count: Optional[int] = None) -> "Pipeline":
+        """Buffer an ``RPOP`` command.
+
+        Args:
+            name: List key name.
+            count: Number of elements to pop.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def lrange(self, name: str, start: int, stop: int) -> "Pipeline":
+        """Buffer an ``LRANGE`` command.
+
+        Args:
+            name: List key name.
+            start: Start index.
+            stop: Stop index.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def llen(self, name: str) -> "Pipeline":
+        """Buffer an ``LLEN`` command.
+
+        Args:
+            name: List key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def lindex(self, name: str, index: int) -> "Pipeline":
+        """Buffer an ``LINDEX`` command.
+
+        Args:
+            name: List key name.
+            index: Zero-based index.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def lpos(
+        self,
+        name: str,
+        value: str,
+        rank: Optional[int] = None,
+        count: Optional[int] = None,
+        maxlen: Optional[int] = None,
+    ) -> "Pipeline":
+        """Buffer an ``LPOS`` command (see :meth:`Redis.lpos`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def linsert(self, name: str, where: Literal["BEFORE", "AFTER"], refvalue: str, value: str) -> "Pipeline":
+        """Buffer an ``LINSERT`` command.
+
+        Args:
+            name: List key name.
+            where: ``"BEFORE"`` or ``"AFTER"``.
+            refvalue: Element to insert next to.
+            value: Element to insert.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def lset(self, name: str, index: int, value: str) -> "Pipeline":
+        """Buffer an ``LSET`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def lrem(self, name: str, count: int, value: str) -> "Pipeline":
+        """Buffer an ``LREM`` command.
+
+        Args:
+            name: List key name.
+            count: Occurrences to remove (0=all, >0=head-to-tail, <0=tail-to-head).
+            value: The value to remove.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def ltrim(self, name: str, start: int, end: int) -> "Pipeline":
+        """Buffer an ``LTRIM`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def rpoplpush(self, src: str, dst: str) -> "Pipeline":
+        """Buffer an ``RPOPLPUSH`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def lmove(
+        self,
+        src: str,
+        dst: str,
+        wherefrom: Literal["LEFT", "RIGHT"] = "LEFT",
+        whereto: Literal["LEFT", "RIGHT"] = "RIGHT",
+    ) -> "Pipeline":
+        """Buffer an ``LMOVE`` command (see :meth:`Redis.lmove`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def blmove(
+        self,
+        src: str,
+        dst: str,
+        wherefrom: Literal["LEFT", "RIGHT"] = "LEFT",
+        whereto: Literal["LEFT", "RIGHT"] = "RIGHT",
+        timeout: float = 0,
+    ) -> "Pipeline":
+        """Buffer a ``BLMOVE`` command (see :meth:`Redis.blmove`).
+
+        The pipeline's connection waits with it, so keep ``timeout`` short
+        and non-zero: the read timeout is not stretched for a pipeline the
+        way it is for :meth:`Redis.blmove`.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def lmpop(
+        self,
+        keys: Sequence[str],
+        direction: Literal["LEFT", "RIGHT"] = "LEFT",
+        count: Optional[int] = None,
+    ) -> "Pipeline":
+        """Buffer an ``LMPOP`` command (see :meth:`Redis.lmpop`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    # ── Set ─────────────────────────────────────────────────────
+
+    def sadd(self, name: str, *members: str) -> "Pipeline":
+        """Buffer an ``SADD`` command.
+
+        Args:
+            name: Set key name.
+            *members: Members to add.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def smembers(self, name: str) -> "Pipeline":
+        """Buffer an ``SMEMBERS`` command.
+
+        Args:
+            name: Set key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def scard(self, name: str) -> "Pipeline":
+        """Buffer an ``SCARD`` command.
+
+        Args:
+            name: Set key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def srem(self, name: str, *members: str) -> "Pipeline":
+        """Buffer an ``SREM`` command.
+
+        Args:
+            name: Set key name.
+            *members: Members to remove.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def sinterstore(self, dest: str, *keys: str) -> "Pipeline":
+        """Buffer an ``SINTERSTORE`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def sunionstore(self, dest: str, *keys: str) -> "Pipeline":
+        """Buffer an ``SUNIONSTORE`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def sdiffstore(self, dest: str, *keys: str) -> "Pipeline":
+        """Buffer an ``SDIFFSTORE`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def smove(self, src: str, dst: str, value: str) -> "Pipeline":
+        """Buffer an ``SMOVE`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def smismember(self, name: str, *values: str) -> "Pipeline":
+        """Buffer an ``SMISMEMBER`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def srandmember(self, name: str, count: Optional[int] = None) -> "Pipeline":
+        """Buffer an ``SRANDMEMBER`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def sintercard(self, keys: Sequence[str], limit: Optional[int] = None) -> "Pipeline":
+        """Buffer an ``SINTERCARD`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def spop(self, name: str, count: Optional[int] = None) -> "Pipeline":
+        """Buffer a ``SPOP`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def sismember(self, name: str, value: str) -> "Pipeline":
+        """Buffer an ``SISMEMBER`` command.
+
+        Args:
+            name: Set key name.
+            value: Value to test.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    # ── Sorted set ──────────────────────────────────────────────
+
+    def zscore(self, name: str, member: str) -> "Pipeline":
+        """Buffer a ``ZSCORE`` command.
+
+        Args:
+            name: Sorted-set key name.
+            member: Member name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zrank(self, name: str, member: str) -> "Pipeline":
+        """Buffer a ``ZRANK`` command.
+
+        Args:
+            name: Sorted-set key name.
+            member: Member name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zcard(self, name: str) -> "Pipeline":
+        """Buffer a ``ZCARD`` command.
+
+        Args:
+            name: Sorted-set key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zrem(self, name: str, *members: str) -> "Pipeline":
+        """Buffer a ``ZREM`` command.
+
+        Args:
+            name: Sorted-set key name.
+            *members: Members to remove.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zincrby(self, name: str, amount: float, member: str) -> "Pipeline":
+        """Buffer a ``ZINCRBY`` command.
+
+        Args:
+            name: Sorted-set key name.
+            amount: Score increment.
+            member: Member name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zrange(
+        self, name: str, start: int, stop: int, withscores: bool = False
+    ) -> "Pipeline":
+        """Buffer a ``ZRANGE`` command.
+
+        Args:
+            name: Sorted-set key name.
+            start: Start index.
+            stop: Stop index.
+            withscores: If ``True``, include scores.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zadd(
+        self,
+        name: str,
+        mapping: dict[str, float] | Iterable[tuple[str, float]],
+        nx: bool = False,
+        xx: bool = False,
+        gt: bool = False,
+        lt: bool = False,
+        ch: bool = False,
+    ) -> "Pipeline":
+        """Buffer a ``ZADD`` command.
+
+        Args:
+            name: Sorted-set key name.
+            mapping: A dict of ``{member: score}``, or an iterable of
+                ``(member, score)`` pairs.
+            nx, xx, gt, lt, ch: Options as on :meth:`Redis.zadd`.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zrevrange(
+        self, name: str, start: int, stop: int, withscores: bool = False
+    ) -> "Pipeline":
+        """Buffer a ``ZREVRANGE`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zrangebyscore(
+        self,
+        name: str,
+        min: str,
+        max: str,
+        withscores: bool = False,
+        offset: Optional[int] = None,
+        count: Optional[int] = None,
+    ) -> "Pipeline":
+        """Buffer a ``ZRANGEBYSCORE`` command.
+
+        Args:
+            name: Sorted-set key name.
+            min: Minimum score (``"-inf"``, ``"(1"`` etc.).
+            max: Maximum score.
+            withscores: If ``True``, include scores.
+            offset: Members to skip (``LIMIT``, needs ``count``).
+            count: Members to return (``LIMIT``, needs ``offset``).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zcount(self, name: str, min: str, max: str) -> "Pipeline":
+        """Buffer a ``ZCOUNT`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zremrangebyscore(self, name: str, min: str, max: str) -> "Pipeline":
+        """Buffer a ``ZREMRANGEBYSCORE`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zremrangebyrank(self, name: str, start: int, stop: int) -> "Pipeline":
+        """Buffer a ``ZREMRANGEBYRANK`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zunionstore(
+        self,
+        dest: str,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+    ) -> "Pipeline":
+        """Buffer a ``ZUNIONSTORE`` command (see :meth:`Redis.zunionstore`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zinterstore(
+        self,
+        dest: str,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+    ) -> "Pipeline":
+        """Buffer a ``ZINTERSTORE`` command (see :meth:`Redis.zinterstore`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zunion(
+        self,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+        withscores: bool = False,
+    ) -> "Pipeline":
+        """Buffer a ``ZUNION`` command (see :meth:`Redis.zunion`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zinter(
+        self,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+        withscores: bool = False,
+    ) -> "Pipeline":
+        """Buffer a ``ZINTER`` command (see :meth:`Redis.zinter`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zdiff(self, keys: Sequence[str], withscores: bool = False) -> "Pipeline":
+        """Buffer a ``ZDIFF`` command (see :meth:`Redis.zdiff`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zrangestore(
+        self,
+        dest: str,
+        name: str,
+        start: str | int | float,
+        end: str | int | float,
+        byscore: bool = False,
+        bylex: bool = False,
+        desc: bool = False,
+        offset: Optional[int] = None,
+        num: Optional[int] = None,
+    ) -> "Pipeline":
+        """Buffer a ``ZRANGESTORE`` command (see :meth:`Redis.zrangestore`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zpopmin(self, name: str, count: Optional[int] = None) -> "Pipeline":
+        """Buffer a ``ZPOPMIN`` command (see :meth:`Redis.zpopmin`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zpopmax(self, name: str, count: Optional[int] = None) -> "Pipeline":
+        """Buffer a ``ZPOPMAX`` command (see :meth:`Redis.zpopmax`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zmpop(
+        self,
+        keys: Sequence[str],
+        min: bool = False,
+        max: bool = False,
+        count: Optional[int] = None,
+    ) -> "Pipeline":
+        """Buffer a ``ZMPOP`` command (see :meth:`Redis.zmpop`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zrandmember(self, name: str, count: Optional[int] = None, withscores: bool = False) -> "Pipeline":
+        """Buffer a ``ZRANDMEMBER`` command (see :meth:`Redis.zrandmember`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def zmscore(self, name: str, *members: str) -> "Pipeline":
+        """Buffer a ``ZMSCORE`` command (see :meth:`Redis.zmscore`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    # ── Geo ─────────────────────────────────────────────────────
+
+    def geoadd(
+        self,
+        name: str,
+        values: Sequence[tuple[float, float, str]],
+        nx: bool = False,
+        xx: bool = False,
+        ch: bool = False,
+    ) -> "Pipeline":
+        """Buffer a ``GEOADD`` command (see :meth:`Redis.geoadd`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def geopos(self, name: str, *members: str) -> "Pipeline":
+        """Buffer a ``GEOPOS`` command (see :meth:`Redis.geopos`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def geodist(
+        self, name: str, member1: str, member2: str, unit: Optional[_GeoUnit] = None
+    ) -> "Pipeline":
+        """Buffer a ``GEODIST`` command (see :meth:`Redis.geodist`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def geosearch(
+        self,
+        name: str,
+        member: Optional[str] = None,
+        longitude: Optional[float] = None,
+        latitude: Optional[float] = None,
+        radius: Optional[float] = None,
+        width: Optional[float] = None,
+        height: Optional[float] = None,
+        unit: _GeoUnit = "m",
+        sort: Optional[Literal["ASC", "DESC"]] = None,
+        count: Optional[int] = None,
+        any: bool = False,
+        withcoord: bool = False,
+        withdist: bool = False,
+        withhash: bool = False,
+    ) -> "Pipeline":
+        """Buffer a ``GEOSEARCH`` command (see :meth:`Redis.geosearch`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def geosearchstore(
+        self,
+        dest: str,
+        source: str,
+        member: Optional[str] = None,
+        longitude: Optional[float] = None,
+        latitude: Optional[float] = None,
+        radius: Optional[float] = None,
+        width: Optional[float] = None,
+        height: Optional[float] = None,
+        unit: _GeoUnit = "m",
+        sort: Optional[Literal["ASC", "DESC"]] = None,
+        count: Optional[int] = None,
+        any: bool = False,
+        storedist: bool = False,
+    ) -> "Pipeline":
+        """Buffer a ``GEOSEARCHSTORE`` command (see :meth:`Redis.geosearchstore`).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    # ── Streams ─────────────────────────────────────────────────
+
+    def xadd(
+        self,
+        name: str,
+        fields: dict[_CommandArg, _CommandArg],
+        id: str = "*",
+        maxlen: Optional[int] = None,
+        minid: Optional[str] = None,
+        approximate: bool = True,
+        nomkstream: bool = False,
+        limit: Optional[int] = None,
+    ) -> "Pipeline":
+        """Buffer an ``XADD`` command (see :meth:`Redis.xadd`)."""
+        ...
+
+    def xlen(self, name: str) -> "Pipeline":
+        """Buffer an ``XLEN`` command."""
+        ...
+
+    def xdel(self, name: str, *ids: str) -> "Pipeline":
+        """Buffer an ``XDEL`` command."""
+        ...
+
+    def xtrim(
+        self,
+        name: str,
+        maxlen: Optional[int] = None,
+        minid: Optional[str] = None,
+        approximate: bool = True,
+        limit: Optional[int] = None,
+    ) -> "Pipeline":
+        """Buffer an ``XTRIM`` command (see :meth:`Redis.xtrim`)."""
+        ...
+
+    def xrange(
+        self, name: str, min: str = "-", max: str = "+", count: Optional[int] = None
+    ) -> "Pipeline":
+        """Buffer an ``XRANGE`` command; its reply is a list of entries."""
+        ...
+
+    def xrevrange(
+        self, name: str, max: str = "+", min: str = "-", count: Optional[int] = None
+    ) -> "Pipeline":
+        """Buffer an ``XREVRANGE`` command; its reply is a list of entries."""
+        ...
+
+    def xread(
+        self,
+        streams: dict[str, str],
+        count: Optional[int] = None,
+        block: Optional[int] = None,
+    ) -> "Pipeline":
+        """Buffer an ``XREAD`` command (see :meth:`Redis.xread`)."""
+        ...
+
+    def xgroup_create(
+        self, name: str, groupname: str, id: str = "$", mkstream: bool = False
+    ) -> "Pipeline":
+        """Buffer an ``XGROUP CREATE`` command."""
+        ...
+
+    def xreadgroup(
+        self,
+        groupname: str,
+        consumername: str,
+        streams: dict[str, str],
+        count: Optional[int] = None,
+        block: Optional[int] = None,
+        noack: bool = False,
+    ) -> "Pipeline":
+        """Buffer an ``XREADGROUP`` command (see :meth:`Redis.xreadgroup`)."""
+        ...
+
+    def xack(self, name: str, groupname: str, *ids: str) -> "Pipeline":
+        """Buffer an ``XACK`` command."""
+        ...
+
+    def xpending(self, name: str, groupname: str) -> "Pipeline":
+        """Buffer an ``XPENDING`` summary (see :meth:`Redis.xpending`)."""
+        ...
+
+    def xclaim(
+        self,
+        name: str,
+        groupname: str,
+        consumername: str,
+        min_idle_time: int,
+        message_ids: Sequence[str],
+        idle: Optional[int] = None,
+        time: Optional[int] = None,
+        retrycount: Optional[int] = None,
+        force: bool = False,
+        justid: bool = False,
+    ) -> "Pipeline":
+        """Buffer an ``XCLAIM`` command (see :meth:`Redis.xclaim`)."""
+        ...
+
+    # ── FalkorDB / Graph ────────────────────────────────────────
+
+    def graph_query(
+        self, graph: str, query: str, timeout: Optional[int] = None
+    ) -> "Pipeline":
+        """Buffer a ``GRAPH.QUERY`` command.
+
+        Args:
+            graph: The graph key name.
+            query: A Cypher query string.
+            timeout: Optional query timeout in milliseconds.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def graph_ro_query(
+        self, graph: str, query: str, timeout: Optional[int] = None
+    ) -> "Pipeline":
+        """Buffer a ``GRAPH.RO_QUERY`` command.
+
+        Args:
+            graph: The graph key name.
+            query: A Cypher query string.
+            timeout: Optional query timeout in milliseconds.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def graph_delete(self, graph: str) -> "Pipeline":
+        """Buffer a ``GRAPH.DELETE`` command.
+
+        Args:
+            graph: The graph key name.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def graph_list(self) -> "Pipeline":
+        """Buffer a ``GRAPH.LIST`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def graph_explain(self, graph: str, query: str) -> "Pipeline":
+        """Buffer a ``GRAPH.EXPLAIN`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def graph_profile(self, graph: str, query: str) -> "Pipeline":
+        """Buffer a ``GRAPH.PROFILE`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def graph_config(self, action: str, name: str, value: Optional[str] = None) -> "Pipeline":
+        """Buffer a ``GRAPH.CONFIG`` command.
+
+        Args:
+            action: ``"GET"`` or ``"SET"``.
+            name: The configuration parameter name.
+            value: Value to set (required for SET).
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    # ── Server ──────────────────────────────────────────────────
+
+    def flushdb(self, mode: Literal["async", "sync"] | None = None, *, confirm: bool = False) -> "Pipeline":
+        """Buffer a ``FLUSHDB`` command (see :meth:`Redis.flushdb`).
+
+        Returns:
+            ``self`` for chaining.
+
+        Raises:
+            ValueError: If the flush was not confirmed, or ``mode`` is not
+                ``"async"`` or ``"sync"``.
+        """
+        ...
+
+    def flushall(self, mode: Literal["async", "sync"] | None = None, *, confirm: bool = False) -> "Pipeline":
+        """Buffer a ``FLUSHALL`` command (see :meth:`Redis.flushall`).
+
+        Returns:
+            ``self`` for chaining.
+
+        Raises:
+            ValueError: If the flush was not confirmed, or ``mode`` is not
+                ``"async"`` or ``"sync"``.
+        """
+        ...
+
+    def dbsize(self) -> "Pipeline":
+        """Buffer a ``DBSIZE`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def echo(self, message: str) -> "Pipeline":
+        """Buffer an ``ECHO`` command.
+
+        Args:
+            message: Message string.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def publish(self, channel: str, message: str) -> "Pipeline":
+        """Buffer a ``PUBLISH`` command.
+
+        Args:
+            channel: Channel name.
+            message: Message to publish.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def time(self) -> "Pipeline":
+        """Buffer a ``TIME`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def info(self, section: Optional[str] = None) -> "Pipeline":
+        """Buffer an ``INFO`` command.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+    def config_get(self, pattern: str = "*") -> "Pipeline":
+        """Buffer a ``CONFIG GET`` command (see :meth:`Redis.config_get`).
+
+        Its reply becomes a ``{name: value}`` dict.
+
+        Returns:
+            ``self`` for chaining.
+        """
+        ...
+
+
+class AsyncRedis:
+    """An asyncio Redis client backed by a connection pool.
+
+    Takes the same arguments as :class:`Redis`. Every command method is a
+    coroutine: the I/O runs on pyrsedis' internal Tokio runtime and never
+    blocks the event loop. :meth:`Redis.dedicated_connection` and
+    :meth:`Redis.pubsub` have no async counterpart yet.
+
+    Example:
+        >>> r = AsyncRedis()
+        >>> await r.set("key", "value")
+        True
+        >>> await r.get("key")
+        'value'
+    """
+
+    def __init__(
+        self,
+        host: str = "127.0.0.1",
+        port: int = 6379,
+        db: int = 0,
+        password: Optional[str] = None,
+        username: Optional[str] = None,
+        pool_size: int = 8,
+        connect_timeout_ms: int = 5000,
+        read_timeout_ms: int = 30000,
+        idle_timeout_ms: int = 300000,
+        max_buffer_size: int = 67108864,
+        decode_responses: bool = True,
+        retry_unsafe: bool = False,
+        serializer: Optional[Literal["msgpack"]] = None,
+        cluster: Optional[Sequence[tuple[str, int]]] = None,
+        resp3_types: bool = False,
+        sentinels: Optional[Sequence[tuple[str, int]]] = None,
+        master_name: Optional[str] = None,
+        sentinel_retries: int = 3,
+        sentinel_retry_backoff_ms: int = 100,
+        ssl: bool = False,
+        ssl_ca_certs: Optional[str] = None,
+        ssl_certfile: Optional[str] = None,
+        ssl_keyfile: Optional[str] = None,
+        ssl_server_hostname: Optional[str] = None,
+        ssl_insecure_skip_verify: bool = False,
+        slot_refresh_interval_ms: int = 30000,
+        unix_socket_path: Optional[str] = None,
+        protocol: Literal[2, 3] = 2,
+        protocol_fallback: bool = True,
+        encryption: Optional["Encryption"] = None,
+        health_check_interval_ms: int = 0,
+        reserved_connections: int = 0,
+        min_idle: int = 0,
+        max_lifetime_ms: int = 0,
+        pool_timeout_ms: int = 0,
+        allow_cross_slot: bool = False,
+        capture_frames: int = 0,
+        address_remap: Optional[dict[str, str] | Callable[[str], Optional[str]]] = None,
+        read_preference: str = "primary",
+        keyless_routing: "str | dict[str, str]" = "first",
+        allow_destructive: bool = False,
+        sentinel_username: Optional[str] = None,
+        sentinel_password: Optional[str] = None,
+        strict_numbers: bool = False,
+    ) -> None:
+        """Create a new asyncio client. Arguments match :class:`Redis`,
+        except that client-side caching is not available."""
+        ...
+
+    @staticmethod
+    def from_url(
+        url: str,
+        pool_size: int = 8,
+        connect_timeout_ms: int = 5000,
+        read_timeout_ms: int = 30000,
+        idle_timeout_ms: int = 300000,
+        decode_responses: bool = True,
+        retry_unsafe: bool = False,
+        serializer: Optional[Literal["msgpack"]] = None,
+        resp3_types: bool = False,
+        sentinel_retries: int = 3,
+        sentinel_retry_backoff_ms: int = 100,
+        ssl_ca_certs: Optional[str] = None,
+        ssl_certfile: Optional[str] = None,
+        ssl_keyfile: Optional[str] = None,
+        ssl_server_hostname: Optional[str] = None,
+        ssl_insecure_skip_verify: bool = False,
+        slot_refresh_interval_ms: int = 30000,
+        protocol: Literal[2, 3] = 2,
+        protocol_fallback: bool = True,
+        encryption: Optional["Encryption"] = None,
+        health_check_interval_ms: int = 0,
+        reserved_connections: int = 0,
+        min_idle: int = 0,
+        max_lifetime_ms: int = 0,
+        pool_timeout_ms: int = 0,
+        allow_cross_slot: bool = False,
+        capture_frames: int = 0,
+        address_remap: Optional[dict[str, str] | Callable[[str], Optional[str]]] = None,
+        read_preference: str = "primary",
+        keyless_routing: "str | dict[str, str]" = "first",
+        allow_destructive: bool = False,
+        strict_numbers: bool = False,
+    ) -> "AsyncRedis":
+        """Create an asyncio client from a URL (see :meth:`Redis.from_url`)."""
+        ...
+
+    def pipeline(self, transaction: bool = False, max_bytes: Optional[int] = None) -> "AsyncPipeline":
+        """Create a pipeline whose :meth:`AsyncPipeline.execute` is awaitable.
+
+        With ``transaction=True`` the commands run inside ``MULTI``/``EXEC``
+        (see :meth:`Redis.pipeline`). Going past ``max_bytes`` raises
+        :class:`PipelineTooLargeError`.
+        """
+        ...
+
+    # ── Core ──────────────────────────────────────────────────────
+
+    async def execute_command(
+        self,
+        *args: _CommandArg,
+        timeout_ms: int | None = None,
+        expected_response_size: int | None = None,
+        route: "str | tuple[str, str | int] | None" = None,
+        node: str | None = None,
+    ) -> Any:
+        """Execute a raw Redis command (see :meth:`Redis.execute_command`)."""
+        ...
+
+    async def try_execute_command(self, *args: _CommandArg, timeout_ms: int = 0) -> Any:
+        """Execute a command only if a pooled connection is free for it
+        (see :meth:`Redis.try_execute_command`)."""
+        ...
+
+    async def execute_on_all_nodes(self, *args: str) -> "NodeResults":
+        """Execute a command on every node (see :meth:`Redis.execute_on_all_nodes`)."""
+        ...
+
+    async def execute_many(self, commands: Sequence[Sequence[str]]) -> list[str | bytes | _GeoMatch]:
+        """Execute a batch of commands in a single round-trip."""
+        ...
+
+    # ── String ────────────────────────────────────────────────────
+
+    async def ping(self) -> bool:
+        """Ping the Redis server."""
+        ...
+
+    async def set(
+        self,
+        name: str | bytes,
+        value: Any,
+        ex: Optional[int] = None,
+        px: Optional[int] = None,
+        nx: bool = False,
+        xx: bool = False,
+    ) -> Optional[bool]:
+        """Set the string value of a key."""
+        ...
+
+    async def get(self, name: str | bytes) -> Any:
+        """Get the value of a key."""
+        ...
+
+    async def delete(self, *names: str) -> int:
+        """Delete one or more keys."""
+        ...
+
+    async def exists(self, *names: str) -> int:
+        """Check if one or more keys exist."""
+        ...
+
+    async def expire(self, name: str, seconds: int, nx: bool = False, xx: bool = False, gt: bool = False, lt: bool = False) -> int:
+        """Set a timeout on a key."""
+        ...
+
+    async def ttl(self, name: str, *, timeout: float | None = None) -> int:
+        """Get the remaining time-to-live of a key in seconds."""
+        ...
+
+    async def exists_many(self, keys: Sequence[str], chunk_size: int = 1000) -> dict[str, bool]:
+        """Check which of many keys exist."""
+        ...
+
+    async def ttl_many(self, keys: Sequence[str], chunk_size: int = 1000) -> dict[str, int]:
+        """Get the TTL of many keys."""
+        ...
+
+    async def expire_many(self, mapping: dict[str, int], chunk_size: int = 1000) -> dict[str, bool]:
+        """Set the expiry of many keys."""
+        ...
+
+    async def persist_many(self, keys: Sequence[str], chunk_size: int = 1000) -> dict[str, bool]:
+        """Remove the expiry of many keys."""
+        ...
+
+    async def purge_expiring(self, pattern: str, older_than_ttl: int, count: int = 1000, pause_ms: int = 10) -> int:
+        """Unlink the keys matching a pattern that are about to expire."""
+        ...
+
+    async def refresh_ttl_if_below(self, keys: Sequence[str], min_ttl: int, new_ttl: int) -> int:
+        """Extend the TTL of the given keys that are about to expire."""
+        ...
+
+    async def load_hash_rows(
+        self,
+        prefix: str,
+        rows: Iterable[Any],
+        key_field: Optional[str] = None,
+        chunk_size: int = 1000,
+        progress: Optional[Callable[[int], Any]] = None,
+    ) -> int:
+        """Load rows into one hash per row."""
+        ...
+
+    async def load_zset(
+        self,
+        name: str,
+        mapping: dict[Any, float] | Iterable[tuple[Any, float]],
+        chunk_size: int = 1000,
+        progress: Optional[Callable[[int], Any]] = None,
+    ) -> int:
+        """Load members into a sorted set."""
+        ...
+
+    async def incr(self, name: str, *, timeout: float | None = None) -> int:
+        """Increment the integer value of a key by one."""
+        ...
+
+    async def decr(self, name: str, *, timeout: float | None = None) -> int:
+        """Decrement the integer value of a key by one."""
+        ...
+
+    async def incrby(self, name: str, amount: int) -> int:
+        """Increment the integer value of a key by ``amount``."""
+        ...
+
+    async def mget(
+        self, *names: str, expected_response_size: int | None = None
+    ) -> list[Optional[str | bytes]]:
+        """Get the values of multiple keys (see :meth:`Redis.mget`)."""
+        ...
+
+    async def get_floats(self, keys: Sequence[str]) -> Any:
+        """Get the values of multiple keys as a ``numpy.float64`` array."""
+        ...
+
+    async def mset(self, mapping: dict[str, str] | Iterable[tuple[str, str]]) -> bool:
+        """Set multiple keys to multiple values."""
+        ...
+
+    # ── Hash ──────────────────────────────────────────────────────
+
+    async def hset(
+        self,
+        name: str,
+        key: Optional[str] = None,
+        value: Optional[str] = None,
+        mapping: Optional[dict[str, str] | Iterable[tuple[str, str]]] = None,
+        *,
+        timeout: float | None = None,
+    ) -> int:
+        """Set one or more hash fields (see :meth:`Redis.hset`)."""
+        ...
+
+    async def hget(self, name: str, key: str, *, timeout: float | None = None) -> Optional[str | bytes]:
+        """Get the value of a hash field."""
+        ...
+
+    async def hgetall(self, name: str, *, timeout: float | None = None) -> dict[Any, Any]:
+        """Get all fields and values of a hash as a dict."""
+        ...
+
+    async def hgetall_floats(self, name: str) -> tuple[list[str], Any]:
+        """Get all fields of a hash with their values as floats."""
+        ...
+
+    async def hdel(self, name: str, *keys: str) -> int:
+        """Delete one or more hash fields."""
+        ...
+
+    async def hexists(self, name: str, key: str, *, timeout: float | None = None) -> int:
+        """Check if a hash field exists."""
+        ...
+
+    async def hkeys(self, name: str, *, timeout: float | None = None) -> list[str | bytes]:
+        """Get all field names in a hash."""
+        ...
+
+    async def hvals(self, name: str, *, timeout: float | None = None) -> list[str | bytes]:
+        """Get all values in a hash."""
+        ...
+
+    async def hlen(self, name: str, *, timeout: float | None = None) -> int:
+        """Get the number of fields in a hash."""
+        ...
+
+    async def hincrby(self, name: str, key: str, amount: int) -> int:
+        """Increment the integer value of a hash field by ``amount``."""
+        ...
+
+    async def hincrbyfloat(self, name: str, key: str, amount: float) -> Any:
+        """Increment the float value of a hash field by ``amount``."""
+        ...
+
+    async def hsetnx(self, name: str, key: str, value: str) -> int:
+        """Set a hash field only if it does not already exist."""
+        ...
+
+    async def hmget(self, name: str, *keys: str) -> list[Optional[str | bytes]]:
+        """Get the values of multiple hash fields."""
+        ...
+
+    async def hrandfield(self, name: str, count: Optional[int] = None, withvalues: bool = False) -> Any:
+        """Return random fields of a hash (see :meth:`Redis.hrandfield`)."""
+        ...
+
+    # ── List ──────────────────────────────────────────────────────
+
+    async def lpush(self, name: str, *values: str) -> int:
+        """Prepend one or more values to a list."""
+        ...
+
+    async def rpush(self, name: str, *values: str) -> int:
+        """Append one or more values to a list."""
+        ...
+
+    async def lrange(self, name: str, start: int, stop: int) -> list[str | bytes]:
+        """Get a range of elements from a list."""
+        ...
+
+    async def llen(self, name: str, *, timeout: float | None = None) -> int:
+        """Get the length of a list."""
+        ...
+
+    async def lpop(self, name: str, count: Optional[int] = None) -> Any:
+        """Remove and return the first element(s) of a list."""
+        ...
+
+    async def rpop(self, name: str, count: Optional[int] = None) -> Any:
+        """Remove and return the last element(s) of a list."""
+        ...
+
+    async def lindex(self, name: str, index: int) -> Optional[str | bytes]:
+        """Get an element from a list by its index."""
+        ...
+
+    async def lset(self, name: str, index: int, value: str) -> Any:
+        """Set the value of an element in a list by its index."""
+        ...
+
+    async def lrem(self, name: str, count: int, value: str) -> int:
+        """Remove occurrences of a value from a list."""
+        ...
+
+    async def lpos(
+        self,
+        name: str,
+        value: str,
+        rank: Optional[int] = None,
+        count: Optional[int] = None,
+        maxlen: Optional[int] = None,
+    ) -> Optional[int] | list[int]:
+        """Find the index of an element in a list."""
+        ...
+
+    async def linsert(self, name: str, where: Literal["BEFORE", "AFTER"], refvalue: str, value: str) -> int:
+        """Insert an element before or after another element in a list."""
+        ...
+
+    async def ltrim(self, name: str, start: int, end: int, *, timeout: float | None = None) -> bool:
+        """Trim a list to the elements between ``start`` and ``end``."""
+        ...
+
+    async def rpoplpush(self, src: str, dst: str, *, timeout: float | None = None) -> Optional[str | bytes]:
+        """Pop the last element of ``src`` and push it onto ``dst``."""
+        ...
+
+    async def lmove(
+        self,
+        src: str,
+        dst: str,
+        wherefrom: Literal["LEFT", "RIGHT"] = "LEFT",
+        whereto: Literal["LEFT", "RIGHT"] = "RIGHT",
+    ) -> Optional[str | bytes]:
+        """Move an element between lists (see :meth:`Redis.lmove`)."""
+        ...
+
+    async def lmpop(
+        self,
+        keys: Sequence[str],
+        direction: Literal["LEFT", "RIGHT"] = "LEFT",
+        count: Optional[int] = None,
+    ) -> Optional[list[Any]]:
+        """Pop elements from the first non-empty list (see :meth:`Redis.lmpop`)."""
+        ...
+
+    # ── Blocking ──────────────────────────────────────────────────
+
+    async def blpop(self, keys: str | Sequence[str], timeout: float = 0) -> Optional[list[Any]]:
+        """Pop the first element of the first non-empty list, blocking."""
+        ...
+
+    async def brpop(self, keys: str | Sequence[str], timeout: float = 0) -> Optional[list[Any]]:
+        """Like :meth:`blpop`, popping from the tail."""
+        ...
+
+    async def brpoplpush(self, src: str, dst: str, timeout: float = 0) -> Any:
+        """Pop the last element of ``src`` and push it onto ``dst``, blocking."""
+        ...
+
+    async def blmove(
+        self,
+        src: str,
+        dst: str,
+        wherefrom: Literal["LEFT", "RIGHT"] = "LEFT",
+        whereto: Literal["LEFT", "RIGHT"] = "RIGHT",
+        timeout: float = 0,
+    ) -> Any:
+        """Move an element between lists, blocking."""
+        ...
+
+    async def bzpopmin(self, keys: str | Sequence[str], timeout: float = 0) -> Optional[tuple[Any, Any, float]]:
+        """Pop the lowest-scored member of the first non-empty sorted set, blocking."""
+        ...
+
+    async def bzpopmax(self, keys: str | Sequence[str], timeout: float = 0) -> Optional[tuple[Any, Any, float]]:
+        """Like :meth:`bzpopmin`, popping the highest-scored member."""
+        ...
+
+    # ── Set ───────────────────────────────────────────────────────
+
+    async def sadd(self, name: str, *members: str) -> int:
+        """Add one or more members to a set."""
+        ...
+
+    async def smembers(self, name: str, *, timeout: float | None = None) -> Any:
+        """Get all members of a set."""
+        ...
+
+    async def scard(self, name: str, *, timeout: float | None = None) -> int:
+        """Get the cardinality (number of members) of a set."""
+        ...
+
+    async def srem(self, name: str, *members: str) -> int:
+        """Remove one or more members from a set."""
+        ...
+
+    async def sismember(self, name: str, value: str, *, timeout: float | None = None) -> int:
+        """Check if a value is a member of a set."""
+        ...
+
+    async def spop(self, name: str, count: Optional[int] = None) -> Any:
+        """Remove and return one or more random members from a set."""
+        ...
+
+    async def sinter(self, *names: str) -> Any:
+        """Return the intersection of one or more sets."""
+        ...
+
+    async def sunion(self, *names: str) -> Any:
+        """Return the union of one or more sets."""
+        ...
+
+    async def sdiff(self, *names: str) -> Any:
+        """Return the difference of the first set with all successive sets."""
+        ...
+
+    async def sinterstore(self, dest: str, *keys: str) -> int:
+        """Store the intersection of one or more sets in ``dest``."""
+        ...
+
+    async def sunionstore(self, dest: str, *keys: str) -> int:
+        """Store the union of one or more sets in ``dest``."""
+        ...
+
+    async def sdiffstore(self, dest: str, *keys: str) -> int:
+        """Store the difference of the first set with all successive sets in ``dest``."""
+        ...
+
+    async def smove(self, src: str, dst: str, value: str) -> int:
+        """Move a member from one set to another."""
+        ...
+
+    async def smismember(self, name: str, *values: str) -> list[int]:
+        """Check which of ``values`` are members of a set."""
+        ...
+
+    async def srandmember(self, name: str, count: Optional[int] = None) -> Any:
+        """Return random members of a set (see :meth:`Redis.srandmember`)."""
+        ...
+
+    async def sintercard(self, keys: Sequence[str], limit: Optional[int] = None) -> int:
+        """Count the members of the intersection of ``keys`` (see :meth:`Redis.sintercard`)."""
+        ...
+
+    # ── Sorted set ────────────────────────────────────────────────
+
+    async def zadd(
+        self,
+        name: str,
+        mapping: dict[str, float] | Iterable[tuple[str, float]],
+        nx: bool = False,
+        xx: bool = False,
+        gt: bool = False,
+        lt: bool = False,
+        ch: bool = False,
+    ) -> int:
+        """Add one or more members to a sorted set, or update scores."""
+        ...
+
+    async def zrem(self, name: str, *members: str) -> int:
+        """Remove one or more members from a sorted set."""
+        ...
+
+    async def zscore(self, name: str, member: str, *, timeout: float | None = None) -> Optional[float]:
+        """Get the score of a member in a sorted set."""
+        ...
+
+    async def zrank(self, name: str, member: str, *, timeout: float | None = None) -> Optional[int]:
+        """Get the rank (0-based) of a member in a sorted set."""
+        ...
+
+    async def zcard(self, name: str, *, timeout: float | None = None) -> int:
+        """Get the number of members in a sorted set."""
+        ...
+
+    async def zcount(self, name: str, min: str, max: str) -> int:
+        """Count members in a sorted set with scores within the given range."""
+        ...
+
+    async def zincrby(self, name: str, amount: float, member: str) -> Any:
+        """Increment the score of a member in a sorted set."""
+        ...
+
+    async def zrange(
+        self, name: str, start: int, stop: int, withscores: bool = False
+    ) -> Any:
+        """Return a range of members from a sorted set by index."""
+        ...
+
+    async def zrevrange(
+        self, name: str, start: int, stop: int, withscores: bool = False
+    ) -> Any:
+        """Return a range of members from a sorted set by index, reversed."""
+        ...
+
+    async def zrangebyscore(
+        self,
+        name: str,
+        min: str,
+        max: str,
+        withscores: bool = False,
+        offset: Optional[int] = None,
+        count: Optional[int] = None,
+    ) -> Any:
+        """Return members with scores between ``min`` and ``max``."""
+        ...
+
+    async def zremrangebyscore(self, name: str, min: str, max: str) -> int:
+        """Remove members with scores between ``min`` and ``max``."""
+        ...
+
+    async def zremrangebyrank(self, name: str, start: int, stop: int) -> int:
+        """Remove members with rank between ``start`` and ``stop``."""
+        ...
+
+    async def zunionstore(
+        self,
+        dest: str,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+    ) -> int:
+        """Store the union of sorted sets in ``dest`` (see :meth:`Redis.zunionstore`)."""
+        ...
+
+    async def zinterstore(
+        self,
+        dest: str,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+    ) -> int:
+        """Store the intersection of sorted sets in ``dest``."""
+        ...
+
+    async def zunion(
+        self,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+        withscores: bool = False,
+    ) -> list[str | bytes | _GeoMatch]:
+        """Return the union of sorted sets (see :meth:`Redis.zunion`)."""
+        ...
+
+    async def zinter(
+        self,
+        keys: Sequence[str],
+        weights: Optional[Sequence[float]] = None,
+        aggregate: Optional[Literal["SUM", "MIN", "MAX"]] = None,
+        withscores: bool = False,
+    ) -> list[Any]:
+        """Return the intersection of sorted sets."""
+        ...
+
+    async def zdiff(self, keys: Sequence[str], withscores: bool = False) -> list[Any]:
+        """Return the members of the first sorted set that are in none of the others."""
+        ...
+
+    async def zrangestore(
+        self,
+        dest: str,
+        name: str,
+        start: str | int | float,
+        end: str | int | float,
+        byscore: bool = False,
+        bylex: bool = False,
+        desc: bool = False,
+        offset: Optional[int] = None,
+        num: Optional[int] = None,
+    ) -> int:
+        """Store a range of ``name`` in ``dest`` (see :meth:`Redis.zrangestore`)."""
+        ...
+
+    async def zpopmin(self, name: str, count: Optional[int] = None) -> list[tuple[Any, float]]:
+        """Remove and return the lowest-scored members as ``(member, score)`` tuples."""
+        ...
+
+    async def zpopmax(self, name: str, count: Optional[int] = None) -> list[tuple[Any, float]]:
+        """Like :meth:`zpopmin`, popping the highest-scored members."""
+        ...
+
+    async def zmpop(
+        self,
+        keys: Sequence[str],
+        min: bool = False,
+        max: bool = False,
+        count: Optional[int] = None,
+    ) -> Optional[tuple[Any, list[tuple[Any, float]]]]:
+        """Pop members from the first non-empty sorted set (see :meth:`Redis.zmpop`)."""
+        ...
+
+    async def zrandmember(self, name: str, count: Optional[int] = None, withscores: bool = False) -> Any:
+        """Return random members of a sorted set (see :meth:`Redis.zrandmember`)."""
+        ...
+
+    async def zmscore(self, name: str, *members: str) -> list[Optional[float]]:
+        """Get the scores of several members as floats, ``None`` for a missing member."""
+        ...
+
+    # ── Geo ─────────────────────────────────────────────────────
+
+    async def geoadd(
+        self,
+        name: str,
+        values: Sequence[tuple[float, float, str]],
+        nx: bool = False,
+        xx: bool = False,
+        ch: bool = False,
+    ) -> int:
+        """Add members with their positions to a geo index (see :meth:`Redis.geoadd`)."""
+        ...
+
+    async def geopos(self, name: str, *members: str) -> list[Optional[tuple[float, float]]]:
+        """Get the positions of members as ``(longitude, latitude)`` tuples."""
+        ...
+
+    async def geodist(
+        self, name: str, member1: str, member2: str, unit: Optional[_GeoUnit] = None
+    ) -> Optional[float]:
+        """Get the distance between two members as a float."""
+        ...
+
+    async def geosearch(
+        self,
+        name: str,
+        member: Optional[str] = None,
+        longitude: Optional[float] = None,
+        latitude: Optional[float] = None,
+        radius: Optional[float] = None,
+        width: Optional[float] = None,
+        height: Optional[float] = None,
+        unit: _GeoUnit = "m",
+        sort: Optional[Literal["ASC", "DESC"]] = None,
+        count: Optional[int] = None,
+        any: bool = False,
+        withcoord: bool = False,
+        withdist: bool = False,
+        withhash: bool = False,
+    ) -> list[str | bytes | _GeoMatch]:
+        """Find the members within an area (see :meth:`Redis.geosearch`)."""
+        ...
+
+    async def geosearchstore(
+        self,
+        dest: str,
+        source: str,
+        member: Optional[str] = None,
+        longitude: Optional[float] = None,
+        latitude: Optional[float] = None,
+        radius: Optional[float] = None,
+        width: Optional[float] = None,
+        height: Optional[float] = None,
+        unit: _GeoUnit = "m",
+        sort: Optional[Literal["ASC", "DESC"]] = None,
+        count: Optional[int] = None,
+        any: bool = False,
+        storedist: bool = False,
+    ) -> int:
+        """Store the members a search finds in ``source`` into ``dest``."""
+        ...
+
+    # ── Key ───────────────────────────────────────────────────────
+
+    async def rename(self, src: str, dst: str, *, timeout: float | None = None) -> Any:
+        """Rename a key."""
+        ...
+
+    async def expiretime(self, name: str, *, timeout: float | None = None) -> int:
+        """Get the absolute Unix time at which a key expires, in seconds."""
+        ...
+
+    async def pexpiretime(self, name: str, *, timeout: float | None = None) -> int:
+        """Get the absolute Unix time at which a key expires, in milliseconds."""
+        ...
+
+    async def persist(self, name: str, *, timeout: float | None = None) -> int:
+        """Remove the expiry from a key."""
+        ...
+
+    async def pexpire(self, name: str, millis: int, nx: bool = False, xx: bool = False, gt: bool = False, lt: bool = False) -> int:
+        """Set a timeout on a key in milliseconds."""
+        ...
+
+    async def pttl(self, name: str) -> int:
+        """Get the remaining TTL of a key in milliseconds."""
+        ...
+
+    async def scan(
+        self,
+        cursor: int = 0,
+        match_pattern: Optional[str] = None,
+        count: Optional[int] = None,
+    ) -> list[Any]:
+        """Incrementally iterate the keyspace."""
+        ...
+
+    async def hscan(
+        self,
+        name: str,
+        cursor: int = 0,
+        match_pattern: Optional[str] = None,
+        count: Optional[int] = None,
+    ) -> list[Any]:
+        """Incrementally iterate the fields of a hash (see :meth:`Redis.hscan`)."""
+        ...
+
+    async def expireat(self, name: str, when: int, nx: bool = False, xx: bool = False, gt: bool = False, lt: bool = False) -> int:
+        """Set an absolute Unix-timestamp expiry on a key."""
+        ...
+
+    async def dump(self, name: str) -> Optional[str | bytes]:
+        """Return a serialised version of the value stored at a key."""
+        ...
+
+    async def unlink(self, *names: str) -> int:
+        """Unlink (async-delete) one or more keys."""
+        ...
+
+    async def type(self, name: str, *, timeout: float | None = None) -> str:
+        """Return the type of the value stored at a key."""
+        ...
+
+    async def keys(self, pattern: str = "*") -> list[str | bytes]:
+        """Find all keys matching a glob-style pattern."""
+        ...
+
+    # ── String (additional) ───────────────────────────────────────
+
+    async def append(self, name: str, value: str, *, timeout: float | None = None) -> int:
+        """Append a value to a key."""
+        ...
+
+    async def strlen(self, name: str, *, timeout: float | None = None) -> int:
+        """Get the length of the string stored at a key."""
+        ...
+
+    async def getrange(self, name: str, start: int, end: int) -> str | bytes:
+        """Get a substring of the string stored at a key."""
+        ...
+
+    async def getset(self, name: str, value: str) -> Optional[str | bytes]:
+        """Set a key and return its old value."""
+        ...
+
+    async def getdel(self, name: str) -> Optional[str | bytes]:
+        """Get the value of a key and delete it."""
+        ...
+
+    async def getex(
+        self,
+        name: str,
+        ex: Optional[int] = None,
+        px: Optional[int] = None,
+        exat: Optional[int] = None,
+        pxat: Optional[int] = None,
+        persist: bool = False,
+    ) -> Optional[str | bytes]:
+        """Get the value of a key and set or clear its expiration."""
+        ...
+
+    async def setnx(self, name: str, value: str, *, timeout: float | None = None) -> int:
+        """Set a key only if it does not already exist."""
+        ...
+
+    async def setex(self, name: str, seconds: int, value: str) -> Any:
+        """Set a key with an expiration in seconds."""
+        ...
+
+    async def incrbyfloat(self, name: str, amount: float) -> Any:
+        """Increment the floating-point value of a key by ``amount``."""
+        ...
+
+    async def decrby(self, name: str, amount: int) -> int:
+        """Decrement the integer value of a key by ``amount``."""
+        ...
+
+    # ── Scripting ─────────────────────────────────────────────────
+
+    async def eval(self, script: str, numkeys: int, *args: str) -> Any:
+        """Evaluate a Lua script server-side."""
+        ...
+
+    async def evalsha(self, sha: str, numkeys: int, *args: str) -> Any:
+        """Evaluate a cached Lua script by its SHA1 digest."""
+        ...
+
+    async def script_load(self, script: str) -> "str | NodeResults":
+        """Load a Lua script into the server's script cache; on every master in cluster mode."""
+        ...
+
+    async def function_load(self, code: str, replace: bool = False) -> "str | NodeResults":
+        """Load a library of functions; on every master in cluster mode."""
+        ...
+
+    async def function_list(self, library: Optional[str] = None, with_code: bool = False) -> list[Any]:
+        """List the loaded libraries and their functions."""
+        ...
+
+    async def function_dump(self) -> bytes:
+        """Serialize every loaded library."""
+        ...
+
+    async def function_restore(self, payload: bytes, policy: Optional[str] = None) -> "bool | NodeResults":
+        """Restore libraries from a :meth:`function_dump` payload; on every master in cluster mode."""
+        ...
+
+    async def fcall(self, function: str, numkeys: int, *args: Any) -> Any:
+        """Call a function loaded with :meth:`function_load`."""
+        ...
+
+    async def fcall_ro(self, function: str, numkeys: int, *args: Any) -> Any:
+        """Call a read-only function."""
+        ...
+
+    # ── FalkorDB / Graph ──────────────────────────────────────────
+
+    async def graph_query(
+        self,
+        graph: str,
+        query: str,
+        timeout: Optional[int] = None,
+        expected_response_size: Optional[int] = None,
+    ) -> Any:
+        """Execute a Cypher query on a FalkorDB graph."""
+        ...
+
+    async def graph_ro_query(
+        self,
+        graph: str,
+        query: str,
+        timeout: Optional[int] = None,
+        expected_response_size: Optional[int] = None,
+    ) -> Any:
+        """Execute a read-only Cypher query on a FalkorDB graph."""
+        ...
+
+    async def graph_query_result(
+        self,
+        graph: str,
+        query: str,
+        timeout: Optional[int] = None,
+        read_only: bool = False,
+        expected_response_size: Optional[int] = None,
+    ) -> "QueryResult":
+        """Execute a Cypher query and keep the parsed result in Rust."""
+        ...
+
+    async def graph_delete(self, graph: str, *, timeout: float | None = None) -> Any:
+        """Delete a graph and all its data."""
+        ...
+
+    async def graph_list(self, *, timeout: float | None = None) -> list[Any]:
+        """List all graphs in the current database."""
+        ...
+
+    async def graph_explain(self, graph: str, query: str) -> Any:
+        """Return the execution plan for a Cypher query without executing it."""
+        ...
+
+    async def graph_profile(self, graph: str, query: str) -> Any:
+        """Execute a Cypher query and return the execution plan with timings."""
+        ...
+
+    async def graph_slowlog(self, graph: str) -> Any:
+        """Return the slow log for a graph."""
+        ...
+
+    async def graph_config(
+        self, action: str, name: str, value: Optional[str] = None
+    ) -> Any:
+        """Get or set a FalkorDB graph configuration parameter."""
+        ...
+
+    # ── Server ────────────────────────────────────────────────────
+
+    async def select(self, db: int) -> Any:
+        """Switch to a different database."""
+        ...
+
+    async def flushall(self, mode: Literal["async", "sync"] | None = None, *, confirm: bool = False) -> Any:
+        """Delete all keys in all databases; on every master in cluster mode
+        (see :meth:`Redis.flushall`)."""
+        ...
+
+    async def config_get(self, pattern: str = "*") -> dict[Any, Any]:
+        """Get the server configuration parameters matching ``pattern`` as a dict."""
+        ...
+
+    async def config_set(self, name: str, value: str) -> Any:
+        """Set a server configuration parameter; on every master in cluster mode."""
+        ...
+
+    async def flushdb(
+        self,
+        mode: Literal["async", "sync"] | None = None,
+        *,
+        confirm: bool = False,
+        timeout: float | None = None,
+    ) -> Any:
+        """Delete all keys in the current database (see :meth:`Redis.flushdb`)."""
+        ...
+
+    async def randomkey(self) -> Optional[str | bytes]:
+        """Return a random key from the current database."""
+        ...
+
+    async def lastsave(self) -> int:
+        """Return the Unix timestamp of the last successful save."""
+        ...
+
+    async def echo(self, message: str, *, timeout: float | None = None) -> str | bytes:
+        """Echo the given message."""
+        ...
+
+    async def publish(self, channel: str, message: str, *, timeout: float | None = None) -> int:
+        """Publish a message to a Pub/Sub channel."""
+        ...
+
+    async def publish_many(self, messages: Sequence[tuple[str, str]]) -> list[int]:
+        """Publish many messages in a single round-trip, with ``SPUBLISH``
+        in cluster mode (see :meth:`Redis.publish_many`)."""
+        ...
+
+    async def time(self) -> list[Any]:
+        """Return the server time."""
+        ...
+
+    async def time_offset(self, refresh: bool = False) -> float:
+        """Seconds to add to the local clock to get the server's."""
+        ...
+
+    async def server_now(self) -> float:
+        """The server's current time in Unix seconds."""
+        ...
+
+    async def info(self, section: Optional[str] = None) -> Any:
+        """Return information and statistics about the server."""
+        ...
+
+    async def sentinel_masters(self) -> list[dict[str, str]]:
+        """The masters the sentinels monitor (Sentinel mode only)."""
+        ...
+
+    async def sentinel_master(self, name: str) -> dict[str, str]:
+        """The sentinels' state of master ``name``."""
+        ...
+
+    async def sentinel_replicas(self, name: str) -> list[dict[str, str]]:
+        """The replicas of master ``name``."""
+        ...
+
+    async def sentinel_get_master_addr(self, name: str) -> Optional[tuple[str, int]]:
+        """The address of master ``name`` as ``(host, port)``, or ``None``."""
+        ...
+
+    async def cluster_setslot(
+        self,
+        slot: int,
+        state: str,
+        node_id: Optional[str] = None,
+        node: Optional[str] = None,
+    ) -> bool:
+        """Set the migration state of a hash slot (see :meth:`Redis.cluster_setslot`)."""
+        ...
+
+    async def cluster_getkeysinslot(self, slot: int, count: int, node: Optional[str] = None) -> list[Any]:
+        """Up to ``count`` keys of a hash slot (see :meth:`Redis.cluster_getkeysinslot`)."""
+        ...
+
+    async def migrate_slot(
+        self,
+        slot: int,
+        from_node: str,
+        to_node: str,
+        batch: int = 100,
+        timeout_ms: int = 5000,
+        progress: Optional[Callable[[int], Any]] = None,
+    ) -> int:
+        """Move a hash slot and its keys to another master (see :meth:`Redis.migrate_slot`)."""
+        ...
+
+    async def cluster_failover(self, node: str, force: bool = False, takeover: bool = False) -> Any:
+        """Promote a replica to master of its slots (see :meth:`Redis.cluster_failover`)."""
+        ...
+
+    def drain_node(self, addr: str) -> None:
+        """Stop routing reads and key-less commands to a node (see :meth:`Redis.drain_node`)."""
+        ...
+
+    def undrain_node(self, addr: str) -> bool:
+        """Route to a drained node again (see :meth:`Redis.undrain_node`)."""
+        ...
+
+    @property
+    def draining_nodes(self) -> list[str]:
+        """The ``"host:port"`` of the nodes being drained."""
+        ...
+
+    async def dbsize(self, *, timeout: float | None = None) -> int:
+        """Return the number of keys in the current database."""
+        ...
+
+    # ── Connection management ──────────────────────────────────────
+
+    @property
+    def pool_idle_count(self) -> int:
+        """Number of idle connections in the pool."""
+        ...
+
+    @property
+    def pool_available(self) -> int:
+        """Number of available connection slots."""
+        ...
+
+    def pool_stats(self) -> dict[str, Any]:
+        """Connection pool statistics (see :meth:`Redis.pool_stats`)."""
+        ...
+
+    def command_stats(self) -> dict[str, dict[str, float]]:
+        """Reply latency and timeouts by command family (see
+        :meth:`Redis.command_stats`)."""
+        ...
+
+    def reset_command_stats(self) -> None:
+        """Zero the counters behind :meth:`command_stats`."""
+        ...
+
+    async def warmup(self, count: Optional[int] = None) -> int:
+        """Open connections until ``count`` are idle in the pool (see
+        :meth:`Redis.warmup`)."""
+        ...
+
+    @property
+    def protocol_version(self) -> Optional[int]:
+        """RESP protocol version negotiated with the server, or ``None``
+        before the first connection is made."""
+        ...
+
+    def disconnect_all(self) -> None:
+        """Close all pooled connections."""
+        ...
+
+    def recent_events(self, limit: Optional[int] = None) -> list[dict[str, Any]]:
+        """Recent connection lifecycle events, oldest first.
+
+        See :meth:`Redis.recent_events`.
+        """
+        ...
+
+    def set_event_callback(self, callback: Optional[Callable[[dict[str, Any]], None]] = None) -> None:
+        """Call ``callback(event)`` for each lifecycle event as it happens.
+
+        See :meth:`Redis.set_event_callback`. The callback is a plain
+        function, not a coroutine, and runs on a pyrsedis I/O thread.
+        """
+        ...
+
+    def dump_frames(self, limit: Optional[int] = None) -> str:
+        """Hexdump of the raw frames captured with ``capture_frames``.
+
+        See :meth:`Redis.dump_frames`.
+        """
+        ...
+
+    async def aclose(self) -> None:
+        """Close all pooled connections."""
+        ...
+
+    async def __aenter__(self) -> "AsyncRedis": ...
+    async def __aexit__(self, exc_type: Any = None, exc_value: Any = None, traceback: Any = None) -> bool: ...
+    def __repr__(self) -> str: ...
+    def __str__(self) -> str: ...
+
+
+class AsyncPipeline(Pipeline):
+    """A :class:`Pipeline` whose :meth:`execute` is a coroutine.
+
+    Created by :meth:`AsyncRedis.pipeline`. Commands are buffered locally,
+    so adding them never blocks.
+
+    Example:
+        >>> pipe = r.pipeline()
+        >>> pipe.set("a", "1").incr("a")
+        >>> await pipe.execute()
+        [True, 2]
+    """
+
+    async def execute(self) -> list[Any]:  # type: ignore[override]
+        """Send all buffered commands in a single round-trip."""
+        ...
+
+
+class DedicatedConnection:
+    """One pooled connection pinned for exclusive use.
+
+    Created by :meth:`Redis.dedicated_connection`. Commands run in order
+    on the same socket, so connection state (``SELECT``, ``WATCH``,
+    ``CLIENT`` settings) carries over between them.
+    """
+
+    def execute_command(self, *args: _CommandArg) -> Any:
+        """Execute a command on the pinned connection.
+
+        Raises:
+            RedisConnectionError: If the connection was released or broke.
+        """
+        ...
+
+    def client_reply(self, mode: str) -> None:
+        """Control server replies on this connection (``CLIENT REPLY``).
+
+        In ``"OFF"`` mode commands return ``None`` without waiting for a
+        reply; ``"SKIP"`` does so for the next command only. The connection
+        is never returned to the shared pool while replies are suppressed:
+        :meth:`close` switches back ``"ON"`` first and closes the socket if
+        that fails.
+
+        Args:
+            mode: ``"ON"``, ``"OFF"`` or ``"SKIP"``.
+        """
+        ...
+
+    def close(self) -> None:
+        """Reset the connection and return it to the pool.
+
+        Safe to call more than once.
+        """
+        ...
+
+    @property
+    def active(self) -> bool:
+        """Whether the connection is still held."""
+        ...
+
+    def __enter__(self) -> "DedicatedConnection": ...
+    def __exit__(self, exc_type: Any = None, exc_value: Any = None, traceback: Any = None) -> bool: ...
+    def __repr__(self) -> str: ...
+
+
+class PubSub:
+    """A Pub/Sub subscriber on a dedicated pooled connection.
+
+    Created by :meth:`Redis.pubsub`. Subscription commands return
+    immediately; their confirmations arrive as messages. Messages are
+    dicts with ``"type"``, ``"pattern"``, ``"channel"`` and ``"data"`` keys.
+
+    If the connection drops while subscribed, the next read reconnects and
+    re-issues every subscription once before raising; the new
+    confirmations arrive as messages.
+    """
+
+    def subscribe(self, *channels: str) -> None:
+        """Subscribe to one or more channels."""
+        ...
+
+    def psubscribe(self, *patterns: str) -> None:
+        """Subscribe to one or more glob-style channel patterns."""
+        ...
+
+    def unsubscribe(self, *channels: str) -> None:
+        """Unsubscribe from the given channels, or from all when none are given."""
+        ...
+
+    def punsubscribe(self, *patterns: str) -> None:
+        """Unsubscribe from the given patterns, or from all when none are given."""
+        ...
+
+    def get_message(
+        self,
+        timeout: Optional[float] = None,
+        ignore_subscribe_messages: bool = False,
+        pattern_filter: Optional[str | Sequence[str]] = None,
+    ) -> Optional[dict[str, Any]]:
+        """Wait for the next message.
+
+        Frames rejected by the filters are dropped in Rust without
+        creating Python objects.
+
+        Args:
+            timeout: Seconds to wait; ``None`` blocks until a message
+                arrives, ``0`` only returns already-received messages.
+            ignore_subscribe_messages: Drop (un)subscribe confirmations.
+            pattern_filter: Glob pattern (or patterns) a message's channel
+                must match; other data messages are dropped.
+
+        Returns:
+            The message dict, or ``None`` if the timeout expired.
+        """
+        ...
+
+    def listen(self) -> Iterator[dict[str, Any]]:
+        """Iterate over messages while subscribed."""
+        ...
+
+    @property
+    def subscribed(self) -> bool:
+        """Whether any channel or pattern subscription is active."""
+        ...
+
+    @property
+    def channels(self) -> list[str]:
+        """Subscribed channel names."""
+        ...
+
+    @property
+    def patterns(self) -> list[str]:
+        """Subscribed channel patterns."""
+        ...
+
+    def close(self) -> None:
+        """Reset the connection and return it to the pool.
+
+        Safe to call more than once.
+        """
+        ...
+
+    def __iter__(self) -> Iterator[dict[str, Any]]: ...
+    def __next__(self) -> dict[str, Any]: ...
+    def __enter__(self) -> "PubSub": ...
+    def __exit__(self, exc_type: Any = None, exc_value: Any = None, traceback: Any = None) -> bool: ...
+    def __repr__(self) -> str: ...
+
+
+class Encryption:
+    """Value encryption callbacks for :class:`Redis` and :class:`AsyncRedis`.
+
+    Values written by ``set`` pass through ``encrypt`` after the
+    serializer; values read by ``get`` pass through ``decrypt``. Each
+    encrypted value is stored behind a short prefix naming its key id, so
+    old values stay readable after ``key_id`` is rotated.
+
+    Example:
+        >>> from cryptography.fernet import Fernet
+        >>> keys = {"2024-06": Fernet(key)}
+        >>> enc = Encryption(
+        ...     "2024-06",
+        ...     encrypt=lambda kid, data: keys[kid].encrypt(data),
+        ...     decrypt=lambda kid, data: keys[kid].decrypt(data),
+        ... )
+        >>> r = Redis(encryption=enc)
+    """
+
+    def __init__(
+        self,
+        key_id: str,
+        encrypt: Callable[[str, bytes], bytes],
+        decrypt: Callable[[str, bytes], bytes],
+    ) -> None:
+        """Create the callbacks.
+
+        Args:
+            key_id: Key id new values are encrypted with (1 to 255 bytes).
+            encrypt: Called as ``encrypt(key_id, plaintext)``; returns the
+                ciphertext.
+            decrypt: Called as ``decrypt(key_id, ciphertext)`` with the key
+                id the value was encrypted with; returns the plaintext.
+
+        Raises:
+            TypeError: If ``key_id`` is empty or too long, or a callback is
+                not callable.
+        """
+        ...
+
+    @property
+    def key_id(self) -> str:
+        """The key id new values are encrypted with."""
+        ...
+
+    def __repr__(self) -> str: ...
+
+
+class ExpiryWatcher:
+    """Handle to a running key expiry watcher.
+
+    Created by :meth:`Redis.on_expire`. Runs in the background until
+    :meth:`stop` is called; dropping the handle does not stop it.
+    """
+
+    def stop(self) -> None:
+        """Stop watching and close the subscription. Safe to call more than once."""
+        ...
+
+    @property
+    def running(self) -> bool:
+        """Whether the watcher is still running."""
+        ...
+
+    @property
+    def channel(self) -> str:
+        """The keyevent channel being watched, e.g. ``"__keyevent@0__:expired"``."""
+        ...
+
+    @property
+    def pattern(self) -> str:
+        """The glob pattern keys must match."""
+        ...
+
+    def __enter__(self) -> "ExpiryWatcher": ...
+    def __exit__(self, *args: Any) -> bool: ...
+    def __repr__(self) -> str: ...
+
+
+class ScanIterator:
+    """Iterator over a SCAN-family command.
+
+    Created by :meth:`Redis.scan_iter`, :meth:`Redis.hscan_iter`,
+    :meth:`Redis.sscan_iter` and :meth:`Redis.zscan_iter`. The next page
+    is fetched when the previous one runs out. As with the commands
+    themselves, an item may be yielded more than once if the data changes
+    during the scan.
+    """
+
+    def __iter__(self) -> "ScanIterator": ...
+    def __next__(self) -> Any: ...
+    def __repr__(self) -> str: ...
+
+
+class Script:
+    """A Lua script returned by :meth:`Redis.register_script`.
+
+    Calling it runs ``EVALSHA`` and falls back to ``EVAL`` when the server
+    answers ``NOSCRIPT``, so a script flushed from the cache or never
+    loaded still runs. Given a :class:`Pipeline` as ``client``, the call is
+    queued there instead; the pipeline loads its scripts with
+    ``SCRIPT LOAD`` before sending its commands.
+    """
+
+    @property
+    def sha(self) -> str:
+        """SHA1 of the source, the name ``EVALSHA`` takes."""
+        ...
+
+    @property
+    def script(self) -> str:
+        """The Lua source."""
+        ...
+
+    def __call__(
+        self,
+        keys: Optional[list[Any]] = None,
+        args: Optional[list[Any]] = None,
+        client: "Redis | Pipeline | None" = None,
+    ) -> Any:
+        """Run the script.
+
+        Args:
+            keys: Key names, seen by the script as ``KEYS``.
+            args: Other arguments, seen as ``ARGV``.
+            client: Run on this :class:`Redis` instead of the one that
+                registered the script, or queue the call on this
+                :class:`Pipeline`, which is returned.
+
+        Returns:
+            The script's reply, or the pipeline.
+        """
+        ...
+
+    def __repr__(self) -> str: ...
+
+
+class FloatArray:
+    """A read-only, Rust-owned ``float64`` buffer.
+
+    Supports the buffer protocol, so ``numpy.frombuffer`` and
+    ``memoryview`` wrap it without copying. Usually seen as the ``base``
+    of arrays returned by :meth:`Redis.get_floats`.
+    """
+
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class Verbatim:
+    """A RESP3 verbatim string with its encoding.
+
+    Returned instead of ``str`` when the client was created with
+    ``resp3_types=True``. ``str(v)`` gives the text.
+    """
+
+    encoding: str
+    """Three-letter encoding, e.g. ``"txt"`` or ``"mkd"``."""
+    text: str
+    """The string content."""
+
+    def __init__(self, encoding: str, text: str) -> None: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+
+class BigNumber(int):
+    """A RESP3 big number: an ``int`` that remembers its wire text.
+
+    Returned instead of ``int`` when the client was created with
+    ``resp3_types=True``.
+    """
+
+    raw: str
+    """The number exactly as sent by the server."""
+
+    def __new__(cls, raw: str) -> "BigNumber": ...
+
+
+class QueryResult:
+    """A parsed graph query result, held in Rust.
+
+    Created by :meth:`Redis.graph_query_result` and
+    :func:`parse_graph_compact`. Arrow export requires
+    pyrsedis built with the ``arrow`` feature and ``pyarrow`` installed.
+
+    Indexing by position gives a :class:`Row`, whose values can be read
+    by column name; indexing by name gives that column as a list::
+
+        res = r.graph_query_result("social", "MATCH (p) RETURN p.name AS name")
+        res[0]["name"]
+        res["name"]
+        for row in res:
+            print(row["name"])
+    """
+
+    @property
+    def columns(self) -> list[str]:
+        """Column names, in result order."""
+        ...
+
+    @property
+    def stats(self) -> dict[str, str]:
+        """Query statistics, e.g. ``{"Nodes created": "1"}``."""
+        ...
+
+    def to_arrow(self) -> Any:
+        """Export as a ``pyarrow.RecordBatch``.
+
+        Node and relation columns hold entity ids; scalar columns are
+        typed from their cells (``int64``, ``double``, ``bool``,
+        ``string``), falling back to text for nested or mixed values.
+
+        Raises:
+            ImportError: If built without Arrow support or ``pyarrow`` is missing.
+        """
+        ...
+
+    def to_pandas(self) -> Any:
+        """Export as a ``pandas.DataFrame`` (via :meth:`to_arrow`)."""
+        ...
+
+    def __arrow_c_array__(self, requested_schema: Any = None) -> tuple[Any, Any]:
+        """Arrow PyCapsule protocol export."""
+        ...
+
+    @overload
+    def __getitem__(self, key: int) -> "Row":
+        """The row at position ``key`` (negative counts from the end).
+
+        Raises:
+            IndexError: If the position is out of range.
+        """
+        ...
+    @overload
+    def __getitem__(self, key: str) -> list[Any]:
+        """Every value of column ``key``, in row order.
+
+        Raises:
+            KeyError: If no column has that name.
+        """
+        ...
+    def __iter__(self) -> Iterator["Row"]: ...
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class Row:
+    """One row of a :class:`QueryResult`.
+
+    Values are read by column name or by position, so code keeps working
+    when a query's ``RETURN`` clause is reordered. Nodes, relations,
+    paths and points are dicts (``id``/``labels``/``properties``,
+    ``id``/``type``/``src``/``dst``/``properties``, ``nodes``/``edges``,
+    ``latitude``/``longitude``).
+    """
+
+    @property
+    def columns(self) -> list[str]:
+        """Column names, in result order."""
+        ...
+
+    def __getitem__(self, key: int | str) -> Any:
+        """The value in column ``key``, a name or a position.
+
+        Raises:
+            KeyError: If no column has that name.
+            IndexError: If the position is out of range.
+        """
+        ...
+
+    def get(self, key: int | str, default: Any = None) -> Any:
+        """The value in column ``key``, or ``default`` if there is none."""
+        ...
+
+    def to_dict(self) -> dict[str, Any]:
+        """The row as a ``{column: value}`` dict."""
+        ...
+
+    def __iter__(self) -> Iterator[Any]: ...
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class NodeResults:
+    """Per-node outcome of a command run on several nodes.
+
+    Returned by :meth:`Redis.execute_on_all_nodes` and, in cluster mode,
+    by :meth:`Redis.flushall`, :meth:`Redis.script_load`,
+    :meth:`Redis.function_load`, :meth:`Redis.function_restore` and
+    :meth:`Redis.config_set`. Nodes are keyed by ``"host:port"``.
+    """
+
+    @property
+    def successes(self) -> dict[str, Any]:
+        """Replies from the nodes that succeeded."""
+        ...
+
+    @property
+    def failures(self) -> dict[str, Exception]:
+        """Exceptions for the nodes that failed."""
+        ...
+
+    @property
+    def ok(self) -> bool:
+        """Whether every node succeeded."""
+        ...
+
+    def raise_for_failures(self) -> None:
+        """Raise the first failure, if any."""
+        ...
+
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
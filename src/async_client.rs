@@ -0,0 +1,2122 @@
+//! Python-facing asyncio client.
+//!
+//! [`AsyncRedis`] mirrors the [`Redis`] API, but every command method is an
+//! `async def` returning an awaitable. Command I/O is spawned onto the
+//! shared Tokio runtime and the coroutine awaits the task's join handle,
+//! so the asyncio event loop is never blocked — no `runtime::block_on`
+//! and no executor threads.
+//!
+//! Stateful helpers that pin a connection (`dedicated_connection`,
+//! `pubsub`) are only available on the synchronous client.
+
+// PyO3 keyword arguments map 1:1 onto Rust parameters, so Python-facing
+// signatures routinely exceed clippy's default argument limit.
+#![allow(clippy::too_many_arguments)]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyTuple};
+
+use crate::client::{
+    command_stats_to_python, encode_value, command_route, expire_args, flush_args, function_restore_args, get_reply, getex_args, hrandfield_args, hset_args, is_pong, linsert_args, lmove_args, lmpop_args, lpos_args, mset_args, per_key_integers, publish_commands, replies_to_list, sentinel_addr, sentinel_entries, sentinel_entry, set_args, set_reply, set_store_args, sintercard_args, smismember_args, smove_args, srandmember_args,
+    zadd_args, zmpop_args, zmscore_args, zpop_args, zrandmember_args, zrangestore_args, zset_combine_args, events_to_python, node_results, set_event_callback, stats_to_python, FloatArray, KeylessRoutes, NodeResults, Pipeline, QueryResult,
+    Redis,
+};
+use crate::blocking::{self, Keys};
+use crate::clock::ServerClock;
+use crate::codec::{CommandArg, Serializer};
+use crate::connection::capture::FrameLog;
+use crate::connection::latency::CommandStats;
+use crate::connection::pool::without_queueing;
+use crate::connection::sizing::expect_reply_size;
+use crate::commands;
+use crate::encryption::Encryption;
+use crate::error::{PyrsedisError, Result};
+use crate::events::EventLog;
+use crate::geo;
+use crate::graph::parse_graph_result;
+use crate::ingest;
+use crate::numbers::{self, Score};
+use crate::purge;
+use crate::reshard;
+use crate::resp::types::RespValue;
+use crate::response::{parse_to_python_with, resp_to_f64_map, resp_to_f64_vec, ParseOptions};
+use crate::router::Router;
+use crate::router::{self, ClientRouter, Route};
+use crate::runtime;
+use crate::scan::{scan_args, ScanKind};
+use crate::streams::{self, ReplyShape};
+
+/// A spawned task that is aborted when its handle is dropped.
+///
+/// A cancelled coroutine drops the future awaiting the task; without
+/// the abort the task would run on, holding its connection, and a
+/// blocking pop would still take an element nobody receives. Aborting
+/// drops the task's connection mid-command, and the pool discards it as
+/// interrupted.
+struct AbortOnDrop<T>(tokio::task::JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl<T> Future for AbortOnDrop<T> {
+    type Output = std::result::Result<T, tokio::task::JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+/// Drive `future` on the shared Tokio runtime and await its result.
+///
+/// The join handle is runtime-agnostic, so it can be awaited from the
+/// asyncio-driven coroutine while the I/O itself runs on Tokio workers.
+/// Cancelling the coroutine aborts the task.
+async fn run<T: Send + 'static>(future: impl Future<Output = Result<T>> + Send + 'static) -> PyResult<T> {
+    match AbortOnDrop(runtime::spawn(future)).await {
+        Ok(result) => Ok(result?),
+        Err(e) => Err(PyRuntimeError::new_err(format!("pyrsedis task failed: {e}"))),
+    }
+}
+
+/// Send one command and return its raw reply frame, failing once
+/// `limit` has passed. `size` is the expected reply size in bytes, if
+/// known.
+async fn run_raw(
+    router: Arc<ClientRouter>,
+    args: Vec<String>,
+    limit: Option<Duration>,
+    size: Option<usize>,
+) -> PyResult<bytes::Bytes> {
+    run(async move {
+        let refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        let raw = router::within(limit, expect_reply_size(size, router.execute_raw(&refs))).await?;
+        router::check_reply(raw, &refs)
+    })
+    .await
+}
+
+/// Like [`run_raw`], with binary-safe arguments.
+async fn run_raw_bytes(
+    router: Arc<ClientRouter>,
+    args: Vec<CommandArg>,
+    route: Option<Route>,
+    limit: Option<Duration>,
+    size: Option<usize>,
+) -> PyResult<bytes::Bytes> {
+    run(async move {
+        let refs: Vec<&[u8]> = args.iter().map(CommandArg::as_bytes).collect();
+        let request = router.execute_raw_bytes_at(route.as_ref(), &refs);
+        let raw = router::within(limit, expect_reply_size(size, request)).await?;
+        router::check_reply(raw, &refs)
+    })
+    .await
+}
+
+/// Send one command and return its parsed reply, expected to be about
+/// `size` bytes when given.
+async fn run_value(router: Arc<ClientRouter>, args: Vec<String>, size: Option<usize>) -> PyResult<RespValue> {
+    run(async move {
+        let refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        expect_reply_size(size, router.execute(&refs)).await
+    })
+    .await
+}
+
+/// Send `commands` in one round-trip and convert the replies to a list.
+async fn run_batch(router: Arc<ClientRouter>, commands: Vec<Vec<String>>, opts: ParseOptions) -> PyResult<Py<PyAny>> {
+    if commands.is_empty() {
+        return Python::attach(|py| Ok(PyList::empty(py).into_any().unbind()));
+    }
+    let raw_responses = run(async move { router.pipeline_raw(&commands).await }).await?;
+    Python::attach(|py| replies_to_list(py, &raw_responses, opts))
+}
+
+/// Run `commands` as one MULTI/EXEC transaction and return its results.
+async fn run_transaction(router: Arc<ClientRouter>, commands: Vec<Vec<String>>, opts: ParseOptions) -> PyResult<Py<PyAny>> {
+    if commands.is_empty() {
+        return Python::attach(|py| Ok(PyList::empty(py).into_any().unbind()));
+    }
+    let raw_responses = run(async move { router.transaction_raw(&commands).await }).await?;
+    Python::attach(|py| replies_to_list(py, &raw_responses, opts))
+}
+
+/// Run a command on every node and collect the per-node outcomes.
+async fn run_on_all(router: Arc<ClientRouter>, args: Vec<String>, opts: ParseOptions) -> PyResult<NodeResults> {
+    let replies = run(async move {
+        let refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        Ok(router.execute_on_all_raw(&refs).await)
+    })
+    .await?;
+    Python::attach(|py| node_results(py, replies, opts))
+}
+
+// ── AsyncRedis ─────────────────────────────────────────────────────
+
+/// An asyncio Redis client backed by a connection pool.
+///
+/// Takes the same arguments as :class:`Redis`; every command method is a
+/// coroutine.
+///
+/// ```python
+/// r = AsyncRedis()
+/// await r.set("key", "value")
+/// await r.get("key")
+/// ```
+#[pyclass(name = "AsyncRedis")]
+pub struct AsyncRedis {
+    router: Arc<ClientRouter>,
+    /// Stash the address for __repr__.
+    addr: String,
+    /// When true, BulkString responses are decoded to Python str.
+    decode_responses: bool,
+    /// Value codec applied by `set`/`get`, if any.
+    serializer: Option<Serializer>,
+    /// Keep RESP3 verbatim strings and big numbers distinguishable.
+    resp3_types: bool,
+    /// Connection lifecycle events of this client.
+    events: Arc<EventLog>,
+    /// Raw frames sent and received, when created with `capture_frames`.
+    frames: Option<Arc<FrameLog>>,
+    /// Round-trip latency of this client's commands by family.
+    command_stats: Arc<CommandStats>,
+    /// RESP version negotiated by this client's connections (0 = none yet).
+    protocol: Arc<AtomicU8>,
+    /// Callbacks encrypting the values of `set`/`get`, if any.
+    encryption: Option<Py<Encryption>>,
+    /// Cached estimate of the server clock offset.
+    clock: Arc<ServerClock>,
+    /// Run `flushdb`/`flushall` without ``confirm=True``.
+    allow_destructive: bool,
+    /// Refuse numbers Redis would misread or round (see [`numbers`]).
+    strict_numbers: bool,
+}
+
+impl From<Redis> for AsyncRedis {
+    fn from(client: Redis) -> Self {
+        Self {
+            router: client.router,
+            addr: client.addr,
+            decode_responses: client.decode_responses,
+            serializer: client.serializer,
+            resp3_types: client.resp3_types,
+            events: client.events,
+            frames: client.frames,
+            command_stats: client.command_stats,
+            protocol: client.protocol,
+            encryption: client.encryption,
+            clock: client.clock,
+            allow_destructive: client.allow_destructive,
+            strict_numbers: client.strict_numbers,
+        }
+    }
+}
+
+impl AsyncRedis {
+    /// How raw replies are converted for this client.
+    fn parse_options(&self) -> ParseOptions {
+        ParseOptions { decode: self.decode_responses, resp3_types: self.resp3_types }
+    }
+
+    /// Execute a command and convert the raw reply to a Python object.
+    async fn exec(&self, args: Vec<String>) -> PyResult<Py<PyAny>> {
+        self.exec_within(args, None).await
+    }
+
+    /// Like [`exec`](Self::exec), failing once `limit` has passed.
+    async fn exec_within(&self, args: Vec<String>, limit: Option<Duration>) -> PyResult<Py<PyAny>> {
+        let raw = run_raw(Arc::clone(&self.router), args, limit, None).await?;
+        let opts = self.parse_options();
+        Python::attach(|py| Ok(parse_to_python_with(py, &raw, opts)?.0))
+    }
+
+    /// Like [`exec`](Self::exec), with the reply expected to be about
+    /// `size` bytes when given.
+    async fn exec_expecting(&self, args: Vec<String>, size: Option<usize>) -> PyResult<Py<PyAny>> {
+        let raw = run_raw(Arc::clone(&self.router), args, None, size).await?;
+        let opts = self.parse_options();
+        Python::attach(|py| Ok(parse_to_python_with(py, &raw, opts)?.0))
+    }
+
+    /// Like [`exec_within`](Self::exec_within), reshaping the reply when
+    /// `shape` is given.
+    async fn exec_shaped(&self, args: Vec<String>, shape: Option<ReplyShape>, limit: Option<Duration>) -> PyResult<Py<PyAny>> {
+        let reply = self.exec_within(args, limit).await?;
+        match shape {
+            Some(shape) => Python::attach(|py| Ok(shape.apply(reply.into_bound(py))?.unbind())),
+            None => Ok(reply),
+        }
+    }
+
+    /// Run a blocking command on a connection held for the whole wait.
+    async fn exec_blocking(&self, (args, block): (Vec<String>, Duration)) -> PyResult<Py<PyAny>> {
+        let router = Arc::clone(&self.router);
+        let raw = run(async move {
+            let refs: Vec<&str> = args.iter().map(String::as_str).collect();
+            let raw = router.execute_blocking(&refs, block).await?;
+            router::check_reply(raw, &refs)
+        })
+        .await?;
+        let opts = self.parse_options();
+        Python::attach(|py| Ok(parse_to_python_with(py, &raw, opts)?.0))
+    }
+
+    /// Fan a server-wide command out to every master in cluster mode,
+    /// returning a [`NodeResults`]; run it normally otherwise.
+    async fn exec_cluster_wide(&self, args: Vec<String>) -> PyResult<Py<PyAny>> {
+        if !self.router.is_cluster() {
+            return self.exec(args).await;
+        }
+        let results = run_on_all(Arc::clone(&self.router), args, self.parse_options()).await?;
+        Python::attach(|py| Ok(Py::new(py, results)?.into_any()))
+    }
+
+    /// Like [`exec_cluster_wide`](Self::exec_cluster_wide), with
+    /// binary-safe arguments.
+    async fn exec_cluster_wide_bytes(&self, args: Vec<CommandArg>) -> PyResult<Py<PyAny>> {
+        let opts = self.parse_options();
+        if !self.router.is_cluster() {
+            let raw = run_raw_bytes(Arc::clone(&self.router), args, None, None, None).await?;
+            return Python::attach(|py| Ok(parse_to_python_with(py, &raw, opts)?.0));
+        }
+        let router = Arc::clone(&self.router);
+        let replies = run(async move {
+            let refs: Vec<&[u8]> = args.iter().map(CommandArg::as_bytes).collect();
+            Ok(router.execute_on_all_raw_bytes(&refs).await)
+        })
+        .await?;
+        Python::attach(|py| Ok(Py::new(py, node_results(py, replies, opts)?)?.into_any()))
+    }
+
+    /// Start `args` as a coroutine from a synchronous method.
+    ///
+    /// Variadic methods cannot be `async fn` (PyO3 cannot move ``*args``
+    /// into a coroutine), so they build the command and return the
+    /// awaitable of [`AsyncRedis::_command`] instead.
+    fn command<'py>(slf: &Bound<'py, Self>, args: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        slf.call_method1(intern!(slf.py(), "_command"), (args,))
+    }
+
+    /// Run `command key` for every key, chunked and pipelined.
+    async fn per_key_integers(
+        &self,
+        command: &'static str,
+        keys: Vec<String>,
+        args: Vec<String>,
+        chunk_size: usize,
+    ) -> PyResult<Vec<i64>> {
+        let router = Arc::clone(&self.router);
+        run(async move { per_key_integers(&router, command, &keys, &args, chunk_size).await }).await
+    }
+
+    /// Pipeline one bulk-load chunk, then report `loaded` to the progress
+    /// callback.
+    async fn load_chunk(&self, commands: Vec<Vec<String>>, loaded: usize, progress: Option<&Py<PyAny>>) -> PyResult<i64> {
+        let router = Arc::clone(&self.router);
+        let total = run(async move { ingest::send_chunk(&router, &commands).await }).await?;
+        Python::attach(|py| ingest::report(progress.map(|p| p.bind(py)), loaded))?;
+        Ok(total)
+    }
+}
+
+commands::command_table!(async_redis_methods! {
+#[pymethods]
+impl AsyncRedis {
+    /// Create a new asyncio Redis client.
+    ///
+    /// Arguments are the same as for :class:`Redis`, except that
+    /// client-side caching is not available.
+    #[new]
+    #[pyo3(signature = (host="127.0.0.1", port=6379, db=0, password=None, username=None, pool_size=8, connect_timeout_ms=5000, read_timeout_ms=30_000, idle_timeout_ms=300_000, max_buffer_size=67_108_864, decode_responses=true, retry_unsafe=false, serializer=None, cluster=None, resp3_types=false, sentinels=None, master_name=None, sentinel_retries=3, sentinel_retry_backoff_ms=100, ssl=false, ssl_ca_certs=None, ssl_certfile=None, ssl_keyfile=None, ssl_server_hostname=None, ssl_insecure_skip_verify=false, slot_refresh_interval_ms=30_000, unix_socket_path=None, protocol=2, protocol_fallback=true, encryption=None, health_check_interval_ms=0, reserved_connections=0, min_idle=0, max_lifetime_ms=0, pool_timeout_ms=0, allow_cross_slot=false, capture_frames=0, address_remap=None, read_preference="primary", keyless_routing=KeylessRoutes::default(), allow_destructive=false, sentinel_username=None, sentinel_password=None, strict_numbers=false))]
+    pub(crate) fn new(
+        host: &str,
+        port: u16,
+        db: u16,
+        password: Option<String>,
+        username: Option<String>,
+        pool_size: usize,
+        connect_timeout_ms: u64,
+        read_timeout_ms: u64,
+        idle_timeout_ms: u64,
+        max_buffer_size: usize,
+        decode_responses: bool,
+        retry_unsafe: bool,
+        serializer: Option<&str>,
+        cluster: Option<Vec<(String, u16)>>,
+        resp3_types: bool,
+        sentinels: Option<Vec<(String, u16)>>,
+        master_name: Option<String>,
+        sentinel_retries: usize,
+        sentinel_retry_backoff_ms: u64,
+        ssl: bool,
+        ssl_ca_certs: Option<String>,
+        ssl_certfile: Option<String>,
+        ssl_keyfile: Option<String>,
+        ssl_server_hostname: Option<String>,
+        ssl_insecure_skip_verify: bool,
+        slot_refresh_interval_ms: u64,
+        unix_socket_path: Option<String>,
+        protocol: u8,
+        protocol_fallback: bool,
+        encryption: Option<Py<Encryption>>,
+        health_check_interval_ms: u64,
+        reserved_connections: usize,
+        min_idle: usize,
+        max_lifetime_ms: u64,
+        pool_timeout_ms: u64,
+        allow_cross_slot: bool,
+        capture_frames: usize,
+        address_remap: Option<Bound<'_, PyAny>>,
+        read_preference: &str,
+        keyless_routing: KeylessRoutes,
+        allow_destructive: bool,
+        sentinel_username: Option<String>,
+        sentinel_password: Option<String>,
+        strict_numbers: bool,
+    ) -> PyResult<Self> {
+        Redis::new(
+            host,
+            port,
+            db,
+            password,
+            username,
+            pool_size,
+            connect_timeout_ms,
+            read_timeout_ms,
+            idle_timeout_ms,
+            max_buffer_size,
+            decode_responses,
+            retry_unsafe,
+            serializer,
+            cluster,
+            resp3_types,
+            sentinels,
+            master_name,
+            sentinel_retries,
+            sentinel_retry_backoff_ms,
+            ssl,
+            ssl_ca_certs,
+            ssl_certfile,
+            ssl_keyfile,
+            ssl_server_hostname,
+            ssl_insecure_skip_verify,
+            slot_refresh_interval_ms,
+            unix_socket_path,
+            protocol,
+            protocol_fallback,
+            false,
+            10_000,
+            None,
+            encryption,
+            health_check_interval_ms,
+            reserved_connections,
+            min_idle,
+            max_lifetime_ms,
+            pool_timeout_ms,
+            allow_cross_slot,
+            capture_frames,
+            address_remap,
+            read_preference,
+            keyless_routing,
+            allow_destructive,
+            sentinel_username,
+            sentinel_password,
+            strict_numbers,
+        )
+        .map(Self::from)
+    }
+
+    /// Create an asyncio client from a URL (see :meth:`Redis.from_url`).
+    #[staticmethod]
+    #[pyo3(signature = (url, pool_size=8, connect_timeout_ms=5000, read_timeout_ms=30_000, idle_timeout_ms=300_000, decode_responses=true, retry_unsafe=false, serializer=None, resp3_types=false, sentinel_retries=3, sentinel_retry_backoff_ms=100, ssl_ca_certs=None, ssl_certfile=None, ssl_keyfile=None, ssl_server_hostname=None, ssl_insecure_skip_verify=false, slot_refresh_interval_ms=30_000, protocol=2, protocol_fallback=true, encryption=None, health_check_interval_ms=0, reserved_connections=0, min_idle=0, max_lifetime_ms=0, pool_timeout_ms=0, allow_cross_slot=false, capture_frames=0, address_remap=None, read_preference="primary", keyless_routing=KeylessRoutes::default(), allow_destructive=false, strict_numbers=false))]
+    fn from_url(
+        url: &str,
+        pool_size: usize,
+        connect_timeout_ms: u64,
+        read_timeout_ms: u64,
+        idle_timeout_ms: u64,
+        decode_responses: bool,
+        retry_unsafe: bool,
+        serializer: Option<&str>,
+        resp3_types: bool,
+        sentinel_retries: usize,
+        sentinel_retry_backoff_ms: u64,
+        ssl_ca_certs: Option<String>,
+        ssl_certfile: Option<String>,
+        ssl_keyfile: Option<String>,
+        ssl_server_hostname: Option<String>,
+        ssl_insecure_skip_verify: bool,
+        slot_refresh_interval_ms: u64,
+        protocol: u8,
+        protocol_fallback: bool,
+        encryption: Option<Py<Encryption>>,
+        health_check_interval_ms: u64,
+        reserved_connections: usize,
+        min_idle: usize,
+        max_lifetime_ms: u64,
+        pool_timeout_ms: u64,
+        allow_cross_slot: bool,
+        capture_frames: usize,
+        address_remap: Option<Bound<'_, PyAny>>,
+        read_preference: &str,
+        keyless_routing: KeylessRoutes,
+        allow_destructive: bool,
+        strict_numbers: bool,
+    ) -> PyResult<Self> {
+        Redis::from_url(
+            url,
+            pool_size,
+            connect_timeout_ms,
+            read_timeout_ms,
+            idle_timeout_ms,
+            decode_responses,
+            retry_unsafe,
+            serializer,
+            resp3_types,
+            sentinel_retries,
+            sentinel_retry_backoff_ms,
+            ssl_ca_certs,
+            ssl_certfile,
+            ssl_keyfile,
+            ssl_server_hostname,
+            ssl_insecure_skip_verify,
+            slot_refresh_interval_ms,
+            protocol,
+            protocol_fallback,
+            false,
+            10_000,
+            None,
+            encryption,
+            health_check_interval_ms,
+            reserved_connections,
+            min_idle,
+            max_lifetime_ms,
+            pool_timeout_ms,
+            allow_cross_slot,
+            capture_frames,
+            address_remap,
+            read_preference,
+            keyless_routing,
+            allow_destructive,
+            strict_numbers,
+        )
+        .map(Self::from)
+    }
+
+    /// Coroutine behind the variadic command methods.
+    #[pyo3(signature = (args, expected_response_size=None))]
+    async fn _command(&self, args: Vec<String>, expected_response_size: Option<usize>) -> PyResult<Py<PyAny>> {
+        self.exec_expecting(args, expected_response_size).await
+    }
+
+    /// Coroutine behind :meth:`execute_command`.
+    async fn _execute(
+        &self,
+        args: Vec<CommandArg>,
+        route: Option<Route>,
+        node: Option<String>,
+        limit: Option<Duration>,
+        size: Option<usize>,
+    ) -> PyResult<Py<PyAny>> {
+        if self.strict_numbers {
+            numbers::check_integers(&args)?;
+        }
+        let route = command_route(route, node)?;
+        let raw = run_raw_bytes(Arc::clone(&self.router), args, route, limit, size).await?;
+        let opts = self.parse_options();
+        Python::attach(|py| Ok(parse_to_python_with(py, &raw, opts)?.0))
+    }
+
+    /// Execute a raw Redis command and return the result (see
+    /// :meth:`Redis.execute_command` for the accepted argument types,
+    /// ``timeout_ms``, ``expected_response_size``, ``route`` and ``node``).
+    #[pyo3(signature = (*args, timeout_ms=None, expected_response_size=None, route=None, node=None))]
+    fn execute_command<'py>(
+        slf: &Bound<'py, Self>,
+        args: &Bound<'py, PyTuple>,
+        timeout_ms: Option<u64>,
+        expected_response_size: Option<usize>,
+        route: Option<Bound<'py, PyAny>>,
+        node: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        if args.is_empty() {
+            return Err(PyrsedisError::Type("execute_command requires at least one argument".into()).into());
+        }
+        let limit = router::command_limit(timeout_ms.map(|ms| ms as f64 / 1000.0))?;
+        // Argument types, the route included, are checked when the
+        // coroutine is created
+        slf.call_method1(intern!(slf.py(), "_execute"), (args, route, node, limit, expected_response_size))
+    }
+
+    /// Coroutine behind :meth:`try_execute_command`.
+    async fn _try_execute(&self, args: Vec<CommandArg>, wait: Duration) -> PyResult<Py<PyAny>> {
+        if self.strict_numbers {
+            numbers::check_integers(&args)?;
+        }
+        let router = Arc::clone(&self.router);
+        let raw = run(async move {
+            let refs: Vec<&[u8]> = args.iter().map(CommandArg::as_bytes).collect();
+            let raw = without_queueing(wait, router.execute_raw_bytes(&refs)).await?;
+            router::check_reply(raw, &refs)
+        })
+        .await?;
+        let opts = self.parse_options();
+        Python::attach(|py| Ok(parse_to_python_with(py, &raw, opts)?.0))
+    }
+
+    /// Execute a command only if a pooled connection is free for it (see
+    /// :meth:`Redis.try_execute_command`).
+    #[pyo3(signature = (*args, timeout_ms=0))]
+    fn try_execute_command<'py>(slf: &Bound<'py, Self>, args: &Bound<'py, PyTuple>, timeout_ms: u64) -> PyResult<Bound<'py, PyAny>> {
+        if args.is_empty() {
+            return Err(PyrsedisError::Type("try_execute_command requires at least one argument".into()).into());
+        }
+        slf.call_method1(intern!(slf.py(), "_try_execute"), (args, Duration::from_millis(timeout_ms)))
+    }
+
+    /// Coroutine behind :meth:`execute_on_all_nodes`.
+    async fn _on_all_nodes(&self, args: Vec<String>) -> PyResult<NodeResults> {
+        run_on_all(Arc::clone(&self.router), args, self.parse_options()).await
+    }
+
+    /// Execute a command on every node and report each node's outcome
+    /// (see :meth:`Redis.execute_on_all_nodes`).
+    #[pyo3(signature = (*args))]
+    fn execute_on_all_nodes<'py>(slf: &Bound<'py, Self>, args: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        if args.is_empty() {
+            return Err(PyrsedisError::Type("execute_on_all_nodes requires at least one argument".into()).into());
+        }
+        slf.call_method1(intern!(slf.py(), "_on_all_nodes"), (args,))
+    }
+
+    /// Execute a batch of commands in a single round-trip.
+    async fn execute_many(&self, commands: Vec<Vec<String>>) -> PyResult<Py<PyAny>> {
+        if commands.iter().any(|cmd| cmd.is_empty()) {
+            return Err(PyrsedisError::Type("execute_many: every command needs at least one argument".into()).into());
+        }
+        run_batch(Arc::clone(&self.router), commands, self.parse_options()).await
+    }
+
+    /// Create a pipeline whose :meth:`AsyncPipeline.execute` is awaitable.
+    ///
+    /// Commands are buffered locally, so adding them never blocks. With
+    /// ``transaction=True`` they run inside ``MULTI``/``EXEC`` (see
+    /// :meth:`Redis.pipeline`). Going past ``max_bytes`` raises
+    /// :class:`PipelineTooLargeError`; there is no ``"flush"`` mode, as
+    /// flushing would block the event loop.
+    #[pyo3(signature = (transaction=false, max_bytes=None))]
+    fn pipeline(&self, py: Python<'_>, transaction: bool, max_bytes: Option<usize>) -> PyResult<Py<AsyncPipeline>> {
+        let mut base = Pipeline::buffered(Arc::clone(&self.router), self.parse_options(), transaction);
+        base.max_bytes = max_bytes;
+        base.strict_numbers = self.strict_numbers;
+        Py::new(py, PyClassInitializer::from(base).add_subclass(AsyncPipeline))
+    }
+
+    // ── Convenience commands ───────────────────────────────────────
+
+    /// Ping the server.
+    async fn ping(&self) -> PyResult<bool> {
+        let raw = run_raw(Arc::clone(&self.router), vec!["PING".into()], None, None).await?;
+        Ok(is_pong(&raw))
+    }
+
+    /// Set a key to a value (see :meth:`Redis.set`).
+    #[pyo3(signature = (name, value, ex=None, px=None, nx=false, xx=false))]
+    async fn set(
+        &self,
+        name: CommandArg,
+        value: Py<PyAny>,
+        ex: Option<u64>,
+        px: Option<u64>,
+        nx: bool,
+        xx: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let encryption = self.encryption.as_ref().map(Py::get);
+        let payload = Python::attach(|py| encode_value(self.serializer, encryption, value.bind(py)))?;
+        let args = set_args(name.as_bytes(), payload, ex, px, nx, xx);
+        let router = Arc::clone(&self.router);
+        let raw = run(async move {
+            let cmd: Vec<&[u8]> = args.iter().map(Vec::as_slice).collect();
+            router.execute_raw_bytes(&cmd).await
+        })
+        .await?;
+        Python::attach(|py| set_reply(py, &raw))
+    }
+
+    /// Get the value of a key (see :meth:`Redis.get`).
+    async fn get(&self, name: CommandArg) -> PyResult<Py<PyAny>> {
+        let raw = run_raw_bytes(Arc::clone(&self.router), vec![CommandArg(b"GET".to_vec()), name], None, None, None).await?;
+        let encryption = self.encryption.as_ref().map(Py::get);
+        Python::attach(|py| get_reply(py, &raw, self.parse_options(), self.serializer, encryption))
+    }
+
+    /// Delete one or more keys.
+    #[pyo3(signature = (*names))]
+    fn delete<'py>(slf: &Bound<'py, Self>, names: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["DEL".into()];
+        cmd.extend(names);
+        Self::command(slf, cmd)
+    }
+
+    /// Check if one or more keys exist.
+    #[pyo3(signature = (*names))]
+    fn exists<'py>(slf: &Bound<'py, Self>, names: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["EXISTS".into()];
+        cmd.extend(names);
+        Self::command(slf, cmd)
+    }
+
+    /// Set a timeout on a key (in seconds), with the conditions of
+    /// :meth:`Redis.expire`.
+    #[pyo3(signature = (name, seconds, nx=false, xx=false, gt=false, lt=false))]
+    #[allow(clippy::too_many_arguments)]
+    async fn expire(&self, name: String, seconds: u64, nx: bool, xx: bool, gt: bool, lt: bool) -> PyResult<Py<PyAny>> {
+        self.exec(expire_args("EXPIRE", name, seconds, nx, xx, gt, lt)).await
+    }
+
+    /// Check which of many keys exist (see :meth:`Redis.exists_many`).
+    #[pyo3(signature = (keys, chunk_size=1000))]
+    async fn exists_many(&self, keys: Vec<String>, chunk_size: usize) -> PyResult<Py<PyDict>> {
+        let replies = self.per_key_integers("EXISTS", keys.clone(), Vec::new(), chunk_size).await?;
+        Python::attach(|py| {
+            let dict = PyDict::new(py);
+            for (key, n) in keys.iter().zip(replies) {
+                dict.set_item(key, n > 0)?;
+            }
+            Ok(dict.unbind())
+        })
+    }
+
+    /// Get the TTL of many keys (see :meth:`Redis.ttl_many`).
+    #[pyo3(signature = (keys, chunk_size=1000))]
+    async fn ttl_many(&self, keys: Vec<String>, chunk_size: usize) -> PyResult<Py<PyDict>> {
+        let replies = self.per_key_integers("TTL", keys.clone(), Vec::new(), chunk_size).await?;
+        Python::attach(|py| {
+            let dict = PyDict::new(py);
+            for (key, ttl) in keys.iter().zip(replies) {
+                dict.set_item(key, ttl)?;
+            }
+            Ok(dict.unbind())
+        })
+    }
+
+    /// Set the expiry of many keys (see :meth:`Redis.expire_many`).
+    #[pyo3(signature = (mapping, chunk_size=1000))]
+    async fn expire_many(&self, mapping: Py<PyDict>, chunk_size: usize) -> PyResult<Py<PyDict>> {
+        let (keys, seconds) = Python::attach(|py| -> PyResult<(Vec<String>, Vec<String>)> {
+            let mut keys = Vec::new();
+            let mut seconds = Vec::new();
+            for (k, v) in mapping.bind(py).iter() {
+                keys.push(k.extract::<String>()?);
+                seconds.push(v.extract::<i64>()?.to_string());
+            }
+            Ok((keys, seconds))
+        })?;
+        let replies = self.per_key_integers("EXPIRE", keys.clone(), seconds, chunk_size).await?;
+        Python::attach(|py| {
+            let dict = PyDict::new(py);
+            for (key, n) in keys.iter().zip(replies) {
+                dict.set_item(key, n == 1)?;
+            }
+            Ok(dict.unbind())
+        })
+    }
+
+    /// Remove the expiry of many keys (see :meth:`Redis.persist_many`).
+    #[pyo3(signature = (keys, chunk_size=1000))]
+    async fn persist_many(&self, keys: Vec<String>, chunk_size: usize) -> PyResult<Py<PyDict>> {
+        let replies = self.per_key_integers("PERSIST", keys.clone(), Vec::new(), chunk_size).await?;
+        Python::attach(|py| {
+            let dict = PyDict::new(py);
+            for (key, n) in keys.iter().zip(replies) {
+                dict.set_item(key, n == 1)?;
+            }
+            Ok(dict.unbind())
+        })
+    }
+
+    /// Unlink the keys matching a pattern that are about to expire (see
+    /// :meth:`Redis.purge_expiring`).
+    #[pyo3(signature = (pattern, older_than_ttl, count=1000, pause_ms=10))]
+    async fn purge_expiring(&self, pattern: String, older_than_ttl: u64, count: u64, pause_ms: u64) -> PyResult<u64> {
+        let older_than_ms = i64::try_from(older_than_ttl.saturating_mul(1000)).unwrap_or(i64::MAX);
+        let pause = Duration::from_millis(pause_ms);
+        let router = Arc::clone(&self.router);
+        run(async move { purge::purge_expiring(&router, &pattern, older_than_ms, count, pause).await }).await
+    }
+
+    /// Extend the TTL of the given keys that are about to expire (see
+    /// :meth:`Redis.refresh_ttl_if_below`).
+    async fn refresh_ttl_if_below(&self, keys: Vec<String>, min_ttl: u64, new_ttl: u64) -> PyResult<u64> {
+        let min_ttl_ms = i64::try_from(min_ttl.saturating_mul(1000)).unwrap_or(i64::MAX);
+        let router = Arc::clone(&self.router);
+        run(async move { purge::refresh_ttl_if_below(&router, &keys, min_ttl_ms, new_ttl).await }).await
+    }
+
+    /// Load rows into one hash per row (see :meth:`Redis.load_hash_rows`).
+    #[pyo3(signature = (prefix, rows, key_field=None, chunk_size=1000, progress=None))]
+    async fn load_hash_rows(
+        &self,
+        prefix: String,
+        rows: Py<PyAny>,
+        key_field: Option<String>,
+        chunk_size: usize,
+        progress: Option<Py<PyAny>>,
+    ) -> PyResult<usize> {
+        ingest::check_chunk_size(chunk_size)?;
+        let mut loader = ingest::HashRows::new(&prefix, key_field);
+        let rows = Python::attach(|py| rows.bind(py).try_iter().map(Bound::unbind))?;
+        let mut loaded = 0;
+        loop {
+            let chunk = Python::attach(|py| loader.next_chunk(&mut rows.bind(py).clone(), chunk_size))?;
+            if chunk.is_empty() {
+                return Ok(loaded);
+            }
+            loaded += chunk.len();
+            self.load_chunk(chunk, loaded, progress.as_ref()).await?;
+        }
+    }
+
+    /// Load members into a sorted set (see :meth:`Redis.load_zset`).
+    #[pyo3(signature = (name, mapping, chunk_size=1000, progress=None))]
+    async fn load_zset(
+        &self,
+        name: String,
+        mapping: Py<PyAny>,
+        chunk_size: usize,
+        progress: Option<Py<PyAny>>,
+    ) -> PyResult<i64> {
+        ingest::check_chunk_size(chunk_size)?;
+        let mut loader = ingest::ZsetMembers::new(&name);
+        let pairs = Python::attach(|py| ingest::ZsetMembers::pairs(mapping.bind(py)).map(Bound::unbind))?;
+        let (mut loaded, mut added) = (0, 0);
+        while let Some((cmd, members)) =
+            Python::attach(|py| loader.next_command(&mut pairs.bind(py).clone(), chunk_size))?
+        {
+            loaded += members;
+            added += self.load_chunk(vec![cmd], loaded, progress.as_ref()).await?;
+        }
+        Ok(added)
+    }
+
+    /// Increment the integer value of a key by a given amount.
+    async fn incrby(&self, name: String, amount: i64) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["INCRBY".into(), name, amount.to_string()]).await
+    }
+
+    /// Get the values of multiple keys (see :meth:`Redis.mget`).
+    #[pyo3(signature = (*names, expected_response_size=None))]
+    fn mget<'py>(slf: &Bound<'py, Self>, names: Vec<String>, expected_response_size: Option<usize>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd: Vec<String> = vec!["MGET".into()];
+        cmd.extend(names);
+        slf.call_method1(intern!(slf.py(), "_command"), (cmd, expected_response_size))
+    }
+
+    /// Get the values of multiple keys as a ``numpy.float64`` array.
+    async fn get_floats(&self, keys: Vec<String>) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["MGET".into()];
+        cmd.extend(keys);
+        let resp = run_value(Arc::clone(&self.router), cmd, None).await?;
+        let values = resp_to_f64_vec(&resp)?;
+        Python::attach(|py| Ok(FloatArray::new(values).into_numpy(py)?.unbind()))
+    }
+
+    /// Set multiple keys to multiple values.
+    async fn mset(&self, mapping: Py<PyAny>) -> PyResult<Py<PyAny>> {
+        let cmd = Python::attach(|py| mset_args(mapping.bind(py)))?;
+        self.exec(cmd).await
+    }
+
+    // ── Hash commands ──────────────────────────────────────────────
+
+    /// Get all fields of a hash with their values as floats.
+    async fn hgetall_floats(&self, name: String) -> PyResult<(Vec<String>, Py<PyAny>)> {
+        let resp = run_value(Arc::clone(&self.router), vec!["HGETALL".into(), name], None).await?;
+        let (fields, values) = resp_to_f64_map(&resp)?;
+        Python::attach(|py| Ok((fields, FloatArray::new(values).into_numpy(py)?.unbind())))
+    }
+
+    /// Set one or more hash fields (see :meth:`Redis.hset`).
+    #[pyo3(signature = (name, key=None, value=None, mapping=None, *, timeout=None))]
+    async fn hset(
+        &self,
+        name: String,
+        key: Option<String>,
+        value: Option<String>,
+        mapping: Option<Py<PyAny>>,
+        timeout: Option<f64>,
+    ) -> PyResult<Py<PyAny>> {
+        let limit = router::command_limit(timeout)?;
+        let cmd = Python::attach(|py| hset_args(name, key, value, mapping.as_ref().map(|m| m.bind(py))))?;
+        self.exec_shaped(cmd, None, limit).await
+    }
+
+    /// Delete one or more hash fields.
+    #[pyo3(signature = (name, *keys))]
+    fn hdel<'py>(slf: &Bound<'py, Self>, name: String, keys: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["HDEL".into(), name];
+        cmd.extend(keys);
+        Self::command(slf, cmd)
+    }
+
+    /// Increment the integer value of a hash field.
+    async fn hincrby(&self, name: String, key: String, amount: i64) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["HINCRBY".into(), name, key, amount.to_string()]).await
+    }
+
+    /// Increment the float value of a hash field.
+    async fn hincrbyfloat(&self, name: String, key: String, amount: f64) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["HINCRBYFLOAT".into(), name, key, amount.to_string()]).await
+    }
+
+    /// Set the value of a hash field only if it does not exist.
+    async fn hsetnx(&self, name: String, key: String, value: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["HSETNX".into(), name, key, value]).await
+    }
+
+    /// Get values of multiple hash fields.
+    #[pyo3(signature = (name, *keys))]
+    fn hmget<'py>(slf: &Bound<'py, Self>, name: String, keys: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["HMGET".into(), name];
+        cmd.extend(keys);
+        Self::command(slf, cmd)
+    }
+
+    /// Return random fields of a hash (see :meth:`Redis.hrandfield`).
+    #[pyo3(signature = (name, count=None, withvalues=false))]
+    async fn hrandfield(&self, name: String, count: Option<i64>, withvalues: bool) -> PyResult<Py<PyAny>> {
+        let cmd = hrandfield_args(name, count, withvalues)?;
+        self.exec_shaped(cmd, withvalues.then_some(ReplyShape::FieldPairs), None).await
+    }
+
+    // ── List commands ──────────────────────────────────────────────
+
+    /// Prepend one or more values to a list.
+    #[pyo3(signature = (name, *values))]
+    fn lpush<'py>(slf: &Bound<'py, Self>, name: String, values: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["LPUSH".into(), name];
+        cmd.extend(values);
+        Self::command(slf, cmd)
+    }
+
+    /// Append one or more values to a list.
+    #[pyo3(signature = (name, *values))]
+    fn rpush<'py>(slf: &Bound<'py, Self>, name: String, values: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["RPUSH".into(), name];
+        cmd.extend(values);
+        Self::command(slf, cmd)
+    }
+
+    /// Get a range of elements from a list.
+    async fn lrange(&self, name: String, start: i64, stop: i64) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["LRANGE".into(), name, start.to_string(), stop.to_string()]).await
+    }
+
+    /// Remove and return the first element(s) of a list.
+    #[pyo3(signature = (name, count=None))]
+    async fn lpop(&self, name: String, count: Option<u64>) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["LPOP".into(), name];
+        if let Some(c) = count {
+            cmd.push(c.to_string());
+        }
+        self.exec(cmd).await
+    }
+
+    /// Remove and return the last element(s) of a list.
+    #[pyo3(signature = (name, count=None))]
+    async fn rpop(&self, name: String, count: Option<u64>) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["RPOP".into(), name];
+        if let Some(c) = count {
+            cmd.push(c.to_string());
+        }
+        self.exec(cmd).await
+    }
+
+    /// Get an element from a list by its index.
+    async fn lindex(&self, name: String, index: i64) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["LINDEX".into(), name, index.to_string()]).await
+    }
+
+    /// Set the value of a list element by its index.
+    async fn lset(&self, name: String, index: i64, value: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["LSET".into(), name, index.to_string(), value]).await
+    }
+
+    /// Remove elements from a list.
+    async fn lrem(&self, name: String, count: i64, value: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["LREM".into(), name, count.to_string(), value]).await
+    }
+
+    /// Find the index of an element in a list (see :meth:`Redis.lpos`).
+    #[pyo3(signature = (name, value, rank=None, count=None, maxlen=None))]
+    async fn lpos(&self, name: String, value: String, rank: Option<i64>, count: Option<u64>, maxlen: Option<u64>) -> PyResult<Py<PyAny>> {
+        self.exec(lpos_args(name, value, rank, count, maxlen)?).await
+    }
+
+    /// Insert an element before or after another element in a list.
+    #[pyo3(signature = (name, r#where, refvalue, value))]
+    async fn linsert(&self, name: String, r#where: String, refvalue: String, value: String) -> PyResult<Py<PyAny>> {
+        self.exec(linsert_args(name, &r#where, refvalue, value)?).await
+    }
+
+    /// Move an element between lists (see :meth:`Redis.lmove`).
+    #[pyo3(signature = (src, dst, wherefrom="LEFT".to_string(), whereto="RIGHT".to_string()))]
+    async fn lmove(&self, src: String, dst: String, wherefrom: String, whereto: String) -> PyResult<Py<PyAny>> {
+        self.exec(lmove_args(src, dst, &wherefrom, &whereto, self.router.is_cluster())?).await
+    }
+
+    /// Pop elements from the first non-empty list (see :meth:`Redis.lmpop`).
+    #[pyo3(signature = (keys, direction="LEFT".to_string(), count=None))]
+    async fn lmpop(&self, keys: Vec<String>, direction: String, count: Option<u64>) -> PyResult<Py<PyAny>> {
+        self.exec(lmpop_args(keys, &direction, count, self.router.is_cluster())?).await
+    }
+
+    // ── Blocking commands ──────────────────────────────────────────
+
+    /// Pop the first element of the first non-empty list, waiting up to
+    /// ``timeout`` seconds (``0`` waits forever). Returns ``[key, element]``
+    /// or ``None``.
+    #[pyo3(signature = (keys, timeout=0.0))]
+    async fn blpop(&self, keys: Keys, timeout: f64) -> PyResult<Py<PyAny>> {
+        self.exec_blocking(blocking::pop_args("BLPOP", keys, timeout)?).await
+    }
+
+    /// Like :meth:`blpop`, popping from the tail.
+    #[pyo3(signature = (keys, timeout=0.0))]
+    async fn brpop(&self, keys: Keys, timeout: f64) -> PyResult<Py<PyAny>> {
+        self.exec_blocking(blocking::pop_args("BRPOP", keys, timeout)?).await
+    }
+
+    /// Pop the last element of ``src`` and push it onto ``dst``, waiting up
+    /// to ``timeout`` seconds.
+    #[pyo3(signature = (src, dst, timeout=0.0))]
+    async fn brpoplpush(&self, src: String, dst: String, timeout: f64) -> PyResult<Py<PyAny>> {
+        self.exec_blocking(blocking::brpoplpush_args(&src, &dst, timeout)?).await
+    }
+
+    /// Move an element between lists, waiting up to ``timeout`` seconds.
+    #[pyo3(signature = (src, dst, wherefrom="LEFT".to_string(), whereto="RIGHT".to_string(), timeout=0.0))]
+    async fn blmove(&self, src: String, dst: String, wherefrom: String, whereto: String, timeout: f64) -> PyResult<Py<PyAny>> {
+        self.exec_blocking(blocking::blmove_args(&src, &dst, &wherefrom, &whereto, timeout)?).await
+    }
+
+    /// Pop the lowest-scored member of the first non-empty sorted set,
+    /// waiting up to ``timeout`` seconds. Returns ``(key, member, score)``
+    /// with a float score, or ``None``.
+    #[pyo3(signature = (keys, timeout=0.0))]
+    async fn bzpopmin(&self, keys: Keys, timeout: f64) -> PyResult<Py<PyAny>> {
+        let reply = self.exec_blocking(blocking::pop_args("BZPOPMIN", keys, timeout)?).await?;
+        Python::attach(|py| Ok(ReplyShape::PoppedMember.apply(reply.into_bound(py))?.unbind()))
+    }
+
+    /// Like :meth:`bzpopmin`, popping the highest-scored member.
+    #[pyo3(signature = (keys, timeout=0.0))]
+    async fn bzpopmax(&self, keys: Keys, timeout: f64) -> PyResult<Py<PyAny>> {
+        let reply = self.exec_blocking(blocking::pop_args("BZPOPMAX", keys, timeout)?).await?;
+        Python::attach(|py| Ok(ReplyShape::PoppedMember.apply(reply.into_bound(py))?.unbind()))
+    }
+
+    // ── Set commands ───────────────────────────────────────────────
+
+    /// Add one or more members to a set.
+    #[pyo3(signature = (name, *members))]
+    fn sadd<'py>(slf: &Bound<'py, Self>, name: String, members: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["SADD".into(), name];
+        cmd.extend(members);
+        Self::command(slf, cmd)
+    }
+
+    /// Remove one or more members from a set.
+    #[pyo3(signature = (name, *members))]
+    fn srem<'py>(slf: &Bound<'py, Self>, name: String, members: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["SREM".into(), name];
+        cmd.extend(members);
+        Self::command(slf, cmd)
+    }
+
+    /// Remove and return random member(s) from a set.
+    #[pyo3(signature = (name, count=None))]
+    async fn spop(&self, name: String, count: Option<u64>) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["SPOP".into(), name];
+        if let Some(c) = count {
+            cmd.push(c.to_string());
+        }
+        self.exec(cmd).await
+    }
+
+    /// Return the intersection of multiple sets.
+    #[pyo3(signature = (*names))]
+    fn sinter<'py>(slf: &Bound<'py, Self>, names: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["SINTER".into()];
+        cmd.extend(names);
+        Self::command(slf, cmd)
+    }
+
+    /// Return the union of multiple sets.
+    #[pyo3(signature = (*names))]
+    fn sunion<'py>(slf: &Bound<'py, Self>, names: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["SUNION".into()];
+        cmd.extend(names);
+        Self::command(slf, cmd)
+    }
+
+    /// Return the difference of multiple sets.
+    #[pyo3(signature = (*names))]
+    fn sdiff<'py>(slf: &Bound<'py, Self>, names: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["SDIFF".into()];
+        cmd.extend(names);
+        Self::command(slf, cmd)
+    }
+
+    /// Store the intersection of multiple sets in ``dest``.
+    #[pyo3(signature = (dest, *keys))]
+    fn sinterstore<'py>(slf: &Bound<'py, Self>, dest: String, keys: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let cmd = set_store_args("SINTERSTORE", dest, keys, slf.borrow().router.is_cluster())?;
+        Self::command(slf, cmd)
+    }
+
+    /// Store the union of multiple sets in ``dest``.
+    #[pyo3(signature = (dest, *keys))]
+    fn sunionstore<'py>(slf: &Bound<'py, Self>, dest: String, keys: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let cmd = set_store_args("SUNIONSTORE", dest, keys, slf.borrow().router.is_cluster())?;
+        Self::command(slf, cmd)
+    }
+
+    /// Store the difference of multiple sets in ``dest``.
+    #[pyo3(signature = (dest, *keys))]
+    fn sdiffstore<'py>(slf: &Bound<'py, Self>, dest: String, keys: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let cmd = set_store_args("SDIFFSTORE", dest, keys, slf.borrow().router.is_cluster())?;
+        Self::command(slf, cmd)
+    }
+
+    /// Move a member from one set to another.
+    async fn smove(&self, src: String, dst: String, value: String) -> PyResult<Py<PyAny>> {
+        self.exec(smove_args(src, dst, value, self.router.is_cluster())?).await
+    }
+
+    /// Check which of ``values`` are members of a set.
+    #[pyo3(signature = (name, *values))]
+    fn smismember<'py>(slf: &Bound<'py, Self>, name: String, values: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        Self::command(slf, smismember_args(name, values)?)
+    }
+
+    /// Return random members of a set (see :meth:`Redis.srandmember`).
+    #[pyo3(signature = (name, count=None))]
+    async fn srandmember(&self, name: String, count: Option<i64>) -> PyResult<Py<PyAny>> {
+        self.exec(srandmember_args(name, count)).await
+    }
+
+    /// Count the members of the intersection of ``keys`` (see :meth:`Redis.sintercard`).
+    #[pyo3(signature = (keys, limit=None))]
+    async fn sintercard(&self, keys: Vec<String>, limit: Option<u64>) -> PyResult<Py<PyAny>> {
+        self.exec(sintercard_args(keys, limit, self.router.is_cluster())?).await
+    }
+
+    // ── Sorted set commands ────────────────────────────────────────
+
+    /// Add members to a sorted set (see :meth:`Redis.zadd`).
+    #[pyo3(signature = (name, mapping, nx=false, xx=false, gt=false, lt=false, ch=false))]
+    async fn zadd(
+        &self,
+        name: String,
+        mapping: Py<PyAny>,
+        nx: bool,
+        xx: bool,
+        gt: bool,
+        lt: bool,
+        ch: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let cmd = Python::attach(|py| zadd_args(name, mapping.bind(py), nx, xx, gt, lt, ch, self.strict_numbers))?;
+        self.exec(cmd).await
+    }
+
+    /// Remove one or more members from a sorted set.
+    #[pyo3(signature = (name, *members))]
+    fn zrem<'py>(slf: &Bound<'py, Self>, name: String, members: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["ZREM".into(), name];
+        cmd.extend(members);
+        Self::command(slf, cmd)
+    }
+
+    /// Count members in a sorted set with scores within a range.
+    async fn zcount(&self, name: String, min: String, max: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["ZCOUNT".into(), name, min, max]).await
+    }
+
+    /// Increment the score of a member in a sorted set.
+    async fn zincrby(&self, name: String, amount: Score, member: String) -> PyResult<Py<PyAny>> {
+        let amount = amount.checked(self.strict_numbers)?;
+        self.exec(vec!["ZINCRBY".into(), name, amount.to_string(), member]).await
+    }
+
+    /// Return a range of members from a sorted set by index.
+    #[pyo3(signature = (name, start, stop, withscores=false))]
+    async fn zrange(&self, name: String, start: i64, stop: i64, withscores: bool) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["ZRANGE".into(), name, start.to_string(), stop.to_string()];
+        if withscores {
+            cmd.push("WITHSCORES".into());
+        }
+        self.exec(cmd).await
+    }
+
+    /// Return a range of members from a sorted set by index (descending).
+    #[pyo3(signature = (name, start, stop, withscores=false))]
+    async fn zrevrange(&self, name: String, start: i64, stop: i64, withscores: bool) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["ZREVRANGE".into(), name, start.to_string(), stop.to_string()];
+        if withscores {
+            cmd.push("WITHSCORES".into());
+        }
+        self.exec(cmd).await
+    }
+
+    /// Return members with scores within a range.
+    #[pyo3(signature = (name, min, max, withscores=false, offset=None, count=None))]
+    async fn zrangebyscore(
+        &self,
+        name: String,
+        min: String,
+        max: String,
+        withscores: bool,
+        offset: Option<i64>,
+        count: Option<i64>,
+    ) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["ZRANGEBYSCORE".into(), name, min, max];
+        if withscores {
+            cmd.push("WITHSCORES".into());
+        }
+        if let (Some(o), Some(c)) = (offset, count) {
+            cmd.push("LIMIT".into());
+            cmd.push(o.to_string());
+            cmd.push(c.to_string());
+        }
+        self.exec(cmd).await
+    }
+
+    /// Remove members with scores within a range.
+    async fn zremrangebyscore(&self, name: String, min: String, max: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["ZREMRANGEBYSCORE".into(), name, min, max]).await
+    }
+
+    /// Remove members with rank within a range.
+    async fn zremrangebyrank(&self, name: String, start: i64, stop: i64) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["ZREMRANGEBYRANK".into(), name, start.to_string(), stop.to_string()]).await
+    }
+
+    /// Store the union of sorted sets in ``dest`` (see :meth:`Redis.zunionstore`).
+    #[pyo3(signature = (dest, keys, weights=None, aggregate=None))]
+    async fn zunionstore(&self, dest: String, keys: Vec<String>, weights: Option<Vec<f64>>, aggregate: Option<String>) -> PyResult<Py<PyAny>> {
+        let cmd = zset_combine_args("ZUNIONSTORE", Some(dest), keys, weights, aggregate.as_deref(), false, self.router.is_cluster())?;
+        self.exec(cmd).await
+    }
+
+    /// Store the intersection of sorted sets in ``dest``.
+    #[pyo3(signature = (dest, keys, weights=None, aggregate=None))]
+    async fn zinterstore(&self, dest: String, keys: Vec<String>, weights: Option<Vec<f64>>, aggregate: Option<String>) -> PyResult<Py<PyAny>> {
+        let cmd = zset_combine_args("ZINTERSTORE", Some(dest), keys, weights, aggregate.as_deref(), false, self.router.is_cluster())?;
+        self.exec(cmd).await
+    }
+
+    /// Return the union of sorted sets (see :meth:`Redis.zunion`).
+    #[pyo3(signature = (keys, weights=None, aggregate=None, withscores=false))]
+    async fn zunion(&self, keys: Vec<String>, weights: Option<Vec<f64>>, aggregate: Option<String>, withscores: bool) -> PyResult<Py<PyAny>> {
+        let cmd = zset_combine_args("ZUNION", None, keys, weights, aggregate.as_deref(), withscores, self.router.is_cluster())?;
+        self.exec_shaped(cmd, withscores.then_some(ReplyShape::ScorePairs), None).await
+    }
+
+    /// Return the intersection of sorted sets.
+    #[pyo3(signature = (keys, weights=None, aggregate=None, withscores=false))]
+    async fn zinter(&self, keys: Vec<String>, weights: Option<Vec<f64>>, aggregate: Option<String>, withscores: bool) -> PyResult<Py<PyAny>> {
+        let cmd = zset_combine_args("ZINTER", None, keys, weights, aggregate.as_deref(), withscores, self.router.is_cluster())?;
+        self.exec_shaped(cmd, withscores.then_some(ReplyShape::ScorePairs), None).await
+    }
+
+    /// Return the members of the first sorted set that are in none of the others.
+    #[pyo3(signature = (keys, withscores=false))]
+    async fn zdiff(&self, keys: Vec<String>, withscores: bool) -> PyResult<Py<PyAny>> {
+        let cmd = zset_combine_args("ZDIFF", None, keys, None, None, withscores, self.router.is_cluster())?;
+        self.exec_shaped(cmd, withscores.then_some(ReplyShape::ScorePairs), None).await
+    }
+
+    /// Store a range of ``name`` in ``dest`` (see :meth:`Redis.zrangestore`).
+    #[pyo3(signature = (dest, name, start, end, byscore=false, bylex=false, desc=false, offset=None, num=None))]
+    async fn zrangestore(
+        &self,
+        dest: String,
+        name: String,
+        start: CommandArg,
+        end: CommandArg,
+        byscore: bool,
+        bylex: bool,
+        desc: bool,
+        offset: Option<i64>,
+        num: Option<i64>,
+    ) -> PyResult<Py<PyAny>> {
+        let cmd = zrangestore_args(dest, name, start, end, byscore, bylex, desc, offset, num, self.router.is_cluster())?;
+        self.exec(cmd).await
+    }
+
+    /// Remove and return the lowest-scored members as ``(member, score)`` tuples.
+    #[pyo3(signature = (name, count=None))]
+    async fn zpopmin(&self, name: String, count: Option<u64>) -> PyResult<Py<PyAny>> {
+        self.exec_shaped(zpop_args("ZPOPMIN", name, count), Some(ReplyShape::ScorePairs), None).await
+    }
+
+    /// Like :meth:`zpopmin`, popping the highest-scored members.
+    #[pyo3(signature = (name, count=None))]
+    async fn zpopmax(&self, name: String, count: Option<u64>) -> PyResult<Py<PyAny>> {
+        self.exec_shaped(zpop_args("ZPOPMAX", name, count), Some(ReplyShape::ScorePairs), None).await
+    }
+
+    /// Pop members from the first non-empty sorted set (see :meth:`Redis.zmpop`).
+    #[pyo3(signature = (keys, min=false, max=false, count=None))]
+    async fn zmpop(&self, keys: Vec<String>, min: bool, max: bool, count: Option<u64>) -> PyResult<Py<PyAny>> {
+        let cmd = zmpop_args(keys, min, max, count, self.router.is_cluster())?;
+        self.exec_shaped(cmd, Some(ReplyShape::PoppedMembers), None).await
+    }
+
+    /// Return random members of a sorted set (see :meth:`Redis.zrandmember`).
+    #[pyo3(signature = (name, count=None, withscores=false))]
+    async fn zrandmember(&self, name: String, count: Option<i64>, withscores: bool) -> PyResult<Py<PyAny>> {
+        let cmd = zrandmember_args(name, count, withscores)?;
+        self.exec_shaped(cmd, withscores.then_some(ReplyShape::ScorePairs), None).await
+    }
+
+    /// Get the scores of several members as floats, ``None`` for a missing member.
+    #[pyo3(signature = (name, *members))]
+    fn zmscore<'py>(slf: &Bound<'py, Self>, name: String, members: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        slf.call_method1(intern!(slf.py(), "_zmscore"), (name, members))
+    }
+
+    /// Coroutine behind :meth:`zmscore`.
+    async fn _zmscore(&self, name: String, members: Vec<String>) -> PyResult<Py<PyAny>> {
+        self.exec_shaped(zmscore_args(name, members)?, Some(ReplyShape::Scores), None).await
+    }
+
+    // ── Geo commands ───────────────────────────────────────────────
+
+    /// Add members with their positions to a geo index (see :meth:`Redis.geoadd`).
+    #[pyo3(signature = (name, values, nx=false, xx=false, ch=false))]
+    async fn geoadd(&self, name: String, values: Vec<(f64, f64, String)>, nx: bool, xx: bool, ch: bool) -> PyResult<Py<PyAny>> {
+        self.exec(geo::geoadd_args(name, values, nx, xx, ch)?).await
+    }
+
+    /// Get the positions of members as ``(longitude, latitude)`` tuples.
+    #[pyo3(signature = (name, *members))]
+    fn geopos<'py>(slf: &Bound<'py, Self>, name: String, members: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        slf.call_method1(intern!(slf.py(), "_geopos"), (name, members))
+    }
+
+    /// Coroutine behind :meth:`geopos`.
+    async fn _geopos(&self, name: String, members: Vec<String>) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["GEOPOS".into(), name];
+        cmd.extend(members);
+        self.exec_shaped(cmd, Some(ReplyShape::GeoPositions), None).await
+    }
+
+    /// Get the distance between two members as a float.
+    #[pyo3(signature = (name, member1, member2, unit=None))]
+    async fn geodist(&self, name: String, member1: String, member2: String, unit: Option<String>) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["GEODIST".into(), name, member1, member2];
+        if let Some(u) = unit {
+            cmd.push(geo::unit(&u)?);
+        }
+        self.exec_shaped(cmd, Some(ReplyShape::GeoDistance), None).await
+    }
+
+    /// Find the members within an area (see :meth:`Redis.geosearch`).
+    #[pyo3(signature = (name, member=None, longitude=None, latitude=None, radius=None, width=None, height=None, unit="m".to_string(), sort=None, count=None, any=false, withcoord=false, withdist=false, withhash=false))]
+    async fn geosearch(&self, name: String, member: Option<String>, longitude: Option<f64>, latitude: Option<f64>, radius: Option<f64>, width: Option<f64>, height: Option<f64>, unit: String, sort: Option<String>, count: Option<u64>, any: bool, withcoord: bool, withdist: bool, withhash: bool) -> PyResult<Py<PyAny>> {
+        let area = geo::Area { member, longitude, latitude, radius, width, height };
+        let cmd = geo::geosearch_args(name, area, &unit, sort.as_deref(), count, any, withcoord, withdist, withhash)?;
+        let shape = (withcoord || withdist || withhash).then_some(ReplyShape::GeoMatches { withcoord, withdist, withhash });
+        self.exec_shaped(cmd, shape, None).await
+    }
+
+    /// Store the members a search finds in ``source`` into ``dest``.
+    #[pyo3(signature = (dest, source, member=None, longitude=None, latitude=None, radius=None, width=None, height=None, unit="m".to_string(), sort=None, count=None, any=false, storedist=false))]
+    async fn geosearchstore(&self, dest: String, source: String, member: Option<String>, longitude: Option<f64>, latitude: Option<f64>, radius: Option<f64>, width: Option<f64>, height: Option<f64>, unit: String, sort: Option<String>, count: Option<u64>, any: bool, storedist: bool) -> PyResult<Py<PyAny>> {
+        let area = geo::Area { member, longitude, latitude, radius, width, height };
+        self.exec(geo::geosearchstore_args(dest, source, area, &unit, sort.as_deref(), count, any, storedist)?).await
+    }
+
+    // ── Key commands ───────────────────────────────────────────────
+
+    /// Set a timeout in milliseconds on a key, with the conditions of
+    /// :meth:`Redis.expire`.
+    #[pyo3(signature = (name, millis, nx=false, xx=false, gt=false, lt=false))]
+    #[allow(clippy::too_many_arguments)]
+    async fn pexpire(&self, name: String, millis: u64, nx: bool, xx: bool, gt: bool, lt: bool) -> PyResult<Py<PyAny>> {
+        self.exec(expire_args("PEXPIRE", name, millis, nx, xx, gt, lt)).await
+    }
+
+    /// Get the remaining time to live of a key in milliseconds.
+    async fn pttl(&self, name: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["PTTL".into(), name]).await
+    }
+
+    /// Incrementally iterate over keys matching a pattern.
+    #[pyo3(signature = (cursor=0, match_pattern=None, count=None))]
+    async fn scan(&self, cursor: u64, match_pattern: Option<String>, count: Option<u64>) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["SCAN".into(), cursor.to_string()];
+        if let Some(p) = match_pattern {
+            cmd.push("MATCH".into());
+            cmd.push(p);
+        }
+        if let Some(c) = count {
+            cmd.push("COUNT".into());
+            cmd.push(c.to_string());
+        }
+        self.exec(cmd).await
+    }
+
+    /// Incrementally iterate over the fields of a hash (see :meth:`Redis.hscan`).
+    #[pyo3(signature = (name, cursor=0, match_pattern=None, count=None))]
+    async fn hscan(&self, name: String, cursor: u64, match_pattern: Option<String>, count: Option<u64>) -> PyResult<Py<PyAny>> {
+        self.exec(scan_args(ScanKind::Hash, Some(&name), cursor, match_pattern.as_deref(), count)).await
+    }
+
+    // ── String commands ────────────────────────────────────────────
+
+    /// Get a substring of the string value stored at a key.
+    async fn getrange(&self, name: String, start: i64, end: i64) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["GETRANGE".into(), name, start.to_string(), end.to_string()]).await
+    }
+
+    /// Set the value of a key and return its old value.
+    async fn getset(&self, name: String, value: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["GETSET".into(), name, value]).await
+    }
+
+    /// Get the value of a key and delete it.
+    async fn getdel(&self, name: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["GETDEL".into(), name]).await
+    }
+
+    /// Get the value of a key and set or clear its expiration (see
+    /// :meth:`Redis.getex`).
+    #[pyo3(signature = (name, ex=None, px=None, exat=None, pxat=None, persist=false))]
+    async fn getex(
+        &self,
+        name: String,
+        ex: Option<u64>,
+        px: Option<u64>,
+        exat: Option<u64>,
+        pxat: Option<u64>,
+        persist: bool,
+    ) -> PyResult<Py<PyAny>> {
+        self.exec(getex_args(name, ex, px, exat, pxat, persist)?).await
+    }
+
+    /// Set the value and expiration of a key (atomic SETEX).
+    async fn setex(&self, name: String, seconds: u64, value: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["SETEX".into(), name, seconds.to_string(), value]).await
+    }
+
+    /// Increment the float value of a key.
+    async fn incrbyfloat(&self, name: String, amount: f64) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["INCRBYFLOAT".into(), name, amount.to_string()]).await
+    }
+
+    /// Decrement the integer value of a key by a given amount.
+    async fn decrby(&self, name: String, amount: i64) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["DECRBY".into(), name, amount.to_string()]).await
+    }
+
+    // ── Scripting ──────────────────────────────────────────────────
+
+    /// Evaluate a Lua script on the server.
+    #[pyo3(signature = (script, numkeys, *args))]
+    fn eval<'py>(slf: &Bound<'py, Self>, script: String, numkeys: u32, args: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["EVAL".into(), script, numkeys.to_string()];
+        cmd.extend(args);
+        Self::command(slf, cmd)
+    }
+
+    /// Evaluate a cached Lua script by its SHA1 hash.
+    #[pyo3(signature = (sha, numkeys, *args))]
+    fn evalsha<'py>(slf: &Bound<'py, Self>, sha: String, numkeys: u32, args: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["EVALSHA".into(), sha, numkeys.to_string()];
+        cmd.extend(args);
+        Self::command(slf, cmd)
+    }
+
+    /// Load a Lua script into the script cache; on every master in
+    /// cluster mode (see :meth:`Redis.script_load`).
+    async fn script_load(&self, script: String) -> PyResult<Py<PyAny>> {
+        self.exec_cluster_wide(vec!["SCRIPT".into(), "LOAD".into(), script]).await
+    }
+
+    // ── Functions ──────────────────────────────────────────────────
+
+    /// Load a library of functions; on every master in cluster mode (see
+    /// :meth:`Redis.function_load`).
+    #[pyo3(signature = (code, replace=false))]
+    async fn function_load(&self, code: String, replace: bool) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["FUNCTION".into(), "LOAD".into()];
+        if replace {
+            cmd.push("REPLACE".into());
+        }
+        cmd.push(code);
+        self.exec_cluster_wide(cmd).await
+    }
+
+    /// List the loaded libraries and their functions.
+    #[pyo3(signature = (library=None, with_code=false))]
+    async fn function_list(&self, library: Option<String>, with_code: bool) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["FUNCTION".into(), "LIST".into()];
+        if let Some(pattern) = library {
+            cmd.extend(["LIBRARYNAME".into(), pattern]);
+        }
+        if with_code {
+            cmd.push("WITHCODE".into());
+        }
+        self.exec(cmd).await
+    }
+
+    /// Serialize every loaded library (returns bytes).
+    async fn function_dump(&self) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["FUNCTION".into(), "DUMP".into()]).await
+    }
+
+    /// Restore libraries from a :meth:`function_dump` payload; on every
+    /// master in cluster mode (see :meth:`Redis.function_restore`).
+    #[pyo3(signature = (payload, policy=None))]
+    async fn function_restore(&self, payload: CommandArg, policy: Option<String>) -> PyResult<Py<PyAny>> {
+        let cmd = function_restore_args(payload, policy.as_deref())?;
+        self.exec_cluster_wide_bytes(cmd).await
+    }
+
+    /// Call a function loaded with :meth:`function_load`.
+    #[pyo3(signature = (function, numkeys, *args))]
+    fn fcall<'py>(slf: &Bound<'py, Self>, function: String, numkeys: u32, args: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["FCALL".into(), function, numkeys.to_string()];
+        cmd.extend(args);
+        Self::command(slf, cmd)
+    }
+
+    /// Call a read-only function (see :meth:`Redis.fcall_ro`).
+    #[pyo3(signature = (function, numkeys, *args))]
+    fn fcall_ro<'py>(slf: &Bound<'py, Self>, function: String, numkeys: u32, args: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["FCALL_RO".into(), function, numkeys.to_string()];
+        cmd.extend(args);
+        Self::command(slf, cmd)
+    }
+
+    // ── FalkorDB / Graph commands ──────────────────────────────────
+
+    /// Execute a Cypher query on a FalkorDB graph.
+    #[pyo3(signature = (graph, query, timeout=None, expected_response_size=None))]
+    async fn graph_query(
+        &self,
+        graph: String,
+        query: String,
+        timeout: Option<u64>,
+        expected_response_size: Option<usize>,
+    ) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["GRAPH.QUERY".into(), graph, query, "--compact".into()];
+        if let Some(ms) = timeout {
+            cmd.push(format!("timeout {ms}"));
+        }
+        self.exec_expecting(cmd, expected_response_size).await
+    }
+
+    /// Execute a read-only Cypher query on a FalkorDB graph.
+    #[pyo3(signature = (graph, query, timeout=None, expected_response_size=None))]
+    async fn graph_ro_query(
+        &self,
+        graph: String,
+        query: String,
+        timeout: Option<u64>,
+        expected_response_size: Option<usize>,
+    ) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["GRAPH.RO_QUERY".into(), graph, query, "--compact".into()];
+        if let Some(ms) = timeout {
+            cmd.push(format!("timeout {ms}"));
+        }
+        self.exec_expecting(cmd, expected_response_size).await
+    }
+
+    /// Execute a Cypher query and return a :class:`QueryResult`.
+    #[pyo3(signature = (graph, query, timeout=None, read_only=false, expected_response_size=None))]
+    async fn graph_query_result(
+        &self,
+        graph: String,
+        query: String,
+        timeout: Option<u64>,
+        read_only: bool,
+        expected_response_size: Option<usize>,
+    ) -> PyResult<QueryResult> {
+        let name = if read_only { "GRAPH.RO_QUERY" } else { "GRAPH.QUERY" };
+        let mut cmd = vec![name.into(), graph, query, "--compact".into()];
+        if let Some(ms) = timeout {
+            cmd.push(format!("timeout {ms}"));
+        }
+        let resp = run_value(Arc::clone(&self.router), cmd, expected_response_size).await?;
+        if let RespValue::Error(msg) = resp {
+            return Err(PyrsedisError::redis(msg).into());
+        }
+        Ok(QueryResult { result: parse_graph_result(&resp)? })
+    }
+
+    /// Return the execution plan for a query without executing it.
+    async fn graph_explain(&self, graph: String, query: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["GRAPH.EXPLAIN".into(), graph, query]).await
+    }
+
+    /// Execute a query and return the execution plan with profiling data.
+    async fn graph_profile(&self, graph: String, query: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["GRAPH.PROFILE".into(), graph, query]).await
+    }
+
+    /// Return the slow log for a graph.
+    async fn graph_slowlog(&self, graph: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["GRAPH.SLOWLOG".into(), graph]).await
+    }
+
+    /// Get or set a FalkorDB graph configuration parameter.
+    #[pyo3(signature = (action, name, value=None))]
+    async fn graph_config(&self, action: String, name: String, value: Option<String>) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["GRAPH.CONFIG".into(), action, name];
+        cmd.extend(value);
+        self.exec(cmd).await
+    }
+
+    // ── Server commands ────────────────────────────────────────────
+
+    /// Select the database with the given index.
+    async fn select(&self, db: u16) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["SELECT".into(), db.to_string()]).await
+    }
+
+    /// Delete all keys in the current database (see :meth:`Redis.flushdb`).
+    #[pyo3(signature = (mode=None, *, confirm=false, timeout=None))]
+    async fn flushdb(&self, mode: Option<String>, confirm: bool, timeout: Option<f64>) -> PyResult<Py<PyAny>> {
+        let limit = router::command_limit(timeout)?;
+        let cmd = flush_args("FLUSHDB", mode.as_deref(), confirm, self.allow_destructive)?;
+        self.exec_shaped(cmd, None, limit).await
+    }
+
+    /// Delete all keys in all databases; on every master in cluster mode
+    /// (see :meth:`Redis.flushall`).
+    #[pyo3(signature = (mode=None, *, confirm=false))]
+    async fn flushall(&self, mode: Option<String>, confirm: bool) -> PyResult<Py<PyAny>> {
+        let cmd = flush_args("FLUSHALL", mode.as_deref(), confirm, self.allow_destructive)?;
+        self.exec_cluster_wide(cmd).await
+    }
+
+    /// Get the server configuration parameters matching ``pattern`` as a
+    /// dict (see :meth:`Redis.config_get`).
+    #[pyo3(signature = (pattern="*".to_string()))]
+    async fn config_get(&self, pattern: String) -> PyResult<Py<PyAny>> {
+        self.exec_shaped(vec!["CONFIG".into(), "GET".into(), pattern], Some(ReplyShape::Dict), None).await
+    }
+
+    /// Set a server configuration parameter; on every master in cluster
+    /// mode (see :meth:`Redis.config_set`).
+    async fn config_set(&self, name: String, value: String) -> PyResult<Py<PyAny>> {
+        self.exec_cluster_wide(vec!["CONFIG".into(), "SET".into(), name, value]).await
+    }
+
+    /// Return a random key from the database.
+    async fn randomkey(&self) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["RANDOMKEY".into()]).await
+    }
+
+    /// Return the UNIX timestamp of the last successful DB save.
+    async fn lastsave(&self) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["LASTSAVE".into()]).await
+    }
+
+    /// Publish many messages in a single round-trip, with ``SPUBLISH`` in
+    /// cluster mode (see :meth:`Redis.publish_many`).
+    async fn publish_many(&self, messages: Vec<(String, String)>) -> PyResult<Py<PyAny>> {
+        let commands = publish_commands(messages, self.router.is_cluster());
+        run_batch(Arc::clone(&self.router), commands, self.parse_options()).await
+    }
+
+    /// Set an expiration timestamp (UNIX seconds) on a key, with the
+    /// conditions of :meth:`Redis.expire`.
+    #[pyo3(signature = (name, when, nx=false, xx=false, gt=false, lt=false))]
+    #[allow(clippy::too_many_arguments)]
+    async fn expireat(&self, name: String, when: u64, nx: bool, xx: bool, gt: bool, lt: bool) -> PyResult<Py<PyAny>> {
+        self.exec(expire_args("EXPIREAT", name, when, nx, xx, gt, lt)).await
+    }
+
+    /// Serialize the value stored at a key (returns bytes).
+    async fn dump(&self, name: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["DUMP".into(), name]).await
+    }
+
+    /// Unlink (async-delete) one or more keys.
+    #[pyo3(signature = (*names))]
+    fn unlink<'py>(slf: &Bound<'py, Self>, names: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+        let mut cmd = vec!["UNLINK".into()];
+        cmd.extend(names);
+        Self::command(slf, cmd)
+    }
+
+    /// Return the server time as ``[seconds, microseconds]``.
+    async fn time(&self) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["TIME".into()]).await
+    }
+
+    /// Seconds to add to the local clock to get the server's (see
+    /// :meth:`Redis.time_offset`).
+    #[pyo3(signature = (refresh=false))]
+    async fn time_offset(&self, refresh: bool) -> PyResult<f64> {
+        let (router, clock) = (Arc::clone(&self.router), Arc::clone(&self.clock));
+        run(async move { clock.offset(&router, refresh).await }).await
+    }
+
+    /// The server's current time in Unix seconds.
+    async fn server_now(&self) -> PyResult<f64> {
+        let (router, clock) = (Arc::clone(&self.router), Arc::clone(&self.clock));
+        run(async move { clock.now(&router).await }).await
+    }
+
+    /// Find all keys matching the given pattern.
+    #[pyo3(signature = (pattern="*".to_string()))]
+    async fn keys(&self, pattern: String) -> PyResult<Py<PyAny>> {
+        self.exec(vec!["KEYS".into(), pattern]).await
+    }
+
+    /// Return information and statistics about the server.
+    #[pyo3(signature = (section=None))]
+    async fn info(&self, section: Option<String>) -> PyResult<Py<PyAny>> {
+        let mut cmd = vec!["INFO".into()];
+        cmd.extend(section);
+        self.exec(cmd).await
+    }
+
+    // ── Sentinel commands ──────────────────────────────────────────
+
+    /// The masters the sentinels monitor (see :meth:`Redis.sentinel_masters`).
+    async fn sentinel_masters(&self) -> PyResult<Py<PyAny>> {
+        let router = Arc::clone(&self.router);
+        let reply = run(async move { router.execute_sentinel(&["SENTINEL", "MASTERS"]).await }).await?;
+        Python::attach(|py| Ok(sentinel_entries(py, &reply)?.into_any().unbind()))
+    }
+
+    /// The sentinels' state of master `name`, as a dict of strings.
+    async fn sentinel_master(&self, name: String) -> PyResult<Py<PyAny>> {
+        let router = Arc::clone(&self.router);
+        let reply = run(async move { router.execute_sentinel(&["SENTINEL", "MASTER", &name]).await }).await?;
+        Python::attach(|py| Ok(sentinel_entry(py, &reply)?.into_any().unbind()))
+    }
+
+    /// The replicas of master `name`, one dict of strings per replica.
+    async fn sentinel_replicas(&self, name: String) -> PyResult<Py<PyAny>> {
+        let router = Arc::clone(&self.router);
+        let reply = run(async move { router.execute_sentinel(&["SENTINEL", "REPLICAS", &name]).await }).await?;
+        Python::attach(|py| Ok(sentinel_entries(py, &reply)?.into_any().unbind()))
+    }
+
+    /// The address of master `name` as ``(host, port)``, or ``None``.
+    async fn sentinel_get_master_addr(&self, name: String) -> PyResult<Py<PyAny>> {
+        let router = Arc::clone(&self.router);
+        let reply = run(async move { router.execute_sentinel(&["SENTINEL", "GET-MASTER-ADDR-BY-NAME", &name]).await }).await?;
+        Python::attach(|py| sentinel_addr(py, &reply))
+    }
+
+    // ── Cluster resharding ─────────────────────────────────────────
+
+    /// Set the migration state of a hash slot (see :meth:`Redis.cluster_setslot`).
+    #[pyo3(signature = (slot, state, node_id=None, node=None))]
+    async fn cluster_setslot(
+        &self,
+        slot: u16,
+        state: String,
+        node_id: Option<String>,
+        node: Option<String>,
+    ) -> PyResult<Py<PyAny>> {
+        let cmd = reshard::setslot_args(slot, &state, node_id.as_deref())?;
+        let args = cmd.into_iter().map(|s| CommandArg(s.into_bytes())).collect();
+        self._execute(args, Some(node.map_or(Route::Slot(slot), Route::Node)), None, None, None).await
+    }
+
+    /// Up to ``count`` keys of a hash slot (see :meth:`Redis.cluster_getkeysinslot`).
+    #[pyo3(signature = (slot, count, node=None))]
+    async fn cluster_getkeysinslot(&self, slot: u16, count: u64, node: Option<String>) -> PyResult<Py<PyAny>> {
+        reshard::check_slot(slot)?;
+        let args = ["CLUSTER".to_string(), "GETKEYSINSLOT".to_string(), slot.to_string(), count.to_string()]
+            .into_iter()
+            .map(|s| CommandArg(s.into_bytes()))
+            .collect();
+        self._execute(args, Some(node.map_or(Route::Slot(slot), Route::Node)), None, None, None).await
+    }
+
+    /// Move a hash slot and its keys to another master (see
+    /// :meth:`Redis.migrate_slot`).
+    #[pyo3(signature = (slot, from_node, to_node, batch=100, timeout_ms=5000, progress=None))]
+    async fn migrate_slot(
+        &self,
+        slot: u16,
+        from_node: String,
+        to_node: String,
+        batch: usize,
+        timeout_ms: u64,
+        progress: Option<Py<PyAny>>,
+    ) -> PyResult<usize> {
+        reshard::check_migration(slot, &from_node, &to_node, batch)?;
+        let router = Arc::clone(&self.router);
+        let (from, to) = (from_node.clone(), to_node.clone());
+        let to_id = run(async move { reshard::begin(&router, slot, &from, &to).await }).await?;
+        let mut moved = 0;
+        loop {
+            let router = Arc::clone(&self.router);
+            let (from, to) = (from_node.clone(), to_node.clone());
+            match run(async move { reshard::migrate_batch(&router, slot, &from, &to, batch, timeout_ms).await }).await? {
+                0 => break,
+                n => moved += n,
+            }
+            Python::attach(|py| ingest::report(progress.as_ref().map(|p| p.bind(py)), moved))?;
+        }
+        let router = Arc::clone(&self.router);
+        run(async move { reshard::finish(&router, slot, &from_node, &to_node, &to_id).await }).await?;
+        Ok(moved)
+    }
+
+    /// Promote a replica to master of its slots (see
+    /// :meth:`Redis.cluster_failover`).
+    #[pyo3(signature = (node, force=false, takeover=false))]
+    async fn cluster_failover(&self, node: String, force: bool, takeover: bool) -> PyResult<Py<PyAny>> {
+        let cmd = reshard::failover_args(force, takeover)?;
+        let args = cmd.into_iter().map(|s| CommandArg(s.into_bytes())).collect();
+        self._execute(args, Some(Route::Node(node)), None, None, None).await
+    }
+
+    /// Stop routing reads and key-less commands to a node before
+    /// maintenance (see :meth:`Redis.drain_node`).
+    fn drain_node(&self, addr: &str) -> PyResult<()> {
+        Ok(self.router.drain_node(addr)?)
+    }
+
+    /// Route to a drained node again (see :meth:`Redis.undrain_node`).
+    fn undrain_node(&self, addr: &str) -> bool {
+        self.router.undrain_node(addr)
+    }
+
+    /// The ``"host:port"`` of the nodes being drained.
+    #[getter]
+    fn draining_nodes(&self) -> Vec<String> {
+        self.router.draining_nodes()
+    }
+
+    // ── Pool introspection ─────────────────────────────────────────
+
+    /// Number of idle connections in the pool.
+    #[getter]
+    fn pool_idle_count(&self) -> usize {
+        self.router.pool_idle_count()
+    }
+
+    /// Number of available connection slots (idle + free permits).
+    #[getter]
+    fn pool_available(&self) -> usize {
+        self.router.pool_available()
+    }
+
+    /// Connection pool statistics (see :meth:`Redis.pool_stats`).
+    fn pool_stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        stats_to_python(py, &self.router.pool_stats())
+    }
+
+    /// Command latency by family (see :meth:`Redis.command_stats`).
+    fn command_stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        command_stats_to_python(py, &self.command_stats)
+    }
+
+    /// Zero the counters reported by :meth:`command_stats`.
+    fn reset_command_stats(&self) {
+        self.command_stats.reset();
+    }
+
+    /// RESP protocol version negotiated with the server, or ``None`` before
+    /// the first connection is made. See :attr:`Redis.protocol_version`.
+    #[getter]
+    fn protocol_version(&self) -> Option<u8> {
+        match self.protocol.load(Ordering::Relaxed) {
+            0 => None,
+            version => Some(version),
+        }
+    }
+
+    /// Open connections until ``count`` are idle in the pool (see
+    /// :meth:`Redis.warmup`).
+    #[pyo3(signature = (count=None))]
+    async fn warmup(&self, count: Option<usize>) -> PyResult<usize> {
+        let router = Arc::clone(&self.router);
+        run(async move { router.warmup(count.unwrap_or(usize::MAX)).await }).await
+    }
+
+    /// Close all pooled connections.
+    fn disconnect_all(&self) {
+        self.router.disconnect_all();
+    }
+
+    /// Recent connection lifecycle events, oldest first.
+    ///
+    /// See :meth:`Redis.recent_events`.
+    #[pyo3(signature = (limit=None))]
+    fn recent_events(&self, py: Python<'_>, limit: Option<usize>) -> PyResult<Vec<Py<PyAny>>> {
+        events_to_python(py, &self.events, limit)
+    }
+
+    /// Call ``callback(event)`` for each lifecycle event as it happens.
+    ///
+    /// See :meth:`Redis.set_event_callback`. The callback is a plain
+    /// function, not a coroutine, and runs on a pyrsedis I/O thread.
+    #[pyo3(signature = (callback=None))]
+    fn set_event_callback(&self, callback: Option<Bound<'_, PyAny>>) -> PyResult<()> {
+        set_event_callback(&self.events, callback)
+    }
+
+    /// Hexdump of the raw frames captured with ``capture_frames``.
+    ///
+    /// See :meth:`Redis.dump_frames`.
+    #[pyo3(signature = (limit=None))]
+    fn dump_frames(&self, limit: Option<usize>) -> String {
+        self.frames.as_ref().map_or_else(String::new, |frames| frames.dump(limit))
+    }
+
+    /// Close all pooled connections (awaitable alias of :meth:`disconnect_all`).
+    async fn aclose(&self) {
+        self.router.disconnect_all();
+    }
+
+    async fn __aenter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (_exc_type=None, _exc_value=None, _traceback=None))]
+    async fn __aexit__(
+        &self,
+        _exc_type: Option<Py<PyAny>>,
+        _exc_value: Option<Py<PyAny>>,
+        _traceback: Option<Py<PyAny>>,
+    ) -> bool {
+        self.router.disconnect_all();
+        false
+    }
+
+    fn __repr__(&self) -> String {
+        format!("AsyncRedis(addr='{}')", self.addr)
+    }
+
+    fn __str__(&self) -> String {
+        format!("AsyncRedis<{}>", self.addr)
+    }
+}
+});
+
+// ── AsyncPipeline ──────────────────────────────────────────────────
+
+/// A :class:`Pipeline` whose :meth:`execute` is a coroutine.
+///
+/// Created by :meth:`AsyncRedis.pipeline`. Command methods are inherited
+/// from :class:`Pipeline` and only buffer locally.
+///
+/// ```python
+/// pipe = r.pipeline()
+/// pipe.set("a", "1").incr("a")
+/// await pipe.execute()  # [True, 2]
+/// ```
+#[pyclass(name = "AsyncPipeline", extends = Pipeline)]
+pub struct AsyncPipeline;
+
+#[pymethods]
+impl AsyncPipeline {
+    /// Send all buffered commands in one round-trip.
+    ///
+    /// Returns:
+    ///     A list of responses, one per buffered command.
+    ///
+    /// Raises:
+    ///     TransactionError: In transaction mode, if the server aborted the
+    ///         transaction.
+    ///     WatchError: If a key passed to :meth:`Pipeline.watch` changed.
+    async fn execute(slf: Py<Self>) -> PyResult<Py<PyAny>> {
+        let (router, commands, opts, transaction, watched, shapes) = Python::attach(|py| {
+            let mut this = slf.bind(py).borrow_mut();
+            let base = this.as_super();
+            base.size_bytes = 0;
+            (
+                Arc::clone(&base.router),
+                std::mem::take(&mut base.commands),
+                base.parse_options(),
+                base.transaction,
+                base.watched.take(),
+                std::mem::take(&mut base.shapes),
+            )
+        });
+        let results = if let Some(lease) = watched {
+            let raw_responses = run(async move { router.transaction_on(lease, &commands).await }).await?;
+            Python::attach(|py| replies_to_list(py, &raw_responses, opts))?
+        } else if transaction {
+            run_transaction(router, commands, opts).await?
+        } else {
+            run_batch(router, commands, opts).await?
+        };
+        Python::attach(|py| streams::apply_shapes(results.bind(py), shapes))?;
+        Ok(results)
+    }
+
+    fn __repr__(slf: PyRef<'_, Self>) -> String {
+        let base = slf.as_super();
+        if base.transaction {
+            format!("AsyncPipeline(commands={}, transaction=True)", base.commands.len())
+        } else {
+            format!("AsyncPipeline(commands={})", base.commands.len())
+        }
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::TestClient;
+    use std::ffi::CString;
+
+    /// Reply to each request read from the socket with the next frame.
+    fn mock_server(replies: &'static [&'static [u8]]) -> u16 {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let mut replies = replies.iter();
+            while let Ok(n @ 1..) = socket.read(&mut buf) {
+                // Answer the `CLIENT SETINFO` pair sent on connect
+                if buf[..n].windows(7).any(|w| w == b"SETINFO") {
+                    socket.write_all(b"+OK\r\n+OK\r\n").unwrap();
+                    continue;
+                }
+                let Some(reply) = replies.next() else { break };
+                socket.write_all(reply).unwrap();
+            }
+            while socket.read(&mut buf).is_ok_and(|n| n > 0) {}
+        });
+        port
+    }
+
+    fn client(port: u16) -> AsyncRedis {
+        TestClient { port, pool_size: 1, connect_timeout_ms: 1000, read_timeout_ms: 2000, decode_responses: true, ..TestClient::default() }
+            .build_async()
+            .unwrap()
+    }
+
+    fn run_asyncio(py: Python<'_>, client: AsyncRedis, body: &str) {
+        let globals = PyDict::new(py);
+        globals.set_item("r", Py::new(py, client).unwrap()).unwrap();
+        let code = CString::new(format!(
+            "import asyncio\nasync def main():\n{}\nasyncio.run(main())\n",
+            body.lines().map(|l| format!("    {l}\n")).collect::<String>()
+        ))
+        .unwrap();
+        py.run(&code, Some(&globals), None).unwrap();
+    }
+
+    #[test]
+    fn commands_are_awaitable() {
+        let port = mock_server(&[b"+PONG\r\n", b":2\r\n", b"$1\r\nv\r\n"]);
+        Python::attach(|py| {
+            run_asyncio(
+                py,
+                client(port),
+                "assert await r.ping() is True\n\
+                 assert await r.delete('a', 'b') == 2\n\
+                 assert await r.get('a') == 'v'",
+            );
+        });
+    }
+
+    #[test]
+    fn pipeline_execute_is_awaitable() {
+        let port = mock_server(&[b"$1\r\n1\r\n:2\r\n"]);
+        Python::attach(|py| {
+            run_asyncio(
+                py,
+                client(port),
+                "pipe = r.pipeline()\n\
+                 pipe.get('a').incr('a')\n\
+                 assert repr(pipe) == 'AsyncPipeline(commands=2)'\n\
+                 assert await pipe.execute() == ['1', 2]\n\
+                 assert len(pipe) == 0\n\
+                 assert await pipe.execute() == []",
+            );
+        });
+    }
+
+    #[test]
+    fn pipeline_max_bytes_raises_on_add() {
+        let port = mock_server(&[b"$1\r\n1\r\n"]);
+        Python::attach(|py| {
+            run_asyncio(
+                py,
+                client(port),
+                "pipe = r.pipeline(max_bytes=20)\n\
+                 pipe.get('a')\n\
+                 assert pipe.size_bytes == 20 and pipe.max_bytes == 20\n\
+                 try:\n    pipe.get('b')\nexcept Exception as e:\n    assert type(e).__name__ == 'PipelineTooLargeError', e\n\
+                 else:\n    raise AssertionError('expected PipelineTooLargeError')\n\
+                 assert await pipe.execute() == ['1']\n\
+                 assert pipe.size_bytes == 0",
+            );
+        });
+    }
+
+    #[test]
+    fn errors_surface_on_await() {
+        Python::attach(|py| {
+            run_asyncio(
+                py,
+                client(1),
+                "coro = r.get('k')\n\
+                 try:\n    await coro\nexcept Exception as e:\n    assert 'connection' in str(e).lower(), e\n\
+                 else:\n    raise AssertionError('expected a connection error')\n\
+                 try:\n    await r.execute_command()\nexcept TypeError:\n    pass\n\
+                 else:\n    raise AssertionError('expected TypeError')",
+            );
+        });
+    }
+
+    #[test]
+    fn cancelled_runs_abort_their_task() {
+        let finished = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = Arc::clone(&finished);
+        let pending = run(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            flag.store(true, Ordering::SeqCst);
+            Ok(())
+        });
+        runtime::block_on(async {
+            // Poll once so the task is spawned, then drop it as a
+            // cancelled coroutine would
+            assert!(tokio::time::timeout(Duration::from_millis(1), pending).await.is_err());
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        });
+        assert!(!finished.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn async_redis_repr() {
+        let r = client(6379);
+        assert_eq!(r.__repr__(), "AsyncRedis(addr='127.0.0.1:6379')");
+        assert_eq!(r.pool_available(), 1);
+    }
+}
@@ -0,0 +1,148 @@
+//! Blocking list and sorted-set commands.
+//!
+//! BLPOP, BRPOP, BLMOVE, BRPOPLPUSH, BZPOPMIN and BZPOPMAX park the
+//! connection on the server until an element arrives or the command's own
+//! timeout expires. They run on a connection checked out for the whole
+//! call (see [`ClientRouter::execute_blocking`]) whose read timeout is
+//! stretched past the block duration, so a long wait is not mistaken for
+//! a dead server.
+//!
+//! [`ClientRouter::execute_blocking`]: crate::router::ClientRouter::execute_blocking
+
+use std::time::Duration;
+
+use pyo3::prelude::*;
+
+use crate::error::{PyrsedisError, Result};
+
+/// One key or a list of keys.
+#[derive(FromPyObject)]
+pub enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Keys {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::One(key) => vec![key],
+            Self::Many(keys) => keys,
+        }
+    }
+}
+
+// ── Timeouts ──────────────────────────────────────────────────────
+
+/// How long the server may block, from a timeout in seconds. Zero blocks
+/// forever.
+pub fn block_duration(timeout: f64) -> Result<Duration> {
+    Duration::try_from_secs_f64(timeout).map_err(|_| {
+        PyrsedisError::Type(format!("timeout must be a non-negative number of seconds, got {timeout}"))
+    })
+}
+
+/// The timeout argument as sent to the server: whole seconds when
+/// integral (accepted by every server version), decimal otherwise.
+fn timeout_arg(timeout: f64) -> String {
+    if timeout.fract() == 0.0 {
+        format!("{}", timeout as u64)
+    } else {
+        timeout.to_string()
+    }
+}
+
+/// The read timeout for a command blocking for `block`: the configured
+/// timeout on top of the block, or none at all when either is unbounded.
+pub fn blocking_read_timeout_ms(base_ms: u64, block: Duration) -> u64 {
+    if base_ms == 0 || block.is_zero() {
+        return 0;
+    }
+    base_ms.saturating_add(u64::try_from(block.as_millis()).unwrap_or(u64::MAX))
+}
+
+// ── Arguments ─────────────────────────────────────────────────────
+
+/// `command key [key ...] timeout` (BLPOP, BRPOP, BZPOPMIN, BZPOPMAX).
+pub fn pop_args(command: &str, keys: Keys, timeout: f64) -> Result<(Vec<String>, Duration)> {
+    let block = block_duration(timeout)?;
+    let keys = keys.into_vec();
+    if keys.is_empty() {
+        return Err(PyrsedisError::Type(format!("{command} needs at least one key")));
+    }
+    let mut cmd = Vec::with_capacity(keys.len() + 2);
+    cmd.push(command.to_string());
+    cmd.extend(keys);
+    cmd.push(timeout_arg(timeout));
+    Ok((cmd, block))
+}
+
+/// `BRPOPLPUSH source destination timeout`.
+pub fn brpoplpush_args(src: &str, dst: &str, timeout: f64) -> Result<(Vec<String>, Duration)> {
+    let block = block_duration(timeout)?;
+    Ok((vec!["BRPOPLPUSH".into(), src.into(), dst.into(), timeout_arg(timeout)], block))
+}
+
+/// A list end, `"LEFT"` or `"RIGHT"` in either case, as sent to the server.
+pub fn side(s: &str) -> Result<String> {
+    let upper = s.to_ascii_uppercase();
+    if upper == "LEFT" || upper == "RIGHT" {
+        Ok(upper)
+    } else {
+        Err(PyrsedisError::Type(format!("expected 'LEFT' or 'RIGHT', got '{s}'")))
+    }
+}
+
+/// `BLMOVE source destination LEFT|RIGHT LEFT|RIGHT timeout`.
+pub fn blmove_args(src: &str, dst: &str, wherefrom: &str, whereto: &str, timeout: f64) -> Result<(Vec<String>, Duration)> {
+    let block = block_duration(timeout)?;
+    Ok((
+        vec!["BLMOVE".into(), src.into(), dst.into(), side(wherefrom)?, side(whereto)?, timeout_arg(timeout)],
+        block,
+    ))
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeouts_are_validated_and_formatted() {
+        assert_eq!(block_duration(0.0).unwrap(), Duration::ZERO);
+        assert_eq!(block_duration(1.5).unwrap(), Duration::from_millis(1500));
+        assert!(block_duration(-1.0).is_err());
+        assert!(block_duration(f64::NAN).is_err());
+        assert!(block_duration(f64::INFINITY).is_err());
+        assert!(block_duration(1e20).is_err());
+        assert_eq!(timeout_arg(0.0), "0");
+        assert_eq!(timeout_arg(5.0), "5");
+        assert_eq!(timeout_arg(0.25), "0.25");
+    }
+
+    #[test]
+    fn read_timeout_covers_the_block() {
+        assert_eq!(blocking_read_timeout_ms(1000, Duration::from_secs(5)), 6000);
+        assert_eq!(blocking_read_timeout_ms(1000, Duration::ZERO), 0);
+        assert_eq!(blocking_read_timeout_ms(0, Duration::from_secs(5)), 0);
+    }
+
+    #[test]
+    fn pop_args_take_one_or_many_keys() {
+        let (cmd, block) = pop_args("BLPOP", Keys::One("q".into()), 2.0).unwrap();
+        assert_eq!(cmd, ["BLPOP", "q", "2"]);
+        assert_eq!(block, Duration::from_secs(2));
+        let (cmd, _) = pop_args("BZPOPMIN", Keys::Many(vec!["a".into(), "b".into()]), 0.5).unwrap();
+        assert_eq!(cmd, ["BZPOPMIN", "a", "b", "0.5"]);
+        assert!(pop_args("BRPOP", Keys::Many(vec![]), 0.0).is_err());
+    }
+
+    #[test]
+    fn move_args_check_sides() {
+        let (cmd, _) = blmove_args("a", "b", "left", "Right", 0.0).unwrap();
+        assert_eq!(cmd, ["BLMOVE", "a", "b", "LEFT", "RIGHT", "0"]);
+        assert!(blmove_args("a", "b", "up", "LEFT", 0.0).is_err());
+        let (cmd, _) = brpoplpush_args("a", "b", 1.0).unwrap();
+        assert_eq!(cmd, ["BRPOPLPUSH", "a", "b", "1"]);
+    }
+}
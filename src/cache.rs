@@ -0,0 +1,505 @@
+//! Client-side caching.
+//!
+//! `Redis(cache=True)` keeps `GET` replies in an in-process LRU cache.
+//! A background task holds one connection subscribed to
+//! `__redis__:invalidate` and publishes its client id in
+//! [`ConnectionConfig::tracking_redirect`](crate::config::ConnectionConfig::tracking_redirect);
+//! every pooled connection then turns on `CLIENT TRACKING ... REDIRECT`
+//! to it, so the server reports each key this client has read once it
+//! changes, and the entry is dropped.
+//!
+//! The cache only serves entries while that connection is up. When it
+//! is lost the cache is emptied and bypassed until tracking has been
+//! re-established.
+//!
+//! After `fork()` the child has neither the parent's background task nor
+//! its subscriber connection; [`reset_after_fork`] empties every cache and
+//! starts tracking again on the child's runtime.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+
+use crate::error::{PyrsedisError, Result};
+use crate::events::EventLog;
+use crate::pubsub::{self, MessageKind};
+use crate::resp::types::RespValue;
+use crate::resp::writer::encode_command_str;
+use crate::router::{ClientRouter, Router};
+use crate::runtime;
+
+/// Channel the server publishes key invalidations on.
+pub const INVALIDATE_CHANNEL: &str = "__redis__:invalidate";
+
+/// Wait before re-establishing tracking after the connection was lost.
+const RETRACK_BACKOFF: Duration = Duration::from_millis(500);
+
+// ── ClientCache ───────────────────────────────────────────────────
+
+/// A cached reply, or `None` while the `GET` that fills it is in flight.
+struct Entry {
+    seq: u64,
+    value: Option<(Bytes, Option<Instant>)>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    /// Whether invalidations are being received; nothing is cached otherwise.
+    active: bool,
+    entries: HashMap<Vec<u8>, Entry>,
+    /// Keys by last use, oldest first.
+    lru: BTreeMap<u64, Vec<u8>>,
+    next_seq: u64,
+}
+
+impl CacheState {
+    fn remove(&mut self, key: &[u8]) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.lru.remove(&entry.seq);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.lru.clear();
+    }
+}
+
+/// Raw `GET` replies keyed by key name, with LRU eviction and an
+/// optional TTL.
+///
+/// A miss is filled in two steps so an invalidation that arrives while
+/// the `GET` is in flight is not lost: [`reserve`](Self::reserve) marks
+/// the key before the command is sent and [`fill`](Self::fill) only
+/// stores the reply if the mark is still there.
+pub struct ClientCache {
+    state: Mutex<CacheState>,
+    max_entries: usize,
+    ttl: Option<Duration>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ClientCache {
+    /// Create an inactive cache holding at most `max_entries` keys.
+    pub fn new(max_entries: usize, ttl: Option<Duration>) -> Self {
+        Self {
+            state: Mutex::new(CacheState::default()),
+            max_entries,
+            ttl,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// The cached reply for `key`, if any.
+    pub fn get(&self, key: &[u8]) -> Option<Bytes> {
+        let mut state = self.state.lock();
+        if !state.active {
+            return None;
+        }
+        let cached = match state.entries.get(key) {
+            Some(Entry { value: Some((raw, expires)), seq }) => {
+                let expired = expires.is_some_and(|at| at <= Instant::now());
+                Some((raw.clone(), *seq, expired))
+            }
+            _ => None,
+        };
+        match cached {
+            Some((raw, seq, false)) => {
+                let next = state.next_seq;
+                state.next_seq += 1;
+                if let Some(entry) = state.entries.get_mut(key) {
+                    entry.seq = next;
+                }
+                if let Some(key) = state.lru.remove(&seq) {
+                    state.lru.insert(next, key);
+                }
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(raw)
+            }
+            Some((_, _, true)) => {
+                state.remove(key);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Mark `key` as being fetched. Returns the token to pass to
+    /// [`fill`](Self::fill), or `None` when the cache is inactive.
+    pub fn reserve(&self, key: &[u8]) -> Option<u64> {
+        let mut state = self.state.lock();
+        if !state.active {
+            return None;
+        }
+        state.remove(key);
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.entries.insert(key.to_vec(), Entry { seq, value: None });
+        state.lru.insert(seq, key.to_vec());
+        while state.entries.len() > self.max_entries {
+            let Some((_, oldest)) = state.lru.pop_first() else { break };
+            state.entries.remove(&oldest);
+        }
+        Some(seq)
+    }
+
+    /// Store the reply for a key reserved with `token`, unless it was
+    /// invalidated (or evicted) in the meantime.
+    pub fn fill(&self, key: &[u8], token: u64, raw: Bytes) {
+        let expires = self.ttl.map(|ttl| Instant::now() + ttl);
+        let mut state = self.state.lock();
+        if let Some(entry) = state.entries.get_mut(key) {
+            if entry.seq == token && entry.value.is_none() {
+                entry.value = Some((raw, expires));
+            }
+        }
+    }
+
+    /// Drop `keys` from the cache.
+    pub fn invalidate<K: AsRef<[u8]>>(&self, keys: &[K]) {
+        let mut state = self.state.lock();
+        for key in keys {
+            state.remove(key.as_ref());
+        }
+    }
+
+    /// Drop every entry.
+    pub fn clear(&self) {
+        self.state.lock().clear();
+    }
+
+    /// Start or stop caching. Either way the cache starts out empty.
+    pub fn set_active(&self, active: bool) {
+        let mut state = self.state.lock();
+        state.active = active;
+        state.clear();
+    }
+
+    /// Whether invalidations are being received.
+    pub fn is_active(&self) -> bool {
+        self.state.lock().active
+    }
+
+    /// Number of cached keys, including ones still being fetched.
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Whether no keys are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Maximum number of cached keys.
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// Lookups answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Lookups that had to go to the server while caching was active.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+/// Apply the payload of an invalidation message: an array of keys, or
+/// null when the server flushed everything (`FLUSHALL`, `FLUSHDB`).
+fn apply_invalidation(cache: &ClientCache, payload: &RespValue) {
+    match payload {
+        RespValue::Array(keys) => {
+            let keys: Vec<&[u8]> = keys.iter().filter_map(RespValue::as_bytes).collect();
+            cache.invalidate(&keys);
+        }
+        _ => cache.clear(),
+    }
+}
+
+// ── Invalidation connection ───────────────────────────────────────
+
+/// What the background task needs to keep tracking alive.
+#[derive(Clone)]
+struct Invalidations {
+    router: Arc<ClientRouter>,
+    events: Arc<EventLog>,
+    cache: Arc<ClientCache>,
+    redirect: Arc<AtomicU64>,
+}
+
+impl Invalidations {
+    /// Subscribe to invalidations on a fresh connection, enable tracking
+    /// on the pool and apply messages until the connection fails.
+    async fn run_once(&self) -> Result<()> {
+        let mut lease = self.router.lease().await?;
+        // Invalidations arrive whenever keys change; never time out a read
+        lease.conn().set_read_timeout(0);
+        let client_id = match lease.conn().execute_str(&["CLIENT", "ID"]).await? {
+            RespValue::Integer(id) => id as u64,
+            RespValue::Error(msg) => return Err(PyrsedisError::redis(msg)),
+            other => {
+                return Err(PyrsedisError::Protocol(format!(
+                    "unexpected CLIENT ID response: {}",
+                    other.type_name()
+                )))
+            }
+        };
+        lease.conn().send_raw(&encode_command_str(&["SUBSCRIBE", INVALIDATE_CHANNEL])).await?;
+        if !matches!(pubsub::split(lease.conn().read_response().await?), Ok((MessageKind::Subscribe, _))) {
+            return Err(PyrsedisError::Protocol(format!("could not subscribe to {INVALIDATE_CHANNEL}")));
+        }
+        self.events.subscribed(lease.conn().addr(), format!("channels: {INVALIDATE_CHANNEL}"));
+
+        // Pooled connections re-initialize with tracking turned on
+        self.redirect.store(client_id, Ordering::Release);
+        self.router.disconnect_all();
+        self.cache.set_active(true);
+        loop {
+            let frame = lease.conn().read_response().await?;
+            if let Ok((MessageKind::Message, items)) = pubsub::split(frame) {
+                apply_invalidation(&self.cache, items.get(1).unwrap_or(&RespValue::Null));
+            }
+        }
+    }
+
+    /// Stop caching: without the invalidation connection entries could
+    /// go stale. Connections still tracking into the old one are closed.
+    fn deactivate(&self) {
+        self.cache.set_active(false);
+        if self.redirect.swap(0, Ordering::AcqRel) != 0 {
+            self.router.disconnect_all();
+        }
+    }
+
+    /// Keep tracking alive until `stop` is notified.
+    async fn run(self, stop: Arc<Notify>) {
+        loop {
+            tokio::select! {
+                _ = stop.notified() => break,
+                // Only returns once the connection is gone
+                _ = self.run_once() => {}
+            }
+            self.deactivate();
+            tokio::select! {
+                _ = stop.notified() => break,
+                _ = tokio::time::sleep(RETRACK_BACKOFF) => {}
+            }
+        }
+        self.deactivate();
+    }
+}
+
+// ── TrackedCache ──────────────────────────────────────────────────
+
+/// Every started [`TrackedCache`], so [`reset_after_fork`] can restart
+/// their tasks without keeping them alive.
+static TRACKED: Mutex<Vec<Weak<Tracking>>> = Mutex::new(Vec::new());
+
+/// A [`ClientCache`] kept up to date by a background invalidation
+/// connection, which is closed when this handle is dropped.
+pub struct TrackedCache {
+    tracking: Arc<Tracking>,
+}
+
+/// The invalidation task of a [`TrackedCache`] and how to stop it.
+struct Tracking {
+    task: Invalidations,
+    /// Replaced on every restart: a task left behind by `fork()` may
+    /// still be registered as a waiter on the old one.
+    stop: Mutex<Arc<Notify>>,
+}
+
+impl Tracking {
+    /// Spawn the invalidation task on the current runtime.
+    fn spawn(&self) {
+        let stop = Arc::new(Notify::new());
+        *self.stop.lock() = Arc::clone(&stop);
+        runtime::spawn(self.task.clone().run(stop));
+    }
+
+    /// Drop the state inherited from the parent process and track again.
+    fn reset_after_fork(&self) {
+        self.task.deactivate();
+        self.spawn();
+    }
+}
+
+impl TrackedCache {
+    /// Start tracking the keys read through `router`. `redirect` must be
+    /// the `tracking_redirect` of the router's connection config.
+    pub(crate) fn start(
+        router: Arc<ClientRouter>,
+        events: Arc<EventLog>,
+        redirect: Arc<AtomicU64>,
+        max_entries: usize,
+        ttl: Option<Duration>,
+    ) -> Self {
+        let cache = Arc::new(ClientCache::new(max_entries, ttl));
+        let task = Invalidations { router, events, cache, redirect };
+        let tracking = Arc::new(Tracking { task, stop: Mutex::new(Arc::new(Notify::new())) });
+        tracking.spawn();
+        let mut tracked = TRACKED.lock();
+        tracked.retain(|t| t.strong_count() > 0);
+        tracked.push(Arc::downgrade(&tracking));
+        Self { tracking }
+    }
+
+    /// The cache itself.
+    pub fn cache(&self) -> &ClientCache {
+        &self.tracking.task.cache
+    }
+}
+
+impl Drop for TrackedCache {
+    fn drop(&mut self) {
+        self.tracking.stop.lock().notify_one();
+    }
+}
+
+/// Restart client-side caching in a freshly forked child.
+///
+/// The inherited invalidation task lived on the parent's runtime, and the
+/// `tracking_redirect` still names the parent's subscriber connection, so
+/// new connections would send the child's invalidations to the parent.
+/// Every cache is emptied and deactivated, the redirect cleared and a new
+/// task started; caching resumes once it has subscribed.
+pub fn reset_after_fork() {
+    let live: Vec<Arc<Tracking>> = TRACKED.lock().iter().filter_map(Weak::upgrade).collect();
+    for tracking in live {
+        tracking.reset_after_fork();
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active(max_entries: usize, ttl: Option<Duration>) -> ClientCache {
+        let cache = ClientCache::new(max_entries, ttl);
+        cache.set_active(true);
+        cache
+    }
+
+    fn cached(cache: &ClientCache, key: &str, value: &'static str) {
+        let token = cache.reserve(key.as_bytes()).unwrap();
+        cache.fill(key.as_bytes(), token, Bytes::from_static(value.as_bytes()));
+    }
+
+    #[test]
+    fn fill_then_hit() {
+        let cache = active(10, None);
+        assert_eq!(cache.get(b"k"), None);
+        cached(&cache, "k", "$1\r\nv\r\n");
+        assert_eq!(cache.get(b"k").as_deref(), Some(&b"$1\r\nv\r\n"[..]));
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+    }
+
+    #[test]
+    fn inactive_cache_is_bypassed() {
+        let cache = ClientCache::new(10, None);
+        assert_eq!(cache.reserve(b"k"), None);
+        assert_eq!(cache.get(b"k"), None);
+        assert_eq!(cache.misses(), 0);
+
+        let cache = active(10, None);
+        cached(&cache, "k", "+v\r\n");
+        cache.set_active(false);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn invalidation_during_fetch_is_not_lost() {
+        let cache = active(10, None);
+        let token = cache.reserve(b"k").unwrap();
+        cache.invalidate(&[b"k"]);
+        cache.fill(b"k", token, Bytes::from_static(b"+stale\r\n"));
+        assert_eq!(cache.get(b"k"), None);
+
+        // A newer fetch supersedes an older one still in flight
+        let old = cache.reserve(b"k").unwrap();
+        let new = cache.reserve(b"k").unwrap();
+        cache.fill(b"k", old, Bytes::from_static(b"+old\r\n"));
+        assert_eq!(cache.get(b"k"), None);
+        cache.fill(b"k", new, Bytes::from_static(b"+new\r\n"));
+        assert_eq!(cache.get(b"k").as_deref(), Some(&b"+new\r\n"[..]));
+    }
+
+    #[test]
+    fn least_recently_used_key_is_evicted() {
+        let cache = active(2, None);
+        cached(&cache, "a", "+a\r\n");
+        cached(&cache, "b", "+b\r\n");
+        assert!(cache.get(b"a").is_some());
+        cached(&cache, "c", "+c\r\n");
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(b"b").is_none());
+        assert!(cache.get(b"a").is_some());
+        assert!(cache.get(b"c").is_some());
+    }
+
+    #[test]
+    fn entries_expire_after_ttl() {
+        let cache = active(10, Some(Duration::from_millis(20)));
+        cached(&cache, "k", "+v\r\n");
+        assert!(cache.get(b"k").is_some());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cache.get(b"k").is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn invalidation_messages() {
+        let bulk = |s: &'static str| RespValue::BulkString(Bytes::from_static(s.as_bytes()));
+        let cache = active(10, None);
+        for key in ["a", "b", "c"] {
+            cached(&cache, key, "+v\r\n");
+        }
+        apply_invalidation(&cache, &RespValue::Array(vec![bulk("a"), bulk("b")]));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(b"c").is_some());
+        apply_invalidation(&cache, &RespValue::Null);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn fork_reset_drops_the_parents_tracking() {
+        use crate::config::ConnectionConfig;
+        use crate::router::standalone::StandaloneRouter;
+
+        let config = ConnectionConfig { port: 1, connect_timeout_ms: 100, ..ConnectionConfig::default() };
+        let redirect = Arc::clone(&config.tracking_redirect);
+        let task = Invalidations {
+            router: Arc::new(ClientRouter::Standalone(StandaloneRouter::new(config))),
+            events: Arc::new(EventLog::new(0)),
+            cache: Arc::new(active(10, None)),
+            redirect: Arc::clone(&redirect),
+        };
+        let parent_stop = Arc::new(Notify::new());
+        let tracking = Tracking { task, stop: Mutex::new(Arc::clone(&parent_stop)) };
+        // State inherited from the parent: tracking into its subscriber
+        cached(&tracking.task.cache, "k", "+v\r\n");
+        redirect.store(42, Ordering::Release);
+
+        tracking.reset_after_fork();
+        assert!(!tracking.task.cache.is_active());
+        assert!(tracking.task.cache.is_empty());
+        assert_eq!(redirect.load(Ordering::Acquire), 0);
+        assert!(!Arc::ptr_eq(&tracking.stop.lock(), &parent_stop));
+        tracking.stop.lock().notify_one();
+    }
+}
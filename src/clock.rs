@@ -0,0 +1,142 @@
+//! Server clock synchronisation.
+//!
+//! `Redis.time_offset()` estimates how far the server's clock is ahead of
+//! the local one from a few `TIME` round-trips, assuming each reply was
+//! produced halfway through its round-trip and keeping the sample with the
+//! shortest one. The estimate is cached, so `Redis.server_now()` costs no
+//! round-trip; expiry deadlines computed from it agree with the server
+//! even when the local clock drifts.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+
+use crate::error::{PyrsedisError, Result};
+use crate::resp::types::RespValue;
+use crate::router::{ClientRouter, Router};
+
+/// `TIME` round-trips per measurement.
+const SAMPLES: usize = 3;
+
+/// How long a measured offset is reused before measuring again.
+pub const OFFSET_TTL: Duration = Duration::from_secs(60);
+
+/// The local wall clock, in Unix seconds.
+pub fn unix_now() -> f64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0.0, |d| d.as_secs_f64())
+}
+
+/// Unix seconds from a `TIME` reply (`[seconds, microseconds]`).
+pub fn server_time(reply: &RespValue) -> Result<f64> {
+    if let Some(msg) = reply.as_error_msg() {
+        return Err(PyrsedisError::redis(msg.to_string()));
+    }
+    let field = |i: usize| -> Option<f64> {
+        let RespValue::Array(items) = reply else { return None };
+        std::str::from_utf8(items.get(i)?.as_bytes()?).ok()?.parse().ok()
+    };
+    match (field(0), field(1)) {
+        (Some(secs), Some(micros)) => Ok(secs + micros / 1e6),
+        _ => Err(PyrsedisError::Protocol(format!("unexpected TIME reply: {reply:?}"))),
+    }
+}
+
+/// Offset of `server` from the local clock, taking the server time to be
+/// read halfway between `before` and `after`.
+pub fn sample_offset(before: f64, after: f64, server: f64) -> f64 {
+    server - (before + after) / 2.0
+}
+
+/// Measure the offset, keeping the sample with the shortest round-trip.
+async fn measure(router: &ClientRouter) -> Result<f64> {
+    let mut best: Option<(f64, f64)> = None;
+    for _ in 0..SAMPLES {
+        let before = unix_now();
+        let reply = router.execute(&["TIME"]).await?;
+        let after = unix_now();
+        let rtt = after - before;
+        let offset = sample_offset(before, after, server_time(&reply)?);
+        if best.is_none_or(|(best_rtt, _)| rtt < best_rtt) {
+            best = Some((rtt, offset));
+        }
+    }
+    Ok(best.map_or(0.0, |(_, offset)| offset))
+}
+
+// ── ServerClock ───────────────────────────────────────────────────
+
+/// A client's cached estimate of the server clock offset.
+#[derive(Default)]
+pub struct ServerClock {
+    measured: Mutex<Option<(f64, Instant)>>,
+}
+
+impl ServerClock {
+    /// The cached offset, if measured within [`OFFSET_TTL`].
+    pub fn cached(&self) -> Option<f64> {
+        self.measured
+            .lock()
+            .filter(|(_, at)| at.elapsed() < OFFSET_TTL)
+            .map(|(offset, _)| offset)
+    }
+
+    /// Seconds to add to the local clock to get the server's, measured
+    /// unless a fresh estimate is cached or `refresh` forces it.
+    pub async fn offset(&self, router: &ClientRouter, refresh: bool) -> Result<f64> {
+        if !refresh {
+            if let Some(offset) = self.cached() {
+                return Ok(offset);
+            }
+        }
+        let offset = measure(router).await?;
+        *self.measured.lock() = Some((offset, Instant::now()));
+        Ok(offset)
+    }
+
+    /// The server's current time in Unix seconds.
+    pub async fn now(&self, router: &ClientRouter) -> Result<f64> {
+        let offset = self.offset(router, false).await?;
+        Ok(unix_now() + offset)
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time_reply(secs: &'static str, micros: &'static str) -> RespValue {
+        RespValue::Array(vec![
+            RespValue::BulkString(bytes::Bytes::from_static(secs.as_bytes())),
+            RespValue::BulkString(bytes::Bytes::from_static(micros.as_bytes())),
+        ])
+    }
+
+    #[test]
+    fn time_reply_is_parsed() {
+        assert_eq!(server_time(&time_reply("1700000000", "250000")).unwrap(), 1_700_000_000.25);
+        assert!(server_time(&time_reply("soon", "0")).is_err());
+        assert!(server_time(&RespValue::Null).is_err());
+        assert!(server_time(&RespValue::Error("ERR nope".into())).is_err());
+    }
+
+    #[test]
+    fn offset_assumes_the_midpoint() {
+        assert_eq!(sample_offset(100.0, 100.2, 100.1), 0.0);
+        assert_eq!(sample_offset(100.0, 100.2, 105.1), 5.0);
+        assert_eq!(sample_offset(100.0, 100.0, 99.5), -0.5);
+    }
+
+    #[test]
+    fn cached_offset_expires() {
+        let clock = ServerClock::default();
+        assert_eq!(clock.cached(), None);
+        *clock.measured.lock() = Some((2.5, Instant::now()));
+        assert_eq!(clock.cached(), Some(2.5));
+        if let Some(stale) = Instant::now().checked_sub(OFFSET_TTL) {
+            *clock.measured.lock() = Some((2.5, stale));
+            assert_eq!(clock.cached(), None);
+        }
+    }
+}
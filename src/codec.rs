@@ -0,0 +1,419 @@
+//! Value codecs for the SET/GET fast path.
+//!
+//! With a serializer configured, `Redis.set` encodes arbitrary Python
+//! values to bytes and `Redis.get` decodes them back, entirely in Rust —
+//! no intermediate Python `bytes` object or pure-Python codec call.
+//!
+//! Supported types: `None`, `bool`, `int` (64-bit signed or unsigned),
+//! `float`, `str`, `bytes`/`bytearray`, `list`/`tuple` (decoded as `list`)
+//! and `dict`.
+//!
+//! [`CommandArg`] encodes plain command arguments without a serializer.
+
+use std::fmt;
+
+use pyo3::prelude::*;
+use pyo3::types::{
+    PyBool, PyByteArray, PyBytes, PyDict, PyFloat, PyInt, PyList, PyMemoryView, PyString, PyTuple,
+};
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq};
+
+use crate::error::{PyrsedisError, Result};
+
+/// Maximum nesting depth when encoding, so self-referencing containers
+/// fail cleanly instead of overflowing the stack.
+const MAX_ENCODE_DEPTH: usize = 512;
+
+// ── Serializer selection ──────────────────────────────────────────
+
+/// A value serializer selectable via ``Redis(serializer=...)``.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Serializer {
+    /// MessagePack via `rmp-serde`.
+    Msgpack,
+}
+
+impl Serializer {
+    /// Parse a serializer name (case-insensitive).
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "msgpack" => Ok(Self::Msgpack),
+            _ => Err(PyrsedisError::Type(format!(
+                "unknown serializer {name:?} (supported: 'msgpack')"
+            ))),
+        }
+    }
+
+    /// The serializer's name.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Msgpack => "msgpack",
+        }
+    }
+
+    /// Encode a Python value.
+    pub fn encode(self, value: &Bound<'_, PyAny>) -> Result<Vec<u8>> {
+        match self {
+            Self::Msgpack => rmp_serde::to_vec(&PyValue { value, depth: 0 })
+                .map_err(|e| PyrsedisError::Type(format!("msgpack encode failed: {e}"))),
+        }
+    }
+
+    /// Decode bytes produced by [`Serializer::encode`] into a Python value.
+    pub fn decode(self, py: Python<'_>, data: &[u8]) -> Result<Py<PyAny>> {
+        match self {
+            Self::Msgpack => {
+                let mut rest = data;
+                let value = PySeed { py }
+                    .deserialize(&mut rmp_serde::Deserializer::new(&mut rest))
+                    .map_err(|e| PyrsedisError::Type(format!("msgpack decode failed: {e}")))?;
+                // a plain string like "hello" starts with a valid fixint
+                if !rest.is_empty() {
+                    return Err(PyrsedisError::Type(format!(
+                        "msgpack decode failed: {} trailing bytes after the value",
+                        rest.len()
+                    )));
+                }
+                Ok(value)
+            }
+        }
+    }
+}
+
+// ── Python → serde ────────────────────────────────────────────────
+
+struct PyValue<'a, 'py> {
+    value: &'a Bound<'py, PyAny>,
+    depth: usize,
+}
+
+impl PyValue<'_, '_> {
+    fn child<'b, 'py>(&self, value: &'b Bound<'py, PyAny>) -> PyValue<'b, 'py> {
+        PyValue { value, depth: self.depth + 1 }
+    }
+}
+
+impl Serialize for PyValue<'_, '_> {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use ser::Error;
+
+        if self.depth > MAX_ENCODE_DEPTH {
+            return Err(S::Error::custom("value nested too deeply"));
+        }
+        let v = self.value;
+        if v.is_none() {
+            return serializer.serialize_unit();
+        }
+        // bool before int: Python bools are ints
+        if let Ok(b) = v.cast::<PyBool>() {
+            return serializer.serialize_bool(b.is_true());
+        }
+        if v.is_instance_of::<PyInt>() {
+            if let Ok(i) = v.extract::<i64>() {
+                return serializer.serialize_i64(i);
+            }
+            if let Ok(u) = v.extract::<u64>() {
+                return serializer.serialize_u64(u);
+            }
+            return Err(S::Error::custom("int out of 64-bit range"));
+        }
+        if let Ok(f) = v.cast::<PyFloat>() {
+            return serializer.serialize_f64(f.value());
+        }
+        if let Ok(s) = v.cast::<PyString>() {
+            return serializer.serialize_str(s.to_str().map_err(S::Error::custom)?);
+        }
+        if let Ok(b) = v.cast::<PyBytes>() {
+            return serializer.serialize_bytes(b.as_bytes());
+        }
+        if let Ok(b) = v.cast::<PyByteArray>() {
+            return serializer.serialize_bytes(&b.to_vec());
+        }
+        if let Ok(d) = v.cast::<PyDict>() {
+            let mut map = serializer.serialize_map(Some(d.len()))?;
+            for (k, item) in d.iter() {
+                map.serialize_entry(&self.child(&k), &self.child(&item))?;
+            }
+            return map.end();
+        }
+        if let Ok(l) = v.cast::<PyList>() {
+            let mut seq = serializer.serialize_seq(Some(l.len()))?;
+            for item in l.iter() {
+                seq.serialize_element(&self.child(&item))?;
+            }
+            return seq.end();
+        }
+        if let Ok(t) = v.cast::<PyTuple>() {
+            let mut seq = serializer.serialize_seq(Some(t.len()))?;
+            for item in t.iter() {
+                seq.serialize_element(&self.child(&item))?;
+            }
+            return seq.end();
+        }
+        let type_name = v.get_type().name().map(|n| n.to_string()).unwrap_or_default();
+        Err(S::Error::custom(format!("unsupported type '{type_name}'")))
+    }
+}
+
+// ── serde → Python ────────────────────────────────────────────────
+
+#[derive(Clone, Copy)]
+struct PySeed<'py> {
+    py: Python<'py>,
+}
+
+fn py_err<E: de::Error>(err: PyErr) -> E {
+    E::custom(err)
+}
+
+impl<'de, 'py> DeserializeSeed<'de> for PySeed<'py> {
+    type Value = Py<PyAny>;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'py> Visitor<'de> for PySeed<'py> {
+    type Value = Py<PyAny>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a msgpack value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+        Ok(self.py.None())
+    }
+
+    fn visit_none<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+        Ok(self.py.None())
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> std::result::Result<Self::Value, D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> std::result::Result<Self::Value, E> {
+        Ok(PyBool::new(self.py, v).to_owned().into_any().unbind())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> std::result::Result<Self::Value, E> {
+        let Ok(int) = v.into_pyobject(self.py);
+        Ok(int.into_any().unbind())
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> std::result::Result<Self::Value, E> {
+        let Ok(int) = v.into_pyobject(self.py);
+        Ok(int.into_any().unbind())
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> std::result::Result<Self::Value, E> {
+        Ok(PyFloat::new(self.py, v).into_any().unbind())
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Self::Value, E> {
+        Ok(PyString::new(self.py, v).into_any().unbind())
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> std::result::Result<Self::Value, E> {
+        Ok(PyBytes::new(self.py, v).into_any().unbind())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error> {
+        let list = PyList::empty(self.py);
+        while let Some(item) = seq.next_element_seed(self)? {
+            list.append(item).map_err(py_err)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<Self::Value, A::Error> {
+        let dict = PyDict::new(self.py);
+        while let Some((k, v)) = map.next_entry_seed(self, self)? {
+            dict.set_item(k, v).map_err(py_err)?;
+        }
+        Ok(dict.into_any().unbind())
+    }
+}
+
+// ── Command arguments ─────────────────────────────────────────────
+
+/// A binary-safe command argument.
+///
+/// Extracted from `str` (UTF-8), `bytes`, `bytearray`, `memoryview`, `int`
+/// or `float`. Bytes are sent as-is, never round-tripped through UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandArg(pub Vec<u8>);
+
+impl CommandArg {
+    /// The encoded argument.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// The argument as a `String`, for paths that still carry text
+    /// commands (pipelines, batches). Fails on non-UTF-8 bytes.
+    pub fn into_string(self) -> Result<String> {
+        String::from_utf8(self.0).map_err(|_| {
+            PyrsedisError::Type("argument is not valid UTF-8; send binary data with execute_command".into())
+        })
+    }
+}
+
+impl AsRef<[u8]> for CommandArg {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for CommandArg {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        encode_arg(&obj).map(Self)
+    }
+}
+
+/// Encode one command argument to the bytes sent on the wire.
+///
+/// Integers and floats use their shortest round-trip decimal form, which
+/// Redis parses back exactly. `bool` is rejected: it has no unambiguous
+/// wire form.
+pub fn encode_arg(v: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    if let Ok(s) = v.cast::<PyString>() {
+        return Ok(s.to_str()?.as_bytes().to_vec());
+    }
+    if let Ok(b) = v.cast::<PyBytes>() {
+        return Ok(b.as_bytes().to_vec());
+    }
+    if let Ok(b) = v.cast::<PyByteArray>() {
+        return Ok(b.to_vec());
+    }
+    if let Ok(m) = v.cast::<PyMemoryView>() {
+        return Ok(m.call_method0("tobytes")?.cast_into::<PyBytes>()?.as_bytes().to_vec());
+    }
+    if !v.is_instance_of::<PyBool>() {
+        if v.is_instance_of::<PyInt>() {
+            return Ok(v.extract::<i128>()?.to_string().into_bytes());
+        }
+        if let Ok(f) = v.cast::<PyFloat>() {
+            return Ok(format!("{:?}", f.value()).into_bytes());
+        }
+    }
+    let type_name = v.get_type().name().map(|n| n.to_string()).unwrap_or_default();
+    Err(PyrsedisError::Type(format!(
+        "invalid argument of type '{type_name}': expected str, bytes, bytearray, memoryview, int or float"
+    ))
+    .into())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip<'py>(py: Python<'py>, expr: &std::ffi::CStr) -> (Bound<'py, PyAny>, Bound<'py, PyAny>) {
+        let original = py.eval(expr, None, None).unwrap();
+        let encoded = Serializer::Msgpack.encode(&original).unwrap();
+        let decoded = Serializer::Msgpack.decode(py, &encoded).unwrap().into_bound(py);
+        (original, decoded)
+    }
+
+    #[test]
+    fn parse_serializer_names() {
+        assert_eq!(Serializer::parse("MsgPack").unwrap(), Serializer::Msgpack);
+        assert_eq!(Serializer::Msgpack.as_str(), "msgpack");
+        assert!(Serializer::parse("pickle").is_err());
+    }
+
+    #[test]
+    fn msgpack_roundtrips_supported_types() {
+        Python::attach(|py| {
+            let (original, decoded) = roundtrip(
+                py,
+                c"{'s': 'é', 'i': -5, 'big': 2**64 - 1, 'f': 1.25, 't': True, 'n': None, 'b': b'\\x00\\xff', 'l': [1, [2, {'k': 'v'}]]}",
+            );
+            assert!(original.eq(&decoded).unwrap());
+        });
+    }
+
+    #[test]
+    fn msgpack_tuples_decode_as_lists() {
+        Python::attach(|py| {
+            let (_, decoded) = roundtrip(py, c"(1, 'a', bytearray(b'x'))");
+            let expected = py.eval(c"[1, 'a', b'x']", None, None).unwrap();
+            assert!(decoded.eq(expected).unwrap());
+        });
+    }
+
+    #[test]
+    fn msgpack_bool_is_not_int() {
+        Python::attach(|py| {
+            let (_, decoded) = roundtrip(py, c"[True, 1]");
+            let items: Vec<Bound<'_, PyAny>> = decoded.extract().unwrap();
+            assert!(items[0].is_instance_of::<PyBool>());
+            assert!(!items[1].is_instance_of::<PyBool>());
+        });
+    }
+
+    #[test]
+    fn msgpack_rejects_unsupported_values() {
+        Python::attach(|py| {
+            for expr in [c"object()", c"2**70", c"{1, 2}"] {
+                let value = py.eval(expr, None, None).unwrap();
+                assert!(matches!(Serializer::Msgpack.encode(&value), Err(PyrsedisError::Type(_))));
+            }
+            let cyclic = py.eval(c"(lambda l: (l.append(l), l)[1])([])", None, None).unwrap();
+            assert!(Serializer::Msgpack.encode(&cyclic).is_err());
+        });
+    }
+
+    #[test]
+    fn msgpack_decode_rejects_garbage() {
+        Python::attach(|py| {
+            assert!(Serializer::Msgpack.decode(py, &[0xc1]).is_err());
+        });
+    }
+
+    #[test]
+    fn msgpack_decode_rejects_trailing_bytes() {
+        Python::attach(|py| {
+            assert!(matches!(Serializer::Msgpack.decode(py, b"hello"), Err(PyrsedisError::Type(_))));
+            let mut encoded = Serializer::Msgpack.encode(&py.eval(c"[1, 'a']", None, None).unwrap()).unwrap();
+            encoded.push(0x00);
+            assert!(Serializer::Msgpack.decode(py, &encoded).is_err());
+        });
+    }
+
+    #[test]
+    fn command_args_are_binary_safe() {
+        Python::attach(|py| {
+            let cases: [(&std::ffi::CStr, &[u8]); 9] = [
+                (c"'é'", "é".as_bytes()),
+                (c"b'\\x00\\xff'", b"\x00\xff"),
+                (c"bytearray(b'\\x80')", b"\x80"),
+                (c"memoryview(b'abc')[1:]", b"bc"),
+                (c"-42", b"-42"),
+                (c"2**100", b"1267650600228229401496703205376"),
+                (c"1.5", b"1.5"),
+                (c"1e300", b"1e300"),
+                (c"float('-inf')", b"-inf"),
+            ];
+            for (expr, expected) in cases {
+                let value = py.eval(expr, None, None).unwrap();
+                assert_eq!(value.extract::<CommandArg>().unwrap().as_bytes(), expected, "{expr:?}");
+            }
+            for expr in [c"True", c"None", c"[1]", c"2**200"] {
+                assert!(py.eval(expr, None, None).unwrap().extract::<CommandArg>().is_err(), "{expr:?}");
+            }
+        });
+    }
+
+    #[test]
+    fn command_arg_into_string_requires_utf8() {
+        assert_eq!(CommandArg(b"key".to_vec()).into_string().unwrap(), "key");
+        assert!(CommandArg(vec![0xff]).into_string().is_err());
+    }
+}
@@ -0,0 +1,267 @@
+//! The command table.
+//!
+//! Commands that map their arguments straight onto a Redis command are
+//! declared once in [`command_table!`] instead of being written out by
+//! hand for `Redis`, `Pipeline` and `AsyncRedis`. Each entry reads
+//!
+//! ```text
+//! /// Docstring.
+//! name(arg: Type, ...) => ["COMMAND", arg, ...] -> Shape;
+//! ```
+//!
+//! where every part of the command is formatted with `ToString`, and the
+//! optional `-> Shape` names the [`ReplyShape`] applied to the reply.
+//! Attributes such as `#[pyo3(name = "...")]` are copied onto every
+//! generated method. The `Redis` and `AsyncRedis` methods also take a
+//! keyword-only `timeout` in seconds bounding the round trip.
+//!
+//! PyO3 allows a single `#[pymethods]` block per class, so the generated
+//! methods cannot live in an `impl` of their own. Instead the hand-written
+//! block is passed through a generator macro, which emits it together with
+//! the table's methods:
+//!
+//! ```ignore
+//! command_table!(redis_methods! {
+//!     #[pymethods]
+//!     impl Redis {
+//!         // hand-written methods
+//!     }
+//! });
+//! ```
+//!
+//! [`ReplyShape`]: crate::streams::ReplyShape
+
+/// Invoke `$gen!` with `$prefix` followed by every table entry.
+macro_rules! command_table {
+    ($gen:ident! { $($prefix:tt)* }) => {
+        $crate::commands::$gen! {
+            { $($prefix)* }
+
+            // Keys
+            /// Get the remaining time to live of a key (in seconds).
+            ///
+            /// Returns:
+            ///     TTL in seconds, ``-1`` if no expiry, ``-2`` if key does not exist.
+            ttl(name: String) => ["TTL", name];
+            /// Get the absolute UNIX time (in seconds) at which a key expires.
+            ///
+            /// Returns:
+            ///     The timestamp, ``-1`` if no expiry, ``-2`` if key does not exist.
+            expiretime(name: String) => ["EXPIRETIME", name];
+            /// Get the absolute UNIX time (in milliseconds) at which a key expires.
+            ///
+            /// Returns:
+            ///     The timestamp, ``-1`` if no expiry, ``-2`` if key does not exist.
+            pexpiretime(name: String) => ["PEXPIRETIME", name];
+            /// Remove the expiration from a key.
+            persist(name: String) => ["PERSIST", name];
+            /// Rename a key.
+            rename(src: String, dst: String) => ["RENAME", src, dst];
+            /// Return the type of the value stored at key as a ``str``
+            /// (``"string"``, ``"hash"``, ..., ``"none"`` if it doesn't exist).
+            #[pyo3(name = "type")]
+            key_type(name: String) => ["TYPE", name] -> Text;
+
+            // Strings
+            /// Increment the integer value of a key by one.
+            incr(name: String) => ["INCR", name];
+            /// Decrement the integer value of a key by one.
+            decr(name: String) => ["DECR", name];
+            /// Append a value to a key.
+            append(name: String, value: String) => ["APPEND", name, value];
+            /// Get the length of the value stored at a key.
+            strlen(name: String) => ["STRLEN", name];
+            /// Set key only if it does not exist.
+            setnx(name: String, value: String) => ["SETNX", name, value];
+
+            // Hashes
+            /// Get the value of a hash field.
+            hget(name: String, key: String) => ["HGET", name, key];
+            /// Get all fields and values of a hash as a dict.
+            hgetall(name: String) => ["HGETALL", name] -> Dict;
+            /// Check if a hash field exists.
+            hexists(name: String, key: String) => ["HEXISTS", name, key];
+            /// Get all field names in a hash.
+            hkeys(name: String) => ["HKEYS", name];
+            /// Get all values in a hash.
+            hvals(name: String) => ["HVALS", name];
+            /// Get the number of fields in a hash.
+            hlen(name: String) => ["HLEN", name];
+
+            // Lists
+            /// Get the length of a list.
+            llen(name: String) => ["LLEN", name];
+            /// Trim a list to the elements between ``start`` and ``end`` (inclusive).
+            ltrim(name: String, start: i64, end: i64) => ["LTRIM", name, start, end];
+            /// Pop the last element of ``src`` and push it onto ``dst``.
+            ///
+            /// Returns:
+            ///     The element, or ``None`` if ``src`` is empty.
+            rpoplpush(src: String, dst: String) => ["RPOPLPUSH", src, dst];
+
+            // Sets
+            /// Get all members of a set.
+            smembers(name: String) => ["SMEMBERS", name];
+            /// Get the number of members in a set.
+            scard(name: String) => ["SCARD", name];
+            /// Check if a value is a member of a set.
+            sismember(name: String, value: String) => ["SISMEMBER", name, value];
+
+            // Sorted sets
+            /// Get the score of a member in a sorted set.
+            zscore(name: String, member: String) => ["ZSCORE", name, member];
+            /// Get the rank of a member in a sorted set (0-based, ascending).
+            zrank(name: String, member: String) => ["ZRANK", name, member];
+            /// Get the number of members in a sorted set.
+            zcard(name: String) => ["ZCARD", name];
+
+            // Graphs
+            /// Delete a graph and all its data.
+            graph_delete(graph: String) => ["GRAPH.DELETE", graph];
+            /// List all graph keys in the database.
+            graph_list() => ["GRAPH.LIST"];
+
+            // Server
+            /// Echo the given message.
+            echo(message: String) => ["ECHO", message];
+            /// Publish a message to a channel.
+            publish(channel: String, message: String) => ["PUBLISH", channel, message];
+            /// Return the number of keys in the current database.
+            dbsize() => ["DBSIZE"];
+        }
+    };
+}
+
+// The generators match the caller's `#[pymethods]` token by token rather
+// than writing their own: PyO3 names the locals it generates after the
+// attribute's span, and an attribute spanned inside this file would hide
+// them from the hand-written methods through macro hygiene.
+
+/// `#[pymethods] impl Redis`: blocking methods returning the reply.
+macro_rules! redis_methods {
+    (
+        { $pound:tt $attr:tt impl $ty:ident { $($body:tt)* } }
+        $(
+            $(#[$meta:meta])*
+            $name:ident($($arg:ident: $argty:ty),*) => [$($part:expr),+] $(-> $shape:ident)?;
+        )*
+    ) => {
+        $pound $attr
+        impl $ty {
+            $($body)*
+
+            $(
+                $(#[$meta])*
+                #[pyo3(signature = ($($arg,)* *, timeout=None))]
+                fn $name(&self, py: Python<'_>, $($arg: $argty,)* timeout: Option<f64>) -> PyResult<Py<PyAny>> {
+                    let limit = $crate::router::command_limit(timeout)?;
+                    let cmd: Vec<String> = vec![$(ToString::to_string(&$part)),+];
+                    self.exec_shaped(py, cmd, None $(.or(Some($crate::streams::ReplyShape::$shape)))?, limit)
+                }
+            )*
+        }
+    };
+}
+
+/// `#[pymethods] impl Pipeline`: chainable methods buffering the command.
+macro_rules! pipeline_methods {
+    (
+        { $pound:tt $attr:tt impl $ty:ident { $($body:tt)* } }
+        $(
+            $(#[$meta:meta])*
+            $name:ident($($arg:ident: $argty:ty),*) => [$($part:expr),+] $(-> $shape:ident)?;
+        )*
+    ) => {
+        $pound $attr
+        impl $ty {
+            $($body)*
+
+            $(
+                $(#[$meta])*
+                fn $name(mut slf: PyRefMut<'_, Self>, $($arg: $argty),*) -> PyResult<PyRefMut<'_, Self>> {
+                    let cmd: Vec<String> = vec![$(ToString::to_string(&$part)),+];
+                    slf.queue_with_shape(cmd, None $(.or(Some($crate::streams::ReplyShape::$shape)))?)?;
+                    Ok(slf)
+                }
+            )*
+        }
+    };
+}
+
+/// `#[pymethods] impl AsyncRedis`: coroutines resolving to the reply.
+macro_rules! async_redis_methods {
+    (
+        { $pound:tt $attr:tt impl $ty:ident { $($body:tt)* } }
+        $(
+            $(#[$meta:meta])*
+            $name:ident($($arg:ident: $argty:ty),*) => [$($part:expr),+] $(-> $shape:ident)?;
+        )*
+    ) => {
+        $pound $attr
+        impl $ty {
+            $($body)*
+
+            $(
+                $(#[$meta])*
+                #[pyo3(signature = ($($arg,)* *, timeout=None))]
+                async fn $name(&self, $($arg: $argty,)* timeout: Option<f64>) -> PyResult<Py<PyAny>> {
+                    let limit = $crate::router::command_limit(timeout)?;
+                    let cmd: Vec<String> = vec![$(ToString::to_string(&$part)),+];
+                    self.exec_shaped(cmd, None $(.or(Some($crate::streams::ReplyShape::$shape)))?, limit).await
+                }
+            )*
+        }
+    };
+}
+
+/// A test checking that every table entry's `Pipeline` method buffers
+/// the command its entry spells out.
+#[cfg(test)]
+macro_rules! pipeline_table_test {
+    (
+        { $pipeline:expr }
+        $(
+            $(#[$meta:meta])*
+            $name:ident($($arg:ident: $argty:ty),*) => [$($part:expr),+] $(-> $shape:ident)?;
+        )*
+    ) => {
+        #[test]
+        fn table_commands_are_buffered_by_pipeline() {
+            use $crate::commands::SampleArg;
+
+            Python::attach(|py| {
+                $({
+                    $(let $arg: $argty = SampleArg::sample(stringify!($arg));)*
+                    let expected: Vec<String> = vec![$(ToString::to_string(&$part)),+];
+                    let pipe = Py::new(py, $pipeline).unwrap();
+                    Pipeline::$name(pipe.bind(py).borrow_mut(), $($arg.clone()),*).unwrap();
+                    assert_eq!(pipe.borrow(py).commands, [expected], stringify!($name));
+                })*
+            });
+        }
+    };
+}
+
+pub(crate) use {async_redis_methods, command_table, pipeline_methods, redis_methods};
+#[cfg(test)]
+pub(crate) use pipeline_table_test;
+
+/// Placeholder values for table arguments in generated tests.
+#[cfg(test)]
+pub(crate) trait SampleArg {
+    fn sample(name: &str) -> Self;
+}
+
+#[cfg(test)]
+impl SampleArg for String {
+    fn sample(name: &str) -> Self {
+        format!("<{name}>")
+    }
+}
+
+#[cfg(test)]
+impl SampleArg for i64 {
+    fn sample(name: &str) -> Self {
+        name.len() as i64
+    }
+}
@@ -0,0 +1,152 @@
+//! Raw RESP frame capture behind `Redis(capture_frames=N)`.
+//!
+//! When enabled, every connection a client opens copies the bytes it
+//! writes and the replies it reads into a shared [`FrameLog`], a ring
+//! buffer of the last N frames. Each frame keeps at most
+//! [`MAX_FRAME_BYTES`], so a capture stays small however large the
+//! values are. [`FrameLog::dump`] renders the frames as a hexdump for
+//! diagnosing parser or proxy issues.
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+
+/// Bytes kept of each captured frame.
+pub const MAX_FRAME_BYTES: usize = 4096;
+
+/// Which way a frame went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Sent,
+    Received,
+}
+
+/// One captured write or reply.
+#[derive(Debug, Clone)]
+struct Frame {
+    direction: Direction,
+    /// Server address ("host:port").
+    addr: String,
+    /// The first [`MAX_FRAME_BYTES`] bytes.
+    data: Vec<u8>,
+    /// Size of the whole frame.
+    len: usize,
+    timestamp: SystemTime,
+}
+
+/// Ring buffer of the frames a client's connections sent and received.
+#[derive(Debug)]
+pub struct FrameLog {
+    frames: Mutex<VecDeque<Frame>>,
+    capacity: usize,
+}
+
+impl FrameLog {
+    /// Create a log keeping the last `capacity` frames.
+    pub fn new(capacity: usize) -> Self {
+        Self { frames: Mutex::new(VecDeque::new()), capacity: capacity.max(1) }
+    }
+
+    /// Copy (the start of) `data` into the log.
+    pub fn record(&self, direction: Direction, addr: &str, data: &[u8]) {
+        let frame = Frame {
+            direction,
+            addr: addr.to_string(),
+            data: data[..data.len().min(MAX_FRAME_BYTES)].to_vec(),
+            len: data.len(),
+            timestamp: SystemTime::now(),
+        };
+        let mut frames = self.frames.lock();
+        if frames.len() >= self.capacity {
+            frames.pop_front();
+        }
+        frames.push_back(frame);
+    }
+
+    /// Hexdump of the frames, oldest first, limited to the last `limit`
+    /// when given.
+    ///
+    /// Each frame starts with a header line such as
+    /// `>>> 127.0.0.1:6379  14 bytes  @1760700000.123456` (`<<<` for
+    /// replies), followed by 16 bytes per line in hex and ASCII.
+    pub fn dump(&self, limit: Option<usize>) -> String {
+        let frames = self.frames.lock();
+        let skip = limit.map_or(0, |n| frames.len().saturating_sub(n));
+        let mut out = String::new();
+        for frame in frames.iter().skip(skip) {
+            let arrow = match frame.direction {
+                Direction::Sent => ">>>",
+                Direction::Received => "<<<",
+            };
+            let secs = frame.timestamp.duration_since(UNIX_EPOCH).map_or(0.0, |d| d.as_secs_f64());
+            let _ = write!(out, "{arrow} {}  {} bytes", frame.addr, frame.len);
+            if frame.data.len() < frame.len {
+                let _ = write!(out, " (first {} shown)", frame.data.len());
+            }
+            let _ = writeln!(out, "  @{secs:.6}");
+            hexdump(&mut out, &frame.data);
+        }
+        out
+    }
+}
+
+/// Append `data` to `out` as `offset  hex  |ascii|` lines.
+fn hexdump(out: &mut String, data: &[u8]) {
+    for (line, chunk) in data.chunks(16).enumerate() {
+        let _ = write!(out, "{:08x}  ", line * 16);
+        for i in 0..16 {
+            match chunk.get(i) {
+                Some(byte) => {
+                    let _ = write!(out, "{byte:02x} ");
+                }
+                None => out.push_str("   "),
+            }
+            if i == 7 {
+                out.push(' ');
+            }
+        }
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+            .collect();
+        let _ = writeln!(out, " |{ascii}|");
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hexdump_lines() {
+        let mut out = String::new();
+        hexdump(&mut out, b"*2\r\n$3\r\nGET\r\n$1\r\nk\r\n");
+        assert_eq!(
+            out,
+            "00000000  2a 32 0d 0a 24 33 0d 0a  47 45 54 0d 0a 24 31 0d  |*2..$3..GET..$1.|\n\
+             00000010  0a 6b 0d 0a                                       |.k..|\n"
+        );
+    }
+
+    #[test]
+    fn ring_buffer_keeps_latest_frames() {
+        let log = FrameLog::new(2);
+        log.record(Direction::Sent, "a:1", b"*1\r\n$4\r\nPING\r\n");
+        log.record(Direction::Received, "a:1", b"+PONG\r\n");
+        log.record(Direction::Sent, "a:1", &[b'x'; MAX_FRAME_BYTES + 10]);
+        let dump = log.dump(None);
+        assert!(!dump.contains("PING"));
+        assert!(dump.starts_with("<<< a:1  7 bytes  @"));
+        assert!(dump.contains("|+PONG..|"));
+        assert!(dump.contains(&format!(">>> a:1  {} bytes (first {MAX_FRAME_BYTES} shown)  @", MAX_FRAME_BYTES + 10)));
+        assert_eq!(dump.lines().count(), 2 + 1 + MAX_FRAME_BYTES / 16);
+
+        let last = log.dump(Some(1));
+        assert!(last.starts_with(">>> "));
+        assert_eq!(FrameLog::new(3).dump(None), "");
+    }
+}
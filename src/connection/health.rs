@@ -0,0 +1,230 @@
+//! Per-connection health tracking.
+//!
+//! Each connection keeps exponentially weighted moving averages of its
+//! response latency and transport error rate. The pool uses the resulting
+//! score to prefer healthy idle connections on checkout and to recycle
+//! connections that have degraded (e.g. a socket stuck behind a lossy path).
+//!
+//! Blocking commands are not latency samples, since their round trip
+//! measures how long the server waited for data, not the connection.
+
+use std::time::Duration;
+
+use crate::connection::load::frame_command;
+
+/// Weight of the newest sample in the moving averages.
+const EWMA_ALPHA: f64 = 0.2;
+
+/// Samples required before a connection can be judged degraded.
+const MIN_SAMPLES: u32 = 5;
+
+/// Error-rate above which a connection is recycled.
+const MAX_ERROR_RATE: f64 = 0.5;
+
+/// A connection is degraded when its latency exceeds the pool baseline by
+/// this factor…
+const DEGRADED_LATENCY_FACTOR: f64 = 5.0;
+
+/// …and is above this absolute floor (µs), so that sub-millisecond jitter
+/// on a healthy LAN never triggers recycling.
+const DEGRADED_LATENCY_FLOOR_US: f64 = 1_000.0;
+
+/// Moving-window health statistics for one connection.
+#[derive(Debug, Clone, Default)]
+pub struct HealthScore {
+    /// EWMA of request→first-reply latency in microseconds.
+    latency_us: f64,
+    /// EWMA of transport failures (1.0 = every recent operation failed).
+    error_rate: f64,
+    /// Number of samples recorded (saturating).
+    samples: u32,
+}
+
+impl HealthScore {
+    /// Record a successful round-trip.
+    pub fn record_latency(&mut self, latency: Duration) {
+        let us = latency.as_secs_f64() * 1_000_000.0;
+        self.latency_us = if self.samples == 0 {
+            us
+        } else {
+            EWMA_ALPHA * us + (1.0 - EWMA_ALPHA) * self.latency_us
+        };
+        self.error_rate *= 1.0 - EWMA_ALPHA;
+        self.samples = self.samples.saturating_add(1);
+    }
+
+    /// Record a transport-level failure (I/O error or timeout).
+    pub fn record_error(&mut self) {
+        self.error_rate = EWMA_ALPHA + (1.0 - EWMA_ALPHA) * self.error_rate;
+        self.samples = self.samples.saturating_add(1);
+    }
+
+    /// Smoothed latency in microseconds.
+    pub fn latency_us(&self) -> f64 {
+        self.latency_us
+    }
+
+    /// Smoothed transport error rate in `[0, 1]`.
+    pub fn error_rate(&self) -> f64 {
+        self.error_rate
+    }
+
+    /// Number of recorded samples.
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Health score in `(0, 1]` — higher is healthier.
+    ///
+    /// The success rate divided by one plus the smoothed latency in
+    /// milliseconds: 1 ms halves the score, 2 ms leave a third of it.
+    pub fn score(&self) -> f64 {
+        (1.0 - self.error_rate) / (1.0 + self.latency_us / 1_000.0)
+    }
+
+    /// Whether this connection should be recycled, given the pool-wide
+    /// latency baseline (µs, 0 = unknown).
+    pub fn is_degraded(&self, baseline_us: f64) -> bool {
+        if self.samples < MIN_SAMPLES {
+            return false;
+        }
+        if self.error_rate > MAX_ERROR_RATE {
+            return true;
+        }
+        baseline_us > 0.0
+            && self.latency_us > DEGRADED_LATENCY_FLOOR_US
+            && self.latency_us > baseline_us * DEGRADED_LATENCY_FACTOR
+    }
+}
+
+/// Whether the request `frame` may block on the server, so its round
+/// trip must not count as a latency sample.
+pub fn is_blocking(frame: &[u8]) -> bool {
+    let Some(name) = frame_command(frame) else { return false };
+    let name = name.to_ascii_uppercase();
+    match name.as_slice() {
+        b"BLPOP" | b"BRPOP" | b"BLMOVE" | b"BRPOPLPUSH" | b"BLMPOP" | b"BZPOPMIN" | b"BZPOPMAX" | b"BZMPOP"
+        | b"WAIT" | b"WAITAOF" => true,
+        b"XREAD" | b"XREADGROUP" => frame
+            .split(|&b| b == b'\n')
+            .any(|line| line.strip_suffix(b"\r").is_some_and(|arg| arg.eq_ignore_ascii_case(b"BLOCK"))),
+        _ => false,
+    }
+}
+
+/// Fold a connection's latency into a pool-wide baseline.
+pub fn update_baseline(baseline_us: f64, sample_us: f64) -> f64 {
+    if baseline_us == 0.0 {
+        sample_us
+    } else {
+        EWMA_ALPHA * sample_us + (1.0 - EWMA_ALPHA) * baseline_us
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_connection_is_healthy() {
+        let h = HealthScore::default();
+        assert_eq!(h.score(), 1.0);
+        assert!(!h.is_degraded(100.0));
+    }
+
+    #[test]
+    fn first_sample_seeds_latency() {
+        let mut h = HealthScore::default();
+        h.record_latency(Duration::from_micros(500));
+        assert_eq!(h.latency_us(), 500.0);
+        h.record_latency(Duration::from_micros(1_500));
+        assert!((h.latency_us() - 700.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn errors_decay_with_successes() {
+        let mut h = HealthScore::default();
+        h.record_error();
+        let after_error = h.error_rate();
+        assert!(after_error > 0.0);
+        h.record_latency(Duration::from_micros(100));
+        assert!(h.error_rate() < after_error);
+    }
+
+    #[test]
+    fn score_halves_at_one_millisecond() {
+        let mut h = HealthScore::default();
+        h.record_latency(Duration::from_millis(1));
+        assert_eq!(h.score(), 0.5);
+        h.record_error();
+        assert!(h.score() < 0.5);
+    }
+
+    #[test]
+    fn lower_latency_scores_higher() {
+        let mut fast = HealthScore::default();
+        let mut slow = HealthScore::default();
+        fast.record_latency(Duration::from_micros(200));
+        slow.record_latency(Duration::from_millis(20));
+        assert!(fast.score() > slow.score());
+    }
+
+    #[test]
+    fn needs_min_samples_before_degrading() {
+        let mut h = HealthScore::default();
+        for _ in 0..MIN_SAMPLES - 1 {
+            h.record_latency(Duration::from_millis(10));
+        }
+        assert!(!h.is_degraded(500.0));
+        h.record_latency(Duration::from_millis(10));
+        assert!(h.is_degraded(500.0));
+    }
+
+    #[test]
+    fn frequent_errors_degrade_without_baseline() {
+        let mut h = HealthScore::default();
+        for _ in 0..MIN_SAMPLES - 1 {
+            h.record_error();
+        }
+        assert!(!h.is_degraded(0.0));
+        h.record_error();
+        assert!(h.is_degraded(0.0));
+    }
+
+    #[test]
+    fn slow_relative_to_baseline_is_degraded() {
+        let mut h = HealthScore::default();
+        for _ in 0..MIN_SAMPLES {
+            h.record_latency(Duration::from_millis(10));
+        }
+        assert!(h.is_degraded(500.0));
+        assert!(!h.is_degraded(5_000.0));
+        // No baseline yet → latency alone never degrades
+        assert!(!h.is_degraded(0.0));
+    }
+
+    #[test]
+    fn latency_floor_ignores_lan_jitter() {
+        let mut h = HealthScore::default();
+        for _ in 0..MIN_SAMPLES {
+            h.record_latency(Duration::from_micros(900));
+        }
+        assert!(!h.is_degraded(50.0));
+    }
+
+    #[test]
+    fn blocking_commands_are_recognised() {
+        assert!(is_blocking(b"*3\r\n$5\r\nblpop\r\n$1\r\nq\r\n$1\r\n0\r\n"));
+        assert!(is_blocking(b"*6\r\n$5\r\nXREAD\r\n$5\r\nBLOCK\r\n$1\r\n0\r\n$7\r\nSTREAMS\r\n$1\r\ns\r\n$1\r\n$\r\n"));
+        assert!(!is_blocking(b"*4\r\n$5\r\nXREAD\r\n$7\r\nSTREAMS\r\n$1\r\ns\r\n$1\r\n0\r\n"));
+        assert!(!is_blocking(b"*2\r\n$3\r\nGET\r\n$1\r\nk\r\n"));
+    }
+
+    #[test]
+    fn baseline_update() {
+        assert_eq!(update_baseline(0.0, 300.0), 300.0);
+        assert!((update_baseline(100.0, 600.0) - 200.0).abs() < 1e-9);
+    }
+}
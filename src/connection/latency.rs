@@ -0,0 +1,255 @@
+//! Per-command-family latency behind `command_stats()`.
+//!
+//! Every connection a client opens reports the round trip of each request
+//! to the client's [`CommandStats`], filed under the family of the command
+//! (strings, hashes, graph, …). Each family keeps a count, a timeout count
+//! and a latency histogram of the same shape as the pool's checkout wait
+//! histogram, so percentiles are accurate to within 25%. The counters are
+//! cumulative until [`CommandStats::reset`].
+
+use crate::connection::stats::{bucket, percentile_ms, BUCKETS};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Group of related commands that latency is reported by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandFamily {
+    Strings,
+    Hashes,
+    Lists,
+    Sets,
+    SortedSets,
+    Streams,
+    Keys,
+    Scripting,
+    PubSub,
+    Graph,
+    Server,
+    Other,
+}
+
+impl CommandFamily {
+    /// Every family, in reporting order.
+    pub const ALL: [Self; 12] = [
+        Self::Strings,
+        Self::Hashes,
+        Self::Lists,
+        Self::Sets,
+        Self::SortedSets,
+        Self::Streams,
+        Self::Keys,
+        Self::Scripting,
+        Self::PubSub,
+        Self::Graph,
+        Self::Server,
+        Self::Other,
+    ];
+
+    /// The family name exposed to Python.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Strings => "strings",
+            Self::Hashes => "hashes",
+            Self::Lists => "lists",
+            Self::Sets => "sets",
+            Self::SortedSets => "sorted_sets",
+            Self::Streams => "streams",
+            Self::Keys => "keys",
+            Self::Scripting => "scripting",
+            Self::PubSub => "pubsub",
+            Self::Graph => "graph",
+            Self::Server => "server",
+            Self::Other => "other",
+        }
+    }
+
+    /// Family of the command named `name` (any case).
+    pub fn of(name: &[u8]) -> Self {
+        // Upper-cased on the stack: this runs for every request
+        let mut upper = [0u8; 32];
+        let Some(buf) = upper.get_mut(..name.len()) else { return Self::Other };
+        buf.copy_from_slice(name);
+        buf.make_ascii_uppercase();
+        let Ok(name) = std::str::from_utf8(buf) else { return Self::Other };
+        if name.starts_with("GRAPH.") {
+            return Self::Graph;
+        }
+        match name {
+            "GET" | "SET" | "MGET" | "MSET" | "MSETNX" | "SETNX" | "SETEX" | "PSETEX" | "GETSET" | "GETDEL"
+            | "GETEX" | "INCR" | "INCRBY" | "INCRBYFLOAT" | "DECR" | "DECRBY" | "APPEND" | "STRLEN"
+            | "GETRANGE" | "SETRANGE" | "SUBSTR" | "LCS" | "SETBIT" | "GETBIT" | "BITCOUNT" | "BITPOS"
+            | "BITOP" | "BITFIELD" | "BITFIELD_RO" => Self::Strings,
+            "HELLO" => Self::Server,
+            _ if name.starts_with('H') => Self::Hashes,
+            "LPUSH" | "RPUSH" | "LPUSHX" | "RPUSHX" | "LPOP" | "RPOP" | "LRANGE" | "LLEN" | "LINDEX" | "LSET"
+            | "LREM" | "LTRIM" | "LINSERT" | "LPOS" | "LMOVE" | "BLMOVE" | "LMPOP" | "BLMPOP" | "BLPOP"
+            | "BRPOP" | "RPOPLPUSH" | "BRPOPLPUSH" => Self::Lists,
+            "SADD" | "SREM" | "SMEMBERS" | "SISMEMBER" | "SMISMEMBER" | "SCARD" | "SPOP" | "SRANDMEMBER"
+            | "SMOVE" | "SINTER" | "SUNION" | "SDIFF" | "SINTERSTORE" | "SUNIONSTORE" | "SDIFFSTORE"
+            | "SINTERCARD" | "SSCAN" => Self::Sets,
+            "BZPOPMIN" | "BZPOPMAX" | "BZMPOP" => Self::SortedSets,
+            _ if name.starts_with('Z') => Self::SortedSets,
+            _ if name.starts_with('X') => Self::Streams,
+            "DEL" | "UNLINK" | "EXISTS" | "EXPIRE" | "PEXPIRE" | "EXPIREAT" | "PEXPIREAT" | "EXPIRETIME"
+            | "PEXPIRETIME" | "TTL" | "PTTL" | "PERSIST" | "TYPE" | "RENAME" | "RENAMENX" | "KEYS" | "SCAN"
+            | "TOUCH" | "COPY" | "DUMP" | "RESTORE" | "OBJECT" | "RANDOMKEY" | "MOVE" | "SORT" | "SORT_RO" => {
+                Self::Keys
+            }
+            "EVAL" | "EVALSHA" | "EVAL_RO" | "EVALSHA_RO" | "SCRIPT" | "FCALL" | "FCALL_RO" | "FUNCTION" => {
+                Self::Scripting
+            }
+            "PUBLISH" | "SPUBLISH" | "SUBSCRIBE" | "UNSUBSCRIBE" | "PSUBSCRIBE" | "PUNSUBSCRIBE"
+            | "SSUBSCRIBE" | "SUNSUBSCRIBE" | "PUBSUB" => Self::PubSub,
+            "PING" | "ECHO" | "AUTH" | "SELECT" | "INFO" | "CONFIG" | "CLIENT" | "CLUSTER" | "COMMAND"
+            | "DBSIZE" | "FLUSHDB" | "FLUSHALL" | "TIME" | "SAVE" | "BGSAVE" | "BGREWRITEAOF" | "LASTSAVE"
+            | "SLOWLOG" | "LATENCY" | "MEMORY" | "DEBUG" | "MULTI" | "EXEC" | "DISCARD" | "WATCH"
+            | "UNWATCH" | "RESET" | "READONLY" | "READWRITE" | "ASKING" | "WAIT" | "SWAPDB" | "ACL"
+            | "ROLE" | "MONITOR" => Self::Server,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Counters of one family.
+struct FamilyCounters {
+    count: AtomicU64,
+    timeouts: AtomicU64,
+    max_us: AtomicU64,
+    buckets: [AtomicU64; BUCKETS],
+}
+
+impl Default for FamilyCounters {
+    fn default() -> Self {
+        Self {
+            count: AtomicU64::new(0),
+            timeouts: AtomicU64::new(0),
+            max_us: AtomicU64::new(0),
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+}
+
+/// Latency and timeouts per command family, shared by a client's
+/// connections.
+pub struct CommandStats {
+    families: [FamilyCounters; CommandFamily::ALL.len()],
+}
+
+impl Default for CommandStats {
+    fn default() -> Self {
+        Self { families: std::array::from_fn(|_| FamilyCounters::default()) }
+    }
+}
+
+impl std::fmt::Debug for CommandStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommandStats").finish_non_exhaustive()
+    }
+}
+
+impl CommandStats {
+    /// Count a reply that took `latency` to arrive.
+    pub fn record_reply(&self, family: CommandFamily, latency: Duration) {
+        let us = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        let counters = &self.families[family as usize];
+        counters.count.fetch_add(1, Ordering::Relaxed);
+        counters.max_us.fetch_max(us, Ordering::Relaxed);
+        counters.buckets[bucket(us)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a request whose reply did not arrive in time.
+    pub fn record_timeout(&self, family: CommandFamily) {
+        self.families[family as usize].timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Zero every counter.
+    pub fn reset(&self) {
+        for counters in &self.families {
+            counters.count.store(0, Ordering::Relaxed);
+            counters.timeouts.store(0, Ordering::Relaxed);
+            counters.max_us.store(0, Ordering::Relaxed);
+            for bucket in &counters.buckets {
+                bucket.store(0, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Copy the counters of every family that has seen a request.
+    pub fn snapshot(&self) -> Vec<FamilySnapshot> {
+        CommandFamily::ALL
+            .into_iter()
+            .zip(&self.families)
+            .map(|(family, counters)| FamilySnapshot {
+                family,
+                count: counters.count.load(Ordering::Relaxed),
+                timeouts: counters.timeouts.load(Ordering::Relaxed),
+                max_us: counters.max_us.load(Ordering::Relaxed),
+                buckets: counters.buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect(),
+            })
+            .filter(|snapshot| snapshot.count > 0 || snapshot.timeouts > 0)
+            .collect()
+    }
+}
+
+/// Point-in-time copy of one family's counters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FamilySnapshot {
+    pub family: CommandFamily,
+    pub count: u64,
+    pub timeouts: u64,
+    pub max_us: u64,
+    buckets: Vec<u64>,
+}
+
+impl FamilySnapshot {
+    /// Latency at percentile `p` (0–100), in milliseconds.
+    pub fn percentile_ms(&self, p: f64) -> f64 {
+        percentile_ms(&self.buckets, self.max_us, p)
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn families_by_command_name() {
+        assert_eq!(CommandFamily::of(b"get"), CommandFamily::Strings);
+        assert_eq!(CommandFamily::of(b"HGETALL"), CommandFamily::Hashes);
+        assert_eq!(CommandFamily::of(b"HELLO"), CommandFamily::Server);
+        assert_eq!(CommandFamily::of(b"BLPOP"), CommandFamily::Lists);
+        assert_eq!(CommandFamily::of(b"SINTERSTORE"), CommandFamily::Sets);
+        assert_eq!(CommandFamily::of(b"ZUNIONSTORE"), CommandFamily::SortedSets);
+        assert_eq!(CommandFamily::of(b"XREADGROUP"), CommandFamily::Streams);
+        assert_eq!(CommandFamily::of(b"EXPIRE"), CommandFamily::Keys);
+        assert_eq!(CommandFamily::of(b"EVALSHA"), CommandFamily::Scripting);
+        assert_eq!(CommandFamily::of(b"PUBLISH"), CommandFamily::PubSub);
+        assert_eq!(CommandFamily::of(b"graph.ro_query"), CommandFamily::Graph);
+        assert_eq!(CommandFamily::of(b"PING"), CommandFamily::Server);
+        assert_eq!(CommandFamily::of(b"PFADD"), CommandFamily::Other);
+    }
+
+    #[test]
+    fn snapshot_skips_unused_families_and_resets() {
+        let stats = CommandStats::default();
+        for ms in 1..=100 {
+            stats.record_reply(CommandFamily::Graph, Duration::from_millis(ms));
+        }
+        stats.record_timeout(CommandFamily::Graph);
+        stats.record_timeout(CommandFamily::Keys);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.iter().map(|s| s.family).collect::<Vec<_>>(), [CommandFamily::Keys, CommandFamily::Graph]);
+        let graph = &snapshot[1];
+        assert_eq!((graph.count, graph.timeouts, graph.max_us), (100, 1, 100_000));
+        let p50 = graph.percentile_ms(50.0);
+        assert!((50.0..=62.5).contains(&p50), "{p50}");
+        assert_eq!(graph.percentile_ms(100.0), 100.0);
+        assert_eq!(snapshot[0].percentile_ms(99.0), 0.0);
+
+        stats.reset();
+        assert!(stats.snapshot().is_empty());
+    }
+}
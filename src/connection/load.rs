@@ -0,0 +1,165 @@
+//! Pool saturation snapshots for timeout errors.
+//!
+//! Every pooled connection shares an [`InFlight`] slot with its pool's
+//! [`PoolLoad`], recording the command it is waiting on. When a checkout
+//! or a read times out, [`PoolLoad::describe`] summarizes how busy the
+//! pool was, so the error points at the cause of the stall. The same
+//! handle carries the pool's cumulative [`PoolStats`].
+
+use crate::connection::stats::PoolStats;
+use parking_lot::Mutex;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+/// Bytes of an encoded request kept to name the command later.
+const HEAD_LEN: usize = 32;
+
+/// The request a connection is waiting on, if any.
+#[derive(Default)]
+pub struct InFlight(Mutex<Option<Request>>);
+
+/// Start of an encoded request and when it was written.
+#[derive(Clone, Copy)]
+struct Request {
+    head: [u8; HEAD_LEN],
+    len: usize,
+    sent: Instant,
+}
+
+impl InFlight {
+    /// Record that `frame` was written at `sent`.
+    ///
+    /// Only the first bytes are copied; the name is parsed on demand.
+    pub fn start(&self, frame: &[u8], sent: Instant) {
+        let len = frame.len().min(HEAD_LEN);
+        let mut head = [0; HEAD_LEN];
+        head[..len].copy_from_slice(&frame[..len]);
+        *self.0.lock() = Some(Request { head, len, sent });
+    }
+
+    /// Record that the reply arrived.
+    pub fn finish(&self) {
+        *self.0.lock() = None;
+    }
+
+    /// Name of the pending command and how long it has been running.
+    pub fn current(&self) -> Option<(String, Duration)> {
+        let request = (*self.0.lock())?;
+        Some((command_name(&request.head[..request.len]), request.sent.elapsed()))
+    }
+}
+
+/// How busy a pool is and what it has done so far, shared with its
+/// connections.
+pub struct PoolLoad {
+    max_size: usize,
+    /// The pool's checkout semaphore (one permit per free connection).
+    semaphore: Mutex<Arc<Semaphore>>,
+    /// Slots of the connections this pool opened; dead ones are pruned on
+    /// [`register`](Self::register).
+    slots: Mutex<Vec<Weak<InFlight>>>,
+    stats: PoolStats,
+}
+
+impl PoolLoad {
+    pub fn new(max_size: usize, semaphore: Arc<Semaphore>) -> Self {
+        Self { max_size, semaphore: Mutex::new(semaphore), slots: Mutex::new(Vec::new()), stats: PoolStats::default() }
+    }
+
+    /// Count checkouts against `semaphore` from now on (after a fork).
+    pub fn replace_semaphore(&self, semaphore: Arc<Semaphore>) {
+        *self.semaphore.lock() = semaphore;
+    }
+
+    /// Counters updated by the pool and its connections.
+    pub fn stats(&self) -> &PoolStats {
+        &self.stats
+    }
+
+    /// Track a new connection's slot.
+    pub fn register(&self, slot: &Arc<InFlight>) {
+        let mut slots = self.slots.lock();
+        slots.retain(|slot| slot.strong_count() > 0);
+        slots.push(Arc::downgrade(slot));
+    }
+
+    /// Connections currently checked out.
+    pub fn in_use(&self) -> usize {
+        self.max_size.saturating_sub(self.semaphore.lock().available_permits())
+    }
+
+    /// The command that has been waiting longest for its reply.
+    pub fn longest_in_flight(&self) -> Option<(String, Duration)> {
+        self.slots
+            .lock()
+            .iter()
+            .filter_map(|slot| slot.upgrade()?.current())
+            .max_by_key(|(_, running)| *running)
+    }
+
+    /// One-line summary, e.g. `pool: 8/8 connections in use, longest
+    /// in-flight: KEYS for 31.2s`.
+    pub fn describe(&self) -> String {
+        let mut summary = format!("pool: {}/{} connections in use", self.in_use(), self.max_size);
+        if let Some((name, running)) = self.longest_in_flight() {
+            summary.push_str(&format!(", longest in-flight: {name} for {running:.1?}"));
+        }
+        summary
+    }
+}
+
+/// Name of the command an encoded request starts with (`?` if the
+/// request was cut off before it).
+fn command_name(frame: &[u8]) -> String {
+    match frame_command(frame) {
+        Some(name) => String::from_utf8_lossy(name).to_ascii_uppercase(),
+        None => "?".to_string(),
+    }
+}
+
+/// The command name bytes of an encoded request, as sent.
+pub(crate) fn frame_command(frame: &[u8]) -> Option<&[u8]> {
+    frame
+        .split(|&b| b == b'\n')
+        .nth(2)
+        .and_then(|line| line.strip_suffix(b"\r"))
+        .filter(|_| frame.starts_with(b"*"))
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_name_from_frame() {
+        assert_eq!(command_name(b"*2\r\n$3\r\nget\r\n$1\r\nk\r\n"), "GET");
+        assert_eq!(command_name(b"*1\r\n$4\r\nPING\r\n"), "PING");
+        // Cut off mid-name, or not an array
+        assert_eq!(command_name(b"*1\r\n$14\r\nCLIENT TRAC"), "?");
+        assert_eq!(command_name(b"PING\r\n"), "?");
+    }
+
+    #[test]
+    fn describe_names_longest_in_flight() {
+        let semaphore = Arc::new(Semaphore::new(4));
+        let load = PoolLoad::new(4, Arc::clone(&semaphore));
+        let _permit = semaphore.try_acquire().unwrap();
+        assert_eq!(load.describe(), "pool: 1/4 connections in use");
+
+        let (slow, fast) = (Arc::new(InFlight::default()), Arc::new(InFlight::default()));
+        load.register(&slow);
+        load.register(&fast);
+        slow.start(b"*1\r\n$4\r\nKEYS\r\n", Instant::now() - Duration::from_secs(3));
+        fast.start(b"*1\r\n$3\r\nGET\r\n", Instant::now());
+        assert_eq!(load.longest_in_flight().unwrap().0, "KEYS");
+        assert!(load.describe().starts_with("pool: 1/4 connections in use, longest in-flight: KEYS for 3."));
+
+        slow.finish();
+        assert_eq!(load.longest_in_flight().unwrap().0, "GET");
+        drop(fast);
+        assert!(load.longest_in_flight().is_none());
+    }
+}
@@ -1,5 +1,13 @@
+pub mod capture;
+pub mod health;
+pub mod latency;
+pub mod load;
 pub mod pool;
+pub mod sizing;
+pub mod stats;
 pub mod tcp;
+#[cfg(feature = "tls")]
+pub mod tls;
 
 pub use pool::ConnectionPool;
 pub use tcp::RedisConnection;
@@ -3,145 +3,482 @@
 //! Uses a semaphore for max size control and a deque for idle connection reuse.
 //! The idle queue uses `parking_lot::Mutex` (sync, held very briefly) so
 //! connections can be returned in `Drop` without needing async.
+//!
+//! Checkout prefers the idle connection with the best [`HealthScore`];
+//! connections whose score has degraded relative to the pool's latency
+//! baseline are recycled instead of being reused. With a health check
+//! interval configured, a connection idle for longer is pinged before it
+//! is handed out and replaced by a fresh one if the ping fails.
+//!
+//! Shared pools run a background reaper that closes idle connections past
+//! `idle_timeout_ms` or older than `max_lifetime_ms` without waiting for a
+//! checkout, then reopens enough of them to keep `min_idle` warm.
+//!
+//! A checkout that waits longer than `pool_timeout_ms` for a free connection
+//! fails with [`PyrsedisError::PoolTimeout`], describing the pool's
+//! [`PoolLoad`] at that moment.
+//!
+//! A request run under [`without_queueing`] does not wait that long: its
+//! checkouts fail with [`PyrsedisError::WouldBlock`] unless a connection
+//! frees up within the wait it was given, zero meaning none.
+//!
+//! Pipelines and transactions check out through [`ConnectionPool::get_bulk`],
+//! which can be capped below the pool size so that `reserved_connections`
+//! stay free for single commands while bulk jobs run.
 
 use crate::config::ConnectionConfig;
+use crate::connection::health::{self, HealthScore};
+use crate::connection::load::PoolLoad;
+use crate::connection::stats::StatsSnapshot;
 use crate::connection::tcp::RedisConnection;
 use crate::error::{PyrsedisError, Result};
+use crate::runtime;
 
 use parking_lot::Mutex as SyncMutex;
 use std::collections::VecDeque;
-use std::time::Duration;
-use tokio::sync::{Semaphore, SemaphorePermit};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::sync::{AcquireError, OwnedSemaphorePermit, Semaphore};
+
+/// Upper bound on the pause between two reaper passes.
+const MAX_REAP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Lower bound on the pause between two reaper passes.
+const MIN_REAP_INTERVAL: Duration = Duration::from_millis(10);
+
+tokio::task_local! {
+    static CHECKOUT_WAIT: Duration;
+}
+
+/// Run `request` with its checkouts waiting at most `wait` for a free
+/// connection, and failing with [`PyrsedisError::WouldBlock`] instead of
+/// queueing behind busy connections.
+pub async fn without_queueing<T>(wait: Duration, request: impl Future<Output = T>) -> T {
+    CHECKOUT_WAIT.scope(wait, request).await
+}
 
 /// An async connection pool.
 pub struct ConnectionPool {
     /// Idle connections ready for reuse (sync mutex — held very briefly).
     idle: SyncMutex<VecDeque<RedisConnection>>,
-    /// Semaphore limiting total checked-out connections.
-    semaphore: Semaphore,
+    /// Semaphore limiting total checked-out connections (replaced by
+    /// [`reset_after_fork`](Self::reset_after_fork)).
+    semaphore: SyncMutex<Arc<Semaphore>>,
+    /// Semaphore limiting connections checked out by pipelines and
+    /// transactions (`None` when no connections are reserved).
+    bulk: SyncMutex<Option<Arc<Semaphore>>>,
     /// Pool configuration.
     config: ConnectionConfig,
     /// Maximum pool size.
     max_size: usize,
+    /// How long a checkout may wait for a free connection, if limited.
+    pool_timeout: Option<Duration>,
+    /// Checked-out count and in-flight commands, shared with connections.
+    load: Arc<PoolLoad>,
     /// How long a connection can be idle before being dropped.
     idle_timeout: Duration,
+    /// How long a connection may live before being replaced, if limited.
+    max_lifetime: Option<Duration>,
+    /// Idle time after which a connection is pinged on checkout, if any.
+    health_check_interval: Option<Duration>,
+    /// Bumped by [`disconnect_all`](Self::disconnect_all); connections
+    /// checked out under an older generation are dropped on return.
+    generation: AtomicU64,
+    /// Pool-wide latency EWMA in microseconds (`f64` bits).
+    latency_baseline: AtomicU64,
+    /// Handle to this pool for the reaper task (empty unless created with
+    /// [`shared`](Self::shared)).
+    this: Weak<ConnectionPool>,
 }
 
 impl ConnectionPool {
     /// Create a new connection pool from config.
+    ///
+    /// Stale connections are only dropped on checkout and return; use
+    /// [`shared`](Self::shared) to also reap them in the background.
     pub fn new(config: ConnectionConfig) -> Self {
+        Self::with_handle(config, Weak::new())
+    }
+
+    /// Create a pool whose idle connections are reaped in the background.
+    ///
+    /// The reaper task holds only a weak reference and exits once the
+    /// pool is dropped.
+    pub fn shared(config: ConnectionConfig) -> Arc<Self> {
+        let pool = Arc::new_cyclic(|this| Self::with_handle(config, this.clone()));
+        pool.start_reaper();
+        pool
+    }
+
+    fn with_handle(config: ConnectionConfig, this: Weak<ConnectionPool>) -> Self {
         let max_size = config.pool_size;
         let idle_timeout = Duration::from_millis(config.idle_timeout_ms);
+        let max_lifetime = (config.max_lifetime_ms > 0).then(|| Duration::from_millis(config.max_lifetime_ms));
+        let health_check_interval =
+            (config.health_check_interval_ms > 0).then(|| Duration::from_millis(config.health_check_interval_ms));
+        let pool_timeout = (config.pool_timeout_ms > 0).then(|| Duration::from_millis(config.pool_timeout_ms));
+        let semaphore = Arc::new(Semaphore::new(max_size));
         Self {
             idle: SyncMutex::new(VecDeque::with_capacity(max_size)),
-            semaphore: Semaphore::new(max_size),
+            load: Arc::new(PoolLoad::new(max_size, Arc::clone(&semaphore))),
+            semaphore: SyncMutex::new(semaphore),
+            bulk: SyncMutex::new(bulk_semaphore(&config)),
             config,
             max_size,
+            pool_timeout,
             idle_timeout,
+            max_lifetime,
+            health_check_interval,
+            generation: AtomicU64::new(0),
+            latency_baseline: AtomicU64::new(0f64.to_bits()),
+            this,
         }
     }
 
+    /// Spawn the background reaper if this pool is [`shared`](Self::shared).
+    fn start_reaper(&self) {
+        if self.this.strong_count() == 0 {
+            return;
+        }
+        let limit = self.max_lifetime.map_or(self.idle_timeout, |lifetime| lifetime.min(self.idle_timeout));
+        let interval = (limit / 2).clamp(MIN_REAP_INTERVAL, MAX_REAP_INTERVAL);
+        let weak = self.this.clone();
+        runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(pool) = weak.upgrade() else {
+                    break; // Pool dropped, exit
+                };
+                pool.reap().await;
+            }
+        });
+    }
+
+    /// Close expired idle connections, then reopen those retired only
+    /// for their age, and any needed to keep `min_idle` connections warm,
+    /// as far as permits not held by checkouts allow.
+    ///
+    /// Reconnect failures are left for the next checkout to report.
+    async fn reap(&self) {
+        let (expired, aged, remaining) = {
+            let mut idle = self.idle.lock();
+            let (expired, kept): (Vec<_>, Vec<_>) = idle.drain(..).partition(|conn| self.is_expired(conn));
+            idle.extend(kept);
+            let aged = expired.iter().filter(|conn| conn.last_used.elapsed() <= self.idle_timeout).count();
+            (expired, aged, idle.len())
+        };
+        drop(expired);
+        let target = (remaining + aged).max(self.config.min_idle);
+        let _ = self.warmup(target).await;
+    }
+
+    /// Whether `conn` sat idle too long or outlived the maximum lifetime.
+    fn is_expired(&self, conn: &RedisConnection) -> bool {
+        conn.last_used.elapsed() > self.idle_timeout
+            || self.max_lifetime.is_some_and(|lifetime| conn.created_at.elapsed() > lifetime)
+    }
+
     /// Get a connection from the pool.
     ///
     /// Returns a [`PoolGuard`] which, when dropped, returns the
     /// connection to the pool.
     pub async fn get(&self) -> Result<PoolGuard<'_>> {
-        let permit = self
-            .semaphore
-            .acquire()
-            .await
-            .map_err(|_| {
-                PyrsedisError::Connection(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "pool semaphore closed",
-                ))
-            })?;
-
-        // Try to get an idle connection (sync lock, very brief)
-        let conn = {
-            let mut idle = self.idle.lock();
-            self.take_healthy_connection(&mut idle)
-        };
+        self.get_with(None, Instant::now()).await
+    }
 
-        let conn = match conn {
-            Some(c) => c,
-            None => self.create_connection().await?,
+    /// Get a connection for a pipeline or transaction.
+    ///
+    /// Like [`get`](Self::get), but with `reserved_connections` configured
+    /// it first waits until fewer than `pool_size - reserved_connections`
+    /// bulk checkouts are outstanding, so single commands always find a
+    /// free connection.
+    pub async fn get_bulk(&self) -> Result<PoolGuard<'_>> {
+        let started = Instant::now();
+        let bulk = self.bulk.lock().clone();
+        let bulk_permit = match bulk {
+            Some(bulk) => Some(self.wait_for(started, bulk.acquire_owned()).await?),
+            None => None,
         };
+        self.get_with(bulk_permit, started).await
+    }
+
+    async fn get_with(&self, bulk_permit: Option<OwnedSemaphorePermit>, started: Instant) -> Result<PoolGuard<'_>> {
+        let permit = self.wait_for(started, self.semaphore().acquire_owned()).await?;
+        self.load.stats().record_checkout(started.elapsed());
 
+        let conn = self.checkout().await?;
         Ok(PoolGuard {
             conn: Some(conn),
             pool: self,
+            generation: self.generation.load(Ordering::Acquire),
+            _permit: permit,
+            _bulk_permit: bulk_permit,
+        })
+    }
+
+    /// Wait for a semaphore `permit`, failing with
+    /// [`PyrsedisError::PoolTimeout`] once `pool_timeout_ms` has passed
+    /// since the checkout `started`, or with [`PyrsedisError::WouldBlock`]
+    /// after the wait allowed by [`without_queueing`].
+    async fn wait_for<P>(
+        &self,
+        started: Instant,
+        permit: impl Future<Output = std::result::Result<P, AcquireError>>,
+    ) -> Result<P> {
+        let wait = CHECKOUT_WAIT.try_with(|wait| *wait).ok();
+        // A zero limit still polls `permit` once, so a free one is taken
+        let acquired = match wait.or(self.pool_timeout) {
+            Some(limit) => match tokio::time::timeout(limit.saturating_sub(started.elapsed()), permit).await {
+                Ok(acquired) => acquired,
+                Err(_) if wait.is_some() => {
+                    return Err(PyrsedisError::WouldBlock(format!(
+                        "no connection was free within {limit:?} ({})",
+                        self.load.describe()
+                    )));
+                }
+                Err(_) => {
+                    return Err(PyrsedisError::PoolTimeout(format!(
+                        "no connection became free after waiting {:.1?} ({})",
+                        started.elapsed(),
+                        self.load.describe()
+                    )));
+                }
+            },
+            None => permit.await,
+        };
+        acquired.map_err(|_| PyrsedisError::Connection(std::io::Error::other("pool semaphore closed")))
+    }
+
+    /// Check out a connection for exclusive, long-lived use.
+    ///
+    /// Unlike [`get`](Self::get), the returned lease does not borrow the
+    /// pool, so it can be held across calls (e.g. by a Python object). It
+    /// still counts against `pool_size` until passed to
+    /// [`release`](Self::release) or dropped (which closes the connection).
+    pub async fn lease(&self) -> Result<LeasedConnection> {
+        let started = Instant::now();
+        let permit = self
+            .wait_for(started, self.semaphore().acquire_owned())
+            .await?;
+        self.load.stats().record_checkout(started.elapsed());
+
+        let conn = self.checkout().await?;
+        Ok(LeasedConnection {
+            conn,
+            generation: self.generation.load(Ordering::Acquire),
+            replies_suppressed: false,
             _permit: permit,
         })
     }
 
+    /// Return a leased connection to the pool.
+    ///
+    /// Sends `RESET` to discard whatever state the holder left behind, then
+    /// re-applies the pool's auth, protocol and db selection. If either step
+    /// fails the connection is closed instead of being reused.
+    ///
+    /// A lease still marked as having replies suppressed (`CLIENT REPLY
+    /// OFF|SKIP`) is always closed: shared-pool callers expect one reply
+    /// per command and would read the wrong responses. So is one whose
+    /// last command was cut short.
+    pub async fn release(&self, mut lease: LeasedConnection) {
+        if lease.replies_suppressed || lease.conn.was_interrupted() {
+            return;
+        }
+        // Subscribers and other long waits may have disabled the timeout
+        lease.conn.set_read_timeout(self.config.read_timeout_ms);
+        let restored = async {
+            lease.conn.reset().await?;
+            lease.conn.init_from(&self.config, self.config.db).await
+        }
+        .await;
+        if restored.is_ok() {
+            self.return_connection(lease.conn, lease.generation);
+        }
+    }
+
+    /// Open connections until `count` are idle (capped at the pool size),
+    /// so the next checkouts skip connection setup and initialization.
+    ///
+    /// Each connection is opened holding a free permit, and only while idle
+    /// and checked-out connections together stay within the pool size, so
+    /// warming up never competes with checkouts for a slot.
+    ///
+    /// Returns how many connections were opened.
+    pub async fn warmup(&self, count: usize) -> Result<usize> {
+        let generation = self.generation.load(Ordering::Acquire);
+        let target = count.min(self.max_size);
+        let semaphore = self.semaphore();
+        let mut opened = 0;
+        while self.idle_count() < target {
+            let Ok(_permit) = semaphore.try_acquire() else {
+                break;
+            };
+            // Idle connections hold no permit: the free ones must cover them
+            if self.idle_count() > semaphore.available_permits() {
+                break;
+            }
+            let conn = self.create_connection().await?;
+            self.return_connection(conn, generation);
+            opened += 1;
+        }
+        Ok(opened)
+    }
+
+    /// Close all idle connections.
+    ///
+    /// Connections currently checked out are closed when they are
+    /// returned instead of going back into the idle queue.
+    pub fn disconnect_all(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        let drained: Vec<RedisConnection> = self.idle.lock().drain(..).collect();
+        drop(drained);
+    }
+
+    /// Abandon all connections inherited from the parent process.
+    ///
+    /// Must only be called in a freshly forked child. The sockets (and
+    /// their registrations with the parent's I/O driver) are shared with
+    /// the parent, so they are leaked rather than closed — closing them
+    /// here would tear down the parent's connections.
+    ///
+    /// The semaphores are replaced by full ones: permits held by threads
+    /// that do not exist in the child are forgotten, and checkouts made
+    /// before the fork release theirs into the old semaphores when dropped.
+    pub fn reset_after_fork(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        for conn in self.idle.lock().drain(..) {
+            std::mem::forget(conn);
+        }
+        // The reaper ran on the parent's runtime, which is gone
+        self.start_reaper();
+        let semaphore = Arc::new(Semaphore::new(self.max_size));
+        self.load.replace_semaphore(Arc::clone(&semaphore));
+        *self.semaphore.lock() = semaphore;
+        *self.bulk.lock() = bulk_semaphore(&self.config);
+    }
+
     /// Return the number of currently idle connections.
     pub fn idle_count(&self) -> usize {
         self.idle.lock().len()
     }
 
+    /// Pool-wide smoothed latency in microseconds (0 until measured).
+    pub fn latency_baseline_us(&self) -> f64 {
+        f64::from_bits(self.latency_baseline.load(Ordering::Relaxed))
+    }
+
+    /// Address this pool connects to (see [`ConnectionConfig::primary_addr`]).
+    pub fn addr(&self) -> String {
+        self.config.primary_addr()
+    }
+
     /// Return the configured max pool size.
     pub fn max_size(&self) -> usize {
         self.max_size
     }
 
+    /// Counters since the pool was created, plus its current size.
+    pub fn stats(&self) -> StatsSnapshot {
+        let mut stats = self.load.stats().snapshot();
+        stats.idle = self.idle_count();
+        stats.in_use = self.load.in_use();
+        stats.max_size = self.max_size;
+        stats
+    }
+
     /// Return the number of available permits (roughly = max_size - checked_out).
     pub fn available(&self) -> usize {
-        self.semaphore.available_permits()
+        self.semaphore.lock().available_permits()
     }
 
-    /// Create a new connection using the pool's config.
-    async fn create_connection(&self) -> Result<RedisConnection> {
-        // VULN-05: Reject TLS requests since TLS is not yet implemented.
-        // Without this check, `rediss://` URLs silently use plaintext,
-        // exposing AUTH passwords and data.
-        if self.config.tls {
-            return Err(PyrsedisError::Protocol(
-                "TLS connections (rediss://) are not yet supported. \
-                 Use redis:// or set tls=false.".into(),
-            ));
+    /// The current checkout semaphore.
+    fn semaphore(&self) -> Arc<Semaphore> {
+        Arc::clone(&self.semaphore.lock())
+    }
+
+    /// Reuse an idle connection or open a new one.
+    ///
+    /// An idle connection unused for longer than the health check
+    /// interval must answer a `PING` first; if it does not (typically
+    /// because the server or a proxy closed it meanwhile) a new connection
+    /// replaces it, so the caller never sees the broken pipe.
+    async fn checkout(&self) -> Result<RedisConnection> {
+        // Sync lock, held very briefly
+        let conn = {
+            let mut idle = self.idle.lock();
+            self.take_healthy_connection(&mut idle)
+        };
+        match conn {
+            Some(mut conn) if self.needs_health_check(&conn) => {
+                if matches!(conn.ping().await, Ok(true)) {
+                    return Ok(conn);
+                }
+                drop(conn);
+                self.create_connection().await
+            }
+            Some(conn) => Ok(conn),
+            None => self.create_connection().await,
         }
+    }
 
+    /// Whether `conn` has been idle long enough to be pinged on checkout.
+    fn needs_health_check(&self, conn: &RedisConnection) -> bool {
+        self.health_check_interval.is_some_and(|interval| conn.last_used.elapsed() > interval)
+    }
+
+    /// Create a new connection using the pool's config.
+    async fn create_connection(&self) -> Result<RedisConnection> {
+        // Fails rather than falling back to plaintext when TLS is requested
+        // but unavailable, so `rediss://` never leaks AUTH passwords (VULN-05).
         let addr = self.config.primary_addr();
-        let timeout = Duration::from_millis(self.config.connect_timeout_ms);
-        let mut conn = RedisConnection::connect_timeout_with_max_buf(
-            &addr,
-            timeout,
-            self.config.max_buffer_size,
-        )
-        .await?;
+        let mut conn = RedisConnection::connect_with_config(&addr, &self.config)
+            .await
+            .inspect_err(|_| self.load.stats().record_error())?;
+        self.load.stats().record_created();
 
         // Apply read timeout (VULN-14: prevents slow-loris attacks)
         conn.set_read_timeout(self.config.read_timeout_ms);
+        conn.attach_load(&self.load);
 
-        conn.init(
-            self.config.username.as_deref(),
-            self.config.password.as_deref(),
-            self.config.db,
-        )
-        .await?;
+        conn.init_from(&self.config, self.config.db).await?;
 
         Ok(conn)
     }
 
-    /// Take a healthy connection from the idle queue (LIFO for cache warmth).
+    /// Take the healthiest connection from the idle queue.
+    ///
+    /// Stale and degraded connections are dropped. Among equally healthy
+    /// connections the most recently returned wins (LIFO for cache warmth).
     fn take_healthy_connection(
         &self,
         idle: &mut VecDeque<RedisConnection>,
     ) -> Option<RedisConnection> {
-        while let Some(conn) = idle.pop_back() {
-            if conn.last_used.elapsed() > self.idle_timeout {
-                continue; // Drop stale connection
-            }
-            return Some(conn);
-        }
-        None
+        let baseline = self.latency_baseline_us();
+        idle.retain(|conn| !self.is_expired(conn) && !conn.health.is_degraded(baseline));
+        let best = idle
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.health.score().total_cmp(&b.health.score()))
+            .map(|(i, _)| i)?;
+        idle.remove(best)
     }
 
     /// Return a connection to the pool (sync — safe for Drop).
-    fn return_connection(&self, conn: RedisConnection) {
-        if conn.last_used.elapsed() > self.idle_timeout {
-            return; // Drop stale connection
+    fn return_connection(&self, conn: RedisConnection, generation: u64) {
+        if generation != self.generation.load(Ordering::Acquire) {
+            return; // Pool was disconnected while this was checked out
+        }
+        if conn.was_interrupted() {
+            return; // A cancelled command may have left a reply unread
+        }
+        if self.is_expired(&conn) {
+            return; // Drop stale or aged connection
+        }
+        if !self.observe_health(&conn.health) {
+            return; // Recycle degraded connection
         }
         let mut idle = self.idle.lock();
         if idle.len() < self.max_size {
@@ -149,13 +486,65 @@ impl ConnectionPool {
         }
         // else: drop it, pool is full
     }
+
+    /// Fold a returning connection into the latency baseline.
+    ///
+    /// Returns `false` if the connection is degraded and should be recycled.
+    fn observe_health(&self, health: &HealthScore) -> bool {
+        let baseline = self.latency_baseline_us();
+        if health.is_degraded(baseline) {
+            return false;
+        }
+        if health.samples() > 0 {
+            let updated = health::update_baseline(baseline, health.latency_us());
+            self.latency_baseline.store(updated.to_bits(), Ordering::Relaxed);
+        }
+        true
+    }
+}
+
+/// The semaphore capping pipeline and transaction checkouts, when
+/// `reserved_connections` keeps some connections for single commands.
+fn bulk_semaphore(config: &ConnectionConfig) -> Option<Arc<Semaphore>> {
+    (config.reserved_connections > 0)
+        .then(|| Arc::new(Semaphore::new(config.pool_size.saturating_sub(config.reserved_connections))))
+}
+
+/// A connection checked out via [`ConnectionPool::lease`].
+///
+/// Holds its pool slot until released or dropped.
+pub struct LeasedConnection {
+    conn: RedisConnection,
+    generation: u64,
+    /// Set while the server may withhold replies (`CLIENT REPLY OFF|SKIP`).
+    replies_suppressed: bool,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl LeasedConnection {
+    /// Access the underlying connection.
+    pub fn conn(&mut self) -> &mut RedisConnection {
+        &mut self.conn
+    }
+
+    /// Mark whether the server may currently withhold replies.
+    pub fn set_replies_suppressed(&mut self, suppressed: bool) {
+        self.replies_suppressed = suppressed;
+    }
+
+    /// Whether the server may currently withhold replies.
+    pub fn replies_suppressed(&self) -> bool {
+        self.replies_suppressed
+    }
 }
 
 /// RAII guard that returns the connection to the pool on drop.
 pub struct PoolGuard<'a> {
     conn: Option<RedisConnection>,
     pool: &'a ConnectionPool,
-    _permit: SemaphorePermit<'a>,
+    generation: u64,
+    _permit: OwnedSemaphorePermit,
+    _bulk_permit: Option<OwnedSemaphorePermit>,
 }
 
 impl<'a> PoolGuard<'a> {
@@ -173,7 +562,7 @@ impl<'a> PoolGuard<'a> {
 impl Drop for PoolGuard<'_> {
     fn drop(&mut self) {
         if let Some(conn) = self.conn.take() {
-            self.pool.return_connection(conn);
+            self.pool.return_connection(conn, self.generation);
         }
     }
 }
@@ -193,26 +582,21 @@ mod tests {
         let addr = listener.local_addr().unwrap().to_string();
 
         tokio::spawn(async move {
-            loop {
-                match listener.accept().await {
-                    Ok((mut socket, _)) => {
-                        tokio::spawn(async move {
-                            let mut buf = vec![0u8; 4096];
-                            loop {
-                                match socket.read(&mut buf).await {
-                                    Ok(0) => break,
-                                    Ok(_) => {
-                                        if socket.write_all(b"+OK\r\n").await.is_err() {
-                                            break;
-                                        }
-                                    }
-                                    Err(_) => break,
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    loop {
+                        match socket.read(&mut buf).await {
+                            Ok(0) => break,
+                            Ok(_) => {
+                                if socket.write_all(b"+OK\r\n").await.is_err() {
+                                    break;
                                 }
                             }
-                        });
+                            Err(_) => break,
+                        }
                     }
-                    Err(_) => break,
-                }
+                });
             }
         });
 
@@ -296,6 +680,109 @@ mod tests {
         drop(g3);
     }
 
+    #[tokio::test]
+    async fn pool_bulk_leaves_reserved_connections() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(ConnectionConfig { reserved_connections: 1, ..test_config(&addr) });
+
+        let b1 = pool.get_bulk().await.unwrap();
+        let _b2 = pool.get_bulk().await.unwrap();
+        assert_eq!(pool.available(), 1);
+
+        // Bulk traffic may not take the reserved connection...
+        let result = tokio::time::timeout(Duration::from_millis(50), pool.get_bulk()).await;
+        assert!(result.is_err());
+
+        // ...but a single command can
+        let mut guard = pool.get().await.unwrap();
+        guard.conn().execute_str(&["PING"]).await.unwrap();
+        drop(guard);
+
+        drop(b1);
+        let _b3 = pool.get_bulk().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn pool_warmup_opens_idle_connections() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        assert_eq!(pool.warmup(2).await.unwrap(), 2);
+        assert_eq!(pool.idle_count(), 2);
+        assert_eq!(pool.available(), 3);
+
+        // Already warm connections count, and the pool size caps the target
+        assert_eq!(pool.warmup(10).await.unwrap(), 1);
+        assert_eq!(pool.idle_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn pool_warmup_stays_within_free_permits() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        let _g1 = pool.get().await.unwrap();
+        let _g2 = pool.get().await.unwrap();
+        // One slot is left, for one idle connection at most
+        assert_eq!(pool.warmup(3).await.unwrap(), 1);
+        assert_eq!(pool.idle_count(), 1);
+
+        let _g3 = pool.get().await.unwrap();
+        assert_eq!(pool.warmup(3).await.unwrap(), 0);
+        assert_eq!(pool.stats().connections_created, 3);
+    }
+
+    #[tokio::test]
+    async fn reaper_drops_idle_connections_without_checkout() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::shared(ConnectionConfig { idle_timeout_ms: 30, ..test_config(&addr) });
+
+        pool.warmup(2).await.unwrap();
+        assert_eq!(pool.idle_count(), 2);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(pool.idle_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn reaper_replaces_connections_past_max_lifetime() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::shared(ConnectionConfig { max_lifetime_ms: 30, ..test_config(&addr) });
+
+        pool.warmup(1).await.unwrap();
+        let opened = pool.idle.lock()[0].created_at;
+        // Retired for its age, and reopened to keep the pool warm
+        let replaced = async {
+            while pool.idle.lock().front().is_none_or(|conn| conn.created_at == opened) {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(2), replaced).await.unwrap();
+        assert_eq!(pool.idle_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn reaper_opens_nothing_while_every_permit_is_out() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::shared(ConnectionConfig { min_idle: 2, idle_timeout_ms: 30, ..test_config(&addr) });
+
+        let guards = [pool.get().await.unwrap(), pool.get().await.unwrap(), pool.get().await.unwrap()];
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(pool.idle_count(), 0);
+        assert_eq!(pool.stats().connections_created, 3);
+        drop(guards);
+    }
+
+    #[tokio::test]
+    async fn pool_drops_aged_connection_on_return() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(ConnectionConfig { max_lifetime_ms: 20, ..test_config(&addr) });
+
+        let guard = pool.get().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        drop(guard);
+        assert_eq!(pool.idle_count(), 0);
+    }
+
     #[tokio::test]
     async fn pool_take_removes_from_pool() {
         let addr = mock_redis_server().await;
@@ -329,6 +816,326 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn pool_disconnect_all_drops_idle() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        {
+            let _g1 = pool.get().await.unwrap();
+            let _g2 = pool.get().await.unwrap();
+        }
+        assert_eq!(pool.idle_count(), 2);
+
+        pool.disconnect_all();
+        assert_eq!(pool.idle_count(), 0);
+        assert_eq!(pool.available(), 3);
+
+        // The pool stays usable afterwards
+        let mut guard = pool.get().await.unwrap();
+        guard.conn().execute_str(&["PING"]).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn pool_disconnect_all_discards_checked_out() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        let guard = pool.get().await.unwrap();
+        pool.disconnect_all();
+        drop(guard);
+
+        assert_eq!(pool.idle_count(), 0);
+        assert_eq!(pool.available(), 3);
+    }
+
+    #[tokio::test]
+    async fn pool_reset_after_fork_restores_permits() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        {
+            let _guard = pool.get().await.unwrap();
+        }
+        // Simulate a guard owned by a thread that did not survive the fork
+        let orphan = pool.get().await.unwrap();
+        std::mem::forget(orphan);
+        let lease = pool.lease().await.unwrap();
+        assert_eq!(pool.available(), 1);
+
+        pool.reset_after_fork();
+        assert_eq!(pool.idle_count(), 0);
+        assert_eq!(pool.available(), 3);
+        // A lease from before the fork does not hand its permit back twice
+        drop(lease);
+        assert_eq!(pool.available(), 3);
+        assert_eq!(pool.stats().in_use, 0);
+    }
+
+    #[tokio::test]
+    async fn pool_tracks_latency_baseline() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+        assert_eq!(pool.latency_baseline_us(), 0.0);
+
+        {
+            let mut guard = pool.get().await.unwrap();
+            guard.conn().execute_str(&["PING"]).await.unwrap();
+            assert_eq!(guard.conn().health.samples(), 1);
+        }
+        assert!(pool.latency_baseline_us() > 0.0);
+    }
+
+    #[tokio::test]
+    async fn blocking_commands_are_not_latency_samples() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        let mut guard = pool.get().await.unwrap();
+        guard.conn().execute_str(&["BLPOP", "q", "0"]).await.unwrap();
+        assert_eq!(guard.conn().health.samples(), 0);
+        guard.conn().execute_str(&["PING"]).await.unwrap();
+        assert_eq!(guard.conn().health.samples(), 1);
+    }
+
+    #[tokio::test]
+    async fn pool_prefers_healthiest_idle() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        {
+            let mut g1 = pool.get().await.unwrap();
+            let mut g2 = pool.get().await.unwrap();
+            g1.conn().execute_str(&["PING"]).await.unwrap();
+            g2.conn().execute_str(&["PING"]).await.unwrap();
+            // g1 saw a slow reply, g2 did not
+            g1.conn().health.record_latency(Duration::from_millis(20));
+            // g1 is returned last, so plain LIFO would hand it out next
+            drop(g2);
+        }
+        assert_eq!(pool.idle_count(), 2);
+
+        let mut guard = pool.get().await.unwrap();
+        assert!(guard.conn().health.latency_us() < 20_000.0);
+    }
+
+    #[tokio::test]
+    async fn pool_recycles_degraded_connection() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        {
+            let mut guard = pool.get().await.unwrap();
+            guard.conn().execute_str(&["PING"]).await.unwrap();
+        }
+        assert!(pool.latency_baseline_us() > 0.0);
+        {
+            let mut guard = pool.get().await.unwrap();
+            for _ in 0..5 {
+                guard.conn().health.record_latency(Duration::from_secs(1));
+            }
+        }
+        assert_eq!(pool.idle_count(), 0);
+        assert_eq!(pool.available(), 3);
+    }
+
+    /// Mock server that answers every command with +RESET\r\n.
+    async fn mock_reset_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    while let Ok(n) = socket.read(&mut buf).await {
+                        if n == 0 || socket.write_all(b"+RESET\r\n").await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        addr
+    }
+
+    /// Accepts connections and reads requests without ever replying.
+    async fn mock_silent_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    while let Ok(1..) = socket.read(&mut buf).await {}
+                });
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn timeouts_describe_pool_load() {
+        let addr = mock_silent_server().await;
+        let config = ConnectionConfig { pool_size: 1, pool_timeout_ms: 30, read_timeout_ms: 200, ..test_config(&addr) };
+        let pool = ConnectionPool::new(config);
+
+        let mut guard = pool.get().await.unwrap();
+        let stalled = guard.conn().execute_str(&["KEYS", "*"]);
+        let waiting = async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            pool.get().await.map(drop)
+        };
+        let (stalled, waiting) = tokio::join!(stalled, waiting);
+
+        let Err(PyrsedisError::PoolTimeout(msg)) = waiting else { panic!("expected a pool timeout") };
+        assert!(msg.contains("1/1 connections in use, longest in-flight: KEYS for"), "{msg}");
+        let Err(PyrsedisError::Timeout(msg)) = stalled else { panic!("expected a read timeout") };
+        assert!(msg.contains("waiting for a reply to KEYS (pool: 1/1 connections in use"), "{msg}");
+    }
+
+    #[tokio::test]
+    async fn checkouts_that_may_not_queue_fail_at_once() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(ConnectionConfig { pool_size: 1, ..test_config(&addr) });
+
+        let guard = pool.get().await.unwrap();
+        let started = Instant::now();
+        let Err(PyrsedisError::WouldBlock(msg)) = without_queueing(Duration::ZERO, pool.get()).await.map(drop) else {
+            panic!("expected the checkout to refuse to wait")
+        };
+        assert!(started.elapsed() < Duration::from_millis(50));
+        assert!(msg.contains("1/1 connections in use"), "{msg}");
+        let waited = without_queueing(Duration::from_millis(20), pool.get()).await.map(drop);
+        assert!(matches!(waited, Err(PyrsedisError::WouldBlock(_))));
+
+        drop(guard);
+        without_queueing(Duration::ZERO, pool.get()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn stats_track_connection_lifecycle() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        pool.get().await.unwrap().conn().execute_str(&["PING"]).await.unwrap();
+        // Reuses the idle connection, which closes when the lease is dropped
+        drop(pool.lease().await.unwrap());
+        drop(pool.get().await.unwrap());
+        pool.disconnect_all();
+        let stats = pool.stats();
+        assert_eq!((stats.connections_created, stats.connections_closed), (2, 2));
+        assert_eq!(stats.checkouts, 3);
+        assert_eq!((stats.idle, stats.in_use, stats.max_size), (0, 0, 3));
+
+        let refused = ConnectionPool::new(test_config("127.0.0.1:1"));
+        assert!(refused.get().await.is_err());
+        assert_eq!(refused.stats().connection_errors, 1);
+    }
+
+    #[tokio::test]
+    async fn pool_lease_holds_slot_until_release() {
+        let addr = mock_reset_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        let lease = pool.lease().await.unwrap();
+        assert_eq!(pool.available(), 2);
+
+        pool.release(lease).await;
+        assert_eq!(pool.available(), 3);
+        assert_eq!(pool.idle_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn pool_release_drops_on_failed_reset() {
+        // Replies +OK to RESET, which is not the expected +RESET
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        let lease = pool.lease().await.unwrap();
+        pool.release(lease).await;
+        assert_eq!(pool.idle_count(), 0);
+        assert_eq!(pool.available(), 3);
+    }
+
+    #[tokio::test]
+    async fn pool_release_refuses_suppressed_replies() {
+        let addr = mock_reset_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        let mut lease = pool.lease().await.unwrap();
+        lease.set_replies_suppressed(true);
+        assert!(lease.replies_suppressed());
+        pool.release(lease).await;
+        assert_eq!(pool.idle_count(), 0);
+        assert_eq!(pool.available(), 3);
+    }
+
+    #[tokio::test]
+    async fn pool_dropped_lease_frees_slot() {
+        let addr = mock_redis_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        let mut lease = pool.lease().await.unwrap();
+        lease.conn().execute_str(&["PING"]).await.unwrap();
+        drop(lease);
+        assert_eq!(pool.idle_count(), 0);
+        assert_eq!(pool.available(), 3);
+    }
+
+    /// Mock server answering +PONG that hangs up on its first client after
+    /// one reply, counting the connections it accepted.
+    async fn mock_flaky_server() -> (String, Arc<AtomicU64>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let accepted = Arc::new(AtomicU64::new(0));
+        let count = Arc::clone(&accepted);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let first = count.fetch_add(1, Ordering::SeqCst) == 0;
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    while let Ok(1..) = socket.read(&mut buf).await {
+                        if socket.write_all(b"+PONG\r\n").await.is_err() || first {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        (addr, accepted)
+    }
+
+    #[tokio::test]
+    async fn health_check_replaces_dead_idle_connection() {
+        let (addr, accepted) = mock_flaky_server().await;
+        let pool = ConnectionPool::new(ConnectionConfig { health_check_interval_ms: 1, ..test_config(&addr) });
+
+        pool.get().await.unwrap().conn().execute_str(&["PING"]).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        // The server hung up meanwhile: the ping fails and a new connection is opened
+        let mut guard = pool.get().await.unwrap();
+        assert_eq!(guard.conn().execute_str(&["PING"]).await.unwrap(), RespValue::SimpleString("PONG".into()));
+        assert_eq!(accepted.load(Ordering::SeqCst), 2);
+        drop(guard);
+
+        // A live connection passes the check and is reused
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        pool.get().await.unwrap().conn().execute_str(&["PING"]).await.unwrap();
+        assert_eq!(accepted.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn no_health_check_hands_out_dead_connection() {
+        let (addr, accepted) = mock_flaky_server().await;
+        let pool = ConnectionPool::new(test_config(&addr));
+
+        pool.get().await.unwrap().conn().execute_str(&["PING"]).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(pool.get().await.unwrap().conn().execute_str(&["PING"]).await.is_err());
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn pool_connect_failure() {
         let config = ConnectionConfig {
@@ -0,0 +1,105 @@
+//! Read buffer sizing behind `expected_response_size=`.
+//!
+//! A reply larger than the read buffer makes the connection double the
+//! buffer, copying what it has read so far, until the reply fits. When the
+//! size is known up front the buffer is grown once instead: either from a
+//! per-call hint ([`expect_reply_size`]) or from [`ReplySizes`], which
+//! learns a moving average of each command's reply size. The hint wins
+//! when both are present.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+/// Commands whose reply sizes are learned; others are not tracked.
+const MAX_COMMANDS: usize = 256;
+
+tokio::task_local! {
+    static EXPECTED_REPLY_SIZE: usize;
+}
+
+/// Run `request` with its replies expected to be about `size` bytes.
+pub async fn expect_reply_size<T>(size: Option<usize>, request: impl Future<Output = T>) -> T {
+    match size {
+        Some(size) => EXPECTED_REPLY_SIZE.scope(size, request).await,
+        None => request.await,
+    }
+}
+
+/// The reply size hinted for the current request, if any.
+pub fn hinted_reply_size() -> Option<usize> {
+    EXPECTED_REPLY_SIZE.try_with(|size| *size).ok()
+}
+
+/// Moving average of one command's reply size, in bytes.
+#[derive(Debug, Default)]
+pub struct ReplySize(AtomicUsize);
+
+impl ReplySize {
+    /// The average so far (0 before the first reply).
+    pub fn expected(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fold a `len`-byte reply into the average, weighted 1/8.
+    pub fn record(&self, len: usize) {
+        let _ = self.0.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |avg| {
+            Some(if avg == 0 { len } else { avg - avg / 8 + len / 8 })
+        });
+    }
+}
+
+/// Learned reply sizes by command name, shared by a client's connections.
+#[derive(Debug, Default)]
+pub struct ReplySizes {
+    commands: Mutex<HashMap<Vec<u8>, Arc<ReplySize>>>,
+}
+
+impl ReplySizes {
+    /// The reply size tracker of the command named `name` (any case), or
+    /// `None` once [`MAX_COMMANDS`] other commands are tracked.
+    pub fn command(&self, name: &[u8]) -> Option<Arc<ReplySize>> {
+        let mut upper = [0u8; 32];
+        let buf = upper.get_mut(..name.len())?;
+        buf.copy_from_slice(name);
+        buf.make_ascii_uppercase();
+        let mut commands = self.commands.lock();
+        if let Some(size) = commands.get(&*buf) {
+            return Some(Arc::clone(size));
+        }
+        if commands.len() >= MAX_COMMANDS {
+            return None;
+        }
+        Some(Arc::clone(commands.entry(buf.to_vec()).or_default()))
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn averages_reply_sizes_per_command() {
+        let sizes = ReplySizes::default();
+        let mget = sizes.command(b"mget").unwrap();
+        assert_eq!(mget.expected(), 0);
+        mget.record(8000);
+        assert_eq!(sizes.command(b"MGET").unwrap().expected(), 8000);
+        mget.record(16_000);
+        assert_eq!(mget.expected(), 9000);
+        assert_eq!(sizes.command(b"GET").unwrap().expected(), 0);
+        assert!(sizes.command(&[b'X'; 40]).is_none());
+    }
+
+    #[tokio::test]
+    async fn hint_is_scoped_to_the_request() {
+        assert_eq!(hinted_reply_size(), None);
+        assert_eq!(expect_reply_size(Some(1 << 20), async { hinted_reply_size() }).await, Some(1 << 20));
+        assert_eq!(expect_reply_size(None, async { hinted_reply_size() }).await, None);
+    }
+}
@@ -0,0 +1,221 @@
+//! Cumulative pool counters behind `pool_stats()`.
+//!
+//! Counters are plain atomics bumped by the pool and its connections.
+//! Checkout waits go into a log-linear histogram (four buckets per power
+//! of two, so percentiles are accurate to within 25%) that snapshots of
+//! several pools can merge, e.g. across the masters of a cluster.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Powers of two covered by the wait histogram (2^40 µs is ~12 days).
+const MAX_EXP: usize = 40;
+
+/// Number of wait histogram buckets.
+pub(crate) const BUCKETS: usize = 4 * MAX_EXP;
+
+/// Counters shared by a pool and the connections it opened.
+pub struct PoolStats {
+    created: AtomicU64,
+    closed: AtomicU64,
+    checkouts: AtomicU64,
+    errors: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    wait_max_us: AtomicU64,
+    wait_buckets: [AtomicU64; BUCKETS],
+}
+
+impl Default for PoolStats {
+    fn default() -> Self {
+        Self {
+            created: AtomicU64::new(0),
+            closed: AtomicU64::new(0),
+            checkouts: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            bytes_sent: AtomicU64::new(0),
+            bytes_received: AtomicU64::new(0),
+            wait_max_us: AtomicU64::new(0),
+            wait_buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+}
+
+impl PoolStats {
+    pub fn record_created(&self) {
+        self.created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_closed(&self) {
+        self.closed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a checkout that waited `wait` for a free connection.
+    pub fn record_checkout(&self, wait: Duration) {
+        let us = u64::try_from(wait.as_micros()).unwrap_or(u64::MAX);
+        self.checkouts.fetch_add(1, Ordering::Relaxed);
+        self.wait_max_us.fetch_max(us, Ordering::Relaxed);
+        self.wait_buckets[bucket(us)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a failed connect or a socket error on an open connection.
+    pub fn record_error(&self) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_sent(&self, bytes: usize) {
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_received(&self, bytes: usize) {
+        self.bytes_received.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Copy the counters; the gauges (`idle`, `in_use`, `max_size`) are
+    /// left for the pool to fill in.
+    pub fn snapshot(&self) -> StatsSnapshot {
+        StatsSnapshot {
+            connections_created: self.created.load(Ordering::Relaxed),
+            connections_closed: self.closed.load(Ordering::Relaxed),
+            checkouts: self.checkouts.load(Ordering::Relaxed),
+            connection_errors: self.errors.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            wait_max_us: self.wait_max_us.load(Ordering::Relaxed),
+            wait_buckets: self.wait_buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect(),
+            ..StatsSnapshot::default()
+        }
+    }
+}
+
+/// Point-in-time copy of one or more pools' statistics.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsSnapshot {
+    pub idle: usize,
+    pub in_use: usize,
+    pub max_size: usize,
+    pub connections_created: u64,
+    pub connections_closed: u64,
+    pub checkouts: u64,
+    pub connection_errors: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub wait_max_us: u64,
+    wait_buckets: Vec<u64>,
+}
+
+impl StatsSnapshot {
+    /// Add another pool's statistics to these.
+    pub fn merge(&mut self, other: &StatsSnapshot) {
+        self.idle += other.idle;
+        self.in_use += other.in_use;
+        self.max_size += other.max_size;
+        self.connections_created += other.connections_created;
+        self.connections_closed += other.connections_closed;
+        self.checkouts += other.checkouts;
+        self.connection_errors += other.connection_errors;
+        self.bytes_sent += other.bytes_sent;
+        self.bytes_received += other.bytes_received;
+        self.wait_max_us = self.wait_max_us.max(other.wait_max_us);
+        if self.wait_buckets.len() < other.wait_buckets.len() {
+            self.wait_buckets.resize(other.wait_buckets.len(), 0);
+        }
+        for (total, count) in self.wait_buckets.iter_mut().zip(&other.wait_buckets) {
+            *total += count;
+        }
+    }
+
+    /// Checkout wait at percentile `p` (0–100), in milliseconds (0 before
+    /// the first checkout).
+    pub fn wait_percentile_ms(&self, p: f64) -> f64 {
+        percentile_ms(&self.wait_buckets, self.wait_max_us, p)
+    }
+}
+
+/// Value at percentile `p` (0–100) of a histogram, in milliseconds,
+/// capped at the largest value recorded (0 when empty).
+pub(crate) fn percentile_ms(buckets: &[u64], max_us: u64, p: f64) -> f64 {
+    let total: u64 = buckets.iter().sum();
+    let rank = ((p / 100.0 * total as f64).ceil() as u64).max(1);
+    let mut seen = 0;
+    for (index, count) in buckets.iter().enumerate() {
+        seen += count;
+        if seen >= rank {
+            return bucket_upper(index).min(max_us) as f64 / 1000.0;
+        }
+    }
+    0.0
+}
+
+/// Histogram bucket for a wait of `us` microseconds.
+pub(crate) fn bucket(us: u64) -> usize {
+    if us < 4 {
+        return us as usize;
+    }
+    let exp = (63 - us.leading_zeros() as usize).min(MAX_EXP);
+    let sub = ((us >> (exp - 2)) & 3) as usize;
+    (4 * (exp - 1) + sub).min(BUCKETS - 1)
+}
+
+/// Largest wait, in microseconds, that falls into bucket `index`.
+fn bucket_upper(index: usize) -> u64 {
+    if index < 4 {
+        return index as u64;
+    }
+    let (exp, sub) = (index / 4 + 1, (index % 4) as u64);
+    ((4 + sub + 1) << (exp - 2)) - 1
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_bound_their_values() {
+        for us in [0, 3, 4, 7, 8, 100, 1_000, 12_345, 1 << 30] {
+            let index = bucket(us);
+            assert!(bucket_upper(index) >= us, "{us}");
+            assert!(index == 0 || bucket_upper(index - 1) < us, "{us}");
+        }
+        assert_eq!(bucket(u64::MAX), BUCKETS - 1);
+    }
+
+    #[test]
+    fn percentiles_within_a_quarter() {
+        let stats = PoolStats::default();
+        for ms in 1..=100 {
+            stats.record_checkout(Duration::from_millis(ms));
+        }
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.checkouts, 100);
+        assert_eq!(snapshot.wait_max_us, 100_000);
+        for (p, exact) in [(50.0, 50.0), (90.0, 90.0), (99.0, 99.0)] {
+            let ms = snapshot.wait_percentile_ms(p);
+            assert!(ms >= exact && ms <= exact * 1.25, "p{p} = {ms}");
+        }
+        assert_eq!(snapshot.wait_percentile_ms(100.0), 100.0);
+        assert_eq!(StatsSnapshot::default().wait_percentile_ms(99.0), 0.0);
+    }
+
+    #[test]
+    fn merge_sums_counters_and_histograms() {
+        let (a, b) = (PoolStats::default(), PoolStats::default());
+        a.record_created();
+        a.record_sent(10);
+        a.record_checkout(Duration::from_millis(1));
+        b.record_created();
+        b.record_received(5);
+        b.record_checkout(Duration::from_millis(8));
+
+        let mut merged = a.snapshot();
+        merged.merge(&b.snapshot());
+        assert_eq!(merged.connections_created, 2);
+        assert_eq!((merged.bytes_sent, merged.bytes_received), (10, 5));
+        assert_eq!(merged.checkouts, 2);
+        assert_eq!(merged.wait_max_us, 8_000);
+        assert!(merged.wait_percentile_ms(50.0) < 2.0);
+        assert_eq!(merged.wait_percentile_ms(100.0), 8.0);
+    }
+}
@@ -1,16 +1,30 @@
 //! Async TCP connection to a Redis server.
 //!
-//! Wraps a `tokio::net::TcpStream` with an integrated read buffer and
-//! RESP parser for efficient, streaming request/response I/O.
+//! Wraps a `tokio::net::TcpStream` (optionally under TLS) or, on Unix, a
+//! `tokio::net::UnixStream` with an integrated read buffer and RESP parser
+//! for efficient, streaming request/response I/O.
 
+use crate::config::ConnectionConfig;
+use crate::connection::capture::{Direction, FrameLog};
+use crate::connection::health::{self, HealthScore};
+use crate::connection::latency::{CommandFamily, CommandStats};
+use crate::connection::load::{frame_command, InFlight, PoolLoad};
+use crate::connection::sizing::{hinted_reply_size, ReplySize, ReplySizes};
+use crate::connection::stats::PoolStats;
 use crate::error::{PyrsedisError, Result};
+use crate::events::EventLog;
+use crate::registry;
 use crate::resp::parser::{parse, resp_frame_len};
 use crate::resp::types::RespValue;
 use crate::resp::writer::{encode_command, encode_command_str};
 
 use bytes::{Bytes, BytesMut};
+use std::pin::Pin;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::Instant;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
 use tokio::net::TcpStream;
 
 /// Default initial read buffer capacity (64 KB).
@@ -23,9 +37,69 @@ const DEFAULT_BUF_CAPACITY: usize = 64 * 1024;
 /// Users can configure a higher limit if needed.
 pub const DEFAULT_MAX_BUF_SIZE: usize = 64 * 1024 * 1024;
 
+/// The transport under a [`RedisConnection`].
+enum Stream {
+    Plain(TcpStream),
+    #[cfg(feature = "tls")]
+    Tls(Box<tokio_rustls::client::TlsStream<TcpStream>>),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixStream),
+}
+
+impl AsyncRead for Stream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(s) => Pin::new(s).poll_read(cx, buf),
+            #[cfg(feature = "tls")]
+            Self::Tls(s) => Pin::new(s).poll_read(cx, buf),
+            #[cfg(unix)]
+            Self::Unix(s) => Pin::new(s).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for Stream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, data: &[u8]) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            Self::Plain(s) => Pin::new(s).poll_write(cx, data),
+            #[cfg(feature = "tls")]
+            Self::Tls(s) => Pin::new(s).poll_write(cx, data),
+            #[cfg(unix)]
+            Self::Unix(s) => Pin::new(s).poll_write(cx, data),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(s) => Pin::new(s).poll_flush(cx),
+            #[cfg(feature = "tls")]
+            Self::Tls(s) => Pin::new(s).poll_flush(cx),
+            #[cfg(unix)]
+            Self::Unix(s) => Pin::new(s).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(s) => Pin::new(s).poll_shutdown(cx),
+            #[cfg(feature = "tls")]
+            Self::Tls(s) => Pin::new(s).poll_shutdown(cx),
+            #[cfg(unix)]
+            Self::Unix(s) => Pin::new(s).poll_shutdown(cx),
+        }
+    }
+}
+
 /// A single async connection to a Redis server.
 pub struct RedisConnection {
-    stream: TcpStream,
+    stream: Stream,
+    /// Server address as given to connect ("host:port").
+    addr: String,
+    /// Lifecycle event sink; taken once the loss of this connection has
+    /// been reported.
+    events: Option<Arc<EventLog>>,
+    /// Where raw frames are copied when frame capture is on.
+    frames: Option<Arc<FrameLog>>,
     /// Read buffer (data read from socket but not yet consumed by parser).
     buf: BytesMut,
     /// Maximum allowed buffer size.
@@ -34,6 +108,49 @@ pub struct RedisConnection {
     read_timeout: Option<std::time::Duration>,
     /// Timestamp of last successful I/O (for idle checks).
     pub last_used: Instant,
+    /// When the connection was opened (for lifetime checks).
+    pub created_at: Instant,
+    /// When the oldest unanswered request was written (for latency tracking).
+    pending_since: Option<Instant>,
+    /// Family of the oldest unanswered request.
+    pending_family: CommandFamily,
+    /// Whether the oldest unanswered request may block on the server.
+    pending_blocks: bool,
+    /// Where round trips are reported by command family.
+    command_stats: Option<Arc<CommandStats>>,
+    /// Where reply sizes are learned by command.
+    reply_sizes: Option<Arc<ReplySizes>>,
+    /// Learned reply size of the oldest unanswered request's command.
+    pending_size: Option<Arc<ReplySize>>,
+    /// Bytes to reserve for the next reply before reading it, 0 once
+    /// reserved.
+    expected_reply: usize,
+    /// The oldest unanswered request, shared with the pool's [`PoolLoad`].
+    in_flight: Arc<InFlight>,
+    /// Load of the pool that opened this connection, for timeout errors.
+    load: Option<Arc<PoolLoad>>,
+    /// Set while a socket read or write is in progress. Still set
+    /// afterwards only if the future doing the I/O was dropped midway.
+    interrupted: bool,
+    /// Moving-window latency / error statistics.
+    pub health: HealthScore,
+}
+
+/// Open a TCP stream, resolving `addr` through the topology registry when
+/// it is enabled. A failed connect evicts the cached addresses so the next
+/// attempt resolves again.
+async fn open_stream(addr: &str) -> Result<TcpStream> {
+    if !registry::is_enabled() {
+        return Ok(TcpStream::connect(addr).await?);
+    }
+    let addrs = registry::resolve(addr).await?;
+    match TcpStream::connect(&addrs[..]).await {
+        Ok(stream) => Ok(stream),
+        Err(e) => {
+            registry::forget_addr(addr);
+            Err(e.into())
+        }
+    }
 }
 
 impl RedisConnection {
@@ -44,15 +161,101 @@ impl RedisConnection {
 
     /// Connect with a configurable max buffer size.
     pub async fn connect_with_max_buf(addr: &str, max_buf_size: usize) -> Result<Self> {
-        let stream = TcpStream::connect(addr).await?;
+        let stream = open_stream(addr).await?;
         stream.set_nodelay(true).ok(); // Disable Nagle for low latency
-        Ok(Self {
+        Ok(Self::from_stream(Stream::Plain(stream), addr, max_buf_size))
+    }
+
+    /// Connect to `addr` as described by `config`: connect timeout, buffer
+    /// limit and, when `config.tls` is set, a TLS handshake. With
+    /// `config.unix_socket_path` set, that socket is used instead of `addr`.
+    ///
+    /// Does not authenticate or select a database (see [`init_from`](Self::init_from)).
+    /// Reports a `connected` event, and later a `disconnected` one if the
+    /// connection is lost, to `config.events`.
+    pub async fn connect_with_config(addr: &str, config: &ConnectionConfig) -> Result<Self> {
+        let mut conn = Self::open_with_config(addr, config).await?;
+        config.events.connected(addr);
+        conn.events = Some(Arc::clone(&config.events));
+        conn.frames = config.frames.clone();
+        conn.command_stats = Some(Arc::clone(&config.command_stats));
+        conn.reply_sizes = Some(Arc::clone(&config.reply_sizes));
+        Ok(conn)
+    }
+
+    async fn open_with_config(addr: &str, config: &ConnectionConfig) -> Result<Self> {
+        let timeout = std::time::Duration::from_millis(config.connect_timeout_ms);
+        if let Some(path) = &config.unix_socket_path {
+            return Self::connect_unix(path, timeout, config.max_buffer_size).await;
+        }
+        if !config.tls {
+            return Self::connect_timeout_with_max_buf(addr, timeout, config.max_buffer_size).await;
+        }
+        #[cfg(feature = "tls")]
+        {
+            let connect = async {
+                let tcp = open_stream(addr).await?;
+                tcp.set_nodelay(true).ok();
+                let host = addr.rsplit_once(':').map_or(addr, |(host, _)| host);
+                let tls = crate::connection::tls::handshake(tcp, host, &config.tls_options).await?;
+                Ok(Self::from_stream(Stream::Tls(Box::new(tls)), addr, config.max_buffer_size))
+            };
+            match tokio::time::timeout(timeout, connect).await {
+                Ok(result) => result,
+                Err(_) => Err(PyrsedisError::Timeout(format!(
+                    "TLS connection to {addr} timed out after {timeout:?}"
+                ))),
+            }
+        }
+        #[cfg(not(feature = "tls"))]
+        {
+            Err(PyrsedisError::Protocol(
+                "TLS requested but pyrsedis was built without the `tls` feature".into(),
+            ))
+        }
+    }
+
+    /// Connect to the Unix domain socket at `path`.
+    #[cfg(unix)]
+    async fn connect_unix(path: &str, timeout: std::time::Duration, max_buf_size: usize) -> Result<Self> {
+        match tokio::time::timeout(timeout, tokio::net::UnixStream::connect(path)).await {
+            Ok(stream) => Ok(Self::from_stream(Stream::Unix(stream?), path, max_buf_size)),
+            Err(_) => Err(PyrsedisError::Timeout(format!(
+                "connection to {path} timed out after {timeout:?}"
+            ))),
+        }
+    }
+
+    #[cfg(not(unix))]
+    async fn connect_unix(_path: &str, _timeout: std::time::Duration, _max_buf_size: usize) -> Result<Self> {
+        Err(PyrsedisError::Protocol(
+            "Unix domain sockets are not supported on this platform".into(),
+        ))
+    }
+
+    fn from_stream(stream: Stream, addr: &str, max_buf_size: usize) -> Self {
+        Self {
             stream,
+            addr: addr.to_string(),
+            events: None,
+            frames: None,
             buf: BytesMut::with_capacity(DEFAULT_BUF_CAPACITY),
             max_buf_size,
             read_timeout: None,
             last_used: Instant::now(),
-        })
+            created_at: Instant::now(),
+            pending_since: None,
+            pending_family: CommandFamily::Other,
+            pending_blocks: false,
+            command_stats: None,
+            reply_sizes: None,
+            pending_size: None,
+            expected_reply: 0,
+            in_flight: Arc::default(),
+            load: None,
+            interrupted: false,
+            health: HealthScore::default(),
+        }
     }
 
     /// Connect with a timeout.
@@ -83,16 +286,58 @@ impl RedisConnection {
         };
     }
 
+    /// The current read timeout in milliseconds (0 = none).
+    pub fn read_timeout_ms(&self) -> u64 {
+        self.read_timeout.map_or(0, |t| t.as_millis() as u64)
+    }
+
+    /// Whether a read or write was cut short by cancellation (e.g. a
+    /// command timeout), leaving half a request or an unread reply on the
+    /// socket. Such a connection must not be reused.
+    pub fn was_interrupted(&self) -> bool {
+        self.interrupted
+    }
+
+    /// The address this connection was opened to.
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    /// Report an unexpected loss of the connection, once.
+    fn report_lost(&mut self, err: &PyrsedisError) {
+        if let PyrsedisError::Connection(io) = err {
+            if let Some(events) = self.events.take() {
+                events.disconnected(&self.addr, &io.to_string());
+            }
+        }
+    }
+
     /// Read from the socket, applying the read timeout if configured.
     async fn read_with_timeout(&mut self) -> Result<usize> {
+        self.interrupted = true;
+        let result = self.read_with_timeout_inner().await;
+        self.interrupted = false;
+        match &result {
+            Ok(n) => self.with_stats(|stats| stats.record_received(*n)),
+            Err(e) => {
+                self.health.record_error();
+                self.with_stats(PoolStats::record_error);
+                self.report_lost(e);
+                if matches!(e, PyrsedisError::Timeout(_)) {
+                    self.record_timeout();
+                }
+            }
+        }
+        result
+    }
+
+    async fn read_with_timeout_inner(&mut self) -> Result<usize> {
         let read_future = self.stream.read_buf(&mut self.buf);
         let n = if let Some(timeout) = self.read_timeout {
             match tokio::time::timeout(timeout, read_future).await {
                 Ok(result) => result?,
                 Err(_) => {
-                    return Err(PyrsedisError::Timeout(format!(
-                        "read timed out after {timeout:?}"
-                    )));
+                    return Err(PyrsedisError::Timeout(self.read_timeout_message(timeout)));
                 }
             }
         } else {
@@ -107,10 +352,125 @@ impl RedisConnection {
         Ok(n)
     }
 
+    /// Number of received bytes not yet consumed as replies.
+    pub fn buffered_len(&self) -> usize {
+        self.buf.len()
+    }
+
     /// Send raw bytes to the server.
     pub async fn send_raw(&mut self, data: &[u8]) -> Result<()> {
-        self.stream.write_all(data).await?;
+        self.interrupted = true;
+        let written = self.stream.write_all(data).await;
+        self.interrupted = false;
+        if let Err(e) = written {
+            self.health.record_error();
+            self.with_stats(PoolStats::record_error);
+            let err = e.into();
+            self.report_lost(&err);
+            return Err(err);
+        }
+        self.with_stats(|stats| stats.record_sent(data.len()));
+        self.capture(Direction::Sent, data);
         self.last_used = Instant::now();
+        if self.pending_since.is_none() {
+            self.pending_since = Some(self.last_used);
+            let command = frame_command(data);
+            self.pending_family = command.map_or(CommandFamily::Other, CommandFamily::of);
+            self.pending_blocks = health::is_blocking(data);
+            self.pending_size = command.zip(self.reply_sizes.as_ref()).and_then(|(name, sizes)| sizes.command(name));
+            self.expected_reply = hinted_reply_size()
+                .or_else(|| self.pending_size.as_ref().map(|size| size.expected()))
+                .unwrap_or(0);
+            self.in_flight.start(data, self.last_used);
+        }
+        Ok(())
+    }
+
+    /// Copy a frame to the capture log, if frame capture is on.
+    ///
+    /// A reply that fails to parse is captured as the unparsed bytes.
+    fn capture(&self, direction: Direction, data: &[u8]) {
+        if let Some(frames) = &self.frames {
+            frames.record(direction, &self.addr, data);
+        }
+    }
+
+    /// Record the round-trip latency and the `len`-byte reply size of the
+    /// oldest pending request.
+    fn record_reply(&mut self, len: usize) {
+        self.last_used = Instant::now();
+        self.expected_reply = 0;
+        if let Some(size) = self.pending_size.take() {
+            size.record(len);
+        }
+        if let Some(sent) = self.pending_since.take() {
+            let latency = self.last_used.duration_since(sent);
+            if !self.pending_blocks {
+                self.health.record_latency(latency);
+            }
+            if let Some(stats) = &self.command_stats {
+                stats.record_reply(self.pending_family, latency);
+            }
+            self.in_flight.finish();
+        }
+    }
+
+    /// Count the pending request as timed out.
+    fn record_timeout(&self) {
+        if let (Some(stats), Some(_)) = (&self.command_stats, self.pending_since) {
+            stats.record_timeout(self.pending_family);
+        }
+    }
+
+    /// Report this connection's pending requests to `load`, so timeouts
+    /// anywhere in the pool can name them.
+    pub fn attach_load(&mut self, load: &Arc<PoolLoad>) {
+        load.register(&self.in_flight);
+        self.load = Some(Arc::clone(load));
+    }
+
+    /// Update the stats of the pool this connection belongs to, if any.
+    fn with_stats(&self, update: impl FnOnce(&PoolStats)) {
+        if let Some(load) = &self.load {
+            update(load.stats());
+        }
+    }
+
+    /// Error message for a read that got no data within `timeout`, naming
+    /// the command waited on and how busy the pool is.
+    fn read_timeout_message(&self, timeout: std::time::Duration) -> String {
+        let mut message = format!("read timed out after {timeout:?}");
+        if let Some((name, _)) = self.in_flight.current() {
+            message.push_str(&format!(" waiting for a reply to {name}"));
+        }
+        if let Some(load) = &self.load {
+            message.push_str(&format!(" ({})", load.describe()));
+        }
+        message
+    }
+
+    /// Make room in the read buffer for the next socket read.
+    ///
+    /// The buffer doubles when nearly full. Before the first read of a
+    /// reply whose size is expected, it grows to hold all of it at once.
+    fn grow_buf(&mut self) -> Result<()> {
+        let expected = std::mem::take(&mut self.expected_reply);
+        let spare = self.buf.capacity() - self.buf.len();
+        if spare >= 4096 && spare >= expected {
+            return Ok(());
+        }
+        let new_cap = (self.buf.capacity() * 2).max(DEFAULT_BUF_CAPACITY).max(self.buf.len() + expected);
+        if new_cap > self.max_buf_size {
+            if self.buf.capacity() >= self.max_buf_size {
+                return Err(PyrsedisError::Protocol(format!(
+                    "RESP message too large: buffer would exceed {} bytes",
+                    self.max_buf_size
+                )));
+            }
+            self.buf.reserve(self.max_buf_size - self.buf.len());
+        } else {
+            self.buf.reserve(new_cap - self.buf.len());
+        }
         Ok(())
     }
 
@@ -133,7 +493,8 @@ impl RedisConnection {
                         if consumed < snapshot.len() {
                             self.buf.extend_from_slice(&snapshot[consumed..]);
                         }
-                        self.last_used = Instant::now();
+                        self.capture(Direction::Received, &snapshot[..consumed]);
+                        self.record_reply(consumed);
                         return Ok(value);
                     }
                     Err(PyrsedisError::Incomplete) => {
@@ -142,27 +503,14 @@ impl RedisConnection {
                     }
                     Err(e) => {
                         self.buf.extend_from_slice(&snapshot);
+                        self.capture(Direction::Received, &snapshot);
                         return Err(e);
                     }
                 }
             }
 
             // Need more data — ensure capacity and read from socket
-            if self.buf.capacity() - self.buf.len() < 4096 {
-                // Need more room — grow
-                let new_cap = (self.buf.capacity() * 2).max(DEFAULT_BUF_CAPACITY);
-                if new_cap > self.max_buf_size {
-                    if self.buf.capacity() >= self.max_buf_size {
-                        return Err(PyrsedisError::Protocol(format!(
-                            "RESP message too large: buffer would exceed {} bytes",
-                            self.max_buf_size
-                        )));
-                    }
-                    self.buf.reserve(self.max_buf_size - self.buf.capacity());
-                } else {
-                    self.buf.reserve(new_cap - self.buf.capacity());
-                }
-            }
+            self.grow_buf()?;
             self.read_with_timeout().await?;
         }
     }
@@ -179,31 +527,22 @@ impl RedisConnection {
                     Ok(len) => {
                         // Split off exactly `len` bytes and freeze them
                         let raw = self.buf.split_to(len).freeze();
-                        self.last_used = Instant::now();
+                        self.capture(Direction::Received, &raw);
+                        self.record_reply(len);
                         return Ok(raw);
                     }
                     Err(PyrsedisError::Incomplete) => {
                         // fall through to read more
                     }
-                    Err(e) => return Err(e),
+                    Err(e) => {
+                        self.capture(Direction::Received, &self.buf);
+                        return Err(e);
+                    }
                 }
             }
 
             // Need more data
-            if self.buf.capacity() - self.buf.len() < 4096 {
-                let new_cap = (self.buf.capacity() * 2).max(DEFAULT_BUF_CAPACITY);
-                if new_cap > self.max_buf_size {
-                    if self.buf.capacity() >= self.max_buf_size {
-                        return Err(PyrsedisError::Protocol(format!(
-                            "RESP message too large: buffer would exceed {} bytes",
-                            self.max_buf_size
-                        )));
-                    }
-                    self.buf.reserve(self.max_buf_size - self.buf.capacity());
-                } else {
-                    self.buf.reserve(new_cap - self.buf.capacity());
-                }
-            }
+            self.grow_buf()?;
             self.read_with_timeout().await?;
         }
     }
@@ -264,6 +603,21 @@ impl RedisConnection {
         }
     }
 
+    /// Send RESET to discard all per-connection state (Redis 6.2+).
+    ///
+    /// Leaves any transaction, unwatches keys, disables tracking and
+    /// monitor mode, unsubscribes, selects db 0 and deauthenticates.
+    pub async fn reset(&mut self) -> Result<()> {
+        match self.execute_str(&["RESET"]).await? {
+            RespValue::SimpleString(ref s) if s == "RESET" => Ok(()),
+            RespValue::Error(msg) => Err(PyrsedisError::redis(msg)),
+            other => Err(PyrsedisError::Protocol(format!(
+                "unexpected RESET response: {}",
+                other.type_name()
+            ))),
+        }
+    }
+
     /// Send HELLO 3 to upgrade to RESP3 protocol.
     pub async fn hello3(
         &mut self,
@@ -290,6 +644,9 @@ impl RedisConnection {
     }
 
     /// Initialize the connection with auth, db select, etc.
+    ///
+    /// A failed step is named in the error (see
+    /// [`PyrsedisError::during_setup`]).
     pub async fn init(
         &mut self,
         username: Option<&str>,
@@ -297,11 +654,127 @@ impl RedisConnection {
         db: u16,
     ) -> Result<()> {
         if let Some(pass) = password {
-            self.auth(username, pass).await?;
+            self.auth(username, pass).await.map_err(|e| e.during_setup("AUTH"))?;
+        }
+        self.select_db(db).await.map_err(|e| e.during_setup("SELECT"))
+    }
+
+    /// Initialize the connection with `config`'s credentials and protocol.
+    ///
+    /// With `protocol=3` this sends `HELLO 3` (authenticating in the same
+    /// round-trip). A server without RESP3 support — Redis before 6.0 has
+    /// no `HELLO` — is either used over RESP2 or rejected with a
+    /// [`PyrsedisError::Protocol`], depending on `protocol_fallback`. The
+    /// outcome is recorded in `config.negotiated_protocol`; once a
+    /// fallback happened, later connections skip the `HELLO` attempt.
+    ///
+    /// While client-side caching is on (`config.tracking_redirect` is
+    /// set), key tracking is enabled with invalidations redirected there.
+    /// With `config.read_only`, `READONLY` is sent so a cluster replica
+    /// serves reads.
+    ///
+    /// Setup is all or nothing: if a step fails, the socket is shut down
+    /// so a half-initialized connection is never used, and the error names
+    /// the step (a refusal by the server is a [`PyrsedisError::Setup`]).
+    pub async fn init_from(&mut self, config: &ConnectionConfig, db: u16) -> Result<()> {
+        let result = self.set_up(config, db).await;
+        if result.is_err() {
+            let _ = self.stream.shutdown().await;
+        }
+        result
+    }
+
+    /// The steps of [`init_from`](Self::init_from).
+    async fn set_up(&mut self, config: &ConnectionConfig, db: u16) -> Result<()> {
+        self.negotiate(config, db).await?;
+        if config.lib_info.load(Ordering::Relaxed) {
+            self.set_lib_info(config).await.map_err(|e| e.during_setup("CLIENT SETINFO"))?;
+        }
+        if config.read_only {
+            let reply = self.execute_str(&["READONLY"]).await.map_err(|e| e.during_setup("READONLY"))?;
+            if let RespValue::Error(msg) = reply {
+                return Err(PyrsedisError::redis(msg).during_setup("READONLY"));
+            }
+        }
+        match config.tracking_redirect.load(Ordering::Acquire) {
+            0 => Ok(()),
+            client_id => self.enable_tracking(client_id).await.map_err(|e| e.during_setup("CLIENT TRACKING")),
         }
-        self.select_db(db).await?;
+    }
+
+    /// Authenticate, pick the protocol and select `db` (see [`init_from`](Self::init_from)).
+    async fn negotiate(&mut self, config: &ConnectionConfig, db: u16) -> Result<()> {
+        let negotiated = &config.negotiated_protocol;
+        let downgraded = negotiated.load(Ordering::Relaxed) == 2;
+        if config.protocol == 3 && !downgraded {
+            match self.hello3(config.username.as_deref(), config.password.as_deref()).await {
+                Ok(_) => {
+                    negotiated.store(3, Ordering::Relaxed);
+                    return self.select_db(db).await.map_err(|e| e.during_setup("SELECT"));
+                }
+                Err(e) if !hello_unsupported(&e) => return Err(e.during_setup("HELLO")),
+                Err(e) if !config.protocol_fallback => {
+                    return Err(PyrsedisError::Protocol(format!(
+                        "server does not support RESP3 (Redis 6.0+ is required for protocol=3): {e}"
+                    )));
+                }
+                Err(_) => {}
+            }
+        }
+        self.init(config.username.as_deref(), config.password.as_deref(), db).await?;
+        negotiated.store(2, Ordering::Relaxed);
         Ok(())
     }
+
+    /// Name the library and its version with `CLIENT SETINFO`, so `CLIENT
+    /// LIST` shows them. Servers before 7.2 reject the command; that is
+    /// not an error, but `config.lib_info` is cleared so it is not sent again.
+    async fn set_lib_info(&mut self, config: &ConnectionConfig) -> Result<()> {
+        let mut frame = encode_command_str(&["CLIENT", "SETINFO", "LIB-NAME", "pyrsedis"]);
+        frame.extend_from_slice(&encode_command_str(&["CLIENT", "SETINFO", "LIB-VER", env!("CARGO_PKG_VERSION")]));
+        self.send_raw(&frame).await?;
+        for _ in 0..2 {
+            if let RespValue::Error(_) = self.read_response().await? {
+                config.lib_info.store(false, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    /// Track the keys this connection reads, sending their invalidations
+    /// to the client with id `redirect`.
+    pub async fn enable_tracking(&mut self, redirect: u64) -> Result<()> {
+        let redirect = redirect.to_string();
+        match self.execute_str(&["CLIENT", "TRACKING", "ON", "REDIRECT", &redirect]).await? {
+            RespValue::SimpleString(_) => Ok(()),
+            RespValue::Error(msg) => Err(PyrsedisError::redis(msg)),
+            other => Err(PyrsedisError::Protocol(format!(
+                "unexpected CLIENT TRACKING response: {}",
+                other.type_name()
+            ))),
+        }
+    }
+}
+
+impl Drop for RedisConnection {
+    fn drop(&mut self) {
+        // A request abandoned mid-I/O ran out of its `timeout_ms`
+        if self.interrupted {
+            self.record_timeout();
+        }
+        self.with_stats(PoolStats::record_closed);
+    }
+}
+
+/// Whether a failed `HELLO` means the server cannot speak RESP3: servers
+/// before 6.0 don't know the command, later ones answer `NOPROTO`.
+fn hello_unsupported(err: &PyrsedisError) -> bool {
+    match err {
+        PyrsedisError::Redis { message, .. } => {
+            message.starts_with("NOPROTO") || message.to_ascii_lowercase().contains("unknown command")
+        }
+        _ => false,
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────────────
@@ -483,6 +956,38 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[cfg(not(feature = "tls"))]
+    #[tokio::test]
+    async fn tls_without_feature_is_refused() {
+        let config = ConnectionConfig { tls: true, ..ConnectionConfig::default() };
+        let result = RedisConnection::connect_with_config("127.0.0.1:6379", &config).await;
+        assert!(matches!(result, Err(PyrsedisError::Protocol(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn connect_over_unix_socket() {
+        let path = std::env::temp_dir().join(format!("pyrsedis-test-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let _ = socket.read(&mut buf).await.unwrap();
+            socket.write_all(b"+PONG\r\n").await.unwrap();
+        });
+
+        let path_str = path.to_str().unwrap().to_string();
+        let config = ConnectionConfig { unix_socket_path: Some(path_str.clone()), ..ConnectionConfig::default() };
+        let mut conn = RedisConnection::connect_with_config(&config.primary_addr(), &config).await.unwrap();
+        assert_eq!(conn.addr(), path_str);
+        assert!(conn.ping().await.unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        // Nothing listens there any more
+        assert!(RedisConnection::connect_with_config(&path_str, &config).await.is_err());
+    }
+
     #[tokio::test]
     async fn connect_with_timeout() {
         // Use a non-routable address to trigger timeout
@@ -513,6 +1018,98 @@ mod tests {
         conn.init(None, None, 0).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn init_from_negotiates_resp3() {
+        let addr = mock_server_multi(vec![b"%1\r\n+proto\r\n:3\r\n".to_vec(), b"+OK\r\n".to_vec()]).await;
+        let config = ConnectionConfig { protocol: 3, password: Some("pw".into()), ..ConnectionConfig::default() };
+        let mut conn = RedisConnection::connect(&addr).await.unwrap();
+        conn.init_from(&config, 1).await.unwrap();
+        assert_eq!(config.negotiated_protocol.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn init_from_falls_back_to_resp2() {
+        let addr = mock_server_multi(vec![
+            b"-ERR unknown command 'HELLO'\r\n".to_vec(),
+            b"+OK\r\n".to_vec(), // AUTH
+        ])
+        .await;
+        let config = ConnectionConfig { protocol: 3, password: Some("pw".into()), ..ConnectionConfig::default() };
+        let mut conn = RedisConnection::connect(&addr).await.unwrap();
+        conn.init_from(&config, 0).await.unwrap();
+        assert_eq!(config.negotiated_protocol.load(Ordering::Relaxed), 2);
+
+        // Once downgraded, new connections go straight to AUTH
+        let addr = mock_server_multi(vec![b"+OK\r\n".to_vec()]).await;
+        let mut conn = RedisConnection::connect(&addr).await.unwrap();
+        conn.init_from(&config, 0).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn init_from_announces_library() {
+        let config = ConnectionConfig { lib_info: Arc::new(true.into()), ..ConnectionConfig::default() };
+        let addr = mock_server_multi(vec![b"+OK\r\n+OK\r\n".to_vec()]).await;
+        let mut conn = RedisConnection::connect(&addr).await.unwrap();
+        conn.init_from(&config, 0).await.unwrap();
+        assert!(config.lib_info.load(Ordering::Relaxed));
+
+        // A server without CLIENT SETINFO is not asked again
+        let unknown = b"-ERR unknown subcommand 'SETINFO'\r\n";
+        let addr = mock_server_multi(vec![[unknown.as_slice(), unknown].concat()]).await;
+        let mut conn = RedisConnection::connect(&addr).await.unwrap();
+        conn.init_from(&config, 0).await.unwrap();
+        assert!(!config.lib_info.load(Ordering::Relaxed));
+        assert!(conn.ping().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn init_from_without_fallback_explains_failure() {
+        let addr = mock_server_multi(vec![b"-ERR unknown command `HELLO`, with args beginning with: `3`, \r\n".to_vec()]).await;
+        let config = ConnectionConfig { protocol: 3, protocol_fallback: false, ..ConnectionConfig::default() };
+        let mut conn = RedisConnection::connect(&addr).await.unwrap();
+        let err = conn.init_from(&config, 0).await.unwrap_err();
+        assert!(matches!(err, PyrsedisError::Protocol(_)));
+        assert!(err.to_string().contains("Redis 6.0+"));
+        assert_eq!(config.negotiated_protocol.load(Ordering::Relaxed), 0);
+
+        // Other HELLO failures are not mistaken for a missing command
+        let addr = mock_server_multi(vec![b"-WRONGPASS invalid username-password pair\r\n".to_vec()]).await;
+        let config = ConnectionConfig { protocol: 3, password: Some("bad".into()), ..ConnectionConfig::default() };
+        let mut conn = RedisConnection::connect(&addr).await.unwrap();
+        assert!(matches!(conn.init_from(&config, 0).await, Err(PyrsedisError::Setup { step: "HELLO", .. })));
+    }
+
+    #[tokio::test]
+    async fn init_from_enables_tracking_when_redirected() {
+        let addr = mock_server_multi(vec![b"+OK\r\n".to_vec()]).await;
+        let config = ConnectionConfig::default();
+        config.tracking_redirect.store(42, Ordering::Release);
+        let mut conn = RedisConnection::connect(&addr).await.unwrap();
+        conn.init_from(&config, 0).await.unwrap();
+
+        // Servers without CLIENT TRACKING fail the connection
+        let addr = mock_server_multi(vec![b"-ERR Unknown subcommand or wrong number of arguments for 'TRACKING'\r\n".to_vec()]).await;
+        let mut conn = RedisConnection::connect(&addr).await.unwrap();
+        assert!(matches!(conn.init_from(&config, 0).await, Err(PyrsedisError::Setup { step: "CLIENT TRACKING", .. })));
+    }
+
+    #[tokio::test]
+    async fn failed_setup_names_the_step_and_closes_the_connection() {
+        let addr = mock_server_multi(vec![b"+OK\r\n".to_vec(), b"-ERR DB index is out of range\r\n".to_vec()]).await;
+        let config = ConnectionConfig { password: Some("pw".into()), ..ConnectionConfig::default() };
+        let mut conn = RedisConnection::connect(&addr).await.unwrap();
+        let err = conn.init_from(&config, 99).await.unwrap_err();
+        assert_eq!(err.to_string(), "connection setup failed at SELECT: ERR DB index is out of range");
+        assert!(conn.ping().await.is_err());
+
+        // A dropped connection keeps its kind, so it is still retried
+        let addr = mock_server_multi(Vec::new()).await;
+        let mut conn = RedisConnection::connect(&addr).await.unwrap();
+        let err = conn.init_from(&config, 0).await.unwrap_err();
+        assert!(matches!(err, PyrsedisError::Connection(_)), "{err:?}");
+        assert!(err.to_string().contains("AUTH failed during connection setup"), "{err}");
+    }
+
     #[tokio::test]
     async fn large_response() {
         // Create a bulk string larger than the default 8KB buffer
@@ -532,6 +1129,32 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn reply_sizes_reserve_the_read_buffer() {
+        use crate::connection::sizing::expect_reply_size;
+
+        let mut big = b"$1000000\r\n".to_vec();
+        big.resize(big.len() + 1_000_000, b'x');
+        big.extend_from_slice(b"\r\n");
+        let addr = mock_server_multi(vec![big.clone(), big, b"+OK\r\n".to_vec()]).await;
+        let config = ConnectionConfig::default();
+        let mut conn = RedisConnection::connect_with_config(&addr, &config).await.unwrap();
+        conn.execute_str(&["GET", "k"]).await.unwrap();
+        assert_eq!(config.reply_sizes.command(b"GET").unwrap().expected(), 1_000_012);
+
+        // The learned size is reserved before the reply is read
+        conn.send_raw(&encode_command_str(&["get", "k"])).await.unwrap();
+        conn.grow_buf().unwrap();
+        assert!(conn.buf.capacity() - conn.buf.len() >= 1_000_012);
+        conn.read_response().await.unwrap();
+
+        // A hint wins over the learned size
+        expect_reply_size(Some(10), conn.send_raw(&encode_command_str(&["GET", "k"]))).await.unwrap();
+        assert_eq!(conn.expected_reply, 10);
+        conn.read_response().await.unwrap();
+        assert_eq!(conn.expected_reply, 0);
+    }
+
     #[tokio::test]
     async fn last_used_updates() {
         let addr = mock_server(b"+PONG\r\n".to_vec()).await;
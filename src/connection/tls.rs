@@ -0,0 +1,262 @@
+//! TLS transport (requires the `tls` feature).
+//!
+//! Builds a rustls client configuration from [`TlsOptions`] and performs
+//! the handshake over an established TCP stream.
+
+use std::io;
+use std::sync::Arc;
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::TlsConnector;
+
+use crate::config::TlsOptions;
+use crate::error::{PyrsedisError, Result};
+
+/// Wrap `stream` in TLS, verifying the server as `host` unless
+/// `opts.server_hostname` overrides it.
+pub(crate) async fn handshake(stream: TcpStream, host: &str, opts: &TlsOptions) -> Result<TlsStream<TcpStream>> {
+    let config = client_config(opts)?;
+    let name = opts.server_hostname.as_deref().unwrap_or(host);
+    let name = name.trim_start_matches('[').trim_end_matches(']');
+    let server_name = ServerName::try_from(name.to_string())
+        .map_err(|e| invalid_input(format!("invalid TLS server name {name:?}: {e}")))?;
+    let tls = TlsConnector::from(Arc::new(config)).connect(server_name, stream).await?;
+    Ok(tls)
+}
+
+/// Build the rustls client configuration for `opts`.
+pub(crate) fn client_config(opts: &TlsOptions) -> Result<ClientConfig> {
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let builder = ClientConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| invalid_input(e.to_string()))?;
+
+    let builder = if opts.insecure_skip_verify {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoVerification(provider)))
+    } else {
+        let mut roots = RootCertStore::empty();
+        match &opts.ca_certs {
+            Some(path) => {
+                for cert in read_certs(path)? {
+                    roots.add(cert).map_err(|e| invalid_input(format!("{path}: {e}")))?;
+                }
+            }
+            None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+        }
+        builder.with_root_certificates(roots)
+    };
+
+    match (&opts.certfile, &opts.keyfile) {
+        (Some(certfile), Some(keyfile)) => {
+            let key = PrivateKeyDer::from_pem_file(keyfile)
+                .map_err(|e| invalid_input(format!("{keyfile}: {e}")))?;
+            builder
+                .with_client_auth_cert(read_certs(certfile)?, key)
+                .map_err(|e| invalid_input(format!("client certificate: {e}")))
+        }
+        (None, None) => Ok(builder.with_no_client_auth()),
+        _ => Err(PyrsedisError::Type(
+            "ssl_certfile and ssl_keyfile must be given together".into(),
+        )),
+    }
+}
+
+/// Read every certificate from a PEM file.
+fn read_certs(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<std::result::Result<Vec<_>, _>>())
+        .map_err(|e| invalid_input(format!("{path}: {e}")))?;
+    if certs.is_empty() {
+        return Err(invalid_input(format!("{path}: no certificates found")));
+    }
+    Ok(certs)
+}
+
+fn invalid_input(msg: String) -> PyrsedisError {
+    PyrsedisError::Connection(io::Error::new(io::ErrorKind::InvalidInput, msg))
+}
+
+/// Verifier for `insecure_skip_verify`: accepts any certificate but still
+/// checks handshake signatures, so the session keys are sound.
+#[derive(Debug)]
+struct NoVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_uses_bundled_roots() {
+        let config = client_config(&TlsOptions::default()).unwrap();
+        assert!(!config.client_auth_cert_resolver.has_certs());
+    }
+
+    #[test]
+    fn insecure_config_builds() {
+        let opts = TlsOptions { insecure_skip_verify: true, ..TlsOptions::default() };
+        assert!(client_config(&opts).is_ok());
+    }
+
+    #[test]
+    fn missing_ca_file_is_reported() {
+        let opts = TlsOptions { ca_certs: Some("/nonexistent/ca.pem".into()), ..TlsOptions::default() };
+        let err = client_config(&opts).unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/ca.pem"), "{err}");
+    }
+
+    #[test]
+    fn certfile_requires_keyfile() {
+        let opts = TlsOptions { certfile: Some("client.pem".into()), ..TlsOptions::default() };
+        assert!(matches!(client_config(&opts), Err(PyrsedisError::Type(_))));
+    }
+
+    #[test]
+    fn ca_file_without_certificates_is_rejected() {
+        let path = std::env::temp_dir().join(format!("pyrsedis-empty-ca-{}.pem", std::process::id()));
+        std::fs::write(&path, "not a certificate\n").unwrap();
+        let opts = TlsOptions { ca_certs: Some(path.to_string_lossy().into_owned()), ..TlsOptions::default() };
+        let err = client_config(&opts).unwrap_err();
+        std::fs::remove_file(&path).ok();
+        assert!(err.to_string().contains("no certificates found"), "{err}");
+    }
+
+    // ── Handshake against a local TLS server ──
+
+    fn fixture(name: &str) -> String {
+        format!("{}/tests/fixtures/tls/{name}", env!("CARGO_MANIFEST_DIR"))
+    }
+
+    /// Start a TLS server for "localhost" that answers every read with `+PONG`.
+    async fn pong_server() -> u16 {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let certs = read_certs(&fixture("server.pem")).unwrap();
+        let key = PrivateKeyDer::from_pem_file(fixture("server.key")).unwrap();
+        let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+        let config = rustls::ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let Ok(mut tls) = acceptor.accept(socket).await else { return };
+                    let mut chunk = [0u8; 1024];
+                    while let Ok(1..) = tls.read(&mut chunk).await {
+                        if tls.write_all(b"+PONG\r\n").await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        port
+    }
+
+    fn tls_config(opts: TlsOptions) -> crate::config::ConnectionConfig {
+        crate::config::ConnectionConfig {
+            tls: true,
+            tls_options: opts,
+            connect_timeout_ms: 2000,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn connects_with_custom_ca() {
+        use crate::connection::tcp::RedisConnection;
+
+        let port = pong_server().await;
+        let opts = TlsOptions { ca_certs: Some(fixture("ca.pem")), ..TlsOptions::default() };
+        let mut conn = RedisConnection::connect_with_config(&format!("localhost:{port}"), &tls_config(opts))
+            .await
+            .unwrap();
+        assert!(conn.ping().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn server_hostname_overrides_verified_name() {
+        use crate::connection::tcp::RedisConnection;
+
+        let port = pong_server().await;
+        let addr = format!("127.0.0.1:{port}");
+        let wrong = TlsOptions {
+            ca_certs: Some(fixture("ca.pem")),
+            server_hostname: Some("redis.example.com".into()),
+            ..TlsOptions::default()
+        };
+        assert!(RedisConnection::connect_with_config(&addr, &tls_config(wrong)).await.is_err());
+
+        let right = TlsOptions {
+            ca_certs: Some(fixture("ca.pem")),
+            server_hostname: Some("localhost".into()),
+            ..TlsOptions::default()
+        };
+        let mut conn = RedisConnection::connect_with_config(&addr, &tls_config(right)).await.unwrap();
+        assert!(conn.ping().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn untrusted_certificate_needs_insecure_flag() {
+        use crate::connection::tcp::RedisConnection;
+
+        let port = pong_server().await;
+        let addr = format!("localhost:{port}");
+        assert!(RedisConnection::connect_with_config(&addr, &tls_config(TlsOptions::default())).await.is_err());
+
+        let insecure = TlsOptions { insecure_skip_verify: true, ..TlsOptions::default() };
+        let mut conn = RedisConnection::connect_with_config(&addr, &tls_config(insecure)).await.unwrap();
+        assert!(conn.ping().await.unwrap());
+    }
+}
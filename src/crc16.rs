@@ -64,6 +64,24 @@ pub fn hash_slot(key: &[u8]) -> u16 {
     crc16(tag) % SLOT_COUNT
 }
 
+/// Split `keys` into batches of at most `chunk_size`, grouped by hash slot.
+///
+/// Returns indices into `keys`. Keys sharing a slot are kept adjacent (in
+/// input order), so each batch touches as few slots — and therefore
+/// cluster nodes — as possible.
+pub fn slot_chunks<K: AsRef<[u8]>>(keys: &[K], chunk_size: usize) -> Vec<Vec<usize>> {
+    let mut order: Vec<(u16, usize)> = keys
+        .iter()
+        .enumerate()
+        .map(|(idx, key)| (hash_slot(key.as_ref()), idx))
+        .collect();
+    order.sort_unstable();
+    order
+        .chunks(chunk_size.max(1))
+        .map(|chunk| chunk.iter().map(|&(_, idx)| idx).collect())
+        .collect()
+}
+
 // ── Tests ──────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -205,4 +223,26 @@ mod tests {
         // Empty key still computes a valid slot
         assert!(hash_slot(b"") < SLOT_COUNT);
     }
+
+    // ── Slot chunking ──
+
+    #[test]
+    fn slot_chunks_groups_and_bounds() {
+        let keys = ["{a}1", "b", "{a}2", "c", "{a}3"];
+        let chunks = slot_chunks(&keys, 2);
+        assert!(chunks.iter().all(|c| c.len() <= 2));
+        let mut all: Vec<usize> = chunks.concat();
+        let tagged: Vec<usize> = all.iter().copied().filter(|&i| keys[i].starts_with("{a}")).collect();
+        // Same-slot keys stay adjacent and in input order
+        let pos = all.iter().position(|&i| i == tagged[0]).unwrap();
+        assert_eq!(&all[pos..pos + 3], &[0, 2, 4]);
+        all.sort_unstable();
+        assert_eq!(all, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn slot_chunks_edge_cases() {
+        assert!(slot_chunks::<&str>(&[], 10).is_empty());
+        assert_eq!(slot_chunks(&["k"], 0), vec![vec![0]]);
+    }
 }
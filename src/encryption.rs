@@ -0,0 +1,198 @@
+//! Value encryption for the SET/GET fast path.
+//!
+//! `Redis(encryption=Encryption(key_id, encrypt, decrypt))` passes every
+//! value written by `Redis.set` through `encrypt` after the serializer,
+//! and every value read by `Redis.get` through `decrypt` before it. The
+//! cryptography itself stays in Python; this module only calls the
+//! callbacks and frames their output.
+//!
+//! Encrypted values are stored as an envelope naming the key they were
+//! encrypted with, so keys can be rotated without rewriting old values:
+//!
+//! ```text
+//! \x00ENC\x01 | key id length (1 byte) | key id (UTF-8) | ciphertext
+//! ```
+//!
+//! Values without the envelope prefix are returned unchanged, so data
+//! written before encryption was turned on stays readable.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes};
+
+use crate::error::{PyrsedisError, Result};
+
+/// Marks an encrypted value.
+pub const ENVELOPE_MAGIC: &[u8] = b"\x00ENC\x01";
+
+// ── Envelope ──────────────────────────────────────────────────────
+
+/// Frame `ciphertext` encrypted with `key_id`.
+pub fn seal_envelope(key_id: &str, ciphertext: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(ENVELOPE_MAGIC.len() + 1 + key_id.len() + ciphertext.len());
+    out.extend_from_slice(ENVELOPE_MAGIC);
+    out.push(key_id.len() as u8);
+    out.extend_from_slice(key_id.as_bytes());
+    out.extend_from_slice(ciphertext);
+    out
+}
+
+/// Split an envelope into its key id and ciphertext. `Ok(None)` when
+/// `data` is not encrypted.
+pub fn open_envelope(data: &[u8]) -> Result<Option<(&str, &[u8])>> {
+    let Some(rest) = data.strip_prefix(ENVELOPE_MAGIC) else {
+        return Ok(None);
+    };
+    let truncated = || PyrsedisError::Type("encrypted value is truncated".into());
+    let (&len, rest) = rest.split_first().ok_or_else(truncated)?;
+    if rest.len() < len as usize {
+        return Err(truncated());
+    }
+    let (key_id, ciphertext) = rest.split_at(len as usize);
+    let key_id = std::str::from_utf8(key_id)
+        .map_err(|_| PyrsedisError::Type("encrypted value has a non UTF-8 key id".into()))?;
+    Ok(Some((key_id, ciphertext)))
+}
+
+/// Reject key ids that cannot be framed in an envelope.
+fn check_key_id(key_id: &str) -> Result<()> {
+    if key_id.is_empty() || key_id.len() > u8::MAX as usize {
+        return Err(PyrsedisError::Type(format!(
+            "key_id must be 1 to 255 bytes long, got {}",
+            key_id.len()
+        )));
+    }
+    Ok(())
+}
+
+// ── Encryption ────────────────────────────────────────────────────
+
+/// Value encryption callbacks for :class:`Redis`.
+///
+/// Args:
+///     key_id: Name of the key new values are encrypted with (1 to 255
+///         bytes of UTF-8). Stored in front of every encrypted value.
+///     encrypt: ``encrypt(key_id, plaintext) -> bytes``.
+///     decrypt: ``decrypt(key_id, ciphertext) -> bytes``, called with the
+///         key id the value was encrypted with, which may be an older one.
+#[pyclass(name = "Encryption", frozen)]
+pub struct Encryption {
+    key_id: String,
+    encrypt: Py<PyAny>,
+    decrypt: Py<PyAny>,
+}
+
+impl Encryption {
+    /// Encrypt `plaintext` with the current key and frame the result.
+    pub fn seal(&self, py: Python<'_>, plaintext: &[u8]) -> PyResult<Vec<u8>> {
+        let ciphertext = self.encrypt.call1(py, (&self.key_id, PyBytes::new(py, plaintext)))?;
+        let ciphertext = callback_bytes(ciphertext.bind(py), "encrypt")?;
+        Ok(seal_envelope(&self.key_id, &ciphertext))
+    }
+
+    /// Decrypt a stored value; values without an envelope are returned as is.
+    pub fn open(&self, py: Python<'_>, data: &[u8]) -> PyResult<Vec<u8>> {
+        let Some((key_id, ciphertext)) = open_envelope(data)? else {
+            return Ok(data.to_vec());
+        };
+        let plaintext = self.decrypt.call1(py, (key_id, PyBytes::new(py, ciphertext)))?;
+        Ok(callback_bytes(plaintext.bind(py), "decrypt")?)
+    }
+}
+
+/// The `bytes` or `bytearray` returned by a callback.
+fn callback_bytes(value: &Bound<'_, PyAny>, name: &str) -> Result<Vec<u8>> {
+    if let Ok(b) = value.cast::<PyBytes>() {
+        return Ok(b.as_bytes().to_vec());
+    }
+    if let Ok(b) = value.cast::<PyByteArray>() {
+        return Ok(b.to_vec());
+    }
+    Err(PyrsedisError::Type(format!(
+        "{name} must return bytes, got {}",
+        value.get_type().name().map(|n| n.to_string()).unwrap_or_default()
+    )))
+}
+
+#[pymethods]
+impl Encryption {
+    #[new]
+    pub(crate) fn new(key_id: String, encrypt: Bound<'_, PyAny>, decrypt: Bound<'_, PyAny>) -> PyResult<Self> {
+        check_key_id(&key_id)?;
+        if !encrypt.is_callable() || !decrypt.is_callable() {
+            return Err(PyrsedisError::Type("encrypt and decrypt must be callable".into()).into());
+        }
+        Ok(Self { key_id, encrypt: encrypt.unbind(), decrypt: decrypt.unbind() })
+    }
+
+    /// The key id new values are encrypted with.
+    #[getter]
+    fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Encryption(key_id={:?})", self.key_id)
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn envelope_roundtrip() {
+        let sealed = seal_envelope("k2", b"\x01\x02");
+        assert_eq!(sealed, b"\x00ENC\x01\x02k2\x01\x02");
+        assert_eq!(open_envelope(&sealed).unwrap(), Some(("k2", &b"\x01\x02"[..])));
+        assert_eq!(open_envelope(&seal_envelope("k", b"")).unwrap(), Some(("k", &b""[..])));
+    }
+
+    #[test]
+    fn plain_values_are_not_envelopes() {
+        assert_eq!(open_envelope(b"hello").unwrap(), None);
+        assert_eq!(open_envelope(b"").unwrap(), None);
+        assert_eq!(open_envelope(b"\x00ENC").unwrap(), None);
+    }
+
+    #[test]
+    fn truncated_envelope_is_an_error() {
+        assert!(open_envelope(b"\x00ENC\x01").is_err());
+        assert!(open_envelope(b"\x00ENC\x01\x05ab").is_err());
+        assert!(open_envelope(b"\x00ENC\x01\x01\xff").is_err());
+    }
+
+    #[test]
+    fn key_id_length_is_checked() {
+        assert!(check_key_id("").is_err());
+        assert!(check_key_id(&"k".repeat(256)).is_err());
+        assert!(check_key_id(&"k".repeat(255)).is_ok());
+    }
+
+    #[test]
+    fn callbacks_see_key_ids() {
+        Python::attach(|py| {
+            // "Encrypts" by reversing and tagging with the key id
+            let encrypt = py.eval(c"lambda k, p: k.encode() + b'|' + p[::-1]", None, None).unwrap();
+            let decrypt = py.eval(c"lambda k, c: c[len(k) + 1:][::-1]", None, None).unwrap();
+            let enc = Encryption::new("v1".into(), encrypt.clone(), decrypt.clone()).unwrap();
+            let sealed = enc.seal(py, b"secret").unwrap();
+            assert_eq!(sealed, seal_envelope("v1", b"v1|terces"));
+            assert_eq!(enc.open(py, &sealed).unwrap(), b"secret");
+
+            // After rotation, old values are decrypted with their own key id
+            let rotated = Encryption::new("v2".into(), encrypt, decrypt).unwrap();
+            assert_eq!(rotated.open(py, &sealed).unwrap(), b"secret");
+            assert_eq!(rotated.open(py, b"plain").unwrap(), b"plain");
+
+            let not_bytes = py.eval(c"lambda k, p: 'text'", None, None).unwrap();
+            let bad = Encryption::new("v1".into(), not_bytes.clone(), not_bytes).unwrap();
+            let err = bad.seal(py, b"x").unwrap_err();
+            assert!(err.to_string().contains("encrypt must return bytes, got str"));
+
+            let not_callable = "nope".into_pyobject(py).unwrap().into_any();
+            assert!(Encryption::new("v1".into(), not_callable.clone(), not_callable).is_err());
+        });
+    }
+}
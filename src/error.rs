@@ -6,7 +6,10 @@ use std::io;
 //
 //  PyrsedisError (Exception)
 //  ├── RedisConnectionError
+//  │   └── ConnectionSetupError   (AUTH/SELECT/... refused on connect)
 //  ├── RedisTimeoutError
+//  │   └── PoolTimeoutError       (no pool connection freed up in time)
+//  │       └── WouldBlockError    (try_execute_command found the pool busy)
 //  ├── ProtocolError
 //  ├── RedisError
 //  │   ├── ResponseError          (generic ERR)
@@ -17,7 +20,11 @@ use std::io;
 //  │   └── ClusterDownError       (CLUSTERDOWN)
 //  ├── GraphError
 //  ├── ClusterError
-//  └── SentinelError
+//  │   └── CrossSlotError         (a command's keys span several slots)
+//  ├── SentinelError
+//  ├── TransactionError
+//  │   └── WatchError             (WATCH conflict)
+//  └── PipelineTooLargeError      (Pipeline max_bytes exceeded)
 
 /// Python exception classes, isolated in a submodule to avoid name
 /// collisions with the Rust `PyrsedisError` enum and its variants.
@@ -34,6 +41,23 @@ pub mod exc {
     pyo3::create_exception!(pyrsedis, GraphError, PyrsedisError, "FalkorDB / graph-specific error.");
     pyo3::create_exception!(pyrsedis, ClusterError, PyrsedisError, "Cluster topology error.");
     pyo3::create_exception!(pyrsedis, SentinelError, PyrsedisError, "Sentinel topology error.");
+    pyo3::create_exception!(pyrsedis, TransactionError, PyrsedisError, "MULTI/EXEC transaction was aborted.");
+    pyo3::create_exception!(pyrsedis, PipelineTooLargeError, PyrsedisError, "A pipeline grew past its max_bytes limit.");
+
+    // Children of RedisConnectionError
+    pyo3::create_exception!(pyrsedis, ConnectionSetupError, RedisConnectionError, "The server refused a step of connection setup (AUTH, SELECT, ...).");
+
+    // Children of RedisTimeoutError
+    pyo3::create_exception!(pyrsedis, PoolTimeoutError, RedisTimeoutError, "No pool connection became free within pool_timeout_ms.");
+
+    // Children of PoolTimeoutError
+    pyo3::create_exception!(pyrsedis, WouldBlockError, PoolTimeoutError, "No pool connection was free for a command that may not queue.");
+
+    // Children of ClusterError
+    pyo3::create_exception!(pyrsedis, CrossSlotError, ClusterError, "A command's keys hash to more than one cluster slot.");
+
+    // Children of TransactionError
+    pyo3::create_exception!(pyrsedis, WatchError, TransactionError, "A watched key changed before EXEC.");
 
     // Children of RedisError
     pyo3::create_exception!(pyrsedis, ResponseError, RedisError, "Generic Redis ERR response.");
@@ -48,12 +72,19 @@ pub mod exc {
 pub fn register_exceptions(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("PyrsedisError", m.py().get_type::<exc::PyrsedisError>())?;
     m.add("RedisConnectionError", m.py().get_type::<exc::RedisConnectionError>())?;
+    m.add("ConnectionSetupError", m.py().get_type::<exc::ConnectionSetupError>())?;
     m.add("RedisTimeoutError", m.py().get_type::<exc::RedisTimeoutError>())?;
+    m.add("PoolTimeoutError", m.py().get_type::<exc::PoolTimeoutError>())?;
+    m.add("WouldBlockError", m.py().get_type::<exc::WouldBlockError>())?;
     m.add("ProtocolError", m.py().get_type::<exc::ProtocolError>())?;
     m.add("RedisError", m.py().get_type::<exc::RedisError>())?;
     m.add("GraphError", m.py().get_type::<exc::GraphError>())?;
     m.add("ClusterError", m.py().get_type::<exc::ClusterError>())?;
+    m.add("CrossSlotError", m.py().get_type::<exc::CrossSlotError>())?;
     m.add("SentinelError", m.py().get_type::<exc::SentinelError>())?;
+    m.add("TransactionError", m.py().get_type::<exc::TransactionError>())?;
+    m.add("WatchError", m.py().get_type::<exc::WatchError>())?;
+    m.add("PipelineTooLargeError", m.py().get_type::<exc::PipelineTooLargeError>())?;
     m.add("ResponseError", m.py().get_type::<exc::ResponseError>())?;
     m.add("WrongTypeError", m.py().get_type::<exc::WrongTypeError>())?;
     m.add("ReadOnlyError", m.py().get_type::<exc::ReadOnlyError>())?;
@@ -168,10 +199,27 @@ pub enum PyrsedisError {
     Type(String),
     /// Operation timed out
     Timeout(String),
+    /// No pool connection became free within `pool_timeout_ms`
+    PoolTimeout(String),
+    /// No pool connection was free for a request that may not queue
+    WouldBlock(String),
     /// Cluster topology errors (no node for slot, etc.)
     Cluster(String),
+    /// A command's keys hash to more than one cluster slot
+    CrossSlot(String),
     /// Sentinel errors (master not found, etc.)
     Sentinel(String),
+    /// A MULTI/EXEC transaction was aborted (EXECABORT)
+    Transaction(String),
+    /// EXEC was refused because a watched key changed
+    Watch(String),
+    /// A buffered pipeline would exceed its `max_bytes` limit
+    PipelineSize(String),
+    /// The server refused a step of connection setup, such as `AUTH`
+    Setup {
+        step: &'static str,
+        message: String,
+    },
 }
 
 impl PyrsedisError {
@@ -225,6 +273,106 @@ impl PyrsedisError {
             _ => None,
         }
     }
+
+    /// Name the connection setup `step` that failed: a server error becomes
+    /// [`Setup`](Self::Setup), and connection errors, timeouts and
+    /// protocol errors keep their kind with the step added to the message.
+    pub fn during_setup(self, step: &'static str) -> Self {
+        match self {
+            Self::Redis { message, .. } => Self::Setup { step, message },
+            Self::Connection(e) => Self::Connection(io::Error::new(e.kind(), format!("{step} failed during connection setup: {e}"))),
+            Self::Timeout(msg) => Self::Timeout(format!("{step} failed during connection setup: {msg}")),
+            Self::Protocol(msg) => Self::Protocol(format!("{step} failed during connection setup: {msg}")),
+            other => other,
+        }
+    }
+
+    /// Append the command that caused a server error to its message, as
+    /// rendered by [`echo_command`]. Other errors are returned unchanged.
+    pub fn for_command<A: AsRef<[u8]>>(self, args: &[A]) -> Self {
+        match self {
+            Self::Redis { kind, message } => Self::Redis {
+                message: format!("{message} (command: {})", echo_command(args)),
+                kind,
+            },
+            other => other,
+        }
+    }
+}
+
+// ── Command echo ───────────────────────────────────────────────────
+
+/// Bytes of one argument shown in an error message.
+const ECHO_ARG_BYTES: usize = 64;
+/// Arguments shown in an error message, command name included.
+const ECHO_MAX_ARGS: usize = 8;
+
+/// Render a command for an error message.
+///
+/// Arguments are decoded lossily and quoted with control characters
+/// escaped; long ones are cut to [`ECHO_ARG_BYTES`] and only the first
+/// [`ECHO_MAX_ARGS`] are shown, so a failing `SET` of a large blob
+/// doesn't flood the message. Passwords given to `AUTH`, `HELLO` and
+/// `MIGRATE` are redacted.
+pub fn echo_command<A: AsRef<[u8]>>(args: &[A]) -> String {
+    let mut out = String::new();
+    for (i, arg) in args.iter().take(ECHO_MAX_ARGS).enumerate() {
+        let arg = arg.as_ref();
+        if i == 0 {
+            out.push_str(&String::from_utf8_lossy(arg).to_ascii_uppercase());
+            continue;
+        }
+        out.push(' ');
+        if is_secret(args, i) {
+            out.push_str("<redacted>");
+            continue;
+        }
+        let shown = &arg[..arg.len().min(ECHO_ARG_BYTES)];
+        out.push('"');
+        for c in String::from_utf8_lossy(shown).chars() {
+            match c {
+                '\'' => out.push(c),
+                c => out.extend(c.escape_debug()),
+            }
+        }
+        out.push('"');
+        if shown.len() < arg.len() {
+            out.push_str(&format!("…({} bytes)", arg.len()));
+        }
+    }
+    if args.len() > ECHO_MAX_ARGS {
+        out.push_str(&format!(" …(+{} args)", args.len() - ECHO_MAX_ARGS));
+    }
+    out
+}
+
+/// Whether argument `i` of `args` is a password.
+fn is_secret<A: AsRef<[u8]>>(args: &[A], i: usize) -> bool {
+    let word_is = |j: Option<usize>, word: &[u8]| {
+        j.and_then(|j| args.get(j)).is_some_and(|a| a.as_ref().eq_ignore_ascii_case(word))
+    };
+    let (back1, back2) = (i.checked_sub(1), i.checked_sub(2));
+    if word_is(Some(0), b"AUTH") {
+        // AUTH [username] password
+        i == args.len() - 1
+    } else if word_is(Some(0), b"HELLO") {
+        // HELLO protover AUTH username password
+        word_is(back2, b"AUTH")
+    } else if word_is(Some(0), b"MIGRATE") {
+        // … AUTH password | AUTH2 username password
+        word_is(back1, b"AUTH") || word_is(back2, b"AUTH2")
+    } else if word_is(Some(0), b"ACL") && word_is(Some(1), b"SETUSER") {
+        // ACL SETUSER username [rule ...]: >password, <password, #hash, !hash
+        i >= 3 && matches!(args[i].as_ref().first(), Some(b'>' | b'<' | b'#' | b'!'))
+    } else if word_is(Some(0), b"CONFIG") && word_is(Some(1), b"SET") {
+        // CONFIG SET parameter value [parameter value ...]
+        i >= 3 && !i.is_multiple_of(2) && (word_is(back1, b"requirepass") || word_is(back1, b"masterauth"))
+    } else if word_is(Some(0), b"SENTINEL") && word_is(Some(1), b"SET") {
+        // SENTINEL SET master option value [option value ...]
+        i >= 4 && i.is_multiple_of(2) && word_is(back1, b"auth-pass")
+    } else {
+        false
+    }
 }
 
 impl fmt::Display for PyrsedisError {
@@ -237,8 +385,14 @@ impl fmt::Display for PyrsedisError {
             Self::Graph(msg) => write!(f, "graph error: {msg}"),
             Self::Type(msg) => write!(f, "type error: {msg}"),
             Self::Timeout(msg) => write!(f, "timeout: {msg}"),
+            Self::PoolTimeout(msg) => write!(f, "pool timeout: {msg}"),
+            Self::WouldBlock(msg) => write!(f, "would block: {msg}"),
             Self::Cluster(msg) => write!(f, "cluster error: {msg}"),
+            Self::CrossSlot(msg) => write!(f, "cross-slot error: {msg}"),
             Self::Sentinel(msg) => write!(f, "sentinel error: {msg}"),
+            Self::Transaction(msg) | Self::Watch(msg) => write!(f, "transaction aborted: {msg}"),
+            Self::PipelineSize(msg) => write!(f, "pipeline too large: {msg}"),
+            Self::Setup { step, message } => write!(f, "connection setup failed at {step}: {message}"),
         }
     }
 }
@@ -268,8 +422,15 @@ impl From<PyrsedisError> for PyErr {
             PyrsedisError::Graph(_) => exc::GraphError::new_err(msg),
             PyrsedisError::Type(_) => pyo3::exceptions::PyTypeError::new_err(msg),
             PyrsedisError::Timeout(_) => exc::RedisTimeoutError::new_err(msg),
+            PyrsedisError::PoolTimeout(_) => exc::PoolTimeoutError::new_err(msg),
+            PyrsedisError::WouldBlock(_) => exc::WouldBlockError::new_err(msg),
             PyrsedisError::Cluster(_) => exc::ClusterError::new_err(msg),
+            PyrsedisError::CrossSlot(_) => exc::CrossSlotError::new_err(msg),
             PyrsedisError::Sentinel(_) => exc::SentinelError::new_err(msg),
+            PyrsedisError::Transaction(_) => exc::TransactionError::new_err(msg),
+            PyrsedisError::Watch(_) => exc::WatchError::new_err(msg),
+            PyrsedisError::PipelineSize(_) => exc::PipelineTooLargeError::new_err(msg),
+            PyrsedisError::Setup { .. } => exc::ConnectionSetupError::new_err(msg),
         }
     }
 }
@@ -282,6 +443,49 @@ pub type Result<T> = std::result::Result<T, PyrsedisError>;
 mod tests {
     use super::*;
 
+    #[test]
+    fn command_echo_is_capped_and_quoted() {
+        assert_eq!(echo_command(&["get", "it's \"k\"\n"]), r#"GET "it's \"k\"\n""#);
+        let blob = [b'x'; 1000];
+        let echo = echo_command(&[b"SET".as_slice(), b"k", &blob]);
+        assert_eq!(echo, format!("SET \"k\" \"{}\"…(1000 bytes)", "x".repeat(64)));
+        assert_eq!(echo_command(&[b"SET".as_slice(), b"\xff\x00"]), "SET \"\u{fffd}\\0\"");
+        let many: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+        assert_eq!(echo_command(&many), r#"0 "1" "2" "3" "4" "5" "6" "7" …(+2 args)"#);
+    }
+
+    #[test]
+    fn command_echo_redacts_passwords() {
+        assert_eq!(echo_command(&["AUTH", "hunter2"]), "AUTH <redacted>");
+        assert_eq!(echo_command(&["auth", "app", "hunter2"]), r#"AUTH "app" <redacted>"#);
+        assert_eq!(
+            echo_command(&["HELLO", "3", "AUTH", "app", "hunter2", "SETNAME", "c"]),
+            r#"HELLO "3" "AUTH" "app" <redacted> "SETNAME" "c""#
+        );
+        assert_eq!(
+            echo_command(&["MIGRATE", "h", "6379", "", "0", "AUTH2", "app", "hunter2"]),
+            r#"MIGRATE "h" "6379" "" "0" "AUTH2" "app" <redacted>"#
+        );
+        assert_eq!(echo_command(&["MIGRATE", "h", "6379", "k", "0", "5000", "AUTH", "pw"]), r#"MIGRATE "h" "6379" "k" "0" "5000" "AUTH" <redacted>"#);
+        assert_eq!(
+            echo_command(&["ACL", "SETUSER", "app", "on", ">hunter2", "~*", "+@all"]),
+            r#"ACL "SETUSER" "app" "on" <redacted> "~*" "+@all""#
+        );
+        assert_eq!(
+            echo_command(&["config", "set", "maxmemory", "1gb", "requirepass", "hunter2"]),
+            r#"CONFIG "set" "maxmemory" "1gb" "requirepass" <redacted>"#
+        );
+        assert_eq!(echo_command(&["CONFIG", "SET", "masterauth", "hunter2"]), r#"CONFIG "SET" "masterauth" <redacted>"#);
+        assert_eq!(echo_command(&["CONFIG", "GET", "requirepass"]), r#"CONFIG "GET" "requirepass""#);
+        assert_eq!(
+            echo_command(&["SENTINEL", "SET", "mymaster", "auth-pass", "hunter2"]),
+            r#"SENTINEL "SET" "mymaster" "auth-pass" <redacted>"#
+        );
+        let err = PyrsedisError::redis("ERR invalid password").for_command(&["AUTH", "hunter2"]);
+        assert_eq!(err.to_string(), "redis error: ERR invalid password (command: AUTH <redacted>)");
+        assert!(matches!(err, PyrsedisError::Redis { kind: RedisErrorKind::Err, .. }));
+    }
+
     #[test]
     fn test_redis_error_kind_err() {
         let (kind, msg) = RedisErrorKind::from_error_msg("ERR unknown command 'FOO'");
@@ -373,7 +577,7 @@ mod tests {
 
     #[test]
     fn test_pyrsedis_error_display() {
-        let err = PyrsedisError::Connection(io::Error::new(io::ErrorKind::Other, "refused"));
+        let err = PyrsedisError::Connection(io::Error::other("refused"));
         assert!(err.to_string().contains("connection error"));
 
         let err = PyrsedisError::Protocol("bad input".into());
@@ -391,11 +595,32 @@ mod tests {
         let err = PyrsedisError::Timeout("3s exceeded".into());
         assert_eq!(err.to_string(), "timeout: 3s exceeded");
 
+        let err = PyrsedisError::PoolTimeout("waited 1s".into());
+        assert_eq!(err.to_string(), "pool timeout: waited 1s");
+
+        let err = PyrsedisError::WouldBlock("pool busy".into());
+        assert_eq!(err.to_string(), "would block: pool busy");
+
         let err = PyrsedisError::Cluster("no node for slot".into());
         assert_eq!(err.to_string(), "cluster error: no node for slot");
 
+        let err = PyrsedisError::CrossSlot("MGET keys must hash to the same slot".into());
+        assert_eq!(err.to_string(), "cross-slot error: MGET keys must hash to the same slot");
+
         let err = PyrsedisError::Sentinel("master not found".into());
         assert_eq!(err.to_string(), "sentinel error: master not found");
+
+        let err = PyrsedisError::Transaction("EXECABORT".into());
+        assert_eq!(err.to_string(), "transaction aborted: EXECABORT");
+
+        let err = PyrsedisError::Watch("watched key changed".into());
+        assert_eq!(err.to_string(), "transaction aborted: watched key changed");
+
+        let err = PyrsedisError::PipelineSize("limit is 10 bytes".into());
+        assert_eq!(err.to_string(), "pipeline too large: limit is 10 bytes");
+
+        let err = PyrsedisError::redis("WRONGPASS invalid username-password pair").during_setup("AUTH");
+        assert_eq!(err.to_string(), "connection setup failed at AUTH: WRONGPASS invalid username-password pair");
     }
 
     #[test]
@@ -418,7 +643,7 @@ mod tests {
 
     #[test]
     fn test_io_error_conversion() {
-        let io_err = io::Error::new(io::ErrorKind::Other, "refused");
+        let io_err = io::Error::other("refused");
         let err: PyrsedisError = io_err.into();
         assert!(matches!(err, PyrsedisError::Connection(_)));
     }
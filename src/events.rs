@@ -0,0 +1,234 @@
+//! Connection lifecycle events.
+//!
+//! Every client owns an [`EventLog`] that records when its connections
+//! are established, lost and re-established, and when Pub/Sub
+//! subscriptions are (re)issued. The most recent events are kept in a ring
+//! buffer for `Redis.recent_events()`; an optional Python callback is
+//! invoked for each one as it happens.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Default number of events kept per client.
+pub const DEFAULT_CAPACITY: usize = 256;
+
+/// What happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    /// A connection to a server was established.
+    Connected,
+    /// A connection was lost unexpectedly (I/O error or server close).
+    Disconnected,
+    /// A connection was established to a server a previous one was lost to.
+    Reconnected,
+    /// Pub/Sub subscriptions were sent.
+    Subscribed,
+}
+
+impl EventKind {
+    /// The event name exposed to Python.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Connected => "connected",
+            Self::Disconnected => "disconnected",
+            Self::Reconnected => "reconnected",
+            Self::Subscribed => "subscribed",
+        }
+    }
+}
+
+/// A single lifecycle event.
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub kind: EventKind,
+    /// Server address ("host:port").
+    pub addr: String,
+    /// Extra context: the error for `Disconnected`, the channels for
+    /// `Subscribed`.
+    pub detail: Option<String>,
+    pub timestamp: SystemTime,
+}
+
+impl Event {
+    /// Convert to a ``{type, address, detail, timestamp}`` dict, with the
+    /// timestamp in seconds since the epoch.
+    pub fn to_python(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let timestamp = self
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or(0.0);
+        let dict = PyDict::new(py);
+        dict.set_item("type", self.kind.as_str())?;
+        dict.set_item("address", &self.addr)?;
+        dict.set_item("detail", self.detail.as_deref())?;
+        dict.set_item("timestamp", timestamp)?;
+        Ok(dict.into_any().unbind())
+    }
+}
+
+#[derive(Debug, Default)]
+struct LogState {
+    events: VecDeque<Event>,
+    /// Addresses with a lost connection not yet replaced.
+    lost: HashSet<String>,
+}
+
+/// Per-client event ring buffer with an optional Python callback.
+#[derive(Debug)]
+pub struct EventLog {
+    state: Mutex<LogState>,
+    callback: Mutex<Option<Arc<Py<PyAny>>>>,
+    capacity: usize,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl EventLog {
+    /// Create a log keeping the last `capacity` events.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(LogState::default()),
+            callback: Mutex::new(None),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Record a new connection to `addr`; reported as `Reconnected` when a
+    /// connection to it was lost before.
+    pub fn connected(&self, addr: &str) {
+        let kind = if self.state.lock().lost.remove(addr) {
+            EventKind::Reconnected
+        } else {
+            EventKind::Connected
+        };
+        self.record(kind, addr, None);
+    }
+
+    /// Record an unexpected connection loss.
+    pub fn disconnected(&self, addr: &str, reason: &str) {
+        self.state.lock().lost.insert(addr.to_string());
+        self.record(EventKind::Disconnected, addr, Some(reason.to_string()));
+    }
+
+    /// Record subscriptions sent on a connection to `addr`.
+    pub fn subscribed(&self, addr: &str, detail: String) {
+        self.record(EventKind::Subscribed, addr, Some(detail));
+    }
+
+    /// Events oldest first, limited to the last `limit` when given.
+    pub fn recent(&self, limit: Option<usize>) -> Vec<Event> {
+        let state = self.state.lock();
+        let skip = limit.map_or(0, |n| state.events.len().saturating_sub(n));
+        state.events.iter().skip(skip).cloned().collect()
+    }
+
+    /// Install or remove the callback invoked with each event dict.
+    pub fn set_callback(&self, callback: Option<Py<PyAny>>) {
+        *self.callback.lock() = callback.map(Arc::new);
+    }
+
+    fn record(&self, kind: EventKind, addr: &str, detail: Option<String>) {
+        let event = Event { kind, addr: addr.to_string(), detail, timestamp: SystemTime::now() };
+        {
+            let mut state = self.state.lock();
+            if state.events.len() >= self.capacity {
+                state.events.pop_front();
+            }
+            state.events.push_back(event.clone());
+        }
+        // Called outside the locks: the callback may well inspect the log
+        let Some(callback) = self.callback.lock().clone() else { return };
+        Python::attach(|py| {
+            let result = event.to_python(py).and_then(|ev| callback.call1(py, (ev,)));
+            if let Err(err) = result {
+                err.write_unraisable(py, Some(callback.bind(py)));
+            }
+        });
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(log: &EventLog) -> Vec<EventKind> {
+        log.recent(None).iter().map(|e| e.kind).collect()
+    }
+
+    #[test]
+    fn reconnect_follows_loss_of_same_address() {
+        let log = EventLog::default();
+        log.connected("a:1");
+        log.disconnected("a:1", "connection reset");
+        log.connected("b:2");
+        log.connected("a:1");
+        log.connected("a:1");
+        assert_eq!(
+            kinds(&log),
+            [
+                EventKind::Connected,
+                EventKind::Disconnected,
+                EventKind::Connected,
+                EventKind::Reconnected,
+                EventKind::Connected,
+            ]
+        );
+        assert_eq!(log.recent(None)[1].detail.as_deref(), Some("connection reset"));
+    }
+
+    #[test]
+    fn ring_buffer_keeps_latest() {
+        let log = EventLog::new(2);
+        log.connected("a:1");
+        log.subscribed("a:1", "channels: x".into());
+        log.disconnected("a:1", "eof");
+        assert_eq!(kinds(&log), [EventKind::Subscribed, EventKind::Disconnected]);
+        assert_eq!(kinds(&EventLog::new(0)), []);
+        let last = log.recent(Some(1));
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].kind, EventKind::Disconnected);
+    }
+
+    #[test]
+    fn callback_receives_event_dicts() {
+        Python::attach(|py| {
+            let seen = pyo3::types::PyList::empty(py);
+            let log = EventLog::default();
+            log.set_callback(Some(seen.getattr("append").unwrap().unbind()));
+            py.detach(|| log.connected("a:1"));
+            assert_eq!(seen.len(), 1);
+            let event = seen.get_item(0).unwrap();
+            assert_eq!(event.get_item("type").unwrap().extract::<String>().unwrap(), "connected");
+            assert_eq!(event.get_item("address").unwrap().extract::<String>().unwrap(), "a:1");
+            assert!(event.get_item("detail").unwrap().is_none());
+            assert!(event.get_item("timestamp").unwrap().extract::<f64>().unwrap() > 0.0);
+
+            log.set_callback(None);
+            log.connected("a:1");
+            assert_eq!(seen.len(), 1);
+        });
+    }
+
+    #[test]
+    fn failing_callback_does_not_propagate() {
+        Python::attach(|py| {
+            let log = EventLog::default();
+            let bad = py.eval(c"lambda event: 1 / 0", None, None).unwrap();
+            log.set_callback(Some(bad.unbind()));
+            log.connected("a:1");
+            assert_eq!(log.recent(None).len(), 1);
+        });
+    }
+}
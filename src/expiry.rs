@@ -0,0 +1,330 @@
+//! Key expiry watcher.
+//!
+//! `Redis.on_expire()` turns on `expired` keyevent notifications and runs
+//! a background subscriber on the shared runtime that calls a Python
+//! function with every expired key matching a glob pattern — the usual
+//! building block for cache invalidation fan-out.
+//!
+//! A dropped subscription is re-established after a short backoff, so a
+//! server restart only loses the expirations that happen while it is down.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+use crate::error::{PyrsedisError, Result};
+use crate::events::EventLog;
+use crate::pubsub::{self, MessageKind};
+use crate::resp::types::RespValue;
+use crate::resp::writer::encode_command_str;
+use crate::router::{ClientRouter, Router};
+use crate::runtime;
+
+/// Wait before re-subscribing after the subscription was lost.
+const RESUBSCRIBE_BACKOFF: Duration = Duration::from_millis(500);
+
+// ── Server configuration ──────────────────────────────────────────
+
+/// Keyevent channel carrying the keys that expired in `db`.
+pub fn expired_channel(db: u16) -> String {
+    format!("__keyevent@{db}__:expired")
+}
+
+/// `notify-keyspace-events` flags extended with keyevent (`E`) and
+/// expired (`x`) notifications, or `None` when `flags` already has both.
+/// `A` is an alias that includes `x`.
+pub fn with_expired_events(flags: &str) -> Option<String> {
+    let keyevent = flags.contains('E');
+    let expired = flags.contains('x') || flags.contains('A');
+    if keyevent && expired {
+        return None;
+    }
+    let mut updated = flags.to_string();
+    if !keyevent {
+        updated.push('E');
+    }
+    if !expired {
+        updated.push('x');
+    }
+    Some(updated)
+}
+
+/// The value from a `CONFIG GET` reply (a RESP2 pair or RESP3 map).
+fn config_value(reply: &RespValue) -> Option<String> {
+    let value = match reply {
+        RespValue::Array(items) => items.get(1)?,
+        RespValue::Map(pairs) => &pairs.first()?.1,
+        _ => return None,
+    };
+    value.as_bytes().map(|b| String::from_utf8_lossy(b).into_owned())
+}
+
+/// Make sure the server publishes `expired` keyevent notifications,
+/// keeping whatever other notification classes are already enabled.
+pub async fn enable_expired_events(router: &ClientRouter) -> Result<()> {
+    let reply = router.execute(&["CONFIG", "GET", "notify-keyspace-events"]).await?;
+    if let Some(msg) = reply.as_error_msg() {
+        return Err(PyrsedisError::redis(msg.to_string()));
+    }
+    let current = config_value(&reply).unwrap_or_default();
+    if let Some(flags) = with_expired_events(&current) {
+        let reply = router.execute(&["CONFIG", "SET", "notify-keyspace-events", &flags]).await?;
+        if let Some(msg) = reply.as_error_msg() {
+            return Err(PyrsedisError::redis(msg.to_string()));
+        }
+    }
+    Ok(())
+}
+
+// ── Background subscriber ─────────────────────────────────────────
+
+/// What the background task needs to deliver expirations.
+struct Subscription {
+    router: Arc<ClientRouter>,
+    events: Arc<EventLog>,
+    channel: String,
+    pattern: Vec<u8>,
+    callback: Py<PyAny>,
+    decode: bool,
+}
+
+impl Subscription {
+    /// Subscribe on a fresh connection and dispatch messages until the
+    /// connection fails.
+    async fn run_once(&self) -> Result<()> {
+        let mut lease = self.router.lease().await?;
+        // Expirations arrive whenever they happen; never time out a read
+        lease.conn().set_read_timeout(0);
+        lease.conn().send_raw(&encode_command_str(&["SUBSCRIBE", &self.channel])).await?;
+        self.events.subscribed(lease.conn().addr(), format!("channels: {}", self.channel));
+        loop {
+            let frame = lease.conn().read_response().await?;
+            if let Ok((MessageKind::Message, items)) = pubsub::split(frame) {
+                if let Some(key) = items.get(1).and_then(RespValue::as_bytes) {
+                    self.dispatch(key);
+                }
+            }
+        }
+    }
+
+    /// Call the callback with `key` if it matches the pattern. Errors are
+    /// reported as unraisable so one bad call does not stop the watcher.
+    fn dispatch(&self, key: &[u8]) {
+        if !pubsub::glob_match(&self.pattern, key) {
+            return;
+        }
+        Python::attach(|py| {
+            let key = if self.decode {
+                PyString::new(py, &String::from_utf8_lossy(key)).into_any()
+            } else {
+                PyBytes::new(py, key).into_any()
+            };
+            if let Err(err) = self.callback.call1(py, (key,)) {
+                err.write_unraisable(py, Some(self.callback.bind(py)));
+            }
+        });
+    }
+
+    /// Keep the subscription alive until `stop` is notified.
+    async fn run(self, stop: Arc<Notify>) {
+        loop {
+            tokio::select! {
+                _ = stop.notified() => return,
+                // Only returns once the connection is gone
+                _ = self.run_once() => {}
+            }
+            tokio::select! {
+                _ = stop.notified() => return,
+                _ = tokio::time::sleep(RESUBSCRIBE_BACKOFF) => {}
+            }
+        }
+    }
+}
+
+// ── ExpiryWatcher ─────────────────────────────────────────────────
+
+/// Handle to a running key expiry watcher.
+///
+/// Created by :meth:`Redis.on_expire`. The watcher runs in the background
+/// until :meth:`stop` is called; dropping the handle does not stop it.
+#[pyclass(name = "ExpiryWatcher")]
+pub struct ExpiryWatcher {
+    stop: Arc<Notify>,
+    task: Option<JoinHandle<()>>,
+    channel: String,
+    pattern: String,
+}
+
+impl ExpiryWatcher {
+    /// Start delivering expirations on `channel` matching `pattern`.
+    pub(crate) fn start(
+        router: Arc<ClientRouter>,
+        events: Arc<EventLog>,
+        channel: String,
+        pattern: String,
+        callback: Py<PyAny>,
+        decode: bool,
+    ) -> Self {
+        let stop = Arc::new(Notify::new());
+        let subscription = Subscription {
+            router,
+            events,
+            channel: channel.clone(),
+            pattern: pattern.clone().into_bytes(),
+            callback,
+            decode,
+        };
+        let task = runtime::spawn(subscription.run(Arc::clone(&stop)));
+        Self { stop, task: Some(task), channel, pattern }
+    }
+}
+
+#[pymethods]
+impl ExpiryWatcher {
+    /// Stop watching and close the subscription. Safe to call more than once.
+    fn stop(&mut self, py: Python<'_>) {
+        if let Some(task) = self.task.take() {
+            self.stop.notify_one();
+            // A callback in flight needs the GIL to finish
+            let _ = py.detach(|| runtime::block_on(task));
+        }
+    }
+
+    /// Whether the watcher is still running.
+    #[getter]
+    fn running(&self) -> bool {
+        self.task.as_ref().is_some_and(|t| !t.is_finished())
+    }
+
+    /// The keyevent channel being watched.
+    #[getter]
+    fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// The glob pattern keys must match.
+    #[getter]
+    fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (_exc_type=None, _exc_value=None, _traceback=None))]
+    fn __exit__(
+        &mut self,
+        py: Python<'_>,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> bool {
+        self.stop(py);
+        false
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ExpiryWatcher(channel='{}', pattern='{}', running={})",
+            self.channel,
+            self.pattern,
+            if self.running() { "True" } else { "False" }
+        )
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expired_flags_are_added_once() {
+        assert_eq!(with_expired_events("").as_deref(), Some("Ex"));
+        assert_eq!(with_expired_events("K$").as_deref(), Some("K$Ex"));
+        assert_eq!(with_expired_events("Ex"), None);
+        assert_eq!(with_expired_events("KEA"), None);
+        assert_eq!(with_expired_events("Kx").as_deref(), Some("KxE"));
+    }
+
+    #[test]
+    fn config_value_from_resp2_and_resp3() {
+        let bulk = |s: &'static str| RespValue::BulkString(bytes::Bytes::from_static(s.as_bytes()));
+        let resp2 = RespValue::Array(vec![bulk("notify-keyspace-events"), bulk("xE")]);
+        assert_eq!(config_value(&resp2).as_deref(), Some("xE"));
+        let resp3 = RespValue::Map(vec![(bulk("notify-keyspace-events"), bulk(""))]);
+        assert_eq!(config_value(&resp3).as_deref(), Some(""));
+        assert_eq!(config_value(&RespValue::Null), None);
+    }
+
+    #[test]
+    fn expired_channel_names_db() {
+        assert_eq!(expired_channel(0), "__keyevent@0__:expired");
+        assert_eq!(expired_channel(3), "__keyevent@3__:expired");
+    }
+
+    #[test]
+    fn on_expire_dispatches_matching_keys() {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (config_tx, config_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for socket in listener.incoming() {
+                let Ok(mut socket) = socket else { return };
+                let config_tx = config_tx.clone();
+                std::thread::spawn(move || {
+                    let mut buf = [0u8; 4096];
+                    while let Ok(n @ 1..) = socket.read(&mut buf) {
+                        let cmd = String::from_utf8_lossy(&buf[..n]).into_owned();
+                        let reply: &[u8] = if cmd.contains("SETINFO") {
+                            b"+OK\r\n+OK\r\n"
+                        } else if cmd.contains("GET") {
+                            b"*2\r\n$22\r\nnotify-keyspace-events\r\n$1\r\nK\r\n"
+                        } else if cmd.contains("SET") {
+                            config_tx.send(cmd).unwrap();
+                            b"+OK\r\n"
+                        } else {
+                            b"*3\r\n$9\r\nsubscribe\r\n$22\r\n__keyevent@0__:expired\r\n:1\r\n\
+                              *3\r\n$7\r\nmessage\r\n$22\r\n__keyevent@0__:expired\r\n$6\r\nuser:1\r\n\
+                              *3\r\n$7\r\nmessage\r\n$22\r\n__keyevent@0__:expired\r\n$5\r\nother\r\n\
+                              *3\r\n$7\r\nmessage\r\n$22\r\n__keyevent@0__:expired\r\n$6\r\nuser:2\r\n"
+                        };
+                        if socket.write_all(reply).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        let r = crate::client::TestClient { port, pool_size: 2, connect_timeout_ms: 1000, read_timeout_ms: 1000, decode_responses: true, ..Default::default() }
+            .build()
+            .unwrap();
+        Python::attach(|py| {
+            let seen = pyo3::types::PyList::empty(py);
+            let not_callable = "nope".into_pyobject(py).unwrap().into_any();
+            assert!(r.on_expire(py, not_callable, "*").is_err());
+            let mut watcher = r.on_expire(py, seen.getattr("append").unwrap(), "user:*").unwrap();
+            assert!(config_rx.recv_timeout(Duration::from_secs(2)).unwrap().contains("KEx"));
+            assert_eq!(watcher.channel(), "__keyevent@0__:expired");
+
+            let deadline = std::time::Instant::now() + Duration::from_secs(5);
+            while seen.len() < 2 && std::time::Instant::now() < deadline {
+                py.detach(|| std::thread::sleep(Duration::from_millis(10)));
+            }
+            let keys: Vec<String> = seen.extract().unwrap();
+            assert_eq!(keys, ["user:1", "user:2"]);
+            assert!(watcher.running());
+            watcher.stop(py);
+            assert!(!watcher.running());
+            assert!(watcher.__repr__().contains("running=False"));
+        });
+    }
+}
@@ -0,0 +1,297 @@
+//! Geospatial commands.
+//!
+//! Builds the arguments of `GEOADD` and `GEOSEARCH`/`GEOSEARCHSTORE`, and
+//! reshapes the geo replies: coordinates and distances arrive as strings
+//! and are returned as floats, and a `GEOSEARCH` match carrying
+//! `WITHDIST`/`WITHHASH`/`WITHCOORD` becomes a dict instead of a nested
+//! list.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyFloat, PyInt, PyList, PyTuple};
+
+use crate::error::{PyrsedisError, Result};
+
+// ── Command arguments ─────────────────────────────────────────────
+
+/// `GEOADD name [NX|XX] [CH] longitude latitude member …`
+pub fn geoadd_args(name: String, values: Vec<(f64, f64, String)>, nx: bool, xx: bool, ch: bool) -> Result<Vec<String>> {
+    if values.is_empty() {
+        return Err(PyrsedisError::Type("geoadd requires at least one (longitude, latitude, member)".into()));
+    }
+    if nx && xx {
+        return Err(PyrsedisError::Type("nx and xx are mutually exclusive".into()));
+    }
+    let mut cmd = vec!["GEOADD".into(), name];
+    if nx {
+        cmd.push("NX".into());
+    }
+    if xx {
+        cmd.push("XX".into());
+    }
+    if ch {
+        cmd.push("CH".into());
+    }
+    for (longitude, latitude, member) in values {
+        cmd.push(longitude.to_string());
+        cmd.push(latitude.to_string());
+        cmd.push(member);
+    }
+    Ok(cmd)
+}
+
+/// A distance unit as the server spells it.
+pub fn unit(unit: &str) -> Result<String> {
+    match unit.to_ascii_lowercase().as_str() {
+        u @ ("m" | "km" | "mi" | "ft") => Ok(u.to_string()),
+        _ => Err(PyrsedisError::Type(format!("unknown unit {unit:?} (expected 'm', 'km', 'mi' or 'ft')"))),
+    }
+}
+
+/// Where a search starts and which area it covers.
+#[derive(Debug, Clone, Default)]
+pub struct Area {
+    pub member: Option<String>,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    pub radius: Option<f64>,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+}
+
+/// Append `FROMMEMBER|FROMLONLAT … BYRADIUS|BYBOX … unit [ASC|DESC]
+/// [COUNT n [ANY]]` to `cmd`.
+pub fn push_search(
+    cmd: &mut Vec<String>,
+    area: Area,
+    unit_name: &str,
+    sort: Option<&str>,
+    count: Option<u64>,
+    any: bool,
+) -> Result<()> {
+    match (area.member, area.longitude, area.latitude) {
+        (Some(member), None, None) => {
+            cmd.push("FROMMEMBER".into());
+            cmd.push(member);
+        }
+        (None, Some(longitude), Some(latitude)) => {
+            cmd.push("FROMLONLAT".into());
+            cmd.push(longitude.to_string());
+            cmd.push(latitude.to_string());
+        }
+        _ => return Err(PyrsedisError::Type("give either member or both longitude and latitude".into())),
+    }
+    match (area.radius, area.width, area.height) {
+        (Some(radius), None, None) => {
+            cmd.push("BYRADIUS".into());
+            cmd.push(radius.to_string());
+        }
+        (None, Some(width), Some(height)) => {
+            cmd.push("BYBOX".into());
+            cmd.push(width.to_string());
+            cmd.push(height.to_string());
+        }
+        _ => return Err(PyrsedisError::Type("give either radius or both width and height".into())),
+    }
+    cmd.push(unit(unit_name)?);
+    if let Some(order) = sort {
+        match order.to_ascii_uppercase().as_str() {
+            o @ ("ASC" | "DESC") => cmd.push(o.into()),
+            _ => return Err(PyrsedisError::Type(format!("sort must be 'ASC' or 'DESC', got {order:?}"))),
+        }
+    }
+    match count {
+        Some(n) => {
+            cmd.push("COUNT".into());
+            cmd.push(n.to_string());
+            if any {
+                cmd.push("ANY".into());
+            }
+        }
+        None if any => return Err(PyrsedisError::Type("any requires count".into())),
+        None => {}
+    }
+    Ok(())
+}
+
+/// `GEOSEARCH name … [WITHCOORD] [WITHDIST] [WITHHASH]`
+#[allow(clippy::too_many_arguments)]
+pub fn geosearch_args(
+    name: String,
+    area: Area,
+    unit_name: &str,
+    sort: Option<&str>,
+    count: Option<u64>,
+    any: bool,
+    withcoord: bool,
+    withdist: bool,
+    withhash: bool,
+) -> Result<Vec<String>> {
+    let mut cmd = vec!["GEOSEARCH".into(), name];
+    push_search(&mut cmd, area, unit_name, sort, count, any)?;
+    for (flag, set) in [("WITHCOORD", withcoord), ("WITHDIST", withdist), ("WITHHASH", withhash)] {
+        if set {
+            cmd.push(flag.into());
+        }
+    }
+    Ok(cmd)
+}
+
+/// `GEOSEARCHSTORE dest source … [STOREDIST]`
+#[allow(clippy::too_many_arguments)]
+pub fn geosearchstore_args(
+    dest: String,
+    source: String,
+    area: Area,
+    unit_name: &str,
+    sort: Option<&str>,
+    count: Option<u64>,
+    any: bool,
+    storedist: bool,
+) -> Result<Vec<String>> {
+    let mut cmd = vec!["GEOSEARCHSTORE".into(), dest, source];
+    push_search(&mut cmd, area, unit_name, sort, count, any)?;
+    if storedist {
+        cmd.push("STOREDIST".into());
+    }
+    Ok(cmd)
+}
+
+// ── Reply shapes ──────────────────────────────────────────────────
+
+/// `value` as a float; `None` stays `None`.
+fn float<'py>(value: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    if value.is_none() {
+        return Ok(value);
+    }
+    value.py().get_type::<PyFloat>().call1((value,))
+}
+
+/// `[longitude, latitude]` as a `(longitude, latitude)` float tuple.
+fn coordinates<'py>(pair: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(list) = pair.cast::<PyList>() else {
+        return Ok(pair);
+    };
+    if list.len() != 2 {
+        return Ok(pair);
+    }
+    let (longitude, latitude) = (float(list.get_item(0)?)?, float(list.get_item(1)?)?);
+    Ok(PyTuple::new(pair.py(), [longitude, latitude])?.into_any())
+}
+
+/// A `GEODIST` reply as a float, or `None` when a member is missing.
+pub fn distance<'py>(reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    Ok(float(reply.clone()).unwrap_or(reply))
+}
+
+/// A `GEOPOS` reply as `[(longitude, latitude) | None, …]`.
+pub fn positions<'py>(reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(list) = reply.cast::<PyList>() else {
+        return Ok(reply);
+    };
+    let items = list.iter().map(coordinates).collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(reply.py(), items)?.into_any())
+}
+
+/// `GEOSEARCH` matches as `{"member", "distance", "hash", "coordinates"}`
+/// dicts holding the fields that were asked for. The server sends them
+/// as `[member, distance?, hash?, [longitude, latitude]?]`.
+pub fn matches<'py>(reply: Bound<'py, PyAny>, withcoord: bool, withdist: bool, withhash: bool) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(list) = reply.cast::<PyList>() else {
+        return Ok(reply);
+    };
+    let py = reply.py();
+    let mut out = Vec::with_capacity(list.len());
+    for item in list.iter() {
+        let Ok(fields) = item.cast::<PyList>() else {
+            return Ok(reply);
+        };
+        let mut fields = fields.iter();
+        let dict = PyDict::new(py);
+        dict.set_item("member", fields.next())?;
+        if withdist {
+            dict.set_item("distance", fields.next().map(float).transpose()?)?;
+        }
+        if withhash {
+            let hash = fields.next().map(|h| py.get_type::<PyInt>().call1((h,))).transpose()?;
+            dict.set_item("hash", hash)?;
+        }
+        if withcoord {
+            dict.set_item("coordinates", fields.next().map(coordinates).transpose()?)?;
+        }
+        out.push(dict);
+    }
+    Ok(PyList::new(py, out)?.into_any())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shaped<'py>(py: Python<'py>, expr: &std::ffi::CStr, shape: fn(Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>>) -> String {
+        let reply = py.eval(expr, None, None).unwrap();
+        shape(reply).unwrap().repr().unwrap().to_string()
+    }
+
+    #[test]
+    fn geoadd_arguments() {
+        let cmd = geoadd_args("g".into(), vec![(13.5, 38.25, "a".into())], false, true, true).unwrap();
+        assert_eq!(cmd, ["GEOADD", "g", "XX", "CH", "13.5", "38.25", "a"]);
+        assert!(geoadd_args("g".into(), vec![], false, false, false).is_err());
+        assert!(geoadd_args("g".into(), vec![(0.0, 0.0, "a".into())], true, true, false).is_err());
+    }
+
+    #[test]
+    fn search_arguments() {
+        let area = Area { member: Some("a".into()), radius: Some(100.0), ..Area::default() };
+        let cmd = geosearch_args("g".into(), area, "KM", Some("asc"), Some(5), true, true, true, false).unwrap();
+        assert_eq!(
+            cmd,
+            ["GEOSEARCH", "g", "FROMMEMBER", "a", "BYRADIUS", "100", "km", "ASC", "COUNT", "5", "ANY", "WITHCOORD", "WITHDIST"]
+        );
+
+        let area = Area { longitude: Some(15.0), latitude: Some(37.0), width: Some(4.0), height: Some(2.5), ..Area::default() };
+        let cmd = geosearchstore_args("d".into(), "g".into(), area, "m", None, None, false, true).unwrap();
+        assert_eq!(cmd, ["GEOSEARCHSTORE", "d", "g", "FROMLONLAT", "15", "37", "BYBOX", "4", "2.5", "m", "STOREDIST"]);
+
+        let both = Area { member: Some("a".into()), longitude: Some(1.0), latitude: Some(1.0), radius: Some(1.0), ..Area::default() };
+        assert!(geosearch_args("g".into(), both, "m", None, None, false, false, false, false).is_err());
+        let no_shape = Area { member: Some("a".into()), ..Area::default() };
+        assert!(geosearch_args("g".into(), no_shape, "m", None, None, false, false, false, false).is_err());
+        let area = Area { member: Some("a".into()), radius: Some(1.0), ..Area::default() };
+        assert!(geosearch_args("g".into(), area.clone(), "yd", None, None, false, false, false, false).is_err());
+        assert!(geosearch_args("g".into(), area.clone(), "m", Some("up"), None, false, false, false, false).is_err());
+        assert!(geosearch_args("g".into(), area, "m", None, None, true, false, false, false).is_err());
+    }
+
+    #[test]
+    fn positions_and_distances_become_floats() {
+        Python::attach(|py| {
+            assert_eq!(shaped(py, c"ValueError('x')", distance), "ValueError('x')");
+            assert_eq!(shaped(py, c"'166274.1516'", distance), "166274.1516");
+            assert_eq!(shaped(py, c"None", distance), "None");
+            assert_eq!(shaped(py, c"[['13.5', b'38.25'], None]", positions), "[(13.5, 38.25), None]");
+        });
+    }
+
+    #[test]
+    fn matches_become_dicts() {
+        Python::attach(|py| {
+            let reply = py.eval(c"[['a', '0.0000', 3479099956230698, ['13.5', '38.25']], ['b', '1.5', 1, [1.0, 2.0]]]", None, None).unwrap();
+            assert_eq!(
+                matches(reply, true, true, true).unwrap().repr().unwrap().to_string(),
+                "[{'member': 'a', 'distance': 0.0, 'hash': 3479099956230698, 'coordinates': (13.5, 38.25)}, \
+                 {'member': 'b', 'distance': 1.5, 'hash': 1, 'coordinates': (1.0, 2.0)}]"
+            );
+            let reply = py.eval(c"[['a', ['1', '2']]]", None, None).unwrap();
+            assert_eq!(
+                matches(reply, true, false, false).unwrap().repr().unwrap().to_string(),
+                "[{'member': 'a', 'coordinates': (1.0, 2.0)}]"
+            );
+            // Plain members pass through
+            let reply = py.eval(c"['a', 'b']", None, None).unwrap();
+            assert_eq!(matches(reply, false, true, false).unwrap().repr().unwrap().to_string(), "['a', 'b']");
+        });
+    }
+}
@@ -24,6 +24,7 @@ use crate::error::{PyrsedisError, Result};
 use crate::resp::types::RespValue;
 
 use std::collections::HashMap;
+use std::fmt;
 
 // ── Column types ──────────────────────────────────────────────────
 
@@ -154,6 +155,56 @@ pub struct GraphResult {
     pub stats: GraphStats,
 }
 
+// ── Display ───────────────────────────────────────────────────────
+
+/// Cypher-like text form, used where a value has no native column type
+/// (e.g. arrays and maps in Arrow export). Nested strings are quoted.
+impl fmt::Display for GraphValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("null"),
+            Self::String(s) => f.write_str(s),
+            Self::Integer(i) => write!(f, "{i}"),
+            Self::Boolean(b) => write!(f, "{b}"),
+            Self::Double(d) => write!(f, "{d}"),
+            Self::Array(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write_nested(f, item)?;
+                }
+                f.write_str("]")
+            }
+            Self::Node(node) => write!(f, "Node({})", node.id),
+            Self::Edge(edge) => write!(f, "Edge({})", edge.id),
+            Self::Path { nodes, edges } => {
+                write!(f, "Path(nodes={}, edges={})", nodes.len(), edges.len())
+            }
+            Self::Map(pairs) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in pairs.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{key}: ")?;
+                    write_nested(f, value)?;
+                }
+                f.write_str("}")
+            }
+            Self::Point(p) => write!(f, "point({{latitude: {}, longitude: {}}})", p.latitude, p.longitude),
+        }
+    }
+}
+
+fn write_nested(f: &mut fmt::Formatter<'_>, value: &GraphValue) -> fmt::Result {
+    match value {
+        GraphValue::String(s) => write!(f, "{s:?}"),
+        other => write!(f, "{other}"),
+    }
+}
+
 // ── Parser ────────────────────────────────────────────────────────
 
 /// Parse a GRAPH.QUERY compact result.
@@ -709,13 +760,13 @@ mod tests {
             ])]),
             RespValue::Array(vec![RespValue::Array(vec![RespValue::Array(vec![
                 RespValue::Integer(5), // Double type
-                RespValue::BulkString(Bytes::from_static(b"3.14")),
+                RespValue::BulkString(Bytes::from_static(b"2.75")),
             ])])]),
             RespValue::Array(vec![]),
         ]);
 
         let result = parse_graph_result(&resp).unwrap();
-        assert_eq!(result.rows[0][0], GraphValue::Double(3.14));
+        assert_eq!(result.rows[0][0], GraphValue::Double(2.75));
     }
 
     #[test]
@@ -746,4 +797,16 @@ mod tests {
             Some(&"10".to_string())
         );
     }
+
+    #[test]
+    fn display_nested_values() {
+        let value = GraphValue::Array(vec![
+            GraphValue::Integer(1),
+            GraphValue::String("a".into()),
+            GraphValue::Map(vec![("k".into(), GraphValue::Boolean(true))]),
+            GraphValue::Null,
+        ]);
+        assert_eq!(value.to_string(), "[1, \"a\", {k: true}, null]");
+        assert_eq!(GraphValue::String("plain".into()).to_string(), "plain");
+    }
 }
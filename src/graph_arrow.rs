@@ -0,0 +1,256 @@
+//! Arrow export for graph query results.
+//!
+//! Builds an Arrow `RecordBatch` straight from a parsed [`GraphResult`], so
+//! tabular consumers (pyarrow, pandas, polars) never see per-cell Python
+//! objects. Batches are handed to Python through the Arrow C Data
+//! Interface (`__arrow_c_array__` capsules).
+//!
+//! Column types:
+//! - Node / relation columns → `Int64` entity ids
+//! - Scalar columns → `Int64`, `Float64`, `Boolean` or `Utf8`, inferred
+//!   from the cells (integers mixed with doubles widen to `Float64`)
+//! - Arrays, maps, paths, points and mixed columns → `Utf8` text
+//! - All-null columns → `Null`
+
+use std::sync::Arc;
+
+use arrow_array::builder::{BooleanBuilder, Float64Builder, Int64Builder, StringBuilder};
+use arrow_array::{Array, ArrayRef, NullArray, RecordBatch, RecordBatchOptions, StructArray};
+use arrow_data::ffi::FFI_ArrowArray;
+use arrow_schema::ffi::FFI_ArrowSchema;
+use arrow_schema::{DataType, Field, Schema};
+
+use crate::error::{PyrsedisError, Result};
+use crate::graph::{ColumnType, GraphResult, GraphValue};
+
+// ── Column typing ─────────────────────────────────────────────────
+
+/// Arrow type chosen for one result column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Null,
+    Int64,
+    Float64,
+    Boolean,
+    Utf8,
+}
+
+impl ColumnKind {
+    fn data_type(self) -> DataType {
+        match self {
+            Self::Null => DataType::Null,
+            Self::Int64 => DataType::Int64,
+            Self::Float64 => DataType::Float64,
+            Self::Boolean => DataType::Boolean,
+            Self::Utf8 => DataType::Utf8,
+        }
+    }
+
+    /// Native kind of a single cell (`Null` for null cells).
+    fn of(value: &GraphValue) -> Self {
+        match value {
+            GraphValue::Null => Self::Null,
+            GraphValue::Integer(_) | GraphValue::Node(_) | GraphValue::Edge(_) => Self::Int64,
+            GraphValue::Double(_) => Self::Float64,
+            GraphValue::Boolean(_) => Self::Boolean,
+            _ => Self::Utf8,
+        }
+    }
+
+    /// Combine two cell kinds into a column kind that can hold both.
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Null, k) | (k, Self::Null) => k,
+            (Self::Int64, Self::Float64) | (Self::Float64, Self::Int64) => Self::Float64,
+            _ => Self::Utf8,
+        }
+    }
+}
+
+fn column_kind(result: &GraphResult, idx: usize) -> ColumnKind {
+    match result.columns[idx].column_type {
+        ColumnType::Node | ColumnType::Relation => ColumnKind::Int64,
+        ColumnType::Scalar | ColumnType::Unknown => result
+            .rows
+            .iter()
+            .filter_map(|row| row.get(idx))
+            .fold(ColumnKind::Null, |kind, cell| kind.merge(ColumnKind::of(cell))),
+    }
+}
+
+// ── Column builders ───────────────────────────────────────────────
+
+fn build_column(result: &GraphResult, idx: usize, kind: ColumnKind) -> ArrayRef {
+    let cells = result.rows.iter().map(|row| row.get(idx).unwrap_or(&GraphValue::Null));
+    let len = result.rows.len();
+    match kind {
+        ColumnKind::Null => Arc::new(NullArray::new(len)),
+        ColumnKind::Int64 => {
+            let mut b = Int64Builder::with_capacity(len);
+            for cell in cells {
+                b.append_option(match cell {
+                    GraphValue::Integer(i) => Some(*i),
+                    GraphValue::Node(n) => Some(n.id),
+                    GraphValue::Edge(e) => Some(e.id),
+                    _ => None,
+                });
+            }
+            Arc::new(b.finish())
+        }
+        ColumnKind::Float64 => {
+            let mut b = Float64Builder::with_capacity(len);
+            for cell in cells {
+                b.append_option(match cell {
+                    GraphValue::Double(d) => Some(*d),
+                    GraphValue::Integer(i) => Some(*i as f64),
+                    _ => None,
+                });
+            }
+            Arc::new(b.finish())
+        }
+        ColumnKind::Boolean => {
+            let mut b = BooleanBuilder::with_capacity(len);
+            for cell in cells {
+                b.append_option(match cell {
+                    GraphValue::Boolean(v) => Some(*v),
+                    _ => None,
+                });
+            }
+            Arc::new(b.finish())
+        }
+        ColumnKind::Utf8 => {
+            let mut b = StringBuilder::with_capacity(len, len * 8);
+            for cell in cells {
+                match cell {
+                    GraphValue::Null => b.append_null(),
+                    other => b.append_value(other.to_string()),
+                }
+            }
+            Arc::new(b.finish())
+        }
+    }
+}
+
+// ── Public API ────────────────────────────────────────────────────
+
+/// Convert a parsed graph result into an Arrow record batch.
+pub fn to_record_batch(result: &GraphResult) -> Result<RecordBatch> {
+    let mut fields = Vec::with_capacity(result.columns.len());
+    let mut columns = Vec::with_capacity(result.columns.len());
+    for (idx, col) in result.columns.iter().enumerate() {
+        let kind = column_kind(result, idx);
+        fields.push(Field::new(&col.name, kind.data_type(), true));
+        columns.push(build_column(result, idx, kind));
+    }
+    let options = RecordBatchOptions::new().with_row_count(Some(result.rows.len()));
+    RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), columns, &options)
+        .map_err(|e| PyrsedisError::Graph(format!("arrow export failed: {e}")))
+}
+
+/// Export a record batch through the Arrow C Data Interface.
+///
+/// The batch is exported as a struct array whose children are the
+/// columns, as the Arrow PyCapsule protocol requires.
+pub fn to_ffi(batch: RecordBatch) -> Result<(FFI_ArrowSchema, FFI_ArrowArray)> {
+    let schema = FFI_ArrowSchema::try_from(batch.schema().as_ref())
+        .map_err(|e| PyrsedisError::Graph(format!("arrow schema export failed: {e}")))?;
+    let data = StructArray::from(batch).into_data();
+    Ok((schema, FFI_ArrowArray::new(&data)))
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{GraphColumn, GraphNode, GraphStats};
+    use arrow_array::{BooleanArray, Float64Array, Int64Array, StringArray};
+
+    fn result(columns: &[(&str, ColumnType)], rows: Vec<Vec<GraphValue>>) -> GraphResult {
+        GraphResult {
+            columns: columns
+                .iter()
+                .map(|(name, column_type)| GraphColumn {
+                    column_type: *column_type,
+                    name: name.to_string(),
+                })
+                .collect(),
+            rows,
+            stats: GraphStats::default(),
+        }
+    }
+
+    #[test]
+    fn scalar_columns_are_typed() {
+        let r = result(
+            &[("i", ColumnType::Scalar), ("d", ColumnType::Scalar), ("b", ColumnType::Scalar), ("s", ColumnType::Scalar)],
+            vec![
+                vec![GraphValue::Integer(1), GraphValue::Double(0.5), GraphValue::Boolean(true), GraphValue::String("x".into())],
+                vec![GraphValue::Null, GraphValue::Integer(2), GraphValue::Null, GraphValue::String("y".into())],
+            ],
+        );
+        let batch = to_record_batch(&r).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+
+        let i = batch.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(i.value(0), 1);
+        assert!(i.is_null(1));
+
+        let d = batch.column(1).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(d.values(), &[0.5, 2.0]);
+
+        let b = batch.column(2).as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert!(b.value(0));
+        assert!(b.is_null(1));
+
+        let s = batch.column(3).as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(s.value(1), "y");
+    }
+
+    #[test]
+    fn mixed_and_nested_columns_fall_back_to_text() {
+        let r = result(
+            &[("m", ColumnType::Scalar)],
+            vec![
+                vec![GraphValue::Integer(1)],
+                vec![GraphValue::Array(vec![GraphValue::Integer(2)])],
+            ],
+        );
+        let batch = to_record_batch(&r).unwrap();
+        let s = batch.column(0).as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(s.value(0), "1");
+        assert_eq!(s.value(1), "[2]");
+    }
+
+    #[test]
+    fn node_columns_export_ids() {
+        let node = GraphNode { id: 42, labels: vec![0], properties: vec![] };
+        let r = result(&[("n", ColumnType::Node)], vec![vec![GraphValue::Node(node)]]);
+        let batch = to_record_batch(&r).unwrap();
+        assert_eq!(batch.schema().field(0).data_type(), &DataType::Int64);
+        let ids = batch.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(ids.value(0), 42);
+    }
+
+    #[test]
+    fn all_null_and_empty_results() {
+        let r = result(&[("x", ColumnType::Scalar)], vec![vec![GraphValue::Null]]);
+        let batch = to_record_batch(&r).unwrap();
+        assert_eq!(batch.schema().field(0).data_type(), &DataType::Null);
+
+        let stats_only = result(&[], vec![]);
+        let batch = to_record_batch(&stats_only).unwrap();
+        assert_eq!(batch.num_columns(), 0);
+        assert_eq!(batch.num_rows(), 0);
+    }
+
+    #[test]
+    fn ffi_export_roundtrip() {
+        let r = result(&[("i", ColumnType::Scalar)], vec![vec![GraphValue::Integer(7)]]);
+        let (schema, array) = to_ffi(to_record_batch(&r).unwrap()).unwrap();
+        let data = unsafe { arrow_array::ffi::from_ffi(array, &schema) }.unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(data.child_data().len(), 1);
+    }
+}
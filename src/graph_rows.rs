@@ -0,0 +1,266 @@
+//! Row access for graph query results.
+//!
+//! A [`QueryResult`](crate::client::QueryResult) keeps its cells in Rust;
+//! indexing it builds a [`Row`], whose values can be read by column name
+//! as well as by position, so code keeps working when a query's `RETURN`
+//! clause is reordered. Cells become Python values only when a row is
+//! built:
+//!
+//! - strings, integers, booleans, doubles and null map to `str`, `int`,
+//!   `bool`, `float` and `None`
+//! - arrays become lists and maps dicts
+//! - nodes, relations, paths and points become dicts (`{"id", "labels",
+//!   "properties"}`, `{"id", "type", "src", "dst", "properties"}`,
+//!   `{"nodes", "edges"}`, `{"latitude", "longitude"}`), with property
+//!   and label ids as the compact reply carries them
+
+use std::sync::Arc;
+
+use pyo3::exceptions::{PyIndexError, PyKeyError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::graph::{GraphEdge, GraphNode, GraphResult, GraphValue};
+
+// ── Values ────────────────────────────────────────────────────────
+
+fn properties<'py>(py: Python<'py>, props: &[(i64, GraphValue)]) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (id, value) in props {
+        dict.set_item(id, to_python(py, value)?)?;
+    }
+    Ok(dict)
+}
+
+fn node<'py>(py: Python<'py>, node: &GraphNode) -> PyResult<Bound<'py, PyAny>> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", node.id)?;
+    dict.set_item("labels", &node.labels)?;
+    dict.set_item("properties", properties(py, &node.properties)?)?;
+    Ok(dict.into_any())
+}
+
+fn edge<'py>(py: Python<'py>, edge: &GraphEdge) -> PyResult<Bound<'py, PyAny>> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", edge.id)?;
+    dict.set_item("type", edge.relation_type)?;
+    dict.set_item("src", edge.src_node)?;
+    dict.set_item("dst", edge.dst_node)?;
+    dict.set_item("properties", properties(py, &edge.properties)?)?;
+    Ok(dict.into_any())
+}
+
+/// A parsed cell as a Python value.
+pub fn to_python<'py>(py: Python<'py>, value: &GraphValue) -> PyResult<Bound<'py, PyAny>> {
+    Ok(match value {
+        GraphValue::Null => py.None().into_bound(py),
+        GraphValue::String(s) => s.into_pyobject(py)?.into_any(),
+        GraphValue::Integer(i) => i.into_pyobject(py)?.into_any(),
+        GraphValue::Boolean(b) => b.into_pyobject(py)?.to_owned().into_any(),
+        GraphValue::Double(d) => d.into_pyobject(py)?.into_any(),
+        GraphValue::Array(items) => {
+            let items = items.iter().map(|item| to_python(py, item)).collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, items)?.into_any()
+        }
+        GraphValue::Map(pairs) => {
+            let dict = PyDict::new(py);
+            for (key, value) in pairs {
+                dict.set_item(key, to_python(py, value)?)?;
+            }
+            dict.into_any()
+        }
+        GraphValue::Node(n) => node(py, n)?,
+        GraphValue::Edge(e) => edge(py, e)?,
+        GraphValue::Path { nodes, edges } => {
+            let dict = PyDict::new(py);
+            dict.set_item("nodes", nodes.iter().map(|n| node(py, n)).collect::<PyResult<Vec<_>>>()?)?;
+            dict.set_item("edges", edges.iter().map(|e| edge(py, e)).collect::<PyResult<Vec<_>>>()?)?;
+            dict.into_any()
+        }
+        GraphValue::Point(p) => {
+            let dict = PyDict::new(py);
+            dict.set_item("latitude", p.latitude)?;
+            dict.set_item("longitude", p.longitude)?;
+            dict.into_any()
+        }
+    })
+}
+
+// ── Keys ──────────────────────────────────────────────────────────
+
+/// A row or column key: a position or a column name.
+#[derive(FromPyObject)]
+pub enum Key {
+    Index(isize),
+    Name(String),
+}
+
+/// `index` as a position in `0..len`, counting from the end when
+/// negative.
+pub fn position(index: isize, len: usize) -> PyResult<usize> {
+    let pos = if index < 0 { index + len as isize } else { index };
+    if pos < 0 || pos as usize >= len {
+        return Err(PyIndexError::new_err(format!("index {index} out of range for {len} items")));
+    }
+    Ok(pos as usize)
+}
+
+/// The position of the column `name`.
+pub fn column(columns: &[String], name: &str) -> PyResult<usize> {
+    columns
+        .iter()
+        .position(|c| c == name)
+        .ok_or_else(|| PyKeyError::new_err(format!("no column {name:?}; the columns are {columns:?}")))
+}
+
+// ── Row ───────────────────────────────────────────────────────────
+
+/// One row of a :class:`QueryResult`.
+///
+/// Values are read by column name or by position; iterating yields the
+/// values in column order.
+///
+/// ```python
+/// res = r.graph_query_result("social", "MATCH (p) RETURN p.name AS name, p.age AS age")
+/// for row in res:
+///     print(row["name"], row["age"])
+/// ```
+#[pyclass(name = "Row", frozen, sequence)]
+pub struct Row {
+    columns: Arc<[String]>,
+    values: Vec<Py<PyAny>>,
+}
+
+impl Row {
+    /// Row `index` of `result`, with `columns` shared between rows.
+    pub fn new(py: Python<'_>, columns: Arc<[String]>, result: &GraphResult, index: usize) -> PyResult<Self> {
+        let values = result.rows[index]
+            .iter()
+            .map(|cell| Ok(to_python(py, cell)?.unbind()))
+            .collect::<PyResult<_>>()?;
+        Ok(Self { columns, values })
+    }
+
+    fn index(&self, key: &Key) -> PyResult<usize> {
+        match key {
+            Key::Index(i) => position(*i, self.values.len()),
+            Key::Name(name) => column(&self.columns, name),
+        }
+    }
+}
+
+#[pymethods]
+impl Row {
+    /// Column names, in result order.
+    #[getter]
+    fn columns(&self) -> Vec<String> {
+        self.columns.to_vec()
+    }
+
+    /// The value in column ``key``, a name or a position.
+    ///
+    /// Raises:
+    ///     KeyError: If no column has that name.
+    ///     IndexError: If the position is out of range.
+    fn __getitem__(&self, py: Python<'_>, key: Key) -> PyResult<Py<PyAny>> {
+        let idx = self.index(&key)?;
+        Ok(self.values.get(idx).map_or_else(|| py.None(), |v| v.clone_ref(py)))
+    }
+
+    /// The value in column ``key``, or ``default`` if there is no such
+    /// column.
+    #[pyo3(signature = (key, default=None))]
+    fn get(&self, py: Python<'_>, key: Key, default: Option<Py<PyAny>>) -> Py<PyAny> {
+        match self.index(&key).ok().and_then(|idx| self.values.get(idx)) {
+            Some(value) => value.clone_ref(py),
+            None => default.unwrap_or_else(|| py.None()),
+        }
+    }
+
+    /// The row as a ``{column: value}`` dict.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for (name, value) in self.columns.iter().zip(&self.values) {
+            dict.set_item(name, value)?;
+        }
+        Ok(dict)
+    }
+
+    fn __len__(&self) -> usize {
+        self.values.len()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        Ok(PyList::new(py, &self.values)?.try_iter()?.into_any())
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let fields = self
+            .columns
+            .iter()
+            .zip(&self.values)
+            .map(|(name, value)| Ok(format!("{name}={}", value.bind(py).repr()?)))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(format!("Row({})", fields.join(", ")))
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{GraphPoint, GraphStats};
+
+    fn result() -> GraphResult {
+        use crate::graph::{ColumnType, GraphColumn};
+        let column = |name: &str| GraphColumn { column_type: ColumnType::Scalar, name: name.into() };
+        GraphResult {
+            columns: vec![column("name"), column("age")],
+            rows: vec![
+                vec![GraphValue::String("a".into()), GraphValue::Integer(1)],
+                vec![GraphValue::String("b".into()), GraphValue::Double(2.5)],
+            ],
+            stats: GraphStats::default(),
+        }
+    }
+
+    #[test]
+    fn rows_read_by_name_or_position() {
+        Python::attach(|py| {
+            let res = result();
+            let columns: Arc<[String]> = vec!["name".to_string(), "age".to_string()].into();
+            let row = Row::new(py, columns, &res, 1).unwrap();
+            assert_eq!(row.columns(), ["name", "age"]);
+            assert_eq!(row.__getitem__(py, Key::Name("age".into())).unwrap().extract::<f64>(py).unwrap(), 2.5);
+            assert_eq!(row.__getitem__(py, Key::Index(-2)).unwrap().extract::<String>(py).unwrap(), "b");
+            let err = row.__getitem__(py, Key::Name("nope".into())).unwrap_err();
+            assert!(err.is_instance_of::<PyKeyError>(py));
+            assert!(row.__getitem__(py, Key::Index(2)).unwrap_err().is_instance_of::<PyIndexError>(py));
+            assert!(row.get(py, Key::Name("nope".into()), None).is_none(py));
+            assert_eq!(row.__repr__(py).unwrap(), "Row(name='b', age=2.5)");
+            assert_eq!(row.to_dict(py).unwrap().repr().unwrap().to_string(), "{'name': 'b', 'age': 2.5}");
+        });
+    }
+
+    #[test]
+    fn entities_become_dicts() {
+        Python::attach(|py| {
+            let node = GraphNode { id: 3, labels: vec![0], properties: vec![(1, GraphValue::Boolean(true))] };
+            let repr = |value: &GraphValue| to_python(py, value).unwrap().repr().unwrap().to_string();
+            assert_eq!(repr(&GraphValue::Node(node)), "{'id': 3, 'labels': [0], 'properties': {1: True}}");
+            let point = GraphValue::Point(GraphPoint { latitude: 1.5, longitude: 2.0 });
+            assert_eq!(repr(&point), "{'latitude': 1.5, 'longitude': 2.0}");
+            let map = GraphValue::Map(vec![("k".into(), GraphValue::Array(vec![GraphValue::Null]))]);
+            assert_eq!(repr(&map), "{'k': [None]}");
+        });
+    }
+
+    #[test]
+    fn positions_count_from_the_end() {
+        assert_eq!(position(-1, 3).unwrap(), 2);
+        assert!(position(3, 3).is_err());
+        assert!(position(-4, 3).is_err());
+        assert_eq!(column(&["a".into(), "b".into()], "b").unwrap(), 1);
+    }
+}
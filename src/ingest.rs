@@ -0,0 +1,341 @@
+//! Bulk loading of hashes and sorted sets.
+//!
+//! `Redis.load_hash_rows()` turns rows — dicts, or the named tuples of
+//! `DataFrame.itertuples()` — into one `HSET` per row, and
+//! `Redis.load_zset()` turns member/score pairs into `ZADD`s. Input is
+//! read and validated a chunk at a time while holding the GIL; each chunk
+//! is then pipelined with the GIL released. An invalid row therefore stops
+//! the load with the earlier chunks already written.
+//!
+//! Every row must have the same columns as the first one. `None` and NaN
+//! values count as missing and are left out of the `HSET`, so sparse
+//! dataframes load without placeholder fields.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyFloat, PyIterator, PyTuple};
+
+use crate::codec::encode_arg;
+use crate::error::{PyrsedisError, Result};
+use crate::resp::types::RespValue;
+use crate::router::{ClientRouter, Router};
+
+/// Field holding the row label in `DataFrame.itertuples()` output.
+const INDEX_FIELD: &str = "Index";
+
+/// Reject a `chunk_size` of zero.
+pub fn check_chunk_size(chunk_size: usize) -> Result<()> {
+    if chunk_size == 0 {
+        return Err(PyrsedisError::Type("chunk_size must be > 0".into()));
+    }
+    Ok(())
+}
+
+/// Pipeline `commands` and add up their integer replies.
+pub async fn send_chunk(router: &ClientRouter, commands: &[Vec<String>]) -> Result<i64> {
+    let mut total = 0;
+    for reply in router.pipeline(commands).await? {
+        match reply {
+            RespValue::Integer(n) => total += n,
+            RespValue::Error(msg) => return Err(PyrsedisError::redis(msg)),
+            other => {
+                return Err(PyrsedisError::Type(format!("{} returned {}", commands[0][0], other.type_name())));
+            }
+        }
+    }
+    Ok(total)
+}
+
+/// Call the `progress` callback, if any, with the count loaded so far.
+pub fn report(progress: Option<&Bound<'_, PyAny>>, loaded: usize) -> PyResult<()> {
+    if let Some(progress) = progress {
+        progress.call1((loaded,))?;
+    }
+    Ok(())
+}
+
+/// Rows of a `load_hash_rows()` call, turned into `HSET`s.
+pub struct HashRows {
+    prefix: String,
+    key_field: Option<String>,
+    /// Sorted column names (key field excluded), fixed by the first row.
+    columns: Option<Vec<String>>,
+    /// Rows read so far, for error messages.
+    read: usize,
+}
+
+impl HashRows {
+    pub fn new(prefix: &str, key_field: Option<String>) -> Self {
+        Self { prefix: prefix.to_string(), key_field, columns: None, read: 0 }
+    }
+
+    /// Read up to `chunk_size` rows from `rows`, returning one `HSET` per
+    /// row (an empty chunk once `rows` is exhausted).
+    pub fn next_chunk(&mut self, rows: &mut Bound<'_, PyIterator>, chunk_size: usize) -> PyResult<Vec<Vec<String>>> {
+        let mut chunk = Vec::with_capacity(chunk_size);
+        while chunk.len() < chunk_size {
+            let Some(row) = rows.next() else { break };
+            chunk.push(self.hset_command(&row?)?);
+            self.read += 1;
+        }
+        Ok(chunk)
+    }
+
+    fn hset_command(&mut self, row: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+        let index = self.read;
+        let (fields, named_tuple) = row_fields(row, index)?;
+        let key_field = match (&self.key_field, named_tuple) {
+            (Some(field), _) => field.as_str(),
+            (None, true) => INDEX_FIELD,
+            (None, false) => {
+                return Err(PyrsedisError::Type("key_field is required when rows are dicts".into()).into());
+            }
+        };
+
+        let mut key = None;
+        let mut columns = Vec::with_capacity(fields.len());
+        let mut cmd = vec!["HSET".to_string(), String::new()];
+        for (name, value) in fields {
+            if name == key_field {
+                key = Some(value);
+                continue;
+            }
+            if let Some(value) = arg_string(&value).map_err(|e| field_error(index, &name, e))? {
+                cmd.push(name.clone());
+                cmd.push(value);
+            }
+            columns.push(name);
+        }
+
+        columns.sort_unstable();
+        match &self.columns {
+            None => self.columns = Some(columns),
+            Some(expected) if *expected != columns => {
+                return Err(PyrsedisError::Type(format!(
+                    "row {index} has columns {columns:?}, expected {expected:?} like the first row"
+                ))
+                .into());
+            }
+            Some(_) => {}
+        }
+
+        let key = key
+            .ok_or_else(|| PyrsedisError::Type(format!("row {index} has no '{key_field}' field")))?;
+        let key = arg_string(&key)
+            .map_err(|e| field_error(index, key_field, e))?
+            .ok_or_else(|| PyrsedisError::Type(format!("row {index} has no value for key field '{key_field}'")))?;
+        if cmd.len() == 2 {
+            return Err(PyrsedisError::Type(format!("row {index} has no values to set")).into());
+        }
+        cmd[1] = format!("{}{key}", self.prefix);
+        Ok(cmd)
+    }
+}
+
+/// Member/score pairs of a `load_zset()` call, turned into `ZADD`s.
+pub struct ZsetMembers {
+    name: String,
+    /// Pairs read so far, for error messages.
+    read: usize,
+}
+
+impl ZsetMembers {
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), read: 0 }
+    }
+
+    /// Iterate the pairs of `mapping`: a dict of member to score, or an
+    /// iterable of `(member, score)` tuples.
+    pub fn pairs<'py>(mapping: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyIterator>> {
+        match mapping.cast::<PyDict>() {
+            Ok(dict) => dict.items().into_any().try_iter(),
+            Err(_) => mapping.try_iter(),
+        }
+    }
+
+    /// Read up to `chunk_size` pairs from `pairs` into one `ZADD`,
+    /// returning it with its member count (`None` once `pairs` is
+    /// exhausted).
+    pub fn next_command(
+        &mut self,
+        pairs: &mut Bound<'_, PyIterator>,
+        chunk_size: usize,
+    ) -> PyResult<Option<(Vec<String>, usize)>> {
+        let mut cmd = vec!["ZADD".to_string(), self.name.clone()];
+        let mut members = 0;
+        while members < chunk_size {
+            let Some(pair) = pairs.next() else { break };
+            let (member, score) = self.member(&pair?)?;
+            cmd.push(score);
+            cmd.push(member);
+            members += 1;
+            self.read += 1;
+        }
+        Ok((members > 0).then_some((cmd, members)))
+    }
+
+    /// Validate one pair into `(member, score)` arguments.
+    fn member(&self, pair: &Bound<'_, PyAny>) -> PyResult<(String, String)> {
+        let index = self.read;
+        let (member, score): (Bound<'_, PyAny>, Bound<'_, PyAny>) = pair.extract().map_err(|_| {
+            PyrsedisError::Type(format!("member {index} must be a (member, score) pair"))
+        })?;
+        let member = arg_string(&member)
+            .map_err(|e| member_error(index, e))?
+            .ok_or_else(|| PyrsedisError::Type(format!("member {index} is None")))?;
+        let score = scalar(&score)?.extract::<f64>().map_err(|e| member_error(index, e))?;
+        if score.is_nan() {
+            return Err(PyrsedisError::Type(format!("member {index} ('{member}') has a NaN score")).into());
+        }
+        Ok((member, encode_score(score)))
+    }
+}
+
+/// A ZADD score argument (`inf` and `-inf` spelled the way Redis expects).
+fn encode_score(score: f64) -> String {
+    match score {
+        f64::INFINITY => "+inf".into(),
+        f64::NEG_INFINITY => "-inf".into(),
+        _ => format!("{score:?}"),
+    }
+}
+
+/// `(name, value)` pairs of a row.
+type Fields<'py> = Vec<(String, Bound<'py, PyAny>)>;
+
+/// The fields of a row, and whether it is a named tuple.
+fn row_fields<'py>(row: &Bound<'py, PyAny>, index: usize) -> PyResult<(Fields<'py>, bool)> {
+    if let Ok(dict) = row.cast::<PyDict>() {
+        let fields = dict
+            .iter()
+            .map(|(name, value)| Ok((name.extract::<String>()?, value)))
+            .collect::<PyResult<_>>()?;
+        return Ok((fields, false));
+    }
+    if let (Ok(tuple), Ok(names)) = (row.cast::<PyTuple>(), row.getattr("_fields")) {
+        let names: Vec<String> = names.extract()?;
+        return Ok((names.into_iter().zip(tuple.iter()).collect(), true));
+    }
+    let type_name = row.get_type().name().map(|n| n.to_string()).unwrap_or_default();
+    Err(PyrsedisError::Type(format!("row {index} must be a dict or a named tuple, got '{type_name}'")).into())
+}
+
+/// A value as a command argument, or `None` for `None` and NaN.
+///
+/// NumPy scalars, as found in dataframe rows, are converted with
+/// `.item()` first.
+fn arg_string(value: &Bound<'_, PyAny>) -> PyResult<Option<String>> {
+    if value.is_none() {
+        return Ok(None);
+    }
+    let value = scalar(value)?;
+    if value.cast::<PyFloat>().is_ok_and(|f| f.value().is_nan()) {
+        return Ok(None);
+    }
+    let bytes = encode_arg(&value)?;
+    String::from_utf8(bytes)
+        .map(Some)
+        .map_err(|_| PyrsedisError::Type("value is not valid UTF-8".into()).into())
+}
+
+/// `value.item()` for NumPy scalars, `value` itself otherwise.
+fn scalar<'py>(value: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let numpy = value
+        .get_type()
+        .module()
+        .is_ok_and(|module| module.to_str().is_ok_and(|m| m == "numpy"));
+    if numpy {
+        return value.call_method0("item");
+    }
+    Ok(value.clone())
+}
+
+fn field_error(index: usize, field: &str, err: PyErr) -> PyErr {
+    Python::attach(|py| PyrsedisError::Type(format!("row {index}, field '{field}': {}", err.value(py))).into())
+}
+
+fn member_error(index: usize, err: PyErr) -> PyErr {
+    Python::attach(|py| PyrsedisError::Type(format!("member {index}: {}", err.value(py))).into())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn hset_chunks(py: Python<'_>, rows: &CStr, key_field: Option<&str>, chunk_size: usize) -> PyResult<Vec<Vec<Vec<String>>>> {
+        let mut loader = HashRows::new("user:", key_field.map(String::from));
+        let mut rows = py.eval(rows, None, None)?.try_iter()?;
+        let mut chunks = Vec::new();
+        loop {
+            let chunk = loader.next_chunk(&mut rows, chunk_size)?;
+            if chunk.is_empty() {
+                return Ok(chunks);
+            }
+            chunks.push(chunk);
+        }
+    }
+
+    #[test]
+    fn hash_rows_from_dicts() {
+        Python::attach(|py| {
+            let rows = c"[{'id': 1, 'name': 'ann', 'score': 1.5}, {'name': 'bob', 'id': 2, 'score': None}, {'id': 3, 'name': 'cy', 'score': 2}]";
+            let chunks = hset_chunks(py, rows, Some("id"), 2).unwrap();
+            assert_eq!(chunks.len(), 2);
+            assert_eq!(chunks[0][0], ["HSET", "user:1", "name", "ann", "score", "1.5"]);
+            // None is left out
+            assert_eq!(chunks[0][1], ["HSET", "user:2", "name", "bob"]);
+            assert_eq!(chunks[1][0], ["HSET", "user:3", "name", "cy", "score", "2"]);
+
+            let err = hset_chunks(py, rows, None, 2).unwrap_err();
+            assert!(err.to_string().contains("key_field is required"));
+        });
+    }
+
+    #[test]
+    fn hash_rows_from_named_tuples() {
+        Python::attach(|py| {
+            let rows = c"(lambda R: [R(7, 'ann', float('nan')), R(8, 'bob', 3.0)])(__import__('collections').namedtuple('Row', ['Index', 'name', 'score']))";
+            let chunks = hset_chunks(py, rows, None, 10).unwrap();
+            assert_eq!(chunks[0], [vec!["HSET", "user:7", "name", "ann"], vec!["HSET", "user:8", "name", "bob", "score", "3.0"]]);
+        });
+    }
+
+    #[test]
+    fn hash_rows_are_validated() {
+        Python::attach(|py| {
+            let err = |rows: &CStr| hset_chunks(py, rows, Some("id"), 10).unwrap_err().to_string();
+            assert!(err(c"[{'id': 1, 'a': 1}, {'id': 2, 'b': 1}]").contains("row 1 has columns [\"b\"], expected [\"a\"]"));
+            assert!(err(c"[{'id': 1, 'a': [1]}]").contains("row 0, field 'a': type error: invalid argument of type 'list'"));
+            assert!(err(c"[{'a': 1}]").contains("row 0 has no 'id' field"));
+            assert!(err(c"[{'id': None, 'a': 1}]").contains("row 0 has no value for key field 'id'"));
+            assert!(err(c"[{'id': 1, 'a': None}]").contains("row 0 has no values to set"));
+            assert!(err(c"[(1, 2)]").contains("row 0 must be a dict or a named tuple, got 'tuple'"));
+        });
+    }
+
+    #[test]
+    fn zset_members_in_chunks() {
+        Python::attach(|py| {
+            let mapping = py.eval(c"{'a': 1, 'b': 2.5, 'c': float('-inf')}", None, None).unwrap();
+            let mut loader = ZsetMembers::new("board");
+            let mut pairs = ZsetMembers::pairs(&mapping).unwrap();
+            let (cmd, n) = loader.next_command(&mut pairs, 2).unwrap().unwrap();
+            assert_eq!((cmd, n), (vec!["ZADD".into(), "board".into(), "1.0".into(), "a".into(), "2.5".into(), "b".into()], 2));
+            let (cmd, n) = loader.next_command(&mut pairs, 2).unwrap().unwrap();
+            assert_eq!((cmd, n), (vec!["ZADD".into(), "board".into(), "-inf".into(), "c".into()], 1));
+            assert!(loader.next_command(&mut pairs, 2).unwrap().is_none());
+
+            let pairs_of = |expr: &CStr| {
+                let mapping = py.eval(expr, None, None).unwrap();
+                let mut pairs = ZsetMembers::pairs(&mapping).unwrap();
+                ZsetMembers::new("board").next_command(&mut pairs, 10).map(|c| c.unwrap().0)
+            };
+            assert_eq!(pairs_of(c"[('x', 3)]").unwrap(), ["ZADD", "board", "3.0", "x"]);
+            assert!(pairs_of(c"[('x', float('nan'))]").unwrap_err().to_string().contains("member 0 ('x') has a NaN score"));
+            assert!(pairs_of(c"[('x', 'high')]").unwrap_err().to_string().contains("member 0:"));
+            assert!(pairs_of(c"['x']").unwrap_err().to_string().contains("member 0 must be a (member, score) pair"));
+        });
+    }
+}
@@ -1,13 +1,37 @@
+pub mod async_client;
+pub mod blocking;
+pub mod cache;
 pub mod client;
+pub mod clock;
+pub mod codec;
+pub mod commands;
 pub mod config;
 pub mod connection;
 pub mod crc16;
+pub mod encryption;
 pub mod error;
+pub mod events;
+pub mod expiry;
+pub mod geo;
 pub mod graph;
+#[cfg(feature = "arrow")]
+pub mod graph_arrow;
+pub mod graph_rows;
+pub mod ingest;
+pub mod numbers;
+pub mod pubsub;
+pub mod purge;
+pub mod registry;
+pub mod reshard;
 pub mod resp;
 pub mod response;
 pub mod router;
 pub mod runtime;
+pub mod scan;
+pub mod script;
+pub mod sha1;
+pub mod sinter;
+pub mod streams;
 
 use pyo3::prelude::*;
 
@@ -17,6 +41,27 @@ fn _pyrsedis(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
     m.add_class::<client::Redis>()?;
     m.add_class::<client::Pipeline>()?;
+    m.add_class::<client::DedicatedConnection>()?;
+    m.add_class::<client::PubSub>()?;
+    m.add_class::<client::FloatArray>()?;
+    m.add_class::<client::QueryResult>()?;
+    m.add_class::<graph_rows::Row>()?;
+    m.add_class::<client::NodeResults>()?;
+    m.add_class::<expiry::ExpiryWatcher>()?;
+    m.add_class::<scan::ScanIterator>()?;
+    m.add_class::<script::Script>()?;
+    m.add_class::<encryption::Encryption>()?;
+    m.add_class::<response::Verbatim>()?;
+    m.add("BigNumber", response::big_number_type(m.py())?)?;
+    m.add_class::<async_client::AsyncRedis>()?;
+    m.add_class::<async_client::AsyncPipeline>()?;
+    m.add_function(wrap_pyfunction!(client::configure_default, m)?)?;
+    m.add_function(wrap_pyfunction!(client::get_default, m)?)?;
+    m.add_function(wrap_pyfunction!(client::before_fork, m)?)?;
+    m.add_function(wrap_pyfunction!(client::after_fork_in_child, m)?)?;
+    m.add_function(wrap_pyfunction!(client::all_clients, m)?)?;
+    m.add_function(wrap_pyfunction!(client::configure_topology_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(client::parse_graph_compact, m)?)?;
     error::register_exceptions(m)?;
     Ok(())
 }
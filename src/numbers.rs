@@ -0,0 +1,141 @@
+//! Strict numeric arguments.
+//!
+//! Numbers are formatted permissively by default: a `float` argument is
+//! sent in its shortest round-trip form wherever it appears, and an `int`
+//! score is converted to the double Redis stores it as. A client created
+//! with `strict_numbers=True` refuses the cases where that hides a bug
+//! instead: a non-integer where Redis parses an integer (`EXPIRE`
+//! seconds, `INCRBY` amounts, list indexes, …), which the server would
+//! reject or a caller would misread, and an `int` score too large to be
+//! held exactly by a double, which would be stored rounded.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyInt};
+
+use crate::error::{PyrsedisError, Result};
+
+/// Positions of the arguments `command` parses as integers, counting the
+/// command name as 0.
+fn integer_positions(command: &[u8]) -> &'static [usize] {
+    match command.to_ascii_uppercase().as_slice() {
+        b"SELECT" => &[1],
+        b"EXPIRE" | b"PEXPIRE" | b"EXPIREAT" | b"PEXPIREAT" | b"SETEX" | b"PSETEX" | b"INCRBY" | b"DECRBY"
+        | b"LINDEX" | b"LSET" | b"SETRANGE" | b"GETBIT" | b"LPOP" | b"RPOP" | b"SPOP" | b"SRANDMEMBER" | b"MOVE" => &[2],
+        b"LRANGE" | b"LTRIM" | b"GETRANGE" | b"SETBIT" | b"ZREMRANGEBYRANK" => &[2, 3],
+        b"HINCRBY" => &[3],
+        _ => &[],
+    }
+}
+
+/// Whether `arg` is an integer as Redis parses one: an optional `-`
+/// followed by digits.
+fn is_integer(arg: &[u8]) -> bool {
+    let digits = arg.strip_prefix(b"-").unwrap_or(arg);
+    !digits.is_empty() && digits.iter().all(u8::is_ascii_digit)
+}
+
+/// Check that every argument of `args` that Redis parses as an integer
+/// is one, including the expiry given to `SET` and `GETEX` with
+/// `EX`/`PX`/`EXAT`/`PXAT`.
+pub fn check_integers<A: AsRef<[u8]>>(args: &[A]) -> Result<()> {
+    let Some(command) = args.first().map(AsRef::as_ref) else {
+        return Ok(());
+    };
+    let mut positions: Vec<usize> = integer_positions(command).to_vec();
+    let options_from = match command.to_ascii_uppercase().as_slice() {
+        b"SET" => Some(3),
+        b"GETEX" => Some(2),
+        _ => None,
+    };
+    if let Some(start) = options_from {
+        for (i, arg) in args.iter().enumerate().skip(start) {
+            if matches!(arg.as_ref().to_ascii_uppercase().as_slice(), b"EX" | b"PX" | b"EXAT" | b"PXAT") {
+                positions.push(i + 1);
+            }
+        }
+    }
+    for i in positions {
+        let Some(arg) = args.get(i).map(AsRef::as_ref) else {
+            continue;
+        };
+        if !is_integer(arg) {
+            return Err(PyrsedisError::Type(format!(
+                "{} expects an integer at argument {i}, got {:?} (strict_numbers)",
+                String::from_utf8_lossy(command).to_ascii_uppercase(),
+                String::from_utf8_lossy(arg),
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// A sorted-set score or increment, extracted from an `int` or `float`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Score {
+    /// The double sent to Redis.
+    pub value: f64,
+    /// Whether `value` equals the Python number exactly; `False` for an
+    /// `int` beyond 2**53 that had to be rounded.
+    pub exact: bool,
+}
+
+impl Score {
+    /// The score, refusing a rounded one when `strict`.
+    pub fn checked(self, strict: bool) -> Result<f64> {
+        if strict && !self.exact {
+            return Err(PyrsedisError::Type(format!(
+                "score cannot be stored exactly as a double; it would become {} (strict_numbers)",
+                self.value
+            )));
+        }
+        Ok(self.value)
+    }
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Score {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        let value: f64 = obj.extract()?;
+        let exact = !obj.is_instance_of::<PyInt>() || obj.is_instance_of::<PyBool>() || obj.eq(value)?;
+        Ok(Self { value, exact })
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_arguments_are_checked() {
+        assert!(check_integers(&["EXPIRE", "k", "10"]).is_ok());
+        assert!(check_integers(&["expire", "k", "10.0"]).is_err());
+        assert!(check_integers(&["LRANGE", "l", "0", "-1"]).is_ok());
+        assert!(check_integers(&["LRANGE", "l", "0", "1e3"]).is_err());
+        assert!(check_integers(&["HINCRBY", "h", "1.5", "2"]).is_ok());
+        assert!(check_integers(&["HINCRBY", "h", "f", "2.5"]).is_err());
+        // Values and unknown commands are left alone
+        assert!(check_integers(&["SET", "k", "1.5"]).is_ok());
+        assert!(check_integers(&["ZADD", "z", "1.5", "m"]).is_ok());
+        let err = check_integers(&["SET", "k", "v", "NX", "EX", "1.5"]).unwrap_err();
+        assert_eq!(err.to_string(), "type error: SET expects an integer at argument 5, got \"1.5\" (strict_numbers)");
+        assert!(check_integers(&["GETEX", "k", "PXAT", "1700000000000"]).is_ok());
+        assert!(check_integers::<&str>(&[]).is_ok());
+    }
+
+    #[test]
+    fn scores_know_when_they_were_rounded() {
+        Python::attach(|py| {
+            let score = |expr: &std::ffi::CStr| py.eval(expr, None, None).unwrap().extract::<Score>().unwrap();
+            assert_eq!(score(c"1.5"), Score { value: 1.5, exact: true });
+            assert_eq!(score(c"2**53"), Score { value: 9_007_199_254_740_992.0, exact: true });
+            let rounded = score(c"2**53 + 1");
+            assert!(!rounded.exact);
+            assert_eq!(rounded.checked(false).unwrap(), 9_007_199_254_740_992.0);
+            assert!(rounded.checked(true).is_err());
+            assert!(score(c"True").exact);
+        });
+    }
+}
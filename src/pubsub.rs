@@ -0,0 +1,358 @@
+//! Pub/Sub frame classification and filtering.
+//!
+//! Subscriber connections receive a mix of subscription confirmations and
+//! data messages, either as RESP2 arrays or RESP3 push frames. Filtering
+//! happens here, on the raw [`RespValue`], so frames the caller does not
+//! want never cross into Python.
+
+use crate::resp::types::RespValue;
+
+// ── Frame classification ──────────────────────────────────────────
+
+/// The kind of a Pub/Sub frame, taken from its first element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    Subscribe,
+    Unsubscribe,
+    PSubscribe,
+    PUnsubscribe,
+    SSubscribe,
+    SUnsubscribe,
+    Message,
+    PMessage,
+    SMessage,
+    Pong,
+}
+
+impl MessageKind {
+    /// Parse a frame kind (case-insensitive).
+    pub fn parse(kind: &[u8]) -> Option<Self> {
+        let kind = match kind.to_ascii_lowercase().as_slice() {
+            b"subscribe" => Self::Subscribe,
+            b"unsubscribe" => Self::Unsubscribe,
+            b"psubscribe" => Self::PSubscribe,
+            b"punsubscribe" => Self::PUnsubscribe,
+            b"ssubscribe" => Self::SSubscribe,
+            b"sunsubscribe" => Self::SUnsubscribe,
+            b"message" => Self::Message,
+            b"pmessage" => Self::PMessage,
+            b"smessage" => Self::SMessage,
+            b"pong" => Self::Pong,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// The frame kind as sent by the server (lowercase).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Subscribe => "subscribe",
+            Self::Unsubscribe => "unsubscribe",
+            Self::PSubscribe => "psubscribe",
+            Self::PUnsubscribe => "punsubscribe",
+            Self::SSubscribe => "ssubscribe",
+            Self::SUnsubscribe => "sunsubscribe",
+            Self::Message => "message",
+            Self::PMessage => "pmessage",
+            Self::SMessage => "smessage",
+            Self::Pong => "pong",
+        }
+    }
+
+    /// Whether this is a (un)subscribe confirmation.
+    pub fn is_subscription(self) -> bool {
+        matches!(
+            self,
+            Self::Subscribe
+                | Self::Unsubscribe
+                | Self::PSubscribe
+                | Self::PUnsubscribe
+                | Self::SSubscribe
+                | Self::SUnsubscribe
+        )
+    }
+
+    /// Whether this frame carries a published payload.
+    pub fn is_data(self) -> bool {
+        matches!(self, Self::Message | Self::PMessage | Self::SMessage)
+    }
+}
+
+/// Classify a Pub/Sub frame, returning its kind and channel name.
+///
+/// Accepts both RESP2 arrays (`["message", channel, payload]`) and RESP3
+/// push frames. For `pmessage` the channel (not the pattern) is returned.
+pub fn classify(frame: &RespValue) -> Option<(MessageKind, &[u8])> {
+    let (kind, rest): (&[u8], &[RespValue]) = match frame {
+        RespValue::Push { kind, data } => (kind.as_bytes(), data),
+        RespValue::Array(items) => {
+            let (first, rest) = items.split_first()?;
+            (first.as_bytes()?, rest)
+        }
+        _ => return None,
+    };
+    let kind = MessageKind::parse(kind)?;
+    let channel_idx = if kind == MessageKind::PMessage { 1 } else { 0 };
+    let channel = rest.get(channel_idx).and_then(RespValue::as_bytes).unwrap_or(b"");
+    Some((kind, channel))
+}
+
+/// Split an owned Pub/Sub frame into its kind and remaining elements.
+///
+/// Frames that are not recognisable Pub/Sub frames are handed back
+/// unchanged as the error value.
+pub fn split(frame: RespValue) -> std::result::Result<(MessageKind, Vec<RespValue>), RespValue> {
+    let kind = match &frame {
+        RespValue::Push { kind, .. } => MessageKind::parse(kind.as_bytes()),
+        RespValue::Array(items) => items.first().and_then(RespValue::as_bytes).and_then(MessageKind::parse),
+        _ => None,
+    };
+    let Some(kind) = kind else {
+        return Err(frame);
+    };
+    match frame {
+        RespValue::Push { data, .. } => Ok((kind, data)),
+        RespValue::Array(mut items) => {
+            items.remove(0);
+            Ok((kind, items))
+        }
+        _ => unreachable!("kind is only parsed from push and array frames"),
+    }
+}
+
+// ── Glob matching ─────────────────────────────────────────────────
+
+/// Match `text` against a Redis-style glob pattern.
+///
+/// Supports `*`, `?`, `[abc]`, `[^abc]`, `[a-z]` and `\` escapes, with the
+/// same semantics as Redis' `stringmatchlen` (used by PSUBSCRIBE/KEYS).
+pub fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0usize, 0usize);
+    // Backtrack point for the most recent `*`: (pattern idx after *, text idx)
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() {
+            match pattern[p] {
+                b'*' => {
+                    star = Some((p + 1, t));
+                    p += 1;
+                    continue;
+                }
+                b'?' => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                b'[' => {
+                    if let Some((matched, next)) = match_class(pattern, p, text[t]) {
+                        if matched {
+                            p = next;
+                            t += 1;
+                            continue;
+                        }
+                    }
+                }
+                b'\\' if p + 1 < pattern.len() => {
+                    if pattern[p + 1] == text[t] {
+                        p += 2;
+                        t += 1;
+                        continue;
+                    }
+                }
+                c => {
+                    if c == text[t] {
+                        p += 1;
+                        t += 1;
+                        continue;
+                    }
+                }
+            }
+        }
+        // Mismatch — widen the last `*` by one byte, or fail
+        match star {
+            Some((sp, st)) => {
+                p = sp;
+                t = st + 1;
+                star = Some((sp, st + 1));
+            }
+            None => return false,
+        }
+    }
+    // Text consumed: the rest of the pattern must be all `*`
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Match one byte against the `[...]` class starting at `pattern[start]`.
+///
+/// Returns whether it matched and the index just past the class, or
+/// `None` if the class is unterminated (treated as a literal mismatch).
+fn match_class(pattern: &[u8], start: usize, c: u8) -> Option<(bool, usize)> {
+    let mut i = start + 1;
+    let negate = pattern.get(i) == Some(&b'^');
+    if negate {
+        i += 1;
+    }
+    let mut matched = false;
+    while i < pattern.len() && pattern[i] != b']' {
+        if pattern[i] == b'\\' && i + 1 < pattern.len() {
+            matched |= pattern[i + 1] == c;
+            i += 2;
+        } else if i + 2 < pattern.len() && pattern[i + 1] == b'-' && pattern[i + 2] != b']' {
+            let (lo, hi) = if pattern[i] <= pattern[i + 2] {
+                (pattern[i], pattern[i + 2])
+            } else {
+                (pattern[i + 2], pattern[i])
+            };
+            matched |= (lo..=hi).contains(&c);
+            i += 3;
+        } else {
+            matched |= pattern[i] == c;
+            i += 1;
+        }
+    }
+    if i >= pattern.len() {
+        return None;
+    }
+    Some((matched != negate, i + 1))
+}
+
+// ── Message filter ────────────────────────────────────────────────
+
+/// Decides which Pub/Sub frames are handed to Python.
+#[derive(Debug, Clone, Default)]
+pub struct MessageFilter {
+    /// Drop (un)subscribe confirmations.
+    pub ignore_subscribe_messages: bool,
+    /// Glob patterns a data message's channel must match (empty = all).
+    pub channel_patterns: Vec<Vec<u8>>,
+}
+
+impl MessageFilter {
+    /// Whether `frame` should be delivered.
+    ///
+    /// Frames that are not recognisable Pub/Sub frames are always
+    /// delivered, so nothing is silently lost.
+    pub fn accepts(&self, frame: &RespValue) -> bool {
+        let Some((kind, channel)) = classify(frame) else {
+            return true;
+        };
+        if kind.is_subscription() {
+            return !self.ignore_subscribe_messages;
+        }
+        if kind.is_data() && !self.channel_patterns.is_empty() {
+            return self.channel_patterns.iter().any(|p| glob_match(p, channel));
+        }
+        true
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    fn bulk(s: &str) -> RespValue {
+        RespValue::BulkString(Bytes::copy_from_slice(s.as_bytes()))
+    }
+
+    fn resp2(items: &[&str]) -> RespValue {
+        RespValue::Array(items.iter().map(|s| bulk(s)).collect())
+    }
+
+    #[test]
+    fn classify_resp2_message() {
+        let frame = resp2(&["message", "news", "hello"]);
+        assert_eq!(classify(&frame), Some((MessageKind::Message, &b"news"[..])));
+    }
+
+    #[test]
+    fn classify_pmessage_returns_channel() {
+        let frame = resp2(&["pmessage", "n*", "news", "hello"]);
+        assert_eq!(classify(&frame), Some((MessageKind::PMessage, &b"news"[..])));
+    }
+
+    #[test]
+    fn classify_resp3_push() {
+        let frame = RespValue::Push {
+            kind: "subscribe".into(),
+            data: vec![bulk("news"), RespValue::Integer(1)],
+        };
+        assert_eq!(classify(&frame), Some((MessageKind::Subscribe, &b"news"[..])));
+    }
+
+    #[test]
+    fn classify_non_pubsub() {
+        assert_eq!(classify(&RespValue::Integer(1)), None);
+        assert_eq!(classify(&resp2(&["bogus", "x"])), None);
+    }
+
+    #[test]
+    fn split_owned_frames() {
+        let (kind, rest) = split(resp2(&["pmessage", "n*", "news", "hi"])).unwrap();
+        assert_eq!(kind, MessageKind::PMessage);
+        assert_eq!(kind.as_str(), "pmessage");
+        assert_eq!(rest, vec![bulk("n*"), bulk("news"), bulk("hi")]);
+
+        let push = RespValue::Push { kind: "message".into(), data: vec![bulk("c"), bulk("d")] };
+        assert_eq!(split(push).unwrap().1.len(), 2);
+
+        assert_eq!(split(RespValue::Integer(3)), Err(RespValue::Integer(3)));
+    }
+
+    #[test]
+    fn glob_literals_and_wildcards() {
+        assert!(glob_match(b"news", b"news"));
+        assert!(!glob_match(b"news", b"newsx"));
+        assert!(glob_match(b"news.*", b"news.sport"));
+        assert!(glob_match(b"*", b""));
+        assert!(glob_match(b"h?llo", b"hallo"));
+        assert!(!glob_match(b"h?llo", b"hllo"));
+        assert!(glob_match(b"a*b*c", b"axxbyyc"));
+        assert!(!glob_match(b"a*b*c", b"axxbyy"));
+    }
+
+    #[test]
+    fn glob_classes() {
+        assert!(glob_match(b"h[ae]llo", b"hello"));
+        assert!(!glob_match(b"h[ae]llo", b"hillo"));
+        assert!(glob_match(b"h[^e]llo", b"hallo"));
+        assert!(!glob_match(b"h[^e]llo", b"hello"));
+        assert!(glob_match(b"h[a-b]llo", b"hbllo"));
+        assert!(glob_match(b"h[b-a]llo", b"hallo"));
+    }
+
+    #[test]
+    fn glob_escapes() {
+        assert!(glob_match(b"a\\*b", b"a*b"));
+        assert!(!glob_match(b"a\\*b", b"axb"));
+    }
+
+    #[test]
+    fn filter_ignores_subscribe_confirmations() {
+        let filter = MessageFilter {
+            ignore_subscribe_messages: true,
+            ..MessageFilter::default()
+        };
+        let confirm = RespValue::Array(vec![bulk("subscribe"), bulk("news"), RespValue::Integer(1)]);
+        assert!(!filter.accepts(&confirm));
+        assert!(filter.accepts(&resp2(&["message", "news", "hi"])));
+        assert!(MessageFilter::default().accepts(&confirm));
+    }
+
+    #[test]
+    fn filter_by_channel_pattern() {
+        let filter = MessageFilter {
+            ignore_subscribe_messages: false,
+            channel_patterns: vec![b"orders.*".to_vec(), b"alerts".to_vec()],
+        };
+        assert!(filter.accepts(&resp2(&["message", "orders.eu", "1"])));
+        assert!(filter.accepts(&resp2(&["message", "alerts", "1"])));
+        assert!(!filter.accepts(&resp2(&["message", "metrics", "1"])));
+        // Confirmations and unknown frames are not subject to the pattern
+        assert!(filter.accepts(&RespValue::Array(vec![bulk("subscribe"), bulk("metrics"), RespValue::Integer(1)])));
+        assert!(filter.accepts(&RespValue::Integer(5)));
+    }
+}
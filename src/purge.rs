@@ -0,0 +1,205 @@
+//! TTL-based key housekeeping.
+//!
+//! `Redis.purge_expiring()` walks the keyspace with `SCAN MATCH` (every
+//! master in cluster mode) and unlinks the keys whose remaining TTL is
+//! below a threshold, or that have no TTL at all. Each SCAN page costs
+//! two pipelined round trips, one `PTTL` and one `UNLINK` per key, grouped
+//! by hash slot, and the job pauses between pages so it can run against a
+//! production instance without hogging it.
+//!
+//! `Redis.refresh_ttl_if_below()` is the other side of the same
+//! housekeeping: it extends the TTL of the given keys that are close to
+//! expiring, the building block of probabilistic early expiration for
+//! caches. The check and the `EXPIRE` run together in one Lua script, so
+//! a key cannot expire or be refreshed by another client in between. The
+//! script gets every key in one `EVALSHA`, or one per hash slot in
+//! cluster mode, sent as a single pipeline.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+
+use crate::client::per_key_integers;
+use crate::error::{PyrsedisError, Result};
+use crate::resp::parser::parse;
+use crate::resp::types::RespValue;
+use crate::router::cluster::slot_groups;
+use crate::router::{check_reply, ClientRouter, Router};
+use crate::scan::{scan_args, ScanKind};
+use crate::sha1::sha1_hex;
+
+/// Unlink the keys matching `pattern` whose TTL is below
+/// `older_than_ms` milliseconds or missing, scanning `count` keys per
+/// page and sleeping `pause` after each page. Returns how many keys were
+/// unlinked.
+///
+/// Keys that are not valid UTF-8 are skipped.
+pub async fn purge_expiring(
+    router: &ClientRouter,
+    pattern: &str,
+    older_than_ms: i64,
+    count: u64,
+    pause: Duration,
+) -> Result<u64> {
+    if count == 0 {
+        return Err(PyrsedisError::Type("count must be > 0".into()));
+    }
+    let mut unlinked = 0;
+    for node in router.scan_nodes() {
+        let mut cursor = 0;
+        loop {
+            let args = scan_args(ScanKind::Keys, None, cursor, Some(pattern), Some(count));
+            let refs: Vec<&str> = args.iter().map(String::as_str).collect();
+            let (next, keys) = scan_page(&router.execute_raw_at(node.as_deref(), &refs).await?)?;
+            if !keys.is_empty() {
+                let ttls = per_key_integers(router, "PTTL", &keys, &[], keys.len()).await?;
+                let doomed = expiring(keys, &ttls, older_than_ms);
+                if !doomed.is_empty() {
+                    let removed = per_key_integers(router, "UNLINK", &doomed, &[], doomed.len()).await?;
+                    unlinked += removed.iter().sum::<i64>() as u64;
+                }
+            }
+            cursor = next;
+            if cursor == 0 {
+                break;
+            }
+            tokio::time::sleep(pause).await;
+        }
+    }
+    Ok(unlinked)
+}
+
+/// Extend the keys in `KEYS` whose `PTTL` is below `ARGV[1]` to `ARGV[2]`
+/// seconds, and return how many were extended. Keys without a TTL or
+/// that do not exist are left alone.
+const REFRESH_SCRIPT: &str = "\
+local refreshed = 0
+for _, key in ipairs(KEYS) do
+  local ttl = redis.call('PTTL', key)
+  if ttl >= 0 and ttl < tonumber(ARGV[1]) then
+    redis.call('EXPIRE', key, ARGV[2])
+    refreshed = refreshed + 1
+  end
+end
+return refreshed";
+
+/// Set the TTL of those of `keys` with less than `min_ttl_ms`
+/// milliseconds left to `new_ttl` seconds. Returns how many keys were
+/// refreshed.
+///
+/// The script is sent with `EVALSHA`; the calls that fail with
+/// `NOSCRIPT` are sent again once it has been loaded on every master.
+pub async fn refresh_ttl_if_below(router: &ClientRouter, keys: &[String], min_ttl_ms: i64, new_ttl: u64) -> Result<u64> {
+    if new_ttl == 0 {
+        return Err(PyrsedisError::Type("new_ttl must be > 0".into()));
+    }
+    if keys.is_empty() {
+        return Ok(0);
+    }
+    let sha = sha1_hex(REFRESH_SCRIPT.as_bytes());
+    let commands = refresh_commands(keys, router.is_cluster(), &sha, min_ttl_ms, new_ttl);
+    let mut replies = router.pipeline(&commands).await?;
+    let missing: Vec<usize> = (0..replies.len()).filter(|&i| is_noscript(&replies[i])).collect();
+    if !missing.is_empty() {
+        let load = ["SCRIPT", "LOAD", REFRESH_SCRIPT];
+        for (_, reply) in router.execute_on_all_raw(&load).await {
+            check_reply(reply?, &load)?;
+        }
+        let retry: Vec<Vec<String>> = missing.iter().map(|&i| commands[i].clone()).collect();
+        for (i, reply) in missing.into_iter().zip(router.pipeline(&retry).await?) {
+            replies[i] = reply;
+        }
+    }
+    let mut refreshed = 0;
+    for reply in replies {
+        refreshed += match reply {
+            RespValue::Integer(n) => n as u64,
+            RespValue::Error(msg) => return Err(PyrsedisError::redis(msg)),
+            other => return Err(PyrsedisError::Protocol(format!("refresh script returned {}", other.type_name()))),
+        };
+    }
+    Ok(refreshed)
+}
+
+/// The `EVALSHA` calls of a refresh: one for all `keys`, or one per hash
+/// slot when `cluster`.
+fn refresh_commands(keys: &[String], cluster: bool, sha: &str, min_ttl_ms: i64, new_ttl: u64) -> Vec<Vec<String>> {
+    let groups: Vec<Vec<usize>> = if cluster {
+        let refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+        slot_groups(&refs).into_iter().map(|(_, group)| group).collect()
+    } else {
+        vec![(0..keys.len()).collect()]
+    };
+    groups
+        .into_iter()
+        .map(|group| {
+            let mut cmd = vec!["EVALSHA".to_string(), sha.to_string(), group.len().to_string()];
+            cmd.extend(group.into_iter().map(|idx| keys[idx].clone()));
+            cmd.extend([min_ttl_ms.to_string(), new_ttl.to_string()]);
+            cmd
+        })
+        .collect()
+}
+
+fn is_noscript(reply: &RespValue) -> bool {
+    matches!(reply, RespValue::Error(msg) if msg.starts_with("NOSCRIPT"))
+}
+
+/// Split a raw `SCAN` reply into the next cursor and the UTF-8 keys.
+fn scan_page(raw: &Bytes) -> Result<(u64, Vec<String>)> {
+    let malformed = || PyrsedisError::Protocol("malformed SCAN reply".into());
+    match parse(raw)?.0 {
+        RespValue::Array(page) if page.len() == 2 => {
+            let cursor = page[0].as_str().and_then(|c| c.parse().ok()).ok_or_else(malformed)?;
+            let RespValue::Array(keys) = &page[1] else { return Err(malformed()) };
+            Ok((cursor, keys.iter().filter_map(|k| k.as_str().map(str::to_string)).collect()))
+        }
+        RespValue::Error(msg) => Err(PyrsedisError::redis(msg)),
+        _ => Err(malformed()),
+    }
+}
+
+/// The keys whose `PTTL` reply in `ttls` is below `older_than_ms` or `-1`
+/// (no TTL). Keys that vanished since the scan (`-2`) are left out.
+fn expiring(keys: Vec<String>, ttls: &[i64], older_than_ms: i64) -> Vec<String> {
+    keys.into_iter()
+        .zip(ttls)
+        .filter(|&(_, &ttl)| ttl == -1 || (0..older_than_ms).contains(&ttl))
+        .map(|(key, _)| key)
+        .collect()
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_short_and_missing_ttls() {
+        let keys = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+        assert_eq!(expiring(keys, &[-1, -2, 0, 59_999, 60_000], 60_000), ["a", "c", "d"]);
+    }
+
+    #[test]
+    fn scan_pages_are_split() {
+        let raw = Bytes::from_static(b"*2\r\n$2\r\n17\r\n*2\r\n$1\r\na\r\n$2\r\n\xff\xfe\r\n");
+        assert_eq!(scan_page(&raw).unwrap(), (17, vec!["a".to_string()]));
+        assert!(matches!(scan_page(&Bytes::from_static(b"-ERR nope\r\n")), Err(PyrsedisError::Redis { .. })));
+        assert!(matches!(scan_page(&Bytes::from_static(b":1\r\n")), Err(PyrsedisError::Protocol(_))));
+    }
+
+    #[test]
+    fn refreshes_are_grouped_by_slot_in_cluster_mode() {
+        let keys = ["{a}1", "b", "{a}2"].map(String::from).to_vec();
+        assert_eq!(
+            refresh_commands(&keys, false, "abc", 5000, 60),
+            [["EVALSHA", "abc", "3", "{a}1", "b", "{a}2", "5000", "60"]]
+        );
+        let cluster = refresh_commands(&keys, true, "abc", 5000, 60);
+        assert_eq!(cluster[0], ["EVALSHA", "abc", "2", "{a}1", "{a}2", "5000", "60"]);
+        assert_eq!(cluster[1], ["EVALSHA", "abc", "1", "b", "5000", "60"]);
+        assert!(is_noscript(&RespValue::Error("NOSCRIPT No matching script".into())));
+        assert!(!is_noscript(&RespValue::Integer(0)));
+    }
+}
@@ -0,0 +1,240 @@
+//! Process-wide topology registry.
+//!
+//! An opt-in cache shared by every client in the process. Clients pointing
+//! at the same endpoints reuse resolved socket addresses and cluster slot
+//! maps instead of each running its own DNS lookups and `CLUSTER SLOTS`
+//! round-trips, which matters when many short-lived clients are created.
+//!
+//! The registry is disabled until [`configure`] is called. Entries expire
+//! after a TTL and the least recently used entry is evicted when a cache is
+//! full.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::error::Result;
+use crate::router::cluster::SlotMap;
+
+/// Default number of entries kept per cache.
+pub const DEFAULT_CAPACITY: usize = 256;
+
+/// Default entry lifetime.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(30);
+
+// ── LRU cache ─────────────────────────────────────────────────────
+
+struct Entry<V> {
+    value: V,
+    stored_at: Instant,
+    last_used: u64,
+}
+
+/// A small LRU cache with per-entry expiry.
+///
+/// Eviction scans for the oldest entry, which is cheap at the capacities
+/// the registry uses and keeps the structure a plain `HashMap`.
+pub(crate) struct Lru<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    capacity: usize,
+    ttl: Duration,
+    clock: u64,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Lru<K, V> {
+    pub(crate) fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity: capacity.max(1),
+            ttl,
+            clock: 0,
+        }
+    }
+
+    /// Return a copy of a fresh entry, dropping it if it has expired.
+    pub(crate) fn get(&mut self, key: &K) -> Option<V> {
+        let entry = self.entries.get_mut(key)?;
+        if entry.stored_at.elapsed() >= self.ttl {
+            self.entries.remove(key);
+            return None;
+        }
+        self.clock += 1;
+        entry.last_used = self.clock;
+        Some(entry.value.clone())
+    }
+
+    /// Insert or replace an entry, evicting the least recently used one
+    /// when full.
+    pub(crate) fn insert(&mut self, key: K, value: V) {
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.clock += 1;
+        self.entries.insert(
+            key,
+            Entry { value, stored_at: Instant::now(), last_used: self.clock },
+        );
+    }
+
+    pub(crate) fn remove(&mut self, key: &K) {
+        self.entries.remove(key);
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+// ── Registry ──────────────────────────────────────────────────────
+
+struct Registry {
+    /// "host:port" → resolved socket addresses.
+    addrs: Lru<String, Vec<SocketAddr>>,
+    /// Seed-set key (see [`seeds_key`]) → last known slot map.
+    slots: Lru<String, SlotMap>,
+}
+
+/// `None` while the registry is disabled.
+static REGISTRY: Mutex<Option<Registry>> = Mutex::new(None);
+
+/// Enable the registry with the given capacity and TTL, or disable it.
+///
+/// Reconfiguring drops every cached entry.
+pub fn configure(enabled: bool, capacity: usize, ttl: Duration) {
+    *REGISTRY.lock() = enabled.then(|| Registry {
+        addrs: Lru::new(capacity, ttl),
+        slots: Lru::new(capacity, ttl),
+    });
+}
+
+/// Whether the registry is enabled.
+pub fn is_enabled() -> bool {
+    REGISTRY.lock().is_some()
+}
+
+/// Resolve "host:port" to socket addresses, consulting the cache when the
+/// registry is enabled.
+pub async fn resolve(addr: &str) -> Result<Vec<SocketAddr>> {
+    if let Some(registry) = REGISTRY.lock().as_mut() {
+        if let Some(addrs) = registry.addrs.get(&addr.to_string()) {
+            return Ok(addrs);
+        }
+    }
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host(addr).await?.collect();
+    if !addrs.is_empty() {
+        if let Some(registry) = REGISTRY.lock().as_mut() {
+            registry.addrs.insert(addr.to_string(), addrs.clone());
+        }
+    }
+    Ok(addrs)
+}
+
+/// Drop the cached addresses for "host:port" (e.g. after a failed connect).
+pub fn forget_addr(addr: &str) {
+    if let Some(registry) = REGISTRY.lock().as_mut() {
+        registry.addrs.remove(&addr.to_string());
+    }
+}
+
+/// Canonical registry key for a set of cluster seed nodes.
+///
+/// Order-insensitive, so clients listing the same seeds differently share
+/// an entry.
+pub fn seeds_key(seeds: &[(String, u16)]) -> String {
+    let mut addrs: Vec<String> = seeds.iter().map(|(h, p)| format!("{h}:{p}")).collect();
+    addrs.sort();
+    addrs.dedup();
+    addrs.join(",")
+}
+
+/// Look up a shared slot map.
+pub(crate) fn slot_map(key: &str) -> Option<SlotMap> {
+    REGISTRY.lock().as_mut()?.slots.get(&key.to_string())
+}
+
+/// Publish a freshly fetched slot map.
+pub(crate) fn store_slot_map(key: &str, map: &SlotMap) {
+    if let Some(registry) = REGISTRY.lock().as_mut() {
+        registry.slots.insert(key.to_string(), map.clone());
+    }
+}
+
+/// Enable the registry with default settings unless it already is, without
+/// dropping entries other tests may rely on.
+#[cfg(test)]
+pub(crate) fn ensure_enabled() {
+    REGISTRY.lock().get_or_insert_with(|| Registry {
+        addrs: Lru::new(DEFAULT_CAPACITY, DEFAULT_TTL),
+        slots: Lru::new(DEFAULT_CAPACITY, DEFAULT_TTL),
+    });
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let mut lru = Lru::new(2, Duration::from_secs(60));
+        lru.insert("a", 1);
+        lru.insert("b", 2);
+        assert_eq!(lru.get(&"a"), Some(1)); // "b" is now oldest
+        lru.insert("c", 3);
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.get(&"b"), None);
+        assert_eq!(lru.get(&"a"), Some(1));
+        assert_eq!(lru.get(&"c"), Some(3));
+    }
+
+    #[test]
+    fn lru_replacing_does_not_evict() {
+        let mut lru = Lru::new(2, Duration::from_secs(60));
+        lru.insert("a", 1);
+        lru.insert("b", 2);
+        lru.insert("a", 10);
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.get(&"a"), Some(10));
+        assert_eq!(lru.get(&"b"), Some(2));
+    }
+
+    #[test]
+    fn lru_entries_expire() {
+        let mut lru = Lru::new(4, Duration::ZERO);
+        lru.insert("a", 1);
+        assert_eq!(lru.get(&"a"), None);
+        assert_eq!(lru.len(), 0);
+    }
+
+    #[test]
+    fn seeds_key_is_order_insensitive() {
+        let a = seeds_key(&[("n2".into(), 7001), ("n1".into(), 7000)]);
+        let b = seeds_key(&[("n1".into(), 7000), ("n2".into(), 7001), ("n1".into(), 7000)]);
+        assert_eq!(a, "n1:7000,n2:7001");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn resolve_caches_when_enabled() {
+        ensure_enabled();
+        let addrs = crate::runtime::block_on(resolve("127.0.0.1:1")).unwrap();
+        assert_eq!(addrs, vec!["127.0.0.1:1".parse().unwrap()]);
+        let cached = REGISTRY.lock().as_mut().unwrap().addrs.get(&"127.0.0.1:1".to_string());
+        assert_eq!(cached, Some(addrs));
+        forget_addr("127.0.0.1:1");
+        let cached = REGISTRY.lock().as_mut().unwrap().addrs.get(&"127.0.0.1:1".to_string());
+        assert_eq!(cached, None);
+    }
+}
@@ -0,0 +1,213 @@
+//! Manual slot migration.
+//!
+//! `Redis.migrate_slot()` moves one hash slot between two masters the way
+//! `redis-cli --cluster reshard` does: the target is marked `IMPORTING`
+//! and the source `MIGRATING`, the keys are moved with `MIGRATE` a batch
+//! at a time, and both nodes are then told that the target owns the slot.
+//! Clients are sent to the target with `ASK` while keys are in flight, so
+//! the slot stays usable throughout. A failed batch leaves the slot
+//! migrating; calling `migrate_slot` again picks up where it stopped.
+//!
+//! `Redis.cluster_failover()` promotes a replica for node maintenance;
+//! draining the master first (`Redis.drain_node()`) keeps reads off it
+//! meanwhile.
+
+use bytes::Bytes;
+
+use crate::crc16::SLOT_COUNT;
+use crate::error::{PyrsedisError, Result};
+use crate::resp::parser::parse;
+use crate::resp::types::RespValue;
+use crate::response::{resp_to_bytes, resp_to_string};
+use crate::router::{check_reply, ClientRouter, Route};
+
+/// Reject a slot outside `0..16384`.
+pub fn check_slot(slot: u16) -> Result<()> {
+    if slot >= SLOT_COUNT {
+        return Err(PyrsedisError::Type(format!("slot must be in 0..{SLOT_COUNT}, got {slot}")));
+    }
+    Ok(())
+}
+
+/// `CLUSTER SETSLOT slot state [node_id]`. `IMPORTING`, `MIGRATING` and
+/// `NODE` name a node; `STABLE` does not.
+pub fn setslot_args(slot: u16, state: &str, node_id: Option<&str>) -> Result<Vec<String>> {
+    check_slot(slot)?;
+    let flag = state.to_ascii_uppercase();
+    let needs_node = match flag.as_str() {
+        "IMPORTING" | "MIGRATING" | "NODE" => true,
+        "STABLE" => false,
+        _ => {
+            return Err(PyrsedisError::Type(format!(
+                "unknown slot state {state:?} (expected 'importing', 'migrating', 'node' or 'stable')"
+            )));
+        }
+    };
+    let mut cmd = vec!["CLUSTER".to_string(), "SETSLOT".to_string(), slot.to_string(), flag];
+    match (needs_node, node_id) {
+        (true, Some(id)) => cmd.push(id.to_string()),
+        (true, None) => return Err(PyrsedisError::Type(format!("slot state {state:?} needs a node_id"))),
+        (false, Some(_)) => return Err(PyrsedisError::Type("slot state 'stable' takes no node_id".into())),
+        (false, None) => {}
+    }
+    Ok(cmd)
+}
+
+/// `CLUSTER FAILOVER`, with `FORCE` or `TAKEOVER`.
+pub fn failover_args(force: bool, takeover: bool) -> Result<Vec<String>> {
+    let mut cmd = vec!["CLUSTER".to_string(), "FAILOVER".to_string()];
+    match (force, takeover) {
+        (true, true) => return Err(PyrsedisError::Type("force and takeover are mutually exclusive".into())),
+        (true, false) => cmd.push("FORCE".into()),
+        (false, true) => cmd.push("TAKEOVER".into()),
+        (false, false) => {}
+    }
+    Ok(cmd)
+}
+
+/// Host and port of a `host:port` node address.
+fn split_addr(addr: &str) -> Result<(&str, &str)> {
+    match addr.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => Ok((host, port)),
+        _ => Err(PyrsedisError::Type(format!("node address must be 'host:port', got {addr:?}"))),
+    }
+}
+
+/// Validate the arguments of a migration before anything is sent.
+pub fn check_migration(slot: u16, from: &str, to: &str, batch: usize) -> Result<()> {
+    check_slot(slot)?;
+    split_addr(from)?;
+    split_addr(to)?;
+    if from == to {
+        return Err(PyrsedisError::Type("from_node and to_node must differ".into()));
+    }
+    if batch == 0 {
+        return Err(PyrsedisError::Type("batch must be > 0".into()));
+    }
+    Ok(())
+}
+
+/// `MIGRATE host port "" 0 timeout KEYS key ...`, moving `keys` to the
+/// node at `to`.
+pub fn migrate_args(to: &str, timeout_ms: u64, keys: &[Bytes]) -> Result<Vec<Vec<u8>>> {
+    let (host, port) = split_addr(to)?;
+    let mut cmd: Vec<Vec<u8>> = vec![
+        b"MIGRATE".to_vec(),
+        host.into(),
+        port.into(),
+        Vec::new(),
+        b"0".to_vec(),
+        timeout_ms.to_string().into_bytes(),
+        b"KEYS".to_vec(),
+    ];
+    cmd.extend(keys.iter().map(|key| key.to_vec()));
+    Ok(cmd)
+}
+
+/// Run `args` on the node at `addr` and parse its reply.
+async fn on_node<A: AsRef<[u8]>>(router: &ClientRouter, addr: &str, args: &[A]) -> Result<RespValue> {
+    let refs: Vec<&[u8]> = args.iter().map(AsRef::as_ref).collect();
+    let raw = router.execute_raw_bytes_at(Some(&Route::Node(addr.to_string())), &refs).await?;
+    Ok(parse(&check_reply(raw, args)?)?.0)
+}
+
+/// The cluster ID of the node at `addr`.
+async fn node_id(router: &ClientRouter, addr: &str) -> Result<String> {
+    let reply = on_node(router, addr, &["CLUSTER", "MYID"]).await?;
+    resp_to_string(&reply)
+        .ok_or_else(|| PyrsedisError::Protocol(format!("unexpected CLUSTER MYID reply: {}", reply.type_name())))
+}
+
+/// Mark `slot` as moving from `from` to `to`, and return the target's ID.
+///
+/// The target starts importing before the source starts migrating, so
+/// an `ASK` redirect never reaches a node that would refuse it.
+pub async fn begin(router: &ClientRouter, slot: u16, from: &str, to: &str) -> Result<String> {
+    let from_id = node_id(router, from).await?;
+    let to_id = node_id(router, to).await?;
+    on_node(router, to, &setslot_args(slot, "importing", Some(&from_id))?).await?;
+    on_node(router, from, &setslot_args(slot, "migrating", Some(&to_id))?).await?;
+    Ok(to_id)
+}
+
+/// Move up to `batch` keys of `slot` from `from` to `to` with one
+/// `MIGRATE`. Returns how many were moved; `0` once none are left.
+pub async fn migrate_batch(
+    router: &ClientRouter,
+    slot: u16,
+    from: &str,
+    to: &str,
+    batch: usize,
+    timeout_ms: u64,
+) -> Result<usize> {
+    let cmd = ["CLUSTER".to_string(), "GETKEYSINSLOT".to_string(), slot.to_string(), batch.to_string()];
+    let keys: Vec<Bytes> = match on_node(router, from, &cmd).await? {
+        RespValue::Array(keys) => keys.iter().filter_map(resp_to_bytes).collect(),
+        other => {
+            return Err(PyrsedisError::Protocol(format!(
+                "unexpected CLUSTER GETKEYSINSLOT reply: {}",
+                other.type_name()
+            )));
+        }
+    };
+    if keys.is_empty() {
+        return Ok(0);
+    }
+    on_node(router, from, &migrate_args(to, timeout_ms, &keys)?).await?;
+    Ok(keys.len())
+}
+
+/// Hand `slot` to the node `to_id`: the target learns first, so it
+/// serves the slot before the source starts redirecting to it. The other
+/// masters learn through the cluster bus.
+pub async fn finish(router: &ClientRouter, slot: u16, from: &str, to: &str, to_id: &str) -> Result<()> {
+    let cmd = setslot_args(slot, "node", Some(to_id))?;
+    on_node(router, to, &cmd).await?;
+    on_node(router, from, &cmd).await?;
+    Ok(())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn setslot_arguments() {
+        assert_eq!(
+            setslot_args(7, "importing", Some("abc")).unwrap(),
+            ["CLUSTER", "SETSLOT", "7", "IMPORTING", "abc"]
+        );
+        assert_eq!(setslot_args(7, "STABLE", None).unwrap(), ["CLUSTER", "SETSLOT", "7", "STABLE"]);
+        assert!(setslot_args(7, "node", None).is_err());
+        assert!(setslot_args(7, "stable", Some("abc")).is_err());
+        assert!(setslot_args(7, "moving", Some("abc")).is_err());
+        assert!(setslot_args(SLOT_COUNT, "stable", None).is_err());
+    }
+
+    #[test]
+    fn failover_arguments() {
+        assert_eq!(failover_args(false, false).unwrap(), ["CLUSTER", "FAILOVER"]);
+        assert_eq!(failover_args(true, false).unwrap(), ["CLUSTER", "FAILOVER", "FORCE"]);
+        assert_eq!(failover_args(false, true).unwrap(), ["CLUSTER", "FAILOVER", "TAKEOVER"]);
+        assert!(failover_args(true, true).is_err());
+    }
+
+    #[test]
+    fn migrations_are_checked() {
+        assert!(check_migration(0, "10.0.0.1:6379", "10.0.0.2:6379", 100).is_ok());
+        assert!(check_migration(0, "10.0.0.1:6379", "10.0.0.1:6379", 100).is_err());
+        assert!(check_migration(0, "10.0.0.1:6379", "10.0.0.2:6379", 0).is_err());
+        assert!(check_migration(0, "10.0.0.1", "10.0.0.2:6379", 100).is_err());
+        assert!(check_migration(SLOT_COUNT, "10.0.0.1:6379", "10.0.0.2:6379", 100).is_err());
+    }
+
+    #[test]
+    fn migrate_arguments() {
+        let keys = [Bytes::from_static(b"a"), Bytes::from_static(b"b")];
+        let cmd = migrate_args("::1:7001", 5000, &keys).unwrap();
+        let expected: [&[u8]; 9] = [b"MIGRATE", b"::1", b"7001", b"", b"0", b"5000", b"KEYS", b"a", b"b"];
+        assert_eq!(cmd, expected);
+    }
+}
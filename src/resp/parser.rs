@@ -61,6 +61,20 @@ pub fn parse_slice(buf: &[u8]) -> Result<(RespValue, usize)> {
 /// This is used by `read_raw_response` to determine where a RESP message
 /// ends without materializing the parsed value.
 pub fn resp_frame_len(buf: &[u8]) -> Result<usize> {
+    frame_len_with(buf, &mut |_| {})
+}
+
+/// Like [`resp_frame_len`], also appending the element count of every
+/// aggregate (array, set, push, map, attribute) to `counts` in pre-order.
+///
+/// Null arrays are skipped. Map and attribute counts are in pairs.
+pub fn resp_frame_shape(buf: &[u8], counts: &mut Vec<usize>) -> Result<usize> {
+    frame_len_with(buf, &mut |count| counts.push(count))
+}
+
+/// Shared walk behind [`resp_frame_len`] and [`resp_frame_shape`],
+/// reporting each aggregate's count to `on_aggregate` before its children.
+fn frame_len_with(buf: &[u8], on_aggregate: &mut impl FnMut(usize)) -> Result<usize> {
     if buf.is_empty() {
         return Err(PyrsedisError::Incomplete);
     }
@@ -105,8 +119,9 @@ pub fn resp_frame_len(buf: &[u8]) -> Result<usize> {
             if count < 0 {
                 return Ok(next); // *-1\r\n  null array
             }
+            on_aggregate(count as usize);
             for _ in 0..count {
-                let child_len = resp_frame_len(&buf[next..])?;
+                let child_len = frame_len_with(&buf[next..], on_aggregate)?;
                 next += child_len;
             }
             Ok(next)
@@ -119,10 +134,11 @@ pub fn resp_frame_len(buf: &[u8]) -> Result<usize> {
                 return Err(PyrsedisError::Protocol("negative map count".into()));
             }
             let count = count as usize;
+            on_aggregate(count);
             for _ in 0..count {
-                let k_len = resp_frame_len(&buf[next..])?;
+                let k_len = frame_len_with(&buf[next..], on_aggregate)?;
                 next += k_len;
-                let v_len = resp_frame_len(&buf[next..])?;
+                let v_len = frame_len_with(&buf[next..], on_aggregate)?;
                 next += v_len;
             }
             Ok(next)
@@ -135,14 +151,15 @@ pub fn resp_frame_len(buf: &[u8]) -> Result<usize> {
                 return Err(PyrsedisError::Protocol("negative attribute count".into()));
             }
             let count = count as usize;
+            on_aggregate(count);
             for _ in 0..count {
-                let k_len = resp_frame_len(&buf[next..])?;
+                let k_len = frame_len_with(&buf[next..], on_aggregate)?;
                 next += k_len;
-                let v_len = resp_frame_len(&buf[next..])?;
+                let v_len = frame_len_with(&buf[next..], on_aggregate)?;
                 next += v_len;
             }
             // Plus one more value (the actual data)
-            let data_len = resp_frame_len(&buf[next..])?;
+            let data_len = frame_len_with(&buf[next..], on_aggregate)?;
             next += data_len;
             Ok(next)
         }
@@ -216,8 +233,14 @@ fn parse_int_from_bytes(bytes: &[u8]) -> Result<i64> {
             .ok_or_else(|| PyrsedisError::Protocol("integer overflow".into()))?;
     }
 
-    // n is always <= 0 here. Negate for positive numbers.
-    Ok(if negative { n } else { -n })
+    // n is always <= 0 here. Negate for positive numbers (i64::MIN has no
+    // positive counterpart, so that negation must be checked).
+    if negative {
+        Ok(n)
+    } else {
+        n.checked_neg()
+            .ok_or_else(|| PyrsedisError::Protocol("integer overflow".into()))
+    }
 }
 
 // ── Type parsers ──────────────────────────────────────────────────
@@ -809,8 +832,8 @@ mod tests {
 
     #[test]
     fn double_positive() {
-        let (val, _) = parse_slice(b",3.14\r\n").unwrap();
-        assert_eq!(val, RespValue::Double(3.14));
+        let (val, _) = parse_slice(b",1.25\r\n").unwrap();
+        assert_eq!(val, RespValue::Double(1.25));
     }
 
     #[test]
@@ -1145,4 +1168,16 @@ mod tests {
     fn integer_sign_only() {
         assert!(parse_slice(b":-\r\n").is_err());
     }
+
+    // ── frame shape ──
+
+    #[test]
+    fn frame_shape_records_aggregates_in_pre_order() {
+        let input = b"*3\r\n%1\r\n+k\r\n*2\r\n:1\r\n:2\r\n*-1\r\n~0\r\n";
+        let mut counts = Vec::new();
+        let len = resp_frame_shape(input, &mut counts).unwrap();
+        assert_eq!(len, input.len());
+        assert_eq!(len, resp_frame_len(input).unwrap());
+        assert_eq!(counts, [3, 1, 2, 0]);
+    }
 }
@@ -194,7 +194,7 @@ mod tests {
     #[test]
     fn as_str_other_types() {
         assert_eq!(RespValue::Integer(42).as_str(), None);
-        assert_eq!(RespValue::Double(3.14).as_str(), None);
+        assert_eq!(RespValue::Double(1.25).as_str(), None);
         assert_eq!(RespValue::Boolean(true).as_str(), None);
         assert_eq!(RespValue::Null.as_str(), None);
         assert_eq!(RespValue::Array(vec![]).as_str(), None);
@@ -252,7 +252,7 @@ mod tests {
 
     #[test]
     fn as_f64_double() {
-        assert_eq!(RespValue::Double(3.14).as_f64(), Some(3.14));
+        assert_eq!(RespValue::Double(1.25).as_f64(), Some(1.25));
     }
 
     #[test]
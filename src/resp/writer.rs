@@ -47,6 +47,18 @@ pub fn encode_command_str(args: &[&str]) -> Vec<u8> {
     encode_command(&byte_args)
 }
 
+/// Number of bytes [`encode_command`] produces for `args`, without
+/// encoding them.
+pub fn encoded_len<A: AsRef<[u8]>>(args: &[A]) -> usize {
+    let mut itoa_buf = Buffer::new();
+    let mut len = 1 + itoa_buf.format(args.len()).len() + 2;
+    for arg in args {
+        let n = arg.as_ref().len();
+        len += 1 + itoa_buf.format(n).len() + 2 + n + 2;
+    }
+    len
+}
+
 /// Encode multiple commands into a single buffer for pipelined writes.
 ///
 /// This avoids N allocations + N syscalls — everything is concatenated
@@ -245,4 +257,13 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn encoded_len_matches_encoding() {
+        let big = vec![b'x'; 12_345];
+        let cases: [&[&[u8]]; 4] = [&[], &[b"PING"], &[b"SET", b"key", b""], &[b"SET", b"key", &big]];
+        for args in cases {
+            assert_eq!(encoded_len(args), encode_command(args).len());
+        }
+    }
 }
@@ -8,11 +8,13 @@
 
 use bytes::Bytes;
 use crate::error::PyrsedisError;
+use crate::resp::parser::resp_frame_shape;
 use crate::resp::types::RespValue;
 
 use memchr::memchr;
 use pyo3::prelude::*;
-use pyo3::types::{PyBool, PyBytes, PyDict, PyFloat, PyList, PySet, PyString};
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyBool, PyBytes, PyDict, PyFloat, PyList, PySet, PyString, PyTuple, PyType};
 
 /// Maximum number of elements allowed in a single RESP array/set/map/push.
 ///
@@ -33,64 +35,162 @@ const MAX_PARSE_DEPTH: usize = 512;
 /// large numbers. Cap at 10,000 digits to prevent CPU DoS.
 const MAX_BIGNUMBER_LEN: usize = 10_000;
 
-/// Build a Python list of `count` elements in-place using CPython FFI.
+/// Replies whose top-level aggregate has at least this many elements get
+/// their [`FrameShape`] scanned off-GIL before conversion.
+pub const PRESIZE_THRESHOLD: usize = 65_536;
+
+extern "C" {
+    // Exported from `cpython/dictobject.h` on every supported CPython, but
+    // not bound by pyo3-ffi.
+    fn _PyDict_NewPresized(minused: pyo3::ffi::Py_ssize_t) -> *mut pyo3::ffi::PyObject;
+}
+
+/// Element counts of every aggregate in one reply frame, in pre-order.
+///
+/// Computed by a cheap structural pass over the raw bytes (see
+/// [`resp_frame_shape`]) that needs no Python objects, so it runs with the
+/// GIL released. The GIL-held build then creates maps with their hash
+/// tables already sized for the verified pair count instead of rehashing
+/// as they grow.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrameShape {
+    counts: Vec<usize>,
+}
+
+impl FrameShape {
+    /// Scan `buf` if its top-level aggregate holds at least
+    /// [`PRESIZE_THRESHOLD`] elements.
+    ///
+    /// Returns `None` for smaller or non-aggregate replies, and for
+    /// malformed frames — the regular parse reports those errors.
+    pub fn scan(buf: &[u8]) -> Option<Self> {
+        if !matches!(buf.first(), Some(b'*' | b'%' | b'~' | b'>' | b'|')) {
+            return None;
+        }
+        let (line, _) = fused_read_line(buf, 1).ok()?;
+        let count = usize::try_from(fused_parse_int(line).ok()?).ok()?;
+        if count < PRESIZE_THRESHOLD {
+            return None;
+        }
+        let mut counts = Vec::new();
+        resp_frame_shape(buf, &mut counts).ok()?;
+        Some(Self { counts })
+    }
+}
+
+/// Walks a [`FrameShape`] in step with the parser, one count per aggregate.
+#[derive(Default)]
+struct ShapeCursor<'a> {
+    counts: std::slice::Iter<'a, usize>,
+}
+
+impl<'a> ShapeCursor<'a> {
+    fn new(shape: Option<&'a FrameShape>) -> Self {
+        Self { counts: shape.map(|s| s.counts.iter()).unwrap_or_default() }
+    }
+
+    /// Verified element count of the aggregate being entered, if scanned.
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        self.counts.next().copied()
+    }
+}
+
+/// Create an empty dict, pre-sized for `presize` entries when known.
+#[inline]
+fn new_dict(py: Python<'_>, presize: Option<usize>) -> PyResult<Bound<'_, PyDict>> {
+    let Some(n) = presize else {
+        return Ok(PyDict::new(py));
+    };
+    // SAFETY: `_PyDict_NewPresized` returns a new reference to an empty dict
+    // (or NULL with an exception set).
+    unsafe {
+        let ptr = _PyDict_NewPresized(n as pyo3::ffi::Py_ssize_t);
+        if ptr.is_null() {
+            return Err(PyErr::fetch(py));
+        }
+        Ok(Bound::from_owned_ptr(py, ptr).cast_into_unchecked())
+    }
+}
+
+/// Build a Python list of `count` elements in-place using CPython FFI,
+/// producing element `i` with `item(i)`.
 ///
 /// Uses `PyList_New` (pre-sized) + `PyList_SET_ITEM` (steals references),
 /// eliminating the intermediate `Vec<Py<PyAny>>` that `PyList::new` requires.
-/// For graph results with millions of small (2-4 element) arrays, this removes
-/// tens of MB of heap allocation + deallocation.
+/// For graph results with millions of small (2-4 element) arrays, or
+/// pipelines with millions of replies, this removes tens of MB of heap
+/// allocation + deallocation.
 ///
 /// # Safety
-/// - All items are parsed via `parse_inner` which produces valid `Py<PyAny>`.
+/// - `item` returns valid `Py<PyAny>` values.
 /// - `PyList_SET_ITEM` steals the reference from `into_ptr()`.
 /// - On error, remaining slots are filled with `Py_None` so the list is valid
 ///   for `Py_DECREF` cleanup.
+/// - `item` may release the GIL: until it is returned the list is reachable
+///   only from here, and the garbage collector skips its unfilled slots.
 ///
 /// # Refcount invariants (VULN-07 documentation)
 /// - `PyList_New` returns a new reference (refcount=1 on the list).
 /// - `PyList_SET_ITEM` **steals** the reference from `item.into_ptr()`,
-///   so no extra IncRef is needed for successfully parsed items.
+///   so no extra IncRef is needed for successfully produced items.
 /// - On error at slot `i`: slots `0..i` already have stolen refs (owned by
 ///   the list). We fill slot `i` and remaining slots `i+1..count` with
 ///   `Py_None` (IncRef'd before SET_ITEM steals it). Then `Py_DecRef(list_ptr)`
 ///   drops the list, which decrefs all `count` items (valid refs or None).
 #[inline]
-unsafe fn build_pylist_ffi(
+pub(crate) fn build_pylist(
     py: Python<'_>,
-    buf: &[u8],
-    mut pos: usize,
     count: usize,
-    depth: usize,
-    decode: bool,
-) -> PyResult<(Py<PyAny>, usize)> {
-    let list_ptr = pyo3::ffi::PyList_New(count as isize);
-    if list_ptr.is_null() {
-        return Err(PyErr::fetch(py));
-    }
+    mut item: impl FnMut(usize) -> PyResult<Py<PyAny>>,
+) -> PyResult<Py<PyAny>> {
+    // SAFETY: see the invariants above
+    unsafe {
+        let list_ptr = pyo3::ffi::PyList_New(count as isize);
+        if list_ptr.is_null() {
+            return Err(PyErr::fetch(py));
+        }
 
-    for i in 0..count {
-        match parse_inner(py, buf, pos, depth, decode) {
-            Ok((item, end)) => {
-                pos = end;
-                pyo3::ffi::PyList_SET_ITEM(list_ptr, i as isize, item.into_ptr());
-            }
-            Err(e) => {
-                // Fill remaining slots with None so the list is valid for cleanup
-                let none = pyo3::ffi::Py_None();
-                pyo3::ffi::Py_IncRef(none);
-                pyo3::ffi::PyList_SET_ITEM(list_ptr, i as isize, none);
-                for j in (i + 1)..count {
-                    let none = pyo3::ffi::Py_None();
-                    pyo3::ffi::Py_IncRef(none);
-                    pyo3::ffi::PyList_SET_ITEM(list_ptr, j as isize, none);
+        for i in 0..count {
+            match item(i) {
+                Ok(item) => {
+                    pyo3::ffi::PyList_SET_ITEM(list_ptr, i as isize, item.into_ptr());
+                }
+                Err(e) => {
+                    // Fill remaining slots with None so the list is valid for cleanup
+                    for j in i..count {
+                        let none = pyo3::ffi::Py_None();
+                        pyo3::ffi::Py_IncRef(none);
+                        pyo3::ffi::PyList_SET_ITEM(list_ptr, j as isize, none);
+                    }
+                    pyo3::ffi::Py_DecRef(list_ptr);
+                    return Err(e);
                 }
-                pyo3::ffi::Py_DecRef(list_ptr);
-                return Err(e);
             }
         }
+
+        Ok(Bound::from_owned_ptr(py, list_ptr).unbind())
     }
+}
 
-    Ok((Bound::from_owned_ptr(py, list_ptr).unbind(), pos))
+/// Parse `count` consecutive frames starting at `pos` into a pre-sized
+/// Python list (see [`build_pylist`]).
+#[inline]
+fn parse_pylist(
+    py: Python<'_>,
+    buf: &[u8],
+    mut pos: usize,
+    count: usize,
+    depth: usize,
+    opts: ParseOptions,
+    shape: &mut ShapeCursor<'_>,
+) -> PyResult<(Py<PyAny>, usize)> {
+    let list = build_pylist(py, count, |_| {
+        let (item, end) = parse_inner(py, buf, pos, depth, opts, shape)?;
+        pos = end;
+        Ok(item)
+    })?;
+    Ok((list, pos))
 }
 
 /// Convert a `RespValue` to a Python object, consuming the value.
@@ -299,11 +399,151 @@ pub fn resp_to_bool(value: &RespValue) -> Option<bool> {
     }
 }
 
+/// Convert a `RespValue` to an optional f64.
+pub fn resp_to_f64(value: &RespValue) -> Option<f64> {
+    match value {
+        RespValue::Double(d) => Some(*d),
+        RespValue::Integer(i) => Some(*i as f64),
+        RespValue::SimpleString(s) => s.parse().ok(),
+        RespValue::BulkString(b) => std::str::from_utf8(b).ok().and_then(|s| s.parse().ok()),
+        _ => None,
+    }
+}
+
+/// Parse one numeric reply element; nil becomes NaN.
+fn float_or_nan(value: &RespValue) -> Result<f64, PyrsedisError> {
+    if matches!(value, RespValue::Null) {
+        return Ok(f64::NAN);
+    }
+    resp_to_f64(value).ok_or_else(|| {
+        PyrsedisError::Type(format!("value is not a valid float: {value:?}"))
+    })
+}
+
+/// Parse an array reply (e.g. MGET) into floats, mapping nil to NaN.
+pub fn resp_to_f64_vec(value: &RespValue) -> Result<Vec<f64>, PyrsedisError> {
+    match value {
+        RespValue::Array(items) => items.iter().map(float_or_nan).collect(),
+        RespValue::Error(msg) => Err(PyrsedisError::redis(msg.clone())),
+        other => Err(PyrsedisError::Type(format!("expected array, got {}", other.type_name()))),
+    }
+}
+
+/// Parse a hash reply (HGETALL, RESP2 flat array or RESP3 map) into
+/// field names and float values.
+pub fn resp_to_f64_map(value: &RespValue) -> Result<(Vec<String>, Vec<f64>), PyrsedisError> {
+    let field = |v: &RespValue| {
+        resp_to_string(v).ok_or_else(|| PyrsedisError::Type("hash field is not valid UTF-8".into()))
+    };
+    let mut fields = Vec::new();
+    let mut values = Vec::new();
+    match value {
+        RespValue::Array(items) => {
+            fields.reserve(items.len() / 2);
+            values.reserve(items.len() / 2);
+            for pair in items.chunks_exact(2) {
+                fields.push(field(&pair[0])?);
+                values.push(float_or_nan(&pair[1])?);
+            }
+        }
+        RespValue::Map(pairs) => {
+            fields.reserve(pairs.len());
+            values.reserve(pairs.len());
+            for (k, v) in pairs {
+                fields.push(field(k)?);
+                values.push(float_or_nan(v)?);
+            }
+        }
+        RespValue::Error(msg) => return Err(PyrsedisError::redis(msg.clone())),
+        other => return Err(PyrsedisError::Type(format!("expected hash, got {}", other.type_name()))),
+    }
+    Ok((fields, values))
+}
+
 /// Check if a RESP response is an "OK" acknowledgment.
 pub fn is_ok_response(value: &RespValue) -> bool {
     matches!(value, RespValue::SimpleString(s) if s == "OK")
 }
 
+// ── RESP3 fidelity types ──────────────────────────────────────────
+
+/// Options for converting raw replies to Python objects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Decode bulk strings to `str` (non-UTF-8 data stays `bytes`).
+    pub decode: bool,
+    /// Return RESP3 verbatim strings as [`Verbatim`] and big numbers as
+    /// `BigNumber` instead of plain `str` / `int`.
+    pub resp3_types: bool,
+}
+
+/// A RESP3 verbatim string, keeping its three-letter encoding.
+///
+/// Compares equal to other ``Verbatim`` values with the same encoding and
+/// text; ``str(v)`` returns the text.
+#[pyclass(name = "Verbatim", frozen, eq, hash, skip_from_py_object)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Verbatim {
+    /// Format of the text, e.g. ``"txt"`` or ``"mkd"``.
+    #[pyo3(get)]
+    pub encoding: String,
+    /// The string payload.
+    #[pyo3(get)]
+    pub text: String,
+}
+
+#[pymethods]
+impl Verbatim {
+    #[new]
+    fn new(encoding: String, text: String) -> Self {
+        Self { encoding, text }
+    }
+
+    fn __str__(&self) -> &str {
+        &self.text
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Verbatim(encoding={:?}, text={:?})", self.encoding, self.text)
+    }
+}
+
+static BIG_NUMBER: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+
+/// The ``BigNumber`` type: an ``int`` subclass whose ``raw`` attribute
+/// holds the digits exactly as the server sent them.
+///
+/// Python cannot subclass ``int`` from a ``#[pyclass]``, so the type is
+/// defined in Python source and created once per interpreter.
+pub fn big_number_type(py: Python<'_>) -> PyResult<&Bound<'_, PyType>> {
+    BIG_NUMBER
+        .get_or_try_init(py, || {
+            let globals = PyDict::new(py);
+            py.run(
+                c"class BigNumber(int):
+    \"\"\"A RESP3 big number: an ``int`` that keeps the server's text in ``raw``.\"\"\"
+
+    __module__ = 'pyrsedis'
+
+    def __new__(cls, raw):
+        self = super().__new__(cls, raw)
+        self.raw = raw
+        return self
+
+    def __repr__(self):
+        return f'BigNumber({self.raw!r})'
+",
+                Some(&globals),
+                None,
+            )?;
+            let ty = globals.get_item("BigNumber")?.ok_or_else(|| {
+                PyrsedisError::Protocol("BigNumber type definition failed".into())
+            })?;
+            Ok::<_, PyErr>(ty.cast_into::<PyType>()?.unbind())
+        })
+        .map(|ty| ty.bind(py))
+}
+
 // ── Fused RESP → Python parser (single pass) ───────────────────────
 
 /// Fast CRLF finder — uses simple scan for short lines (RESP integers/lengths),
@@ -377,7 +617,7 @@ fn fused_parse_int(bytes: &[u8]) -> std::result::Result<i64, PyrsedisError> {
     if check > 9 {
         // At least one non-digit byte — find it for the error message
         for &b in digits {
-            if b < b'0' || b > b'9' {
+            if !b.is_ascii_digit() {
                 return Err(PyrsedisError::Protocol(
                     format!("invalid byte in integer: 0x{b:02x}")
                 ));
@@ -422,27 +662,49 @@ pub fn parse_to_python(
     py: Python<'_>,
     buf: &Bytes,
     decode: bool,
+) -> PyResult<(Py<PyAny>, usize)> {
+    parse_to_python_with(py, buf, ParseOptions { decode, resp3_types: false })
+}
+
+/// Like [`parse_to_python`], with full control over the conversion.
+pub fn parse_to_python_with(
+    py: Python<'_>,
+    buf: &Bytes,
+    opts: ParseOptions,
+) -> PyResult<(Py<PyAny>, usize)> {
+    parse_to_python_shaped(py, buf, None, opts)
+}
+
+/// Like [`parse_to_python_with`], pre-sizing containers from a
+/// [`FrameShape`] scanned off-GIL from the same `buf`.
+pub fn parse_to_python_shaped(
+    py: Python<'_>,
+    buf: &Bytes,
+    shape: Option<&FrameShape>,
+    opts: ParseOptions,
 ) -> PyResult<(Py<PyAny>, usize)> {
     if buf.is_empty() {
         return Err(PyrsedisError::Incomplete.into());
     }
     // Delegate to the inner function that works on &[u8] with offset tracking.
     // This avoids Bytes::slice() atomic refcount ops on every recursive call.
-    let (obj, end) = parse_inner(py, buf, 0, 0, decode)?;
+    let (obj, end) = parse_inner(py, buf, 0, 0, opts, &mut ShapeCursor::new(shape))?;
     Ok((obj, end))
 }
 
 /// Inner recursive parser operating on `&[u8]` with offset tracking.
 ///
 /// Returns `(python_object, offset_after_consumed_bytes)`.
-/// All positions are absolute offsets into the original buffer.
+/// All positions are absolute offsets into the original buffer. `shape`
+/// is advanced once per non-null aggregate, matching [`resp_frame_shape`].
 #[inline]
 fn parse_inner(
     py: Python<'_>,
     buf: &[u8],
     pos: usize,
     depth: usize,
-    decode: bool,
+    opts: ParseOptions,
+    shape: &mut ShapeCursor<'_>,
 ) -> PyResult<(Py<PyAny>, usize)> {
     if depth > MAX_PARSE_DEPTH {
         return Err(PyrsedisError::Protocol(
@@ -491,7 +753,7 @@ fn parse_inner(
                 return Err(PyrsedisError::Incomplete.into());
             }
             let data = &buf[next..next + len];
-            if decode {
+            if opts.decode {
                 match std::str::from_utf8(data) {
                     Ok(s) => Ok((PyString::new(py, s).into_any().unbind(), total)),
                     Err(_) => Ok((PyBytes::new(py, data).into_any().unbind(), total)),
@@ -508,8 +770,8 @@ fn parse_inner(
                 return Ok((py.None(), next)); // null array
             }
             let count = validated_count(count)?;
-            // SAFETY: parse_inner produces valid Py<PyAny>, build_pylist_ffi handles errors
-            unsafe { build_pylist_ffi(py, buf, next, count, depth + 1, decode) }
+            shape.next();
+            parse_pylist(py, buf, next, count, depth + 1, opts, shape)
         }
         b'_' => {
             // Null
@@ -527,7 +789,8 @@ fn parse_inner(
             Ok((PyBool::new(py, b).to_owned().into_any().unbind(), pos + 4))
         }
         b',' => {
-            // Double → Python float
+            // Double → Python float. `inf`, `-inf` and `nan` map to the IEEE
+            // values and `-0` keeps its sign.
             let (line, next) = fused_read_line(buf, pos + 1).map_err(|e| -> PyErr { e.into() })?;
             let s = std::str::from_utf8(line)
                 .map_err(|e| PyrsedisError::Protocol(format!("invalid UTF-8 in double: {e}")))?;
@@ -546,8 +809,11 @@ fn parse_inner(
             }
             let s = std::str::from_utf8(line)
                 .map_err(|e| PyrsedisError::Protocol(format!("invalid UTF-8 in big number: {e}")))?;
-            let builtins = py.import("builtins")?;
-            let py_int = builtins.getattr("int")?.call1((s,))?;
+            let py_int = if opts.resp3_types {
+                big_number_type(py)?.call1((s,))?
+            } else {
+                py.import("builtins")?.getattr("int")?.call1((s,))?
+            };
             Ok((py_int.unbind(), next))
         }
         b'!' => {
@@ -579,13 +845,20 @@ fn parse_inner(
             }
             let data = &buf[next..next + len];
             // Skip "txt:" or "mkd:" prefix (4 bytes)
-            let text = if data.len() > 4 && data[3] == b':' {
-                &data[4..]
+            let (encoding, text) = if data.len() > 4 && data[3] == b':' {
+                (&data[..3], &data[4..])
             } else {
-                data
+                (&b""[..], data)
             };
             let s = std::str::from_utf8(text)
                 .map_err(|e| PyrsedisError::Protocol(format!("invalid UTF-8: {e}")))?;
+            if opts.resp3_types {
+                let verbatim = Verbatim {
+                    encoding: String::from_utf8_lossy(encoding).into_owned(),
+                    text: s.to_string(),
+                };
+                return Ok((Py::new(py, verbatim)?.into_any(), total));
+            }
             Ok((PyString::new(py, s).into_any().unbind(), total))
         }
         b'%' => {
@@ -593,11 +866,11 @@ fn parse_inner(
             let (line, mut next) = fused_read_line(buf, pos + 1).map_err(|e| -> PyErr { e.into() })?;
             let count = fused_parse_int(line).map_err(|e| -> PyErr { e.into() })?;
             let count = validated_count(count)?;
-            let dict = PyDict::new(py);
+            let dict = new_dict(py, shape.next())?;
             for _ in 0..count {
-                let (key, end_k) = parse_inner(py, buf, next, depth + 1, decode)?;
+                let (key, end_k) = parse_inner(py, buf, next, depth + 1, opts, shape)?;
                 next = end_k;
-                let (val, end_v) = parse_inner(py, buf, next, depth + 1, decode)?;
+                let (val, end_v) = parse_inner(py, buf, next, depth + 1, opts, shape)?;
                 next = end_v;
                 dict.set_item(key, val)?;
             }
@@ -608,9 +881,10 @@ fn parse_inner(
             let (line, mut next) = fused_read_line(buf, pos + 1).map_err(|e| -> PyErr { e.into() })?;
             let count = fused_parse_int(line).map_err(|e| -> PyErr { e.into() })?;
             let count = validated_count(count)?;
+            shape.next();
             let set = PySet::empty(py)?;
             for _ in 0..count {
-                let (item, end) = parse_inner(py, buf, next, depth + 1, decode)?;
+                let (item, end) = parse_inner(py, buf, next, depth + 1, opts, shape)?;
                 next = end;
                 set.add(item)?;
             }
@@ -621,23 +895,23 @@ fn parse_inner(
             let (line, next) = fused_read_line(buf, pos + 1).map_err(|e| -> PyErr { e.into() })?;
             let count = fused_parse_int(line).map_err(|e| -> PyErr { e.into() })?;
             let count = validated_count(count)?;
-            // SAFETY: same as array arm
-            unsafe { build_pylist_ffi(py, buf, next, count, depth + 1, decode) }
+            shape.next();
+            parse_pylist(py, buf, next, count, depth + 1, opts, shape)
         }
         b'|' => {
             // Attribute → dict with __data__ and __attrs__
             let (line, mut next) = fused_read_line(buf, pos + 1).map_err(|e| -> PyErr { e.into() })?;
             let count = fused_parse_int(line).map_err(|e| -> PyErr { e.into() })?;
             let count = validated_count(count)?;
-            let attrs_dict = PyDict::new(py);
+            let attrs_dict = new_dict(py, shape.next())?;
             for _ in 0..count {
-                let (key, end_k) = parse_inner(py, buf, next, depth + 1, decode)?;
+                let (key, end_k) = parse_inner(py, buf, next, depth + 1, opts, shape)?;
                 next = end_k;
-                let (val, end_v) = parse_inner(py, buf, next, depth + 1, decode)?;
+                let (val, end_v) = parse_inner(py, buf, next, depth + 1, opts, shape)?;
                 next = end_v;
                 attrs_dict.set_item(key, val)?;
             }
-            let (data, end) = parse_inner(py, buf, next, depth + 1, decode)?;
+            let (data, end) = parse_inner(py, buf, next, depth + 1, opts, shape)?;
             next = end;
             let dict = PyDict::new(py);
             dict.set_item("__attrs__", attrs_dict)?;
@@ -650,6 +924,47 @@ fn parse_inner(
     }
 }
 
+// ── Python → RESP ─────────────────────────────────────────────────
+
+/// Rebuild the `RespValue` behind a reply that was already converted to
+/// Python objects, such as a pipeline result or a recorded reply.
+///
+/// `str` and `bytes` become bulk strings and lists and tuples arrays. A
+/// `float` or `bool` becomes the bulk string a graph reply carries it as
+/// (`"2.5"`, `"true"`).
+pub fn python_to_resp(obj: &Bound<'_, PyAny>) -> PyResult<RespValue> {
+    if obj.is_none() {
+        return Ok(RespValue::Null);
+    }
+    if let Ok(b) = obj.cast::<PyBool>() {
+        let text: &'static [u8] = if b.is_true() { b"true" } else { b"false" };
+        return Ok(RespValue::BulkString(Bytes::from_static(text)));
+    }
+    if let Ok(i) = obj.extract::<i64>() {
+        return Ok(RespValue::Integer(i));
+    }
+    if let Ok(f) = obj.cast::<PyFloat>() {
+        return Ok(RespValue::BulkString(Bytes::from(f.value().to_string())));
+    }
+    if let Ok(s) = obj.cast::<PyString>() {
+        return Ok(RespValue::BulkString(Bytes::from(s.to_str()?.to_owned())));
+    }
+    if let Ok(b) = obj.cast::<PyBytes>() {
+        return Ok(RespValue::BulkString(Bytes::copy_from_slice(b.as_bytes())));
+    }
+    if let Ok(dict) = obj.cast::<PyDict>() {
+        return dict
+            .iter()
+            .map(|(k, v)| Ok((python_to_resp(&k)?, python_to_resp(&v)?)))
+            .collect::<PyResult<_>>()
+            .map(RespValue::Map);
+    }
+    if obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>() {
+        return obj.try_iter()?.map(|item| python_to_resp(&item?)).collect::<PyResult<_>>().map(RespValue::Array);
+    }
+    Err(PyrsedisError::Type(format!("cannot convert {} to a reply value", obj.get_type().name()?)).into())
+}
+
 // ── Tests ──────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -690,6 +1005,58 @@ mod tests {
         assert_eq!(resp_to_string(&v), Some("hello".to_string()));
     }
 
+    // ── resp_to_f64 ──
+
+    #[test]
+    fn to_f64_variants() {
+        assert_eq!(resp_to_f64(&RespValue::Double(1.25)), Some(1.25));
+        assert_eq!(resp_to_f64(&RespValue::Integer(3)), Some(3.0));
+        assert_eq!(resp_to_f64(&RespValue::BulkString(Bytes::from_static(b"-0.5"))), Some(-0.5));
+        assert_eq!(resp_to_f64(&RespValue::BulkString(Bytes::from_static(b"inf"))), Some(f64::INFINITY));
+        assert_eq!(resp_to_f64(&RespValue::BulkString(Bytes::from_static(b"abc"))), None);
+        assert_eq!(resp_to_f64(&RespValue::Null), None);
+    }
+
+    #[test]
+    fn to_f64_vec_maps_nil_to_nan() {
+        let v = RespValue::Array(vec![
+            RespValue::BulkString(Bytes::from_static(b"1.5")),
+            RespValue::Null,
+            RespValue::BulkString(Bytes::from_static(b"2")),
+        ]);
+        let floats = resp_to_f64_vec(&v).unwrap();
+        assert_eq!(floats[0], 1.5);
+        assert!(floats[1].is_nan());
+        assert_eq!(floats[2], 2.0);
+    }
+
+    #[test]
+    fn to_f64_vec_rejects_non_numeric() {
+        let v = RespValue::Array(vec![RespValue::BulkString(Bytes::from_static(b"x"))]);
+        assert!(matches!(resp_to_f64_vec(&v), Err(PyrsedisError::Type(_))));
+    }
+
+    #[test]
+    fn to_f64_map_resp2_and_resp3() {
+        let resp2 = RespValue::Array(vec![
+            RespValue::BulkString(Bytes::from_static(b"a")),
+            RespValue::BulkString(Bytes::from_static(b"0.25")),
+            RespValue::BulkString(Bytes::from_static(b"b")),
+            RespValue::BulkString(Bytes::from_static(b"4")),
+        ]);
+        let (fields, values) = resp_to_f64_map(&resp2).unwrap();
+        assert_eq!(fields, vec!["a", "b"]);
+        assert_eq!(values, vec![0.25, 4.0]);
+
+        let resp3 = RespValue::Map(vec![(
+            RespValue::BulkString(Bytes::from_static(b"c")),
+            RespValue::Double(9.5),
+        )]);
+        let (fields, values) = resp_to_f64_map(&resp3).unwrap();
+        assert_eq!(fields, vec!["c"]);
+        assert_eq!(values, vec![9.5]);
+    }
+
     // ── resp_to_i64 ──
 
     #[test]
@@ -883,10 +1250,10 @@ mod tests {
     #[test]
     fn python_double() {
         Python::attach(|py| {
-            let v = RespValue::Double(3.14);
+            let v = RespValue::Double(1.25);
             let obj = resp_to_python(py, v).unwrap();
             let f: f64 = obj.extract(py).unwrap();
-            assert!((f - 3.14).abs() < 1e-10);
+            assert!((f - 1.25).abs() < 1e-10);
         });
     }
 
@@ -936,4 +1303,103 @@ mod tests {
             assert_eq!(s, "hello world");
         });
     }
+
+    // ── RESP3 fidelity ──
+
+    fn parse_typed<'py>(py: Python<'py>, frame: &'static [u8], resp3_types: bool) -> Bound<'py, PyAny> {
+        let opts = ParseOptions { decode: true, resp3_types };
+        let (obj, used) = parse_to_python_with(py, &Bytes::from_static(frame), opts).unwrap();
+        assert_eq!(used, frame.len());
+        obj.into_bound(py)
+    }
+
+    #[test]
+    fn fused_verbatim_keeps_encoding_when_requested() {
+        Python::attach(|py| {
+            let plain = parse_typed(py, b"=15\r\ntxt:Some string\r\n", false);
+            assert_eq!(plain.extract::<String>().unwrap(), "Some string");
+
+            let typed = parse_typed(py, b"=15\r\nmkd:Some string\r\n", true);
+            let verbatim = typed.cast::<Verbatim>().unwrap().get();
+            assert_eq!(verbatim.encoding, "mkd");
+            assert_eq!(verbatim.text, "Some string");
+            assert_eq!(typed.str().unwrap().to_str().unwrap(), "Some string");
+        });
+    }
+
+    #[test]
+    fn fused_big_number_keeps_raw_text_when_requested() {
+        Python::attach(|py| {
+            let frame = b"(+03492890328409238509324850943850943825024385\r\n";
+            let plain = parse_typed(py, frame, false);
+            assert!(plain.get_type().is(py.get_type::<pyo3::types::PyInt>()));
+
+            let typed = parse_typed(py, frame, true);
+            assert!(typed.is_instance_of::<pyo3::types::PyInt>());
+            assert!(typed.eq(&plain).unwrap());
+            let raw: String = typed.getattr("raw").unwrap().extract().unwrap();
+            assert_eq!(raw, "+03492890328409238509324850943850943825024385");
+        });
+    }
+
+    #[test]
+    fn fused_double_special_values() {
+        Python::attach(|py| {
+            let neg_zero: f64 = parse_typed(py, b",-0\r\n", true).extract().unwrap();
+            assert_eq!(neg_zero, 0.0);
+            assert!(neg_zero.is_sign_negative());
+            let inf: f64 = parse_typed(py, b",inf\r\n", true).extract().unwrap();
+            assert_eq!(inf, f64::INFINITY);
+            let neg_inf: f64 = parse_typed(py, b",-inf\r\n", false).extract().unwrap();
+            assert_eq!(neg_inf, f64::NEG_INFINITY);
+            let nan: f64 = parse_typed(py, b",nan\r\n", false).extract().unwrap();
+            assert!(nan.is_nan());
+        });
+    }
+
+    #[test]
+    fn fused_resp3_types_apply_inside_containers() {
+        Python::attach(|py| {
+            let list = parse_typed(py, b"*2\r\n=7\r\ntxt:abc\r\n(12\r\n", true);
+            let items: Vec<Bound<'_, PyAny>> = list.extract().unwrap();
+            assert!(items[0].cast::<Verbatim>().is_ok());
+            assert!(items[1].hasattr("raw").unwrap());
+        });
+    }
+
+    #[test]
+    fn build_pylist_fills_in_place_and_cleans_up() {
+        Python::attach(|py| {
+            let list = build_pylist(py, 3, |i| Ok((i * 10).into_pyobject(py)?.into_any().unbind())).unwrap();
+            assert_eq!(list.extract::<Vec<usize>>(py).unwrap(), [0, 10, 20]);
+            let err = build_pylist(py, 3, |i| {
+                if i == 1 {
+                    return Err(PyrsedisError::Protocol("bad item".into()).into());
+                }
+                Ok(py.None())
+            });
+            assert!(err.is_err());
+            assert_eq!(build_pylist(py, 0, |_| unreachable!()).unwrap().bind(py).len().unwrap(), 0);
+        });
+    }
+
+    #[test]
+    fn frame_shape_presizes_maps_with_same_result() {
+        Python::attach(|py| {
+            let mut frame = format!("*{PRESIZE_THRESHOLD}\r\n%2\r\n+a\r\n:1\r\n+b\r\n*-1\r\n").into_bytes();
+            for _ in 1..PRESIZE_THRESHOLD {
+                frame.extend_from_slice(b":7\r\n");
+            }
+            let frame = Bytes::from(frame);
+            let shape = FrameShape::scan(&frame).unwrap();
+            assert_eq!(shape.counts, [PRESIZE_THRESHOLD, 2]);
+            let opts = ParseOptions::default();
+            let (shaped, end) = parse_to_python_shaped(py, &frame, Some(&shape), opts).unwrap();
+            let (plain, _) = parse_to_python_with(py, &frame, opts).unwrap();
+            assert_eq!(end, frame.len());
+            assert!(shaped.bind(py).eq(plain.bind(py)).unwrap());
+            assert!(FrameShape::scan(b"*2\r\n:1\r\n:2\r\n").is_none());
+            assert!(FrameShape::scan(b"+OK\r\n").is_none());
+        });
+    }
 }
@@ -0,0 +1,152 @@
+//! Per-node circuit breaker for the cluster router.
+//!
+//! A node that stops answering would otherwise cost every request routed
+//! to it a full connect or read timeout. After [`FAILURE_THRESHOLD`]
+//! consecutive connection failures the node's breaker opens: requests to
+//! it fail at once with `ClusterDownError` (reads go to a replica when the
+//! slot has one). After [`OPEN_FOR`] the breaker lets a single probe
+//! request through (half-open); its success closes the breaker, its
+//! failure opens it again.
+
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::error::{PyrsedisError, Result};
+
+/// Consecutive connection failures that open a node's breaker.
+pub const FAILURE_THRESHOLD: u32 = 5;
+
+/// How long an open breaker rejects requests before probing the node.
+pub const OPEN_FOR: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    /// Requests pass; `failures` consecutive ones have failed.
+    Closed { failures: u32 },
+    /// Requests are rejected until `until`.
+    Open { until: Instant },
+    /// One probe request, sent at `since`, is in flight.
+    HalfOpen { since: Instant },
+}
+
+/// Circuit breaker of one cluster node.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    state: Mutex<State>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self { state: Mutex::new(State::Closed { failures: 0 }) }
+    }
+}
+
+impl CircuitBreaker {
+    /// Whether requests to the node are being rejected (a probe in flight
+    /// counts as open).
+    pub fn is_open(&self) -> bool {
+        match *self.state.lock() {
+            State::Closed { .. } => false,
+            State::Open { until } => Instant::now() < until,
+            State::HalfOpen { .. } => true,
+        }
+    }
+
+    /// Let a request to the node at `addr` through, or fail it with
+    /// `CLUSTERDOWN` while the breaker is open.
+    ///
+    /// Once [`OPEN_FOR`] has passed, one request is let through as a
+    /// probe. A probe that never reports back (its future was dropped) is
+    /// replaced after another [`OPEN_FOR`].
+    pub fn admit(&self, addr: &str) -> Result<()> {
+        let mut state = self.state.lock();
+        let now = Instant::now();
+        let probe_due = match *state {
+            State::Closed { .. } => return Ok(()),
+            State::Open { until } => now >= until,
+            State::HalfOpen { since } => now >= since + OPEN_FOR,
+        };
+        if probe_due {
+            *state = State::HalfOpen { since: now };
+            return Ok(());
+        }
+        Err(PyrsedisError::redis(format!("CLUSTERDOWN node {addr} is unreachable (circuit breaker open)")))
+    }
+
+    /// Record the outcome of a request that was let through.
+    ///
+    /// Connection failures and timeouts count against the node, and any
+    /// reply, an error reply included, shows it is up. Other errors (such
+    /// as a pool timeout) say nothing about the node and are ignored.
+    pub fn record<T>(&self, result: &Result<T>) {
+        let mut state = self.state.lock();
+        match result {
+            Ok(_) | Err(PyrsedisError::Redis { .. } | PyrsedisError::Setup { .. }) => *state = State::Closed { failures: 0 },
+            Err(PyrsedisError::Connection(_) | PyrsedisError::Timeout(_)) => {
+                *state = match *state {
+                    State::Closed { failures } if failures + 1 < FAILURE_THRESHOLD => {
+                        State::Closed { failures: failures + 1 }
+                    }
+                    _ => State::Open { until: Instant::now() + OPEN_FOR },
+                };
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::RedisErrorKind;
+
+    fn refused() -> Result<()> {
+        Err(PyrsedisError::Connection(std::io::Error::from(std::io::ErrorKind::ConnectionRefused)))
+    }
+
+    fn is_cluster_down(result: Result<()>) -> bool {
+        matches!(result, Err(PyrsedisError::Redis { kind: RedisErrorKind::ClusterDown, .. }))
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::default();
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            breaker.admit("n:1").unwrap();
+            breaker.record(&refused());
+        }
+        // A reply, even an error one, resets the count
+        breaker.record::<()>(&Err(PyrsedisError::redis("ERR bad")));
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            breaker.record(&refused());
+        }
+        breaker.record::<()>(&Err(PyrsedisError::PoolTimeout("busy".into())));
+        assert!(!breaker.is_open());
+        breaker.record(&refused());
+        assert!(breaker.is_open());
+        assert!(is_cluster_down(breaker.admit("n:1")));
+    }
+
+    #[test]
+    fn half_open_lets_one_probe_through() {
+        let breaker = CircuitBreaker::default();
+        *breaker.state.lock() = State::Open { until: Instant::now() };
+        breaker.admit("n:1").unwrap();
+        assert!(is_cluster_down(breaker.admit("n:1")));
+        breaker.record(&refused());
+        assert!(is_cluster_down(breaker.admit("n:1")));
+
+        *breaker.state.lock() = State::Open { until: Instant::now() };
+        breaker.admit("n:1").unwrap();
+        breaker.record(&Ok(()));
+        assert!(!breaker.is_open());
+        breaker.admit("n:1").unwrap();
+
+        // A probe that never reports back is replaced
+        *breaker.state.lock() = State::HalfOpen { since: Instant::now() - 2 * OPEN_FOR };
+        breaker.admit("n:1").unwrap();
+    }
+}
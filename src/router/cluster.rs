@@ -1,34 +1,85 @@
 //! Redis Cluster topology router.
 //!
 //! Routes commands to the correct node based on the hash slot of the key.
-//! Handles MOVED and ASK redirections, replica reads for read-only commands,
-//! and periodic slot map refresh.
+//! Handles MOVED and ASK redirections, replica reads for read-only commands
+//! (see [`ReadPreference`]),
+//! periodic slot map refresh and a circuit breaker per node (see
+//! [`breaker`](crate::router::breaker)). The slot map comes from `CLUSTER SHARDS`,
+//! or `CLUSTER SLOTS` on servers older than Redis 7, and names nodes by
+//! their advertised hostname when they have one.
+//!
+//! A node can be drained ahead of maintenance: routing then passes it over
+//! as it does a node whose breaker is tripped, and a drained master is
+//! released once a refreshed slot map gives its slots to another node.
 
-use crate::config::ConnectionConfig;
-use crate::connection::pool::ConnectionPool;
+use crate::config::{ConnectionConfig, KeylessRouting, ReadPreference};
+use crate::connection::pool::{ConnectionPool, LeasedConnection};
+use crate::connection::stats::StatsSnapshot;
 use crate::connection::tcp::RedisConnection;
 use crate::crc16::hash_slot;
+use crate::registry;
 use crate::error::{PyrsedisError, RedisErrorKind, Result};
+use crate::resp::parser::resp_frame_len;
 use crate::resp::types::RespValue;
-use crate::resp::writer::encode_command_str;
-use crate::router::Router;
+use crate::resp::writer::{encode_command, encode_command_str, encode_pipeline};
+use crate::router::breaker::CircuitBreaker;
+use crate::router::sampler::{NodeSample, SAMPLE_INTERVAL};
+use crate::router::keys::{command_slot, key_positions, shared_slot};
+use crate::router::{wrap_transaction, Router};
 use crate::runtime;
 
-use parking_lot::RwLock;
+use bytes::Bytes;
+use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Maximum number of MOVED/ASK redirects before giving up.
 const MAX_REDIRECTS: usize = 5;
 
-/// Background slot refresh interval.
-const SLOT_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+/// Default background slot refresh interval in milliseconds.
+pub const DEFAULT_SLOT_REFRESH_INTERVAL_MS: u64 = 30_000;
+
+/// MOVED redirects since the last full refresh after which the whole
+/// slot map is re-fetched instead of patched one slot at a time.
+const MOVED_REFRESH_THRESHOLD: usize = 3;
+
+/// Minimum time between two MOVED-triggered refreshes.
+const MOVED_REFRESH_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// Each refresh wait varies randomly by up to this fraction of the
+/// interval in either direction.
+const SLOT_REFRESH_JITTER: f64 = 0.1;
+
+/// A per-call routing override, the ``route`` of ``execute_command``.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// The master owning the command's keys, even for a read the read
+    /// preference would send to a replica.
+    Master,
+    /// A replica of the slot owning the command's keys.
+    Replica,
+    /// This node (`"host:port"`), whatever the command's keys.
+    Node(String),
+    /// The master of this slot.
+    Slot(u16),
+}
+
+/// `interval` randomized by up to ±[`SLOT_REFRESH_JITTER`], so clients
+/// started together do not all send `CLUSTER SLOTS` at the same moment.
+fn jittered(interval: Duration) -> Duration {
+    // RandomState is freshly keyed per call; no need for an RNG crate
+    let bits = RandomState::new().hash_one(Instant::now()) >> 11;
+    let unit = bits as f64 / (1u64 << 53) as f64; // [0, 1)
+    interval.mul_f64(1.0 + SLOT_REFRESH_JITTER * (2.0 * unit - 1.0))
+}
 
 // ── Read-only command classification ──────────────────────────────
 
 /// Commands that can be routed to replicas.
-fn is_read_only_command(cmd: &str) -> bool {
+pub(crate) fn is_read_only_command(cmd: &str) -> bool {
     matches!(
         cmd.to_ascii_uppercase().as_str(),
         "GET"
@@ -97,6 +148,7 @@ fn is_read_only_command(cmd: &str) -> bool {
             | "GEORADIUSBYMEMBER_RO"
             | "GEOSEARCH"
             | "GRAPH.RO_QUERY"
+            | "FCALL_RO"
     )
 }
 
@@ -104,7 +156,7 @@ fn is_read_only_command(cmd: &str) -> bool {
 
 /// A range of hash slots mapped to a master and zero or more replicas.
 #[derive(Debug, Clone)]
-struct SlotRange {
+pub(crate) struct SlotRange {
     start: u16,
     end: u16,
     master: String,
@@ -113,13 +165,13 @@ struct SlotRange {
 
 /// Slot map: sorted list of slot ranges for binary-search lookup.
 #[derive(Debug, Clone, Default)]
-struct SlotMap {
+pub(crate) struct SlotMap {
     ranges: Vec<SlotRange>,
 }
 
 impl SlotMap {
-    /// Look up the master address for a hash slot.
-    fn master_for_slot(&self, slot: u16) -> Option<&str> {
+    /// Look up the range containing a hash slot.
+    fn range_for_slot(&self, slot: u16) -> Option<&SlotRange> {
         self.ranges
             .binary_search_by(|r| {
                 if slot < r.start {
@@ -131,33 +183,22 @@ impl SlotMap {
                 }
             })
             .ok()
-            .map(|i| self.ranges[i].master.as_str())
+            .map(|i| &self.ranges[i])
     }
 
-    /// Look up a replica address for a hash slot (random pick).
-    /// Falls back to master if no replicas.
-    fn replica_for_slot(&self, slot: u16) -> Option<&str> {
-        self.ranges
-            .binary_search_by(|r| {
-                if slot < r.start {
-                    std::cmp::Ordering::Greater
-                } else if slot > r.end {
-                    std::cmp::Ordering::Less
-                } else {
-                    std::cmp::Ordering::Equal
-                }
-            })
-            .ok()
-            .map(|i| {
-                let range = &self.ranges[i];
-                if range.replicas.is_empty() {
-                    range.master.as_str()
-                } else {
-                    // Simple round-robin via slot number to distribute
-                    let idx = (slot as usize) % range.replicas.len();
-                    range.replicas[idx].as_str()
-                }
-            })
+    /// Look up the master address for a hash slot.
+    fn master_for_slot(&self, slot: u16) -> Option<&str> {
+        self.range_for_slot(slot).map(|range| range.master.as_str())
+    }
+
+    /// Whether `addr` is a replica of any slot range.
+    fn is_replica(&self, addr: &str) -> bool {
+        self.ranges.iter().any(|range| range.replicas.iter().any(|r| r == addr))
+    }
+
+    /// Whether `addr` is a master or replica of any slot range.
+    fn has_node(&self, addr: &str) -> bool {
+        self.ranges.iter().any(|range| range.master == addr) || self.is_replica(addr)
     }
 
     /// Update a single slot's master (used after MOVED redirect).
@@ -225,9 +266,98 @@ impl SlotMap {
         ranges.sort_by_key(|r| r.start);
         Ok(Self { ranges })
     }
+
+    /// Parse the result of `CLUSTER SHARDS` (Redis 7+) into a slot map.
+    ///
+    /// Replicas that are not `online` are left out. With `tls`, a node's
+    /// `tls-port` is used in preference to its `port`.
+    fn from_cluster_shards(resp: &RespValue, tls: bool) -> Result<Self> {
+        let RespValue::Array(shards) = resp else {
+            return Err(PyrsedisError::Cluster(format!(
+                "CLUSTER SHARDS: expected array, got {:?}",
+                resp.type_name()
+            )));
+        };
+
+        let mut ranges = Vec::new();
+        for shard in shards {
+            let shard = fields(shard).ok_or_else(|| PyrsedisError::Cluster("CLUSTER SHARDS: expected shard map".into()))?;
+            let (Some(RespValue::Array(slots)), Some(RespValue::Array(nodes))) = (field(&shard, "slots"), field(&shard, "nodes")) else {
+                continue;
+            };
+            let mut master = None;
+            let mut replicas = Vec::new();
+            for node in nodes {
+                let node = fields(node).ok_or_else(|| PyrsedisError::Cluster("CLUSTER SHARDS: expected node map".into()))?;
+                let addr = shard_node_addr(&node, tls)?;
+                match field(&node, "role").and_then(RespValue::as_str) {
+                    Some("master") => master = Some(addr),
+                    _ if field(&node, "health").and_then(RespValue::as_str) == Some("online") => replicas.push(addr),
+                    _ => {}
+                }
+            }
+            // A shard without a master serves no slots
+            let Some(master) = master else { continue };
+            for bounds in slots.chunks_exact(2) {
+                let (Some(start), Some(end)) = (bounds[0].as_int(), bounds[1].as_int()) else {
+                    return Err(PyrsedisError::Cluster("CLUSTER SHARDS: invalid slot range".into()));
+                };
+                ranges.push(SlotRange {
+                    start: start as u16,
+                    end: end as u16,
+                    master: master.clone(),
+                    replicas: replicas.clone(),
+                });
+            }
+        }
+
+        ranges.sort_by_key(|r| r.start);
+        Ok(Self { ranges })
+    }
+}
+
+/// Name/value pairs of a RESP3 map, or of a RESP2 array alternating names
+/// and values.
+fn fields(val: &RespValue) -> Option<Vec<(&str, &RespValue)>> {
+    match val {
+        RespValue::Map(pairs) => pairs.iter().map(|(k, v)| Some((k.as_str()?, v))).collect(),
+        RespValue::Array(items) => items.chunks_exact(2).map(|kv| Some((kv[0].as_str()?, &kv[1]))).collect(),
+        _ => None,
+    }
+}
+
+/// The value named `name` in `fields`.
+fn field<'a>(fields: &[(&str, &'a RespValue)], name: &str) -> Option<&'a RespValue> {
+    fields.iter().find(|(k, _)| *k == name).map(|(_, v)| *v)
+}
+
+/// The host to connect to for a node, preferring its advertised hostname
+/// (needed to validate TLS certificates) over its endpoint or IP.
+fn preferred_host<'a>(fields: &[(&str, &'a RespValue)], endpoint: Option<&'a str>) -> Option<&'a str> {
+    field(fields, "hostname")
+        .and_then(RespValue::as_str)
+        .into_iter()
+        .chain(endpoint)
+        .chain(field(fields, "ip").and_then(RespValue::as_str))
+        .find(|host| !host.is_empty() && *host != "?")
+}
+
+/// "host:port" of a node map from CLUSTER SHARDS.
+fn shard_node_addr(node: &[(&str, &RespValue)], tls: bool) -> Result<String> {
+    let endpoint = field(node, "endpoint").and_then(RespValue::as_str);
+    let host = preferred_host(node, endpoint)
+        .ok_or_else(|| PyrsedisError::Cluster("CLUSTER SHARDS: node has no address".into()))?;
+    let (port, tls_port) = (field(node, "port"), field(node, "tls-port"));
+    let port = if tls { tls_port.or(port) } else { port.or(tls_port) };
+    let port = port
+        .and_then(RespValue::as_int)
+        .ok_or_else(|| PyrsedisError::Cluster("CLUSTER SHARDS: invalid port".into()))?;
+    Ok(format!("{host}:{port}"))
 }
 
-/// Parse a node array `[host, port, ...]` from CLUSTER SLOTS into "host:port".
+/// Parse a node array `[endpoint, port, id, {metadata}]` from CLUSTER
+/// SLOTS into "host:port", preferring a `hostname` in the metadata (Redis
+/// 7+) over the endpoint.
 fn parse_node_addr(val: &RespValue) -> Result<String> {
     let items = match val {
         RespValue::Array(arr) => arr,
@@ -242,8 +372,8 @@ fn parse_node_addr(val: &RespValue) -> Result<String> {
             "CLUSTER SLOTS: node array too short".into(),
         ));
     }
-    let host = items[0]
-        .as_str()
+    let metadata = items.get(3).and_then(fields).unwrap_or_default();
+    let host = preferred_host(&metadata, items[0].as_str())
         .ok_or_else(|| PyrsedisError::Cluster("CLUSTER SLOTS: invalid host".into()))?;
     let port = items[1]
         .as_int()
@@ -251,47 +381,95 @@ fn parse_node_addr(val: &RespValue) -> Result<String> {
     Ok(format!("{host}:{port}"))
 }
 
-// ── Key extraction ────────────────────────────────────────────────
-
-/// Extract the first key from a command's arguments.
+/// Parse a raw `-ERR…` reply frame into its error kind and message.
 ///
-/// Most commands have the key at args[1]. Commands with special key
-/// positions are handled here.
-fn extract_key<'a>(args: &'a [&str]) -> Option<&'a str> {
-    if args.is_empty() {
-        return None;
-    }
-    let cmd = args[0].to_ascii_uppercase();
-    match cmd.as_str() {
-        // Key-less commands
-        "PING" | "INFO" | "DBSIZE" | "CLUSTER" | "CONFIG" | "CLIENT" | "COMMAND" | "TIME"
-        | "RANDOMKEY" | "WAIT" | "SAVE" | "BGSAVE" | "BGREWRITEAOF" | "FLUSHALL"
-        | "FLUSHDB" | "LASTSAVE" | "SLOWLOG" | "DEBUG" | "MULTI" | "EXEC" | "DISCARD"
-        | "SCRIPT" | "SUBSCRIBE" | "UNSUBSCRIBE" | "PSUBSCRIBE" | "PUNSUBSCRIBE" | "QUIT" => {
-            None
-        }
-        // EVAL/EVALSHA: key is after numkeys at args[3] (if numkeys > 0)
-        "EVAL" | "EVALSHA" => {
-            if args.len() >= 4 {
-                if let Ok(numkeys) = args[2].parse::<usize>() {
-                    if numkeys > 0 && args.len() > 3 {
-                        return Some(args[3]);
-                    }
-                }
+/// Returns `None` for non-error replies.
+fn raw_error(raw: &[u8]) -> Option<(RedisErrorKind, String)> {
+    let msg = raw.strip_prefix(b"-")?.strip_suffix(b"\r\n")?;
+    Some(RedisErrorKind::from_error_msg(std::str::from_utf8(msg).ok()?))
+}
+
+// ── Cross-slot splitting ──────────────────────────────────────────
+
+/// How the per-slot replies of a split multi-key command are combined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Merge {
+    /// Integer replies are added up (`DEL`, `UNLINK`, `EXISTS`, `TOUCH`).
+    Sum,
+    /// Array replies are put back into key order (`MGET`).
+    Gather,
+}
+
+/// How to merge `command` when its keys are split by slot, or `None` if
+/// it cannot be split.
+fn split_merge(command: &str) -> Option<Merge> {
+    match command.to_ascii_uppercase().as_str() {
+        "MGET" => Some(Merge::Gather),
+        "DEL" | "UNLINK" | "EXISTS" | "TOUCH" => Some(Merge::Sum),
+        _ => None,
+    }
+}
+
+/// Key positions and per-slot commands bound for one node.
+type SplitBatch = (Vec<Vec<usize>>, Vec<Vec<String>>);
+
+/// Positions of `keys` grouped by hash slot, in order of first appearance.
+pub(crate) fn slot_groups(keys: &[&str]) -> Vec<(u16, Vec<usize>)> {
+    let mut groups: Vec<(u16, Vec<usize>)> = Vec::new();
+    let mut by_slot: HashMap<u16, usize> = HashMap::new();
+    for (idx, key) in keys.iter().enumerate() {
+        let slot = hash_slot(key.as_bytes());
+        let group = *by_slot.entry(slot).or_insert_with(|| {
+            groups.push((slot, Vec::new()));
+            groups.len() - 1
+        });
+        groups[group].1.push(idx);
+    }
+    groups
+}
+
+/// Combine the raw replies of a split command into the reply the
+/// unsplit command would have had. Each part pairs a reply with the key
+/// positions its command covered. An error reply is returned as-is.
+fn merge_replies(merge: Merge, key_count: usize, parts: Vec<(Vec<usize>, Bytes)>) -> Result<Bytes> {
+    if let Some((_, error)) = parts.iter().find(|(_, raw)| matches!(raw.first(), Some(b'-' | b'!'))) {
+        return Ok(error.clone());
+    }
+    let malformed = || PyrsedisError::Protocol("unexpected reply to a split multi-key command".into());
+    match merge {
+        Merge::Sum => {
+            let mut total = 0i64;
+            for (_, raw) in &parts {
+                let digits = raw.strip_prefix(b":").and_then(|r| r.strip_suffix(b"\r\n")).ok_or_else(malformed)?;
+                total += std::str::from_utf8(digits).ok().and_then(|d| d.parse::<i64>().ok()).ok_or_else(malformed)?;
             }
-            None
+            Ok(Bytes::from(format!(":{total}\r\n")))
         }
-        // XREAD/XREADGROUP: key follows "STREAMS" keyword
-        "XREAD" | "XREADGROUP" => {
-            for (i, arg) in args.iter().enumerate() {
-                if arg.eq_ignore_ascii_case("STREAMS") && i + 1 < args.len() {
-                    return Some(args[i + 1]);
+        Merge::Gather => {
+            let mut frames = vec![Bytes::new(); key_count];
+            for (indices, raw) in parts {
+                let header = raw.iter().position(|&b| b == b'\n').ok_or_else(malformed)? + 1;
+                // `*` plus at least one digit before the CRLF
+                if !raw.starts_with(b"*") || header < 4 {
+                    return Err(malformed());
+                }
+                let count = std::str::from_utf8(&raw[1..header - 2]).ok().and_then(|c| c.parse::<usize>().ok());
+                if count != Some(indices.len()) {
+                    return Err(malformed());
+                }
+                let mut pos = header;
+                for idx in indices {
+                    let len = resp_frame_len(&raw[pos..])?;
+                    frames[idx] = raw.slice(pos..pos + len);
+                    pos += len;
                 }
             }
-            None
+            let mut merged = format!("*{key_count}\r\n").into_bytes();
+            for frame in &frames {
+                merged.extend_from_slice(frame);
+            }
+            Ok(Bytes::from(merged))
         }
-        // Default: key at position 1
-        _ => args.get(1).copied(),
     }
 }
 
@@ -308,43 +486,59 @@ pub struct ClusterRouter {
     slot_map: RwLock<SlotMap>,
     /// Base config (used for creating new node pools).
     config: ConnectionConfig,
-    /// Whether to route reads to replicas.
-    read_from_replicas: bool,
+    /// Key under which slot maps are shared via the topology registry.
+    seeds_key: String,
+    /// Set whenever a command is routed; the background refresh clears it
+    /// and skips its turn when it was not set.
+    active: AtomicBool,
+    /// MOVED redirects seen since the slot map was last fetched in full.
+    moved: AtomicUsize,
+    /// When a MOVED redirect last triggered a full refresh.
+    moved_refresh_at: Mutex<Option<Instant>>,
+    /// Circuit breaker per node, keyed like `nodes`.
+    breakers: Mutex<HashMap<String, Arc<CircuitBreaker>>>,
+    /// Round trip and zone per node, kept while the read preference
+    /// needs them.
+    samples: Mutex<HashMap<String, Arc<NodeSample>>>,
+    /// Key-less commands routed so far, for round-robin routing.
+    keyless_turn: AtomicUsize,
+    /// Nodes being drained, with the slot ranges each one served as
+    /// master when the drain started.
+    draining: Mutex<HashMap<String, Vec<(u16, u16)>>>,
 }
 
 impl ClusterRouter {
     /// Create a new cluster router from seed nodes.
     ///
     /// Connects to the first available seed node, runs `CLUSTER SLOTS`,
-    /// and builds the initial slot map + per-node pools.
-    pub async fn new(
-        seeds: Vec<(String, u16)>,
-        config: ConnectionConfig,
-        read_from_replicas: bool,
-    ) -> Result<Arc<Self>> {
+    /// and builds the initial slot map + per-node pools. When the topology
+    /// registry is enabled and holds a fresh slot map for the same seeds,
+    /// that map is used instead and no connection is made.
+    ///
+    /// Read-only commands are routed by `config.read_preference`.
+    pub async fn new(seeds: Vec<(String, u16)>, config: ConnectionConfig) -> Result<Arc<Self>> {
         if seeds.is_empty() {
             return Err(PyrsedisError::Cluster(
                 "at least one seed node is required".into(),
             ));
         }
 
-        let router = Arc::new(Self {
-            nodes: RwLock::new(HashMap::new()),
-            slot_map: RwLock::new(SlotMap::default()),
-            config,
-            read_from_replicas,
-        });
+        let router = Arc::new(Self::unconnected(config, registry::seeds_key(&seeds)));
 
         // Connect to first available seed and refresh slot map
         let mut last_err = None;
-        for (host, port) in &seeds {
-            let addr = format!("{host}:{port}");
-            match router.refresh_slots_from(&addr).await {
-                Ok(_) => {
-                    last_err = None;
-                    break;
+        if let Some(map) = registry::slot_map(&router.seeds_key) {
+            router.install_slot_map(map);
+        } else {
+            for (host, port) in &seeds {
+                let addr = format!("{host}:{port}");
+                match router.refresh_slots_from(&addr).await {
+                    Ok(_) => {
+                        last_err = None;
+                        break;
+                    }
+                    Err(e) => last_err = Some(e),
                 }
-                Err(e) => last_err = Some(e),
             }
         }
         if let Some(e) = last_err {
@@ -353,78 +547,227 @@ impl ClusterRouter {
             )));
         }
 
+        if router.config.read_preference.needs_sampling() {
+            let weak = Arc::downgrade(&router);
+            runtime::spawn(async move {
+                loop {
+                    tokio::time::sleep(SAMPLE_INTERVAL).await;
+                    let Some(router) = weak.upgrade() else {
+                        break; // Router dropped, exit
+                    };
+                    router.sample_nodes();
+                }
+            });
+        }
+
         // Start background slot refresh
-        let weak = Arc::downgrade(&router);
-        runtime::spawn(async move {
-            loop {
-                tokio::time::sleep(SLOT_REFRESH_INTERVAL).await;
-                let Some(router) = weak.upgrade() else {
-                    break; // Router dropped, exit
-                };
-                // Pick any known node and refresh
-                let addr = {
-                    let nodes = router.nodes.read();
-                    nodes.keys().next().cloned()
-                };
-                if let Some(addr) = addr {
-                    let _ = router.refresh_slots_from(&addr).await;
+        let interval = Duration::from_millis(router.config.slot_refresh_interval_ms);
+        if !interval.is_zero() {
+            let weak = Arc::downgrade(&router);
+            runtime::spawn(async move {
+                loop {
+                    tokio::time::sleep(jittered(interval)).await;
+                    let Some(router) = weak.upgrade() else {
+                        break; // Router dropped, exit
+                    };
+                    // An idle client has nothing to route; a stale map is
+                    // repaired by MOVED redirects once commands resume.
+                    if !router.active.swap(false, Ordering::Relaxed) {
+                        continue;
+                    }
+                    // Pick any known node and refresh
+                    let addr = {
+                        let nodes = router.nodes.read();
+                        nodes.keys().next().cloned()
+                    };
+                    if let Some(addr) = addr {
+                        // A hung node must not hold up the next refresh
+                        let _ = tokio::time::timeout(interval, router.refresh_slots_from(&addr)).await;
+                    }
                 }
-            }
-        });
+            });
+        }
 
         Ok(router)
     }
 
+    /// A router without a slot map, for the seeds in `seeds_key`.
+    fn unconnected(config: ConnectionConfig, seeds_key: String) -> Self {
+        Self {
+            nodes: RwLock::new(HashMap::new()),
+            slot_map: RwLock::new(SlotMap::default()),
+            config,
+            seeds_key,
+            active: AtomicBool::new(false),
+            moved: AtomicUsize::new(0),
+            moved_refresh_at: Mutex::new(None),
+            breakers: Mutex::new(HashMap::new()),
+            samples: Mutex::new(HashMap::new()),
+            keyless_turn: AtomicUsize::new(0),
+            draining: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Ping every known node in the background and record the round trip
+    /// (and, the first time, the zone) in its [`NodeSample`].
+    fn sample_nodes(self: &Arc<Self>) {
+        let nodes: Vec<(String, Arc<ConnectionPool>)> =
+            self.nodes.read().iter().map(|(addr, pool)| (addr.clone(), Arc::clone(pool))).collect();
+        for (addr, pool) in nodes {
+            let sample = Arc::clone(self.samples.lock().entry(addr).or_default());
+            runtime::spawn(async move {
+                let ping = async {
+                    let mut guard = pool.get().await?;
+                    if !sample.zone_known() {
+                        sample.set_zone(&guard.conn().execute_str(&["HELLO"]).await?);
+                    }
+                    let start = Instant::now();
+                    let pong = guard.conn().execute_str(&["PING"]).await?;
+                    Ok::<_, PyrsedisError>((start.elapsed(), pong))
+                };
+                match tokio::time::timeout(SAMPLE_INTERVAL, ping).await {
+                    Ok(Ok((rtt, RespValue::SimpleString(_)))) => sample.record_rtt(rtt),
+                    _ => sample.forget_rtt(),
+                }
+            });
+        }
+    }
+
     /// Refresh the slot map by querying a specific node.
+    ///
+    /// Uses `CLUSTER SHARDS` and falls back to `CLUSTER SLOTS` on servers
+    /// older than Redis 7.
     async fn refresh_slots_from(&self, addr: &str) -> Result<()> {
-        let timeout = Duration::from_millis(self.config.connect_timeout_ms);
-        let mut conn =
-            RedisConnection::connect_timeout_with_max_buf(addr, timeout, self.config.max_buffer_size)
-                .await?;
+        let mut conn = RedisConnection::connect_with_config(&self.remapped(addr), &self.config).await?;
 
-        // Auth if needed
-        conn.init(
-            self.config.username.as_deref(),
-            self.config.password.as_deref(),
-            0, // Cluster doesn't use DB selection
-        )
-        .await?;
+        // Cluster doesn't use DB selection
+        conn.init_from(&self.config, 0).await?;
 
-        let resp = conn.execute_str(&["CLUSTER", "SLOTS"]).await?;
-        let new_map = SlotMap::from_cluster_slots(&resp)?;
+        let new_map = match conn.execute_str(&["CLUSTER", "SHARDS"]).await? {
+            RespValue::Error(_) | RespValue::BulkError(_) => {
+                SlotMap::from_cluster_slots(&conn.execute_str(&["CLUSTER", "SLOTS"]).await?)?
+            }
+            shards => SlotMap::from_cluster_shards(&shards, self.config.tls)?,
+        };
+        registry::store_slot_map(&self.seeds_key, &new_map);
+        self.install_slot_map(new_map);
+        self.moved.store(0, Ordering::Relaxed);
+        Ok(())
+    }
 
-        // Ensure pools exist for all nodes in the new map
+    /// Point `slot` at `addr` after a MOVED redirect.
+    ///
+    /// During resharding many slots move at once, so patching them one
+    /// redirect at a time costs a round trip each. Once
+    /// [`MOVED_REFRESH_THRESHOLD`] redirects have piled up, the whole map
+    /// is re-fetched from `addr` instead, at most once per
+    /// [`MOVED_REFRESH_DEBOUNCE`].
+    async fn handle_moved(&self, slot: u16, addr: &str) {
+        self.slot_map.write().update_slot_master(slot, addr);
+        if self.moved.fetch_add(1, Ordering::Relaxed) + 1 < MOVED_REFRESH_THRESHOLD {
+            return;
+        }
+        {
+            let mut last = self.moved_refresh_at.lock();
+            if last.is_some_and(|at| at.elapsed() < MOVED_REFRESH_DEBOUNCE) {
+                return;
+            }
+            *last = Some(Instant::now());
+        }
+        // The patched slot already routes this command; a failed refresh
+        // just leaves the rest to further redirects.
+        let _ = self.refresh_slots_from(addr).await;
+    }
+
+    /// Create pools for every node in `map` and make it the active map.
+    ///
+    /// Drained masters that no longer own the slots they had when the
+    /// drain started are released.
+    fn install_slot_map(&self, map: SlotMap) {
+        let mut missing: Vec<&String> = {
+            let nodes = self.nodes.read();
+            map.ranges
+                .iter()
+                .flat_map(|range| std::iter::once(&range.master).chain(&range.replicas))
+                .filter(|addr| !nodes.contains_key(addr.as_str()))
+                .collect()
+        };
+        missing.sort_unstable();
+        missing.dedup();
+        // Configured before taking the lock: an address remap callback
+        // needs the GIL
+        let configs: Vec<_> = missing
+            .into_iter()
+            .filter_map(|addr| Some((addr, self.node_config(addr, map.is_replica(addr))?)))
+            .collect();
         {
             let mut nodes = self.nodes.write();
-            for range in &new_map.ranges {
-                self.ensure_pool_for(&mut nodes, &range.master);
-                for replica in &range.replicas {
-                    self.ensure_pool_for(&mut nodes, replica);
-                }
+            for (addr, cfg) in configs {
+                nodes.entry(addr.clone()).or_insert_with(|| ConnectionPool::shared(cfg));
             }
         }
+        self.draining.lock().retain(|addr, owned| {
+            owned.is_empty() || owned.iter().any(|&(start, _)| map.master_for_slot(start) == Some(addr.as_str()))
+        });
+        *self.slot_map.write() = map;
+    }
 
-        // Install the new slot map
-        *self.slot_map.write() = new_map;
+    /// Stop routing reads and key-less commands to the node at `addr`
+    /// ahead of maintenance. Writes to a drained master's slots still go
+    /// to it, as no other node can take them, until a failover moves the
+    /// slots; the drain then ends by itself with the next slot map
+    /// refresh. A drained replica stays drained until
+    /// [`undrain_node`](Self::undrain_node).
+    pub fn drain_node(&self, addr: &str) -> Result<()> {
+        let map = self.slot_map.read();
+        if !map.has_node(addr) {
+            return Err(PyrsedisError::Cluster(format!("{addr} is not a node of the cluster")));
+        }
+        let owned = map.ranges.iter().filter(|range| range.master == addr).map(|range| (range.start, range.end)).collect();
+        self.draining.lock().insert(addr.to_string(), owned);
         Ok(())
     }
 
-    /// Ensure a connection pool exists for the given address.
-    fn ensure_pool_for(&self, nodes: &mut HashMap<String, Arc<ConnectionPool>>, addr: &str) {
-        if !nodes.contains_key(addr) {
-            let parts: Vec<&str> = addr.rsplitn(2, ':').collect();
-            if parts.len() == 2 {
-                let mut cfg = self.config.clone();
-                cfg.host = parts[1].to_string();
-                cfg.port = parts[0].parse().unwrap_or(6379);
-                cfg.db = 0; // Cluster doesn't use DB selection
-                nodes.insert(addr.to_string(), Arc::new(ConnectionPool::new(cfg)));
-            }
+    /// Route to `addr` again; `false` if it was not being drained.
+    pub fn undrain_node(&self, addr: &str) -> bool {
+        self.draining.lock().remove(addr).is_some()
+    }
+
+    /// The nodes being drained, sorted.
+    pub fn draining_nodes(&self) -> Vec<String> {
+        let mut nodes: Vec<String> = self.draining.lock().keys().cloned().collect();
+        nodes.sort_unstable();
+        nodes
+    }
+
+    /// The address to connect to for the announced node `addr`, after
+    /// any `address_remap`.
+    fn remapped(&self, addr: &str) -> String {
+        match &self.config.address_remap {
+            Some(remap) => remap.apply(addr),
+            None => addr.to_string(),
         }
     }
 
+    /// Pool config for the node announced as `addr`, or `None` if the
+    /// address has no port. Connections to a `replica` send `READONLY`.
+    fn node_config(&self, addr: &str, replica: bool) -> Option<ConnectionConfig> {
+        let target = self.remapped(addr);
+        let (host, port) = target.rsplit_once(':')?;
+        let mut cfg = self.config.clone();
+        cfg.host = host.to_string();
+        cfg.port = port.parse().unwrap_or(6379);
+        cfg.db = 0; // Cluster doesn't use DB selection
+        cfg.read_only = replica;
+        Some(cfg)
+    }
+
     /// Get the connection pool for a given address, creating if needed.
+    ///
+    /// Every routed command goes through here, so this also marks the
+    /// router as active for the background refresh.
     fn get_pool(&self, addr: &str) -> Arc<ConnectionPool> {
+        self.active.store(true, Ordering::Relaxed);
         // Fast path: read lock
         {
             let nodes = self.nodes.read();
@@ -433,12 +776,40 @@ impl ClusterRouter {
             }
         }
         // Slow path: write lock, create pool
-        let mut nodes = self.nodes.write();
-        self.ensure_pool_for(&mut nodes, addr);
-        nodes.get(addr).cloned().unwrap_or_else(|| {
+        let replica = self.slot_map.read().is_replica(addr);
+        let Some(cfg) = self.node_config(addr, replica) else {
             // Fallback: create with default config
-            Arc::new(ConnectionPool::new(self.config.clone()))
-        })
+            return ConnectionPool::shared(self.config.clone());
+        };
+        let mut nodes = self.nodes.write();
+        nodes.entry(addr.to_string()).or_insert_with(|| ConnectionPool::shared(cfg)).clone()
+    }
+
+    /// The circuit breaker of the node at `addr`, creating it if needed.
+    fn breaker(&self, addr: &str) -> Arc<CircuitBreaker> {
+        Arc::clone(self.breakers.lock().entry(addr.to_string()).or_default())
+    }
+
+    /// Whether the breaker of the node at `addr` is rejecting requests.
+    fn is_tripped(&self, addr: &str) -> bool {
+        self.breakers.lock().get(addr).is_some_and(|breaker| breaker.is_open())
+    }
+
+    /// Whether routing should avoid the node at `addr`: its breaker is
+    /// tripped or it is being drained.
+    fn is_passed_over(&self, addr: &str) -> bool {
+        self.is_tripped(addr) || self.draining.lock().contains_key(addr)
+    }
+
+    /// Run `request` against the node at `addr` through its circuit
+    /// breaker: fail fast while the breaker is open, and count the
+    /// outcome otherwise.
+    async fn through_breaker<T>(&self, addr: &str, request: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+        let breaker = self.breaker(addr);
+        breaker.admit(addr)?;
+        let result = request.await;
+        breaker.record(&result);
+        result
     }
 
     /// Route a command to the correct node, handling MOVED/ASK.
@@ -446,35 +817,451 @@ impl ClusterRouter {
         if args.is_empty() {
             return Err(PyrsedisError::Protocol("empty command".into()));
         }
-        let slot = extract_key(args).map(|k| hash_slot(k.as_bytes()));
-        let is_read = is_read_only_command(args[0]);
+        let addr = self.node_for(command_slot(args)?, is_read_only_command(args[0]), args[0])?;
+        self.execute_on(&addr, args, MAX_REDIRECTS).await
+    }
 
-        // Determine target node
-        let addr = if let Some(slot) = slot {
-            let map = self.slot_map.read();
-            if is_read && self.read_from_replicas {
-                map.replica_for_slot(slot)
-                    .unwrap_or_else(|| map.master_for_slot(slot).unwrap_or(""))
-                    .to_string()
-            } else {
-                map.master_for_slot(slot).unwrap_or("").to_string()
+    /// Address of the node serving `slot`, or of the master picked by
+    /// [`keyless_node`](Self::keyless_node) for a key-less `command`.
+    ///
+    /// Reads go where [`read_node`](Self::read_node) sends them. Returns an
+    /// empty string when the slot map has no owner, or no node can serve
+    /// the read.
+    fn node_addr(&self, slot: Option<u16>, is_read: bool, command: &str) -> String {
+        let map = self.slot_map.read();
+        let Some(slot) = slot else {
+            return self.keyless_node(&map, self.config.keyless_routing_for(command)).unwrap_or("").to_string();
+        };
+        if !is_read {
+            return map.master_for_slot(slot).unwrap_or("").to_string();
+        }
+        map.range_for_slot(slot).and_then(|range| self.read_node(range, slot)).unwrap_or("").to_string()
+    }
+
+    /// The master that serves a key-less command under `routing`,
+    /// skipping masters whose breaker is tripped or that are drained.
+    fn keyless_node<'a>(&self, map: &'a SlotMap, routing: KeylessRouting) -> Option<&'a str> {
+        let mut masters: Vec<&'a str> = Vec::new();
+        for range in &map.ranges {
+            if !masters.contains(&range.master.as_str()) {
+                masters.push(&range.master);
+            }
+        }
+        let up: Vec<&'a str> = masters.iter().copied().filter(|addr| !self.is_passed_over(addr)).collect();
+        let nodes = if up.is_empty() { masters } else { up };
+        if nodes.is_empty() {
+            return None;
+        }
+        let turn = match routing {
+            KeylessRouting::First => 0,
+            KeylessRouting::RoundRobin => self.keyless_turn.fetch_add(1, Ordering::Relaxed),
+            KeylessRouting::Random => RandomState::new().hash_one(Instant::now()) as usize,
+            KeylessRouting::LeastLoaded => {
+                // Start the scan at the next turn so ties rotate
+                let start = self.keyless_turn.fetch_add(1, Ordering::Relaxed);
+                let pools = self.nodes.read();
+                let in_use = |addr: &str| pools.get(addr).map_or(0, |p| p.max_size().saturating_sub(p.available()));
+                (start..start + nodes.len()).min_by_key(|i| in_use(nodes[i % nodes.len()])).unwrap_or(start)
             }
-        } else {
-            // Key-less command: pick any master
-            let map = self.slot_map.read();
-            map.ranges
-                .first()
-                .map(|r| r.master.clone())
-                .unwrap_or_default()
         };
+        Some(nodes[turn % nodes.len()])
+    }
+
+    /// The node of `range` that serves a read of `slot` under the read
+    /// preference, or `None` if a replica is required and none is up.
+    ///
+    /// Nodes whose circuit breaker is tripped, or that are drained, are
+    /// passed over; a read the master should serve goes to a replica while
+    /// the master is. Replicas are picked round-robin by slot.
+    fn read_node<'a>(&self, range: &'a SlotRange, slot: u16) -> Option<&'a str> {
+        let replicas: Vec<&'a str> =
+            range.replicas.iter().map(String::as_str).filter(|addr| !self.is_passed_over(addr)).collect();
+        let pick = |nodes: &[&'a str]| (!nodes.is_empty()).then(|| nodes[slot as usize % nodes.len()]);
+        let master = range.master.as_str();
+        match &self.config.read_preference {
+            ReadPreference::Primary if self.is_passed_over(master) => pick(&replicas).or(Some(master)),
+            ReadPreference::Primary => Some(master),
+            ReadPreference::PreferReplica => pick(&replicas).or(Some(master)),
+            ReadPreference::ReplicaRequired => pick(&replicas),
+            ReadPreference::LowestLatency => {
+                let samples = self.samples.lock();
+                let rtt = |addr: &str| samples.get(addr).and_then(|s| s.rtt()).unwrap_or(Duration::MAX);
+                // Ties, such as before the first ping, go to the master
+                let master_up = (!self.is_passed_over(master)).then_some(master);
+                master_up.into_iter().chain(replicas).min_by_key(|addr| rtt(addr)).or(Some(master))
+            }
+            ReadPreference::AzAffinity(zone) => {
+                let local: Vec<&'a str> = {
+                    let samples = self.samples.lock();
+                    let in_zone = |addr: &&str| samples.get(*addr).and_then(|s| s.zone()).as_ref() == Some(zone);
+                    replicas.iter().copied().filter(in_zone).collect()
+                };
+                pick(&local).or_else(|| pick(&replicas)).or(Some(master))
+            }
+        }
+    }
 
+    /// Like [`node_addr`](Self::node_addr), failing when no node owns the slot.
+    fn node_for(&self, slot: Option<u16>, is_read: bool, command: &str) -> Result<String> {
+        let addr = self.node_addr(slot, is_read, command);
         if addr.is_empty() {
             return Err(PyrsedisError::Cluster(
                 "no node available for command".into(),
             ));
         }
+        Ok(addr)
+    }
 
-        self.execute_on(&addr, args, MAX_REDIRECTS).await
+    // ── Raw single-pass path ──
+
+    /// Execute a command and return the raw RESP frame, following
+    /// MOVED/ASK redirects.
+    pub async fn execute_raw(&self, args: &[&str]) -> Result<Bytes> {
+        if args.is_empty() {
+            return Err(PyrsedisError::Protocol("empty command".into()));
+        }
+        let addr = self.node_for(command_slot(args)?, is_read_only_command(args[0]), args[0])?;
+        self.execute_raw_on(&addr, &encode_command_str(args), false, MAX_REDIRECTS).await
+    }
+
+    /// Like [`execute_raw`](Self::execute_raw), with binary-safe arguments.
+    pub async fn execute_raw_bytes(&self, args: &[&[u8]]) -> Result<Bytes> {
+        if args.is_empty() {
+            return Err(PyrsedisError::Protocol("empty command".into()));
+        }
+        // Routing only inspects the command name and numeric arguments;
+        // the key itself is hashed from its raw bytes.
+        let text: Vec<&str> = args.iter().map(|a| std::str::from_utf8(a).unwrap_or("")).collect();
+        let name = text[0].to_ascii_uppercase();
+        let slot = shared_slot(&name, key_positions(&text).into_iter().map(|i| args[i]))?;
+        let addr = self.node_for(slot, is_read_only_command(text[0]), text[0])?;
+        self.execute_raw_on(&addr, &encode_command(args), false, MAX_REDIRECTS).await
+    }
+
+    /// Like [`execute_raw_bytes`](Self::execute_raw_bytes), on the node
+    /// `route` picks whatever the read preference. Redirects are still
+    /// followed, so a write sent to a replica ends up on its master.
+    pub async fn execute_raw_bytes_at(&self, route: &Route, args: &[&[u8]]) -> Result<Bytes> {
+        let addr = match route {
+            Route::Node(addr) if !self.slot_map.read().has_node(addr) => {
+                return Err(PyrsedisError::Cluster(format!("{addr} is not a node of the cluster")));
+            }
+            Route::Node(addr) => addr.clone(),
+            Route::Slot(slot) => self.node_for(Some(*slot), false, "")?,
+            Route::Master | Route::Replica => {
+                let text: Vec<&str> = args.iter().map(|a| std::str::from_utf8(a).unwrap_or("")).collect();
+                let name = text.first().map(|n| n.to_ascii_uppercase()).unwrap_or_default();
+                let slot = shared_slot(&name, key_positions(&text).into_iter().map(|i| args[i]))?;
+                if *route == Route::Master {
+                    self.node_for(slot, false, &name)?
+                } else {
+                    self.replica_for(slot)?
+                }
+            }
+        };
+        self.execute_raw_on(&addr, &encode_command(args), false, MAX_REDIRECTS).await
+    }
+
+    /// A replica of the range owning `slot`, picked by slot among those
+    /// whose breaker is closed and that are not drained.
+    fn replica_for(&self, slot: Option<u16>) -> Result<String> {
+        let Some(slot) = slot else {
+            return Err(PyrsedisError::Type("route='replica' needs a command with keys; use ('node', addr)".into()));
+        };
+        let map = self.slot_map.read();
+        let replicas: Vec<&str> = map
+            .range_for_slot(slot)
+            .map(|range| range.replicas.iter().map(String::as_str).filter(|addr| !self.is_passed_over(addr)).collect())
+            .unwrap_or_default();
+        match replicas.len() {
+            0 => Err(PyrsedisError::Cluster(format!("no replica available for slot {slot}"))),
+            n => Ok(replicas[slot as usize % n].to_string()),
+        }
+    }
+
+    /// Like [`execute_raw`](Self::execute_raw), splitting a multi-key
+    /// command whose keys span several slots when `allow_cross_slot` is
+    /// set.
+    ///
+    /// Each slot gets its own command; the commands for one node are
+    /// pipelined, nodes run concurrently, and the replies are merged in
+    /// key order (see [`merge_replies`]).
+    pub async fn execute_raw_split(self: &Arc<Self>, args: &[&str]) -> Result<Bytes> {
+        let merge = match args.first() {
+            Some(command) if self.config.allow_cross_slot => split_merge(command),
+            _ => None,
+        };
+        let keys = args.get(1..).unwrap_or_default();
+        let groups = slot_groups(keys);
+        let Some(merge) = merge.filter(|_| groups.len() > 1) else {
+            return self.execute_raw(args).await;
+        };
+
+        let is_read = is_read_only_command(args[0]);
+        let mut by_node: HashMap<String, SplitBatch> = HashMap::new();
+        for (slot, indices) in groups {
+            let mut cmd = Vec::with_capacity(indices.len() + 1);
+            cmd.push(args[0].to_string());
+            cmd.extend(indices.iter().map(|&idx| keys[idx].to_string()));
+            let (positions, commands) = by_node.entry(self.node_for(Some(slot), is_read, args[0])?).or_default();
+            positions.push(indices);
+            commands.push(cmd);
+        }
+        let tasks: Vec<_> = by_node
+            .into_values()
+            .map(|(positions, commands)| {
+                let router = Arc::clone(self);
+                runtime::spawn(async move {
+                    let replies = router.pipeline_raw(&commands).await?;
+                    Ok::<_, PyrsedisError>(positions.into_iter().zip(replies).collect::<Vec<_>>())
+                })
+            })
+            .collect();
+        let mut parts = Vec::new();
+        for task in tasks {
+            let replies = task
+                .await
+                .unwrap_or_else(|e| Err(PyrsedisError::Cluster(format!("fan-out task failed: {e}"))))?;
+            parts.extend(replies);
+        }
+        merge_replies(merge, keys.len(), parts)
+    }
+
+    /// Execute a pipeline and return raw RESP frames in command order.
+    ///
+    /// Commands are grouped per node and each group is sent in one write.
+    /// Redirected commands are re-sent individually to their new node.
+    pub async fn pipeline_raw(&self, commands: &[Vec<String>]) -> Result<Vec<Bytes>> {
+        let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+        for (idx, cmd_args) in commands.iter().enumerate() {
+            let refs: Vec<&str> = cmd_args.iter().map(|s| s.as_str()).collect();
+            let is_read = !refs.is_empty() && is_read_only_command(refs[0]);
+            groups.entry(self.node_addr(command_slot(&refs)?, is_read, refs.first().copied().unwrap_or(""))).or_default().push(idx);
+        }
+
+        let mut results: Vec<Bytes> = vec![Bytes::new(); commands.len()];
+        for (addr, group) in &groups {
+            if addr.is_empty() {
+                for idx in group {
+                    results[*idx] = Bytes::from_static(b"-ERR no node for slot\r\n");
+                }
+                continue;
+            }
+            let encoded: Vec<Vec<u8>> = group
+                .iter()
+                .map(|idx| {
+                    let refs: Vec<&str> = commands[*idx].iter().map(|s| s.as_str()).collect();
+                    encode_command_str(&refs)
+                })
+                .collect();
+            let replies = self
+                .through_breaker(addr, async {
+                    let pool = self.get_pool(addr);
+                    let mut guard = pool.get_bulk().await?;
+                    guard.conn().send_raw(&encoded.concat()).await?;
+                    let mut replies = Vec::with_capacity(encoded.len());
+                    for _ in &encoded {
+                        replies.push(guard.conn().read_raw_response().await?);
+                    }
+                    Ok(replies)
+                })
+                .await?;
+
+            let mut redirected = Vec::new();
+            for ((idx, cmd), raw) in group.iter().zip(&encoded).zip(replies) {
+                match raw_error(&raw) {
+                    Some((kind @ (RedisErrorKind::Moved { .. } | RedisErrorKind::Ask { .. }), _)) => {
+                        redirected.push((*idx, cmd, kind));
+                    }
+                    _ => results[*idx] = raw,
+                }
+            }
+
+            for (idx, cmd, kind) in redirected {
+                results[idx] = self.follow_raw_redirect(kind, cmd, MAX_REDIRECTS - 1).await?;
+            }
+        }
+        Ok(results)
+    }
+
+    /// Run `commands` as one MULTI/EXEC transaction and return the raw
+    /// replies to MULTI, each queued command and EXEC.
+    ///
+    /// Every keyed command must hash to the same slot; the transaction is
+    /// sent to that slot's master. Redirects are not followed: a MOVED
+    /// reply to a queued command aborts the transaction anyway.
+    pub async fn transaction_raw(&self, commands: &[Vec<String>]) -> Result<Vec<Bytes>> {
+        let mut slot = None;
+        for cmd in commands {
+            let refs: Vec<&str> = cmd.iter().map(|s| s.as_str()).collect();
+            if let Some(key_slot) = command_slot(&refs)? {
+                if slot.is_some_and(|s| s != key_slot) {
+                    return Err(PyrsedisError::Cluster(
+                        "all keys in a transaction must hash to the same slot".into(),
+                    ));
+                }
+                slot = Some(key_slot);
+            }
+        }
+        let addr = self.node_for(slot, false, "")?;
+        let wrapped = wrap_transaction(commands);
+        self.through_breaker(&addr, async {
+            let pool = self.get_pool(&addr);
+            let mut guard = pool.get_bulk().await?;
+            let result = async {
+                guard.conn().send_raw(&encode_pipeline(&wrapped)).await?;
+                let mut replies = Vec::with_capacity(wrapped.len());
+                for _ in &wrapped {
+                    replies.push(guard.conn().read_raw_response().await?);
+                }
+                Ok(replies)
+            }
+            .await;
+            if result.is_err() {
+                // Possibly mid-transaction — never hand it back to the pool
+                drop(guard.take());
+            }
+            result
+        })
+        .await
+    }
+
+    /// Addresses of every master in the slot map, sorted.
+    pub fn masters(&self) -> Vec<String> {
+        let map = self.slot_map.read();
+        let mut masters: Vec<String> = map.ranges.iter().map(|r| r.master.clone()).collect();
+        masters.sort();
+        masters.dedup();
+        masters
+    }
+
+    /// Run a command on every master concurrently and return each node's
+    /// raw reply or error, in [`masters`](Self::masters) order.
+    ///
+    /// One node failing does not affect the others, so callers can report
+    /// partial failures per node.
+    pub async fn execute_on_masters_raw(self: &Arc<Self>, args: &[&str]) -> Vec<(String, Result<Bytes>)> {
+        self.execute_encoded_on_masters(encode_command_str(args)).await
+    }
+
+    /// Like [`execute_on_masters_raw`](Self::execute_on_masters_raw), with
+    /// binary-safe arguments.
+    pub async fn execute_on_masters_raw_bytes(self: &Arc<Self>, args: &[&[u8]]) -> Vec<(String, Result<Bytes>)> {
+        self.execute_encoded_on_masters(encode_command(args)).await
+    }
+
+    async fn execute_encoded_on_masters(self: &Arc<Self>, cmd: Vec<u8>) -> Vec<(String, Result<Bytes>)> {
+        let cmd = Arc::new(cmd);
+        let tasks: Vec<_> = self
+            .masters()
+            .into_iter()
+            .map(|addr| {
+                let router = Arc::clone(self);
+                let cmd = Arc::clone(&cmd);
+                let task = runtime::spawn({
+                    let addr = addr.clone();
+                    async move { router.execute_raw_on(&addr, &cmd, false, MAX_REDIRECTS).await }
+                });
+                (addr, task)
+            })
+            .collect();
+        let mut results = Vec::with_capacity(tasks.len());
+        for (addr, task) in tasks {
+            let result = task
+                .await
+                .unwrap_or_else(|e| Err(PyrsedisError::Cluster(format!("fan-out task failed: {e}"))));
+            results.push((addr, result));
+        }
+        results
+    }
+
+    /// Run a command on the node at `addr` and return its raw reply,
+    /// following redirects.
+    pub async fn execute_raw_at(&self, addr: &str, args: &[&str]) -> Result<Bytes> {
+        self.execute_raw_on(addr, &encode_command_str(args), false, MAX_REDIRECTS).await
+    }
+
+    /// Send an encoded command to `addr` and read the raw reply, following
+    /// redirects. With `asking`, the command is preceded by `ASKING`.
+    fn execute_raw_on<'a>(
+        &'a self,
+        addr: &'a str,
+        cmd: &'a [u8],
+        asking: bool,
+        redirects_left: usize,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Bytes>> + Send + 'a>> {
+        Box::pin(async move {
+            let raw = self
+                .through_breaker(addr, async {
+                    let pool = self.get_pool(addr);
+                    let mut guard = pool.get().await?;
+                    if asking {
+                        guard.conn().send_raw(&encode_command_str(&["ASKING"])).await?;
+                        let _ = guard.conn().read_raw_response().await?;
+                    }
+                    guard.conn().send_raw(cmd).await?;
+                    guard.conn().read_raw_response().await
+                })
+                .await?;
+
+            match raw_error(&raw) {
+                Some((kind @ (RedisErrorKind::Moved { .. } | RedisErrorKind::Ask { .. }), _)) => {
+                    self.follow_raw_redirect(kind, cmd, redirects_left).await
+                }
+                Some((RedisErrorKind::ClusterDown, msg)) => Err(PyrsedisError::Cluster(msg)),
+                Some((RedisErrorKind::TryAgain, msg)) => {
+                    if redirects_left == 0 {
+                        return Err(PyrsedisError::redis(msg));
+                    }
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    self.execute_raw_on(addr, cmd, asking, redirects_left - 1).await
+                }
+                _ => Ok(raw),
+            }
+        })
+    }
+
+    /// Re-send `cmd` to the node named by a MOVED or ASK redirect.
+    async fn follow_raw_redirect(&self, redirect: RedisErrorKind, cmd: &[u8], redirects_left: usize) -> Result<Bytes> {
+        match redirect {
+            RedisErrorKind::Moved { .. } if redirects_left == 0 => {
+                Err(PyrsedisError::Cluster("too many MOVED redirects".into()))
+            }
+            RedisErrorKind::Moved { slot, addr } => {
+                self.handle_moved(slot, &addr).await;
+                self.execute_raw_on(&addr, cmd, false, redirects_left - 1).await
+            }
+            RedisErrorKind::Ask { .. } if redirects_left == 0 => {
+                Err(PyrsedisError::Cluster("too many ASK redirects".into()))
+            }
+            RedisErrorKind::Ask { addr, .. } => self.execute_raw_on(&addr, cmd, true, redirects_left - 1).await,
+            other => Err(PyrsedisError::Cluster(format!("unexpected redirect: {other:?}"))),
+        }
+    }
+
+    /// Check out a connection to one of the masters for exclusive use.
+    ///
+    /// Cluster leases are not returned to a pool: [`release`](Self::release)
+    /// closes them.
+    pub async fn lease(&self) -> Result<LeasedConnection> {
+        let addr = self.node_for(None, false, "")?;
+        self.get_pool(&addr).lease().await
+    }
+
+    /// Check out a connection to the master serving `key`.
+    pub async fn lease_for(&self, key: &str) -> Result<LeasedConnection> {
+        let addr = self.node_for(Some(hash_slot(key.as_bytes())), false, "")?;
+        self.get_pool(&addr).lease().await
+    }
+
+    /// Close a connection obtained from [`lease`](Self::lease).
+    pub async fn release(&self, lease: LeasedConnection) {
+        drop(lease);
+    }
+
+    /// Comma-separated seed addresses, for display.
+    pub fn seeds(&self) -> &str {
+        &self.seeds_key
     }
 
     /// Execute a command on a specific node, following redirects.
@@ -485,11 +1272,15 @@ impl ClusterRouter {
         redirects_left: usize,
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<RespValue>> + Send + 'a>> {
         Box::pin(async move {
-            let pool = self.get_pool(addr);
-            let mut guard = pool.get().await?;
             let cmd = encode_command_str(args);
-            guard.conn().send_raw(&cmd).await?;
-            let result = guard.conn().read_response().await?;
+            let result = self
+                .through_breaker(addr, async {
+                    let pool = self.get_pool(addr);
+                    let mut guard = pool.get().await?;
+                    guard.conn().send_raw(&cmd).await?;
+                    guard.conn().read_response().await
+                })
+                .await?;
 
             // Check for redirects
             if let RespValue::Error(ref msg) = result {
@@ -501,8 +1292,7 @@ impl ClusterRouter {
                                 "too many MOVED redirects".into(),
                             ));
                         }
-                        self.slot_map.write().update_slot_master(slot, &new_addr);
-                        drop(guard);
+                        self.handle_moved(slot, &new_addr).await;
                         return self.execute_on(&new_addr, args, redirects_left - 1).await;
                     }
                     RedisErrorKind::Ask { addr: new_addr, .. } => {
@@ -511,14 +1301,17 @@ impl ClusterRouter {
                                 "too many ASK redirects".into(),
                             ));
                         }
-                        drop(guard);
-                        let target_pool = self.get_pool(&new_addr);
-                        let mut target_guard = target_pool.get().await?;
-                        let asking_cmd = encode_command_str(&["ASKING"]);
-                        target_guard.conn().send_raw(&asking_cmd).await?;
-                        let _ = target_guard.conn().read_response().await?;
-                        target_guard.conn().send_raw(&cmd).await?;
-                        return target_guard.conn().read_response().await;
+                        return self
+                            .through_breaker(&new_addr, async {
+                                let target_pool = self.get_pool(&new_addr);
+                                let mut target_guard = target_pool.get().await?;
+                                let asking_cmd = encode_command_str(&["ASKING"]);
+                                target_guard.conn().send_raw(&asking_cmd).await?;
+                                let _ = target_guard.conn().read_response().await?;
+                                target_guard.conn().send_raw(&cmd).await?;
+                                target_guard.conn().read_response().await
+                            })
+                            .await;
                     }
                     RedisErrorKind::ClusterDown => {
                         return Err(PyrsedisError::Cluster(msg.clone()));
@@ -527,7 +1320,6 @@ impl ClusterRouter {
                         if redirects_left == 0 {
                             return Err(PyrsedisError::redis(msg.clone()));
                         }
-                        drop(guard);
                         tokio::time::sleep(Duration::from_millis(50)).await;
                         return self.execute_on(addr, args, redirects_left - 1).await;
                     }
@@ -551,25 +1343,8 @@ impl Router for ClusterRouter {
 
         for (idx, cmd_args) in commands.iter().enumerate() {
             let refs: Vec<&str> = cmd_args.iter().map(|s| s.as_str()).collect();
-            let slot = extract_key(&refs).map(|k| hash_slot(k.as_bytes()));
             let is_read = !refs.is_empty() && is_read_only_command(refs[0]);
-
-            let addr = if let Some(slot) = slot {
-                let map = self.slot_map.read();
-                if is_read && self.read_from_replicas {
-                    map.replica_for_slot(slot)
-                        .unwrap_or_else(|| map.master_for_slot(slot).unwrap_or(""))
-                        .to_string()
-                } else {
-                    map.master_for_slot(slot).unwrap_or("").to_string()
-                }
-            } else {
-                let map = self.slot_map.read();
-                map.ranges
-                    .first()
-                    .map(|r| r.master.clone())
-                    .unwrap_or_default()
-            };
+            let addr = self.node_addr(command_slot(&refs)?, is_read, refs.first().copied().unwrap_or(""));
 
             groups.entry(addr).or_default().push((idx, cmd_args.clone()));
         }
@@ -585,7 +1360,7 @@ impl Router for ClusterRouter {
                 continue;
             }
             let pool = self.get_pool(addr);
-            let mut guard = pool.get().await?;
+            let mut guard = pool.get_bulk().await?;
 
             // Send all commands for this node
             for (_, cmd_args) in group {
@@ -602,7 +1377,7 @@ impl Router for ClusterRouter {
                     let (kind, _) = RedisErrorKind::from_error_msg(msg);
                     match kind {
                         RedisErrorKind::Moved { slot, addr: new_addr } => {
-                            self.slot_map.write().update_slot_master(slot, &new_addr);
+                            self.handle_moved(slot, &new_addr).await;
                             let refs: Vec<&str> = cmd_args.iter().map(|s| s.as_str()).collect();
                             results[*idx] =
                                 Some(self.execute_on(&new_addr, &refs, MAX_REDIRECTS - 1).await?);
@@ -641,53 +1416,43 @@ impl Router for ClusterRouter {
     fn pool_available(&self) -> usize {
         self.nodes.read().values().map(|p| p.available()).sum()
     }
-}
 
-// ── Tests ──────────────────────────────────────────────────────────
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    // ── extract_key ──
-
-    #[test]
-    fn extract_key_get() {
-        assert_eq!(extract_key(&["GET", "mykey"]), Some("mykey"));
-    }
-
-    #[test]
-    fn extract_key_set() {
-        assert_eq!(extract_key(&["SET", "mykey", "value"]), Some("mykey"));
+    fn pool_stats(&self) -> StatsSnapshot {
+        let mut stats = StatsSnapshot::default();
+        for pool in self.nodes.read().values() {
+            stats.merge(&pool.stats());
+        }
+        stats
     }
 
-    #[test]
-    fn extract_key_ping() {
-        assert_eq!(extract_key(&["PING"]), None);
+    fn disconnect_all(&self) {
+        for pool in self.nodes.read().values() {
+            pool.disconnect_all();
+        }
     }
 
-    #[test]
-    fn extract_key_info() {
-        assert_eq!(extract_key(&["INFO", "server"]), None);
+    /// Warms the pool of every master.
+    async fn warmup(&self, count: usize) -> Result<usize> {
+        let mut opened = 0;
+        for addr in self.masters() {
+            opened += self.get_pool(&addr).warmup(count).await?;
+        }
+        Ok(opened)
     }
 
-    #[test]
-    fn extract_key_eval_with_keys() {
-        assert_eq!(
-            extract_key(&["EVAL", "return 1", "1", "mykey"]),
-            Some("mykey")
-        );
+    fn reset_after_fork(&self) {
+        for pool in self.nodes.read().values() {
+            pool.reset_after_fork();
+        }
     }
+}
 
-    #[test]
-    fn extract_key_eval_no_keys() {
-        assert_eq!(extract_key(&["EVAL", "return 1", "0"]), None);
-    }
+// ── Tests ──────────────────────────────────────────────────────────
 
-    #[test]
-    fn extract_key_empty() {
-        assert_eq!(extract_key(&[]), None);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KeylessCategory;
 
     // ── is_read_only_command ──
 
@@ -702,6 +1467,12 @@ mod tests {
         assert!(is_read_only_command("GRAPH.RO_QUERY"));
     }
 
+    #[test]
+    fn read_only_fcall_ro() {
+        assert!(is_read_only_command("FCALL_RO"));
+        assert!(!is_read_only_command("FCALL"));
+    }
+
     #[test]
     fn not_read_only_set() {
         assert!(!is_read_only_command("SET"));
@@ -746,33 +1517,136 @@ mod tests {
         assert_eq!(map.master_for_slot(16383), Some("node3:6379"));
     }
 
+    /// A router for one slot range, without connecting.
+    fn router_with(preference: ReadPreference, master: &str, replicas: &[&str]) -> ClusterRouter {
+        let config = ConnectionConfig { read_preference: preference, ..ConnectionConfig::default() };
+        let router = ClusterRouter::unconnected(config, String::new());
+        router.slot_map.write().ranges = vec![SlotRange {
+            start: 0,
+            end: 16383,
+            master: master.into(),
+            replicas: replicas.iter().map(|r| r.to_string()).collect(),
+        }];
+        router
+    }
+
+    fn trip(router: &ClusterRouter, addr: &str) {
+        let refused = || Err::<(), _>(PyrsedisError::Connection(std::io::ErrorKind::ConnectionRefused.into()));
+        for _ in 0..crate::router::breaker::FAILURE_THRESHOLD {
+            router.breaker(addr).record(&refused());
+        }
+    }
+
     #[test]
-    fn slot_map_replica_fallback() {
-        let map = SlotMap {
-            ranges: vec![SlotRange {
-                start: 0,
-                end: 16383,
-                master: "master:6379".into(),
-                replicas: vec![],
-            }],
-        };
-        // No replicas → falls back to master
-        assert_eq!(map.replica_for_slot(100), Some("master:6379"));
+    fn replica_reads_fall_back_to_master() {
+        // No replicas → falls back to master, unless one is required
+        let router = router_with(ReadPreference::PreferReplica, "master:6379", &[]);
+        assert_eq!(router.node_addr(Some(100), true, "GET"), "master:6379");
+        let router = router_with(ReadPreference::ReplicaRequired, "master:6379", &[]);
+        assert_eq!(router.node_addr(Some(100), true, "GET"), "");
+        assert!(router.node_for(Some(100), true, "GET").is_err());
+        assert_eq!(router.node_addr(Some(100), false, "GET"), "master:6379");
     }
 
     #[test]
-    fn slot_map_replica_selection() {
-        let map = SlotMap {
-            ranges: vec![SlotRange {
-                start: 0,
-                end: 16383,
-                master: "master:6379".into(),
-                replicas: vec!["r1:6379".into(), "r2:6379".into()],
-            }],
+    fn read_preferences_pick_nodes() {
+        let replicas = ["r1:6379", "r2:6379"];
+        let router = router_with(ReadPreference::Primary, "m:6379", &replicas);
+        assert_eq!(router.node_addr(Some(100), true, "GET"), "m:6379");
+
+        // Replicas round-robin by slot, skipping tripped ones
+        let router = router_with(ReadPreference::PreferReplica, "m:6379", &replicas);
+        assert_eq!(router.node_addr(Some(100), true, "GET"), "r1:6379");
+        assert_eq!(router.node_addr(Some(101), true, "GET"), "r2:6379");
+        assert_eq!(router.node_addr(Some(101), false, "GET"), "m:6379");
+        trip(&router, "r2:6379");
+        assert_eq!(router.node_addr(Some(101), true, "GET"), "r1:6379");
+
+        let router = router_with(ReadPreference::ReplicaRequired, "m:6379", &replicas);
+        trip(&router, "r1:6379");
+        trip(&router, "r2:6379");
+        assert_eq!(router.node_addr(Some(100), true, "GET"), "");
+
+        // Unsampled nodes tie and the master wins
+        let router = router_with(ReadPreference::LowestLatency, "m:6379", &replicas);
+        assert_eq!(router.node_addr(Some(100), true, "GET"), "m:6379");
+        for (addr, us) in [("m:6379", 500), ("r1:6379", 300), ("r2:6379", 100)] {
+            router.samples.lock().entry(addr.into()).or_default().record_rtt(Duration::from_micros(us));
+        }
+        assert_eq!(router.node_addr(Some(100), true, "GET"), "r2:6379");
+
+        let router = router_with(ReadPreference::AzAffinity("b".into()), "m:6379", &replicas);
+        assert_eq!(router.node_addr(Some(101), true, "GET"), "r2:6379");
+        for (addr, zone) in [("r1:6379", "b"), ("r2:6379", "a")] {
+            let zone = RespValue::Map(vec![(bulk("availability_zone"), bulk(zone))]);
+            router.samples.lock().entry(addr.into()).or_default().set_zone(&zone);
+        }
+        assert_eq!(router.node_addr(Some(100), true, "GET"), "r1:6379");
+        assert_eq!(router.node_addr(Some(101), true, "GET"), "r1:6379");
+    }
+
+    #[test]
+    fn drained_nodes_are_passed_over_until_their_slots_move() {
+        let router = router_with(ReadPreference::PreferReplica, "m:6379", &["r1:6379", "r2:6379"]);
+        assert!(router.drain_node("elsewhere:6379").is_err());
+        router.drain_node("r1:6379").unwrap();
+        router.drain_node("m:6379").unwrap();
+        assert_eq!(router.draining_nodes(), ["m:6379", "r1:6379"]);
+        assert_eq!(router.node_addr(Some(100), true, "GET"), "r2:6379");
+        // Writes have nowhere else to go
+        assert_eq!(router.node_addr(Some(100), false, "SET"), "m:6379");
+        let router_primary = router_with(ReadPreference::Primary, "m:6379", &["r1:6379"]);
+        router_primary.drain_node("m:6379").unwrap();
+        assert_eq!(router_primary.node_addr(Some(100), true, "GET"), "r1:6379");
+
+        // After a failover the old master is released, the replica is not
+        let mut map = router.slot_map.read().clone();
+        map.ranges[0].master = "r2:6379".into();
+        map.ranges[0].replicas = vec!["m:6379".into(), "r1:6379".into()];
+        router.install_slot_map(map);
+        assert_eq!(router.draining_nodes(), ["r1:6379"]);
+        assert!(router.undrain_node("r1:6379"));
+        assert!(!router.undrain_node("r1:6379"));
+    }
+
+    #[tokio::test]
+    async fn keyless_commands_spread_over_masters() {
+        let ranges = |router: &ClusterRouter| {
+            router.slot_map.write().ranges = [(0, 5000, "a:6379"), (5001, 10000, "b:6379"), (10001, 16383, "a:6379")]
+                .into_iter()
+                .map(|(start, end, master)| SlotRange { start, end, master: master.into(), replicas: vec!["r:6379".into()] })
+                .collect();
         };
-        // Should pick a replica (not master)
-        let result = map.replica_for_slot(100);
-        assert!(result == Some("r1:6379") || result == Some("r2:6379"));
+        let router = ClusterRouter::unconnected(ConnectionConfig::default(), String::new());
+        ranges(&router);
+        assert_eq!(router.node_addr(None, false, "RANDOMKEY"), "a:6379");
+        assert_eq!(router.node_addr(None, false, "RANDOMKEY"), "a:6379");
+
+        let config = ConnectionConfig { keyless_routing: KeylessRouting::RoundRobin, ..ConnectionConfig::default() };
+        let router = ClusterRouter::unconnected(config, String::new());
+        ranges(&router);
+        let picks: Vec<String> = (0..4).map(|_| router.node_addr(None, true, "RANDOMKEY")).collect();
+        assert_eq!(picks, ["a:6379", "b:6379", "a:6379", "b:6379"]);
+        trip(&router, "a:6379");
+        assert_eq!(router.node_addr(None, false, "RANDOMKEY"), "b:6379");
+        assert_eq!(router.node_addr(None, false, "RANDOMKEY"), "b:6379");
+
+        // Categories can be routed on their own; idle masters tie and rotate
+        let config = ConnectionConfig {
+            keyless_routing: KeylessRouting::LeastLoaded,
+            keyless_overrides: vec![(KeylessCategory::Scripting, KeylessRouting::First)],
+            ..ConnectionConfig::default()
+        };
+        let router = ClusterRouter::unconnected(config, String::new());
+        ranges(&router);
+        let picks: Vec<String> = (0..4).map(|_| router.node_addr(None, false, "SCRIPT")).collect();
+        assert_eq!(picks, ["a:6379"; 4]);
+        let picks: Vec<String> = (0..4).map(|_| router.node_addr(None, false, "INFO")).collect();
+        assert_eq!(picks, ["a:6379", "b:6379", "a:6379", "b:6379"]);
+
+        // An explicit node must belong to the cluster
+        let err = router.execute_raw_bytes_at(&Route::Node("elsewhere:6379".into()), &[b"PING"]).await.unwrap_err();
+        assert!(matches!(err, PyrsedisError::Cluster(_)));
     }
 
     #[test]
@@ -821,8 +1695,607 @@ mod tests {
         assert_eq!(map.ranges.len(), 2);
         assert_eq!(map.master_for_slot(0), Some("127.0.0.1:7000"));
         assert_eq!(map.master_for_slot(5461), Some("127.0.0.1:7001"));
-        assert_eq!(map.replica_for_slot(0), Some("127.0.0.1:7003"));
-        // No replicas for second range → falls back to master
-        assert_eq!(map.replica_for_slot(5461), Some("127.0.0.1:7001"));
+        assert_eq!(map.range_for_slot(0).unwrap().replicas, ["127.0.0.1:7003"]);
+        assert!(map.range_for_slot(5461).unwrap().replicas.is_empty());
+    }
+
+    fn bulk(s: &str) -> RespValue {
+        RespValue::BulkString(Bytes::copy_from_slice(s.as_bytes()))
+    }
+
+    #[test]
+    fn slot_map_prefers_advertised_hostnames() {
+        // Redis 7 CLUSTER SLOTS: endpoint, port, id, then metadata
+        let node = |endpoint: &str, port, hostname: &str| {
+            RespValue::Array(vec![
+                bulk(endpoint),
+                RespValue::Integer(port),
+                bulk("id"),
+                RespValue::Array(vec![bulk("hostname"), bulk(hostname)]),
+            ])
+        };
+        let resp = RespValue::Array(vec![RespValue::Array(vec![
+            RespValue::Integer(0),
+            RespValue::Integer(16383),
+            node("10.0.0.1", 6379, "node-a.example.com"),
+            node("10.0.0.2", 6379, ""),
+        ])]);
+        let map = SlotMap::from_cluster_slots(&resp).unwrap();
+        assert_eq!(map.master_for_slot(0), Some("node-a.example.com:6379"));
+        assert_eq!(map.range_for_slot(0).unwrap().replicas, ["10.0.0.2:6379"]);
+    }
+
+    #[test]
+    fn slot_map_from_cluster_shards() {
+        let node = |pairs: &[(&str, RespValue)]| {
+            RespValue::Array(pairs.iter().flat_map(|(k, v)| [bulk(k), v.clone()]).collect())
+        };
+        let shard = |slots: &[i64], nodes: Vec<RespValue>| {
+            RespValue::Map(vec![
+                (bulk("slots"), RespValue::Array(slots.iter().map(|&s| RespValue::Integer(s)).collect())),
+                (bulk("nodes"), RespValue::Array(nodes)),
+            ])
+        };
+        let resp = RespValue::Array(vec![
+            shard(
+                &[0, 99, 200, 16383],
+                vec![
+                    node(&[
+                        ("ip", bulk("10.0.0.1")),
+                        ("endpoint", bulk("10.0.0.1")),
+                        ("hostname", bulk("a.example.com")),
+                        ("port", RespValue::Integer(6379)),
+                        ("tls-port", RespValue::Integer(6380)),
+                        ("role", bulk("master")),
+                        ("health", bulk("online")),
+                    ]),
+                    node(&[
+                        ("ip", bulk("10.0.0.2")),
+                        ("endpoint", bulk("10.0.0.2")),
+                        ("port", RespValue::Integer(6379)),
+                        ("role", bulk("replica")),
+                        ("health", bulk("online")),
+                    ]),
+                    node(&[
+                        ("ip", bulk("10.0.0.3")),
+                        ("port", RespValue::Integer(6379)),
+                        ("role", bulk("replica")),
+                        ("health", bulk("failed")),
+                    ]),
+                ],
+            ),
+            shard(
+                &[100, 199],
+                vec![node(&[
+                    ("ip", bulk("10.0.0.4")),
+                    ("endpoint", bulk("?")),
+                    ("port", RespValue::Integer(6379)),
+                    ("role", bulk("master")),
+                    ("health", bulk("online")),
+                ])],
+            ),
+            // No slots assigned yet
+            shard(&[], vec![]),
+        ]);
+
+        let map = SlotMap::from_cluster_shards(&resp, false).unwrap();
+        assert_eq!(map.ranges.len(), 3);
+        assert_eq!(map.master_for_slot(0), Some("a.example.com:6379"));
+        assert_eq!(map.master_for_slot(150), Some("10.0.0.4:6379"));
+        assert_eq!(map.master_for_slot(16383), Some("a.example.com:6379"));
+        assert_eq!(map.ranges[0].replicas, ["10.0.0.2:6379"]);
+        assert!(map.range_for_slot(150).unwrap().replicas.is_empty());
+
+        let map = SlotMap::from_cluster_shards(&resp, true).unwrap();
+        assert_eq!(map.master_for_slot(0), Some("a.example.com:6380"));
+        assert_eq!(map.master_for_slot(150), Some("10.0.0.4:6379"));
+        assert!(SlotMap::from_cluster_shards(&RespValue::Integer(1), false).is_err());
+    }
+
+    // ── Topology registry ──
+
+    #[tokio::test]
+    async fn new_router_reuses_shared_slot_map() {
+        registry::ensure_enabled();
+        // Nothing listens on these seeds, so only a shared map can succeed.
+        let seeds = vec![("127.0.0.1".to_string(), 2), ("127.0.0.1".to_string(), 1)];
+        let config = ConnectionConfig::default();
+        assert!(ClusterRouter::new(seeds.clone(), config.clone()).await.is_err());
+
+        let map = SlotMap {
+            ranges: vec![SlotRange {
+                start: 0,
+                end: 16383,
+                master: "127.0.0.1:7000".into(),
+                replicas: vec![],
+            }],
+        };
+        registry::store_slot_map(&registry::seeds_key(&seeds), &map);
+
+        let reversed: Vec<_> = seeds.into_iter().rev().collect();
+        let router = ClusterRouter::new(reversed, config).await.unwrap();
+        assert_eq!(router.slot_map.read().master_for_slot(42), Some("127.0.0.1:7000"));
+        assert!(router.nodes.read().contains_key("127.0.0.1:7000"));
+    }
+
+    // ── Background refresh ──
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let interval = Duration::from_millis(30_000);
+        let waits: Vec<Duration> = (0..200).map(|_| jittered(interval)).collect();
+        assert!(waits.iter().all(|w| *w >= Duration::from_millis(27_000) && *w <= Duration::from_millis(33_000)));
+        assert!(waits.iter().any(|w| *w != waits[0]));
+    }
+
+    #[tokio::test]
+    async fn refresh_skipped_while_idle() {
+        use std::sync::atomic::AtomicUsize;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let refreshes = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&refreshes);
+        fake_node(listener, move |args| match args[0].as_str() {
+            "CLUSTER" => {
+                counter.fetch_add(1, Ordering::SeqCst);
+                slots_reply(port, 1)
+            }
+            _ => b"+PONG\r\n".to_vec(),
+        })
+        .await;
+
+        let config = ConnectionConfig { slot_refresh_interval_ms: 20, ..ConnectionConfig::default() };
+        let seeds = vec![("127.0.0.1".to_string(), port)];
+        let router = ClusterRouter::new(seeds, config).await.unwrap();
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+
+        router.execute_raw(&["PING"]).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert_eq!(refreshes.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn zero_interval_disables_refresh() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        fake_node(listener, move |_| slots_reply(port, 1)).await;
+
+        let config = ConnectionConfig { slot_refresh_interval_ms: 0, ..ConnectionConfig::default() };
+        let seeds = vec![("127.0.0.1".to_string(), port)];
+        let router = ClusterRouter::new(seeds, config).await.unwrap();
+        assert_eq!(Arc::strong_count(&router), 1);
+        assert_eq!(Arc::weak_count(&router), 0);
+    }
+
+    // ── Raw single-pass path ──
+
+    #[test]
+    fn raw_error_classifies_redirects() {
+        let (kind, _) = raw_error(b"-MOVED 3999 127.0.0.1:7001\r\n").unwrap();
+        assert!(matches!(kind, RedisErrorKind::Moved { slot: 3999, ref addr } if addr == "127.0.0.1:7001"));
+        let (kind, _) = raw_error(b"-ASK 1 h:1\r\n").unwrap();
+        assert!(matches!(kind, RedisErrorKind::Ask { .. }));
+        assert!(raw_error(b"+OK\r\n").is_none());
+        assert!(raw_error(b"$5\r\n-MOVE\r\n").is_none());
+    }
+
+    /// Start a fake cluster node that answers each command with `reply(args)`.
+    ///
+    /// Like a Redis 6 node, it rejects `CLUSTER SHARDS`, so routers fall
+    /// back to `CLUSTER SLOTS`.
+    async fn fake_node<F>(listener: tokio::net::TcpListener, reply: F)
+    where
+        F: Fn(&[String]) -> Vec<u8> + Send + Sync + 'static,
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let reply = Arc::new(reply);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let reply = Arc::clone(&reply);
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    while let Ok(n @ 1..) = socket.read(&mut chunk).await {
+                        buf.extend_from_slice(&chunk[..n]);
+                        let mut out = Vec::new();
+                        while let Ok((RespValue::Array(items), used)) = crate::resp::parser::parse(&Bytes::copy_from_slice(&buf)) {
+                            buf.drain(..used);
+                            let args: Vec<String> = items.iter().filter_map(|v| v.as_str().map(String::from)).collect();
+                            if args[0] == "CLUSTER" && args[1] == "SHARDS" {
+                                out.extend(b"-ERR unknown subcommand 'SHARDS'\r\n");
+                                continue;
+                            }
+                            out.extend(reply(&args));
+                        }
+                        if socket.write_all(&out).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    /// `CLUSTER SLOTS` reply assigning every slot to `port`, with `slot`
+    /// in a range of its own so a MOVED for it leaves the others alone.
+    fn slots_reply(port: u16, slot: u16) -> Vec<u8> {
+        let node = format!("*2\r\n$9\r\n127.0.0.1\r\n:{port}\r\n");
+        let ranges = [(0, slot - 1), (slot, slot), (slot + 1, 16383)];
+        let mut out = b"*3\r\n".to_vec();
+        for (start, end) in ranges {
+            out.extend(format!("*3\r\n:{start}\r\n:{end}\r\n{node}").into_bytes());
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn raw_path_follows_moved_and_groups_pipelines() {
+        let a = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let b = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (port_a, port_b) = (a.local_addr().unwrap().port(), b.local_addr().unwrap().port());
+
+        // Node A owns every slot according to CLUSTER SLOTS, but "moved"
+        // now lives on node B.
+        fake_node(a, move |args| match args[0].as_str() {
+            "CLUSTER" => slots_reply(port_a, hash_slot(b"moved")),
+            _ if args[1] == "moved" => {
+                format!("-MOVED {} 127.0.0.1:{port_b}\r\n", hash_slot(b"moved")).into_bytes()
+            }
+            _ => format!("${}\r\na:{}\r\n", args[1].len() + 2, args[1]).into_bytes(),
+        })
+        .await;
+        fake_node(b, |args| format!("${}\r\nb:{}\r\n", args[1].len() + 2, args[1]).into_bytes()).await;
+
+        let seeds = vec![("127.0.0.1".to_string(), port_a)];
+        let router = ClusterRouter::new(seeds, ConnectionConfig::default()).await.unwrap();
+
+        let raw = router.execute_raw(&["GET", "here"]).await.unwrap();
+        assert_eq!(&raw[..], b"$6\r\na:here\r\n");
+        let raw = router.execute_raw_bytes(&[b"GET", b"moved"]).await.unwrap();
+        assert_eq!(&raw[..], b"$7\r\nb:moved\r\n");
+        let moved_owner = router.slot_map.read().master_for_slot(hash_slot(b"moved")).map(String::from);
+        assert_eq!(moved_owner, Some(format!("127.0.0.1:{port_b}")));
+
+        let commands: Vec<Vec<String>> = ["x", "moved", "y"]
+            .iter()
+            .map(|k| vec!["GET".to_string(), k.to_string()])
+            .collect();
+        let replies = router.pipeline_raw(&commands).await.unwrap();
+        assert_eq!(replies.len(), 3);
+        assert_eq!(&replies[0][..], b"$3\r\na:x\r\n");
+        assert_eq!(&replies[1][..], b"$7\r\nb:moved\r\n");
+        assert_eq!(&replies[2][..], b"$3\r\na:y\r\n");
+    }
+
+    #[tokio::test]
+    async fn announced_addresses_are_remapped() {
+        use crate::config::AddressRemap;
+
+        let a = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let b = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (port_a, port_b) = (a.local_addr().unwrap().port(), b.local_addr().unwrap().port());
+
+        // Node A announces itself and node B by addresses only reachable
+        // inside the cluster's network.
+        fake_node(a, |args| match args[0].as_str() {
+            "CLUSTER" => b"*1\r\n*3\r\n:0\r\n:16383\r\n*2\r\n$8\r\n10.0.0.1\r\n:6379\r\n".to_vec(),
+            _ if args[1] == "moved" => format!("-MOVED {} 10.0.0.2:6379\r\n", hash_slot(b"moved")).into_bytes(),
+            _ => b"$1\r\na\r\n".to_vec(),
+        })
+        .await;
+        fake_node(b, |_| b"$1\r\nb\r\n".to_vec()).await;
+
+        let remap = HashMap::from([
+            ("10.0.0.1:6379".to_string(), format!("127.0.0.1:{port_a}")),
+            ("10.0.0.2:6379".to_string(), format!("127.0.0.1:{port_b}")),
+        ]);
+        let config = ConnectionConfig {
+            connect_timeout_ms: 500,
+            address_remap: Some(AddressRemap::Static(Arc::new(remap))),
+            ..ConnectionConfig::default()
+        };
+        let seeds = vec![("127.0.0.1".to_string(), port_a)];
+        let router = ClusterRouter::new(seeds, config).await.unwrap();
+
+        assert_eq!(&router.execute_raw(&["GET", "here"]).await.unwrap()[..], b"$1\r\na\r\n");
+        assert_eq!(&router.execute_raw(&["GET", "moved"]).await.unwrap()[..], b"$1\r\nb\r\n");
+        // Nodes keep the names the cluster announced
+        let mut masters = router.nodes.read().keys().cloned().collect::<Vec<_>>();
+        masters.sort();
+        assert_eq!(masters, ["10.0.0.1:6379", "10.0.0.2:6379"]);
+    }
+
+    #[tokio::test]
+    async fn dead_master_trips_its_breaker() {
+        use crate::router::breaker::FAILURE_THRESHOLD;
+        use std::sync::atomic::AtomicUsize;
+
+        let dead = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let dead_port = dead.local_addr().unwrap().port();
+        drop(dead);
+        let replica = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let replica_port = replica.local_addr().unwrap().port();
+
+        let readonly = Arc::new(AtomicUsize::new(0));
+        let seen = Arc::clone(&readonly);
+        fake_node(replica, move |args| match args[0].as_str() {
+            "CLUSTER" => format!(
+                "*1\r\n*4\r\n:0\r\n:16383\r\n*2\r\n$9\r\n127.0.0.1\r\n:{dead_port}\r\n\
+                 *2\r\n$9\r\n127.0.0.1\r\n:{replica_port}\r\n"
+            )
+            .into_bytes(),
+            "READONLY" => {
+                seen.fetch_add(1, Ordering::Relaxed);
+                b"+OK\r\n".to_vec()
+            }
+            _ => b"$1\r\nr\r\n".to_vec(),
+        })
+        .await;
+
+        let config = ConnectionConfig { connect_timeout_ms: 500, ..ConnectionConfig::default() };
+        let seeds = vec![("127.0.0.1".to_string(), replica_port)];
+        let router = ClusterRouter::new(seeds, config).await.unwrap();
+
+        for _ in 0..FAILURE_THRESHOLD {
+            let err = router.execute_raw(&["GET", "k"]).await.unwrap_err();
+            assert!(matches!(err, PyrsedisError::Connection(_)), "{err:?}");
+        }
+        // Writes fail fast; reads go to the replica
+        let err = router.execute_raw(&["SET", "k", "v"]).await.unwrap_err();
+        assert!(matches!(err, PyrsedisError::Redis { kind: RedisErrorKind::ClusterDown, .. }), "{err:?}");
+        assert_eq!(&router.execute_raw(&["GET", "k"]).await.unwrap()[..], b"$1\r\nr\r\n");
+        assert_eq!(readonly.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn routes_override_the_read_preference() {
+        let master = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let replica = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (port_m, port_r) = (master.local_addr().unwrap().port(), replica.local_addr().unwrap().port());
+        fake_node(master, move |args| match args[0].as_str() {
+            "CLUSTER" => format!(
+                "*1\r\n*4\r\n:0\r\n:16383\r\n*2\r\n$9\r\n127.0.0.1\r\n:{port_m}\r\n\
+                 *2\r\n$9\r\n127.0.0.1\r\n:{port_r}\r\n"
+            )
+            .into_bytes(),
+            _ => b"$1\r\nm\r\n".to_vec(),
+        })
+        .await;
+        fake_node(replica, |args| match args[0].as_str() {
+            "READONLY" => b"+OK\r\n".to_vec(),
+            _ => b"$1\r\nr\r\n".to_vec(),
+        })
+        .await;
+
+        let config = ConnectionConfig { read_preference: ReadPreference::PreferReplica, ..ConnectionConfig::default() };
+        let router = ClusterRouter::new(vec![("127.0.0.1".to_string(), port_m)], config).await.unwrap();
+        let get: &[&[u8]] = &[b"GET", b"k"];
+        assert_eq!(&router.execute_raw_bytes(get).await.unwrap()[..], b"$1\r\nr\r\n");
+        let routed = |route: Route| {
+            let router = Arc::clone(&router);
+            async move { router.execute_raw_bytes_at(&route, get).await }
+        };
+        assert_eq!(&routed(Route::Master).await.unwrap()[..], b"$1\r\nm\r\n");
+        assert_eq!(&routed(Route::Replica).await.unwrap()[..], b"$1\r\nr\r\n");
+        assert_eq!(&routed(Route::Slot(0)).await.unwrap()[..], b"$1\r\nm\r\n");
+        assert_eq!(&routed(Route::Node(format!("127.0.0.1:{port_r}"))).await.unwrap()[..], b"$1\r\nr\r\n");
+
+        // A replica has to be picked by slot
+        let err = router.execute_raw_bytes_at(&Route::Replica, &[b"PING"]).await.unwrap_err();
+        assert!(matches!(err, PyrsedisError::Type(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn nodes_are_sampled() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        fake_node(listener, move |args| match args[0].as_str() {
+            "CLUSTER" => slots_reply(port, 100),
+            "HELLO" => b"*4\r\n$6\r\nserver\r\n$6\r\nvalkey\r\n$17\r\navailability_zone\r\n$4\r\naz-1\r\n".to_vec(),
+            _ => b"+PONG\r\n".to_vec(),
+        })
+        .await;
+        let seeds = vec![("127.0.0.1".to_string(), port)];
+        let router = ClusterRouter::new(seeds, ConnectionConfig::default()).await.unwrap();
+
+        router.sample_nodes();
+        let addr = format!("127.0.0.1:{port}");
+        for _ in 0..100 {
+            if router.samples.lock().get(&addr).is_some_and(|s| s.rtt().is_some()) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let sample = Arc::clone(&router.samples.lock()[&addr]);
+        assert!(sample.rtt().is_some());
+        assert_eq!(sample.zone().as_deref(), Some("az-1"));
+    }
+
+    #[tokio::test]
+    async fn repeated_moved_refreshes_whole_map() {
+        use std::sync::atomic::AtomicUsize;
+
+        let a = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let b = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (port_a, port_b) = (a.local_addr().unwrap().port(), b.local_addr().unwrap().port());
+
+        // Every slot has moved from A to B, but only B knows it. A's map
+        // gives each key a range of its own, so one MOVED patches one key.
+        let keys = ["k0", "k1", "k2", "k3", "k4", "k5"];
+        let mut slots: Vec<u16> = keys.iter().map(|key| hash_slot(key.as_bytes())).collect();
+        slots.sort();
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        for slot in slots {
+            if start < slot {
+                ranges.push((start, slot - 1));
+            }
+            ranges.push((slot, slot));
+            start = slot + 1;
+        }
+        ranges.push((start, 16383));
+        let mut slots_a = format!("*{}\r\n", ranges.len()).into_bytes();
+        for (start, end) in ranges {
+            slots_a.extend(format!("*3\r\n:{start}\r\n:{end}\r\n*2\r\n$9\r\n127.0.0.1\r\n:{port_a}\r\n").into_bytes());
+        }
+        let (moved, refreshes) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let (moved_a, refreshes_b) = (Arc::clone(&moved), Arc::clone(&refreshes));
+        fake_node(a, move |args| match args[0].as_str() {
+            "CLUSTER" => slots_a.clone(),
+            _ => {
+                moved_a.fetch_add(1, Ordering::SeqCst);
+                format!("-MOVED {} 127.0.0.1:{port_b}\r\n", hash_slot(args[1].as_bytes())).into_bytes()
+            }
+        })
+        .await;
+        fake_node(b, move |args| match args[0].as_str() {
+            "CLUSTER" => {
+                refreshes_b.fetch_add(1, Ordering::SeqCst);
+                slots_reply(port_b, 1)
+            }
+            _ => format!("${}\r\nb:{}\r\n", args[1].len() + 2, args[1]).into_bytes(),
+        })
+        .await;
+
+        let config = ConnectionConfig { slot_refresh_interval_ms: 0, ..ConnectionConfig::default() };
+        let seeds = vec![("127.0.0.1".to_string(), port_a)];
+        let router = ClusterRouter::new(seeds, config).await.unwrap();
+        for key in keys {
+            let raw = router.execute_raw(&["GET", key]).await.unwrap();
+            assert_eq!(&raw[..], format!("$4\r\nb:{key}\r\n").as_bytes());
+        }
+        // The third redirect re-fetched the map; later keys go straight to B.
+        assert_eq!(moved.load(Ordering::SeqCst), MOVED_REFRESH_THRESHOLD);
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+        assert_eq!(router.masters(), [format!("127.0.0.1:{port_b}")]);
+    }
+
+    #[tokio::test]
+    async fn fan_out_reports_each_master() {
+        let a = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let b = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (port_a, port_b) = (a.local_addr().unwrap().port(), b.local_addr().unwrap().port());
+
+        // Slots split between A and B; B rejects CONFIG SET.
+        let slots = format!(
+            "*2\r\n*3\r\n:0\r\n:8191\r\n*2\r\n$9\r\n127.0.0.1\r\n:{port_a}\r\n\
+             *3\r\n:8192\r\n:16383\r\n*2\r\n$9\r\n127.0.0.1\r\n:{port_b}\r\n"
+        );
+        fake_node(a, move |args| match args[0].as_str() {
+            "CLUSTER" => slots.clone().into_bytes(),
+            _ => b"+OK\r\n".to_vec(),
+        })
+        .await;
+        fake_node(b, |_| b"-ERR Unsupported CONFIG parameter\r\n".to_vec()).await;
+
+        let seeds = vec![("127.0.0.1".to_string(), port_a)];
+        let router = ClusterRouter::new(seeds, ConnectionConfig::default()).await.unwrap();
+        let (addr_a, addr_b) = (format!("127.0.0.1:{port_a}"), format!("127.0.0.1:{port_b}"));
+        let mut expected = vec![addr_a.clone(), addr_b.clone()];
+        expected.sort();
+        assert_eq!(router.masters(), expected);
+
+        let results: HashMap<String, Result<Bytes>> =
+            router.execute_on_masters_raw(&["CONFIG", "SET", "x", "1"]).await.into_iter().collect();
+        assert_eq!(results.len(), 2);
+        assert_eq!(&results[&addr_a].as_ref().unwrap()[..], b"+OK\r\n");
+        assert!(results[&addr_b].as_ref().unwrap().starts_with(b"-ERR"));
+    }
+
+    #[test]
+    fn slot_groups_keep_first_appearance_order() {
+        let groups = slot_groups(&["{a}1", "b", "{a}2", "b"]);
+        assert_eq!(groups, vec![(hash_slot(b"a"), vec![0, 2]), (hash_slot(b"b"), vec![1, 3])]);
+        assert!(split_merge("mget").is_some());
+        assert_eq!(split_merge("Del"), Some(Merge::Sum));
+        assert_eq!(split_merge("MSET"), None);
+    }
+
+    #[test]
+    fn merge_replies_restore_key_order() {
+        let parts = vec![
+            (vec![0, 2], Bytes::from_static(b"*2\r\n$1\r\na\r\n$-1\r\n")),
+            (vec![1], Bytes::from_static(b"*1\r\n$1\r\nb\r\n")),
+        ];
+        let merged = merge_replies(Merge::Gather, 3, parts).unwrap();
+        assert_eq!(&merged[..], b"*3\r\n$1\r\na\r\n$1\r\nb\r\n$-1\r\n");
+
+        let parts = vec![(vec![0], Bytes::from_static(b":1\r\n")), (vec![1, 2], Bytes::from_static(b":2\r\n"))];
+        assert_eq!(&merge_replies(Merge::Sum, 3, parts).unwrap()[..], b":3\r\n");
+
+        // A server error is passed through; a wrong reply count is not.
+        let parts = vec![(vec![0], Bytes::from_static(b":1\r\n")), (vec![1], Bytes::from_static(b"-ERR no\r\n"))];
+        assert_eq!(&merge_replies(Merge::Sum, 2, parts).unwrap()[..], b"-ERR no\r\n");
+        let parts = vec![(vec![0, 1], Bytes::from_static(b"*1\r\n$1\r\na\r\n"))];
+        assert!(merge_replies(Merge::Gather, 2, parts).is_err());
+
+        // RESP3 bulk errors count as errors too.
+        let parts = vec![(vec![0], Bytes::from_static(b"!7\r\nERR bad\r\n")), (vec![1], Bytes::from_static(b":1\r\n"))];
+        assert_eq!(&merge_replies(Merge::Sum, 2, parts).unwrap()[..], b"!7\r\nERR bad\r\n");
+    }
+
+    #[test]
+    fn merge_replies_reject_short_frames() {
+        for raw in [&b"\n"[..], b"\r\n", b"*\r\n", b"$1\r\na\r\n"] {
+            let parts = vec![(vec![0], Bytes::from_static(raw))];
+            assert!(merge_replies(Merge::Gather, 1, parts).is_err(), "{raw:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn cross_slot_commands_split_per_node() {
+        let a = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let b = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (port_a, port_b) = (a.local_addr().unwrap().port(), b.local_addr().unwrap().port());
+        let slots = format!(
+            "*2\r\n*3\r\n:0\r\n:8191\r\n*2\r\n$9\r\n127.0.0.1\r\n:{port_a}\r\n\
+             *3\r\n:8192\r\n:16383\r\n*2\r\n$9\r\n127.0.0.1\r\n:{port_b}\r\n"
+        );
+        // MGET answers "<node>:<key>" per key; DEL counts its keys.
+        fn reply(node: &str, args: &[String]) -> Vec<u8> {
+            let keys = &args[1..];
+            if args[0] == "DEL" {
+                return format!(":{}\r\n", keys.len()).into_bytes();
+            }
+            let mut out = format!("*{}\r\n", keys.len());
+            for key in keys {
+                out.push_str(&format!("${}\r\n{node}:{key}\r\n", key.len() + 2));
+            }
+            out.into_bytes()
+        }
+        fake_node(a, move |args| match args[0].as_str() {
+            "CLUSTER" => slots.clone().into_bytes(),
+            _ => reply("a", args),
+        })
+        .await;
+        fake_node(b, |args| reply("b", args)).await;
+
+        let seeds = vec![("127.0.0.1".to_string(), port_a)];
+        let config = ConnectionConfig { allow_cross_slot: true, ..ConnectionConfig::default() };
+        let router = ClusterRouter::new(seeds, config).await.unwrap();
+
+        let keys = ["k1", "k2", "k3", "k4", "k5", "k6"];
+        let mut expected = format!("*{}\r\n", keys.len());
+        for key in keys {
+            let node = if hash_slot(key.as_bytes()) < 8192 { "a" } else { "b" };
+            expected.push_str(&format!("$4\r\n{node}:{key}\r\n"));
+        }
+        let mget: Vec<&str> = std::iter::once("MGET").chain(keys).collect();
+        let raw = router.execute_raw_split(&mget).await.unwrap();
+        assert_eq!(std::str::from_utf8(&raw).unwrap(), expected);
+
+        let del: Vec<&str> = std::iter::once("DEL").chain(keys).collect();
+        assert_eq!(&router.execute_raw_split(&del).await.unwrap()[..], b":6\r\n");
+
+        // MSET cannot be split; it fails before anything is sent
+        let err = router.execute_raw_split(&["MSET", "a", "1", "b", "2"]).await.unwrap_err();
+        assert!(matches!(err, PyrsedisError::CrossSlot(_)));
+        let mset = vec!["MSET".to_string(), "a".into(), "1".into(), "b".into(), "2".into()];
+        assert!(matches!(router.pipeline_raw(&[mset]).await, Err(PyrsedisError::CrossSlot(_))));
     }
 }
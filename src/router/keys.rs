@@ -0,0 +1,202 @@
+//! Key positions of Redis commands, for cluster routing.
+//!
+//! [`key_positions`] looks a command up in a small key-spec table,
+//! modelled on the key specs `COMMAND INFO` reports: most commands take
+//! one key at `args[1]`, others a range of keys, a `numkeys` count, keys
+//! after a keyword (`STREAMS`, `STORE`, `KEYS`) or none at all. [`shared_slot`]
+//! then checks that every key hashes to one slot, honouring `{hash tags}`,
+//! so a command that would be answered with `CROSSSLOT` fails before it
+//! is sent.
+
+use crate::crc16::hash_slot;
+use crate::error::{PyrsedisError, Result};
+
+/// Where a command's keys are.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KeySpec {
+    /// No keys.
+    None,
+    /// One key at this position.
+    Single(usize),
+    /// Every `step`-th argument from `first` up to `last` (counted from
+    /// the end when negative: `-1` is the last argument).
+    Range { first: usize, last: isize, step: usize },
+    /// `args[at]` holds a key count; the keys follow it. `dest` is an
+    /// extra key before the count (the `*STORE` commands).
+    NumKeys { at: usize, dest: bool },
+    /// Half of the arguments after `STREAMS` are keys (the other half
+    /// their IDs).
+    Streams,
+    /// A key at `args[1]`, plus the argument after `STORE` or
+    /// `STOREDIST`.
+    Store,
+    /// `<command> <subcommand> <key>`.
+    Subcommand,
+    /// `MIGRATE host port key db timeout ... [KEYS key ...]`: the key at
+    /// `args[3]` unless empty, plus every argument after `KEYS`.
+    Migrate,
+}
+
+/// Key spec of the (upper-case) command `name`.
+fn key_spec(name: &str) -> KeySpec {
+    const ALL: KeySpec = KeySpec::Range { first: 1, last: -1, step: 1 };
+    const TWO: KeySpec = KeySpec::Range { first: 1, last: 2, step: 1 };
+    match name {
+        "PING" | "INFO" | "DBSIZE" | "CLUSTER" | "CONFIG" | "CLIENT" | "COMMAND" | "TIME" | "RANDOMKEY"
+        | "WAIT" | "SAVE" | "BGSAVE" | "BGREWRITEAOF" | "FLUSHALL" | "FLUSHDB" | "LASTSAVE" | "SLOWLOG"
+        | "DEBUG" | "MULTI" | "EXEC" | "DISCARD" | "UNWATCH" | "SCRIPT" | "FUNCTION" | "SUBSCRIBE"
+        | "UNSUBSCRIBE" | "PSUBSCRIBE" | "PUNSUBSCRIBE" | "PUBLISH" | "PUBSUB" | "QUIT" | "ECHO" | "SELECT"
+        | "AUTH" | "HELLO" | "KEYS" | "SCAN" | "SWAPDB" | "LATENCY" | "MONITOR" | "ACL" | "READONLY"
+        | "READWRITE" | "GRAPH.LIST" | "GRAPH.CONFIG" => KeySpec::None,
+        "MGET" | "DEL" | "UNLINK" | "EXISTS" | "TOUCH" | "WATCH" | "SINTER" | "SUNION" | "SDIFF"
+        | "SINTERSTORE" | "SUNIONSTORE" | "SDIFFSTORE" | "PFCOUNT" | "PFMERGE" => ALL,
+        "MSET" | "MSETNX" => KeySpec::Range { first: 1, last: -1, step: 2 },
+        "BLPOP" | "BRPOP" | "BZPOPMIN" | "BZPOPMAX" => KeySpec::Range { first: 1, last: -2, step: 1 },
+        "RENAME" | "RENAMENX" | "SMOVE" | "RPOPLPUSH" | "BRPOPLPUSH" | "LMOVE" | "BLMOVE" | "COPY"
+        | "GEOSEARCHSTORE" | "ZRANGESTORE" | "LCS" => TWO,
+        "BITOP" => KeySpec::Range { first: 2, last: -1, step: 1 },
+        "EVAL" | "EVALSHA" | "EVAL_RO" | "EVALSHA_RO" | "FCALL" | "FCALL_RO" | "BLMPOP" | "BZMPOP" => {
+            KeySpec::NumKeys { at: 2, dest: false }
+        }
+        "ZUNION" | "ZINTER" | "ZDIFF" | "ZINTERCARD" | "SINTERCARD" | "LMPOP" | "ZMPOP" => {
+            KeySpec::NumKeys { at: 1, dest: false }
+        }
+        "ZUNIONSTORE" | "ZINTERSTORE" | "ZDIFFSTORE" => KeySpec::NumKeys { at: 2, dest: true },
+        "XREAD" | "XREADGROUP" => KeySpec::Streams,
+        "SORT" | "SORT_RO" | "GEORADIUS" | "GEORADIUSBYMEMBER" => KeySpec::Store,
+        "XGROUP" | "XINFO" | "OBJECT" | "MEMORY" => KeySpec::Subcommand,
+        "MIGRATE" => KeySpec::Migrate,
+        _ => KeySpec::Single(1),
+    }
+}
+
+/// Positions of every key in `args`, in argument order.
+///
+/// Arguments that are missing or malformed (a `numkeys` that is not a
+/// number) yield fewer keys rather than an error; the server reports
+/// those.
+pub fn key_positions(args: &[&str]) -> Vec<usize> {
+    let Some(name) = args.first() else { return Vec::new() };
+    let len = args.len();
+    match key_spec(&name.to_ascii_uppercase()) {
+        KeySpec::None => Vec::new(),
+        KeySpec::Single(at) => (at < len).then_some(at).into_iter().collect(),
+        KeySpec::Range { first, last, step } => {
+            let last = if last < 0 { len as isize + last } else { last.min(len as isize - 1) };
+            if last < first as isize {
+                return Vec::new();
+            }
+            (first..=last as usize).step_by(step).collect()
+        }
+        KeySpec::NumKeys { at, dest } => {
+            let count = args.get(at).and_then(|n| n.parse::<usize>().ok()).unwrap_or(0);
+            let mut positions: Vec<usize> = if dest { vec![1] } else { Vec::new() };
+            positions.extend((at + 1..len).take(count));
+            positions
+        }
+        KeySpec::Streams => match args.iter().position(|a| a.eq_ignore_ascii_case("STREAMS")) {
+            Some(at) => (at + 1..len).take((len - at - 1) / 2).collect(),
+            None => Vec::new(),
+        },
+        KeySpec::Store => {
+            let mut positions: Vec<usize> = (len > 1).then_some(1).into_iter().collect();
+            let store = (2..len.saturating_sub(1))
+                .filter(|&i| args[i].eq_ignore_ascii_case("STORE") || args[i].eq_ignore_ascii_case("STOREDIST"));
+            positions.extend(store.map(|i| i + 1));
+            positions
+        }
+        KeySpec::Subcommand => (len > 2).then_some(2).into_iter().collect(),
+        KeySpec::Migrate => {
+            let mut positions: Vec<usize> = (len > 3 && !args[3].is_empty()).then_some(3).into_iter().collect();
+            if let Some(at) = (6..len).find(|&i| args[i].eq_ignore_ascii_case("KEYS")) {
+                positions.extend(at + 1..len);
+            }
+            positions
+        }
+    }
+}
+
+/// The slot every key in `keys` hashes to, or `None` without keys.
+///
+/// Fails with [`PyrsedisError::CrossSlot`] naming the first two keys in
+/// different slots.
+pub fn shared_slot<'a>(command: &str, keys: impl IntoIterator<Item = &'a [u8]>) -> Result<Option<u16>> {
+    let mut first: Option<(&[u8], u16)> = None;
+    for key in keys {
+        let slot = hash_slot(key);
+        match first {
+            None => first = Some((key, slot)),
+            Some((first_key, first_slot)) if first_slot != slot => {
+                return Err(PyrsedisError::CrossSlot(format!(
+                    "{command} keys must hash to the same slot ('{}' is in slot {first_slot}, '{}' in slot {slot}); use a hash tag",
+                    String::from_utf8_lossy(first_key),
+                    String::from_utf8_lossy(key),
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(first.map(|(_, slot)| slot))
+}
+
+/// The slot of `args`' keys (see [`shared_slot`]).
+pub fn command_slot(args: &[&str]) -> Result<Option<u16>> {
+    let Some(name) = args.first() else { return Ok(None) };
+    shared_slot(&name.to_ascii_uppercase(), key_positions(args).into_iter().map(|i| args[i].as_bytes()))
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys<'a>(args: &[&'a str]) -> Vec<&'a str> {
+        key_positions(args).into_iter().map(|i| args[i]).collect()
+    }
+
+    #[test]
+    fn key_specs() {
+        assert_eq!(keys(&["GET", "k"]), ["k"]);
+        assert_eq!(keys(&["SET", "k", "v"]), ["k"]);
+        assert_eq!(keys(&["XADD", "s", "*", "f", "v"]), ["s"]);
+        assert!(keys(&["PING"]).is_empty());
+        assert!(keys(&["INFO", "server"]).is_empty());
+        assert!(keys(&["GET"]).is_empty());
+        assert!(keys(&[]).is_empty());
+        assert_eq!(keys(&["MGET", "a", "b", "c"]), ["a", "b", "c"]);
+        assert_eq!(keys(&["MSET", "a", "1", "b", "2"]), ["a", "b"]);
+        assert_eq!(keys(&["BLPOP", "a", "b", "0"]), ["a", "b"]);
+        assert_eq!(keys(&["lmove", "a", "b", "LEFT", "RIGHT"]), ["a", "b"]);
+        assert_eq!(keys(&["EVAL", "s", "2", "a", "b", "arg"]), ["a", "b"]);
+        assert!(keys(&["EVAL", "s", "0", "arg"]).is_empty());
+        assert_eq!(keys(&["FCALL_RO", "f", "1", "{u}a", "arg"]), ["{u}a"]);
+        assert_eq!(keys(&["ZUNIONSTORE", "d", "2", "a", "b", "WEIGHTS", "1", "2"]), ["d", "a", "b"]);
+        assert_eq!(keys(&["ZINTER", "2", "a", "b", "WITHSCORES"]), ["a", "b"]);
+        assert_eq!(keys(&["XREAD", "COUNT", "1", "STREAMS", "a", "b", "0", "0"]), ["a", "b"]);
+        assert_eq!(keys(&["SORT", "l", "BY", "w_*", "STORE", "out"]), ["l", "out"]);
+        assert_eq!(keys(&["GEORADIUS", "g", "0", "0", "5", "km", "STOREDIST", "d"]), ["g", "d"]);
+        assert_eq!(keys(&["XGROUP", "CREATE", "s", "g", "$"]), ["s"]);
+        assert_eq!(keys(&["BITOP", "AND", "d", "a", "b"]), ["d", "a", "b"]);
+        assert_eq!(keys(&["LCS", "a", "b", "LEN"]), ["a", "b"]);
+        assert_eq!(keys(&["MIGRATE", "h", "6379", "k", "0", "1000", "COPY"]), ["k"]);
+        assert_eq!(keys(&["MIGRATE", "h", "6379", "", "0", "1000", "AUTH", "pw", "KEYS", "a", "b"]), ["a", "b"]);
+    }
+
+    #[test]
+    fn slots_honor_hash_tags() {
+        assert_eq!(command_slot(&["PING"]).unwrap(), None);
+        assert_eq!(command_slot(&["MGET", "{u}a", "{u}b"]).unwrap(), Some(hash_slot(b"u")));
+        let err = command_slot(&["mset", "a", "1", "b", "2"]).unwrap_err();
+        assert!(matches!(err, PyrsedisError::CrossSlot(_)));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "cross-slot error: MSET keys must hash to the same slot ('a' is in slot {}, 'b' in slot {}); use a hash tag",
+                hash_slot(b"a"),
+                hash_slot(b"b")
+            )
+        );
+        assert!(command_slot(&["SORT", "{t}l", "STORE", "out"]).is_err());
+    }
+}
@@ -1,13 +1,27 @@
+pub mod breaker;
 pub mod cluster;
+pub mod keys;
+pub mod retry;
+pub mod sampler;
 pub mod sentinel;
 pub mod standalone;
 
-pub use cluster::ClusterRouter;
+pub use cluster::{ClusterRouter, Route};
 pub use sentinel::SentinelRouter;
 pub use standalone::StandaloneRouter;
 
-use crate::error::Result;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+
+use crate::blocking::blocking_read_timeout_ms;
+use crate::connection::pool::LeasedConnection;
+use crate::connection::stats::StatsSnapshot;
+use crate::error::{PyrsedisError, Result};
+use crate::resp::parser::{parse, resp_frame_len};
 use crate::resp::types::RespValue;
+use crate::resp::writer::{encode_command_str, encode_pipeline};
 
 /// Common interface for all Redis topology routers.
 ///
@@ -31,4 +45,482 @@ pub trait Router: Send + Sync {
 
     /// Number of available connection slots across pools.
     fn pool_available(&self) -> usize;
+
+    /// Statistics summed across pools.
+    fn pool_stats(&self) -> StatsSnapshot;
+
+    /// Close all idle connections across pools.
+    fn disconnect_all(&self);
+
+    /// Open connections until `count` are idle in each pool, returning
+    /// how many were opened.
+    fn warmup(&self, count: usize) -> impl std::future::Future<Output = Result<usize>> + Send;
+
+    /// Abandon connections inherited from a parent process after `fork()`.
+    fn reset_after_fork(&self);
+}
+
+// ── Client router ─────────────────────────────────────────────────
+
+/// The router behind a Python client, whichever topology it uses.
+///
+/// [`Router`] uses `async fn` and is therefore not object-safe; this enum
+/// dispatches to the concrete router instead, including the raw
+/// single-pass methods the client fast paths rely on.
+// Always held behind an `Arc`, so the variant size difference is moot.
+#[allow(clippy::large_enum_variant)]
+pub enum ClientRouter {
+    /// A single server.
+    Standalone(StandaloneRouter),
+    /// A Redis Cluster. Shared with the background slot refresh task.
+    Cluster(Arc<ClusterRouter>),
+    /// A Sentinel-managed master.
+    Sentinel(Arc<SentinelRouter>),
+}
+
+impl ClientRouter {
+    /// Execute a command and return the raw RESP frame.
+    pub async fn execute_raw(&self, args: &[&str]) -> Result<Bytes> {
+        match self {
+            Self::Standalone(r) => r.execute_raw(args).await,
+            Self::Cluster(r) => r.execute_raw_split(args).await,
+            Self::Sentinel(r) => r.execute_raw(args).await,
+        }
+    }
+
+    /// Like [`execute_raw`](Self::execute_raw), with binary-safe arguments.
+    pub async fn execute_raw_bytes(&self, args: &[&[u8]]) -> Result<Bytes> {
+        match self {
+            Self::Standalone(r) => r.execute_raw_bytes(args).await,
+            Self::Cluster(r) => r.execute_raw_bytes(args).await,
+            Self::Sentinel(r) => r.execute_raw_bytes(args).await,
+        }
+    }
+
+    /// Like [`execute_raw_bytes`](Self::execute_raw_bytes), on the cluster
+    /// node `route` picks when given. Other topologies have a single node
+    /// and reject it.
+    pub async fn execute_raw_bytes_at(&self, route: Option<&Route>, args: &[&[u8]]) -> Result<Bytes> {
+        match (self, route) {
+            (_, None) => self.execute_raw_bytes(args).await,
+            (Self::Cluster(r), Some(route)) => r.execute_raw_bytes_at(route, args).await,
+            (_, Some(_)) => Err(PyrsedisError::Type("route is only supported in cluster mode".into())),
+        }
+    }
+
+    /// Execute a pipeline and return raw RESP frames in command order.
+    pub async fn pipeline_raw(&self, commands: &[Vec<String>]) -> Result<Vec<Bytes>> {
+        match self {
+            Self::Standalone(r) => r.pipeline_raw(commands).await,
+            Self::Cluster(r) => r.pipeline_raw(commands).await,
+            Self::Sentinel(r) => r.pipeline_raw(commands).await,
+        }
+    }
+
+    /// Run `commands` atomically inside MULTI/EXEC on one connection and
+    /// return the raw reply of each command, unpacked from the EXEC array.
+    ///
+    /// Fails with [`PyrsedisError::Transaction`] when the server aborts
+    /// the transaction.
+    pub async fn transaction_raw(&self, commands: &[Vec<String>]) -> Result<Vec<Bytes>> {
+        let replies = match self {
+            Self::Standalone(r) => r.pipeline_raw(&wrap_transaction(commands)).await?,
+            Self::Cluster(r) => r.transaction_raw(commands).await?,
+            Self::Sentinel(r) => r.pipeline_raw(&wrap_transaction(commands)).await?,
+        };
+        unpack_exec(&replies, commands.len())
+    }
+
+    /// Run a command on every node that owns data — each master in a
+    /// cluster, the one server otherwise — and return each node's raw
+    /// reply or error, keyed by address.
+    pub async fn execute_on_all_raw(&self, args: &[&str]) -> Vec<(String, Result<Bytes>)> {
+        match self {
+            Self::Standalone(r) => vec![(r.addr(), r.execute_raw(args).await)],
+            Self::Cluster(r) => r.execute_on_masters_raw(args).await,
+            Self::Sentinel(r) => vec![(r.master_addr(), r.execute_raw(args).await)],
+        }
+    }
+
+    /// Like [`execute_on_all_raw`](Self::execute_on_all_raw), with
+    /// binary-safe arguments.
+    pub async fn execute_on_all_raw_bytes(&self, args: &[&[u8]]) -> Vec<(String, Result<Bytes>)> {
+        match self {
+            Self::Standalone(r) => vec![(r.addr(), r.execute_raw_bytes(args).await)],
+            Self::Cluster(r) => r.execute_on_masters_raw_bytes(args).await,
+            Self::Sentinel(r) => vec![(r.master_addr(), r.execute_raw_bytes(args).await)],
+        }
+    }
+
+    /// Send a `SENTINEL` command to the sentinels of a Sentinel client;
+    /// other topologies have none.
+    pub async fn execute_sentinel(&self, args: &[&str]) -> Result<RespValue> {
+        match self {
+            Self::Sentinel(r) => r.execute_on_sentinel(args).await,
+            _ => Err(PyrsedisError::Type("sentinel commands are only supported in sentinel mode".into())),
+        }
+    }
+
+    /// The nodes a keyspace SCAN has to visit: every master of a cluster,
+    /// or just the one server (`None`).
+    pub fn scan_nodes(&self) -> Vec<Option<String>> {
+        match self {
+            Self::Cluster(r) => r.masters().into_iter().map(Some).collect(),
+            _ => vec![None],
+        }
+    }
+
+    /// Like [`execute_raw`](Self::execute_raw), on a node returned by
+    /// [`scan_nodes`](Self::scan_nodes).
+    pub async fn execute_raw_at(&self, addr: Option<&str>, args: &[&str]) -> Result<Bytes> {
+        match (self, addr) {
+            (Self::Cluster(r), Some(addr)) => r.execute_raw_at(addr, args).await,
+            _ => self.execute_raw(args).await,
+        }
+    }
+
+    /// Like [`transaction_raw`](Self::transaction_raw), on a leased
+    /// connection that holds the caller's WATCHes. The lease is released
+    /// afterwards, or dropped if the connection failed.
+    ///
+    /// Fails with [`PyrsedisError::Watch`] when a watched key changed.
+    pub async fn transaction_on(&self, mut lease: LeasedConnection, commands: &[Vec<String>]) -> Result<Vec<Bytes>> {
+        let wrapped = wrap_transaction(commands);
+        let replies = async {
+            lease.conn().send_raw(&encode_pipeline(&wrapped)).await?;
+            let mut replies = Vec::with_capacity(wrapped.len());
+            for _ in &wrapped {
+                replies.push(lease.conn().read_raw_response().await?);
+            }
+            Ok(replies)
+        }
+        .await;
+        match replies {
+            Ok(replies) => {
+                self.release(lease).await;
+                unpack_exec(&replies, commands.len())
+            }
+            // Possibly mid-transaction — never hand it back to the pool
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Check out a connection for exclusive use.
+    pub async fn lease(&self) -> Result<LeasedConnection> {
+        match self {
+            Self::Standalone(r) => r.lease().await,
+            Self::Cluster(r) => r.lease().await,
+            Self::Sentinel(r) => r.lease().await,
+        }
+    }
+
+    /// Give back a connection obtained from [`lease`](Self::lease).
+    pub async fn release(&self, lease: LeasedConnection) {
+        match self {
+            Self::Standalone(r) => r.release(lease).await,
+            Self::Cluster(r) => r.release(lease).await,
+            Self::Sentinel(r) => r.release(lease).await,
+        }
+    }
+
+    /// Run a blocking command (BLPOP and friends) that may wait up to
+    /// `block` on the server, zero meaning forever, and return the raw
+    /// reply.
+    ///
+    /// The command gets a leased connection to the node serving its first
+    /// key, with the read timeout stretched past `block`. It is never
+    /// retried: the server may already have popped an element.
+    pub async fn execute_blocking(&self, args: &[&str], block: Duration) -> Result<Bytes> {
+        let mut lease = match (self, args.get(1)) {
+            (Self::Cluster(r), Some(key)) => r.lease_for(key).await?,
+            _ => self.lease().await?,
+        };
+        let base = lease.conn().read_timeout_ms();
+        lease.conn().set_read_timeout(blocking_read_timeout_ms(base, block));
+        let result = async {
+            lease.conn().send_raw(&encode_command_str(args)).await?;
+            lease.conn().read_raw_response().await
+        }
+        .await;
+        if result.is_ok() {
+            // Releasing restores the configured read timeout
+            self.release(lease).await;
+        }
+        // Otherwise the reply may still be in flight — drop the connection
+        result
+    }
+
+    /// Drain the cluster node `addr` (see [`ClusterRouter::drain_node`]).
+    /// Other topologies have a single node and reject it.
+    pub fn drain_node(&self, addr: &str) -> Result<()> {
+        match self {
+            Self::Cluster(r) => r.drain_node(addr),
+            _ => Err(PyrsedisError::Type("drain_node is only supported in cluster mode".into())),
+        }
+    }
+
+    /// End the drain of the cluster node `addr`; `false` if it was not
+    /// being drained.
+    pub fn undrain_node(&self, addr: &str) -> bool {
+        match self {
+            Self::Cluster(r) => r.undrain_node(addr),
+            _ => false,
+        }
+    }
+
+    /// The cluster nodes being drained; none outside cluster mode.
+    pub fn draining_nodes(&self) -> Vec<String> {
+        match self {
+            Self::Cluster(r) => r.draining_nodes(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether this client talks to a Redis Cluster.
+    pub fn is_cluster(&self) -> bool {
+        matches!(self, Self::Cluster(_))
+    }
+}
+
+// ── Command timeouts ──────────────────────────────────────────────
+
+/// A per-command time limit given in seconds, if any.
+pub fn command_limit(seconds: Option<f64>) -> Result<Option<Duration>> {
+    match seconds {
+        Some(s) => match Duration::try_from_secs_f64(s) {
+            Ok(limit) if !limit.is_zero() => Ok(Some(limit)),
+            _ => Err(PyrsedisError::Type(format!("timeout must be a positive number of seconds, got {s}"))),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Run `request` — connection checkout, retries and redirects included —
+/// failing with [`PyrsedisError::Timeout`] once `limit` has passed.
+///
+/// The connection a cut-short request was using is closed instead of
+/// going back to the pool, since its reply may still arrive.
+pub async fn within<T>(limit: Option<Duration>, request: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+    let Some(limit) = limit else { return request.await };
+    match tokio::time::timeout(limit, request).await {
+        Ok(result) => result,
+        Err(_) => Err(PyrsedisError::Timeout(format!("command did not complete within {limit:?}"))),
+    }
+}
+
+/// Turn a raw reply that is a server error into that error, naming the
+/// command `args` that caused it.
+pub fn check_reply<A: AsRef<[u8]>>(raw: Bytes, args: &[A]) -> Result<Bytes> {
+    if !matches!(raw.first(), Some(b'-' | b'!')) {
+        return Ok(raw);
+    }
+    match parse(&raw) {
+        Ok((RespValue::Error(msg) | RespValue::BulkError(msg), _)) => Err(PyrsedisError::redis(msg).for_command(args)),
+        _ => Ok(raw),
+    }
+}
+
+// ── Transactions ──────────────────────────────────────────────────
+
+/// `commands` preceded by MULTI and followed by EXEC.
+pub(crate) fn wrap_transaction(commands: &[Vec<String>]) -> Vec<Vec<String>> {
+    let mut wrapped = Vec::with_capacity(commands.len() + 2);
+    wrapped.push(vec!["MULTI".to_string()]);
+    wrapped.extend_from_slice(commands);
+    wrapped.push(vec!["EXEC".to_string()]);
+    wrapped
+}
+
+/// The message of a raw `-ERR…` frame, or `None` for other frames.
+fn error_message(raw: &[u8]) -> Option<String> {
+    let msg = raw.strip_prefix(b"-")?.strip_suffix(b"\r\n")?;
+    Some(String::from_utf8_lossy(msg).into_owned())
+}
+
+/// Turn the replies to MULTI, `count` queued commands and EXEC into the
+/// per-command replies held in the EXEC array.
+fn unpack_exec(replies: &[Bytes], count: usize) -> Result<Vec<Bytes>> {
+    let (Some(multi), Some(exec)) = (replies.first(), replies.last()) else {
+        return Err(PyrsedisError::Protocol("missing MULTI/EXEC replies".into()));
+    };
+    if replies.len() != count + 2 {
+        return Err(PyrsedisError::Protocol("missing MULTI/EXEC replies".into()));
+    }
+    if let Some(msg) = error_message(multi) {
+        return Err(PyrsedisError::redis(msg));
+    }
+    if let Some(msg) = error_message(exec) {
+        // EXECABORT only says "previous errors"; report the first of them
+        let queued_err = replies[1..=count].iter().find_map(|r| error_message(r));
+        return Err(PyrsedisError::Transaction(queued_err.unwrap_or(msg)));
+    }
+    if exec.as_ref() == b"*-1\r\n" || exec.as_ref() == b"_\r\n" {
+        return Err(PyrsedisError::Watch("a watched key was modified".into()));
+    }
+
+    // "*<count>\r\n" followed by one frame per command
+    let header_end = exec
+        .windows(2)
+        .position(|w| w == b"\r\n")
+        .filter(|_| exec.starts_with(b"*"))
+        .ok_or_else(|| PyrsedisError::Protocol("EXEC reply is not an array".into()))?;
+    let len: usize = std::str::from_utf8(&exec[1..header_end])
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| PyrsedisError::Protocol("invalid EXEC array length".into()))?;
+    if len != count {
+        return Err(PyrsedisError::Protocol(format!(
+            "EXEC returned {len} replies for {count} commands"
+        )));
+    }
+    let mut pos = header_end + 2;
+    let mut out = Vec::with_capacity(count);
+    for _ in 0..count {
+        let frame_len = resp_frame_len(&exec[pos..])?;
+        out.push(exec.slice(pos..pos + frame_len));
+        pos += frame_len;
+    }
+    Ok(out)
+}
+
+impl Router for ClientRouter {
+    async fn execute(&self, args: &[&str]) -> Result<RespValue> {
+        match self {
+            Self::Standalone(r) => r.execute(args).await,
+            Self::Cluster(r) => r.execute(args).await,
+            Self::Sentinel(r) => r.execute(args).await,
+        }
+    }
+
+    async fn pipeline(&self, commands: &[Vec<String>]) -> Result<Vec<RespValue>> {
+        match self {
+            Self::Standalone(r) => r.pipeline(commands).await,
+            Self::Cluster(r) => r.pipeline(commands).await,
+            Self::Sentinel(r) => r.pipeline(commands).await,
+        }
+    }
+
+    fn pool_idle_count(&self) -> usize {
+        match self {
+            Self::Standalone(r) => r.pool_idle_count(),
+            Self::Cluster(r) => r.pool_idle_count(),
+            Self::Sentinel(r) => r.pool_idle_count(),
+        }
+    }
+
+    fn pool_available(&self) -> usize {
+        match self {
+            Self::Standalone(r) => r.pool_available(),
+            Self::Cluster(r) => r.pool_available(),
+            Self::Sentinel(r) => r.pool_available(),
+        }
+    }
+
+    fn pool_stats(&self) -> StatsSnapshot {
+        match self {
+            Self::Standalone(r) => r.pool_stats(),
+            Self::Cluster(r) => r.pool_stats(),
+            Self::Sentinel(r) => r.pool_stats(),
+        }
+    }
+
+    fn disconnect_all(&self) {
+        match self {
+            Self::Standalone(r) => r.disconnect_all(),
+            Self::Cluster(r) => r.disconnect_all(),
+            Self::Sentinel(r) => r.disconnect_all(),
+        }
+    }
+
+    async fn warmup(&self, count: usize) -> Result<usize> {
+        match self {
+            Self::Standalone(r) => r.warmup(count).await,
+            Self::Cluster(r) => r.warmup(count).await,
+            Self::Sentinel(r) => r.warmup(count).await,
+        }
+    }
+
+    fn reset_after_fork(&self) {
+        match self {
+            Self::Standalone(r) => r.reset_after_fork(),
+            Self::Cluster(r) => r.reset_after_fork(),
+            Self::Sentinel(r) => r.reset_after_fork(),
+        }
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(raw: &[&'static [u8]]) -> Vec<Bytes> {
+        raw.iter().map(|r| Bytes::from_static(r)).collect()
+    }
+
+    #[test]
+    fn wrap_adds_multi_and_exec() {
+        let wrapped = wrap_transaction(&[vec!["INCR".into(), "k".into()]]);
+        assert_eq!(wrapped, vec![vec!["MULTI".to_string()], vec!["INCR".into(), "k".into()], vec!["EXEC".into()]]);
+    }
+
+    #[test]
+    fn unpack_exec_splits_array() {
+        let replies = frames(&[b"+OK\r\n", b"+QUEUED\r\n", b"+QUEUED\r\n", b"*2\r\n:1\r\n$2\r\nhi\r\n"]);
+        let out = unpack_exec(&replies, 2).unwrap();
+        assert_eq!(out, frames(&[b":1\r\n", b"$2\r\nhi\r\n"]));
+    }
+
+    #[test]
+    fn unpack_exec_reports_queue_error() {
+        let replies = frames(&[
+            b"+OK\r\n",
+            b"+QUEUED\r\n",
+            b"-ERR unknown command 'NOPE'\r\n",
+            b"-EXECABORT Transaction discarded because of previous errors.\r\n",
+        ]);
+        match unpack_exec(&replies, 2) {
+            Err(PyrsedisError::Transaction(msg)) => assert_eq!(msg, "ERR unknown command 'NOPE'"),
+            other => panic!("expected transaction error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unpack_exec_null_means_watch_conflict() {
+        for null in [&b"*-1\r\n"[..], b"_\r\n"] {
+            let replies = vec![Bytes::from_static(b"+OK\r\n"), Bytes::copy_from_slice(null)];
+            assert!(matches!(unpack_exec(&replies, 0), Err(PyrsedisError::Watch(_))));
+        }
+    }
+
+    #[test]
+    fn unpack_exec_rejects_malformed() {
+        let replies = frames(&[b"-ERR MULTI calls can not be nested\r\n", b"*0\r\n"]);
+        assert!(matches!(unpack_exec(&replies, 0), Err(PyrsedisError::Redis { .. })));
+        let replies = frames(&[b"+OK\r\n", b"+QUEUED\r\n", b"*2\r\n:1\r\n:2\r\n"]);
+        assert!(matches!(unpack_exec(&replies, 1), Err(PyrsedisError::Protocol(_))));
+        assert!(matches!(unpack_exec(&replies, 5), Err(PyrsedisError::Protocol(_))));
+    }
+
+    #[test]
+    fn command_limits_must_be_positive() {
+        assert_eq!(command_limit(None).unwrap(), None);
+        assert_eq!(command_limit(Some(0.5)).unwrap(), Some(Duration::from_millis(500)));
+        assert!(command_limit(Some(0.0)).is_err());
+        assert!(command_limit(Some(-1.0)).is_err());
+        assert!(command_limit(Some(f64::NAN)).is_err());
+        assert!(command_limit(Some(1e20)).is_err());
+    }
+
+    #[tokio::test]
+    async fn within_cuts_off_slow_requests() {
+        assert_eq!(within(None, async { Ok(1) }).await.unwrap(), 1);
+        assert_eq!(within(Some(Duration::from_secs(1)), async { Ok(2) }).await.unwrap(), 2);
+        let slow = within(Some(Duration::from_millis(10)), async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(3)
+        });
+        assert!(matches!(slow.await, Err(PyrsedisError::Timeout(_))));
+    }
 }
@@ -0,0 +1,202 @@
+//! Retry safety classification.
+//!
+//! A connection error after a command has been written is ambiguous: the
+//! server may have executed it and only the reply was lost. Replaying such
+//! a command is only harmless when executing it twice leaves the dataset in
+//! the same state as executing it once.
+
+use crate::error::PyrsedisError;
+
+// ── Idempotency classification ────────────────────────────────────
+
+/// Commands whose repeated execution has the same effect as a single one.
+///
+/// Reads are trivially safe. Writes qualify when they set absolute state
+/// (SET, HSET, SADD, DEL, EXPIRE…) rather than apply a delta (INCR, LPUSH,
+/// APPEND…) or pop/move data (LPOP, RPOPLPUSH, SPOP…).
+pub fn is_idempotent_command(cmd: &str) -> bool {
+    matches!(
+        cmd.to_ascii_uppercase().as_str(),
+        // Reads
+        "GET"
+            | "MGET"
+            | "KEYS"
+            | "SCAN"
+            | "TYPE"
+            | "TTL"
+            | "PTTL"
+            | "EXISTS"
+            | "STRLEN"
+            | "GETRANGE"
+            | "HGET"
+            | "HMGET"
+            | "HGETALL"
+            | "HKEYS"
+            | "HVALS"
+            | "HLEN"
+            | "HEXISTS"
+            | "HSCAN"
+            | "LRANGE"
+            | "LLEN"
+            | "LINDEX"
+            | "LPOS"
+            | "SMEMBERS"
+            | "SCARD"
+            | "SISMEMBER"
+            | "SMISMEMBER"
+            | "SINTER"
+            | "SUNION"
+            | "SDIFF"
+            | "SSCAN"
+            | "ZRANGE"
+            | "ZREVRANGE"
+            | "ZRANGEBYSCORE"
+            | "ZSCORE"
+            | "ZRANK"
+            | "ZCARD"
+            | "ZCOUNT"
+            | "ZSCAN"
+            | "DBSIZE"
+            | "RANDOMKEY"
+            | "INFO"
+            | "TIME"
+            | "LASTSAVE"
+            | "DUMP"
+            | "PING"
+            | "ECHO"
+            | "GRAPH.RO_QUERY"
+            | "GRAPH.LIST"
+            | "GRAPH.EXPLAIN"
+            // Absolute-state writes
+            | "SET"
+            | "MSET"
+            | "SETEX"
+            | "PSETEX"
+            | "HSET"
+            | "HMSET"
+            | "HDEL"
+            | "LSET"
+            | "SADD"
+            | "SREM"
+            | "ZADD"
+            | "ZREM"
+            | "ZREMRANGEBYSCORE"
+            | "DEL"
+            | "UNLINK"
+            | "EXPIRE"
+            | "PEXPIRE"
+            | "EXPIREAT"
+            | "PEXPIREAT"
+            | "PERSIST"
+            | "FLUSHDB"
+            | "FLUSHALL"
+            | "SCRIPT"
+            | "GRAPH.DELETE"
+    )
+}
+
+/// Whether a full command line is idempotent.
+///
+/// Some absolute-state writes turn into deltas or reads-before-write
+/// through their options: `ZADD … INCR` adds to the score and `SET … GET`
+/// returns the previous value, which a replay would report differently.
+pub fn is_idempotent<S: AsRef<str>>(args: &[S]) -> bool {
+    let Some(cmd) = args.first().map(AsRef::as_ref) else {
+        return false;
+    };
+    // options follow the key (ZADD) or the key and value (SET)
+    let has_option = |from: usize, flag: &str| {
+        args.iter().skip(from).any(|a| a.as_ref().eq_ignore_ascii_case(flag))
+    };
+    if cmd.eq_ignore_ascii_case("ZADD") {
+        return !has_option(2, "INCR");
+    }
+    if cmd.eq_ignore_ascii_case("SET") {
+        return !has_option(3, "GET");
+    }
+    is_idempotent_command(cmd)
+}
+
+/// Whether a command may be replayed after a connection error.
+///
+/// Non-idempotent commands are only replayed when `retry_unsafe` is set.
+pub fn may_retry(args: &[&str], retry_unsafe: bool) -> bool {
+    retry_unsafe || is_idempotent(args)
+}
+
+/// Whether every command of a pipeline may be replayed.
+pub fn may_retry_pipeline(commands: &[Vec<String>], retry_unsafe: bool) -> bool {
+    retry_unsafe || commands.iter().all(|cmd| is_idempotent(cmd))
+}
+
+/// Whether an error leaves the connection in an unknown state.
+///
+/// Only transport errors qualify — a Redis error reply means the command
+/// was received and rejected, so it is never ambiguous.
+pub fn is_ambiguous_failure(err: &PyrsedisError) -> bool {
+    matches!(err, PyrsedisError::Connection(_) | PyrsedisError::Timeout(_))
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_are_idempotent() {
+        assert!(is_idempotent_command("GET"));
+        assert!(is_idempotent_command("hgetall"));
+        assert!(is_idempotent_command("GRAPH.RO_QUERY"));
+    }
+
+    #[test]
+    fn absolute_writes_are_idempotent() {
+        assert!(is_idempotent_command("SET"));
+        assert!(is_idempotent_command("del"));
+        assert!(is_idempotent_command("SADD"));
+    }
+
+    #[test]
+    fn delta_writes_are_not_idempotent() {
+        for cmd in ["INCR", "INCRBY", "LPUSH", "RPUSH", "APPEND", "LPOP", "SPOP", "ZINCRBY", "GRAPH.QUERY", "EVAL"] {
+            assert!(!is_idempotent_command(cmd), "{cmd} must not be retried");
+        }
+    }
+
+    #[test]
+    fn options_can_make_writes_unsafe() {
+        assert!(is_idempotent(&["ZADD", "z", "1", "m"]));
+        assert!(!is_idempotent(&["ZADD", "z", "incr", "1", "m"]));
+        assert!(is_idempotent(&["SET", "k", "v", "EX", "10"]));
+        assert!(!is_idempotent(&["set", "k", "v", "GET"]));
+        assert!(is_idempotent(&["SET", "get", "GET"]));
+        assert!(!may_retry(&["ZADD", "z", "INCR", "1", "m"], false));
+        let pipeline = vec![vec!["SET".to_string(), "k".into(), "v".into(), "GET".into()]];
+        assert!(!may_retry_pipeline(&pipeline, false));
+    }
+
+    #[test]
+    fn may_retry_honours_override() {
+        assert!(may_retry(&["GET", "k"], false));
+        assert!(!may_retry(&["INCR", "k"], false));
+        assert!(may_retry(&["INCR", "k"], true));
+        assert!(!may_retry(&[], false));
+    }
+
+    #[test]
+    fn pipeline_retry_requires_all_safe() {
+        let safe = vec![vec!["SET".to_string(), "a".into(), "1".into()], vec!["GET".to_string(), "a".into()]];
+        let unsafe_ = vec![vec!["SET".to_string(), "a".into(), "1".into()], vec!["INCR".to_string(), "a".into()]];
+        assert!(may_retry_pipeline(&safe, false));
+        assert!(!may_retry_pipeline(&unsafe_, false));
+        assert!(may_retry_pipeline(&unsafe_, true));
+    }
+
+    #[test]
+    fn ambiguous_failures() {
+        assert!(is_ambiguous_failure(&PyrsedisError::Connection(std::io::Error::other("reset"))));
+        assert!(is_ambiguous_failure(&PyrsedisError::Timeout("read".into())));
+        assert!(!is_ambiguous_failure(&PyrsedisError::redis("ERR bad")));
+    }
+}
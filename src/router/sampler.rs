@@ -0,0 +1,117 @@
+//! Node sampling behind latency- and zone-aware replica reads.
+//!
+//! With `read_preference="lowest_latency"` or `"az_affinity:<zone>"`, the
+//! cluster router pings every node each [`SAMPLE_INTERVAL`] and keeps a
+//! [`NodeSample`] per node: a moving average of the `PING` round trip and
+//! the node's availability zone. The zone is read once from the node's
+//! `HELLO` reply, which carries an `availability_zone` field on Valkey 8
+//! and later; other servers have no zone.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+use crate::resp::types::RespValue;
+
+/// Time between two pings of a node.
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What is known about one node.
+#[derive(Debug, Default)]
+pub struct NodeSample {
+    /// Average round trip in microseconds, 0 while unknown.
+    rtt_us: AtomicU64,
+    /// The node's zone, `None` until `HELLO` has been asked.
+    zone: Mutex<Option<Option<String>>>,
+}
+
+impl NodeSample {
+    /// Average `PING` round trip, if the node answered the last ping.
+    pub fn rtt(&self) -> Option<Duration> {
+        match self.rtt_us.load(Ordering::Relaxed) {
+            0 => None,
+            us => Some(Duration::from_micros(us)),
+        }
+    }
+
+    /// Fold a round trip into the average, weighted 1/4.
+    pub fn record_rtt(&self, rtt: Duration) {
+        let us = u64::try_from(rtt.as_micros()).unwrap_or(u64::MAX).max(1);
+        let _ = self.rtt_us.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |avg| {
+            Some(if avg == 0 { us } else { (avg - avg / 4 + us / 4).max(1) })
+        });
+    }
+
+    /// Forget the round trip after a failed ping, so the node is not
+    /// preferred until it answers again.
+    pub fn forget_rtt(&self) {
+        self.rtt_us.store(0, Ordering::Relaxed);
+    }
+
+    /// The node's availability zone, if it reports one.
+    pub fn zone(&self) -> Option<String> {
+        self.zone.lock().clone().flatten()
+    }
+
+    /// Whether the zone has been asked for.
+    pub fn zone_known(&self) -> bool {
+        self.zone.lock().is_some()
+    }
+
+    /// Record the zone from a `HELLO` reply.
+    pub fn set_zone(&self, hello: &RespValue) {
+        *self.zone.lock() = Some(hello_zone(hello));
+    }
+}
+
+/// The `availability_zone` field of a `HELLO` reply (a RESP3 map or a
+/// RESP2 array of pairs).
+fn hello_zone(hello: &RespValue) -> Option<String> {
+    let zone = match hello {
+        RespValue::Map(pairs) => pairs.iter().find(|(k, _)| k.as_str() == Some("availability_zone"))?.1.as_str(),
+        RespValue::Array(items) => items
+            .chunks_exact(2)
+            .find(|pair| pair[0].as_str() == Some("availability_zone"))?[1]
+            .as_str(),
+        _ => None,
+    }?;
+    (!zone.is_empty()).then(|| zone.to_string())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    fn bulk(s: &str) -> RespValue {
+        RespValue::BulkString(Bytes::copy_from_slice(s.as_bytes()))
+    }
+
+    #[test]
+    fn averages_round_trips() {
+        let sample = NodeSample::default();
+        assert_eq!(sample.rtt(), None);
+        sample.record_rtt(Duration::from_micros(800));
+        sample.record_rtt(Duration::from_micros(400));
+        assert_eq!(sample.rtt(), Some(Duration::from_micros(700)));
+        sample.forget_rtt();
+        assert_eq!(sample.rtt(), None);
+    }
+
+    #[test]
+    fn zone_from_hello() {
+        let sample = NodeSample::default();
+        assert!(!sample.zone_known());
+        sample.set_zone(&RespValue::Array(vec![bulk("server"), bulk("valkey"), bulk("availability_zone"), bulk("az-1")]));
+        assert_eq!(sample.zone(), Some("az-1".into()));
+
+        sample.set_zone(&RespValue::Map(vec![(bulk("availability_zone"), bulk(""))]));
+        assert!(sample.zone_known());
+        assert_eq!(sample.zone(), None);
+        sample.set_zone(&RespValue::Error("ERR unknown command 'HELLO'".into()));
+        assert_eq!(sample.zone(), None);
+    }
+}
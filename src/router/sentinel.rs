@@ -2,24 +2,52 @@
 //!
 //! Resolves the current master via Sentinel, maintains a connection pool to it,
 //! and automatically fails over when the master changes.
+//!
+//! A background task stays subscribed to a sentinel's `+switch-master`
+//! channel and moves the router to the promoted master as soon as the
+//! sentinels announce a failover, so commands do not first have to fail
+//! against the demoted one.
+//!
+//! With a `read_preference` other than `"primary"`, the master's healthy
+//! replicas are discovered with `SENTINEL REPLICAS` and read-only commands
+//! are spread over them. The list is fetched again after a failover and
+//! whenever the sentinels report a replica going down, coming back or
+//! being added.
 
-use crate::config::ConnectionConfig;
-use crate::connection::pool::ConnectionPool;
+use crate::config::{ConnectionConfig, ReadPreference};
+use crate::connection::pool::{ConnectionPool, LeasedConnection};
+use crate::connection::stats::StatsSnapshot;
 use crate::connection::tcp::RedisConnection;
 use crate::error::{PyrsedisError, Result};
 use crate::resp::types::RespValue;
-use crate::resp::writer::encode_command_str;
+use crate::resp::writer::{encode_command, encode_command_str, encode_pipeline};
+use crate::router::cluster::is_read_only_command;
+use crate::router::retry;
 use crate::router::Router;
+use crate::runtime;
 
+use bytes::Bytes;
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
 use std::time::Duration;
 
 /// Default number of retries when failover is detected.
-const DEFAULT_RETRY_COUNT: usize = 3;
+pub const DEFAULT_RETRY_COUNT: usize = 3;
 
 /// Default backoff between retries in milliseconds.
-const DEFAULT_RETRY_BACKOFF_MS: u64 = 100;
+pub const DEFAULT_RETRY_BACKOFF_MS: u64 = 100;
+
+/// Sentinel channel announcing a master's promoted replacement.
+const SWITCH_MASTER_CHANNEL: &str = "+switch-master";
+
+/// Sentinel channels announcing that a replica went down, came back or
+/// was discovered.
+const REPLICA_CHANNELS: [&str; 3] = ["+sdown", "-sdown", "+slave"];
+
+/// Pause before the `+switch-master` watcher tries the next sentinel, and
+/// how often it checks that the router is still alive while idle.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Router for Redis Sentinel topology.
 ///
@@ -30,6 +58,11 @@ pub struct SentinelRouter {
     master_pool: RwLock<Arc<ConnectionPool>>,
     /// Current master address.
     master_addr: RwLock<String>,
+    /// Healthy replicas of the master serving reads, empty with the
+    /// `"primary"` read preference.
+    replicas: RwLock<Vec<(String, Arc<ConnectionPool>)>>,
+    /// Counter spreading reads over `replicas` in turn.
+    replica_turn: AtomicUsize,
     /// Sentinel node addresses.
     sentinels: Vec<(String, u16)>,
     /// Master name to resolve.
@@ -65,17 +98,23 @@ impl SentinelRouter {
 
         // Resolve master
         let master_addr = resolve_master(&sentinels, &master_name, &config).await?;
-        let master_pool = create_master_pool(&master_addr, &config);
+        let master_pool = create_pool(&master_addr, &config);
 
-        Ok(Arc::new(Self {
-            master_pool: RwLock::new(Arc::new(master_pool)),
+        let router = Arc::new(Self {
+            master_pool: RwLock::new(master_pool),
             master_addr: RwLock::new(master_addr),
+            replicas: RwLock::new(Vec::new()),
+            replica_turn: AtomicUsize::new(0),
             sentinels,
             master_name,
             config,
             retry_count,
             retry_backoff,
-        }))
+        });
+        router.refresh_replicas().await;
+        let weak = Arc::downgrade(&router);
+        runtime::spawn(watch_switch_master(weak));
+        Ok(router)
     }
 
     /// Get the current master pool.
@@ -87,14 +126,93 @@ impl SentinelRouter {
     async fn failover(&self) -> Result<()> {
         let new_addr =
             resolve_master(&self.sentinels, &self.master_name, &self.config).await?;
+        if self.switch_to(new_addr) {
+            self.refresh_replicas().await;
+        }
+        Ok(())
+    }
 
+    /// Send commands to the master at `addr` from now on. Returns whether
+    /// the master changed.
+    fn switch_to(&self, addr: String) -> bool {
         let current = self.master_addr.read().clone();
-        if new_addr != current {
-            let new_pool = create_master_pool(&new_addr, &self.config);
-            *self.master_pool.write() = Arc::new(new_pool);
-            *self.master_addr.write() = new_addr;
+        if addr == current {
+            return false;
+        }
+        let new_pool = create_pool(&addr, &self.config);
+        *self.master_pool.write() = new_pool;
+        *self.master_addr.write() = addr;
+        true
+    }
+
+    /// Ask the sentinels for the master's healthy replicas, keeping the
+    /// pools of those already known. The current list stays when no
+    /// sentinel answers.
+    async fn refresh_replicas(&self) {
+        if self.config.read_preference == ReadPreference::Primary {
+            return;
+        }
+        let Ok(addrs) = resolve_replicas(&self.sentinels, &self.master_name, &self.config).await else {
+            return;
+        };
+        let mut replicas = self.replicas.write();
+        let pools = addrs
+            .into_iter()
+            .map(|addr| {
+                let known = replicas.iter().find(|(a, _)| *a == addr).map(|(_, pool)| Arc::clone(pool));
+                let pool = known.unwrap_or_else(|| create_pool(&addr, &self.config));
+                (addr, pool)
+            })
+            .collect();
+        *replicas = pools;
+    }
+
+    /// Addresses of the replicas currently serving reads.
+    pub fn replica_addrs(&self) -> Vec<String> {
+        self.replicas.read().iter().map(|(addr, _)| addr.clone()).collect()
+    }
+
+    /// The replica to send read-only `command` to, taking each in turn.
+    /// `None` sends it to the master; with `"replica_required"` and no
+    /// replica up, reads fail instead.
+    fn read_replica(&self, command: &str) -> Result<Option<(String, Arc<ConnectionPool>)>> {
+        let preference = &self.config.read_preference;
+        if *preference == ReadPreference::Primary || !is_read_only_command(command) {
+            return Ok(None);
+        }
+        let replicas = self.replicas.read();
+        if replicas.is_empty() {
+            return match preference {
+                ReadPreference::ReplicaRequired => Err(PyrsedisError::Sentinel(format!(
+                    "no replica of '{}' is available",
+                    self.master_name
+                ))),
+                _ => Ok(None),
+            };
+        }
+        let turn = self.replica_turn.fetch_add(1, Ordering::Relaxed);
+        Ok(Some(replicas[turn % replicas.len()].clone()))
+    }
+
+    /// Send a read-only command to a replica when the read preference
+    /// asks for one. `None` means it should go to the master: reads are
+    /// primary-only, or the replica failed and `"prefer_replica"` falls
+    /// back. A failed replica stops serving reads until the sentinels
+    /// list it again.
+    async fn read_from_replica(&self, cmd: &[u8], name: &str) -> Result<Option<Bytes>> {
+        let Some((addr, pool)) = self.read_replica(name)? else {
+            return Ok(None);
+        };
+        let err = match send_raw_on(&pool, cmd).await {
+            Ok(raw) if !raw.starts_with(b"-LOADING") && !raw.starts_with(b"-MASTERDOWN") => return Ok(Some(raw)),
+            Ok(raw) => PyrsedisError::redis(String::from_utf8_lossy(&raw[1..]).trim_end().to_string()),
+            Err(e) => e,
+        };
+        self.replicas.write().retain(|(a, _)| *a != addr);
+        match self.config.read_preference {
+            ReadPreference::ReplicaRequired => Err(err),
+            _ => Ok(None),
         }
-        Ok(())
     }
 
     /// Execute with automatic failover retry.
@@ -123,6 +241,11 @@ impl SentinelRouter {
 
             let cmd = encode_command_str(args);
             if let Err(e) = guard.conn().send_raw(&cmd).await {
+                // The write may have partially reached the server
+                drop(guard.take());
+                if !retry::may_retry(args, self.config.retry_unsafe) {
+                    return Err(e);
+                }
                 last_err = Some(e);
                 continue;
             }
@@ -138,8 +261,12 @@ impl SentinelRouter {
                     return Ok(resp);
                 }
                 Err(e) => {
-                    // Connection error → try failover
-                    if matches!(e, PyrsedisError::Connection(_)) {
+                    // Connection error → try failover, unless the command
+                    // may already have been applied and is unsafe to replay
+                    drop(guard.take());
+                    if matches!(e, PyrsedisError::Connection(_))
+                        && retry::may_retry(args, self.config.retry_unsafe)
+                    {
                         last_err = Some(e);
                         continue;
                     }
@@ -152,6 +279,164 @@ impl SentinelRouter {
             PyrsedisError::Sentinel("all failover retries exhausted".into())
         }))
     }
+
+    /// Execute a command and return the raw RESP frame, failing over like
+    /// [`Router::execute`].
+    ///
+    /// Read-only commands go to a replica when the read preference says so.
+    pub async fn execute_raw(&self, args: &[&str]) -> Result<Bytes> {
+        let name = args.first().copied().unwrap_or("");
+        let cmd = encode_command_str(args);
+        if let Some(raw) = self.read_from_replica(&cmd, name).await? {
+            return Ok(raw);
+        }
+        self.execute_raw_with_retry(&cmd, name).await
+    }
+
+    /// Like [`execute_raw`](Self::execute_raw), with binary-safe arguments.
+    pub async fn execute_raw_bytes(&self, args: &[&[u8]]) -> Result<Bytes> {
+        let name = args.first().and_then(|c| std::str::from_utf8(c).ok()).unwrap_or("");
+        let cmd = encode_command(args);
+        if let Some(raw) = self.read_from_replica(&cmd, name).await? {
+            return Ok(raw);
+        }
+        self.execute_raw_with_retry(&cmd, name).await
+    }
+
+    /// Execute a pipeline on the current master and return raw RESP frames.
+    ///
+    /// A transport failure triggers one master re-resolution and replay,
+    /// provided every command may be retried.
+    pub async fn pipeline_raw(&self, commands: &[Vec<String>]) -> Result<Vec<Bytes>> {
+        match self.pipeline_raw_once(commands).await {
+            Err(e)
+                if retry::is_ambiguous_failure(&e)
+                    && retry::may_retry_pipeline(commands, self.config.retry_unsafe) =>
+            {
+                self.failover().await?;
+                self.pipeline_raw_once(commands).await
+            }
+            result => result,
+        }
+    }
+
+    /// Check out a connection to the current master for exclusive use.
+    pub async fn lease(&self) -> Result<LeasedConnection> {
+        self.current_pool().lease().await
+    }
+
+    /// Close a connection obtained from [`lease`](Self::lease).
+    ///
+    /// Not returned to the pool: the master may have changed while it was
+    /// leased, and the connection would then point at a replica.
+    pub async fn release(&self, lease: LeasedConnection) {
+        drop(lease);
+    }
+
+    /// Send `args` to the sentinels, one after the other until one
+    /// answers, and return its reply. An error reply is returned as an
+    /// error.
+    pub async fn execute_on_sentinel(&self, args: &[&str]) -> Result<RespValue> {
+        let mut last_err = None;
+        for (host, port) in &self.sentinels {
+            let addr = format!("{host}:{port}");
+            let reply = match connect_sentinel(&addr, &self.config).await {
+                Ok(mut conn) => conn.execute_str(args).await,
+                Err(e) => Err(e),
+            };
+            match reply {
+                Ok(RespValue::Error(msg) | RespValue::BulkError(msg)) => {
+                    return Err(PyrsedisError::redis(msg).for_command(args));
+                }
+                Ok(reply) => return Ok(reply),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| PyrsedisError::Sentinel("could not contact any sentinel".into())))
+    }
+
+    /// Name of the monitored master.
+    pub fn master_name(&self) -> &str {
+        &self.master_name
+    }
+
+    /// Address of the master currently in use.
+    pub fn master_addr(&self) -> String {
+        self.master_addr.read().clone()
+    }
+
+    /// Raw-frame counterpart of [`execute_with_retry`](Self::execute_with_retry).
+    async fn execute_raw_with_retry(&self, cmd: &[u8], name: &str) -> Result<Bytes> {
+        let mut last_err = None;
+
+        for attempt in 0..=self.retry_count {
+            if attempt > 0 {
+                tokio::time::sleep(self.retry_backoff).await;
+                if let Err(e) = self.failover().await {
+                    last_err = Some(e);
+                    continue;
+                }
+            }
+
+            let pool = self.current_pool();
+            let mut guard = match pool.get().await {
+                Ok(g) => g,
+                Err(e) => {
+                    last_err = Some(e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = guard.conn().send_raw(cmd).await {
+                drop(guard.take());
+                if !retry::may_retry(&[name], self.config.retry_unsafe) {
+                    return Err(e);
+                }
+                last_err = Some(e);
+                continue;
+            }
+            match guard.conn().read_raw_response().await {
+                Ok(raw) if raw.starts_with(b"-READONLY") => {
+                    let msg = String::from_utf8_lossy(&raw[1..]).trim_end().to_string();
+                    last_err = Some(PyrsedisError::redis(msg));
+                }
+                Ok(raw) => return Ok(raw),
+                Err(e) => {
+                    drop(guard.take());
+                    if matches!(e, PyrsedisError::Connection(_))
+                        && retry::may_retry(&[name], self.config.retry_unsafe)
+                    {
+                        last_err = Some(e);
+                        continue;
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| {
+            PyrsedisError::Sentinel("all failover retries exhausted".into())
+        }))
+    }
+
+    async fn pipeline_raw_once(&self, commands: &[Vec<String>]) -> Result<Vec<Bytes>> {
+        let pool = self.current_pool();
+        let mut guard = pool.get_bulk().await?;
+        let buf = encode_pipeline(commands);
+        let result = async {
+            guard.conn().send_raw(&buf).await?;
+            let mut responses = Vec::with_capacity(commands.len());
+            for _ in commands {
+                responses.push(guard.conn().read_raw_response().await?);
+            }
+            Ok(responses)
+        }
+        .await;
+        if result.is_err() {
+            drop(guard.take());
+        }
+        result
+    }
 }
 
 impl Router for SentinelRouter {
@@ -162,7 +447,7 @@ impl Router for SentinelRouter {
     async fn pipeline(&self, commands: &[Vec<String>]) -> Result<Vec<RespValue>> {
         // Pipelines go to the current master, no per-command failover
         let pool = self.current_pool();
-        let mut guard = pool.get().await?;
+        let mut guard = pool.get_bulk().await?;
 
         // Send all commands
         for cmd_args in commands {
@@ -190,28 +475,188 @@ impl Router for SentinelRouter {
     fn pool_available(&self) -> usize {
         self.current_pool().available()
     }
+
+    /// Stats of the current master's pool; they restart after a failover.
+    fn pool_stats(&self) -> StatsSnapshot {
+        self.current_pool().stats()
+    }
+
+    fn disconnect_all(&self) {
+        self.current_pool().disconnect_all();
+    }
+
+    async fn warmup(&self, count: usize) -> Result<usize> {
+        self.current_pool().warmup(count).await
+    }
+
+    fn reset_after_fork(&self) {
+        self.current_pool().reset_after_fork();
+    }
 }
 
 // ── Helpers ────────────────────────────────────────────────────────
 
+/// Connect to the sentinel at `addr`, authenticating when a password is set.
+async fn connect_sentinel(addr: &str, config: &ConnectionConfig) -> Result<RedisConnection> {
+    let mut conn = RedisConnection::connect_with_config(addr, config).await?;
+    if let Some(ref pass) = config.sentinel_password {
+        conn.auth(config.sentinel_username.as_deref(), pass).await?;
+    } else if let Some(ref pass) = config.password {
+        // Sentinels may share the data nodes' auth, or have none
+        let _ = conn.auth(config.username.as_deref(), pass).await;
+    }
+    Ok(conn)
+}
+
+/// Follow `+switch-master` announcements for as long as the router lives,
+/// moving it to each newly promoted master.
+///
+/// Sentinels are tried in turn; when the subscribed one goes away the
+/// next is subscribed to after [`WATCH_INTERVAL`].
+async fn watch_switch_master(router: Weak<SentinelRouter>) {
+    for turn in 0.. {
+        let Some((addr, config)) = router.upgrade().map(|r| {
+            let (host, port) = &r.sentinels[turn % r.sentinels.len()];
+            (format!("{host}:{port}"), r.config.clone())
+        }) else {
+            break; // Router dropped, exit
+        };
+        if let Ok(mut conn) = connect_sentinel(&addr, &config).await {
+            let mut channels = vec!["SUBSCRIBE", SWITCH_MASTER_CHANNEL];
+            if config.read_preference != ReadPreference::Primary {
+                channels.extend(REPLICA_CHANNELS);
+            }
+            let subscribe = encode_command_str(&channels);
+            if conn.send_raw(&subscribe).await.is_ok() {
+                loop {
+                    let message = match tokio::time::timeout(WATCH_INTERVAL, conn.read_response()).await {
+                        Ok(Ok(RespValue::Error(_)) | Err(_)) => break,
+                        Ok(Ok(message)) => message,
+                        Err(_) if router.strong_count() == 0 => return,
+                        Err(_) => continue,
+                    };
+                    let Some(router) = router.upgrade() else { return };
+                    if let Some(master) = switched_master(&message, &router.master_name) {
+                        if router.switch_to(master) {
+                            router.refresh_replicas().await;
+                        }
+                    } else if replica_event(&message, &router.master_name) {
+                        router.refresh_replicas().await;
+                    }
+                }
+            }
+        }
+        tokio::time::sleep(WATCH_INTERVAL).await;
+    }
+}
+
+/// The new address of `master_name` announced by a `+switch-master`
+/// message, whose payload reads `<name> <old-ip> <old-port> <new-ip> <new-port>`.
+fn switched_master(message: &RespValue, master_name: &str) -> Option<String> {
+    let RespValue::Array(items) = message else { return None };
+    let [kind, channel, payload] = items.as_slice() else { return None };
+    if kind.as_str() != Some("message") || channel.as_str() != Some(SWITCH_MASTER_CHANNEL) {
+        return None;
+    }
+    match payload.as_str()?.split_whitespace().collect::<Vec<_>>()[..] {
+        [name, _, _, host, port] if name == master_name => Some(format!("{host}:{port}")),
+        _ => None,
+    }
+}
+
+/// Whether `message` reports a replica of `master_name` going down,
+/// coming back or being added. The payload reads
+/// `slave <name> <ip> <port> @ <master-name> <master-ip> <master-port>`.
+fn replica_event(message: &RespValue, master_name: &str) -> bool {
+    let RespValue::Array(items) = message else { return false };
+    let [kind, channel, payload] = items.as_slice() else { return false };
+    if kind.as_str() != Some("message") || !channel.as_str().is_some_and(|c| REPLICA_CHANNELS.contains(&c)) {
+        return false;
+    }
+    let words: Vec<&str> = payload.as_str().unwrap_or("").split_whitespace().collect();
+    matches!(words[..], ["slave", _, _, _, "@", name, ..] if name == master_name)
+}
+
+/// Addresses of the healthy replicas in a `SENTINEL REPLICAS` reply: not
+/// flagged down or disconnected, with their link to the master up.
+fn healthy_replicas(reply: &RespValue) -> Vec<String> {
+    let RespValue::Array(entries) = reply else { return Vec::new() };
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let field = |name: &str| -> Option<&str> {
+                match entry {
+                    RespValue::Map(pairs) => pairs.iter().find(|(k, _)| k.as_str() == Some(name))?.1.as_str(),
+                    RespValue::Array(items) => {
+                        items.chunks_exact(2).find(|pair| pair[0].as_str() == Some(name))?[1].as_str()
+                    }
+                    _ => None,
+                }
+            };
+            let flags = field("flags").unwrap_or("");
+            let down = flags.split(',').any(|f| matches!(f, "s_down" | "o_down" | "disconnected"));
+            if down || field("master-link-status").is_some_and(|s| s != "ok") {
+                return None;
+            }
+            Some(format!("{}:{}", field("ip")?, field("port")?))
+        })
+        .collect()
+}
+
+/// Ask the sentinels for the healthy replicas of `master_name`.
+async fn resolve_replicas(
+    sentinels: &[(String, u16)],
+    master_name: &str,
+    config: &ConnectionConfig,
+) -> Result<Vec<String>> {
+    let mut last_err = None;
+    for (host, port) in sentinels {
+        let addr = format!("{host}:{port}");
+        let reply = match connect_sentinel(&addr, config).await {
+            Ok(mut conn) => conn.execute_str(&["SENTINEL", "REPLICAS", master_name]).await,
+            Err(e) => Err(e),
+        };
+        match reply {
+            Ok(reply @ RespValue::Array(_)) => return Ok(healthy_replicas(&reply)),
+            Ok(other) => {
+                last_err = Some(PyrsedisError::Sentinel(format!(
+                    "unexpected SENTINEL REPLICAS response from {addr}: {:?}",
+                    other.type_name()
+                )));
+            }
+            Err(e) => last_err = Some(e),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| PyrsedisError::Sentinel("could not contact any sentinel".into())))
+}
+
+/// Send one encoded command on a connection from `pool` and read its raw
+/// reply, dropping the connection on failure.
+async fn send_raw_on(pool: &ConnectionPool, cmd: &[u8]) -> Result<Bytes> {
+    let mut guard = pool.get().await?;
+    let result = async {
+        guard.conn().send_raw(cmd).await?;
+        guard.conn().read_raw_response().await
+    }
+    .await;
+    if result.is_err() {
+        drop(guard.take());
+    }
+    result
+}
+
 /// Resolve the master address by querying sentinel nodes.
 async fn resolve_master(
     sentinels: &[(String, u16)],
     master_name: &str,
     config: &ConnectionConfig,
 ) -> Result<String> {
-    let timeout = Duration::from_millis(config.connect_timeout_ms);
     let mut last_err = None;
 
     for (host, port) in sentinels {
         let addr = format!("{host}:{port}");
-        match RedisConnection::connect_timeout(&addr, timeout).await {
+        match connect_sentinel(&addr, config).await {
             Ok(mut conn) => {
-                // Sentinels may require auth too
-                if let Some(ref pass) = config.password {
-                    let _ = conn.auth(config.username.as_deref(), pass).await;
-                }
-
                 match conn
                     .execute_str(&["SENTINEL", "get-master-addr-by-name", master_name])
                     .await
@@ -258,15 +703,15 @@ async fn resolve_master(
     }))
 }
 
-/// Create a connection pool for the resolved master.
-fn create_master_pool(addr: &str, config: &ConnectionConfig) -> ConnectionPool {
+/// Create a connection pool for the master or a replica at `addr`.
+fn create_pool(addr: &str, config: &ConnectionConfig) -> Arc<ConnectionPool> {
     let parts: Vec<&str> = addr.rsplitn(2, ':').collect();
     let mut cfg = config.clone();
     if parts.len() == 2 {
         cfg.host = parts[1].to_string();
         cfg.port = parts[0].parse().unwrap_or(6379);
     }
-    ConnectionPool::new(cfg)
+    ConnectionPool::shared(cfg)
 }
 
 // ── Tests ──────────────────────────────────────────────────────────
@@ -276,9 +721,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn create_master_pool_parses_addr() {
+    fn create_pool_parses_addr() {
         let config = ConnectionConfig::default();
-        let pool = create_master_pool("10.0.0.1:6380", &config);
+        let pool = create_pool("10.0.0.1:6380", &config);
         // Pool should be created successfully
         assert_eq!(pool.max_size(), config.pool_size);
     }
@@ -292,11 +737,290 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// Start a fake server that answers each command with `reply(args)`.
+    async fn fake_server<F>(listener: tokio::net::TcpListener, reply: F)
+    where
+        F: Fn(&[String]) -> Vec<u8> + Send + Sync + 'static,
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let reply = Arc::new(reply);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let reply = Arc::clone(&reply);
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    while let Ok(n @ 1..) = socket.read(&mut chunk).await {
+                        buf.extend_from_slice(&chunk[..n]);
+                        let mut out = Vec::new();
+                        while let Ok((RespValue::Array(items), used)) =
+                            crate::resp::parser::parse(&Bytes::copy_from_slice(&buf))
+                        {
+                            buf.drain(..used);
+                            let args: Vec<String> =
+                                items.iter().filter_map(|v| v.as_str().map(String::from)).collect();
+                            out.extend(reply(&args));
+                        }
+                        if socket.write_all(&out).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn raw_path_fails_over_on_readonly() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let old = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let new = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let sentinel = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (old_port, new_port) = (old.local_addr().unwrap().port(), new.local_addr().unwrap().port());
+        let sentinel_port = sentinel.local_addr().unwrap().port();
+
+        // The sentinel reports the old master first, then the promoted one.
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let seen = Arc::clone(&lookups);
+        fake_server(sentinel, move |args| {
+            if args[0] == "SUBSCRIBE" {
+                return Vec::new();
+            }
+            let port = if seen.fetch_add(1, Ordering::SeqCst) == 0 { old_port } else { new_port };
+            let port = port.to_string();
+            format!("*2\r\n$9\r\n127.0.0.1\r\n${}\r\n{port}\r\n", port.len()).into_bytes()
+        })
+        .await;
+        fake_server(old, |_| b"-READONLY You can't write against a read only replica.\r\n".to_vec()).await;
+        fake_server(new, |args| format!("+{}\r\n", args[0]).into_bytes()).await;
+
+        let sentinels = vec![("127.0.0.1".to_string(), sentinel_port)];
+        let router = SentinelRouter::new(sentinels, "mymaster".into(), ConnectionConfig::default(), Some(2), Some(1))
+            .await
+            .unwrap();
+        assert_eq!(router.master_addr(), format!("127.0.0.1:{old_port}"));
+
+        let raw = router.execute_raw(&["SET", "k", "v"]).await.unwrap();
+        assert_eq!(&raw[..], b"+SET\r\n");
+        assert_eq!(router.master_addr(), format!("127.0.0.1:{new_port}"));
+
+        let commands = vec![vec!["PING".to_string()], vec!["GET".to_string(), "k".to_string()]];
+        let replies = router.pipeline_raw(&commands).await.unwrap();
+        assert_eq!(&replies[0][..], b"+PING\r\n");
+        assert_eq!(&replies[1][..], b"+GET\r\n");
+        assert_eq!(lookups.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn raw_path_gives_up_after_retries() {
+        let replica = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let sentinel = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let replica_port = replica.local_addr().unwrap().port().to_string();
+        let sentinel_port = sentinel.local_addr().unwrap().port();
+        fake_server(sentinel, move |_| {
+            format!("*2\r\n$9\r\n127.0.0.1\r\n${}\r\n{replica_port}\r\n", replica_port.len()).into_bytes()
+        })
+        .await;
+        fake_server(replica, |_| b"-READONLY replica\r\n".to_vec()).await;
+
+        let sentinels = vec![("127.0.0.1".to_string(), sentinel_port)];
+        let router = SentinelRouter::new(sentinels, "mymaster".into(), ConnectionConfig::default(), Some(1), Some(1))
+            .await
+            .unwrap();
+        let err = router.execute_raw(&["SET", "k", "v"]).await.unwrap_err();
+        assert!(matches!(err, PyrsedisError::Redis { .. }), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn switch_master_event_moves_to_the_promoted_master() {
+        let sentinel = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let sentinel_port = sentinel.local_addr().unwrap().port();
+        fake_server(sentinel, |args| match args[0].as_str() {
+            "SUBSCRIBE" => b"*3\r\n$9\r\nsubscribe\r\n$14\r\n+switch-master\r\n:1\r\n\
+                *3\r\n$7\r\nmessage\r\n$14\r\n+switch-master\r\n$33\r\nother 10.0.0.1 6379 10.0.0.2 6379\r\n\
+                *3\r\n$7\r\nmessage\r\n$14\r\n+switch-master\r\n$36\r\nmymaster 10.0.0.1 6379 10.0.0.3 6380\r\n"
+                .to_vec(),
+            _ => b"*2\r\n$8\r\n10.0.0.1\r\n$4\r\n6379\r\n".to_vec(),
+        })
+        .await;
+
+        let sentinels = vec![("127.0.0.1".to_string(), sentinel_port)];
+        let router = SentinelRouter::new(sentinels, "mymaster".into(), ConnectionConfig::default(), None, None)
+            .await
+            .unwrap();
+        for _ in 0..100 {
+            if router.master_addr() != "10.0.0.1:6379" {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(router.master_addr(), "10.0.0.3:6380");
+    }
+
+    #[test]
+    fn switch_master_payload() {
+        let bulk = |s: &str| RespValue::BulkString(Bytes::copy_from_slice(s.as_bytes()));
+        let message = |payload: &str| RespValue::Array(vec![bulk("message"), bulk("+switch-master"), bulk(payload)]);
+        assert_eq!(switched_master(&message("m 1.1.1.1 1 2.2.2.2 2"), "m"), Some("2.2.2.2:2".into()));
+        assert_eq!(switched_master(&message("n 1.1.1.1 1 2.2.2.2 2"), "m"), None);
+        assert_eq!(switched_master(&message("m 2.2.2.2"), "m"), None);
+        assert_eq!(switched_master(&bulk("m 1.1.1.1 1 2.2.2.2 2"), "m"), None);
+    }
+
+    /// A sentinel for `mymaster` at `master_port`, listing `replicas`
+    /// as `SENTINEL REPLICAS` entries.
+    async fn fake_sentinel(master_port: u16, replicas: Vec<(u16, &'static str)>) -> u16 {
+        let sentinel = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = sentinel.local_addr().unwrap().port();
+        fake_server(sentinel, move |args| match args.get(1).map(String::as_str) {
+            Some("get-master-addr-by-name") => {
+                format!("*2\r\n$9\r\n127.0.0.1\r\n$5\r\n{master_port}\r\n").into_bytes()
+            }
+            Some("REPLICAS") => {
+                let mut out = format!("*{}\r\n", replicas.len());
+                for (port, flags) in &replicas {
+                    let fields = [("ip", "127.0.0.1".to_string()), ("port", port.to_string()), ("flags", flags.to_string())];
+                    out.push_str("*6\r\n");
+                    for (k, v) in fields {
+                        out.push_str(&format!("${}\r\n{k}\r\n${}\r\n{v}\r\n", k.len(), v.len()));
+                    }
+                }
+                out.into_bytes()
+            }
+            Some("MASTER") => b"-ERR No such master with that name\r\n".to_vec(),
+            _ => Vec::new(),
+        })
+        .await;
+        port
+    }
+
+    /// A data node answering every command with `reply`.
+    async fn fake_node(reply: &'static [u8]) -> u16 {
+        let node = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = node.local_addr().unwrap().port();
+        fake_server(node, move |_| reply.to_vec()).await;
+        port
+    }
+
+    #[tokio::test]
+    async fn reads_go_to_healthy_replicas() {
+        let master = fake_node(b"$6\r\nmaster\r\n").await;
+        let replica = fake_node(b"$7\r\nreplica\r\n").await;
+        let down = fake_node(b"$4\r\ndown\r\n").await;
+        let sentinel = fake_sentinel(master, vec![(replica, "slave"), (down, "slave,s_down")]).await;
+        let config = ConnectionConfig { read_preference: ReadPreference::PreferReplica, ..ConnectionConfig::default() };
+        let router = SentinelRouter::new(vec![("127.0.0.1".into(), sentinel)], "mymaster".into(), config, None, None)
+            .await
+            .unwrap();
+        assert_eq!(router.replica_addrs(), [format!("127.0.0.1:{replica}")]);
+        assert_eq!(&router.execute_raw(&["GET", "k"]).await.unwrap()[..], b"$7\r\nreplica\r\n");
+        assert_eq!(&router.execute_raw_bytes(&[b"HGET", b"h", b"f"]).await.unwrap()[..], b"$7\r\nreplica\r\n");
+        assert_eq!(&router.execute_raw(&["SET", "k", "v"]).await.unwrap()[..], b"$6\r\nmaster\r\n");
+    }
+
+    #[tokio::test]
+    async fn sentinel_commands_reach_a_live_sentinel() {
+        let master = fake_node(b"+OK\r\n").await;
+        let sentinel = fake_sentinel(master, vec![(7001, "slave")]).await;
+        let dead = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+        let sentinels = vec![("127.0.0.1".into(), dead), ("127.0.0.1".into(), sentinel)];
+        let router = SentinelRouter::new(sentinels, "mymaster".into(), ConnectionConfig::default(), None, None)
+            .await
+            .unwrap();
+
+        let reply = router.execute_on_sentinel(&["SENTINEL", "REPLICAS", "mymaster"]).await.unwrap();
+        assert_eq!(reply.into_array().unwrap().len(), 1);
+        let err = router.execute_on_sentinel(&["SENTINEL", "MASTER", "other"]).await.unwrap_err();
+        assert_eq!(err.to_string(), "redis error: ERR No such master with that name (command: SENTINEL \"MASTER\" \"other\")");
+    }
+
+    #[tokio::test]
+    async fn failed_replica_reads_fall_back_to_the_master() {
+        let master = fake_node(b"$6\r\nmaster\r\n").await;
+        let gone = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+        let sentinel = fake_sentinel(master, vec![(gone, "slave")]).await;
+        let sentinels = vec![("127.0.0.1".to_string(), sentinel)];
+        let config = ConnectionConfig {
+            read_preference: ReadPreference::PreferReplica,
+            connect_timeout_ms: 200,
+            ..ConnectionConfig::default()
+        };
+        let router = SentinelRouter::new(sentinels.clone(), "mymaster".into(), config.clone(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(&router.execute_raw(&["GET", "k"]).await.unwrap()[..], b"$6\r\nmaster\r\n");
+        assert!(router.replica_addrs().is_empty());
+
+        // Without a replica, required replica reads fail
+        let config = ConnectionConfig { read_preference: ReadPreference::ReplicaRequired, ..config };
+        let router = SentinelRouter::new(sentinels, "mymaster".into(), config, None, None).await.unwrap();
+        assert!(router.execute_raw(&["GET", "k"]).await.is_err());
+        assert!(router.execute_raw(&["GET", "k"]).await.is_err());
+        assert_eq!(&router.execute_raw(&["SET", "k", "v"]).await.unwrap()[..], b"$6\r\nmaster\r\n");
+    }
+
+    #[test]
+    fn replica_listing_and_events() {
+        let bulk = |s: &str| RespValue::BulkString(Bytes::copy_from_slice(s.as_bytes()));
+        let entry = |fields: &[&str]| RespValue::Array(fields.iter().map(|f| bulk(f)).collect());
+        let reply = RespValue::Array(vec![
+            entry(&["ip", "10.0.0.2", "port", "6379", "flags", "slave", "master-link-status", "ok"]),
+            entry(&["ip", "10.0.0.3", "port", "6379", "flags", "slave,disconnected"]),
+            entry(&["ip", "10.0.0.4", "port", "6379", "flags", "slave", "master-link-status", "err"]),
+            RespValue::Map(vec![(bulk("ip"), bulk("10.0.0.5")), (bulk("port"), bulk("6380")), (bulk("flags"), bulk("slave"))]),
+        ]);
+        assert_eq!(healthy_replicas(&reply), ["10.0.0.2:6379", "10.0.0.5:6380"]);
+
+        let message = |channel: &str, payload: &str| RespValue::Array(vec![bulk("message"), bulk(channel), bulk(payload)]);
+        let payload = "slave 10.0.0.2:6379 10.0.0.2 6379 @ mymaster 10.0.0.1 6379";
+        assert!(replica_event(&message("+sdown", payload), "mymaster"));
+        assert!(replica_event(&message("+slave", payload), "mymaster"));
+        assert!(!replica_event(&message("+sdown", payload), "other"));
+        assert!(!replica_event(&message("+sdown", "master mymaster 10.0.0.1 6379"), "mymaster"));
+        assert!(!replica_event(&message("+switch-master", payload), "mymaster"));
+    }
+
+    #[tokio::test]
+    async fn sentinels_get_their_own_credentials() {
+        let sentinel = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let sentinel_addr = sentinel.local_addr().unwrap();
+        fake_server(sentinel, |args| match args[0].as_str() {
+            "AUTH" if args[1..] == ["suser", "spass"] => b"+OK\r\n".to_vec(),
+            "AUTH" => b"-WRONGPASS invalid username-password pair\r\n".to_vec(),
+            _ => b"*2\r\n$9\r\n127.0.0.1\r\n$4\r\n6380\r\n".to_vec(),
+        })
+        .await;
+        let sentinels = vec![("127.0.0.1".to_string(), sentinel_addr.port())];
+        let mut config = ConnectionConfig {
+            username: Some("user".into()),
+            password: Some("pass".into()),
+            sentinel_username: Some("suser".into()),
+            sentinel_password: Some("spass".into()),
+            ..ConnectionConfig::default()
+        };
+        let master = resolve_master(&sentinels, "mymaster", &config).await.unwrap();
+        assert_eq!(master, "127.0.0.1:6380");
+
+        // A rejected sentinel login is an error once sentinel auth is explicit
+        config.sentinel_password = Some("wrong".into());
+        assert!(resolve_master(&sentinels, "mymaster", &config).await.is_err());
+
+        // Without sentinel credentials the data ones are tried and a refusal ignored
+        config.sentinel_username = None;
+        config.sentinel_password = None;
+        let master = resolve_master(&sentinels, "mymaster", &config).await.unwrap();
+        assert_eq!(master, "127.0.0.1:6380");
+    }
+
     #[tokio::test]
     async fn resolve_master_unreachable() {
         let sentinels = vec![("127.0.0.1".to_string(), 1u16)];
-        let mut config = ConnectionConfig::default();
-        config.connect_timeout_ms = 100;
+        let config = ConnectionConfig {
+            connect_timeout_ms: 100,
+            ..ConnectionConfig::default()
+        };
         let result = resolve_master(&sentinels, "mymaster", &config).await;
         assert!(result.is_err());
     }
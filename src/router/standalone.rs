@@ -4,34 +4,64 @@
 
 use bytes::Bytes;
 use crate::config::ConnectionConfig;
-use crate::connection::pool::ConnectionPool;
+use crate::connection::pool::{ConnectionPool, LeasedConnection};
+use crate::connection::stats::StatsSnapshot;
 use crate::error::Result;
 use crate::resp::types::RespValue;
-use crate::resp::writer::{encode_command_str, encode_pipeline};
+use crate::resp::writer::{encode_command, encode_command_str, encode_pipeline};
+use crate::router::retry;
 use crate::router::Router;
+use std::sync::Arc;
 
 /// Router for standalone (single-server) Redis topology.
+///
+/// A command that fails with a transport error is replayed once on a fresh
+/// connection, provided it is idempotent (see [`retry`]) or the config
+/// opts into `retry_unsafe`.
 pub struct StandaloneRouter {
-    pool: ConnectionPool,
+    pool: Arc<ConnectionPool>,
+    retry_unsafe: bool,
 }
 
 impl StandaloneRouter {
     /// Create a new standalone router.
     pub fn new(config: ConnectionConfig) -> Self {
+        let retry_unsafe = config.retry_unsafe;
         Self {
-            pool: ConnectionPool::new(config),
+            pool: ConnectionPool::shared(config),
+            retry_unsafe,
         }
     }
 
+    /// Address of the server.
+    pub fn addr(&self) -> String {
+        self.pool.addr()
+    }
+
     /// Execute a command and return the raw RESP frame as `Bytes`.
     ///
     /// Only performs a lightweight frame-length check (no `RespValue` tree).
     /// The caller can then do a single-pass `parse_to_python` with the GIL held.
     pub async fn execute_raw(&self, args: &[&str]) -> Result<Bytes> {
-        let mut guard = self.pool.get().await?;
         let cmd = encode_command_str(args);
-        guard.conn().send_raw(&cmd).await?;
-        guard.conn().read_raw_response().await
+        match self.execute_raw_once(&cmd).await {
+            Err(e) if retry::is_ambiguous_failure(&e) && retry::may_retry(args, self.retry_unsafe) => {
+                self.execute_raw_once(&cmd).await
+            }
+            result => result,
+        }
+    }
+
+    /// Like [`execute_raw`](Self::execute_raw), with binary-safe arguments.
+    pub async fn execute_raw_bytes(&self, args: &[&[u8]]) -> Result<Bytes> {
+        let cmd = encode_command(args);
+        let name = args.first().and_then(|c| std::str::from_utf8(c).ok()).unwrap_or("");
+        match self.execute_raw_once(&cmd).await {
+            Err(e) if retry::is_ambiguous_failure(&e) && retry::may_retry(&[name], self.retry_unsafe) => {
+                self.execute_raw_once(&cmd).await
+            }
+            result => result,
+        }
     }
 
     /// Execute a pipeline and return raw RESP frames as `Vec<Bytes>`.
@@ -39,40 +69,110 @@ impl StandaloneRouter {
     /// Each response is returned as raw bytes (no parsing) so the caller
     /// can do single-pass `parse_to_python` with the GIL held.
     pub async fn pipeline_raw(&self, commands: &[Vec<String>]) -> Result<Vec<Bytes>> {
+        match self.pipeline_raw_once(commands).await {
+            Err(e) if retry::is_ambiguous_failure(&e) && retry::may_retry_pipeline(commands, self.retry_unsafe) => {
+                self.pipeline_raw_once(commands).await
+            }
+            result => result,
+        }
+    }
+
+    /// Check out a connection for exclusive use (see [`ConnectionPool::lease`]).
+    pub async fn lease(&self) -> Result<LeasedConnection> {
+        self.pool.lease().await
+    }
+
+    /// Reset a leased connection and return it to the pool.
+    pub async fn release(&self, lease: LeasedConnection) {
+        self.pool.release(lease).await;
+    }
+
+    async fn execute_raw_once(&self, cmd: &[u8]) -> Result<Bytes> {
         let mut guard = self.pool.get().await?;
-        let buf = encode_pipeline(commands);
-        guard.conn().send_raw(&buf).await?;
+        let result = async {
+            guard.conn().send_raw(cmd).await?;
+            guard.conn().read_raw_response().await
+        }
+        .await;
+        if result.is_err() {
+            // Connection state is unknown — never hand it back to the pool
+            drop(guard.take());
+        }
+        result
+    }
 
-        let mut responses = Vec::with_capacity(commands.len());
-        for _ in commands {
-            responses.push(guard.conn().read_raw_response().await?);
+    async fn pipeline_raw_once(&self, commands: &[Vec<String>]) -> Result<Vec<Bytes>> {
+        let mut guard = self.pool.get_bulk().await?;
+        let buf = encode_pipeline(commands);
+        let result = async {
+            guard.conn().send_raw(&buf).await?;
+            let mut responses = Vec::with_capacity(commands.len());
+            for _ in commands {
+                responses.push(guard.conn().read_raw_response().await?);
+            }
+            Ok(responses)
         }
-        Ok(responses)
+        .await;
+        if result.is_err() {
+            drop(guard.take());
+        }
+        result
     }
-}
 
-impl Router for StandaloneRouter {
-    async fn execute(&self, args: &[&str]) -> Result<RespValue> {
+    async fn execute_once(&self, args: &[&str]) -> Result<RespValue> {
         let mut guard = self.pool.get().await?;
         let cmd = encode_command_str(args);
-        guard.conn().send_raw(&cmd).await?;
-        guard.conn().read_response().await
+        let result = async {
+            guard.conn().send_raw(&cmd).await?;
+            guard.conn().read_response().await
+        }
+        .await;
+        if result.is_err() {
+            drop(guard.take());
+        }
+        result
     }
 
-    async fn pipeline(&self, commands: &[Vec<String>]) -> Result<Vec<RespValue>> {
-        let mut guard = self.pool.get().await?;
+    async fn pipeline_once(&self, commands: &[Vec<String>]) -> Result<Vec<RespValue>> {
+        let mut guard = self.pool.get_bulk().await?;
 
         // Encode ALL commands into a single buffer — one allocation, one write
         let buf = encode_pipeline(commands);
-        guard.conn().send_raw(&buf).await?;
+        let result = async {
+            guard.conn().send_raw(&buf).await?;
+
+            // Read all responses
+            let mut responses = Vec::with_capacity(commands.len());
+            for _ in commands {
+                responses.push(guard.conn().read_response().await?);
+            }
+            Ok(responses)
+        }
+        .await;
+        if result.is_err() {
+            drop(guard.take());
+        }
+        result
+    }
+}
 
-        // Read all responses
-        let mut responses = Vec::with_capacity(commands.len());
-        for _ in commands {
-            responses.push(guard.conn().read_response().await?);
+impl Router for StandaloneRouter {
+    async fn execute(&self, args: &[&str]) -> Result<RespValue> {
+        match self.execute_once(args).await {
+            Err(e) if retry::is_ambiguous_failure(&e) && retry::may_retry(args, self.retry_unsafe) => {
+                self.execute_once(args).await
+            }
+            result => result,
         }
+    }
 
-        Ok(responses)
+    async fn pipeline(&self, commands: &[Vec<String>]) -> Result<Vec<RespValue>> {
+        match self.pipeline_once(commands).await {
+            Err(e) if retry::is_ambiguous_failure(&e) && retry::may_retry_pipeline(commands, self.retry_unsafe) => {
+                self.pipeline_once(commands).await
+            }
+            result => result,
+        }
     }
 
     fn pool_idle_count(&self) -> usize {
@@ -82,6 +182,22 @@ impl Router for StandaloneRouter {
     fn pool_available(&self) -> usize {
         self.pool.available()
     }
+
+    fn pool_stats(&self) -> StatsSnapshot {
+        self.pool.stats()
+    }
+
+    fn disconnect_all(&self) {
+        self.pool.disconnect_all();
+    }
+
+    async fn warmup(&self, count: usize) -> Result<usize> {
+        self.pool.warmup(count).await
+    }
+
+    fn reset_after_fork(&self) {
+        self.pool.reset_after_fork();
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────────────
@@ -114,6 +230,35 @@ mod tests {
         addr
     }
 
+    /// Mock server that drops the first connection after reading a command
+    /// (simulating a lost reply), then answers every later command.
+    async fn mock_server_dropping_first(reply: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        tokio::spawn(async move {
+            let mut first = true;
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = vec![0u8; 4096];
+                if first {
+                    first = false;
+                    let _ = socket.read(&mut buf).await;
+                    continue; // drop the socket without replying
+                }
+                tokio::spawn(async move {
+                    while let Ok(n) = socket.read(&mut buf).await {
+                        if n == 0 || socket.write_all(reply).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        addr
+    }
+
     fn router_config(addr: &str) -> ConnectionConfig {
         let parts: Vec<&str> = addr.split(':').collect();
         ConnectionConfig {
@@ -188,6 +333,61 @@ mod tests {
         assert_eq!(results[2], RespValue::Integer(42));
     }
 
+    #[tokio::test]
+    async fn standalone_retries_idempotent_command() {
+        let addr = mock_server_dropping_first(b"$1\r\nv\r\n").await;
+        let router = StandaloneRouter::new(router_config(&addr));
+
+        let raw = router.execute_raw(&["GET", "k"]).await.unwrap();
+        assert_eq!(&raw[..], b"$1\r\nv\r\n");
+    }
+
+    #[tokio::test]
+    async fn standalone_execute_raw_bytes_retries_idempotent() {
+        let addr = mock_server_dropping_first(b"+OK\r\n").await;
+        let router = StandaloneRouter::new(router_config(&addr));
+
+        let raw = router.execute_raw_bytes(&[b"SET", b"k", &[0x00, 0xff]]).await.unwrap();
+        assert_eq!(&raw[..], b"+OK\r\n");
+    }
+
+    #[tokio::test]
+    async fn standalone_does_not_retry_unsafe_command() {
+        let addr = mock_server_dropping_first(b":1\r\n").await;
+        let router = StandaloneRouter::new(router_config(&addr));
+
+        assert!(router.execute_raw(&["INCR", "k"]).await.is_err());
+        // The broken connection was discarded, so the next call succeeds
+        assert_eq!(router.pool_idle_count(), 0);
+        let raw = router.execute_raw(&["INCR", "k"]).await.unwrap();
+        assert_eq!(&raw[..], b":1\r\n");
+    }
+
+    #[tokio::test]
+    async fn standalone_retry_unsafe_opt_in() {
+        let addr = mock_server_dropping_first(b":1\r\n").await;
+        let config = ConnectionConfig {
+            retry_unsafe: true,
+            ..router_config(&addr)
+        };
+        let router = StandaloneRouter::new(config);
+
+        let result = router.execute(&["INCR", "k"]).await.unwrap();
+        assert_eq!(result, RespValue::Integer(1));
+    }
+
+    #[tokio::test]
+    async fn standalone_pipeline_retry_requires_all_safe() {
+        let addr = mock_server_dropping_first(b"+OK\r\n").await;
+        let router = StandaloneRouter::new(router_config(&addr));
+
+        let commands = vec![
+            vec!["SET".into(), "a".into(), "1".into()],
+            vec!["INCR".into(), "b".into()],
+        ];
+        assert!(router.pipeline_raw(&commands).await.is_err());
+    }
+
     #[tokio::test]
     async fn standalone_pool_stats() {
         let addr = mock_server_with_responses(vec![b"+PONG\r\n".to_vec()]).await;
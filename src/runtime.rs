@@ -4,11 +4,19 @@
 //! of the Python process. All async I/O (Redis connections, sentinel monitoring,
 //! etc.) runs on this runtime's thread pool.
 
-use std::sync::OnceLock;
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicPtr, Ordering};
 use tokio::runtime::Runtime;
 
-/// Global tokio runtime, initialized once on first use.
-static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+/// Global tokio runtime, initialized on first use.
+///
+/// Stored as a leaked pointer rather than a `OnceLock` so that a forked
+/// child can discard the parent's runtime (whose worker threads do not
+/// exist in the child) via [`reset_after_fork`].
+static RUNTIME: AtomicPtr<Runtime> = AtomicPtr::new(std::ptr::null_mut());
+
+/// Serializes runtime construction.
+static INIT_LOCK: Mutex<()> = Mutex::new(());
 
 /// Get (or initialize) the global tokio runtime.
 ///
@@ -16,31 +24,59 @@ static RUNTIME: OnceLock<Runtime> = OnceLock::new();
 /// (typically equal to the number of CPU cores). Override with the
 /// `PYRSEDIS_RUNTIME_THREADS` environment variable.
 pub fn get_runtime() -> &'static Runtime {
-    RUNTIME.get_or_init(|| {
-        let mut builder = tokio::runtime::Builder::new_multi_thread();
-        builder.enable_all();
-
-        // Allow overriding thread count
-        if let Ok(threads) = std::env::var("PYRSEDIS_RUNTIME_THREADS") {
-            if let Ok(n) = threads.parse::<usize>() {
-                if n > 0 {
-                    builder.worker_threads(n);
-                }
+    let ptr = RUNTIME.load(Ordering::Acquire);
+    if !ptr.is_null() {
+        // SAFETY: runtimes are leaked and never freed, so the pointer
+        // stays valid for the lifetime of the process.
+        return unsafe { &*ptr };
+    }
+
+    let _guard = INIT_LOCK.lock();
+    let ptr = RUNTIME.load(Ordering::Acquire);
+    if !ptr.is_null() {
+        // SAFETY: see above.
+        return unsafe { &*ptr };
+    }
+    let rt: &'static Runtime = Box::leak(Box::new(build_runtime()));
+    RUNTIME.store(rt as *const Runtime as *mut Runtime, Ordering::Release);
+    rt
+}
+
+fn build_runtime() -> Runtime {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.enable_all();
+
+    // Allow overriding thread count
+    if let Ok(threads) = std::env::var("PYRSEDIS_RUNTIME_THREADS") {
+        if let Ok(n) = threads.parse::<usize>() {
+            if n > 0 {
+                builder.worker_threads(n);
             }
         }
+    }
 
-        match builder.thread_name("pyrsedis-rt").build() {
-            Ok(rt) => rt,
-            Err(e) => {
-                // Cannot return an error from OnceLock::get_or_init, so we
-                // must panic here. This is acceptable because runtime creation
-                // failure (e.g. ulimit too low) is unrecoverable. PyO3 will
-                // catch the panic at the FFI boundary and convert it to a
-                // Python RuntimeError.
-                panic!("pyrsedis: failed to create tokio runtime: {e}");
-            }
+    match builder.thread_name("pyrsedis-rt").build() {
+        Ok(rt) => rt,
+        Err(e) => {
+            // Cannot return an error from the lazy initializer, so we
+            // must panic here. This is acceptable because runtime creation
+            // failure (e.g. ulimit too low) is unrecoverable. PyO3 will
+            // catch the panic at the FFI boundary and convert it to a
+            // Python RuntimeError.
+            panic!("pyrsedis: failed to create tokio runtime: {e}");
         }
-    })
+    }
+}
+
+/// Forget the inherited runtime in a forked child process.
+///
+/// The parent's worker threads are not copied by `fork()` and its I/O
+/// driver shares an epoll instance with the parent, so the old runtime is
+/// leaked (never dropped or driven). The next [`get_runtime`] call builds
+/// a fresh one.
+pub fn reset_after_fork() {
+    let _guard = INIT_LOCK.lock();
+    RUNTIME.store(std::ptr::null_mut(), Ordering::Release);
 }
 
 /// Block on a future using the global runtime.
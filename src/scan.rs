@@ -0,0 +1,231 @@
+//! Cursor-driven SCAN iterators.
+//!
+//! `Redis.scan_iter()` and its `hscan_iter` / `sscan_iter` / `zscan_iter`
+//! siblings return a [`ScanIterator`] that issues the next SCAN call
+//! whenever the items of the previous page run out, so Python code never
+//! sees a cursor. A keyspace scan on a cluster walks every master in turn.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyList, PyString, PyTuple};
+
+use crate::error::PyrsedisError;
+use crate::response::{parse_to_python_with, ParseOptions};
+use crate::router::ClientRouter;
+use crate::runtime;
+
+/// Which SCAN variant an iterator drives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanKind {
+    /// `SCAN` over the keyspace; yields keys.
+    Keys,
+    /// `HSCAN`; yields `(field, value)` tuples.
+    Hash,
+    /// `SSCAN`; yields members.
+    Set,
+    /// `ZSCAN`; yields `(member, score)` tuples.
+    SortedSet,
+}
+
+impl ScanKind {
+    fn command(self) -> &'static str {
+        match self {
+            Self::Keys => "SCAN",
+            Self::Hash => "HSCAN",
+            Self::Set => "SSCAN",
+            Self::SortedSet => "ZSCAN",
+        }
+    }
+}
+
+/// `command [key] cursor [MATCH pattern] [COUNT count]`.
+pub fn scan_args(kind: ScanKind, key: Option<&str>, cursor: u64, pattern: Option<&str>, count: Option<u64>) -> Vec<String> {
+    let mut cmd = vec![kind.command().to_string()];
+    cmd.extend(key.map(str::to_string));
+    cmd.push(cursor.to_string());
+    if let Some(p) = pattern {
+        cmd.push("MATCH".into());
+        cmd.push(p.into());
+    }
+    if let Some(c) = count {
+        cmd.push("COUNT".into());
+        cmd.push(c.to_string());
+    }
+    cmd
+}
+
+/// Split a `[cursor, [item, ...]]` page.
+pub(crate) fn split_page<'py>(page: &Bound<'py, PyAny>) -> PyResult<(u64, Bound<'py, PyList>)> {
+    let malformed = || PyrsedisError::Protocol("malformed SCAN reply".into());
+    let page = page.cast::<PyList>().map_err(|_| malformed())?;
+    if page.len() != 2 {
+        return Err(malformed().into());
+    }
+    let cursor = page.get_item(0)?;
+    let cursor = if let Ok(s) = cursor.cast::<PyString>() {
+        s.to_str()?.parse().ok()
+    } else if let Ok(b) = cursor.cast::<PyBytes>() {
+        std::str::from_utf8(b.as_bytes()).ok().and_then(|s| s.parse().ok())
+    } else {
+        None
+    };
+    let items = page.get_item(1)?.cast_into::<PyList>().map_err(|_| malformed())?;
+    Ok((cursor.ok_or_else(malformed)?, items))
+}
+
+/// A ZSCAN score, sent as a bulk string (or a double under RESP3).
+fn score(item: &Bound<'_, PyAny>) -> PyResult<f64> {
+    if let Ok(v) = item.extract::<f64>() {
+        return Ok(v);
+    }
+    let text = match item.cast::<PyBytes>() {
+        Ok(b) => String::from_utf8_lossy(b.as_bytes()).into_owned(),
+        Err(_) => item.str()?.to_string(),
+    };
+    text.parse()
+        .map_err(|_| PyrsedisError::Protocol(format!("invalid score in ZSCAN reply: {text}")).into())
+}
+
+// ── ScanIterator ──────────────────────────────────────────────────
+
+/// Iterator over the results of a SCAN-family command.
+///
+/// Created by :meth:`Redis.scan_iter`, :meth:`Redis.hscan_iter`,
+/// :meth:`Redis.sscan_iter` and :meth:`Redis.zscan_iter`. Each page is
+/// fetched when the previous one is used up. Like the commands it drives,
+/// it may yield an item more than once if the data changes mid-scan.
+#[pyclass(name = "ScanIterator")]
+pub struct ScanIterator {
+    router: Arc<ClientRouter>,
+    opts: ParseOptions,
+    kind: ScanKind,
+    key: Option<String>,
+    pattern: Option<String>,
+    count: Option<u64>,
+    /// Nodes still to scan; the front one is in progress.
+    nodes: VecDeque<Option<String>>,
+    cursor: u64,
+    pending: VecDeque<Py<PyAny>>,
+}
+
+impl ScanIterator {
+    pub(crate) fn new(
+        router: Arc<ClientRouter>,
+        opts: ParseOptions,
+        kind: ScanKind,
+        key: Option<String>,
+        pattern: Option<String>,
+        count: Option<u64>,
+    ) -> Self {
+        let nodes = match kind {
+            ScanKind::Keys => router.scan_nodes().into(),
+            // Routed by key like any other command
+            _ => VecDeque::from([None]),
+        };
+        Self { router, opts, kind, key, pattern, count, nodes, cursor: 0, pending: VecDeque::new() }
+    }
+
+    /// Fetch the next page into `pending`.
+    fn fetch(&mut self, py: Python<'_>) -> PyResult<()> {
+        let Some(node) = self.nodes.front().cloned() else { return Ok(()) };
+        let args = scan_args(self.kind, self.key.as_deref(), self.cursor, self.pattern.as_deref(), self.count);
+        let refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        let router = &self.router;
+        let raw = py.detach(|| runtime::block_on(router.execute_raw_at(node.as_deref(), &refs)))?;
+        let (page, _) = parse_to_python_with(py, &raw, self.opts)?;
+        let (cursor, items) = split_page(page.bind(py))?;
+        match self.kind {
+            ScanKind::Keys | ScanKind::Set => self.pending.extend(items.iter().map(Bound::unbind)),
+            ScanKind::Hash | ScanKind::SortedSet => {
+                if items.len() % 2 != 0 {
+                    return Err(PyrsedisError::Protocol("odd number of items in SCAN reply".into()).into());
+                }
+                let mut iter = items.iter();
+                while let (Some(first), Some(second)) = (iter.next(), iter.next()) {
+                    let pair = if self.kind == ScanKind::SortedSet {
+                        PyTuple::new(py, [first, score(&second)?.into_pyobject(py)?.into_any()])?
+                    } else {
+                        PyTuple::new(py, [first, second])?
+                    };
+                    self.pending.push_back(pair.into_any().unbind());
+                }
+            }
+        }
+        self.cursor = cursor;
+        if cursor == 0 {
+            self.nodes.pop_front();
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl ScanIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<Py<PyAny>>> {
+        // Pages may be empty even mid-scan, so keep going until the cursor ends
+        while self.pending.is_empty() && !self.nodes.is_empty() {
+            self.fetch(py)?;
+        }
+        Ok(self.pending.pop_front())
+    }
+
+    fn __repr__(&self) -> String {
+        let target = match &self.key {
+            Some(key) => format!("{} {key}", self.kind.command()),
+            None => self.kind.command().to_string(),
+        };
+        let done = self.nodes.is_empty() && self.pending.is_empty();
+        format!(
+            "ScanIterator({target}, match={:?}, done={})",
+            self.pattern.as_deref().unwrap_or("*"),
+            if done { "True" } else { "False" }
+        )
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_args_include_options() {
+        assert_eq!(scan_args(ScanKind::Keys, None, 0, None, None), ["SCAN", "0"]);
+        assert_eq!(
+            scan_args(ScanKind::Hash, Some("h"), 17, Some("f*"), Some(100)),
+            ["HSCAN", "h", "17", "MATCH", "f*", "COUNT", "100"]
+        );
+        assert_eq!(scan_args(ScanKind::SortedSet, Some("z"), 0, None, Some(5)), ["ZSCAN", "z", "0", "COUNT", "5"]);
+    }
+
+    #[test]
+    fn pages_are_split() {
+        Python::attach(|py| {
+            let page = py.eval(c"['42', ['a', 'b']]", None, None).unwrap();
+            let (cursor, items) = split_page(&page).unwrap();
+            assert_eq!(cursor, 42);
+            assert_eq!(items.len(), 2);
+            let page = py.eval(c"[b'0', []]", None, None).unwrap();
+            assert_eq!(split_page(&page).unwrap().0, 0);
+            assert!(split_page(&py.eval(c"['x', []]", None, None).unwrap()).is_err());
+            assert!(split_page(&py.eval(c"None", None, None).unwrap()).is_err());
+        });
+    }
+
+    #[test]
+    fn scores_accept_strings_and_floats() {
+        Python::attach(|py| {
+            assert_eq!(score(&py.eval(c"'1.5'", None, None).unwrap()).unwrap(), 1.5);
+            assert_eq!(score(&py.eval(c"b'-inf'", None, None).unwrap()).unwrap(), f64::NEG_INFINITY);
+            assert_eq!(score(&py.eval(c"2.0", None, None).unwrap()).unwrap(), 2.0);
+            assert!(score(&py.eval(c"'x'", None, None).unwrap()).is_err());
+        });
+    }
+}
@@ -0,0 +1,101 @@
+//! Callable Lua scripts.
+//!
+//! `Redis.register_script()` returns a [`Script`] holding the source and
+//! its SHA-1, computed locally. Calling it runs `EVALSHA` and falls back to
+//! `EVAL` on `NOSCRIPT`, which also caches the script again. Given a
+//! pipeline as `client`, it queues `EVALSHA` there instead; a pipelined
+//! call cannot be retried, so the pipeline loads its scripts with
+//! `SCRIPT LOAD` before sending its commands.
+
+use pyo3::prelude::*;
+
+use crate::client::{Pipeline, Redis};
+use crate::codec::CommandArg;
+use crate::error::{PyrsedisError, Result};
+use crate::sha1::sha1_hex;
+
+/// A Lua script registered with :meth:`Redis.register_script`.
+///
+/// ```python
+/// script = r.register_script("return redis.call('GET', KEYS[1])")
+/// script(keys=["k"])
+/// pipe = r.pipeline()
+/// script(keys=["k"], client=pipe)
+/// pipe.execute()
+/// ```
+#[pyclass(name = "Script", frozen)]
+pub struct Script {
+    client: Py<Redis>,
+    script: String,
+    sha: String,
+}
+
+impl Script {
+    /// Wrap `script`, run by default on `client`.
+    pub(crate) fn new(client: Py<Redis>, script: String) -> Self {
+        let sha = sha1_hex(script.as_bytes());
+        Self { client, script, sha }
+    }
+
+    /// `EVALSHA` with `keys` and `args`, for a pipeline.
+    fn evalsha_args(&self, keys: Vec<CommandArg>, args: Vec<CommandArg>) -> Result<Vec<String>> {
+        let mut cmd = vec!["EVALSHA".to_string(), self.sha.clone(), keys.len().to_string()];
+        for arg in keys.into_iter().chain(args) {
+            cmd.push(arg.into_string()?);
+        }
+        Ok(cmd)
+    }
+}
+
+#[pymethods]
+impl Script {
+    /// Run the script.
+    ///
+    /// Args:
+    ///     keys: Key names, seen by the script as ``KEYS``.
+    ///     args: Other arguments, seen as ``ARGV``.
+    ///     client: Run on this :class:`Redis` instead of the one that
+    ///         registered the script, or queue the call on this
+    ///         :class:`Pipeline`, which is returned.
+    ///
+    /// Returns:
+    ///     The script's reply, or the pipeline.
+    #[pyo3(signature = (keys=None, args=None, client=None))]
+    fn __call__(
+        &self,
+        py: Python<'_>,
+        keys: Option<Vec<CommandArg>>,
+        args: Option<Vec<CommandArg>>,
+        client: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        let (keys, args) = (keys.unwrap_or_default(), args.unwrap_or_default());
+        let Some(client) = client else {
+            return self.client.borrow(py).eval_cached(py, &self.sha, &self.script, &keys, &args);
+        };
+        if let Ok(pipe) = client.cast::<Pipeline>() {
+            let cmd = self.evalsha_args(keys, args)?;
+            pipe.borrow_mut().queue_script(&self.script, cmd)?;
+            return Ok(pipe.clone().into_any().unbind());
+        }
+        let redis = client
+            .cast::<Redis>()
+            .map_err(|_| PyrsedisError::Type("client must be a Redis or a Pipeline".into()))?;
+        redis.borrow().eval_cached(py, &self.sha, &self.script, &keys, &args)
+    }
+
+    /// SHA-1 of the source, the name ``EVALSHA`` takes.
+    #[getter]
+    fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    /// The Lua source.
+    #[getter]
+    fn script(&self) -> &str {
+        &self.script
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Script(sha='{}')", self.sha)
+    }
+}
@@ -0,0 +1,124 @@
+//! Paginated set intersections.
+//!
+//! `Redis.sinter_page()` walks the intersection of large sets a page at a
+//! time. The first page checks with `SINTERCARD ... LIMIT 1` whether the
+//! intersection is empty at all, and if not stores it once with
+//! `SINTERSTORE` into a snapshot key; every page is then an `SSCAN` of
+//! that snapshot. The snapshot carries a TTL that each page renews, so an
+//! abandoned pagination cleans up after itself, and it is deleted as soon
+//! as the last page has been read.
+
+use crate::crc16::{extract_hash_tag, hash_slot};
+use crate::error::{PyrsedisError, Result};
+
+/// Prefix of snapshot keys.
+const SNAPSHOT_PREFIX: &str = "pyrsedis:sinter:";
+
+/// 64-bit FNV-1a, stable across processes and Rust versions.
+fn fnv1a(data: &[u8], mut hash: u64) -> u64 {
+    for &b in data {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// The snapshot key for the intersection of `keys`.
+///
+/// The same keys always map to the same snapshot, so a later page finds
+/// the one stored by the first. The key carries the hash tag of the
+/// input keys to land in their cluster slot.
+pub fn snapshot_key(keys: &[String]) -> Result<String> {
+    let Some(first) = keys.first() else {
+        return Err(PyrsedisError::Type("sinter_page needs at least one key".into()));
+    };
+    let mut digest = 0xcbf2_9ce4_8422_2325;
+    for key in keys {
+        digest = fnv1a(key.as_bytes(), digest);
+        // Separator, so ["ab", "c"] and ["a", "bc"] differ
+        digest = fnv1a(&[0], digest);
+    }
+    let tag = String::from_utf8_lossy(extract_hash_tag(first.as_bytes()));
+    let key = format!("{SNAPSHOT_PREFIX}{{{tag}}}:{digest:016x}");
+    if hash_slot(key.as_bytes()) != hash_slot(first.as_bytes()) {
+        return Err(PyrsedisError::Type(format!(
+            "cannot derive a snapshot key in the slot of '{first}'; use a hash tag"
+        )));
+    }
+    Ok(key)
+}
+
+/// `SINTERCARD numkeys key [key ...] LIMIT 1`: non-zero when the
+/// intersection has any member.
+pub fn any_member_args(keys: &[String]) -> Vec<String> {
+    let mut cmd = Vec::with_capacity(keys.len() + 4);
+    cmd.push("SINTERCARD".to_string());
+    cmd.push(keys.len().to_string());
+    cmd.extend(keys.iter().cloned());
+    cmd.push("LIMIT".into());
+    cmd.push("1".into());
+    cmd
+}
+
+/// The pipeline for one page: on the first page (`cursor == 0`) store
+/// the snapshot, then renew its TTL and scan it.
+pub fn page_commands(keys: &[String], snapshot: &str, cursor: u64, count: u64, ttl: u64) -> Vec<Vec<String>> {
+    let mut commands = Vec::with_capacity(3);
+    if cursor == 0 {
+        let mut store = vec!["SINTERSTORE".to_string(), snapshot.to_string()];
+        store.extend(keys.iter().cloned());
+        commands.push(store);
+    }
+    commands.push(vec!["EXPIRE".into(), snapshot.into(), ttl.to_string()]);
+    commands.push(vec![
+        "SSCAN".into(),
+        snapshot.into(),
+        cursor.to_string(),
+        "COUNT".into(),
+        count.to_string(),
+    ]);
+    commands
+}
+
+/// The error for a page whose snapshot has expired.
+pub fn snapshot_expired() -> PyrsedisError {
+    PyrsedisError::redis("ERR sinter_page snapshot expired; start again from cursor 0")
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn snapshot_key_is_stable_and_slotted() {
+        let a = snapshot_key(&keys(&["{u1}:a", "{u1}:b"])).unwrap();
+        assert_eq!(a, snapshot_key(&keys(&["{u1}:a", "{u1}:b"])).unwrap());
+        assert!(a.starts_with("pyrsedis:sinter:{u1}:"));
+        assert_ne!(a, snapshot_key(&keys(&["{u1}:b", "{u1}:a"])).unwrap());
+        assert_ne!(snapshot_key(&keys(&["ab", "c"])).unwrap(), snapshot_key(&keys(&["a", "bc"])).unwrap());
+
+        let plain = snapshot_key(&keys(&["tags"])).unwrap();
+        assert_eq!(hash_slot(plain.as_bytes()), hash_slot(b"tags"));
+        assert!(snapshot_key(&[]).is_err());
+        // The tag of "a}" would be cut short at its brace
+        assert!(snapshot_key(&keys(&["a}"])).is_err());
+    }
+
+    #[test]
+    fn page_commands_store_only_on_the_first_page() {
+        let first = page_commands(&keys(&["a", "b"]), "snap", 0, 50, 60);
+        assert_eq!(first[0], ["SINTERSTORE", "snap", "a", "b"]);
+        assert_eq!(first[1], ["EXPIRE", "snap", "60"]);
+        assert_eq!(first[2], ["SSCAN", "snap", "0", "COUNT", "50"]);
+        let later = page_commands(&keys(&["a", "b"]), "snap", 12, 50, 60);
+        assert_eq!(later.len(), 2);
+        assert_eq!(later[1], ["SSCAN", "snap", "12", "COUNT", "50"]);
+        assert_eq!(any_member_args(&keys(&["a", "b"])), ["SINTERCARD", "2", "a", "b", "LIMIT", "1"]);
+    }
+}
@@ -0,0 +1,614 @@
+//! Redis Streams helpers.
+//!
+//! Builds the arguments of the `X*` commands shared by `Redis` and
+//! `Pipeline`, and reshapes their replies: a stream entry arrives as
+//! `[id, [field, value, …]]` and is returned as `(id, {field: value})`.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+
+use crate::codec::CommandArg;
+use crate::error::{PyrsedisError, Result};
+use crate::geo;
+
+// ── Reply shapes ──────────────────────────────────────────────────
+
+/// How a parsed reply is reshaped before it is returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplyShape {
+    /// A list of entries (`XRANGE`, `XREVRANGE`, `XCLAIM`).
+    Entries,
+    /// Entries per stream (`XREAD`, `XREADGROUP`): `{stream: [entry, …]}`,
+    /// or `None` when nothing arrived before the block timeout.
+    Streams,
+    /// Members with scores (`ZUNION`/`ZINTER`/`ZDIFF` `WITHSCORES`,
+    /// `ZPOPMIN`, `ZRANDMEMBER`): `[(member, score), …]` with float scores.
+    ScorePairs,
+    /// Fields with values (`HRANDFIELD` `WITHVALUES`): `[(field, value), …]`.
+    FieldPairs,
+    /// Alternating fields and values (`HGETALL`, `CONFIG GET`):
+    /// `{field: value}`. A RESP3 map already is one.
+    Dict,
+    /// An `XPENDING` summary: `{"pending", "min", "max", "consumers"}`,
+    /// with the consumers as `{name: count}`.
+    PendingSummary,
+    /// Scores that may be missing (`ZMSCORE`): floats, `None` kept.
+    Scores,
+    /// A member popped by `BZPOPMIN`/`BZPOPMAX`: `(key, member, score)`.
+    PoppedMember,
+    /// Members popped by `ZMPOP`: `(key, [(member, score), …])`.
+    PoppedMembers,
+    /// A status word such as the `TYPE` reply, as `str` even when the
+    /// server sends it as a bulk string and `decode_responses` is off.
+    Text,
+    /// `GEOPOS` positions as `(longitude, latitude)` float tuples.
+    GeoPositions,
+    /// A `GEODIST` distance as a float.
+    GeoDistance,
+    /// `GEOSEARCH` matches as dicts holding the requested fields.
+    GeoMatches { withcoord: bool, withdist: bool, withhash: bool },
+}
+
+impl ReplyShape {
+    /// Reshape `reply`. Anything that does not look like the expected
+    /// reply (an error, a `JUSTID` list) is returned unchanged.
+    pub fn apply<'py>(self, reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            Self::Entries => entries(reply),
+            Self::Streams => streams(reply),
+            Self::ScorePairs => score_pairs(reply),
+            Self::FieldPairs => field_pairs(reply),
+            Self::Dict => field_dict(reply),
+            Self::PendingSummary => pending_summary(reply),
+            Self::Scores => scores(reply),
+            Self::PoppedMember => popped_member(reply),
+            Self::PoppedMembers => popped_members(reply),
+            Self::Text => text(reply),
+            Self::GeoPositions => geo::positions(reply),
+            Self::GeoDistance => geo::distance(reply),
+            Self::GeoMatches { withcoord, withdist, withhash } => geo::matches(reply, withcoord, withdist, withhash),
+        }
+    }
+}
+
+/// Reshape the items of `results` at the given positions.
+pub fn apply_shapes(results: &Bound<'_, PyAny>, shapes: Vec<(usize, ReplyShape)>) -> PyResult<()> {
+    let Ok(list) = results.cast::<PyList>() else {
+        return Ok(());
+    };
+    for (idx, shape) in shapes {
+        if idx < list.len() {
+            list.set_item(idx, shape.apply(list.get_item(idx)?)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// `[field, value, …]` (or a RESP3 map) as a dict.
+fn field_dict<'py>(fields: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(list) = fields.cast::<PyList>() else {
+        return Ok(fields);
+    };
+    let dict = PyDict::new(fields.py());
+    let items: Vec<Bound<'py, PyAny>> = list.iter().collect();
+    for pair in items.chunks_exact(2) {
+        dict.set_item(&pair[0], &pair[1])?;
+    }
+    Ok(dict.into_any())
+}
+
+/// `[id, fields]` as `(id, {field: value})`. Entries deleted while
+/// pending come back from `XREADGROUP` with `None` fields.
+fn entry<'py>(item: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(pair) = item.cast::<PyList>() else {
+        return Ok(item);
+    };
+    if pair.len() != 2 {
+        return Ok(item);
+    }
+    let id = pair.get_item(0)?;
+    let fields = pair.get_item(1)?;
+    let fields = if fields.is_none() { fields } else { field_dict(fields)? };
+    Ok(PyTuple::new(item.py(), [id, fields])?.into_any())
+}
+
+fn entries<'py>(reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(list) = reply.cast::<PyList>() else {
+        return Ok(reply);
+    };
+    let items = list.iter().map(entry).collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(reply.py(), items)?.into_any())
+}
+
+/// `[[stream, entries], …]` (RESP2) or `{stream: entries}` (RESP3) as
+/// `{stream: [(id, {field: value}), …]}`.
+fn streams<'py>(reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let out = PyDict::new(reply.py());
+    if let Ok(map) = reply.cast::<PyDict>() {
+        for (name, items) in map.iter() {
+            out.set_item(name, entries(items)?)?;
+        }
+        return Ok(out.into_any());
+    }
+    let Ok(list) = reply.cast::<PyList>() else {
+        return Ok(reply);
+    };
+    for item in list.iter() {
+        let Ok(pair) = item.cast::<PyList>() else {
+            return Ok(reply);
+        };
+        if pair.len() != 2 {
+            return Ok(reply);
+        }
+        out.set_item(pair.get_item(0)?, entries(pair.get_item(1)?)?)?;
+    }
+    Ok(out.into_any())
+}
+
+/// `bytes` decoded as `str`, invalid UTF-8 replaced.
+fn text<'py>(reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    match reply.cast::<PyBytes>() {
+        Ok(bytes) => Ok(PyString::new(reply.py(), &String::from_utf8_lossy(bytes.as_bytes())).into_any()),
+        Err(_) => Ok(reply),
+    }
+}
+
+/// The pairs of a `[a, b, …]` (RESP2) or `[[a, b], …]` (RESP3) list.
+fn reply_pairs<'py>(list: &Bound<'py, PyList>) -> PyResult<Vec<(Bound<'py, PyAny>, Bound<'py, PyAny>)>> {
+    let items: Vec<Bound<'py, PyAny>> = list.iter().collect();
+    if items.first().is_some_and(|i| i.is_instance_of::<PyList>()) {
+        items.iter().map(|pair| Ok((pair.get_item(0)?, pair.get_item(1)?))).collect()
+    } else {
+        Ok(items.chunks_exact(2).map(|pair| (pair[0].clone(), pair[1].clone())).collect())
+    }
+}
+
+/// `[member, score, …]` (RESP2) or `[[member, score], …]` (RESP3) as
+/// `[(member, score), …]`.
+fn score_pairs<'py>(reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(list) = reply.cast::<PyList>() else {
+        return Ok(reply);
+    };
+    let py = reply.py();
+    let float = py.get_type::<PyFloat>();
+    let tuples = reply_pairs(list)?
+        .into_iter()
+        .map(|(member, score)| PyTuple::new(py, [member, float.call1((score,))?]))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(py, tuples)?.into_any())
+}
+
+/// `[field, value, …]` (RESP2) or `[[field, value], …]` (RESP3) as
+/// `[(field, value), …]`.
+fn field_pairs<'py>(reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(list) = reply.cast::<PyList>() else {
+        return Ok(reply);
+    };
+    let py = reply.py();
+    let tuples = reply_pairs(list)?
+        .into_iter()
+        .map(|(field, value)| PyTuple::new(py, [field, value]))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(py, tuples)?.into_any())
+}
+
+/// `[count, min, max, [[consumer, count], …]]` as `{"pending": count,
+/// "min": min, "max": max, "consumers": {consumer: count}}`. The consumer
+/// list is `None` when nothing is pending, and its counts arrive as
+/// strings.
+fn pending_summary<'py>(reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(list) = reply.cast::<PyList>() else {
+        return Ok(reply);
+    };
+    if list.len() != 4 {
+        return Ok(reply);
+    }
+    let py = reply.py();
+    let consumers = PyDict::new(py);
+    if let Ok(owners) = list.get_item(3)?.cast::<PyList>() {
+        for (name, count) in reply_pairs(owners)? {
+            consumers.set_item(name, py.get_type::<PyInt>().call1((count,))?)?;
+        }
+    }
+    let out = PyDict::new(py);
+    out.set_item("pending", list.get_item(0)?)?;
+    out.set_item("min", list.get_item(1)?)?;
+    out.set_item("max", list.get_item(2)?)?;
+    out.set_item("consumers", consumers)?;
+    Ok(out.into_any())
+}
+
+/// A list of scores as floats; missing members stay `None`.
+fn scores<'py>(reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(list) = reply.cast::<PyList>() else {
+        return Ok(reply);
+    };
+    let py = reply.py();
+    let float = py.get_type::<PyFloat>();
+    let items = list
+        .iter()
+        .map(|score| if score.is_none() { Ok(score) } else { float.call1((score,)) })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new(py, items)?.into_any())
+}
+
+/// `[key, member, score]` as `(key, member, score)` with a float score.
+fn popped_member<'py>(reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(list) = reply.cast::<PyList>() else {
+        return Ok(reply);
+    };
+    if list.len() != 3 {
+        return Ok(reply);
+    }
+    let score = reply.py().get_type::<PyFloat>().call1((list.get_item(2)?,))?;
+    Ok(PyTuple::new(reply.py(), [list.get_item(0)?, list.get_item(1)?, score])?.into_any())
+}
+
+/// `[key, [[member, score], …]]` as `(key, [(member, score), …])`.
+fn popped_members<'py>(reply: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    let Ok(list) = reply.cast::<PyList>() else {
+        return Ok(reply);
+    };
+    if list.len() != 2 {
+        return Ok(reply);
+    }
+    let members = score_pairs(list.get_item(1)?)?;
+    Ok(PyTuple::new(reply.py(), [list.get_item(0)?, members])?.into_any())
+}
+
+// ── Command arguments ─────────────────────────────────────────────
+
+/// Extract a `{key: value}` dict as text arguments.
+fn pairs(mapping: &Bound<'_, PyDict>) -> PyResult<Vec<(String, String)>> {
+    mapping
+        .iter()
+        .map(|(k, v)| Ok((k.extract::<CommandArg>()?.into_string()?, v.extract::<CommandArg>()?.into_string()?)))
+        .collect()
+}
+
+/// Append a `MAXLEN`/`MINID` trimming strategy and its `LIMIT`.
+fn push_trim(
+    cmd: &mut Vec<String>,
+    maxlen: Option<u64>,
+    minid: Option<&str>,
+    approximate: bool,
+    limit: Option<u64>,
+) -> Result<()> {
+    let (strategy, threshold) = match (maxlen, minid) {
+        (Some(_), Some(_)) => return Err(PyrsedisError::Type("maxlen and minid are mutually exclusive".into())),
+        (Some(n), None) => ("MAXLEN", n.to_string()),
+        (None, Some(id)) => ("MINID", id.to_string()),
+        (None, None) if limit.is_some() => return Err(PyrsedisError::Type("limit requires maxlen or minid".into())),
+        (None, None) => return Ok(()),
+    };
+    if limit.is_some() && !approximate {
+        return Err(PyrsedisError::Type("limit requires approximate=True".into()));
+    }
+    cmd.push(strategy.into());
+    cmd.push(if approximate { "~" } else { "=" }.into());
+    cmd.push(threshold);
+    if let Some(count) = limit {
+        cmd.push("LIMIT".into());
+        cmd.push(count.to_string());
+    }
+    Ok(())
+}
+
+/// `XADD name [NOMKSTREAM] [MAXLEN|MINID ~ threshold [LIMIT count]] id field value …`
+#[allow(clippy::too_many_arguments)]
+pub fn xadd_args(
+    name: &str,
+    fields: &Bound<'_, PyDict>,
+    id: &str,
+    maxlen: Option<u64>,
+    minid: Option<&str>,
+    approximate: bool,
+    nomkstream: bool,
+    limit: Option<u64>,
+) -> PyResult<Vec<String>> {
+    if fields.is_empty() {
+        return Err(PyrsedisError::Type("xadd requires at least one field".into()).into());
+    }
+    let mut cmd = vec!["XADD".into(), name.into()];
+    if nomkstream {
+        cmd.push("NOMKSTREAM".into());
+    }
+    push_trim(&mut cmd, maxlen, minid, approximate, limit)?;
+    cmd.push(id.into());
+    for (field, value) in pairs(fields)? {
+        cmd.push(field);
+        cmd.push(value);
+    }
+    Ok(cmd)
+}
+
+/// `XTRIM name MAXLEN|MINID ~|= threshold [LIMIT count]`
+pub fn xtrim_args(
+    name: &str,
+    maxlen: Option<u64>,
+    minid: Option<&str>,
+    approximate: bool,
+    limit: Option<u64>,
+) -> Result<Vec<String>> {
+    if maxlen.is_none() && minid.is_none() {
+        return Err(PyrsedisError::Type("xtrim requires maxlen or minid".into()));
+    }
+    let mut cmd = vec!["XTRIM".into(), name.into()];
+    push_trim(&mut cmd, maxlen, minid, approximate, limit)?;
+    Ok(cmd)
+}
+
+/// The `MINID` that keeps entries younger than `max_age_ms` at server
+/// time `server_now` (Unix seconds).
+pub fn retention_minid(server_now: f64, max_age_ms: u64) -> String {
+    let now_ms = (server_now * 1000.0) as u64;
+    format!("{}-0", now_ms.saturating_sub(max_age_ms))
+}
+
+/// `XRANGE name start end [COUNT n]` (or `XREVRANGE`, with `start` being
+/// the upper bound).
+pub fn range_args(command: &str, name: &str, start: &str, end: &str, count: Option<u64>) -> Vec<String> {
+    let mut cmd = vec![command.into(), name.into(), start.into(), end.into()];
+    if let Some(n) = count {
+        cmd.push("COUNT".into());
+        cmd.push(n.to_string());
+    }
+    cmd
+}
+
+/// `[COUNT n] [BLOCK ms] [NOACK] STREAMS key … id …`, appended to `cmd`.
+fn push_streams(
+    cmd: &mut Vec<String>,
+    streams: &Bound<'_, PyDict>,
+    count: Option<u64>,
+    block: Option<u64>,
+    noack: bool,
+) -> PyResult<()> {
+    if streams.is_empty() {
+        return Err(PyrsedisError::Type("at least one stream is required".into()).into());
+    }
+    if let Some(n) = count {
+        cmd.push("COUNT".into());
+        cmd.push(n.to_string());
+    }
+    if let Some(ms) = block {
+        cmd.push("BLOCK".into());
+        cmd.push(ms.to_string());
+    }
+    if noack {
+        cmd.push("NOACK".into());
+    }
+    cmd.push("STREAMS".into());
+    let (keys, ids): (Vec<String>, Vec<String>) = pairs(streams)?.into_iter().unzip();
+    cmd.extend(keys);
+    cmd.extend(ids);
+    Ok(())
+}
+
+/// `XREAD [COUNT n] [BLOCK ms] STREAMS key … id …`
+pub fn xread_args(streams: &Bound<'_, PyDict>, count: Option<u64>, block: Option<u64>) -> PyResult<Vec<String>> {
+    let mut cmd = vec!["XREAD".into()];
+    push_streams(&mut cmd, streams, count, block, false)?;
+    Ok(cmd)
+}
+
+/// `XREADGROUP GROUP group consumer [COUNT n] [BLOCK ms] [NOACK] STREAMS …`
+pub fn xreadgroup_args(
+    group: &str,
+    consumer: &str,
+    streams: &Bound<'_, PyDict>,
+    count: Option<u64>,
+    block: Option<u64>,
+    noack: bool,
+) -> PyResult<Vec<String>> {
+    let mut cmd = vec!["XREADGROUP".into(), "GROUP".into(), group.into(), consumer.into()];
+    push_streams(&mut cmd, streams, count, block, noack)?;
+    Ok(cmd)
+}
+
+/// `XGROUP CREATE name group id [MKSTREAM]`
+pub fn xgroup_create_args(name: &str, group: &str, id: &str, mkstream: bool) -> Vec<String> {
+    let mut cmd = vec!["XGROUP".into(), "CREATE".into(), name.into(), group.into(), id.into()];
+    if mkstream {
+        cmd.push("MKSTREAM".into());
+    }
+    cmd
+}
+
+/// `XCLAIM name group consumer min-idle-time id … [options]`
+#[allow(clippy::too_many_arguments)]
+pub fn xclaim_args(
+    name: &str,
+    group: &str,
+    consumer: &str,
+    min_idle_time: u64,
+    ids: Vec<String>,
+    idle: Option<u64>,
+    time: Option<u64>,
+    retrycount: Option<u64>,
+    force: bool,
+    justid: bool,
+) -> Result<Vec<String>> {
+    if ids.is_empty() {
+        return Err(PyrsedisError::Type("xclaim requires at least one id".into()));
+    }
+    let mut cmd = vec!["XCLAIM".into(), name.into(), group.into(), consumer.into(), min_idle_time.to_string()];
+    cmd.extend(ids);
+    for (option, value) in [("IDLE", idle), ("TIME", time), ("RETRYCOUNT", retrycount)] {
+        if let Some(v) = value {
+            cmd.push(option.into());
+            cmd.push(v.to_string());
+        }
+    }
+    if force {
+        cmd.push("FORCE".into());
+    }
+    if justid {
+        cmd.push("JUSTID".into());
+    }
+    Ok(cmd)
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shaped(py: Python<'_>, shape: ReplyShape, expr: &std::ffi::CStr) -> String {
+        let reply = py.eval(expr, None, None).unwrap();
+        shape.apply(reply).unwrap().repr().unwrap().to_string()
+    }
+
+    #[test]
+    fn entries_become_id_dict_tuples() {
+        Python::attach(|py| {
+            assert_eq!(
+                shaped(py, ReplyShape::Entries, c"[['1-0', ['a', '1', 'b', '2']], ['2-0', None]]"),
+                "[('1-0', {'a': '1', 'b': '2'}), ('2-0', None)]"
+            );
+            // JUSTID replies and errors pass through
+            assert_eq!(shaped(py, ReplyShape::Entries, c"['1-0', '2-0']"), "['1-0', '2-0']");
+            assert_eq!(shaped(py, ReplyShape::Entries, c"ValueError('x')"), "ValueError('x')");
+        });
+    }
+
+    #[test]
+    fn streams_become_dicts() {
+        Python::attach(|py| {
+            let expected = "{'s': [('1-0', {'f': 'v'})]}";
+            assert_eq!(shaped(py, ReplyShape::Streams, c"[['s', [['1-0', ['f', 'v']]]]]"), expected);
+            assert_eq!(shaped(py, ReplyShape::Streams, c"{'s': [['1-0', ['f', 'v']]]}"), expected);
+            assert_eq!(shaped(py, ReplyShape::Streams, c"None"), "None");
+        });
+    }
+
+    #[test]
+    fn scores_pair_up_as_floats() {
+        Python::attach(|py| {
+            let expected = "[('a', 1.0), ('b', 2.5)]";
+            assert_eq!(shaped(py, ReplyShape::ScorePairs, c"['a', '1', 'b', '2.5']"), expected);
+            assert_eq!(shaped(py, ReplyShape::ScorePairs, c"[['a', 1.0], ['b', 2.5]]"), expected);
+            assert_eq!(shaped(py, ReplyShape::ScorePairs, c"[b'a', b'inf']"), "[(b'a', inf)]");
+            assert_eq!(shaped(py, ReplyShape::ScorePairs, c"[]"), "[]");
+        });
+    }
+
+    #[test]
+    fn field_values_pair_up() {
+        Python::attach(|py| {
+            let expected = "[('f', 'v'), ('g', '2')]";
+            assert_eq!(shaped(py, ReplyShape::FieldPairs, c"['f', 'v', 'g', '2']"), expected);
+            assert_eq!(shaped(py, ReplyShape::FieldPairs, c"[['f', 'v'], ['g', '2']]"), expected);
+        });
+    }
+
+    #[test]
+    fn flat_maps_become_dicts() {
+        Python::attach(|py| {
+            assert_eq!(shaped(py, ReplyShape::Dict, c"[b'f', b'v', b'g', b'2']"), "{b'f': b'v', b'g': b'2'}");
+            assert_eq!(shaped(py, ReplyShape::Dict, c"{'f': 'v'}"), "{'f': 'v'}");
+            assert_eq!(shaped(py, ReplyShape::Dict, c"[]"), "{}");
+            assert_eq!(
+                shaped(py, ReplyShape::PendingSummary, c"[3, '1-0', '2-0', [['alice', '2'], [b'bob', b'1']]]"),
+                "{'pending': 3, 'min': '1-0', 'max': '2-0', 'consumers': {'alice': 2, b'bob': 1}}"
+            );
+            assert_eq!(
+                shaped(py, ReplyShape::PendingSummary, c"[0, None, None, None]"),
+                "{'pending': 0, 'min': None, 'max': None, 'consumers': {}}"
+            );
+        });
+    }
+
+    #[test]
+    fn popped_members_carry_float_scores() {
+        Python::attach(|py| {
+            assert_eq!(shaped(py, ReplyShape::Scores, c"['1', None, 2.5]"), "[1.0, None, 2.5]");
+            assert_eq!(shaped(py, ReplyShape::PoppedMember, c"['z', 'a', '1']"), "('z', 'a', 1.0)");
+            assert_eq!(shaped(py, ReplyShape::PoppedMember, c"None"), "None");
+            assert_eq!(
+                shaped(py, ReplyShape::PoppedMembers, c"['z', [['a', '1'], ['b', 2.0]]]"),
+                "('z', [('a', 1.0), ('b', 2.0)])"
+            );
+            assert_eq!(shaped(py, ReplyShape::PoppedMembers, c"None"), "None");
+        });
+    }
+
+    #[test]
+    fn text_is_decoded() {
+        Python::attach(|py| {
+            assert_eq!(shaped(py, ReplyShape::Text, c"b'hash'"), "'hash'");
+            assert_eq!(shaped(py, ReplyShape::Text, c"'none'"), "'none'");
+        });
+    }
+
+    #[test]
+    fn apply_shapes_by_position() {
+        Python::attach(|py| {
+            let results = py.eval(c"[1, [['1-0', ['f', 'v']]], 3]", None, None).unwrap();
+            apply_shapes(&results, vec![(1, ReplyShape::Entries), (9, ReplyShape::Entries)]).unwrap();
+            assert_eq!(results.repr().unwrap().to_string(), "[1, [('1-0', {'f': 'v'})], 3]");
+        });
+    }
+
+    #[test]
+    fn xadd_and_xtrim_arguments() {
+        Python::attach(|py| {
+            let fields = PyDict::new(py);
+            fields.set_item("temp", 21.5).unwrap();
+            assert_eq!(
+                xadd_args("s", &fields, "*", Some(1000), None, true, true, None).unwrap(),
+                ["XADD", "s", "NOMKSTREAM", "MAXLEN", "~", "1000", "*", "temp", "21.5"]
+            );
+            assert_eq!(
+                xadd_args("s", &fields, "*", None, Some("7-0"), true, false, Some(50)).unwrap(),
+                ["XADD", "s", "MINID", "~", "7-0", "LIMIT", "50", "*", "temp", "21.5"]
+            );
+            assert!(xadd_args("s", &fields, "*", Some(1), Some("0-1"), true, false, None).is_err());
+            assert!(xadd_args("s", &fields, "*", None, None, true, false, Some(50)).is_err());
+            assert!(xadd_args("s", &fields, "*", Some(1), None, false, false, Some(50)).is_err());
+            assert!(xadd_args("s", &PyDict::new(py), "*", None, None, true, false, None).is_err());
+        });
+        assert_eq!(
+            xtrim_args("s", None, Some("5-0"), false, None).unwrap(),
+            ["XTRIM", "s", "MINID", "=", "5-0"]
+        );
+        assert_eq!(
+            xtrim_args("s", Some(10), None, true, Some(100)).unwrap(),
+            ["XTRIM", "s", "MAXLEN", "~", "10", "LIMIT", "100"]
+        );
+        assert!(xtrim_args("s", None, None, true, None).is_err());
+        assert!(xtrim_args("s", Some(10), None, false, Some(100)).is_err());
+    }
+
+    #[test]
+    fn retention_cutoff() {
+        assert_eq!(retention_minid(1_700_000_000.25, 60_000), "1699999940250-0");
+        assert_eq!(retention_minid(1.0, 60_000), "0-0");
+    }
+
+    #[test]
+    fn read_and_claim_arguments() {
+        Python::attach(|py| {
+            let streams = PyDict::new(py);
+            streams.set_item("a", "0").unwrap();
+            streams.set_item("b", "$").unwrap();
+            assert_eq!(
+                xread_args(&streams, Some(10), Some(500)).unwrap(),
+                ["XREAD", "COUNT", "10", "BLOCK", "500", "STREAMS", "a", "b", "0", "$"]
+            );
+            assert_eq!(
+                xreadgroup_args("g", "c", &streams, None, None, true).unwrap(),
+                ["XREADGROUP", "GROUP", "g", "c", "NOACK", "STREAMS", "a", "b", "0", "$"]
+            );
+            assert!(xread_args(&PyDict::new(py), None, None).is_err());
+        });
+        assert_eq!(
+            xclaim_args("s", "g", "c", 60_000, vec!["1-0".into()], None, None, Some(3), true, true).unwrap(),
+            ["XCLAIM", "s", "g", "c", "60000", "1-0", "RETRYCOUNT", "3", "FORCE", "JUSTID"]
+        );
+        assert!(xclaim_args("s", "g", "c", 0, vec![], None, None, None, false, false).is_err());
+        assert_eq!(xgroup_create_args("s", "g", "$", true), ["XGROUP", "CREATE", "s", "g", "$", "MKSTREAM"]);
+        assert_eq!(range_args("XREVRANGE", "s", "+", "-", Some(5)), ["XREVRANGE", "s", "+", "-", "COUNT", "5"]);
+    }
+}
@@ -4,7 +4,9 @@ Python integration test suite for pyrsedis.
 Requires a running Redis server (default: localhost:6379).
 Run with: pytest tests/python/ -v
 """
+import asyncio
 import os
+import time
 
 import pytest
 
@@ -28,7 +30,7 @@ def r():
         client.ping()
     except Exception:
         pytest.skip("Redis server not available")
-    client.flushdb()
+    client.flushdb(confirm=True)
     return client
 
 
@@ -43,6 +45,27 @@ class TestStrings:
     def test_get_nonexistent(self, r):
         assert r.get("nonexistent") is None
 
+    def test_binary_keys_and_values(self, r, redis_url):
+        from pyrsedis import Redis
+
+        raw = Redis.from_url(redis_url, decode_responses=False)
+        assert raw.set(b"bin:\x00\xff", b"\x00\xff\xfe") is True
+        assert raw.get(b"bin:\x00\xff") == b"\x00\xff\xfe"
+        raw.execute_command("SET", b"bin:\x80", memoryview(b"\x01\x02"), "EX", 60)
+        assert raw.execute_command("GET", bytearray(b"bin:\x80")) == b"\x01\x02"
+        assert 0 < raw.execute_command("TTL", b"bin:\x80") <= 60
+
+    def test_numeric_arguments(self, r):
+        r.set("num", 42)
+        assert r.get("num") == "42"
+        assert r.execute_command("INCRBY", "num", 8) == 50
+        r.set("flt", 1.5)
+        assert r.execute_command("INCRBYFLOAT", "flt", 0.25) == "1.75"
+        with pytest.raises(TypeError):
+            r.set("k", True)
+        with pytest.raises(TypeError):
+            r.execute_command("SET", "k", None)
+
     def test_set_with_ex(self, r):
         r.set("k", "v", ex=10)
         ttl = r.ttl("k")
@@ -74,6 +97,99 @@ class TestStrings:
         r.set("k", "v")
         assert r.exists("k") == 1
 
+    def test_exists_many(self, r):
+        keys = [f"em:{i}" for i in range(2500)]
+        for k in keys[::2]:
+            r.set(k, "v")
+        result = r.exists_many(keys, chunk_size=300)
+        assert len(result) == len(keys)
+        assert all(result[k] is (i % 2 == 0) for i, k in enumerate(keys))
+        assert r.exists_many([]) == {}
+
+    def test_ttl_many(self, r):
+        r.set("tm:ex", "v", ex=100)
+        r.set("tm:persist", "v")
+        result = r.ttl_many(["tm:ex", "tm:persist", "tm:missing"])
+        assert 0 < result["tm:ex"] <= 100
+        assert result["tm:persist"] == -1
+        assert result["tm:missing"] == -2
+
+    def test_expire_many(self, r):
+        keys = [f"xm:{i}" for i in range(2500)]
+        for k in keys[:-1]:
+            r.set(k, "v")
+        result = r.expire_many({k: 100 for k in keys}, chunk_size=300)
+        assert all(result[k] for k in keys[:-1])
+        assert result[keys[-1]] is False
+        assert 0 < r.ttl(keys[0]) <= 100
+        assert r.expire_many({}) == {}
+
+    def test_persist_many(self, r):
+        r.set("pm:ex", "v", ex=100)
+        r.set("pm:persist", "v")
+        result = r.persist_many(["pm:ex", "pm:persist", "pm:missing"])
+        assert result == {"pm:ex": True, "pm:persist": False, "pm:missing": False}
+        assert r.ttl("pm:ex") == -1
+
+    def test_purge_expiring(self, r):
+        r.set("pe:soon", "v", ex=10)
+        r.set("pe:later", "v", ex=1000)
+        r.set("pe:forever", "v")
+        r.set("other:soon", "v", ex=10)
+        assert r.purge_expiring("pe:*", older_than_ttl=60, count=1, pause_ms=0) == 2
+        assert r.exists("pe:soon", "pe:forever") == 0
+        assert r.exists("pe:later", "other:soon") == 2
+
+    def test_expire_conditions(self, r):
+        r.set("ex:k", "v")
+        assert r.expire("ex:k", 100, xx=True) == 0
+        assert r.expire("ex:k", 100, nx=True) == 1
+        assert r.expire("ex:k", 200, lt=True) == 0
+        assert r.expire("ex:k", 50, lt=True) == 1
+        assert r.pexpire("ex:k", 60_000, gt=True) == 1
+        assert 50 < r.ttl("ex:k") <= 60
+        assert r.pipeline().expire("ex:k", 10, nx=True).execute() == [0]
+        when = r.expiretime("ex:k")
+        assert when > 0
+        assert r.expireat("ex:k", when + 100, gt=True) == 1
+        assert r.pexpiretime("ex:k") == (when + 100) * 1000
+        assert r.expiretime("ex:missing") == -2
+
+    def test_refresh_ttl_if_below(self, r):
+        r.set("rt:soon", "v", ex=10)
+        r.set("rt:later", "v", ex=1000)
+        r.set("rt:forever", "v")
+        r.execute_command("SCRIPT", "FLUSH")
+        assert r.refresh_ttl_if_below(["rt:soon", "rt:later", "rt:forever", "rt:missing"], min_ttl=60, new_ttl=300) == 1
+        assert 10 < r.ttl("rt:soon") <= 300
+        assert r.ttl("rt:later") > 300
+        assert r.ttl("rt:forever") == -1
+        assert r.refresh_ttl_if_below([], min_ttl=60, new_ttl=300) == 0
+
+    def test_load_hash_rows(self, r):
+        from collections import namedtuple
+
+        Row = namedtuple("Row", ["Index", "name", "score"])
+        rows = [Row(i, f"n{i}", float("nan") if i == 3 else i / 2) for i in range(2500)]
+        seen = []
+        assert r.load_hash_rows("lh:", rows, chunk_size=1000, progress=seen.append) == 2500
+        assert seen == [1000, 2000, 2500]
+        assert r.hmget("lh:1", "name", "score") == ["n1", "0.5"]
+        assert r.hmget("lh:3", "name", "score") == ["n3", None]
+
+        dicts = [{"id": "a", "v": 1}, {"id": "b", "w": 2}]
+        with pytest.raises(TypeError, match="row 1 has columns"):
+            r.load_hash_rows("lh:", dicts, key_field="id")
+        assert r.exists("lh:a") == 0
+
+    def test_load_zset(self, r):
+        assert r.load_zset("lz", {f"m{i}": i for i in range(2500)}, chunk_size=1000) == 2500
+        assert r.zcard("lz") == 2500
+        assert r.load_zset("lz", [("m0", 10), ("new", float("inf"))]) == 1
+        assert r.zscore("lz", "m0") == "10"
+        with pytest.raises(TypeError, match="NaN score"):
+            r.load_zset("lz", [("bad", float("nan"))])
+
     def test_incr_decr(self, r):
         assert r.incr("counter") == 1
         assert r.incr("counter") == 2
@@ -96,6 +212,34 @@ class TestStrings:
         assert result[1] == "2"
         assert result[2] is None
 
+    def test_mset_pairs(self, r):
+        # Later pairs win, as the keys are sent in order
+        assert r.mset([("a", "1"), ("b", "2"), ("a", "3")]) is True
+        assert r.mget("a", "b") == ["3", "2"]
+        assert r.pipeline().mset(iter([("c", "4")])).execute() == [True]
+
+    def test_expected_response_size(self, r):
+        big = "x" * 200_000
+        r.mset({"a": big, "b": big})
+        assert r.mget("a", "b", expected_response_size=400_100) == [big, big]
+        assert r.execute_command("GET", "a", expected_response_size=1) == big
+        assert r.mget("a", "b") == [big, big]
+
+    def test_get_floats(self, r):
+        np = pytest.importorskip("numpy")
+        r.mset({"f1": "1.5", "f2": "-2"})
+        arr = r.get_floats(["f1", "missing", "f2"])
+        assert arr.dtype == np.float64
+        assert arr[0] == 1.5
+        assert np.isnan(arr[1])
+        assert arr[2] == -2.0
+
+    def test_get_floats_non_numeric(self, r):
+        pytest.importorskip("numpy")
+        r.set("f", "abc")
+        with pytest.raises(TypeError):
+            r.get_floats(["f"])
+
     def test_append_strlen(self, r):
         assert r.append("k", "hello") == 5
         assert r.append("k", " world") == 11
@@ -110,6 +254,31 @@ class TestStrings:
         assert r.getdel("k") == "v"
         assert r.get("k") is None
 
+    def test_getex(self, r):
+        r.set("k", "v")
+        assert r.getex("k", ex=100) == "v"
+        assert 0 < r.ttl("k") <= 100
+        assert r.getex("k", persist=True) == "v"
+        assert r.ttl("k") == -1
+        assert r.getex("missing") is None
+        with pytest.raises(TypeError):
+            r.getex("k", ex=1, px=1000)
+
+    def test_pipeline_getex_getset_getdel(self, r):
+        r.set("k", "v")
+        pipe = r.pipeline()
+        pipe.getex("k", px=100_000).getset("k", "w").getdel("k")
+        assert pipe.execute() == ["v", "v", "w"]
+
+    def test_type_is_str(self, r, redis_url):
+        from pyrsedis import Redis
+
+        r.hset("h", "f", "v")
+        assert r.type("h") == "hash"
+        assert r.type("missing") == "none"
+        raw = Redis.from_url(redis_url, decode_responses=False)
+        assert raw.type("h") == "hash"
+
     def test_setnx(self, r):
         assert r.setnx("k", "v") == 1
         assert r.setnx("k", "v2") == 0
@@ -155,11 +324,43 @@ class TestHashes:
     def test_hget_nonexistent(self, r):
         assert r.hget("nosuchhash", "f") is None
 
+    def test_hset_mapping(self, r):
+        assert r.hset("h", mapping={"a": "1", "b": "2"}) == 2
+        assert r.hset("h", "c", "3", mapping=[("a", "9")]) == 1
+        assert r.hmget("h", "a", "b", "c") == ["9", "2", "3"]
+        with pytest.raises(TypeError):
+            r.hset("h", "only-key")
+        with pytest.raises(TypeError):
+            r.hset("h", mapping={})
+
+    def test_hrandfield(self, r):
+        r.hset("h", mapping={"a": "1", "b": "2"})
+        assert r.hrandfield("h") in ("a", "b")
+        assert sorted(r.hrandfield("h", 2)) == ["a", "b"]
+        assert sorted(r.hrandfield("h", 2, withvalues=True)) == [("a", "1"), ("b", "2")]
+        assert r.hrandfield("nosuchhash") is None
+        with pytest.raises(TypeError):
+            r.hrandfield("h", withvalues=True)
+
+    def test_hscan(self, r):
+        r.hset("h", mapping={"f1": "a", "f2": "b", "g": "c"})
+        cursor, items = r.hscan("h", 0, match_pattern="f*", count=100)
+        assert int(cursor) == 0
+        assert sorted(zip(items[::2], items[1::2])) == [("f1", "a"), ("f2", "b")]
+
     def test_hgetall(self, r):
         r.hset("h", "a", "1")
         r.hset("h", "b", "2")
-        result = r.hgetall("h")
-        assert len(result) == 4  # flat list: [field, value, field, value]
+        assert r.hgetall("h") == {"a": "1", "b": "2"}
+        assert r.hgetall("missing") == {}
+        assert r.pipeline().hgetall("h").execute() == [{"a": "1", "b": "2"}]
+
+    def test_hgetall_floats(self, r):
+        pytest.importorskip("numpy")
+        r.hset("h", "a", "0.25")
+        r.hset("h", "b", "4")
+        fields, values = r.hgetall_floats("h")
+        assert dict(zip(fields, values.tolist())) == {"a": 0.25, "b": 4.0}
 
     def test_hdel(self, r):
         r.hset("h", "a", "1")
@@ -232,6 +433,55 @@ class TestLists:
         assert r.lrem("l", 2, "a") == 2
         assert r.llen("l") == 3
 
+    def test_lpos(self, r):
+        r.rpush("l", "a", "b", "a", "c", "a")
+        assert r.lpos("l", "a") == 0
+        assert r.lpos("l", "a", rank=-1) == 4
+        assert r.lpos("l", "a", count=0) == [0, 2, 4]
+        assert r.lpos("l", "a", count=0, maxlen=3) == [0, 2]
+        assert r.lpos("l", "z") is None
+        with pytest.raises(TypeError):
+            r.lpos("l", "a", rank=0)
+
+    def test_linsert(self, r):
+        r.rpush("l", "a", "c")
+        assert r.linsert("l", "before", "c", "b") == 3
+        assert r.linsert("l", "AFTER", "missing", "x") == -1
+        assert r.lrange("l", 0, -1) == ["a", "b", "c"]
+        with pytest.raises(TypeError):
+            r.linsert("l", "between", "a", "x")
+
+    def test_ltrim_and_rpoplpush(self, r):
+        r.rpush("l", "a", "b", "c", "d")
+        r.ltrim("l", 0, 2)
+        assert r.lrange("l", 0, -1) == ["a", "b", "c"]
+        assert r.rpoplpush("l", "dst") == "c"
+        assert r.lrange("dst", 0, -1) == ["c"]
+        assert r.rpoplpush("empty", "dst") is None
+
+    def test_list_commands_in_pipeline(self, r):
+        r.rpush("l", "a", "b", "c")
+        pipe = r.pipeline()
+        pipe.lpos("l", "b").linsert("l", "AFTER", "b", "x").ltrim("l", 0, 1).rpoplpush("l", "dst")
+        assert pipe.execute()[:2] == [1, 4]
+
+    def test_lmove_and_lmpop(self, r):
+        r.rpush("l", "a", "b", "c")
+        assert r.lmove("l", "dst") == "a"
+        assert r.lmove("l", "dst", "RIGHT", "left") == "c"
+        assert r.lrange("dst", 0, -1) == ["c", "a"]
+        assert r.lmove("empty", "dst") is None
+        assert r.lmpop(["empty", "dst"], count=5) == ["dst", ["c", "a"]]
+        assert r.lmpop(["empty", "dst"]) is None
+        with pytest.raises(TypeError):
+            r.lmove("l", "dst", "UP")
+
+    def test_queue_commands_in_pipeline(self, r):
+        r.rpush("l", "a", "b", "c")
+        pipe = r.pipeline()
+        pipe.lmove("l", "dst").blmove("l", "dst", "RIGHT", "RIGHT", timeout=0.1).lmpop(["l"], direction="RIGHT")
+        assert pipe.execute() == ["a", "c", ["l", ["b"]]]
+
 
 # ── Set commands ────────────────────────────────────────────────────
 
@@ -273,11 +523,66 @@ class TestSets:
         result = r.sdiff("s1", "s2")
         assert len(result) == 1
 
+    def test_sinter_page(self, r):
+        r.sadd("{p}:a", *[str(i) for i in range(300)])
+        r.sadd("{p}:b", *[str(i) for i in range(0, 300, 2)])
+        seen, cursor = [], 0
+        while True:
+            cursor, members = r.sinter_page(["{p}:a", "{p}:b"], cursor, count=20)
+            seen.extend(members)
+            if cursor == 0:
+                break
+        assert sorted(set(seen), key=int) == [str(i) for i in range(0, 300, 2)]
+        assert r.keys("pyrsedis:sinter:*") == []
+
+    def test_sinter_page_empty(self, r):
+        r.sadd("{p}:a", "x")
+        r.sadd("{p}:b", "y")
+        assert r.sinter_page(["{p}:a", "{p}:b"]) == (0, [])
+        assert r.keys("pyrsedis:sinter:*") == []
+
+    def test_store_variants(self, r):
+        r.sadd("a", "1", "2", "3")
+        r.sadd("b", "2", "3", "4")
+        assert r.sinterstore("inter", "a", "b") == 2
+        assert r.sunionstore("union", "a", "b") == 4
+        assert r.sdiffstore("diff", "a", "b") == 1
+        assert r.smembers("diff") == ["1"]
+
+    def test_smove(self, r):
+        r.sadd("a", "1", "2")
+        assert r.smove("a", "b", "1") == 1
+        assert r.smove("a", "b", "missing") == 0
+        assert r.sismember("b", "1") == 1
+
+    def test_smismember_srandmember(self, r):
+        r.sadd("s", "a", "b", "c")
+        assert r.smismember("s", "a", "z", "c") == [1, 0, 1]
+        assert r.srandmember("s") in ("a", "b", "c")
+        assert sorted(r.srandmember("s", 3)) == ["a", "b", "c"]
+        assert len(r.srandmember("s", -5)) == 5
+        assert r.srandmember("missing") is None
+
+    def test_sintercard(self, r):
+        r.sadd("a", "1", "2", "3")
+        r.sadd("b", "2", "3", "4")
+        assert r.sintercard(["a", "b"]) == 2
+        assert r.sintercard(["a", "b"], limit=1) == 1
+        pipe = r.pipeline()
+        pipe.sintercard(["a", "b"]).smismember("a", "1", "4").srandmember("a", 0)
+        assert pipe.execute() == [2, [1, 0], []]
+
 
 # ── Sorted set commands ─────────────────────────────────────────────
 
 
 class TestSortedSets:
+    def test_zadd_pairs(self, r):
+        assert r.zadd("z", [("a", 1), ("b", 2.5)]) == 2
+        assert r.zrange("z", 0, -1, withscores=True) == ["a", "1", "b", "2.5"]
+        with pytest.raises(TypeError):
+            r.zadd("z", [("a",)])
+
     def test_zadd_zscore_zcard(self, r):
         assert r.zadd("z", {"a": 1, "b": 2, "c": 3}) == 3
         assert r.zscore("z", "b") == "2"
@@ -331,6 +636,260 @@ class TestSortedSets:
         assert r.zremrangebyrank("z", 0, 0) == 1
         assert r.zcard("z") == 2
 
+    def test_zunion_zinter(self, r):
+        r.zadd("z1", {"a": 1, "b": 2})
+        r.zadd("z2", {"b": 3, "c": 4})
+        assert r.zunionstore("out", ["z1", "z2"], weights=[2, 1]) == 3
+        assert r.zscore("out", "b") == "7"
+        assert r.zinterstore("out", ["z1", "z2"], aggregate="max") == 1
+        assert r.zscore("out", "b") == "3"
+        assert r.zunion(["z1", "z2"]) == ["a", "b", "c"]
+        assert r.zinter(["z1", "z2"], aggregate="MIN", withscores=True) == [("b", 2.0)]
+        assert r.zunion(["z1", "z2"], weights=[1, 0], withscores=True)[0] == ("c", 0.0)
+        with pytest.raises(TypeError):
+            r.zunion(["z1", "z2"], weights=[1])
+        with pytest.raises(TypeError):
+            r.zinter(["z1"], aggregate="AVG")
+
+    def test_zdiff_zrangestore(self, r):
+        r.zadd("z1", {"a": 1, "b": 2, "c": 3})
+        r.zadd("z2", {"b": 5})
+        assert r.zdiff(["z1", "z2"]) == ["a", "c"]
+        assert r.zdiff(["z1", "z2"], withscores=True) == [("a", 1.0), ("c", 3.0)]
+        assert r.zrangestore("out", "z1", 0, 1, desc=True) == 2
+        assert r.zrange("out", 0, -1) == ["b", "c"]
+        assert r.zrangestore("out", "z1", "(1", "+inf", byscore=True, offset=0, num=1) == 1
+        assert r.zrange("out", 0, -1) == ["b"]
+        with pytest.raises(TypeError):
+            r.zrangestore("out", "z1", 0, 1, offset=0, num=1)
+
+    def test_zpop(self, r):
+        r.zadd("z", {"a": 1, "b": 2, "c": 3, "d": 4})
+        assert r.zpopmin("z") == [("a", 1.0)]
+        assert r.zpopmax("z", 2) == [("d", 4.0), ("c", 3.0)]
+        assert r.zmpop(["missing", "z"], max=True) == ("z", [("b", 2.0)])
+        assert r.zmpop(["z"], min=True) is None
+        with pytest.raises(TypeError):
+            r.zmpop(["z"])
+
+    def test_zrandmember_zmscore(self, r):
+        r.zadd("z", {"a": 1.5, "b": 2})
+        assert r.zrandmember("z") in ("a", "b")
+        assert sorted(r.zrandmember("z", 2, withscores=True)) == [("a", 1.5), ("b", 2.0)]
+        assert len(r.zrandmember("z", -5)) == 5
+        assert r.zmscore("z", "a", "missing", "b") == [1.5, None, 2.0]
+        with pytest.raises(TypeError):
+            r.zrandmember("z", withscores=True)
+
+
+# ── Blocking commands ───────────────────────────────────────────────
+
+
+class TestBlockingCommands:
+    def test_blpop_brpop(self, r):
+        r.rpush("q", "a", "b")
+        assert r.blpop("q", timeout=1) == ["q", "a"]
+        assert r.brpop(["missing", "q"], timeout=1) == ["q", "b"]
+        assert r.blpop("q", timeout=0.1) is None
+
+    def test_move_commands(self, r):
+        r.rpush("src", "a", "b")
+        assert r.brpoplpush("src", "dst", timeout=1) == "b"
+        assert r.blmove("src", "dst", "LEFT", "LEFT", timeout=1) == "a"
+        assert r.lrange("dst", 0, -1) == ["a", "b"]
+        with pytest.raises(TypeError):
+            r.blmove("src", "dst", "UP", "LEFT")
+
+    def test_bzpop(self, r):
+        r.zadd("z", {"a": 1, "b": 2})
+        assert r.bzpopmin("z", timeout=1) == ("z", "a", 1.0)
+        assert r.bzpopmax(["z"], timeout=1) == ("z", "b", 2.0)
+        assert r.bzpopmin("z", timeout=0.1) is None
+
+    def test_wait_longer_than_read_timeout(self, r, redis_url):
+        import threading
+
+        from pyrsedis import Redis
+
+        client = Redis.from_url(redis_url, read_timeout_ms=100)
+        # The pusher only runs if the waiting call released the GIL
+        timer = threading.Timer(0.3, lambda: r.rpush("late", "x"))
+        timer.start()
+        try:
+            assert client.blpop("late", timeout=5) == ["late", "x"]
+        finally:
+            timer.join()
+        assert client.blpop("late", timeout=0.2) is None
+        assert client.ping() is True
+
+    def test_invalid_timeout(self, r):
+        with pytest.raises(TypeError):
+            r.blpop("q", timeout=-1)
+        with pytest.raises(TypeError):
+            r.blpop([], timeout=1)
+
+
+# ── Scan iterators ──────────────────────────────────────────────────
+
+
+class TestScanIterators:
+    def test_scan_iter(self, r):
+        for i in range(50):
+            r.set(f"it:{i}", "v")
+        r.set("other", "v")
+        keys = set(r.scan_iter(match="it:*", count=7))
+        assert keys == {f"it:{i}" for i in range(50)}
+        assert len(set(r.scan_iter())) == 51
+
+    def test_hscan_iter(self, r):
+        r.hset("h", "a", "1")
+        r.hset("h", "b", "2")
+        assert dict(r.hscan_iter("h")) == {"a": "1", "b": "2"}
+        assert list(r.hscan_iter("h", match="a")) == [("a", "1")]
+
+    def test_sscan_zscan_iter(self, r):
+        r.sadd("s", "x", "y")
+        assert sorted(r.sscan_iter("s")) == ["x", "y"]
+        r.zadd("z", {"a": 1.5, "b": 2})
+        assert sorted(r.zscan_iter("z")) == [("a", 1.5), ("b", 2.0)]
+        assert list(r.zscan_iter("missing")) == []
+
+
+# ── Geo commands ────────────────────────────────────────────────────
+
+
+class TestGeo:
+    def _sicily(self, r):
+        return r.geoadd("sicily", [
+            (13.361389, 38.115556, "Palermo"),
+            (15.087269, 37.502669, "Catania"),
+        ])
+
+    def test_geoadd_geopos_geodist(self, r):
+        assert self._sicily(r) == 2
+        palermo, missing = r.geopos("sicily", "Palermo", "Rome")
+        assert missing is None
+        assert palermo == pytest.approx((13.361389, 38.115556), abs=1e-5)
+        assert r.geodist("sicily", "Palermo", "Catania", "km") == pytest.approx(166.2742, abs=1e-3)
+        assert r.geodist("sicily", "Palermo", "Rome") is None
+        with pytest.raises(TypeError):
+            r.geodist("sicily", "Palermo", "Catania", "yd")
+
+    def test_geosearch_matches(self, r):
+        self._sicily(r)
+        assert sorted(r.geosearch("sicily", longitude=15, latitude=37, radius=200, unit="km")) == ["Catania", "Palermo"]
+        matches = r.geosearch(
+            "sicily", member="Catania", radius=100, unit="km", sort="ASC",
+            withdist=True, withhash=True, withcoord=True,
+        )
+        assert [m["member"] for m in matches] == ["Catania"]
+        assert matches[0]["distance"] == 0.0
+        assert isinstance(matches[0]["hash"], int)
+        assert matches[0]["coordinates"] == pytest.approx((15.087269, 37.502669), abs=1e-5)
+        with pytest.raises(TypeError):
+            r.geosearch("sicily", member="Catania", longitude=15, latitude=37, radius=1)
+
+    def test_geosearchstore(self, r):
+        self._sicily(r)
+        n = r.geosearchstore("{sicily}near", "sicily", longitude=15, latitude=37, width=400, height=400, unit="km", storedist=True)
+        assert n == 2
+
+    def test_pipeline_shapes(self, r):
+        self._sicily(r)
+        pipe = r.pipeline()
+        pipe.geopos("sicily", "Catania").geodist("sicily", "Palermo", "Catania")
+        pipe.geosearch("sicily", member="Palermo", radius=1, withdist=True)
+        pos, dist, matches = pipe.execute()
+        assert isinstance(pos[0], tuple)
+        assert isinstance(dist, float)
+        assert matches == [{"member": "Palermo", "distance": 0.0}]
+
+
+# ── Stream commands ─────────────────────────────────────────────────
+
+
+class TestStreams:
+    def test_xadd_xrange(self, r):
+        first = r.xadd("s", {"temp": 21.5, "unit": "C"})
+        r.xadd("s", {"temp": 22}, id="9999999999999-0")
+        assert r.xlen("s") == 2
+        entries = r.xrange("s")
+        assert entries[0] == (first, {"temp": "21.5", "unit": "C"})
+        assert r.xrevrange("s", count=1) == [("9999999999999-0", {"temp": "22"})]
+        assert r.xrange("missing") == []
+
+    def test_xadd_trimming(self, r):
+        for i in range(5):
+            r.xadd("s", {"n": i}, maxlen=3, approximate=False)
+        assert r.xlen("s") == 3
+        assert r.xadd("nope", {"a": 1}, nomkstream=True) is None
+        with pytest.raises(TypeError):
+            r.xadd("s", {"a": 1}, maxlen=1, minid="0-1")
+
+    def test_xdel_xtrim(self, r):
+        ids = [r.xadd("s", {"n": i}) for i in range(4)]
+        assert r.xdel("s", ids[0], ids[1]) == 2
+        assert r.xtrim("s", maxlen=1, approximate=False) == 1
+        assert [e[0] for e in r.xrange("s")] == [ids[3]]
+        with pytest.raises(TypeError):
+            r.xtrim("s")
+
+    def test_stream_retention(self, r):
+        r.xadd("s", {"n": 0}, id="1-0")
+        r.xadd("s", {"n": 1}, id="2-0")
+        fresh = r.xadd("s", {"n": 2})
+        assert r.stream_retention("s", 60_000, approximate=False) == 2
+        assert [e[0] for e in r.xrange("s")] == [fresh]
+        r.xadd("s", {"n": 3}, minid=fresh, limit=10)
+        assert r.xlen("s") == 2
+        with pytest.raises(TypeError):
+            r.xadd("s", {"n": 4}, limit=10)
+
+    def test_xread(self, r):
+        a = r.xadd("a", {"k": "v"})
+        r.xadd("b", {"k": "w"})
+        result = r.xread({"a": "0", "b": "0"}, count=1)
+        assert result["a"] == [(a, {"k": "v"})]
+        assert set(result) == {"a", "b"}
+        assert r.xread({"a": "$"}, block=50) is None
+
+    def test_consumer_group(self, r):
+        assert r.xgroup_create("s", "g", id="0", mkstream=True) is True
+        first = r.xadd("s", {"job": "1"})
+        r.xadd("s", {"job": "2"})
+        result = r.xreadgroup("g", "c1", {"s": ">"}, count=1)
+        assert result == {"s": [(first, {"job": "1"})]}
+        # Pending entries for this consumer
+        assert r.xreadgroup("g", "c1", {"s": "0"}) == {"s": [(first, {"job": "1"})]}
+        assert r.xclaim("s", "g", "c2", 0, [first], justid=True) == [first]
+        assert r.xclaim("s", "g", "c1", 0, [first]) == [(first, {"job": "1"})]
+        assert r.xpending("s", "g") == {"pending": 1, "min": first, "max": first, "consumers": {"c1": 1}}
+        assert r.xack("s", "g", first) == 1
+        assert r.xreadgroup("g", "c1", {"s": "0"}) == {"s": []}
+        assert r.xpending("s", "g") == {"pending": 0, "min": None, "max": None, "consumers": {}}
+
+    def test_deleted_pending_entry(self, r):
+        r.xgroup_create("s", "g", id="0", mkstream=True)
+        entry = r.xadd("s", {"a": 1})
+        r.xreadgroup("g", "c", {"s": ">"})
+        r.xdel("s", entry)
+        assert r.xreadgroup("g", "c", {"s": "0"}) == {"s": [(entry, None)]}
+
+    def test_pipeline(self, r):
+        pipe = r.pipeline()
+        pipe.xadd("s", {"a": 1}, id="1-0").xlen("s").xrange("s").xread({"s": "0"})
+        assert pipe.execute() == [
+            "1-0",
+            1,
+            [("1-0", {"a": "1"})],
+            {"s": [("1-0", {"a": "1"})]},
+        ]
+
+    def test_transaction_pipeline(self, r):
+        pipe = r.pipeline(transaction=True)
+        pipe.xadd("s", {"a": 1}, id="1-0").xrevrange("s")
+        assert pipe.execute() == ["1-0", [("1-0", {"a": "1"})]]
+
 
 # ── Pipeline ────────────────────────────────────────────────────────
 
@@ -347,6 +906,38 @@ class TestPipeline:
         assert results[2] == "1"
         assert results[3] == "2"
 
+    def test_execute_command_arguments(self, r):
+        pipe = r.pipeline()
+        pipe.execute_command("SET", b"pn", 7).execute_command("INCRBY", "pn", 3)
+        assert pipe.execute()[1] == 10
+        with pytest.raises(TypeError):
+            pipe.execute_command("SET", b"\xff", "v")
+        assert len(pipe) == 0
+
+    def test_max_bytes_raise(self, r):
+        from pyrsedis import PipelineTooLargeError
+
+        pipe = r.pipeline(max_bytes=100)
+        while True:
+            try:
+                pipe.set("mb", "x" * 10)
+            except PipelineTooLargeError:
+                break
+        assert 0 < pipe.size_bytes <= 100
+        assert pipe.max_bytes == 100
+        assert all(pipe.execute())
+        assert pipe.size_bytes == 0
+
+    def test_max_bytes_flush(self, r):
+        pipe = r.pipeline(max_bytes=1000, on_overflow="flush")
+        for i in range(200):
+            pipe.incr("mbf")
+        assert pipe.size_bytes <= 1000
+        assert len(pipe) == 200
+        assert pipe.execute() == list(range(1, 201))
+        with pytest.raises(TypeError):
+            r.pipeline(transaction=True, on_overflow="flush")
+
     def test_empty_pipeline(self, r):
         pipe = r.pipeline()
         results = pipe.execute()
@@ -381,6 +972,20 @@ class TestPipeline:
         assert results[2] == "11"
         assert results[3] == 1
 
+    def test_pipeline_mirrors(self, r):
+        pipe = r.pipeline()
+        pipe.mset({"pm_a": "1", "pm_b": "2"}).mget("pm_a", "pm_b", "pm_missing")
+        pipe.setex("pm_ex", 60, "v").getset("pm_a", "3").getdel("pm_b")
+        pipe.zadd("pm_z", {"x": 1, "y": 2}).zadd("pm_z", {"x": 0}, gt=True, ch=True)
+        pipe.zrangebyscore("pm_z", "-inf", "+inf", withscores=False, offset=0, count=1)
+        pipe.keys("pm_*").info("server").pexpire("pm_a", 5000)
+        results = pipe.execute()
+        assert results[:7] == [True, ["1", "2", None], True, "1", "2", 2, 0]
+        assert results[7] == ["x"]
+        assert sorted(results[8]) == ["pm_a", "pm_ex", "pm_z"]
+        assert "redis_version" in results[9]
+        assert results[10] == 1
+
     def test_pipeline_large_batch(self, r):
         pipe = r.pipeline()
         for i in range(100):
@@ -392,6 +997,101 @@ class TestPipeline:
         # Verify last GET
         assert results[199] == "v99"
 
+    def test_execute_many(self, r):
+        results = r.execute_many([("SET", "m", "1"), ["INCR", "m"], ("GET", "m")])
+        assert results == ["OK", 2, "2"]
+
+    def test_execute_many_empty(self, r):
+        assert r.execute_many([]) == []
+
+    def test_execute_many_rejects_empty_command(self, r):
+        with pytest.raises(TypeError):
+            r.execute_many([("PING",), ()])
+
+    def test_immediate_pipeline(self, r):
+        pipe = r.pipeline(immediate=True)
+        assert pipe.immediate is True
+        pipe.set("imm", "1").incr("imm")
+        # Already applied before execute()
+        assert r.get("imm") == "2"
+        assert len(pipe) == 2
+        assert pipe.execute() == ["OK", 2]
+        assert len(pipe) == 0
+
+    def test_transaction_pipeline(self, r):
+        pipe = r.pipeline(transaction=True)
+        assert pipe.transaction is True
+        pipe.set("tx", "1").incr("tx").get("tx")
+        assert pipe.execute()[1:] == [2, "2"]
+        assert r.pipeline(transaction=True).execute() == []
+
+    def test_transaction_abort(self, r):
+        from pyrsedis import TransactionError
+
+        pipe = r.pipeline(transaction=True)
+        pipe.set("tx_abort", "1").execute_command("NOSUCHCOMMAND")
+        with pytest.raises(TransactionError):
+            pipe.execute()
+        assert r.get("tx_abort") is None
+        assert r.ping() is True
+
+    def test_transaction_rejects_immediate(self, r):
+        with pytest.raises(TypeError):
+            r.pipeline(immediate=True, transaction=True)
+
+    def test_watch_conflict_raises(self, r):
+        from pyrsedis import WatchError
+
+        r.set("watched", "1")
+        pipe = r.pipeline(transaction=True)
+        pipe.watch("watched")
+        assert pipe.watching is True
+        r.set("watched", "2")  # another connection changes the key
+        pipe.set("watched", "3")
+        with pytest.raises(WatchError):
+            pipe.execute()
+        assert pipe.watching is False
+        assert r.get("watched") == "2"
+
+    def test_unwatch_releases_connection(self, r):
+        available = r.pool_available
+        pipe = r.pipeline(transaction=True)
+        pipe.watch("watched")
+        assert r.pool_available == available - 1
+        pipe.unwatch()
+        assert r.pool_available == available
+        with pytest.raises(TypeError):
+            r.pipeline().watch("watched")
+
+    def test_transaction_helper_retries(self, r):
+        r.set("balance", "0")
+        attempts = []
+
+        def incr(pipe):
+            attempts.append(1)
+            balance = int(r.get("balance"))
+            if len(attempts) == 1:
+                r.set("balance", "100")  # conflicting write
+            pipe.set("balance", str(balance + 10))
+
+        r.transaction(incr, "balance")
+        assert len(attempts) == 2
+        assert r.get("balance") == "110"
+
+    def test_transaction_helper_gives_up(self, r):
+        from pyrsedis import TransactionError, WatchError
+
+        r.set("contended", "0")
+
+        def always_conflict(pipe):
+            r.incr("contended")
+            pipe.incr("contended")
+
+        with pytest.raises(WatchError):
+            r.transaction(always_conflict, "contended", retries=2)
+        assert r.get("contended") == "3"
+        assert issubclass(WatchError, TransactionError)
+
 
 # ── Server commands ─────────────────────────────────────────────────
 
@@ -408,6 +1108,43 @@ class TestServer:
         r.set("k", "v")
         assert r.dbsize() == 1
 
+    def test_flush_needs_confirmation(self, r, redis_url):
+        from pyrsedis import Redis
+
+        r.set("k", "v")
+        with pytest.raises(ValueError):
+            r.flushdb()
+        with pytest.raises(ValueError):
+            r.pipeline().flushall()
+        assert r.dbsize() == 1
+        assert r.flushdb("async", confirm=True) is True
+        assert r.dbsize() == 0
+        with pytest.raises(ValueError):
+            r.flushdb("later", confirm=True)
+
+        trusted = Redis.from_url(redis_url, allow_destructive=True)
+        trusted.set("k", "v")
+        assert trusted.pipeline().flushdb("sync").execute() == [True]
+        assert trusted.dbsize() == 0
+
+    def test_strict_numbers(self, r, redis_url):
+        from pyrsedis import Redis
+
+        r.set("k", "v")
+        with pytest.raises(Exception):
+            r.execute_command("EXPIRE", "k", 1.5)
+        r.zadd("z", {"a": 2**53 + 1})
+
+        strict = Redis.from_url(redis_url, strict_numbers=True)
+        with pytest.raises(TypeError, match="EXPIRE expects an integer"):
+            strict.execute_command("EXPIRE", "k", 10.0)
+        with pytest.raises(TypeError):
+            strict.pipeline().execute_command("SET", "k", "v", "EX", 1.5)
+        with pytest.raises(TypeError, match="exactly"):
+            strict.zadd("z", {"a": 2**53 + 1})
+        assert strict.execute_command("EXPIRE", "k", 10) == 1
+        assert strict.zadd("z", {"b": 2**53, "c": 0.5}) == 2
+
     def test_keys(self, r):
         r.set("aaa", "1")
         r.set("bbb", "2")
@@ -422,17 +1159,137 @@ class TestServer:
         result = r.time()
         assert len(result) == 2
 
+    def test_time_offset(self, r):
+        offset = r.time_offset()
+        assert r.time_offset() == offset
+        secs, micros = r.time()
+        server = int(secs) + int(micros) / 1e6
+        assert abs(r.server_now() - server) < 1.0
+        assert isinstance(r.time_offset(refresh=True), float)
+
     def test_execute_command(self, r):
         result = r.execute_command("SET", "k", "v")
         assert result == "OK"
         result = r.execute_command("GET", "k")
         assert result == "v"
 
+    def test_command_timeout(self, r):
+        from pyrsedis import RedisTimeoutError
+
+        with pytest.raises(RedisTimeoutError):
+            r.execute_command("DEBUG", "SLEEP", "0.5", timeout_ms=100)
+        # The abandoned connection's late reply never reaches this one
+        assert r.execute_command("ECHO", "after", timeout_ms=2000) == "after"
+        r.set("k", "v")
+        assert r.ttl("k", timeout=1.0) == -1
+        with pytest.raises(TypeError):
+            r.ttl("k", timeout=0)
+
+    def test_execute_on_all_nodes(self, r):
+        from pyrsedis import NodeResults, ResponseError
+
+        res = r.execute_on_all_nodes("PING")
+        assert isinstance(res, NodeResults)
+        assert res.ok and len(res) == 1
+        assert list(res.successes.values()) == [True]
+        res = r.execute_on_all_nodes("CONFIG", "SET", "no-such-parameter", "1")
+        assert not res.ok
+        assert res.successes == {}
+        (err,) = res.failures.values()
+        assert isinstance(err, ResponseError)
+        with pytest.raises(ResponseError):
+            res.raise_for_failures()
+
+    def test_config_set(self, r):
+        assert r.config_set("maxmemory-policy", "noeviction") is True
+
+    def test_config_get(self, r):
+        assert r.config_get("maxmemory-policy") == {"maxmemory-policy": "noeviction"}
+        assert "maxmemory" in r.config_get()
+        assert r.pipeline().config_get("maxmemory-policy").execute() == [{"maxmemory-policy": "noeviction"}]
+
     def test_repr(self, r):
         rep = repr(r)
         assert "Redis" in rep
         assert "127.0.0.1" in rep
 
+    def test_publish_many(self, r):
+        assert r.publish_many([("nobody_a", "1"), ("nobody_b", "2")]) == [0, 0]
+        assert r.publish_many([]) == []
+
+    def test_health_check_reconnects(self, r, redis_url):
+        from pyrsedis import Redis
+
+        checked = Redis.from_url(redis_url, pool_size=1, health_check_interval_ms=1)
+        client_id = checked.execute_command("CLIENT", "ID")
+        r.execute_command("CLIENT", "KILL", "ID", client_id)
+        time.sleep(0.01)
+        # The killed connection fails its ping and is replaced unnoticed
+        assert checked.ping() is True
+        assert checked.execute_command("CLIENT", "ID") != client_id
+
+    def test_min_idle_and_warmup(self, redis_url):
+        from pyrsedis import Redis
+
+        warm = Redis.from_url(redis_url, pool_size=4, min_idle=2)
+        assert warm.pool_idle_count == 2
+        assert warm.warmup() == 2
+        assert warm.pool_idle_count == 4
+
+    def test_pool_timeout_names_stalled_command(self, redis_url):
+        import threading
+        from pyrsedis import PoolTimeoutError, Redis
+
+        r = Redis.from_url(redis_url, pool_size=1, pool_timeout_ms=100)
+        stall = threading.Thread(target=r.execute_command, args=("DEBUG", "SLEEP", "0.5"))
+        stall.start()
+        time.sleep(0.1)
+        with pytest.raises(PoolTimeoutError, match="1/1 connections in use, longest in-flight: DEBUG"):
+            r.ping()
+        stall.join()
+
+    def test_try_execute_command_does_not_queue(self, redis_url):
+        import threading
+        from pyrsedis import Redis, WouldBlockError
+
+        r = Redis.from_url(redis_url, pool_size=1)
+        assert r.try_execute_command("PING") == "PONG"
+        stall = threading.Thread(target=r.execute_command, args=("DEBUG", "SLEEP", "0.3"))
+        stall.start()
+        time.sleep(0.1)
+        assert r.pool_available == 0
+        started = time.monotonic()
+        with pytest.raises(WouldBlockError, match="1/1 connections in use"):
+            r.try_execute_command("GET", "k")
+        assert time.monotonic() - started < 0.1
+        assert r.try_execute_command("SET", "k", "v", timeout_ms=2000) == "OK"
+        stall.join()
+
+    def test_pool_stats(self, redis_url):
+        from pyrsedis import Redis
+
+        r = Redis.from_url(redis_url, pool_size=2)
+        r.set("k", "v")
+        assert r.get("k") == "v"
+        stats = r.pool_stats()
+        assert stats["connections_created"] == 1
+        assert stats["checkouts"] == 2
+        assert stats["in_use"] == 0 and stats["max_size"] == 2
+        assert stats["bytes_sent"] > 0 and stats["bytes_received"] > 0
+        assert 0 <= stats["wait_ms_p50"] <= stats["wait_ms_max"]
+
+    def test_command_stats(self, r):
+        r.reset_command_stats()
+        r.set("k", "v")
+        r.get("k")
+        r.hset("h", "f", "v")
+        stats = r.command_stats()
+        assert stats["strings"]["count"] == 2 and stats["hashes"]["count"] == 1
+        assert 0 <= stats["strings"]["latency_ms_p50"] <= stats["strings"]["latency_ms_max"]
+        assert "graph" not in stats
+        r.reset_command_stats()
+        assert r.command_stats() == {}
+
     def test_pool_idle_count(self, r):
         # After ping, we should have an idle connection
         assert r.pool_idle_count >= 0
@@ -440,6 +1297,1156 @@ class TestServer:
     def test_pool_available(self, r):
         assert r.pool_available > 0
 
+    def test_protocol_version(self, r):
+        # The fixture already pinged, so a connection was negotiated
+        assert r.protocol_version == 2
+
+
+# ── Dedicated connections ───────────────────────────────────────────
+
+
+class TestDedicatedConnection:
+    def test_state_persists_within_lease(self, r):
+        with r.dedicated_connection() as conn:
+            assert conn.active is True
+            conn.execute_command("SELECT", "1")
+            conn.execute_command("SET", "lease_key", "v")
+            assert conn.execute_command("GET", "lease_key") == "v"
+            assert r.get("lease_key") is None  # pool connections stay on db 0
+            conn.execute_command("DEL", "lease_key")
+        assert conn.active is False
+
+    def test_reset_on_return(self, r):
+        with r.dedicated_connection() as conn:
+            conn.execute_command("SELECT", "2")
+        # Every pooled connection is back on the configured db
+        r.set("after_reset", "1")
+        assert r.dbsize() == 1
+
+    def test_use_after_close_raises(self, r):
+        import pyrsedis
+
+        conn = r.dedicated_connection()
+        conn.close()
+        conn.close()
+        with pytest.raises(pyrsedis.RedisConnectionError):
+            conn.execute_command("PING")
+
+    def test_client_reply_off_and_on(self, r):
+        with r.dedicated_connection() as conn:
+            conn.client_reply("OFF")
+            assert conn.execute_command("SET", "quiet", "1") is None
+            conn.client_reply("ON")
+            assert conn.execute_command("GET", "quiet") == "1"
+
+    def test_client_reply_skip(self, r):
+        with r.dedicated_connection() as conn:
+            conn.client_reply("SKIP")
+            assert conn.execute_command("SET", "skipped", "1") is None
+            assert conn.execute_command("GET", "skipped") == "1"
+
+    def test_client_reply_restored_before_return(self, r):
+        conn = r.dedicated_connection()
+        conn.execute_command("CLIENT", "REPLY", "OFF")
+        conn.close()
+        # Shared pool still gets one reply per command
+        for _ in range(r.pool_available):
+            assert r.ping() is True
+
+    def test_client_reply_invalid_mode(self, r):
+        with r.dedicated_connection() as conn:
+            with pytest.raises(TypeError):
+                conn.client_reply("LOUD")
+
+    def test_holds_pool_slot(self, r):
+        before = r.pool_available
+        conn = r.dedicated_connection()
+        assert r.pool_available == before - 1
+        conn.close()
+        assert r.pool_available == before
+
+
+# ── Pub/Sub ─────────────────────────────────────────────────────────
+
+
+class TestPubSub:
+    def test_subscribe_and_receive(self, r):
+        with r.pubsub() as p:
+            p.subscribe("ps_news")
+            confirm = p.get_message(timeout=1.0)
+            assert confirm == {"type": "subscribe", "pattern": None, "channel": "ps_news", "data": 1}
+            assert r.publish("ps_news", "hello") == 1
+            msg = p.get_message(timeout=1.0)
+            assert msg["type"] == "message"
+            assert msg["data"] == "hello"
+
+    def test_get_message_timeout(self, r):
+        with r.pubsub() as p:
+            p.subscribe("ps_quiet")
+            assert p.get_message(timeout=1.0, ignore_subscribe_messages=True) is None
+            assert p.get_message(timeout=0) is None
+
+    def test_get_message_rejects_bad_timeouts(self, r):
+        with r.pubsub() as p:
+            p.subscribe("ps_quiet")
+            for timeout in (-1.0, float("nan"), 1e20):
+                with pytest.raises(TypeError):
+                    p.get_message(timeout=timeout)
+
+    def test_pattern_subscribe_and_filter(self, r):
+        with r.pubsub() as p:
+            p.psubscribe("ps.*")
+            p.get_message(timeout=1.0)
+            r.publish("ps.a", "1")
+            r.publish("ps.b", "2")
+            msg = p.get_message(timeout=1.0, pattern_filter="ps.b")
+            assert msg["pattern"] == "ps.*"
+            assert msg["channel"] == "ps.b"
+
+    def test_listen_stops_after_unsubscribe(self, r):
+        with r.pubsub() as p:
+            p.subscribe("ps_a")
+            p.get_message(timeout=1.0)
+            r.publish("ps_a", "x")
+            seen = []
+            for msg in p.listen():
+                seen.append(msg["type"])
+                if msg["type"] == "message":
+                    p.unsubscribe()
+            assert seen[0] == "message"
+            assert p.subscribed is False
+
+    def test_connection_returned_on_close(self, r):
+        available = r.pool_available
+        p = r.pubsub()
+        p.subscribe("ps_x")
+        assert r.pool_available == available - 1
+        p.close()
+        assert r.pool_available == available
+        assert r.ping() is True
+
+    def test_resubscribes_after_connection_loss(self, r):
+        with r.pubsub() as p:
+            p.subscribe("ps_resub")
+            p.get_message(timeout=1.0)
+            r.execute_command("CLIENT", "KILL", "TYPE", "pubsub")
+            # The next read reconnects and replays the subscription
+            p.get_message(timeout=1.0)
+            assert r.publish("ps_resub", "back") == 1
+            msg = p.get_message(timeout=1.0, ignore_subscribe_messages=True)
+            assert msg["data"] == "back"
+        kinds = [e["type"] for e in r.recent_events()]
+        assert "disconnected" in kinds
+        assert kinds[-1] == "subscribed"
+
+
+# ── Expiry watcher ──────────────────────────────────────────────────
+
+
+class TestExpiryWatcher:
+    def test_callback_receives_matching_keys(self, r):
+        expired = []
+        with r.on_expire(expired.append, pattern="ttl:*") as watcher:
+            assert watcher.running
+            assert watcher.channel == "__keyevent@0__:expired"
+            r.set("ttl:a", "1", px=50)
+            r.set("other", "1", px=50)
+            deadline = time.time() + 5
+            while not expired and time.time() < deadline:
+                time.sleep(0.05)
+                r.exists("ttl:a", "other")  # trigger lazy expiry
+        assert expired == ["ttl:a"]
+        assert not watcher.running
+        assert "x" in r.execute_command("CONFIG", "GET", "notify-keyspace-events")[1]
+
+    def test_rejects_non_callable(self, r):
+        with pytest.raises(TypeError):
+            r.on_expire("not callable")
+
+
+# ── Serializer ──────────────────────────────────────────────────────
+
+
+class TestSerializer:
+    @pytest.fixture
+    def rm(self, r, redis_url):
+        from pyrsedis import Redis
+
+        return Redis.from_url(redis_url, serializer="msgpack")
+
+    def test_roundtrip_values(self, rm):
+        values = {
+            "ser_dict": {"a": 1, "b": [1.5, None, True], "c": {"nested": "é"}},
+            "ser_list": [1, "two", b"\x00\xff", [3]],
+            "ser_int": -(2**40),
+            "ser_float": 3.25,
+            "ser_str": "hello",
+            "ser_bytes": b"\x00\x01\xfe",
+            "ser_none": None,
+        }
+        for key, value in values.items():
+            assert rm.set(key, value) is True
+            assert rm.get(key) == value
+
+    def test_tuple_decodes_as_list(self, rm):
+        rm.set("ser_tuple", (1, "a"))
+        assert rm.get("ser_tuple") == [1, "a"]
+
+    def test_missing_key_is_none(self, rm):
+        assert rm.get("ser_missing") is None
+
+    def test_set_options_still_apply(self, rm):
+        assert rm.set("ser_nx", {"v": 1}, nx=True) is True
+        assert rm.set("ser_nx", {"v": 2}, nx=True) is None
+        assert rm.get("ser_nx") == {"v": 1}
+
+    def test_unsupported_value_raises(self, rm):
+        with pytest.raises(TypeError):
+            rm.set("ser_bad", object())
+
+    def test_unknown_serializer_raises(self):
+        from pyrsedis import Redis
+
+        with pytest.raises(TypeError):
+            Redis(serializer="pickle")
+
+
+# ── RESP3 reply types ───────────────────────────────────────────────
+
+
+class TestResp3Types:
+    @pytest.fixture
+    def rt(self, r, redis_url):
+        from pyrsedis import Redis
+
+        return Redis.from_url(redis_url, resp3_types=True)
+
+    def test_verbatim_string(self, rt):
+        from pyrsedis import Verbatim
+
+        with rt.dedicated_connection() as conn:
+            conn.execute_command("HELLO", "3")
+            info = conn.execute_command("CLIENT", "INFO")
+        assert isinstance(info, Verbatim)
+        assert info.encoding == "txt"
+        assert str(info) == info.text
+        assert "id=" in info.text
+
+    def test_verbatim_is_plain_str_by_default(self, r):
+        with r.dedicated_connection() as conn:
+            conn.execute_command("HELLO", "3")
+            info = conn.execute_command("CLIENT", "INFO")
+        assert isinstance(info, str)
+
+    def test_big_number_type(self):
+        from pyrsedis import BigNumber
+
+        n = BigNumber("12345678901234567890123")
+        assert isinstance(n, int)
+        assert n == 12345678901234567890123
+        assert n.raw == "12345678901234567890123"
+
+    def test_protocol_3_native_types(self, r, redis_url):
+        from pyrsedis import Redis
+
+        r3 = Redis.from_url(redis_url, protocol=3)
+        r3.hset("h", "f", "v")
+        r3.sadd("s", "a")
+        assert r3.hgetall("h") == {"f": "v"}
+        assert r3.smembers("s") == {"a"}
+        assert r3.protocol_version == 3
+
+    def test_protocol_3_pubsub_pushes(self, r, redis_url):
+        from pyrsedis import Redis
+
+        r3 = Redis.from_url(redis_url, protocol=3)
+        with r3.pubsub() as p:
+            p.subscribe("resp3_news")
+            assert p.get_message(timeout=1.0)["type"] == "subscribe"
+            r.publish("resp3_news", "hi")
+            assert p.get_message(timeout=1.0)["data"] == "hi"
+
+    def test_invalid_protocol(self):
+        from pyrsedis import Redis
+
+        with pytest.raises(TypeError):
+            Redis(protocol=4)
+
+
+# ── Client-side caching ─────────────────────────────────────────────
+
+
+class TestClientCache:
+    @pytest.fixture
+    def rc(self, r, redis_url):
+        import time
+
+        from pyrsedis import Redis
+
+        client = Redis.from_url(redis_url, cache=True)
+        client.ping()
+        deadline = time.monotonic() + 2.0
+        while not client.cache_info()["tracking"] and time.monotonic() < deadline:
+            time.sleep(0.01)
+        assert client.cache_info()["tracking"]
+        return client
+
+    def _wait_until(self, cond):
+        import time
+
+        deadline = time.monotonic() + 2.0
+        while not cond() and time.monotonic() < deadline:
+            time.sleep(0.01)
+        return cond()
+
+    def test_repeated_get_is_cached(self, rc):
+        rc.set("ck", "v1")
+        assert rc.get("ck") == "v1"
+        assert rc.get("ck") == "v1"
+        info = rc.cache_info()
+        assert info["hits"] == 1
+        assert info["size"] == 1
+
+    def test_own_write_evicts(self, rc):
+        rc.set("ck", "v1")
+        assert rc.get("ck") == "v1"
+        rc.set("ck", "v2")
+        assert rc.get("ck") == "v2"
+
+    def test_other_client_write_invalidates(self, r, rc):
+        rc.set("ck", "v1")
+        assert rc.get("ck") == "v1"
+        r.set("ck", "v2")
+        assert self._wait_until(lambda: rc.cache_info()["size"] == 0)
+        assert rc.get("ck") == "v2"
+
+    def test_flush_clears_cache(self, r, rc):
+        rc.set("ck", "v1")
+        rc.get("ck")
+        r.flushdb(confirm=True)
+        assert self._wait_until(lambda: rc.cache_info()["size"] == 0)
+        assert rc.get("ck") is None
+
+    def test_ttl_and_clear(self, r, redis_url):
+        import time
+
+        from pyrsedis import Redis
+
+        rc = Redis.from_url(redis_url, cache=True, cache_ttl=0.05)
+        rc.set("ck", "v1")
+        assert self._wait_until(lambda: rc.cache_info()["tracking"])
+        rc.get("ck")
+        assert rc.cache_info()["size"] == 1
+        time.sleep(0.1)
+        rc.get("ck")
+        assert rc.cache_info()["hits"] == 0
+        rc.cache_clear()
+        assert rc.cache_info()["size"] == 0
+
+    def test_disabled_by_default(self, r):
+        assert r.cache_info() is None
+        r.cache_clear()
+
+    def test_invalid_options(self):
+        from pyrsedis import Redis
+
+        with pytest.raises(TypeError):
+            Redis(cache=True, pool_size=1)
+        with pytest.raises(TypeError):
+            Redis(cache=True, cache_max_entries=0)
+        with pytest.raises(TypeError):
+            Redis(cache=True, cache_ttl=-1)
+
+
+# ── Value encryption ────────────────────────────────────────────────
+
+
+def _xor(key_id, data):
+    key = key_id.encode()
+    return bytes(b ^ key[i % len(key)] for i, b in enumerate(data))
+
+
+class TestEncryption:
+    @pytest.fixture
+    def enc(self):
+        from pyrsedis import Encryption
+
+        return Encryption("k1", encrypt=_xor, decrypt=_xor)
+
+    @pytest.fixture
+    def renc(self, r, redis_url, enc):
+        from pyrsedis import Redis
+
+        return Redis.from_url(redis_url, encryption=enc)
+
+    def test_roundtrip_stores_ciphertext(self, r, renc):
+        renc.set("secret", "hello")
+        assert renc.get("secret") == "hello"
+        raw = r.execute_command("GET", "secret")
+        assert isinstance(raw, bytes)
+        assert raw.startswith(b"\x00ENC\x01\x02k1")
+        assert b"hello" not in raw
+
+    def test_with_serializer(self, r, redis_url, enc):
+        from pyrsedis import Redis
+
+        rs = Redis.from_url(redis_url, serializer="msgpack", encryption=enc)
+        rs.set("doc", {"ssn": "123-45-6789"})
+        assert rs.get("doc") == {"ssn": "123-45-6789"}
+
+    def test_key_rotation_and_plain_values(self, r, redis_url, renc):
+        from pyrsedis import Encryption, Redis
+
+        renc.set("old", "v")
+        r.set("plain", "p")
+        seen = []
+
+        def decrypt(key_id, data):
+            seen.append(key_id)
+            return _xor(key_id, data)
+
+        rotated = Redis.from_url(redis_url, encryption=Encryption("k2", _xor, decrypt))
+        assert rotated.get("old") == "v"
+        assert rotated.get("plain") == "p"
+        assert seen == ["k1"]
+
+    def test_callback_errors_propagate(self, r, redis_url):
+        from pyrsedis import Encryption, Redis
+
+        def fail(key_id, data):
+            raise ValueError("no key")
+
+        rf = Redis.from_url(redis_url, encryption=Encryption("k", fail, fail))
+        with pytest.raises(ValueError):
+            rf.set("k", "v")
+
+    def test_async_client(self, r, redis_url, enc):
+        from pyrsedis import AsyncRedis
+
+        ar = AsyncRedis.from_url(redis_url, encryption=enc)
+
+        async def main():
+            await ar.set("secret", "hello")
+            return await ar.get("secret")
+
+        assert asyncio.run(main()) == "hello"
+        assert r.get("secret") != "hello"
+
+    def test_invalid_arguments(self):
+        from pyrsedis import Encryption
+
+        with pytest.raises(TypeError):
+            Encryption("", _xor, _xor)
+        with pytest.raises(TypeError):
+            Encryption("k", "not callable", _xor)
+
+
+# ── Cluster ─────────────────────────────────────────────────────────
+
+
+class TestCluster:
+    """Runs against a Redis Cluster named by ``REDIS_CLUSTER_URL``
+    (e.g. ``redis+cluster://127.0.0.1:7000``)."""
+
+    @pytest.fixture
+    def rc(self):
+        from pyrsedis import Redis
+
+        url = os.environ.get("REDIS_CLUSTER_URL")
+        if not url:
+            pytest.skip("REDIS_CLUSTER_URL not set")
+        return Redis.from_url(url)
+
+    def test_keys_spread_across_slots(self, rc):
+        keys = [f"cluster_key_{i}" for i in range(50)]
+        for key in keys:
+            assert rc.set(key, key) is True
+        assert [rc.get(key) for key in keys] == keys
+        assert rc.delete(*keys[:1]) == 1
+
+    def test_pipeline_routes_per_node(self, rc):
+        pipe = rc.pipeline()
+        for i in range(20):
+            pipe.set(f"cluster_pipe_{i}", str(i))
+        for i in range(20):
+            pipe.get(f"cluster_pipe_{i}")
+        results = pipe.execute()
+        assert results[:20] == [True] * 20
+        assert results[20:] == [str(i) for i in range(20)]
+
+    def test_constructor_seeds(self):
+        from pyrsedis import Redis
+
+        url = os.environ.get("REDIS_CLUSTER_URL")
+        if not url:
+            pytest.skip("REDIS_CLUSTER_URL not set")
+        host, port = url.split("://", 1)[1].split(",")[0].rsplit(":", 1)
+        rc = Redis(cluster=[(host, int(port))])
+        assert rc.ping() is True
+
+    def test_slot_refresh_interval(self):
+        from pyrsedis import Redis
+
+        url = os.environ.get("REDIS_CLUSTER_URL")
+        if not url:
+            pytest.skip("REDIS_CLUSTER_URL not set")
+        for interval in (0, 50):
+            rc = Redis.from_url(url, slot_refresh_interval_ms=interval)
+            assert rc.set("cluster_refresh", "1") is True
+            time.sleep(0.2)
+            assert rc.get("cluster_refresh") == "1"
+
+    def test_fan_out_commands_report_per_node(self, rc):
+        from pyrsedis import NodeResults
+
+        res = rc.flushall(confirm=True)
+        assert isinstance(res, NodeResults)
+        assert res.ok and len(res) >= 1
+        sha = rc.script_load("return 1")
+        assert sha.ok
+        assert len(set(sha.successes.values())) == 1
+        res = rc.config_set("no-such-parameter", "1")
+        assert not res.ok
+        assert set(res.failures) == set(rc.execute_on_all_nodes("PING").successes)
+
+    def test_set_store_needs_one_slot(self, rc):
+        from pyrsedis import ClusterError
+
+        rc.sadd("{t}a", "1", "2")
+        rc.sadd("{t}b", "2")
+        assert rc.sinterstore("{t}out", "{t}a", "{t}b") == 1
+        assert rc.smove("{t}a", "{t}b", "1") == 1
+        with pytest.raises(ClusterError):
+            rc.sunionstore("out", "{t}a", "{t}b")
+
+    def test_cross_slot_split(self, rc):
+        from pyrsedis import CrossSlotError, Redis
+
+        keys = [f"cluster_split_{i}" for i in range(10)]
+        for key in keys:
+            rc.set(key, key)
+        with pytest.raises(CrossSlotError):
+            rc.mget(*keys)
+
+        split = Redis.from_url(os.environ["REDIS_CLUSTER_URL"], allow_cross_slot=True)
+        assert split.mget(*keys, "cluster_split_missing") == keys + [None]
+        assert split.exists(*keys) == 10
+        assert split.unlink(*keys[:5]) == 5
+        assert split.delete(*keys) == 5
+
+    def test_address_remap(self):
+        from pyrsedis import Redis
+
+        url = os.environ.get("REDIS_CLUSTER_URL")
+        if not url:
+            pytest.skip("REDIS_CLUSTER_URL not set")
+        seen = []
+
+        def remap(addr):
+            seen.append(addr)
+            return None
+
+        rc = Redis.from_url(url, address_remap=remap)
+        assert rc.set("cluster_remap", "1") is True
+        assert set(seen) >= set(rc.execute_on_all_nodes("PING").successes)
+        with pytest.raises(TypeError):
+            Redis.from_url(url, address_remap={"10.0.0.1": "localhost:7001"})
+        with pytest.raises(TypeError):
+            Redis(address_remap={})
+
+    def test_read_preference(self):
+        from pyrsedis import Redis
+
+        url = os.environ.get("REDIS_CLUSTER_URL")
+        if not url:
+            pytest.skip("REDIS_CLUSTER_URL not set")
+        for preference in ("prefer_replica", "lowest_latency", "az_affinity:none"):
+            rc = Redis.from_url(url, read_preference=preference)
+            assert rc.set("cluster_read_pref", "1") is True
+            assert rc.get("cluster_read_pref") in ("1", None)
+        with pytest.raises(TypeError):
+            Redis.from_url(url, read_preference="nearest")
+        with pytest.raises(TypeError):
+            Redis(read_preference="prefer_replica")
+
+    def test_keyless_routing(self):
+        from pyrsedis import ClusterError, Redis
+
+        url = os.environ.get("REDIS_CLUSTER_URL")
+        if not url:
+            pytest.skip("REDIS_CLUSTER_URL not set")
+        rc = Redis.from_url(url, keyless_routing="round_robin")
+        assert all(rc.ping() for _ in range(6))
+        for node in rc.execute_on_all_nodes("PING").successes:
+            assert rc.execute_command("PING", node=node) == "PONG"
+        with pytest.raises(ClusterError):
+            rc.execute_command("PING", node="127.0.0.1:1")
+        rc.set("routed", "1")
+        assert rc.execute_command("GET", "routed", route="master") == "1"
+        assert rc.execute_command("PING", route=("slot", 0)) == "PONG"
+        with pytest.raises(TypeError):
+            rc.execute_command("GET", "routed", route="nearest")
+        with pytest.raises(TypeError):
+            Redis().execute_command("PING", route="master")
+        split = Redis.from_url(url, keyless_routing={"default": "least_loaded", "scripting": "first"})
+        assert all(split.ping() for _ in range(6))
+        assert split.script_load("return 1").ok
+        with pytest.raises(TypeError):
+            Redis.from_url(url, keyless_routing="nearest")
+        with pytest.raises(TypeError):
+            Redis.from_url(url, keyless_routing={"admin": "first"})
+        with pytest.raises(TypeError):
+            Redis(keyless_routing="random")
+
+    def test_cross_slot_fails_before_sending(self, rc):
+        from pyrsedis import ClusterError, CrossSlotError
+
+        with pytest.raises(CrossSlotError):
+            rc.mset({"cs_a": "1", "cs_b": "2"})
+        with pytest.raises(ClusterError):
+            rc.execute_command("ZUNIONSTORE", "cs_out", "2", "cs_a", "cs_b")
+        assert rc.mset({"{cs}a": "1", "{cs}b": "2"}) is True
+        assert rc.execute_command("ZUNIONSTORE", "{cs}out", "1", "{cs}z") == 0
+
+    def test_migrate_slot(self, rc):
+        masters = list(rc.execute_on_all_nodes("PING").successes)
+        if len(masters) < 2:
+            pytest.skip("needs two masters")
+        for i in range(5):
+            rc.set(f"{{reshard}}{i}", str(i))
+        slot = rc.execute_command("CLUSTER", "KEYSLOT", "{reshard}")
+        owner_id = rc.execute_command("CLUSTER", "MYID", route=("slot", slot))
+        source = next(m for m in masters if rc.execute_command("CLUSTER", "MYID", node=m) == owner_id)
+        target = next(m for m in masters if m != source)
+        assert len(rc.cluster_getkeysinslot(slot, 100)) == 5
+
+        seen = []
+        assert rc.migrate_slot(slot, source, target, batch=2, progress=seen.append) == 5
+        assert seen == [2, 4, 5]
+        assert rc.cluster_getkeysinslot(slot, 100, node=source) == []
+        assert rc.get("{reshard}3") == "3"
+        assert rc.migrate_slot(slot, target, source) == 5
+        with pytest.raises(TypeError):
+            rc.cluster_setslot(slot, "moving", node_id=owner_id)
+        with pytest.raises(TypeError):
+            rc.migrate_slot(slot, source, source)
+
+    def test_drain_node(self, rc):
+        from pyrsedis import ClusterError, ResponseError
+
+        master = next(iter(rc.execute_on_all_nodes("PING").successes))
+        rc.drain_node(master)
+        assert rc.draining_nodes == [master]
+        rc.set("drained", "1")  # writes still reach a drained master
+        assert rc.get("drained") == "1"
+        assert rc.undrain_node(master) is True
+        assert rc.undrain_node(master) is False
+        assert rc.draining_nodes == []
+        with pytest.raises(ClusterError):
+            rc.drain_node("127.0.0.1:1")
+        with pytest.raises(TypeError):
+            Redis().drain_node(master)
+        with pytest.raises(TypeError):
+            rc.cluster_failover(master, force=True, takeover=True)
+        with pytest.raises(ResponseError):
+            rc.cluster_failover(master)  # only a replica can fail over
+
+    def test_dedicated_connection_unsupported(self, rc):
+        from pyrsedis import ClusterError
+
+        with pytest.raises(ClusterError):
+            rc.dedicated_connection()
+
+    def test_unreachable_seeds_raise(self):
+        from pyrsedis import ClusterError, Redis
+
+        with pytest.raises(ClusterError):
+            Redis(cluster=[("127.0.0.1", 1)], connect_timeout_ms=200)
+
+
+# ── Sentinel ────────────────────────────────────────────────────────
+
+
+class TestSentinel:
+    """Runs against a Sentinel deployment named by ``REDIS_SENTINEL_URL``
+    (e.g. ``redis+sentinel://mymaster@127.0.0.1:26379``)."""
+
+    @pytest.fixture
+    def rs(self):
+        from pyrsedis import Redis
+
+        url = os.environ.get("REDIS_SENTINEL_URL")
+        if not url:
+            pytest.skip("REDIS_SENTINEL_URL not set")
+        return Redis.from_url(url)
+
+    def test_commands_reach_master(self, rs):
+        assert rs.set("sentinel_key", "v") is True
+        assert rs.get("sentinel_key") == "v"
+        role = rs.execute_command("ROLE")
+        assert role[0] == "master"
+
+    def test_pipeline(self, rs):
+        pipe = rs.pipeline()
+        pipe.set("sentinel_pipe", "1")
+        pipe.get("sentinel_pipe")
+        assert pipe.execute() == [True, "1"]
+
+    def test_repr_names_master(self, rs):
+        assert "@" in repr(rs)
+
+    def test_replica_reads(self):
+        from pyrsedis import Redis
+
+        url = os.environ.get("REDIS_SENTINEL_URL")
+        if not url:
+            pytest.skip("REDIS_SENTINEL_URL not set")
+        rs = Redis.from_url(url, read_preference="prefer_replica")
+        assert rs.set("sentinel_replica_key", "v") is True
+        assert rs.get("sentinel_replica_key") in ("v", None)
+        with pytest.raises(TypeError):
+            Redis.from_url(url, read_preference="lowest_latency")
+
+    def test_sentinel_commands(self, rs):
+        masters = rs.sentinel_masters()
+        name = masters[0]["name"]
+        assert rs.sentinel_master(name)["name"] == name
+        assert isinstance(rs.sentinel_replicas(name), list)
+        host, port = rs.sentinel_get_master_addr(name)
+        assert isinstance(host, str) and isinstance(port, int)
+        assert rs.sentinel_get_master_addr("no-such-master") is None
+
+    def test_sentinel_commands_need_sentinel_mode(self, r):
+        with pytest.raises(TypeError):
+            r.sentinel_masters()
+
+    def test_sentinels_require_master_name(self):
+        from pyrsedis import Redis, SentinelError
+
+        with pytest.raises(SentinelError):
+            Redis(sentinels=[("127.0.0.1", 26379)])
+
+    def test_cluster_and_sentinels_are_exclusive(self):
+        from pyrsedis import Redis
+
+        with pytest.raises(TypeError):
+            Redis(cluster=[("127.0.0.1", 7000)], sentinels=[("127.0.0.1", 26379)], master_name="m")
+
+
+# ── TLS ─────────────────────────────────────────────────────────────
+
+
+class TestTls:
+    """Runs against a TLS-enabled server named by ``REDIS_TLS_URL``
+    (e.g. ``rediss://127.0.0.1:6380``). ``REDIS_TLS_CA`` optionally points
+    at the CA bundle that signed its certificate."""
+
+    @pytest.fixture
+    def rt(self):
+        from pyrsedis import Redis
+
+        url = os.environ.get("REDIS_TLS_URL")
+        if not url:
+            pytest.skip("REDIS_TLS_URL not set")
+        ca = os.environ.get("REDIS_TLS_CA")
+        return Redis.from_url(url, ssl_ca_certs=ca, ssl_insecure_skip_verify=ca is None)
+
+    def test_roundtrip(self, rt):
+        assert rt.ping() is True
+        assert rt.set("tls_key", "v") is True
+        assert rt.get("tls_key") == "v"
+
+    def test_pipeline(self, rt):
+        pipe = rt.pipeline()
+        pipe.set("tls_pipe", "1")
+        pipe.get("tls_pipe")
+        assert pipe.execute() == [True, "1"]
+
+
+# ── Unix domain socket ──────────────────────────────────────────────
+
+
+class TestUnixSocket:
+    """Runs against a server listening on the socket named by
+    ``REDIS_UNIX_SOCKET`` (e.g. ``/var/run/redis/redis.sock``)."""
+
+    @pytest.fixture
+    def socket_path(self):
+        path = os.environ.get("REDIS_UNIX_SOCKET")
+        if not path:
+            pytest.skip("REDIS_UNIX_SOCKET not set")
+        return path
+
+    def test_constructor_kwarg(self, socket_path):
+        from pyrsedis import Redis
+
+        ru = Redis(unix_socket_path=socket_path)
+        assert ru.ping() is True
+        assert ru.set("unix_key", "v") is True
+        assert ru.get("unix_key") == "v"
+        assert socket_path in repr(ru)
+
+    def test_url(self, socket_path):
+        from pyrsedis import Redis
+
+        ru = Redis.from_url(f"unix://{socket_path}?db=1")
+        assert ru.set("unix_db_key", "1") is True
+        assert "db=1" in ru.execute_command("CLIENT", "INFO")
+
+    def test_rejects_ssl(self, socket_path):
+        from pyrsedis import Redis
+
+        with pytest.raises(TypeError):
+            Redis(unix_socket_path=socket_path, ssl=True)
+
+
+# ── Asyncio client ──────────────────────────────────────────────────
+
+
+class TestAsyncRedis:
+    @pytest.fixture
+    def ar(self, r, redis_url):
+        from pyrsedis import AsyncRedis
+
+        return AsyncRedis.from_url(redis_url)
+
+    def test_set_get(self, ar):
+        async def main():
+            assert await ar.set("async_k", "v") is True
+            return await ar.get("async_k")
+
+        assert asyncio.run(main()) == "v"
+
+    def test_blocking_pop(self, ar, r):
+        async def main():
+            waiter = asyncio.ensure_future(ar.blpop("async_q", timeout=5))
+            await asyncio.sleep(0.1)
+            await ar.rpush("async_q", "v")
+            return await waiter
+
+        assert asyncio.run(main()) == ["async_q", "v"]
+
+    def test_cancelled_blocking_pop_leaves_the_element(self, ar, r):
+        async def main():
+            with pytest.raises(asyncio.TimeoutError):
+                await asyncio.wait_for(ar.blpop("async_cancel_q", timeout=0), 0.2)
+            await ar.rpush("async_cancel_q", "v")
+            await asyncio.sleep(0.1)
+            return await ar.lrange("async_cancel_q", 0, -1)
+
+        assert asyncio.run(main()) == ["v"]
+
+    def test_command_timeout(self, ar):
+        from pyrsedis import RedisTimeoutError
+
+        async def main():
+            with pytest.raises(RedisTimeoutError):
+                await ar.execute_command("DEBUG", "SLEEP", "0.5", timeout_ms=100)
+            return await ar.echo("after", timeout=2.0)
+
+        assert asyncio.run(main()) == "after"
+
+    def test_binary_execute_command(self, ar):
+        async def main():
+            await ar.execute_command("SET", b"async_bin", b"\x00\xff", "EX", 60)
+            return await ar.execute_command("STRLEN", b"async_bin")
+
+        assert asyncio.run(main()) == 2
+
+    def test_variadic_commands(self, ar):
+        async def main():
+            await ar.mset({"async_a": "1", "async_b": "2"})
+            assert await ar.exists("async_a", "async_b", "async_missing") == 2
+            assert await ar.mget("async_a", "async_b") == ["1", "2"]
+            return await ar.delete("async_a", "async_b")
+
+        assert asyncio.run(main()) == 2
+
+    def test_concurrent_gather(self, ar):
+        async def main():
+            await asyncio.gather(*(ar.incr("async_counter") for _ in range(50)))
+            return await ar.get("async_counter")
+
+        assert asyncio.run(main()) == "50"
+
+    def test_pipeline(self, ar):
+        async def main():
+            pipe = ar.pipeline()
+            pipe.set("async_p", "1").incr("async_p").get("async_p")
+            return await pipe.execute()
+
+        assert asyncio.run(main()) == [True, 2, "2"]
+
+    def test_transaction_pipeline(self, ar):
+        async def main():
+            pipe = ar.pipeline(transaction=True)
+            pipe.incr("async_tx").incr("async_tx")
+            return await pipe.execute()
+
+        assert asyncio.run(main()) == [1, 2]
+
+    def test_exists_many(self, ar):
+        async def main():
+            await ar.set("async_x", "1")
+            return await ar.exists_many(["async_x", "async_y"])
+
+        assert asyncio.run(main()) == {"async_x": True, "async_y": False}
+
+    def test_load_hash_rows_and_zset(self, ar):
+        async def main():
+            rows = [{"id": i, "v": i} for i in range(5)]
+            loaded = await ar.load_hash_rows("async_lh:", rows, key_field="id", chunk_size=2)
+            added = await ar.load_zset("async_lz", {"a": 1, "b": 2})
+            return loaded, added, await ar.hget("async_lh:4", "v")
+
+        assert asyncio.run(main()) == (5, 2, "4")
+
+    def test_expire_and_persist_many(self, ar):
+        async def main():
+            await ar.set("async_xm", "1")
+            expired = await ar.expire_many({"async_xm": 100, "async_xm_missing": 100})
+            persisted = await ar.persist_many(["async_xm"])
+            return expired, persisted
+
+        expired, persisted = asyncio.run(main())
+        assert expired == {"async_xm": True, "async_xm_missing": False}
+        assert persisted == {"async_xm": True}
+
+    def test_purge_expiring(self, ar):
+        async def main():
+            await ar.set("async_pe", "1", ex=5)
+            return await ar.purge_expiring("async_pe*", 60)
+
+        assert asyncio.run(main()) == 1
+
+    def test_refresh_ttl_if_below(self, ar):
+        async def main():
+            await ar.set("async_rt", "1", ex=5)
+            refreshed = await ar.refresh_ttl_if_below(["async_rt"], 60, 300)
+            return refreshed, await ar.ttl("async_rt")
+
+        refreshed, ttl = asyncio.run(main())
+        assert refreshed == 1
+        assert ttl > 60
+
+    def test_errors_raise_on_await(self, ar):
+        from pyrsedis import WrongTypeError
+
+        async def main():
+            await ar.lpush("async_list", "a")
+            await ar.get("async_list")
+
+        with pytest.raises(WrongTypeError):
+            asyncio.run(main())
+
+    def test_context_manager(self, redis_url):
+        from pyrsedis import AsyncRedis
+
+        async def main():
+            async with AsyncRedis.from_url(redis_url) as client:
+                return await client.ping()
+
+        assert asyncio.run(main()) is True
+
+
+# ── Default client ──────────────────────────────────────────────────
+
+
+class TestDefaultClient:
+    def test_configure_and_get_default(self, r, redis_url):
+        import pyrsedis
+
+        client = pyrsedis.configure_default(url=redis_url)
+        assert pyrsedis.get_default() is client
+        client.set("default_key", "v")
+        assert pyrsedis.get_default().get("default_key") == "v"
+
+    def test_configure_default_invalid_url(self):
+        import pyrsedis
+
+        with pytest.raises(pyrsedis.PyrsedisError):
+            pyrsedis.configure_default(url="ftp://bad")
+
+
+# ── Process lifecycle ───────────────────────────────────────────────
+
+
+class TestLifecycle:
+    def test_disconnect_all(self, r):
+        r.ping()
+        assert r.pool_idle_count >= 1
+        r.disconnect_all()
+        assert r.pool_idle_count == 0
+        # Reconnects lazily
+        assert r.ping() is True
+
+    def test_before_fork_closes_idle(self, r):
+        import pyrsedis
+
+        r.ping()
+        pyrsedis.before_fork()
+        assert r.pool_idle_count == 0
+        assert r.ping() is True
+
+    def test_all_clients(self, r):
+        import gc
+
+        import pyrsedis
+        from pyrsedis import Redis
+
+        r.ping()
+        entry = next(c for c in pyrsedis.all_clients() if c["addr"] == repr(r)[12:-2])
+        assert entry["topology"] == "standalone"
+        assert entry["pool"]["connections_created"] >= 1
+
+        other = Redis(port=1, db=5)
+        assert any(c["addr"] == "127.0.0.1:1" and c["db"] == 5 for c in pyrsedis.all_clients())
+        del other
+        gc.collect()
+        assert not any(c["addr"] == "127.0.0.1:1" for c in pyrsedis.all_clients())
+
+    @pytest.mark.skipif(not hasattr(os, "fork"), reason="requires os.fork")
+    def test_fork_hooks(self, r):
+        import pyrsedis
+
+        r.set("fork_key", "parent")
+        pyrsedis.before_fork()
+        pid = os.fork()
+        if pid == 0:
+            code = 1
+            try:
+                pyrsedis.after_fork_in_child()
+                r.set("fork_key", "child")
+                code = 0
+            finally:
+                os._exit(code)
+        _, status = os.waitpid(pid, 0)
+        assert os.WEXITSTATUS(status) == 0
+        assert r.get("fork_key") == "child"
+
+    def test_topology_cache(self, r, redis_url):
+        import pyrsedis
+        from pyrsedis import Redis
+
+        pyrsedis.configure_topology_cache(capacity=16, ttl_ms=60_000)
+        try:
+            clients = [Redis.from_url(redis_url) for _ in range(3)]
+            assert all(c.ping() is True for c in clients)
+        finally:
+            pyrsedis.configure_topology_cache(enabled=False)
+        assert r.ping() is True
+
+    def test_topology_cache_rejects_zero_capacity(self):
+        import pyrsedis
+
+        with pytest.raises(TypeError):
+            pyrsedis.configure_topology_cache(capacity=0)
+
+
+# ── Lifecycle events ────────────────────────────────────────────────
+
+
+class TestEvents:
+    def test_recent_events(self, r):
+        r.ping()
+        events = r.recent_events()
+        assert events[0]["type"] == "connected"
+        assert events[0]["detail"] is None
+        assert events[0]["timestamp"] > 0
+        assert r.recent_events(limit=0) == []
+
+    def test_event_callback(self, r, redis_url):
+        from pyrsedis import Redis
+
+        seen = []
+        client = Redis.from_url(redis_url)
+        client.set_event_callback(seen.append)
+        client.disconnect_all()
+        client.ping()
+        assert [e["type"] for e in seen] == ["connected"]
+        client.set_event_callback(None)
+        client.disconnect_all()
+        client.ping()
+        assert len(seen) == 1
+
+    def test_event_callback_must_be_callable(self, r):
+        with pytest.raises(TypeError):
+            r.set_event_callback(42)
+
+    def test_dump_frames(self, r, redis_url):
+        from pyrsedis import Redis
+
+        assert r.dump_frames() == ""
+        client = Redis.from_url(redis_url, capture_frames=4)
+        client.set("frame", "x" * 5000)
+        client.ping()
+        dump = client.dump_frames()
+        assert "|*1..$4..PING..|" in dump
+        assert "(first 4096 shown)" in dump
+        assert client.dump_frames(limit=1).startswith("<<< ")
+        assert "|+PONG..|" in client.dump_frames(limit=1)
+
+
+# ── Graph export ────────────────────────────────────────────────────
+
+
+class TestGraphExport:
+    @pytest.fixture
+    def g(self, r):
+        try:
+            r.graph_query_result("pyrsedis_test", "CREATE (:P {name: 'a', age: 1}), (:P {name: 'b', age: 2.5})")
+        except Exception:
+            pytest.skip("FalkorDB module not available")
+        yield r
+        r.graph_delete("pyrsedis_test")
+
+    def test_columns_and_len(self, g):
+        res = g.graph_query_result("pyrsedis_test", "MATCH (p:P) RETURN p.name AS name, p.age AS age ORDER BY name")
+        assert res.columns == ["name", "age"]
+        assert len(res) == 2
+
+    def test_to_arrow_types(self, g):
+        pa = pytest.importorskip("pyarrow")
+        res = g.graph_query_result("pyrsedis_test", "MATCH (p:P) RETURN p.name AS name, p.age AS age ORDER BY name", read_only=True)
+        batch = res.to_arrow()
+        assert batch.schema.field("name").type == pa.string()
+        assert batch.schema.field("age").type == pa.float64()
+        assert batch.column("name").to_pylist() == ["a", "b"]
+        assert batch.column("age").to_pylist() == [1.0, 2.5]
+
+    def test_to_pandas(self, g):
+        pytest.importorskip("pandas")
+        df = g.graph_query_result("pyrsedis_test", "MATCH (p:P) RETURN count(p) AS n").to_pandas()
+        assert list(df.columns) == ["n"]
+        assert df["n"][0] == 2
+
+    def test_parse_graph_compact(self, g):
+        from pyrsedis import parse_graph_compact
+
+        query = "MATCH (p:P) RETURN p.name AS name, p.age AS age ORDER BY name"
+        pipe = g.pipeline()
+        pipe.graph_query("pyrsedis_test", query)
+        res = parse_graph_compact(pipe.execute()[0])
+        assert res.columns == ["name", "age"]
+        assert len(res) == 2
+        assert len(parse_graph_compact(g.graph_query("pyrsedis_test", query))) == 2
+
+    def test_rows_by_column_name(self, g):
+        res = g.graph_query_result("pyrsedis_test", "MATCH (p:P) RETURN p.age AS age, p.name AS name ORDER BY name")
+        first = res[0]
+        assert first.columns == ["age", "name"]
+        assert first["name"] == "a"
+        assert first[0] == 1
+        assert first.get("missing", "x") == "x"
+        assert first.to_dict() == {"age": 1, "name": "a"}
+        assert res[-1]["age"] == 2.5
+        assert res["name"] == ["a", "b"]
+        assert [row["name"] for row in res] == ["a", "b"]
+        with pytest.raises(KeyError):
+            first["missing"]
+        with pytest.raises(IndexError):
+            res[2]
+
 
 # ── Scripting ───────────────────────────────────────────────────────
 
@@ -460,6 +2467,41 @@ class TestScripting:
         result = r.evalsha(sha_str, 0)
         assert result == "ok"
 
+    def test_functions(self, r):
+        code = (
+            "#!lua name=pyrsedis_test\n"
+            "redis.register_function('echo_key', function(keys, args) return keys[1] .. args[1] end)\n"
+            "redis.register_function{function_name='len_ro', callback=function(keys)"
+            " return redis.call('STRLEN', keys[1]) end, flags={'no-writes'}}\n"
+        )
+        assert r.function_load(code, replace=True) == "pyrsedis_test"
+        assert r.fcall("echo_key", 1, "k", "!") == "k!"
+        r.set("fk", "abc")
+        assert r.fcall_ro("len_ro", 1, "fk") == 3
+        assert len(r.function_list(library="pyrsedis_*")) == 1
+
+        payload = r.function_dump()
+        assert isinstance(payload, bytes)
+        assert r.function_restore(payload, policy="replace") is True
+        with pytest.raises(TypeError):
+            r.function_restore(payload, policy="merge")
+        r.execute_command("FUNCTION", "DELETE", "pyrsedis_test")
+
+    def test_register_script(self, r):
+        incr_by = r.register_script("return redis.call('INCRBY', KEYS[1], ARGV[1])")
+        assert incr_by.sha == r.script_load(incr_by.script)
+        assert incr_by(keys=["sk"], args=[2]) == 2
+        r.execute_command("SCRIPT", "FLUSH")
+        assert incr_by(keys=["sk"], args=[3]) == 5
+
+        r.execute_command("SCRIPT", "FLUSH")
+        pipe = r.pipeline()
+        assert incr_by(keys=["sk"], args=[1], client=pipe) is pipe
+        pipe.get("sk")
+        assert pipe.execute() == [6, "6"]
+        with pytest.raises(TypeError):
+            incr_by(client="nope")
+
     def test_scan(self, r):
         for i in range(5):
             r.set(f"scan_{i}", "v")
@@ -476,12 +2518,27 @@ class TestExceptions:
         import pyrsedis
 
         assert issubclass(pyrsedis.RedisConnectionError, pyrsedis.PyrsedisError)
+        assert issubclass(pyrsedis.ConnectionSetupError, pyrsedis.RedisConnectionError)
         assert issubclass(pyrsedis.RedisTimeoutError, pyrsedis.PyrsedisError)
         assert issubclass(pyrsedis.ProtocolError, pyrsedis.PyrsedisError)
         assert issubclass(pyrsedis.RedisError, pyrsedis.PyrsedisError)
         assert issubclass(pyrsedis.GraphError, pyrsedis.PyrsedisError)
         assert issubclass(pyrsedis.ClusterError, pyrsedis.PyrsedisError)
+        assert issubclass(pyrsedis.CrossSlotError, pyrsedis.ClusterError)
         assert issubclass(pyrsedis.SentinelError, pyrsedis.PyrsedisError)
+        assert issubclass(pyrsedis.TransactionError, pyrsedis.PyrsedisError)
+        assert issubclass(pyrsedis.WatchError, pyrsedis.TransactionError)
+        assert issubclass(pyrsedis.PipelineTooLargeError, pyrsedis.PyrsedisError)
+        assert issubclass(pyrsedis.PoolTimeoutError, pyrsedis.RedisTimeoutError)
+        assert issubclass(pyrsedis.WouldBlockError, pyrsedis.PoolTimeoutError)
+
+    def test_setup_error_names_the_step(self, r):
+        """A refused SELECT raises ConnectionSetupError naming the step."""
+        import pyrsedis
+
+        client = pyrsedis.Redis(db=100000)
+        with pytest.raises(pyrsedis.ConnectionSetupError, match="SELECT"):
+            client.ping()
 
     def test_redis_error_subclasses(self):
         """RedisError children form a proper tree."""
@@ -544,6 +2601,7 @@ class TestExceptions:
             r.lpush("mystr", "value")
         except pyrsedis.WrongTypeError as e:
             assert "WRONGTYPE" in str(e)
+            assert str(e).endswith('(command: LPUSH "mystr" "value")')
 
     @pytest.fixture
     def r(self):
@@ -551,7 +2609,7 @@ class TestExceptions:
 
         try:
             client = Redis()
-            client.flushdb()
+            client.flushdb(confirm=True)
             return client
         except Exception:
             pytest.skip("Redis server not available")
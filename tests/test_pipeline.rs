@@ -83,8 +83,8 @@ async fn pipeline_large_batch() {
     assert_eq!(results.len(), count * 2);
 
     // First 100 should be OK
-    for i in 0..count {
-        assert_eq!(results[i], RespValue::SimpleString("OK".into()));
+    for result in &results[..count] {
+        assert_eq!(*result, RespValue::SimpleString("OK".into()));
     }
 
     // Next 100 should be the values
@@ -277,7 +277,7 @@ async fn script_load_and_evalsha() {
     let sha = exec_bulk(&r, &["SCRIPT", "LOAD", "return 'ok'"]).await;
     let sha_str = std::str::from_utf8(&sha).unwrap();
 
-    let result = exec(&r, &["EVALSHA", &sha_str, "0"]).await;
+    let result = exec(&r, &["EVALSHA", sha_str, "0"]).await;
     assert_eq!(result, RespValue::BulkString(Bytes::from_static(b"ok")));
 }
 